@@ -0,0 +1,190 @@
+//! `.arclang/outputs` — the files `build`, `explorer` and `diagram` have
+//! generated, one absolute path per line, so that `arclang clean` deletes
+//! exactly what ArcLang wrote and nothing else.
+//!
+//! A generated file is recorded in the nearest directory above it that holds
+//! `.arclang/`; outside an ArcLang project nothing is recorded.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::CliError;
+
+/// Extensions `clean` deletes; anything else recorded is left alone.
+pub const GENERATED_EXTENSIONS: &[&str] = &["json", "mermaid", "html", "svg", "dot"];
+
+fn outputs_file(project: &Path) -> PathBuf {
+    project.join(".arclang").join("outputs")
+}
+
+/// Record a file just written by a command.
+pub fn record(file: &Path) -> io::Result<()> {
+    let file = file.canonicalize()?;
+    let Some(project) = file.ancestors().skip(1).find(|dir| dir.join(".arclang").is_dir()) else {
+        return Ok(());
+    };
+    if recorded(project)?.contains(&file) {
+        return Ok(());
+    }
+    let mut log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(outputs_file(project))?;
+    writeln!(log, "{}", file.display())
+}
+
+/// Files recorded in `project`, oldest first.
+pub fn recorded(project: &Path) -> io::Result<Vec<PathBuf>> {
+    match fs::read_to_string(outputs_file(project)) {
+        Ok(text) => Ok(text.lines().filter(|l| !l.is_empty()).map(PathBuf::from).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CleanSummary {
+    pub files: usize,
+    pub bytes: u64,
+    /// Recorded files left in place, with the reason.
+    pub skipped: Vec<(PathBuf, &'static str)>,
+}
+
+/// Delete the recorded outputs of `project` and, with `cache`, its
+/// incremental-compilation cache. Source `.arc` files are never deleted.
+pub fn clean(project: &Path, cache: bool) -> Result<CleanSummary, CliError> {
+    if !project.join(".arclang").is_dir() {
+        return Err(CliError::Config(format!(
+            "{} is not an ArcLang project (no .arclang directory); refusing to delete anything",
+            project.display()
+        )));
+    }
+    let root = project.canonicalize()?;
+    let mut summary = CleanSummary::default();
+
+    for file in recorded(project)? {
+        if !file.is_file() {
+            continue;
+        }
+        let extension = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let reason = if extension == "arc" {
+            Some("ArcLang source")
+        } else if !file.starts_with(&root) {
+            Some("outside the project")
+        } else if !GENERATED_EXTENSIONS.contains(&extension) {
+            Some("not a generated file type")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => summary.skipped.push((file, reason)),
+            None => {
+                summary.bytes += file.metadata()?.len();
+                fs::remove_file(&file)?;
+                summary.files += 1;
+            }
+        }
+    }
+    if let Err(e) = fs::remove_file(outputs_file(project)) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(e.into());
+        }
+    }
+
+    if cache {
+        let cache_dir = project.join(".arclang").join("cache");
+        if cache_dir.is_dir() {
+            let (files, bytes) = tree_size(&cache_dir)?;
+            fs::remove_dir_all(&cache_dir)?;
+            summary.files += files;
+            summary.bytes += bytes;
+        }
+        let stray = project.join("compilation_cache.bin");
+        if stray.is_file() {
+            summary.bytes += stray.metadata()?.len();
+            fs::remove_file(&stray)?;
+            summary.files += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Number of files and their total size under `dir`.
+fn tree_size(dir: &Path) -> io::Result<(usize, u64)> {
+    let mut files = 0;
+    let mut bytes = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            let (f, b) = tree_size(&entry.path())?;
+            files += f;
+            bytes += b;
+        } else {
+            files += 1;
+            bytes += metadata.len();
+        }
+    }
+    Ok((files, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".arclang")).unwrap();
+        fs::write(dir.path().join("model.arc"), "model M {}\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn removes_recorded_outputs_but_never_sources() {
+        let dir = project();
+        let json = dir.path().join("model.json");
+        let html = dir.path().join("model_explorer.html");
+        fs::write(&json, "{}").unwrap();
+        fs::write(&html, "<html></html>").unwrap();
+        for file in [&json, &html, &dir.path().join("model.arc")] {
+            record(file).unwrap();
+        }
+        record(&json).unwrap();
+        assert_eq!(recorded(dir.path()).unwrap().len(), 3);
+
+        let summary = clean(dir.path(), false).unwrap();
+
+        assert_eq!(summary.files, 2);
+        assert_eq!(summary.bytes, 2 + 13);
+        assert!(!json.exists() && !html.exists());
+        assert!(dir.path().join("model.arc").exists());
+        assert_eq!(summary.skipped.len(), 1);
+        assert!(recorded(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn cache_is_only_removed_on_request() {
+        let dir = project();
+        let cache = dir.path().join(".arclang").join("cache");
+        fs::create_dir(&cache).unwrap();
+        fs::write(cache.join("compilation_cache.bin"), [0u8; 16]).unwrap();
+
+        assert_eq!(clean(dir.path(), false).unwrap().files, 0);
+        assert!(cache.exists());
+
+        let summary = clean(dir.path(), true).unwrap();
+        assert_eq!((summary.files, summary.bytes), (1, 16));
+        assert!(!cache.exists());
+    }
+
+    #[test]
+    fn refuses_a_directory_that_is_not_a_project() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("notes.json"), "{}").unwrap();
+
+        assert!(matches!(clean(dir.path(), true), Err(CliError::Config(_))));
+        assert!(dir.path().join("notes.json").exists());
+    }
+}
@@ -1,29 +1,66 @@
-//! ArcLang Language Server (LSP over stdio).
+//! ArcLang Language Server (LSP over stdio or TCP).
 //!
-//! Diagnostics come straight from the strict compiler: parser/semantic errors
-//! (which carry `at line L, column C` positions) are published as LSP errors,
-//! compilation warnings as LSP warnings.
+//! Diagnostics come straight from the strict compiler: located parser and
+//! semantic errors are published as LSP errors spanning the offending token,
+//! compilation warnings (which carry `at line L, column C` positions) as LSP
+//! warnings.
+//!
+//! Go-to-definition and hover work on element ids at trace endpoints and
+//! declarations. They need a model that compiles; while it does not, the
+//! requests answer nothing and the diagnostics say why. Completion offers
+//! keywords and the ids of the document's last model that compiled, since
+//! the line being typed rarely parses.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
 
-use regex::Regex;
 use tower_lsp::jsonrpc::Result as LspResult;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
+use crate::compiler::completion::{self, CompletionKind};
+use crate::compiler::diagnostic::{records, DiagnosticRecord, Severity, SourcePosition, SourceSpan};
+use crate::compiler::semantic::SemanticModel;
 use crate::compiler::{Compiler, CompilerConfig};
 
 pub struct ArcLangLanguageServer {
     client: Client,
+    documents: Mutex<HashMap<Url, Document>>,
+}
+
+struct Document {
+    text: String,
+    /// Model of the latest version that compiled.
+    model: Option<SemanticModel>,
 }
 
 impl ArcLangLanguageServer {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            documents: Mutex::new(HashMap::new()),
+        }
     }
 
-    async fn check(&self, uri: Url, text: &str) {
-        let diagnostics = compute_diagnostics(text);
+    async fn check(&self, uri: Url, text: String) {
+        let outcome = Compiler::new(CompilerConfig::default()).compile_string(&text);
+        let diagnostics = records(&outcome).into_iter().map(to_lsp).collect();
+        {
+            let mut documents = self.documents.lock().unwrap();
+            let document = documents
+                .entry(uri.clone())
+                .or_insert_with(|| Document { text: String::new(), model: None });
+            document.text = text;
+            if let Ok(result) = outcome {
+                document.model = Some(result.semantic_model);
+            }
+        }
         self.client.publish_diagnostics(uri, diagnostics, None).await;
     }
+
+    fn document(&self, uri: &Url) -> Option<String> {
+        self.documents.lock().unwrap().get(uri).map(|d| d.text.clone())
+    }
 }
 
 #[tower_lsp::async_trait]
@@ -38,6 +75,12 @@ impl LanguageServer for ArcLangLanguageServer {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
                     TextDocumentSyncKind::FULL,
                 )),
+                definition_provider: Some(OneOf::Left(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec!["\"".to_string()]),
+                    ..Default::default()
+                }),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
                 ..Default::default()
             },
         })
@@ -54,62 +97,207 @@ impl LanguageServer for ArcLangLanguageServer {
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.check(params.text_document.uri, &params.text_document.text)
+        self.check(params.text_document.uri, params.text_document.text)
             .await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         // FULL sync: the last change carries the whole document.
         if let Some(change) = params.content_changes.into_iter().last() {
-            self.check(params.text_document.uri, &change.text).await;
+            self.check(params.text_document.uri, change.text).await;
         }
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
         if let Some(text) = params.text {
-            self.check(params.text_document.uri, &text).await;
+            self.check(params.text_document.uri, text).await;
         }
     }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.documents.lock().unwrap().remove(&params.text_document.uri);
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> LspResult<Option<GotoDefinitionResponse>> {
+        let TextDocumentPositionParams { text_document, position } =
+            params.text_document_position_params;
+        let Some(text) = self.document(&text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(definition(&text, position)
+            .map(|range| GotoDefinitionResponse::Scalar(Location::new(text_document.uri, range))))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> LspResult<Option<CompletionResponse>> {
+        let TextDocumentPositionParams { text_document, position } = params.text_document_position;
+        let documents = self.documents.lock().unwrap();
+        let Some(document) = documents.get(&text_document.uri) else {
+            return Ok(None);
+        };
+        let items = completions(&document.text, position, document.model.as_ref());
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
+        let TextDocumentPositionParams { text_document, position } =
+            params.text_document_position_params;
+        let Some(text) = self.document(&text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(hover(&text, position).map(|markdown| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: markdown,
+            }),
+            range: None,
+        }))
+    }
 }
 
-/// Compile the source and turn errors/warnings into LSP diagnostics.
+/// Compile the buffer in memory and turn its errors, warnings and
+/// traceability findings into LSP diagnostics.
 pub fn compute_diagnostics(source: &str) -> Vec<Diagnostic> {
-    let mut compiler = Compiler::new(CompilerConfig::default());
-    match compiler.compile_string(source) {
-        Ok(result) => result
-            .warnings
-            .iter()
-            .map(|warning| diagnostic_from_message(warning, DiagnosticSeverity::WARNING))
-            .collect(),
-        Err(error) => vec![diagnostic_from_message(
-            &error.to_string(),
-            DiagnosticSeverity::ERROR,
-        )],
+    let outcome = Compiler::new(CompilerConfig::default()).compile_string(source);
+    records(&outcome).into_iter().map(to_lsp).collect()
+}
+
+/// Keywords and element ids for the word being typed at `position`.
+pub fn completions(source: &str, position: Position, model: Option<&SemanticModel>) -> Vec<CompletionItem> {
+    let Some(line) = source.lines().nth(position.line as usize) else {
+        return Vec::new();
+    };
+    let prefix: String = line.chars().take(position.character as usize).collect();
+    let found = completion::complete(&prefix, model);
+    let start = Position::new(position.line, prefix[..found.start].chars().count() as u32);
+
+    found
+        .items
+        .into_iter()
+        .map(|item| CompletionItem {
+            kind: Some(match item.kind {
+                CompletionKind::Keyword => CompletionItemKind::KEYWORD,
+                CompletionKind::Reference => CompletionItemKind::REFERENCE,
+            }),
+            detail: item.detail,
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                Range { start, end: position },
+                item.replacement,
+            ))),
+            label: item.label,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Where the element referenced or declared at `position` is declared.
+pub fn definition(source: &str, position: Position) -> Option<Range> {
+    let model = analyze(source)?;
+    let id = id_at(&model, position)?;
+    model.all_elements.get(id)?.span.as_ref().map(to_range)
+}
+
+/// Markdown describing the element at `position`: a requirement's text and
+/// priority, a component's type, or an element's kind.
+pub fn hover(source: &str, position: Position) -> Option<String> {
+    let model = analyze(source)?;
+    let id = id_at(&model, position)?;
+
+    if let Some(req) = model.requirements.iter().find(|r| r.id == id) {
+        let mut text = format!("**{}**", req.id);
+        if let Some(title) = &req.title {
+            text.push_str(&format!(" {title}"));
+        }
+        if !req.description.is_empty() {
+            text.push_str(&format!("\n\n{}", req.description));
+        }
+        text.push_str(&format!("\n\nPriority: {}", req.priority));
+        if let Some(level) = &req.safety_level {
+            text.push_str(&format!(" · Safety level: {level}"));
+        }
+        return Some(text);
     }
+    if let Some(comp) = model.components.iter().find(|c| c.id == id) {
+        return Some(format!(
+            "**{}** {}\n\nType: {} ({})",
+            comp.id, comp.name, comp.component_type, comp.level
+        ));
+    }
+    let element = model.all_elements.get(id)?;
+    Some(format!("**{}** {}\n\n{}", element.id, element.name, element.element_type))
+}
+
+fn analyze(source: &str) -> Option<SemanticModel> {
+    Compiler::new(CompilerConfig::default())
+        .compile_string(source)
+        .ok()
+        .map(|result| result.semantic_model)
 }
 
-/// Extract `at line L, column C` from a compiler message; defaults to 0:0.
-fn diagnostic_from_message(message: &str, severity: DiagnosticSeverity) -> Diagnostic {
-    // Compiled once per call; diagnostics volume is tiny, clarity wins.
-    let position_re = Regex::new(r"at line (\d+), column (\d+)").unwrap();
-    let (line, column) = position_re
-        .captures(message)
-        .and_then(|caps| {
-            let line: u32 = caps[1].parse().ok()?;
-            let column: u32 = caps[2].parse().ok()?;
-            // LSP positions are 0-based; compiler spans are 1-based.
-            Some((line.saturating_sub(1), column.saturating_sub(1)))
+/// The id of the trace endpoint or element declaration under `position`.
+fn id_at(model: &SemanticModel, position: Position) -> Option<&str> {
+    let covers = |span: &Option<SourceSpan>| {
+        span.as_ref().is_some_and(|span| {
+            let range = to_range(span);
+            range.start.line == position.line
+                && range.start.character <= position.character
+                && position.character < range.end.character
         })
-        .unwrap_or((0, 0));
+    };
+
+    model
+        .traces
+        .iter()
+        .find_map(|trace| {
+            if covers(&trace.from_span) {
+                Some(trace.from.as_str())
+            } else if covers(&trace.to_span) {
+                Some(trace.to.as_str())
+            } else {
+                None
+            }
+        })
+        .or_else(|| {
+            model
+                .all_elements
+                .values()
+                .find(|element| covers(&element.span))
+                .map(|element| element.id.as_str())
+        })
+}
+
+fn to_range(span: &SourceSpan) -> Range {
+    let line = span.line.saturating_sub(1) as u32;
+    let start = span.column.saturating_sub(1) as u32;
+    Range {
+        start: Position::new(line, start),
+        end: Position::new(line, start + span.len.max(1) as u32),
+    }
+}
+
+fn to_lsp(record: DiagnosticRecord) -> Diagnostic {
+    // LSP positions are 0-based; compiler ranges are 1-based. Unlocated
+    // findings go at the top of the file.
+    let position = |p: SourcePosition| Position {
+        line: p.line.saturating_sub(1) as u32,
+        character: p.column.saturating_sub(1) as u32,
+    };
+    let range = match record.range {
+        Some(range) => Range { start: position(range.start), end: position(range.end) },
+        None => Range { start: Position::new(0, 0), end: Position::new(0, 1) },
+    };
 
     Diagnostic {
-        range: Range {
-            start: Position { line, character: column },
-            end: Position { line, character: column + 1 },
-        },
-        severity: Some(severity),
+        range,
+        severity: Some(match record.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        }),
+        code: Some(NumberOrString::String(record.code.to_string())),
         source: Some("arclang".to_string()),
-        message: message.to_string(),
+        message: record.message,
         ..Default::default()
     }
 }
@@ -122,6 +310,21 @@ pub async fn run_stdio() {
     Server::new(stdin, stdout, socket).serve(service).await;
 }
 
+/// Listen on `127.0.0.1:port`, serving each client connection in its own
+/// session until the process is stopped.
+pub async fn run_tcp(port: u16) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    eprintln!("arclang-lsp listening on {}", listener.local_addr()?);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            let (read, write) = tokio::io::split(stream);
+            let (service, socket) = LspService::new(ArcLangLanguageServer::new);
+            Server::new(read, write, socket).serve(service).await;
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +339,7 @@ mod tests {
         // Compiler says line 2, column 3 -> LSP 0-based 1:2
         assert_eq!(diag.range.start.line, 1);
         assert_eq!(diag.range.start.character, 2);
+        assert_eq!(diag.range.end.character, 9, "underlines the whole token");
         assert!(diag.message.contains("garbage"));
     }
 
@@ -150,9 +354,75 @@ mod tests {
     }
 
     #[test]
-    fn clean_model_has_no_diagnostics() {
+    fn traceability_findings_are_published_with_their_code() {
         let source = "model Test {\n}\n\narchitecture logical {\n  component \"C\" { id: \"LC-001\" }\n}\n";
         let diagnostics = compute_diagnostics(source);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].code, Some(NumberOrString::String("E-TRACE-ORPHAN".to_string())));
+    }
+
+    const NAVIGATION: &str = "model Test {\n}\n\nrequirements system {\n  req \"REQ-001\" \"Braking\" { description: \"Stop the car\" priority: \"High\" }\n}\n\narchitecture logical {\n  component \"Brake Controller\" { id: \"COMP-001\" }\n}\n\ntrace \"REQ-001\" allocated_to \"COMP-001\" {}\n";
+
+    #[test]
+    fn definition_jumps_from_trace_endpoint_to_declaration() {
+        // Line 11: `trace "REQ-001" allocated_to "COMP-001" {}`
+        let requirement = definition(NAVIGATION, Position::new(11, 8)).unwrap();
+        assert_eq!(requirement.start, Position::new(4, 6));
+
+        let component = definition(NAVIGATION, Position::new(11, 33)).unwrap();
+        assert_eq!(component.start, Position::new(8, 12));
+
+        assert_eq!(definition(NAVIGATION, Position::new(11, 20)), None);
+    }
+
+    #[test]
+    fn hover_describes_requirement_and_component() {
+        let requirement = hover(NAVIGATION, Position::new(11, 8)).unwrap();
+        assert!(requirement.contains("**REQ-001** Braking"), "{requirement}");
+        assert!(requirement.contains("Stop the car"));
+        assert!(requirement.contains("Priority: High"));
+
+        let component = hover(NAVIGATION, Position::new(8, 15)).unwrap();
+        assert!(component.starts_with("**COMP-001** Brake Controller"), "{component}");
+        assert!(component.contains("Type: Logical"));
+    }
+
+    #[test]
+    fn unresolved_reference_is_a_diagnostic_not_a_definition() {
+        let source = NAVIGATION.replace("allocated_to \"COMP-001\"", "allocated_to \"COMP-404\"");
+        assert_eq!(definition(&source, Position::new(11, 33)), None);
+        assert!(compute_diagnostics(&source)
+            .iter()
+            .any(|d| d.severity == Some(DiagnosticSeverity::ERROR) && d.message.contains("COMP-404")));
+    }
+
+    #[test]
+    fn completion_items_are_kinded_keywords_or_references() {
+        let model = analyze(NAVIGATION).unwrap();
+        let editing = format!("{NAVIGATION}tra");
+        let keywords = completions(&editing, Position::new(12, 3), Some(&model));
+        assert_eq!(keywords.len(), 1);
+        assert_eq!(keywords[0].label, "trace");
+        assert_eq!(keywords[0].kind, Some(CompletionItemKind::KEYWORD));
+
+        // The buffer being edited does not compile; the last model supplies ids.
+        let editing = format!("{NAVIGATION}trace \"COMP-001\" satisfies \"R");
+        let references = completions(&editing, Position::new(12, 29), Some(&model));
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].label, "REQ-001");
+        assert_eq!(references[0].kind, Some(CompletionItemKind::REFERENCE));
+        let Some(CompletionTextEdit::Edit(edit)) = &references[0].text_edit else {
+            panic!("expected a text edit");
+        };
+        assert_eq!(edit.range.start, Position::new(12, 28));
+        assert_eq!(edit.new_text, "REQ-001");
+    }
+
+    #[test]
+    fn clean_model_has_no_diagnostics() {
+        let source = "model Test {\n}\n\nrequirements system {\n  req \"R-1\" \"R\" { description: \"r\" }\n}\n\narchitecture logical {\n  component \"C\" { id: \"LC-001\" }\n}\n\ntrace \"R-1\" allocated_to \"LC-001\" {}\n";
+        let diagnostics = compute_diagnostics(source);
         assert!(diagnostics.is_empty(), "unexpected: {diagnostics:?}");
     }
 }
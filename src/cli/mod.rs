@@ -1,8 +1,14 @@
+pub mod artifacts;
 pub mod repl;
 pub mod language_server;
+pub mod sync_config;
+pub mod templates;
+pub mod plugins;
 
 use clap::{Parser, Subcommand};
 use std::path::{Path, PathBuf};
+use crate::compiler::lint_levels::{level_warnings, LintLevelConfig, LintLevels};
+use crate::compiler::profile::{PhaseTimer, Profile};
 
 #[derive(Parser)]
 #[clap(name = "arclang")]
@@ -32,31 +38,93 @@ pub enum Commands {
         #[clap(long)]
         incremental: bool,
         
+        /// Run every check (optimization level 3): dead elements and
+        /// Arcadia cross-layer consistency on top of full validation
         #[clap(long)]
         release: bool,
         
         #[clap(long)]
         target: Option<String>,
+        
+        /// Warn about traces to undeclared elements instead of failing
+        #[clap(long, conflicts_with = "incremental")]
+        allow_dangling: bool,
+
+        /// Report this lint's findings as errors (repeatable; overrides
+        /// `[lint]` in .arclang/config.toml)
+        #[clap(long, value_name = "LINT")]
+        deny: Vec<String>,
+
+        /// Report this lint's findings as warnings
+        #[clap(long, value_name = "LINT")]
+        warn: Vec<String>,
+
+        /// Silence this lint
+        #[clap(long, value_name = "LINT")]
+        allow: Vec<String>,
+
+        /// Threads for parsing imported files (default: one per core;
+        /// 1 parses serially)
+        #[clap(short, long)]
+        jobs: Option<usize>,
+
+        /// Print the wall time of each compiler phase
+        #[clap(long)]
+        profile: bool,
+
+        /// Also write the profile as JSON to FILE (implies --profile)
+        #[clap(long, value_name = "FILE")]
+        profile_json: Option<PathBuf>,
     },
     
     Check {
         #[clap(value_parser)]
         input: PathBuf,
         
+        /// Also run the methodology, requirements quality and dead-element
+        /// lints
         #[clap(long)]
         lint: bool,
         
+        /// TOML file tuning the requirements quality lints
+        #[clap(long, value_parser, requires = "lint")]
+        lint_config: Option<PathBuf>,
+        
         #[clap(long)]
         safety: bool,
+        
+        /// Print diagnostics as JSON lines (for editors and CI annotations)
+        #[clap(long)]
+        json: bool,
+        
+        /// Warn about traces to undeclared elements instead of failing
+        #[clap(long)]
+        allow_dangling: bool,
+
+        /// Report this lint's findings as errors (repeatable; overrides
+        /// `[lint]` in .arclang/config.toml)
+        #[clap(long, value_name = "LINT")]
+        deny: Vec<String>,
+
+        /// Report this lint's findings as warnings
+        #[clap(long, value_name = "LINT")]
+        warn: Vec<String>,
+
+        /// Silence this lint
+        #[clap(long, value_name = "LINT")]
+        allow: Vec<String>,
     },
     
+    /// Reformat a model to canonical layout (prints to stdout by default)
     Format {
         #[clap(value_parser)]
         input: PathBuf,
         
-        #[clap(long)]
+        /// Exit non-zero and print a unified diff if the file is not formatted
+        #[clap(long, conflicts_with = "write")]
         check: bool,
         
+        /// Overwrite the file in place (only when the content changes)
         #[clap(long)]
         write: bool,
     },
@@ -65,6 +133,7 @@ pub enum Commands {
         #[clap(value_parser)]
         name: String,
         
+        /// minimal (default), automotive-iso26262 or avionics-do178c
         #[clap(long)]
         template: Option<String>,
     },
@@ -83,9 +152,13 @@ pub enum Commands {
         #[clap(value_parser)]
         input: PathBuf,
 
+        /// Only traces with one end of this element type (requirement,
+        /// component, function, test_case, ...); the matrix rows
         #[clap(long)]
         from: Option<String>,
 
+        /// Only traces with the other end of this element type; the matrix
+        /// columns
         #[clap(long)]
         to: Option<String>,
 
@@ -94,6 +167,10 @@ pub enum Commands {
 
         #[clap(long)]
         matrix: bool,
+
+        /// With --matrix, write an HTML heatmap of the matrix here
+        #[clap(short, long, value_parser)]
+        output: Option<PathBuf>,
     },
 
     /// Production-readiness gate: PASS/FAIL verdict against what an
@@ -107,15 +184,67 @@ pub enum Commands {
         standard: SafetyStandard,
     },
 
+    /// Three-way merge of two model versions against their common base,
+    /// element by element: changes to different elements never conflict
+    Merge {
+        /// The common ancestor of both versions
+        #[clap(value_parser)]
+        base: PathBuf,
+
+        /// Our version of the model
+        #[clap(value_parser)]
+        ours: PathBuf,
+
+        /// Their version of the model
+        #[clap(value_parser)]
+        theirs: PathBuf,
+
+        /// Resolve non-semantic conflicts (an id added on both sides)
+        /// by renaming their element instead of stopping
+        #[clap(long)]
+        auto_resolve: bool,
+
+        /// Output as JSON (for CI pipelines)
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Union of separately authored subsystem models into one, for analysis
+    /// across them (not a version merge: see `merge`)
+    MergeModels {
+        /// The models to combine, in order
+        #[clap(value_parser, required = true, num_args = 2..)]
+        inputs: Vec<PathBuf>,
+
+        #[clap(short, long, value_parser)]
+        output: PathBuf,
+
+        /// Prefix each input's ids with its file stem (`brake::REQ-001`)
+        /// instead of failing on ids declared by several inputs
+        #[clap(long)]
+        namespace: bool,
+
+        /// Output the report as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Review workflow for a changeset (the content of the model to push);
+    /// state is kept in .arclang/reviews/
+    Review {
+        #[clap(subcommand)]
+        review_command: ReviewCommands,
+    },
+
     /// Semantic diff between two model versions: compares by stable
     /// identity (UUID), so moving blocks is no change and renaming an
     /// element is a modification, not a remove+add
     Diff {
-        /// The old version of the model
+        /// The old version of the model (`.arc`, or a `.json` baseline)
         #[clap(value_parser)]
         old: PathBuf,
 
-        /// The new version of the model
+        /// The new version of the model (`.arc`, or a `.json` baseline)
         #[clap(value_parser)]
         new: PathBuf,
 
@@ -124,17 +253,58 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Snapshot the compiled model as a JSON baseline, to `diff` later
+    /// revisions against
+    Baseline {
+        #[clap(value_parser)]
+        input: PathBuf,
+
+        #[clap(short, long, value_parser)]
+        output: PathBuf,
+    },
+
     /// Change-impact analysis: everything transitively affected when an
     /// element (requirement, component, function) changes
     Impact {
         #[clap(value_parser)]
         input: PathBuf,
 
-        /// Element id or unambiguous name (e.g. "REQ-AEB-004")
-        #[clap(value_parser)]
+        /// Element id or unambiguous name (e.g. "COMP-001")
+        #[clap(short, long)]
         element: String,
+
+        /// Output as JSON (for tooling)
+        #[clap(long)]
+        json: bool,
     },
     
+    /// How the model evolved in git: every commit that changed the file,
+    /// compiled, with its requirement count and traceability coverage
+    History {
+        #[clap(value_parser)]
+        input: PathBuf,
+
+        /// Also show when this element was added, modified or removed
+        #[clap(short, long)]
+        element: Option<String>,
+
+        /// Output as JSON (for tooling)
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Where each requirement comes from: the commits that introduced and
+    /// last changed it, and what it derives from; requirements with nothing
+    /// upstream are flagged as unjustified
+    Blame {
+        #[clap(value_parser)]
+        input: PathBuf,
+
+        /// Output as JSON (for tooling)
+        #[clap(long)]
+        json: bool,
+    },
+
     Export {
         #[clap(value_parser)]
         input: PathBuf,
@@ -142,8 +312,14 @@ pub enum Commands {
         #[clap(short, long, value_parser)]
         output: PathBuf,
         
+        /// A built-in format (json, capella, mermaid, ...) or `custom:<name>`
+        /// for a registered export plugin
         #[clap(short, long)]
-        format: ExportFormat,
+        format: String,
+
+        /// Flow direction of the Mermaid flowchart
+        #[clap(long, value_enum, default_value = "tb")]
+        direction: DiagramDirection,
     },
     
     Import {
@@ -155,6 +331,11 @@ pub enum Commands {
         
         #[clap(short, long, value_parser)]
         output: PathBuf,
+
+        /// CSV import: the requirement field a header holds, e.g.
+        /// `--column "Req No=id"` (repeatable).
+        #[clap(long = "column", value_name = "HEADER=FIELD")]
+        columns: Vec<String>,
     },
     
     Safety {
@@ -172,6 +353,20 @@ pub enum Commands {
         
         #[clap(long)]
         report: bool,
+
+        /// Fail when the model does not comply with `--standard`: a blocking
+        /// non-compliance or a compliance percentage under `--min-compliance`
+        #[clap(long)]
+        gate: bool,
+
+        /// `--gate`: lowest acceptable compliance percentage
+        #[clap(long, default_value = "0", value_name = "PERCENT")]
+        min_compliance: f64,
+
+        /// `--gate`: severities that block (critical, major, minor,
+        /// observation, or none); all of them by default
+        #[clap(long, value_name = "SEVERITY", value_delimiter = ',')]
+        blocking: Vec<String>,
     },
     
     Serve {
@@ -218,10 +413,39 @@ pub enum Commands {
         #[clap(long)]
         metrics: bool,
         
+        /// With --metrics or --ownership: print JSON for dashboards
+        #[clap(long)]
+        json: bool,
+        
+        /// Elements grouped by `owner`, with counts and trace coverage per
+        /// owner; unowned elements are listed as unassigned
+        #[clap(long, conflicts_with = "metrics")]
+        ownership: bool,
+        
         #[clap(long)]
         dependencies: bool,
+
+        /// With --dependencies: print the graph as a Graphviz DOT digraph
+        #[clap(long, requires = "dependencies")]
+        dot: bool,
     },
     
+    /// Raw node/edge lists of the model for graph tools (Gephi, Cytoscape)
+    Graph {
+        #[clap(value_parser)]
+        input: PathBuf,
+
+        #[clap(short, long, value_parser)]
+        output: PathBuf,
+
+        #[clap(short, long, value_enum, default_value = "json")]
+        format: GraphFormat,
+
+        /// Write Cytoscape.js elements JSON (`{"elements": {...}}`)
+        #[clap(long)]
+        cytoscape: bool,
+    },
+
     Diagram {
         #[clap(value_parser)]
         input: PathBuf,
@@ -235,6 +459,18 @@ pub enum Commands {
         #[clap(long, default_value = "System Architecture")]
         title: String,
         
+        /// Flow direction of the Mermaid flowchart
+        #[clap(long, value_enum, default_value = "tb")]
+        direction: DiagramDirection,
+        
+        /// Draw only the elements related to this one (id or name)
+        #[clap(long)]
+        root: Option<String>,
+        
+        /// With --root: how many trace/interface hops away to include
+        #[clap(long, default_value_t = 1, requires = "root")]
+        depth: u32,
+        
         #[clap(long)]
         open: bool,
     },
@@ -246,22 +482,42 @@ pub enum SyncCommands {
         #[clap(long)]
         plm: Option<String>,
         
+        /// Requirements connector name from .arclang/sync.toml
         #[clap(long)]
         requirements: Option<String>,
         
         #[clap(long)]
         dry_run: bool,
+        
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
+        
+        /// Sync-managed model file, relative to the project
+        #[clap(long, default_value = "requirements.arc")]
+        output: PathBuf,
     },
     
     Push {
+        /// PLM connector name from .arclang/sync.toml
         #[clap(long)]
         plm: Option<String>,
         
+        /// Requirements connector name from .arclang/sync.toml
         #[clap(long)]
         requirements: Option<String>,
         
+        /// List the API calls without making them
         #[clap(long)]
         dry_run: bool,
+        
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
+        
+        /// Model to push, relative to the project
+        #[clap(long, default_value = "requirements.arc")]
+        model: PathBuf,
     },
     
     Status {
@@ -276,19 +532,128 @@ pub enum SyncCommands {
         #[clap(long)]
         url: String,
         
+        /// Path to a credentials file (referenced, never copied)
         #[clap(long)]
         credentials: Option<PathBuf>,
+        
+        /// Connector name in .arclang/sync.toml (defaults to the type)
+        #[clap(long)]
+        name: Option<String>,
+        
+        /// Project key / id on the remote system
+        #[clap(long)]
+        remote_project: Option<String>,
+        
+        /// Connector-specific setting, e.g. --option issue_type=Story
+        #[clap(long = "option", value_name = "KEY=VALUE")]
+        options: Vec<String>,
+        
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
+        
+        /// Overwrite an existing connector of the same name
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReviewCommands {
+    /// Open a review of the model's current content
+    Create {
+        /// Reviewer whose approval is required (repeatable)
+        #[clap(long = "reviewer", required = true)]
+        reviewers: Vec<String>,
+
+        /// Defaults to $USER
+        #[clap(long)]
+        author: Option<String>,
+
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
+
+        /// Model under review, relative to the project
+        #[clap(long, default_value = "requirements.arc")]
+        model: PathBuf,
+    },
+
+    Comment {
+        /// Review id, e.g. RR-1
+        #[clap(value_parser)]
+        id: String,
+
+        #[clap(long)]
+        message: String,
+
+        /// Defaults to $USER
+        #[clap(long)]
+        author: Option<String>,
+
+        /// Element the comment is about
+        #[clap(long)]
+        element: Option<String>,
+
+        #[clap(long, default_value = "general")]
+        kind: ReviewCommentKind,
+
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
+    },
+
+    /// Approve (or with --request-changes, reject) as one of the reviewers
+    Approve {
+        /// Review id, e.g. RR-1
+        #[clap(value_parser)]
+        id: String,
+
+        /// Defaults to $USER
+        #[clap(long)]
+        reviewer: Option<String>,
+
+        #[clap(long)]
+        request_changes: bool,
+
+        #[clap(long)]
+        comment: Option<String>,
+
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
+    },
+
+    /// Show a review and its outstanding reviewers (all reviews without an id)
+    Status {
+        #[clap(value_parser)]
+        id: Option<String>,
+
+        /// Project directory holding .arclang/
+        #[clap(long, default_value = ".")]
+        project: PathBuf,
     },
 }
 
+#[derive(Debug, clap::ValueEnum, Clone)]
+pub enum ReviewCommentKind {
+    General,
+    Question,
+    Suggestion,
+    Issue,
+    Blocker,
+}
+
 #[derive(Subcommand)]
 pub enum PluginCommands {
     List,
     
     Install {
+        /// Plugin directory, or .tar / .tar.gz archive, with a plugin.toml
         #[clap(value_parser)]
-        name: String,
+        source: PathBuf,
         
+        /// Require the manifest to declare this version
         #[clap(long)]
         version: Option<String>,
     },
@@ -330,6 +695,8 @@ pub enum ExportFormat {
     Simulink,
     FMI,
     ReqIF,
+    CSV,
+    XLSX,
     CHeaders,
     Proto,
 }
@@ -344,6 +711,7 @@ pub enum ImportFormat {
     XML,
     DOORS,
     ReqIF,
+    CSV,
 }
 
 #[derive(Debug, clap::ValueEnum, Clone)]
@@ -353,6 +721,28 @@ pub enum SafetyStandard {
     IEC61508,
 }
 
+/// `arclang graph --format`.
+#[derive(Debug, clap::ValueEnum, Clone, Copy)]
+pub enum GraphFormat {
+    Json,
+}
+
+/// `--direction`: layers top to bottom or left to right.
+#[derive(Debug, clap::ValueEnum, Clone, Copy)]
+pub enum DiagramDirection {
+    Tb,
+    Lr,
+}
+
+impl From<DiagramDirection> for crate::compiler::mermaid_generator::FlowDirection {
+    fn from(direction: DiagramDirection) -> Self {
+        match direction {
+            DiagramDirection::Tb => Self::TopBottom,
+            DiagramDirection::Lr => Self::LeftRight,
+        }
+    }
+}
+
 #[derive(Debug, clap::ValueEnum, Clone)]
 pub enum DiagramFormat {
     Mermaid,
@@ -372,9 +762,41 @@ pub enum DiagramFormat {
     All,
 }
 
+/// The sections of a project's `.arclang/config.toml` the checks read.
+#[derive(serde::Deserialize)]
+struct ProjectConfig {
+    #[serde(default)]
+    id_conventions: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    lint: LintLevelConfig,
+    #[serde(default)]
+    build: BuildSection,
+    #[serde(default)]
+    unreferenced: UnreferencedSection,
+}
+
+/// `[build]` of `.arclang/config.toml`.
+#[derive(Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuildSection {
+    /// Artifact store shared with other projects by incremental builds;
+    /// relative paths are relative to the project.
+    global_store: Option<PathBuf>,
+}
+
+/// `[unreferenced]` of `.arclang/config.toml`.
+#[derive(Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UnreferencedSection {
+    /// Ids of elements meant to stand alone, never reported as dead.
+    #[serde(default)]
+    standalone: Vec<String>,
+}
+
 pub struct CliRunner {
     verbose: bool,
     config_path: Option<PathBuf>,
+    export_plugins: crate::plugins::ExportRegistry,
 }
 
 impl CliRunner {
@@ -382,16 +804,41 @@ impl CliRunner {
         Self {
             verbose: cli.verbose,
             config_path: cli.config.clone(),
+            export_plugins: crate::plugins::ExportRegistry::with_builtin_plugins(),
         }
     }
+
+    /// Make `plugin` available to `export -f custom:<name>`.
+    pub fn register_export_plugin(&mut self, plugin: Box<dyn crate::plugins::ExportPlugin>) {
+        self.export_plugins.register(plugin);
+    }
     
     pub fn run(&self, command: Commands) -> Result<(), CliError> {
         match command {
-            Commands::Build { input, output, incremental, release, target } => {
-                self.run_build(input, output, incremental, release, target)
+            Commands::Build {
+                input, output, incremental, release, target, allow_dangling, deny, warn, allow, jobs, profile, profile_json,
+            } => {
+                let config = crate::CompilerConfig {
+                    optimization_level: if release { 3 } else { 0 },
+                    allow_dangling,
+                    enable_parallel: jobs != Some(1),
+                    num_threads: jobs.unwrap_or(0),
+                    profile: profile || profile_json.is_some(),
+                    ..Default::default()
+                };
+                let levels = LintLevelConfig { deny, warn, allow };
+                match self.run_build(input, output, incremental, target, config, &levels)? {
+                    Some(profile) => Self::report_profile(&profile, profile_json.as_deref()),
+                    None => Ok(()),
+                }
             }
-            Commands::Check { input, lint, safety } => {
-                self.run_check(input, lint, safety)
+            Commands::Check { input, lint, lint_config, safety, json, allow_dangling, deny, warn, allow } => {
+                let config = crate::CompilerConfig { allow_dangling, ..Default::default() };
+                let levels = LintLevelConfig { deny, warn, allow };
+                if json {
+                    return self.run_check_json(input, config, &levels);
+                }
+                self.run_check(input, config, lint, lint_config, safety, &levels)
             }
             Commands::Format { input, check, write } => {
                 self.run_format(input, check, write)
@@ -405,26 +852,45 @@ impl CliRunner {
             Commands::Plugin { plugin_command } => {
                 self.run_plugin(plugin_command)
             }
-            Commands::Trace { input, from, to, validate, matrix } => {
-                self.run_trace(input, from, to, validate, matrix)
+            Commands::Trace { input, from, to, validate, matrix, output } => {
+                self.run_trace(input, from, to, validate, matrix, output)
+            }
+            Commands::Impact { input, element, json } => {
+                self.run_impact(input, element, json)
             }
-            Commands::Impact { input, element } => {
-                self.run_impact(input, element)
+            Commands::History { input, element, json } => {
+                self.run_history(input, element, json)
+            }
+            Commands::Blame { input, json } => {
+                self.run_blame(input, json)
             }
             Commands::Diff { old, new, json } => {
                 self.run_diff(old, new, json)
             }
+            Commands::Baseline { input, output } => {
+                self.run_baseline(input, output)
+            }
+            Commands::Merge { base, ours, theirs, auto_resolve, json } => {
+                self.run_merge(base, ours, theirs, auto_resolve, json)
+            }
+            Commands::MergeModels { inputs, output, namespace, json } => {
+                self.run_merge_models(inputs, output, namespace, json)
+            }
+            Commands::Review { review_command } => {
+                self.run_review(review_command)
+            }
             Commands::Gate { input, standard } => {
                 self.run_gate(input, standard)
             }
-            Commands::Export { input, output, format } => {
-                self.run_export(input, output, format)
+            Commands::Export { input, output, format, direction } => {
+                self.run_export(input, output, format, direction)
             }
-            Commands::Import { input, format, output } => {
-                self.run_import(input, format, output)
+            Commands::Import { input, format, output, columns } => {
+                self.run_import(input, format, output, &columns)
             }
-            Commands::Safety { input, standard, fmea, fta, report } => {
-                self.run_safety(input, standard, fmea, fta, report)
+            Commands::Safety { input, standard, fmea, fta, report, gate, min_compliance, blocking } => {
+                let gate = gate.then(|| compliance_gate(min_compliance, &blocking)).transpose()?;
+                self.run_safety(input, standard, fmea, fta, report, gate)
             }
             Commands::Serve { port } => {
                 self.run_serve(port)
@@ -441,11 +907,14 @@ impl CliRunner {
             Commands::Clean { project, cache } => {
                 self.run_clean(project, cache)
             }
-            Commands::Info { input, metrics, dependencies } => {
-                self.run_info(input, metrics, dependencies)
+            Commands::Info { input, metrics, json, ownership, dependencies, dot } => {
+                self.run_info(input, metrics, json, ownership, dependencies, dot)
+            }
+            Commands::Graph { input, output, format, cytoscape } => {
+                self.run_graph(input, output, format, cytoscape)
             }
-            Commands::Diagram { input, output, format, title, open } => {
-                self.run_diagram(input, output, format, title, open)
+            Commands::Diagram { input, output, format, title, direction, root, depth, open } => {
+                self.run_diagram(input, output, format, title, direction, root, depth, open)
             }
         }
     }
@@ -455,74 +924,256 @@ impl CliRunner {
         input: PathBuf,
         output: Option<PathBuf>,
         incremental: bool,
-        release: bool,
         target: Option<String>,
-    ) -> Result<(), CliError> {
+        config: crate::CompilerConfig,
+        lint_overrides: &LintLevelConfig,
+    ) -> Result<Option<Profile>, CliError> {
         println!("Building {}...", input.display());
+        let levels = Self::project_lint_levels(&input, lint_overrides)?;
+        let config = crate::CompilerConfig { standalone: Self::project_standalone(&input)?, ..config };
         
-        let mut config = crate::CompilerConfig::default();
-        config.optimization_level = if release { 3 } else { 0 };
+        let output_path = output.unwrap_or_else(|| input.with_extension("json"));
+        if incremental {
+            return self.run_incremental_build(&input, &output_path, &config, &levels);
+        }
         
         let mut compiler = crate::Compiler::new(config);
         
         match compiler.compile_file(&input) {
-            Ok(result) => {
-                let output_path = output.unwrap_or_else(|| {
-                    input.with_extension("json")
-                });
-                
-                if let Err(e) = std::fs::write(&output_path, &result.output) {
-                    return Err(CliError::Io(e));
-                }
-                
-                if !result.warnings.is_empty() {
-                    eprintln!("⚠ {} warning(s):", result.warnings.len());
-                    for warning in &result.warnings {
+            Ok(mut result) => {
+                let mut profile = result.profile.take();
+                let timer = PhaseTimer::start(profile.as_mut(), "validation");
+                let leveled = level_warnings(&result.warnings, &result.semantic_model, &levels);
+                drop(timer);
+                if !leveled.compiler.is_empty() {
+                    eprintln!("⚠ {} warning(s):", leveled.compiler.len());
+                    for warning in &leveled.compiler {
                         eprintln!("  warning: {}", warning);
                     }
                 }
+                Self::check_denied_lints(&leveled.denied)?;
 
-                println!("✓ Compilation successful");
-                println!("  Output: {}", output_path.display());
-                println!("  Requirements: {}", result.semantic_model.requirements.len());
-                println!("  Components: {}", result.semantic_model.components.len());
-                println!("  Functions: {}", result.semantic_model.functions.len());
-                println!("  Traces: {}", result.semantic_model.traces.len());
+                let timer = PhaseTimer::start(profile.as_mut(), "write output");
+                if let Err(e) = std::fs::write(&output_path, &result.output) {
+                    return Err(CliError::Io(e));
+                }
+                artifacts::record(&output_path)?;
+                drop(timer);
+
+                Self::print_build_summary(&output_path, &result.semantic_model);
+                if let Some(stats) = &result.parse_stats {
+                    println!(
+                        "  Parsed: {} file(s) on {} thread(s) in {:.1} ms ({:.1} ms serially, {:.1}x)",
+                        stats.files,
+                        stats.threads,
+                        stats.wall.as_secs_f64() * 1000.0,
+                        stats.serial.as_secs_f64() * 1000.0,
+                        stats.speedup()
+                    );
+                }
 
-                Ok(())
+                Ok(profile)
             }
             Err(e) => {
-                eprintln!("✗ Compilation failed: {}", e);
-                Err(CliError::Compilation(e.to_string()))
+                eprintln!("✗ Compilation failed");
+                Err(Self::report_compile_error(&input, &e))
             }
         }
     }
     
-    fn run_check(&self, input: PathBuf, lint: bool, safety: bool) -> Result<(), CliError> {
+    /// Recompile only the files under `input` that changed since the last
+    /// build, reusing the rest from `.arclang/cache`. Without a cache every
+    /// file is stale, so the first run is a full build.
+    fn run_incremental_build(
+        &self,
+        input: &Path,
+        output_path: &Path,
+        config: &crate::CompilerConfig,
+        levels: &LintLevels,
+    ) -> Result<Option<Profile>, CliError> {
+        use crate::compiler::incremental::{CacheStrategy, IncrementalCompiler, IncrementalConfig};
+        use crate::compiler::profile::CacheProfile;
+
+        let compile_error = |e: crate::compiler::incremental::IncrementalError| {
+            eprintln!("✗ Compilation failed: {}", e);
+            CliError::Compilation(e.to_string())
+        };
+
+        let mut profile = config.profile.then(Profile::default);
+        let timer = PhaseTimer::start(profile.as_mut(), "cache load");
+        let mut compiler = IncrementalCompiler::new(IncrementalConfig {
+            cache_dir: PathBuf::from(".arclang").join("cache"),
+            max_cache_size_mb: 256,
+            enable_parallel: true,
+            num_threads: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            cache_strategy: CacheStrategy::ContentBased,
+            global_store_dir: Self::project_global_store(input)?,
+        })
+        .map_err(compile_error)?;
+
+        drop(timer);
+
+        let root = input.canonicalize()?.to_string_lossy().into_owned();
+        let started = profile.is_some().then(std::time::Instant::now);
+        let stale = compiler.stale_files(&root).map_err(compile_error)?;
+        let result = compiler.compile_incremental(&stale).map_err(compile_error)?;
+        if let (Some(profile), Some(started)) = (&mut profile, started) {
+            // The cache is saved inside compile_incremental
+            profile.record("incremental compile", started.elapsed().saturating_sub(result.cache_save_time));
+            profile.record("cache save", result.cache_save_time);
+            profile.cache = Some(CacheProfile {
+                hit_ratio: result.cache_hit_ratio,
+                compiled: result.compiled_files.len(),
+                cached: result.cached_files.len(),
+            });
+        }
+
+        let timer = PhaseTimer::start(profile.as_mut(), "semantic model");
+        let semantic_model = compiler.cached_semantic_model(&root).map_err(compile_error)?;
+        drop(timer);
+        if let Some(profile) = &mut profile {
+            profile.count_elements(&semantic_model);
+        }
+        let timer = PhaseTimer::start(profile.as_mut(), "validation");
+        let denied = level_warnings(&[], &semantic_model, levels).denied;
+        drop(timer);
+        Self::check_denied_lints(&denied)?;
+
+        let timer = PhaseTimer::start(profile.as_mut(), "codegen");
+        let output = crate::compiler::codegen::CodeGenerator::new(config)
+            .generate(&semantic_model)
+            .map_err(|e| CliError::Compilation(e.to_string()))?;
+        drop(timer);
+        let timer = PhaseTimer::start(profile.as_mut(), "write output");
+        std::fs::write(output_path, output)?;
+        artifacts::record(output_path)?;
+        drop(timer);
+
+        Self::print_build_summary(output_path, &semantic_model);
+        println!(
+            "  Incremental: {} compiled, {} cached ({:.0}% cache hits)",
+            result.compiled_files.len(),
+            result.cached_files.len(),
+            result.cache_hit_ratio * 100.0
+        );
+        for invalidation in &result.invalidations {
+            println!("    ↻ {}: {}", invalidation.file, invalidation.reason);
+        }
+        if !result.global_store_hits.is_empty() {
+            println!("  Global store: {} file(s) reused", result.global_store_hits.len());
+        }
+
+        Ok(profile)
+    }
+
+    /// Print `profile`, and write it as JSON to `json_path` if given.
+    fn report_profile(profile: &Profile, json_path: Option<&Path>) -> Result<(), CliError> {
+        print!("{}", profile.render());
+        if let Some(path) = json_path {
+            std::fs::write(path, profile.to_json())?;
+            println!("  Profile written to {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// Print the findings of denied lints; an error when there are any.
+    fn check_denied_lints(denied: &[String]) -> Result<(), CliError> {
+        if denied.is_empty() {
+            return Ok(());
+        }
+        eprintln!("\n✗ Denied lints:");
+        for finding in denied {
+            eprintln!("  {}", finding);
+        }
+        Err(CliError::Compilation(format!("{} denied lint finding(s)", denied.len())))
+    }
+
+    fn print_build_summary(output_path: &Path, semantic_model: &crate::compiler::semantic::SemanticModel) {
+        println!("✓ Compilation successful");
+        println!("  Output: {}", output_path.display());
+        println!("  Requirements: {}", semantic_model.requirements.len());
+        println!("  Components: {}", semantic_model.components.len());
+        println!("  Functions: {}", semantic_model.functions.len());
+        println!("  Traces: {}", semantic_model.traces.len());
+    }
+    
+    fn run_check(
+        &self,
+        input: PathBuf,
+        config: crate::CompilerConfig,
+        lint: bool,
+        lint_config: Option<PathBuf>,
+        safety: bool,
+        lint_overrides: &LintLevelConfig,
+    ) -> Result<(), CliError> {
         println!("Checking {}...", input.display());
+        let levels = Self::project_lint_levels(&input, lint_overrides)?;
         
-        let config = crate::CompilerConfig::default();
+        let lint_config = match lint_config {
+            Some(path) => {
+                let text = std::fs::read_to_string(&path)?;
+                toml::from_str(&text).map_err(|e| CliError::Config(format!("invalid {}: {e}", path.display())))?
+            }
+            None => crate::compiler::requirement_lint::LintConfig::default(),
+        };
+        
+        // Dead elements are a lint finding
+        let config = crate::CompilerConfig {
+            optimization_level: if lint { config.optimization_level.max(2) } else { config.optimization_level },
+            standalone: Self::project_standalone(&input)?,
+            ..config
+        };
         let mut compiler = crate::Compiler::new(config);
         
         match compiler.compile_file(&input) {
             Ok(result) => {
                 println!("✓ No compilation errors");
 
-                if !result.warnings.is_empty() {
+                let leveled = level_warnings(&result.warnings, &result.semantic_model, &levels);
+                if !leveled.compiler.is_empty() {
                     println!("\n⚠ Compilation warnings:");
-                    for warning in &result.warnings {
+                    for warning in &leveled.compiler {
                         println!("  {}", warning);
                     }
                 }
 
-                let warnings = result.semantic_model.validate_traceability();
-                if !warnings.is_empty() {
+                if !leveled.traceability.is_empty() {
                     println!("\n⚠ Traceability warnings:");
-                    for warning in &warnings {
+                    for warning in &leveled.traceability {
                         println!("  {}", warning);
                     }
                 }
                 
+                let errors = result.semantic_model.traceability_errors();
+                if !errors.is_empty() {
+                    eprintln!("\n✗ Traceability errors:");
+                    for error in &errors {
+                        eprintln!("  {}", error);
+                    }
+                    return Err(CliError::Compilation(format!("{} traceability error(s)", errors.len())));
+                }
+                Self::check_denied_lints(&leveled.denied)?;
+
+                let conventions = Self::project_id_conventions(&input)?;
+                let findings = crate::compiler::id_conventions::check_id_conventions(&result.semantic_model, &conventions);
+                if !findings.is_empty() {
+                    eprintln!("\n✗ ID conventions:");
+                    for finding in &findings {
+                        eprintln!("  error[{}]: {}", finding.code, finding.diagnostic);
+                    }
+                    let renames: Vec<_> = findings
+                        .iter()
+                        .filter_map(|f| Some((&f.element, f.suggestion.as_ref()?)))
+                        .collect();
+                    if !renames.is_empty() {
+                        eprintln!("\nSuggested ids:");
+                        for (id, suggestion) in renames {
+                            eprintln!("  {id} -> {suggestion}");
+                        }
+                    }
+                    return Err(CliError::Compilation(format!("{} id convention error(s)", findings.len())));
+                }
+                
                 if lint {
                     let lints = crate::compiler::semantic::arcadia_methodology_lints(&result.ast);
                     if lints.is_empty() {
@@ -533,6 +1184,27 @@ impl CliRunner {
                             println!("  {}", advisory);
                         }
                     }
+
+                    let findings = crate::compiler::requirement_lint::lint_requirements(&result.semantic_model, &lint_config);
+                    if findings.is_empty() {
+                        println!("✓ Requirements quality: no findings");
+                    } else {
+                        println!("\nℹ Requirements quality:");
+                        for finding in &findings {
+                            let label = match finding.severity {
+                                crate::compiler::diagnostic::Severity::Error => "error",
+                                crate::compiler::diagnostic::Severity::Warning => "warning",
+                            };
+                            println!("  {label}[{}]: {}", finding.code, finding.diagnostic);
+                        }
+                        let errors = findings
+                            .iter()
+                            .filter(|f| f.severity == crate::compiler::diagnostic::Severity::Error)
+                            .count();
+                        if errors > 0 {
+                            return Err(CliError::Compilation(format!("{errors} requirements quality error(s)")));
+                        }
+                    }
                 }
 
                 if lint || safety {
@@ -548,32 +1220,567 @@ impl CliRunner {
                 Ok(())
             }
             Err(e) => {
-                eprintln!("✗ Check failed: {}", e);
-                Err(CliError::Compilation(e.to_string()))
+                eprintln!("✗ Check failed");
+                Err(Self::report_compile_error(&input, &e))
             }
         }
     }
     
-    fn run_format(&self, _input: PathBuf, _check: bool, _write: bool) -> Result<(), CliError> {
-        Err(CliError::NotImplemented("the formatter is not implemented yet".to_string()))
-    }
-
-    fn run_new(&self, _name: String, _template: Option<String>) -> Result<(), CliError> {
-        Err(CliError::NotImplemented(
-            "project scaffolding ('new') is not implemented yet".to_string(),
-        ))
-    }
+    /// `check --json`: one JSON object per diagnostic and line (severity,
+    /// stable code, message, file, range); exit code 1 on any error.
+    fn run_check_json(
+        &self,
+        input: PathBuf,
+        config: crate::CompilerConfig,
+        lint_overrides: &LintLevelConfig,
+    ) -> Result<(), CliError> {
+        use crate::compiler::diagnostic::{leveled_records, Severity};
+
+        let levels = Self::project_lint_levels(&input, lint_overrides)?;
+        let mut compiler = crate::Compiler::new(config);
+        let outcome = compiler.compile_file(&input);
+        let mut records = leveled_records(&outcome, &levels);
+        if let Ok(result) = &outcome {
+            let conventions = Self::project_id_conventions(&input)?;
+            records.extend(
+                crate::compiler::id_conventions::check_id_conventions(&result.semantic_model, &conventions)
+                    .iter()
+                    .map(|finding| finding.diagnostic.record(Severity::Error, finding.code)),
+            );
+        }
+
+        let mut errors = 0;
+        for mut record in records {
+            record.file.get_or_insert_with(|| input.clone());
+            if record.severity == Severity::Error {
+                errors += 1;
+            }
+            println!(
+                "{}",
+                serde_json::to_string(&record).map_err(|e| CliError::Compilation(e.to_string()))?
+            );
+        }
+        if errors == 0 {
+            Ok(())
+        } else {
+            Err(CliError::Compilation(format!("{} error(s) in {}", errors, input.display())))
+        }
+    }
+    
+    /// The nearest `.arclang/config.toml` above `input` and its contents;
+    /// `None` outside a project.
+    fn project_config(input: &Path) -> Result<Option<(PathBuf, ProjectConfig)>, CliError> {
+        let input = input.canonicalize()?;
+        let Some(path) = input
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join(".arclang").join("config.toml"))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+        let text = std::fs::read_to_string(&path)?;
+        let config: ProjectConfig =
+            toml::from_str(&text).map_err(|e| CliError::Config(format!("invalid {}: {e}", path.display())))?;
+        Ok(Some((path, config)))
+    }
+
+    /// The `[id_conventions]` of the project holding `input`. None
+    /// configured outside a project.
+    fn project_id_conventions(input: &Path) -> Result<crate::compiler::id_conventions::IdConventions, CliError> {
+        let Some((path, config)) = Self::project_config(input)? else {
+            return Ok(Default::default());
+        };
+        crate::compiler::id_conventions::IdConventions::new(&config.id_conventions)
+            .map_err(|e| CliError::Config(format!("{}: {e}", path.display())))
+    }
+
+    /// The `[unreferenced] standalone` ids of the project holding `input`.
+    fn project_standalone(input: &Path) -> Result<Vec<String>, CliError> {
+        Ok(Self::project_config(input)?.map(|(_, config)| config.unreferenced.standalone).unwrap_or_default())
+    }
+
+    /// The `[build] global_store` of the project holding `input`.
+    fn project_global_store(input: &Path) -> Result<Option<PathBuf>, CliError> {
+        let Some((path, config)) = Self::project_config(input)? else {
+            return Ok(None);
+        };
+        let project = path.parent().and_then(Path::parent).unwrap_or(Path::new("."));
+        Ok(config.build.global_store.map(|store| project.join(store)))
+    }
+
+    /// The `[lint]` levels of the project holding `input`, with the
+    /// command-line `overrides` on top.
+    fn project_lint_levels(input: &Path, overrides: &LintLevelConfig) -> Result<LintLevels, CliError> {
+        let mut levels = LintLevels::default();
+        if let Some((path, config)) = Self::project_config(input)? {
+            levels.apply(&config.lint).map_err(|e| CliError::Config(format!("{}: [lint] {e}", path.display())))?;
+        }
+        levels.apply(overrides).map_err(CliError::Config)?;
+        Ok(levels)
+    }
+    
+    /// Print each located error rustc-style (the offending line, underlined)
+    /// and summarize them for the exit message.
+    fn report_compile_error(input: &Path, error: &crate::CompilerError) -> CliError {
+        let diagnostics = error.diagnostics();
+        for diagnostic in &diagnostics {
+            let file = diagnostic
+                .span
+                .as_ref()
+                .and_then(|span| span.file.as_deref())
+                .unwrap_or(input);
+            let source = std::fs::read_to_string(file).ok();
+            eprint!("{}", diagnostic.render(source.as_deref()));
+        }
+        CliError::Compilation(format!("{} error(s) in {}", diagnostics.len(), input.display()))
+    }
+    
+    fn run_format(&self, input: PathBuf, check: bool, write: bool) -> Result<(), CliError> {
+        let source = std::fs::read_to_string(&input)?;
+        let formatted = crate::compiler::formatter::format_source(&source)
+            .map_err(|e| CliError::Compilation(format!("{}: {e}", input.display())))?;
+
+        if check {
+            if formatted == source {
+                println!("✓ {} is formatted", input.display());
+                return Ok(());
+            }
+            let path = input.display().to_string();
+            print!(
+                "{}",
+                similar::TextDiff::from_lines(&source, &formatted)
+                    .unified_diff()
+                    .header(&path, &path)
+            );
+            return Err(CliError::NotFormatted(input));
+        }
+
+        if write {
+            if formatted == source {
+                println!("✓ {} already formatted", input.display());
+            } else {
+                std::fs::write(&input, &formatted)?;
+                println!("✓ Formatted {}", input.display());
+            }
+            return Ok(());
+        }
+
+        print!("{}", formatted);
+        Ok(())
+    }
+
+    fn run_new(&self, name: String, template: Option<String>) -> Result<(), CliError> {
+        let template_name = template.as_deref().unwrap_or(templates::DEFAULT_TEMPLATE);
+        let template = templates::find(template_name).ok_or_else(|| {
+            let known: Vec<&str> = templates::TEMPLATES.iter().map(|t| t.name).collect();
+            CliError::Config(format!(
+                "unknown template '{}' (available: {})",
+                template_name,
+                known.join(", ")
+            ))
+        })?;
+
+        let dir = PathBuf::from(&name);
+        let files = templates::scaffold(&dir, template)?;
+
+        println!("✓ Created {} from template '{}'", dir.display(), template.name);
+        for file in &files {
+            println!("  {}", file.display());
+        }
+        println!("\nNext: cd {} && arclang check main.arc", dir.display());
+        Ok(())
+    }
+
+    fn run_sync(&self, sync_command: SyncCommands) -> Result<(), CliError> {
+        use sync_config::{connector_kind, ConnectorConfig, SyncConfig};
+
+        match sync_command {
+            SyncCommands::Pull { plm, requirements, dry_run, project, output } => {
+                if plm.is_some() {
+                    return Err(CliError::NotImplemented(
+                        "pulling PLM baselines is not implemented yet".to_string(),
+                    ));
+                }
+                self.run_sync_pull(requirements.as_deref(), dry_run, &project, &output)
+            }
+            SyncCommands::Push { plm, requirements, dry_run, project, model } => {
+                if requirements.is_some() && plm.is_some() {
+                    return Err(CliError::Config("push to --requirements or --plm, not both".to_string()));
+                }
+                if !dry_run {
+                    Self::ensure_reviewed(&project, &model)?;
+                }
+                match plm {
+                    Some(name) => self.run_sync_push_plm(&name, dry_run, &project, &model),
+                    None => self.run_sync_push(requirements.as_deref(), dry_run, &project, &model),
+                }
+            }
+            SyncCommands::Configure { plm_type, url, credentials, name, remote_project, options, project, force } => {
+                let kind = connector_kind(&plm_type).map_err(CliError::Config)?;
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(CliError::Config(format!("connector URL must be http(s): {url}")));
+                }
+                if let Some(path) = &credentials {
+                    if !path.is_file() {
+                        println!("⚠ Credentials file {} does not exist yet", path.display());
+                    }
+                }
+                let options = options
+                    .iter()
+                    .map(|option| {
+                        option
+                            .split_once('=')
+                            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                            .ok_or_else(|| CliError::Config(format!("--option expects KEY=VALUE, got '{option}'")))
+                    })
+                    .collect::<Result<_, _>>()?;
+                let name = name.unwrap_or_else(|| kind.to_string());
+                let connector = ConnectorConfig {
+                    kind: kind.to_string(),
+                    url,
+                    credentials,
+                    auth: None,
+                    project: remote_project,
+                    options,
+                };
+                let mut config = SyncConfig::load(&project).map_err(CliError::Config)?;
+                config.insert(&name, connector, force).map_err(CliError::Config)?;
+                let path = config.save(&project).map_err(CliError::Config)?;
+                println!("✓ Configured connector '{}' ({}) in {}", name, kind, path.display());
+                Ok(())
+            }
+            _ => Err(CliError::NotImplemented(
+                "PLM synchronization is not implemented yet".to_string(),
+            )),
+        }
+    }
+    
+    fn run_sync_pull(&self, name: Option<&str>, dry_run: bool, project: &Path, output: &Path) -> Result<(), CliError> {
+        use crate::compiler::ast;
+        use crate::integrations::sync as rm_sync;
+
+        let config = sync_config::SyncConfig::load(project).map_err(CliError::Config)?;
+        let (name, connector) = config.select(name, true).map_err(CliError::Config)?;
+        if !connector.is_requirements() {
+            return Err(CliError::Config(format!("connector '{name}' ({}) is not a requirements connector", connector.kind)));
+        }
+        let remote = Self::remote_project(project, name, connector)?;
+
+        // Local side: the sync-managed file, if a previous pull wrote one
+        let target = project.join(output);
+        let (local, local_traces) = if target.exists() {
+            let source = std::fs::read_to_string(&target)?;
+            if !source.starts_with(rm_sync::PULL_HEADER) {
+                return Err(CliError::Sync(format!(
+                    "{} was not written by `arclang sync pull`; choose another --output",
+                    target.display()
+                )));
+            }
+            let tokens = crate::compiler::lexer::Lexer::new(&source)
+                .tokenize()
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            let model = crate::compiler::parser::Parser::new(tokens)
+                .parse()
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            let requirements: Vec<ast::Requirement> =
+                model.system_analysis.iter().flat_map(|sa| sa.requirements.clone()).collect();
+            let traces: Vec<(String, String, String)> = model
+                .traces
+                .iter()
+                .map(|t| (t.from.clone(), t.trace_type.clone(), t.to.clone()))
+                .collect();
+            (requirements, traces)
+        } else {
+            (Vec::new(), Vec::new())
+        };
 
-    fn run_sync(&self, _sync_command: SyncCommands) -> Result<(), CliError> {
-        Err(CliError::NotImplemented(
-            "PLM synchronization is not implemented yet".to_string(),
-        ))
+        println!("Pulling requirements from '{}' ({})...", name, connector.url);
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| CliError::Sync(format!("Failed to create runtime: {}", e)))?;
+        let baseline = runtime
+            .block_on(async {
+                let mut client = remote.connector()?;
+                client.connect(&remote.rm_config()?).await?;
+                let baseline = client.fetch_baseline().await;
+                let _ = client.disconnect().await;
+                baseline
+            })
+            .map_err(|e| CliError::Sync(e.to_string()))?;
+
+        let plan = rm_sync::plan_pull(&local, &local_traces, &baseline);
+        println!(
+            "{} new, {} changed, {} unchanged",
+            plan.added.len(),
+            plan.changed.len(),
+            plan.unchanged
+        );
+        for id in &plan.added {
+            println!("  + {}", id);
+        }
+        for (id, fields) in &plan.changed {
+            println!("  ~ {} ({})", id, fields.join(", "));
+        }
+        for id in &plan.orphaned {
+            println!("⚠ {} exists locally but not in '{}' (potentially orphaned, kept)", id, name);
+        }
+
+        if dry_run {
+            println!("Dry run: {} not written", target.display());
+            return Ok(());
+        }
+        if let Some(dir) = target.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&target, rm_sync::render_pull(name, &plan))?;
+        println!("✓ Wrote {} ({} requirements)", target.display(), plan.requirements.len());
+        Ok(())
     }
     
-    fn run_plugin(&self, _plugin_command: PluginCommands) -> Result<(), CliError> {
-        Err(CliError::NotImplemented(
-            "the plugin system is not implemented yet".to_string(),
-        ))
+    fn remote_project<'a>(
+        project: &Path,
+        name: &str,
+        connector: &'a sync_config::ConnectorConfig,
+    ) -> Result<crate::integrations::sync::RemoteProject<'a>, CliError> {
+        use crate::integrations::sync::{load_credentials, RemoteProject};
+
+        let auth = match (&connector.auth, &connector.credentials) {
+            (Some(auth), _) => auth.resolve(),
+            (None, Some(credentials)) => load_credentials(&project.join(credentials)),
+            (None, None) => {
+                return Err(CliError::Config(format!(
+                    "connector '{name}' has no credentials file or [connectors.{name}.auth] table"
+                )))
+            }
+        }
+        .map_err(|e| CliError::Sync(e.to_string()))?;
+        Ok(RemoteProject {
+            kind: &connector.kind,
+            url: &connector.url,
+            project: connector.project.as_deref().unwrap_or_default(),
+            options: &connector.options,
+            auth,
+        })
+    }
+
+    /// With `review_required` set in .arclang/collaboration.toml, only an
+    /// approved changeset may be pushed.
+    fn ensure_reviewed(project: &Path, model: &Path) -> Result<(), CliError> {
+        use crate::collaboration::review::{model_changeset_id, ReviewStore};
+
+        if !Self::collaboration_config(project)?.review_required {
+            return Ok(());
+        }
+        ReviewStore::open(project).require_approval(&model_changeset_id(&project.join(model))?)?;
+        Ok(())
+    }
+
+    fn collaboration_config(project: &Path) -> Result<crate::collaboration::CollaborationConfig, CliError> {
+        let path = project.join(".arclang").join("collaboration.toml");
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+        let text = std::fs::read_to_string(&path)?;
+        toml::from_str(&text).map_err(|e| CliError::Config(format!("invalid {}: {e}", path.display())))
+    }
+
+    fn compile_for_push(project: &Path, model: &Path) -> Result<crate::compiler::CompilationResult, CliError> {
+        let path = project.join(model);
+        let mut compiler = crate::Compiler::new(crate::CompilerConfig::default());
+        compiler
+            .compile_file(&path)
+            .map_err(|e| CliError::Compilation(format!("{}: {}", path.display(), e)))
+    }
+
+    fn run_sync_push(&self, name: Option<&str>, dry_run: bool, project: &Path, model: &Path) -> Result<(), CliError> {
+        use crate::integrations::requirements_management::{delta_computer::RMDeltaComputer, RMIntegrationManager};
+        use crate::integrations::sync as rm_sync;
+
+        let config = sync_config::SyncConfig::load(project).map_err(CliError::Config)?;
+        let (name, connector) = config.select(name, true).map_err(CliError::Config)?;
+        if !connector.is_requirements() {
+            return Err(CliError::Config(format!("connector '{name}' ({}) is not a requirements connector", connector.kind)));
+        }
+        let remote = Self::remote_project(project, name, connector)?;
+        let compiled = Self::compile_for_push(project, model)?;
+
+        println!("Pushing {} to '{}' ({})...", model.display(), name, connector.url);
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| CliError::Sync(format!("Failed to create runtime: {}", e)))?;
+        let result = runtime
+            .block_on(async {
+                let rm_config = remote.rm_config()?;
+                let mut client = remote.connector()?;
+                client.connect(&rm_config).await?;
+                let baseline = client.fetch_baseline().await?;
+                let delta = RMDeltaComputer::new(compiled.semantic_model, Some(baseline)).compute_delta()?;
+                if dry_run {
+                    let _ = client.disconnect().await;
+                    return Ok((delta, None));
+                }
+                let mut manager = RMIntegrationManager::new(rm_config.clone());
+                manager.register_connector(rm_config.system, client);
+                let result = manager.sync_to_rm(&delta).await?;
+                Ok((delta, Some(result)))
+            })
+            .map_err(|e: crate::integrations::requirements_management::RMError| CliError::Sync(e.to_string()))?;
+
+        let (delta, result) = result;
+        for id in &delta.deleted_requirements {
+            println!("⚠ {} exists in '{}' but not locally (not deleted remotely)", id, name);
+        }
+        let Some(result) = result else {
+            let calls = rm_sync::describe_rm_delta(&delta);
+            if calls.is_empty() {
+                println!("Dry run: '{}' is up to date", name);
+            }
+            for call in calls {
+                println!("  {}", call);
+            }
+            return Ok(());
+        };
+
+        for id in &result.requirements_created {
+            println!("  + {}", id);
+        }
+        for id in &result.requirements_updated {
+            println!("  ~ {}", id);
+        }
+        for id in &result.trace_links_created {
+            println!("  → {}", id);
+        }
+        for (id, error) in result.requirements_failed.iter().chain(&result.trace_links_failed) {
+            println!("✗ {}: {}", id, error);
+        }
+        println!(
+            "{} created, {} updated, {} trace links, {} failed",
+            result.requirements_created.len(),
+            result.requirements_updated.len(),
+            result.trace_links_created.len(),
+            result.requirements_failed.len() + result.trace_links_failed.len()
+        );
+        if result.success {
+            println!("✓ Pushed to '{}'", name);
+            Ok(())
+        } else {
+            Err(CliError::Sync(format!("push to '{}' completed with failures", name)))
+        }
+    }
+
+    fn run_sync_push_plm(&self, name: &str, dry_run: bool, project: &Path, model: &Path) -> Result<(), CliError> {
+        use crate::integrations::plm_integration::{delta_computer::DeltaComputer, PLMError};
+        use crate::integrations::sync as rm_sync;
+
+        let config = sync_config::SyncConfig::load(project).map_err(CliError::Config)?;
+        let (name, connector) = config.select(Some(name), false).map_err(CliError::Config)?;
+        if connector.is_requirements() {
+            return Err(CliError::Config(format!("connector '{name}' ({}) is not a PLM connector", connector.kind)));
+        }
+        let remote = Self::remote_project(project, name, connector)?;
+        let compiled = Self::compile_for_push(project, model)?;
+
+        println!("Pushing {} to '{}' ({})...", model.display(), name, connector.url);
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| CliError::Sync(format!("Failed to create runtime: {}", e)))?;
+        let (delta, plan, result) = runtime
+            .block_on(async {
+                let mut client = remote.plm_connector()?;
+                client.connect(&remote.plm_config()?).await?;
+                let baseline = client.fetch_baseline().await?;
+                let delta = DeltaComputer::new(compiled.ast, Some(baseline)).compute_delta()?;
+                let plan = client.plan_changes(&delta);
+                let result = if dry_run { None } else { Some(client.execute_plan(&plan).await?) };
+                let _ = client.disconnect().await;
+                Ok((delta, plan, result))
+            })
+            .map_err(|e: PLMError| CliError::Sync(e.to_string()))?;
+
+        for part in &delta.deleted_parts {
+            println!("⚠ {} exists in '{}' but not locally (not deleted remotely)", part, name);
+        }
+        let Some(result) = result else {
+            let calls = rm_sync::describe_plm_plan(&plan, &delta);
+            if calls.is_empty() {
+                println!("Dry run: '{}' is up to date", name);
+            }
+            for call in calls {
+                println!("  {}", call);
+            }
+            return Ok(());
+        };
+
+        for part in &result.parts_created {
+            println!("  + {}", part);
+        }
+        for part in &result.parts_updated {
+            println!("  ~ {}", part);
+        }
+        for (part, error) in &result.parts_failed {
+            println!("✗ {}: {}", part, error);
+        }
+        if let Some(eco) = &result.eco_id {
+            println!("✓ Raised change order {}", eco);
+        }
+        println!(
+            "{} created, {} updated, {} failed",
+            result.parts_created.len(),
+            result.parts_updated.len(),
+            result.parts_failed.len()
+        );
+        if result.success {
+            println!("✓ Pushed to '{}'", name);
+            Ok(())
+        } else {
+            Err(CliError::Sync(format!("push to '{}' completed with failures", name)))
+        }
+    }
+
+    fn run_plugin(&self, plugin_command: PluginCommands) -> Result<(), CliError> {
+        let registry = plugins::PluginRegistry::open(Path::new("."));
+        match plugin_command {
+            PluginCommands::List => {
+                let installed = registry.list()?;
+                if installed.is_empty() {
+                    println!("No plugins installed (.arclang/plugins)");
+                    return Ok(());
+                }
+                println!("{:<24} {:<10} {:<9} DESCRIPTION", "NAME", "VERSION", "STATUS");
+                for plugin in &installed {
+                    println!(
+                        "{:<24} {:<10} {:<9} {}",
+                        plugin.name,
+                        plugin.version,
+                        if plugin.enabled { "enabled" } else { "disabled" },
+                        plugin.description
+                    );
+                }
+            }
+            PluginCommands::Info { name } => {
+                let plugin = registry.get(&name)?;
+                println!("Plugin: {}", plugin.name);
+                println!("  Version: {}", plugin.version);
+                println!("  Description: {}", plugin.description);
+                println!("  Capabilities: {}", plugin.capabilities.join(", "));
+                println!("  Enabled: {}", plugin.enabled);
+            }
+            PluginCommands::Install { source, version } => {
+                let plugin = registry.install(&source, version.as_deref())?;
+                println!("✓ Installed plugin '{}' {}", plugin.name, plugin.version);
+            }
+            PluginCommands::Uninstall { name } => {
+                let plugin = registry.uninstall(&name)?;
+                println!("✓ Uninstalled plugin '{}' {}", plugin.name, plugin.version);
+            }
+            PluginCommands::Enable { name } => {
+                registry.set_enabled(&name, true)?;
+                println!("✓ Enabled plugin '{}'", name);
+            }
+            PluginCommands::Disable { name } => {
+                registry.set_enabled(&name, false)?;
+                println!("✓ Disabled plugin '{}'", name);
+            }
+        }
+        Ok(())
     }
     
     fn run_trace(
@@ -583,7 +1790,27 @@ impl CliRunner {
         to: Option<String>,
         validate: bool,
         matrix: bool,
+        output: Option<PathBuf>,
     ) -> Result<(), CliError> {
+        use crate::compiler::semantic::{parse_element_kind, ELEMENT_KINDS};
+        use crate::integrations::requirements_management::TraceabilityMatrix;
+
+        if output.is_some() && !matrix {
+            return Err(CliError::Config("--output writes the matrix heatmap; add --matrix".to_string()));
+        }
+        let kind = |name: Option<String>| -> Result<Option<&'static str>, CliError> {
+            name.map(|name| {
+                parse_element_kind(&name).ok_or_else(|| {
+                    CliError::Config(format!(
+                        "unknown element type '{name}' (expected one of: {})",
+                        ELEMENT_KINDS.join(", ")
+                    ))
+                })
+            })
+            .transpose()
+        };
+        let (from, to) = (kind(from)?, kind(to)?);
+        let filtered = from.is_some() || to.is_some();
         println!("Analyzing traceability in {}...", input.display());
         
         let config = crate::CompilerConfig::default();
@@ -592,6 +1819,9 @@ impl CliRunner {
         match compiler.compile_file(&input) {
             Ok(result) => {
                 if validate {
+                    for error in result.semantic_model.traceability_errors() {
+                        println!("✗ {}", error);
+                    }
                     let warnings = result.semantic_model.validate_traceability();
                     if warnings.is_empty() {
                         println!("✓ All elements properly traced");
@@ -601,9 +1831,40 @@ impl CliRunner {
                             println!("  {}", warning);
                         }
                     }
+                    print_coverage_gaps(&result.semantic_model.coverage_gaps());
                 }
                 
-                if matrix {
+                let table = if filtered {
+                    TraceabilityMatrix::between(&result.semantic_model, from, to)
+                } else {
+                    TraceabilityMatrix::from_model(&result.semantic_model)
+                };
+                if let Some(output) = &output {
+                    let title = input.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                    let html = crate::integrations::traceability_html::generate_traceability_heatmap(
+                        &table,
+                        &format!("{} traceability", title),
+                    );
+                    std::fs::write(output, html).map_err(CliError::Io)?;
+                    artifacts::record(output)?;
+                    println!("✓ Matrix heatmap written to {}", output.display());
+                } else if filtered {
+                    println!("\nTraceability Matrix ({} → {}):", table.source_type, table.target_type);
+                    println!("═══════════════════════════════════════");
+                    for row in &table.rows {
+                        let cells: Vec<_> = table.cells.iter().filter(|cell| cell.row_id == row.id).collect();
+                        if cells.is_empty() {
+                            println!("  {} → (none)", row.id);
+                        }
+                        for cell in cells {
+                            println!("  {} → {} ({:?})", row.id, cell.column_id, cell.link_type.clone().unwrap_or_default());
+                            if let Some(ref rationale) = cell.rationale {
+                                println!("    Rationale: {}", rationale);
+                            }
+                        }
+                    }
+                    println!("  {:.1}% of {} element(s) linked", table.coverage_percentage, table.source_type);
+                } else if matrix {
                     println!("\nTraceability Matrix:");
                     println!("═══════════════════════════════════════");
                     for trace in &result.semantic_model.traces {
@@ -687,14 +1948,22 @@ impl CliRunner {
     }
 
     fn run_diff(&self, old: PathBuf, new: PathBuf, json: bool) -> Result<(), CliError> {
-        let compile = |path: &PathBuf| -> Result<crate::compiler::semantic::SemanticModel, CliError> {
+        use crate::compiler::semantic_diff::{Baseline, ElementRef};
+
+        let load = |path: &PathBuf| -> Result<crate::compiler::semantic::SemanticModel, CliError> {
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let text = std::fs::read_to_string(path)?;
+                return Baseline::from_json(&text)
+                    .map(|baseline| baseline.model)
+                    .map_err(|e| CliError::Config(format!("invalid baseline {}: {e}", path.display())));
+            }
             crate::Compiler::new(crate::CompilerConfig::default())
                 .compile_file(path)
                 .map(|r| r.semantic_model)
                 .map_err(|e| CliError::Compilation(format!("{}: {e}", path.display())))
         };
-        let old_model = compile(&old)?;
-        let new_model = compile(&new)?;
+        let old_model = load(&old)?;
+        let new_model = load(&new)?;
         let report = crate::compiler::semantic_diff::diff_models(&old_model, &new_model);
 
         if json {
@@ -709,26 +1978,31 @@ impl CliRunner {
                 println!("  No semantic changes.");
                 return Ok(());
             }
+            let row = |change: &str, entry: &ElementRef| {
+                let flag = if entry.safety_relevant { "⚠" } else { " " };
+                println!(
+                    "  {flag} {change:<9} {:<14} {:<16} {}",
+                    entry.element_type, entry.id, entry.name
+                );
+            };
+            println!("\n    {:<9} {:<14} {:<16} NAME", "CHANGE", "TYPE", "ID");
             for entry in &report.added {
-                println!("  + added    {} '{}' [{}]", entry.element_type, entry.name, entry.id);
+                row("added", entry);
             }
             for entry in &report.removed {
-                println!("  - removed  {} '{}' [{}]", entry.element_type, entry.name, entry.id);
+                row("removed", entry);
             }
             for entry in &report.modified {
-                println!(
-                    "  ~ modified {} '{}' [{}]",
-                    entry.element.element_type, entry.element.name, entry.element.id
-                );
+                row("modified", &entry.element);
                 for change in &entry.changes {
-                    println!("      {}: \"{}\" -> \"{}\"", change.field, change.old, change.new);
+                    println!("{:<29}{}: \"{}\" -> \"{}\"", "", change.field, change.old, change.new);
                 }
             }
             for trace in &report.traces_added {
-                println!("  + trace    {} {} {}", trace.from, trace.trace_type, trace.to);
+                println!("    {:<9} {:<14} {} {} {}", "added", "trace", trace.from, trace.trace_type, trace.to);
             }
             for trace in &report.traces_removed {
-                println!("  - trace    {} {} {}", trace.from, trace.trace_type, trace.to);
+                println!("    {:<9} {:<14} {} {} {}", "removed", "trace", trace.from, trace.trace_type, trace.to);
             }
             println!(
                 "\n  Total: {} added, {} removed, {} modified, {} trace(s) added, {} trace(s) removed",
@@ -738,6 +2012,21 @@ impl CliRunner {
                 report.traces_added.len(),
                 report.traces_removed.len()
             );
+
+            let impact = &report.integrity_impact;
+            println!("\n  Integrity impact: {:?}", impact.severity);
+            if impact.breaks_traceability {
+                println!("    ⚠ deletes trace targets: traceability is broken");
+            }
+            if impact.affects_safety_requirements {
+                println!("    ⚠ touches safety requirements (marked ⚠ above)");
+            }
+            if !impact.orphaned_elements.is_empty() {
+                println!("    ⚠ no longer satisfied: {}", impact.orphaned_elements.join(", "));
+            }
+            if !impact.impacts_interfaces.is_empty() {
+                println!("    ⚠ interfaces affected: {}", impact.impacts_interfaces.join(", "));
+            }
         }
 
         // Like diff(1): exit 1 when there are differences, so CI can gate on it.
@@ -748,16 +2037,364 @@ impl CliRunner {
         }
     }
 
-    fn run_impact(&self, input: PathBuf, element: String) -> Result<(), CliError> {
+    fn run_review(&self, review_command: ReviewCommands) -> Result<(), CliError> {
+        use crate::collaboration::review::{model_changeset_id, ReviewStore};
+        use crate::collaboration::{Approval, CommentType, ReviewComment};
+
+        let user = |name: Option<String>| {
+            name.or_else(|| std::env::var("USER").ok())
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+
+        match review_command {
+            ReviewCommands::Create { reviewers, author, project, model } => {
+                let store = ReviewStore::open(&project);
+                let review = store.create(&model_changeset_id(&project.join(&model))?, &user(author), reviewers)?;
+                println!(
+                    "✓ Opened {} for {} (changeset {})",
+                    review.id,
+                    model.display(),
+                    review.changeset_id
+                );
+                println!("  Reviewers: {}", review.reviewers.join(", "));
+                Ok(())
+            }
+            ReviewCommands::Comment { id, message, author, element, kind, project } => {
+                let store = ReviewStore::open(&project);
+                let mut review = store.load(&id)?;
+                review.add_comment(ReviewComment {
+                    id: format!("{}-C{}", review.id, review.comments.len() + 1),
+                    author: user(author),
+                    timestamp: chrono::Utc::now(),
+                    element_id: element,
+                    comment_text: message,
+                    comment_type: match kind {
+                        ReviewCommentKind::General => CommentType::General,
+                        ReviewCommentKind::Question => CommentType::Question,
+                        ReviewCommentKind::Suggestion => CommentType::Suggestion,
+                        ReviewCommentKind::Issue => CommentType::Issue,
+                        ReviewCommentKind::Blocker => CommentType::Blocker,
+                    },
+                });
+                store.save(&review)?;
+                println!("✓ Commented on {}", review.id);
+                Ok(())
+            }
+            ReviewCommands::Approve { id, reviewer, request_changes, comment, project } => {
+                let store = ReviewStore::open(&project);
+                let mut review = store.load(&id)?;
+                review.add_approval(Approval {
+                    reviewer: user(reviewer),
+                    timestamp: chrono::Utc::now(),
+                    approved: !request_changes,
+                    comment,
+                })?;
+                store.save(&review)?;
+                println!("✓ {} is now {:?}", review.id, review.status);
+                Self::print_outstanding_reviewers(&review);
+                Ok(())
+            }
+            ReviewCommands::Status { id, project } => {
+                let store = ReviewStore::open(&project);
+                let reviews = match id {
+                    Some(id) => vec![store.load(&id)?],
+                    None => store.list()?,
+                };
+                if reviews.is_empty() {
+                    println!("No reviews in {}", project.join(".arclang").join("reviews").display());
+                }
+                for review in &reviews {
+                    println!(
+                        "{} [{:?}] changeset {} by {} ({} comment(s))",
+                        review.id,
+                        review.status,
+                        review.changeset_id,
+                        review.author,
+                        review.comments.len()
+                    );
+                    Self::print_outstanding_reviewers(review);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn print_outstanding_reviewers(review: &crate::collaboration::ReviewRequest) {
+        let outstanding = review.outstanding_reviewers();
+        if !outstanding.is_empty() {
+            println!("  Waiting on: {}", outstanding.join(", "));
+        }
+    }
+
+    fn run_merge_models(&self, inputs: Vec<PathBuf>, output: PathBuf, namespace: bool, json: bool) -> Result<(), CliError> {
+        use crate::compiler::model_union::{union_models, UnionInput};
+
+        let sources = inputs
+            .iter()
+            .map(|path| {
+                Ok(UnionInput { label: path.display().to_string(), source: std::fs::read_to_string(path)? })
+            })
+            .collect::<Result<Vec<_>, CliError>>()?;
+        let name = output.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let union = union_models(&sources, &name, namespace).map_err(|e| CliError::Compilation(e.to_string()))?;
+
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "collisions": union.collisions,
+                    "cross_model_traces": union.cross_model_traces,
+                }))
+                .map_err(|e| CliError::Compilation(e.to_string()))?
+            );
+        } else {
+            println!("Model union of {} input(s):", inputs.len());
+            for collision in &union.collisions {
+                println!("  {} '{}' declared in {}", if namespace { "~" } else { "✗" }, collision.name, collision.inputs.join(", "));
+            }
+            for cross in &union.cross_model_traces {
+                println!("  {} → {}: {} cross-model trace(s)", cross.from, cross.to, cross.count);
+            }
+            let cross_total: usize = union.cross_model_traces.iter().map(|c| c.count).sum();
+            println!("\n  {} collision(s), {} cross-model trace(s)", union.collisions.len(), cross_total);
+        }
+        if !namespace && !union.collisions.is_empty() {
+            return Err(CliError::Compilation(format!(
+                "{} name(s) or id(s) declared by several inputs; rerun with --namespace to prefix each input's ids",
+                union.collisions.len()
+            )));
+        }
+
+        let result = crate::Compiler::new(crate::CompilerConfig::default())
+            .compile_string(&union.source)
+            .map_err(|e| CliError::Compilation(format!("the union does not compile: {e}")))?;
+        std::fs::write(&output, &union.source)?;
+        artifacts::record(&output)?;
+        if !json {
+            println!(
+                "✓ {} written: {} requirement(s), {} component(s), {} trace(s)",
+                output.display(),
+                result.semantic_model.requirements.len(),
+                result.semantic_model.components.len(),
+                result.semantic_model.traces.len()
+            );
+        }
+        Ok(())
+    }
+
+    fn run_merge(
+        &self,
+        base: PathBuf,
+        ours: PathBuf,
+        theirs: PathBuf,
+        auto_resolve: bool,
+        json: bool,
+    ) -> Result<(), CliError> {
+        use crate::collaboration::semantic_merge::{merge_with, MergeOptions};
+        use crate::collaboration::{ChangeType, ConflictResolutionPolicy};
+
+        let compile = |path: &PathBuf| -> Result<crate::compiler::semantic::SemanticModel, CliError> {
+            crate::Compiler::new(crate::CompilerConfig::default())
+                .compile_file(path)
+                .map(|r| r.semantic_model)
+                .map_err(|e| CliError::Compilation(format!("{}: {e}", path.display())))
+        };
+        let base_model = compile(&base)?;
+        let ours_model = compile(&ours)?;
+        let theirs_model = compile(&theirs)?;
+        let options = MergeOptions {
+            policy: if auto_resolve {
+                ConflictResolutionPolicy::AutoResolveNonSemantic
+            } else {
+                ConflictResolutionPolicy::Manual
+            },
+            ours_label: ours.display().to_string(),
+            theirs_label: theirs.display().to_string(),
+        };
+        let result = merge_with(&base_model, &ours_model, &theirs_model, &options);
+
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&result)
+                    .map_err(|e| CliError::Compilation(e.to_string()))?
+            );
+        } else {
+            println!(
+                "Semantic merge: {} + {} (base {})",
+                ours.display(),
+                theirs.display(),
+                base.display()
+            );
+            for change in &result.merged_changes {
+                let marker = match change.change_type {
+                    ChangeType::Added => "+ added   ",
+                    ChangeType::Deleted => "- deleted ",
+                    _ => "~ modified",
+                };
+                println!("  {} {:?} [{}]", marker, change.element_type, change.element_id);
+            }
+            for resolution in &result.auto_resolved {
+                println!(
+                    "  ✓ auto-resolved {:?} [{}] ({:?}, confidence {:.1})",
+                    resolution.conflict_type, resolution.element_id, resolution.strategy, resolution.confidence
+                );
+            }
+            for conflict in &result.conflicts {
+                match &conflict.resolution {
+                    Some(resolution) => println!(
+                        "  ✓ resolved {:?} [{}] ({:?}): {}",
+                        conflict.conflict_type, conflict.element_id, resolution.resolution_strategy, resolution.rationale
+                    ),
+                    None => println!("  ! {:?}: {}", conflict.conflict_type, conflict.description),
+                }
+            }
+            for warning in &result.warnings {
+                println!("  ⚠ {}", warning);
+            }
+            println!(
+                "\n  Total: {} change(s) merged, {} conflict(s)",
+                result.merged_changes.len(),
+                result.conflicts.len()
+            );
+        }
+
+        if result.success {
+            Ok(())
+        } else {
+            Err(CliError::MergeConflicts(
+                result.conflicts.iter().filter(|c| c.resolution.is_none()).count(),
+            ))
+        }
+    }
+
+    fn run_history(&self, input: PathBuf, element: Option<String>, json: bool) -> Result<(), CliError> {
+        use crate::collaboration::git_integration::{model_history, ElementChange, GitManager};
+
+        let git = GitManager::discover(&input)?;
+        let revisions = git.file_history(&input)?;
+        let history = model_history(&revisions, element.as_deref());
+
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&history)
+                    .map_err(|e| CliError::Compilation(e.to_string()))?
+            );
+            return Ok(());
+        }
+
+        println!("Model history: {} ({} revision(s))", input.display(), history.revisions.len());
+        if history.revisions.is_empty() {
+            println!("  No commit touches this file.");
+            return Ok(());
+        }
+        println!(
+            "\n  {:<8} {:<10} {:>5} {:>6} {:>7} {:>9}  SUMMARY",
+            "COMMIT", "DATE", "REQS", "COMPS", "TRACES", "COVERAGE"
+        );
+        for entry in &history.revisions {
+            let revision = &entry.revision;
+            let date = revision.date.format("%Y-%m-%d");
+            match (&entry.metrics, &entry.note) {
+                (Some(m), _) => println!(
+                    "  {:<8} {:<10} {:>5} {:>6} {:>7} {:>8.1}%  {}",
+                    revision.commit, date, m.requirements, m.components, m.traces, m.coverage, revision.summary
+                ),
+                (None, note) => println!(
+                    "  {:<8} {:<10} {:<30}  {}",
+                    revision.commit,
+                    date,
+                    format!("({})", note.as_deref().unwrap_or_default()),
+                    revision.summary
+                ),
+            }
+        }
+
+        if let Some(element) = &history.element {
+            println!("\n  {}:", element);
+            if history.element_events.is_empty() {
+                println!("    never in this file");
+            }
+            for event in &history.element_events {
+                let marker = match event.change {
+                    ElementChange::Added => "+ added   ",
+                    ElementChange::Removed => "- removed ",
+                    ElementChange::Modified => "~ modified",
+                };
+                let revision = &event.revision;
+                println!(
+                    "    {} {} {} {}: {}",
+                    marker,
+                    revision.commit,
+                    revision.date.format("%Y-%m-%d"),
+                    revision.author,
+                    revision.summary
+                );
+                for change in &event.changes {
+                    println!("{:<16}{}: \"{}\" -> \"{}\"", "", change.field, change.old, change.new);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_blame(&self, input: PathBuf, json: bool) -> Result<(), CliError> {
+        use crate::collaboration::git_integration::{requirement_origins, GitManager, RevisionRef};
+
+        let model = crate::Compiler::new(crate::CompilerConfig::default())
+            .compile_file(&input)
+            .map(|r| r.semantic_model)
+            .map_err(|e| CliError::Compilation(format!("{}: {e}", input.display())))?;
+        let git = GitManager::discover(&input)?;
+        let origins = requirement_origins(&model, &git.file_history(&input)?);
+
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&origins)
+                    .map_err(|e| CliError::Compilation(e.to_string()))?
+            );
+            return Ok(());
+        }
+
+        let unjustified = origins.iter().filter(|o| o.unjustified()).count();
+        println!(
+            "Requirement origins: {} ({} requirement(s), {} unjustified)",
+            input.display(),
+            origins.len(),
+            unjustified
+        );
+        if origins.is_empty() {
+            return Ok(());
+        }
+        let commit = |revision: &Option<RevisionRef>| match revision {
+            Some(r) => format!("{} {} {}", r.commit, r.date.format("%Y-%m-%d"), r.author),
+            None => "(uncommitted)".to_string(),
+        };
+        println!("\n  {:<14} {:<30} {:<30} UPSTREAM", "REQUIREMENT", "INTRODUCED", "LAST MODIFIED");
+        for origin in &origins {
+            let upstream =
+                if origin.unjustified() { "⚠ unjustified".to_string() } else { origin.upstream.join(", ") };
+            println!(
+                "  {:<14} {:<30} {:<30} {}",
+                origin.id,
+                commit(&origin.introduced),
+                commit(&origin.last_modified),
+                upstream
+            );
+        }
+        Ok(())
+    }
+
+    fn run_impact(&self, input: PathBuf, element: String, json: bool) -> Result<(), CliError> {
         let config = crate::CompilerConfig::default();
         let mut compiler = crate::Compiler::new(config);
         let result = compiler
             .compile_file(&input)
             .map_err(|e| CliError::Compilation(e.to_string()))?;
 
-        let entries = result
-            .semantic_model
-            .impact_of(&element)
+        let impact = crate::collaboration::change_tracking::element_impact(&result.semantic_model, &element)
             .ok_or_else(|| {
                 CliError::Compilation(format!(
                     "element '{}' not found (or its name is ambiguous — use an id)",
@@ -765,14 +2402,33 @@ impl CliRunner {
                 ))
             })?;
 
-        println!("Impact analysis for '{}':", element);
-        if entries.is_empty() {
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&impact).map_err(|e| CliError::Compilation(e.to_string()))?
+            );
+            return Ok(());
+        }
+
+        println!("Impact analysis for '{}':", impact.element);
+        if impact.affected.is_empty() {
             println!("  No connected elements — changing it affects nothing traced.");
             return Ok(());
         }
+        println!("  Estimated integrity impact: {:?}", impact.integrity_impact.severity);
+
+        if !impact.safety_requirements.is_empty() {
+            println!("\n  ⚠ Safety requirements affected ({}):", impact.safety_requirements.len());
+            for req in &impact.safety_requirements {
+                println!("    {} '{}' [{}] at distance {}", req.safety_level, req.name, req.id, req.depth);
+            }
+        }
+        if !impact.integrity_impact.impacts_interfaces.is_empty() {
+            println!("\n  Interfaces affected: {}", impact.integrity_impact.impacts_interfaces.join(", "));
+        }
 
         let mut current_depth = 0;
-        for entry in &entries {
+        for entry in &impact.affected {
             if entry.depth != current_depth {
                 current_depth = entry.depth;
                 let label = if current_depth == 1 { "directly affected" } else { "transitively affected" };
@@ -783,7 +2439,7 @@ impl CliRunner {
                 entry.element_type, entry.name, entry.id, entry.via, entry.via_element
             );
         }
-        println!("\n  Total affected elements: {}", entries.len());
+        println!("\n  Total affected elements: {}", impact.affected.len());
         Ok(())
     }
 
@@ -791,8 +2447,34 @@ impl CliRunner {
         &self,
         input: PathBuf,
         output: PathBuf,
-        format: ExportFormat,
+        format: String,
+        direction: DiagramDirection,
     ) -> Result<(), CliError> {
+        use clap::ValueEnum;
+
+        // Plugins first, so a site can override a built-in format by name.
+        let plugin_format = format.strip_prefix("custom:").unwrap_or(&format);
+        if format.starts_with("custom:") || self.export_plugins.get(plugin_format).is_some() {
+            return self.run_plugin_export(input, output, plugin_format);
+        }
+        let format = ExportFormat::from_str(&format, true).map_err(|_| {
+            CliError::Config(format!(
+                "unknown export format '{}' (built-in: {}; plugins: {})",
+                format,
+                ExportFormat::value_variants()
+                    .iter()
+                    .filter_map(|v| v.to_possible_value())
+                    .map(|v| v.get_name().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.export_plugins
+                    .formats()
+                    .iter()
+                    .map(|f| format!("custom:{f}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
         println!("Exporting {} to {:?} format...", input.display(), format);
         
         let mut config = crate::CompilerConfig::default();
@@ -800,19 +2482,21 @@ impl CliRunner {
         // Set target based on format (Mermaid uses default, others specify target)
         config.target = match format {
             ExportFormat::JSON => "json".to_string(),
-            ExportFormat::Capella => "capella".to_string(),
-            ExportFormat::XML => "capella".to_string(),
+            ExportFormat::Capella => "json".to_string(),
+            ExportFormat::XML => "json".to_string(),
             ExportFormat::Markdown => "markdown".to_string(),
             ExportFormat::Mermaid => "json".to_string(),
             ExportFormat::PlantUML => "json".to_string(),
             ExportFormat::HTML => "json".to_string(),
-            ExportFormat::PDF => "json".to_string(),
+            ExportFormat::PDF => "markdown".to_string(),
             ExportFormat::YAML => "json".to_string(),
             ExportFormat::Terraform => "terraform".to_string(),
             ExportFormat::SysML => "json".to_string(),
             ExportFormat::Simulink => "json".to_string(),
             ExportFormat::FMI => "json".to_string(),
             ExportFormat::ReqIF => "json".to_string(),
+            ExportFormat::CSV => "json".to_string(),
+            ExportFormat::XLSX => "json".to_string(),
             ExportFormat::CHeaders => "json".to_string(),
             ExportFormat::Proto => "json".to_string(),
         };
@@ -829,30 +2513,25 @@ impl CliRunner {
                     }
                     ExportFormat::Mermaid => {
                         use crate::compiler::mermaid_generator::generate_mermaid_flowchart;
-                        generate_mermaid_flowchart(&result.semantic_model, "System Requirements", "elk")
+                        generate_mermaid_flowchart(&result.semantic_model, "System Requirements", "elk", direction.into())
                             .map_err(|e| CliError::Compilation(e.to_string()))?
                     }
                     ExportFormat::PlantUML => {
                         use crate::compiler::plantuml_generator::generate_plantuml_component;
-                        generate_plantuml_component(&result.semantic_model)
+                        generate_plantuml_component(&result.semantic_model, "System Architecture")
                             .map_err(|e| CliError::Compilation(e.to_string()))?
                     }
                     ExportFormat::HTML => {
-                        // The explorer is the renderer that actually draws the
-                        // diagram (layers, components, ports, labeled edges).
-                        // The ELK v2 pipeline's to_html only dumps layout JSON.
-                        use crate::compiler::arcviz_explorer::generate_explorer_html;
-
-                        let mut semantic_model = result.semantic_model.clone();
-                        if semantic_model.name.is_none() {
-                            // No `model` header: fall back to the file name.
-                            semantic_model.name = input
+                        // Standalone report; the interactive architecture
+                        // view is `arclang explorer`.
+                        let title = result.semantic_model.name.clone().unwrap_or_else(|| {
+                            input
                                 .file_stem()
-                                .map(|stem| stem.to_string_lossy().to_string());
-                        }
-                        let (html, _json) = generate_explorer_html(&semantic_model, &result.ast)
-                            .map_err(|e| CliError::Compilation(format!("HTML generation failed: {}", e)))?;
-                        html
+                                .map(|stem| stem.to_string_lossy().to_string())
+                                .unwrap_or_else(|| "ArcLang Model".to_string())
+                        });
+                        crate::compiler::html_report::generate_html_report(&result.semantic_model, &title)
+                            .map_err(|e| CliError::Compilation(format!("HTML generation failed: {}", e)))?
                     }
                     ExportFormat::SysML => {
                         // SysML v2 textual notation (interoperability subset)
@@ -870,6 +2549,10 @@ impl CliRunner {
                         // Requirements exchange with DOORS/Polarion/Jama
                         crate::compiler::reqif::generate_reqif(&result.semantic_model, &result.ast)
                     }
+                    ExportFormat::CSV => {
+                        // One row per requirement, for spreadsheet users
+                        crate::compiler::requirements_csv::generate_requirements_csv(&result.ast)
+                    }
                     ExportFormat::Simulink => {
                         // MATLAB script rebuilding the architecture in System Composer
                         crate::compiler::simulink_generator::generate_simulink_script(
@@ -906,10 +2589,40 @@ impl CliRunner {
                         println!("  Output directory: {}", output.display());
                         return Ok(());
                     }
+                    ExportFormat::XLSX => {
+                        // Traceability workbook for auditors; binary output,
+                        // so written here.
+                        let xlsx = crate::integrations::traceability_xlsx::generate_traceability_xlsx(&result.semantic_model)
+                            .map_err(|e| CliError::Compilation(format!("XLSX export failed: {}", e)))?;
+                        std::fs::write(&output, xlsx).map_err(CliError::Io)?;
+                        println!("✓ Export successful");
+                        println!("  Input: {}", input.display());
+                        println!("  Output: {}", output.display());
+                        println!("  Format: {:?}", format);
+                        return Ok(());
+                    }
                     ExportFormat::PDF => {
-                        return Err(CliError::NotImplemented(
-                            "PDF export not implemented in v3 — use --format html and print to PDF".to_string()
-                        ));
+                        // Rendered from the Markdown report (the `markdown`
+                        // target); binary output, so written here.
+                        let title = result.semantic_model.name.clone().unwrap_or_else(|| {
+                            input
+                                .file_stem()
+                                .map(|stem| stem.to_string_lossy().to_string())
+                                .unwrap_or_else(|| "ArcLang Model".to_string())
+                        });
+                        let pdf = crate::compiler::pdf_generator::generate_pdf(&title, &result.output);
+                        std::fs::write(&output, pdf).map_err(CliError::Io)?;
+                        println!("✓ Export successful");
+                        println!("  Input: {}", input.display());
+                        println!("  Output: {}", output.display());
+                        println!("  Format: {:?}", format);
+                        return Ok(());
+                    }
+                    ExportFormat::Capella => crate::compiler::capella_exporter::export_capella(&result.semantic_model),
+                    ExportFormat::XML => crate::compiler::xml::export_xml(&result.semantic_model),
+                    ExportFormat::YAML => {
+                        crate::compiler::yaml::export_yaml(&result.semantic_model)
+                            .map_err(|e| CliError::Compilation(e.to_string()))?
                     }
                     ExportFormat::Terraform => {
                         use crate::compiler::terraform_databricks_generator::{generate_terraform_databricks, TerraformConfig};
@@ -937,11 +2650,30 @@ impl CliRunner {
         }
     }
     
+    fn run_plugin_export(&self, input: PathBuf, output: PathBuf, format: &str) -> Result<(), CliError> {
+        let mut compiler = crate::Compiler::new(crate::CompilerConfig::default());
+        let result = compiler
+            .compile_file(&input)
+            .map_err(|e| Self::report_compile_error(&input, &e))?;
+        let content = self
+            .export_plugins
+            .export(format, &result.semantic_model)
+            .map_err(|e| CliError::Plugin(e.to_string()))?;
+        std::fs::write(&output, content)?;
+
+        println!("✓ Export successful");
+        println!("  Input: {}", input.display());
+        println!("  Output: {}", output.display());
+        println!("  Format: custom:{}", format);
+        Ok(())
+    }
+
     fn run_import(
         &self,
         input: PathBuf,
         format: ImportFormat,
         output: PathBuf,
+        columns: &[String],
     ) -> Result<(), CliError> {
         println!("Importing from {:?}: {}...", format, input.display());
         
@@ -972,7 +2704,89 @@ impl CliRunner {
                 let content = std::fs::read_to_string(&input)
                     .map_err(|e| CliError::Io(e))?;
                 
-                let arc_code = import_mermaid(&content)
+                let arc_code = import_mermaid(&content)
+                    .map_err(|e| CliError::Compilation(e.to_string()))?;
+                
+                std::fs::write(&output, arc_code)
+                    .map_err(|e| CliError::Io(e))?;
+                
+                println!("✓ Import successful");
+                println!("  Input: {}", input.display());
+                println!("  Output: {}", output.display());
+                println!("  Format: Mermaid -> ArcLang");
+                
+                Ok(())
+            }
+            ImportFormat::ReqIF => {
+                let content = std::fs::read_to_string(&input)
+                    .map_err(|e| CliError::Io(e))?;
+
+                let arc_code = crate::compiler::reqif::import_reqif(&content)
+                    .map_err(CliError::Compilation)?;
+
+                std::fs::write(&output, arc_code)
+                    .map_err(|e| CliError::Io(e))?;
+
+                println!("✓ Import successful");
+                println!("  Input: {}", input.display());
+                println!("  Output: {}", output.display());
+                println!("  Format: ReqIF -> ArcLang");
+
+                Ok(())
+            }
+            ImportFormat::DOORS => {
+                use crate::compiler::doors_importer::import_doors_stream;
+                use std::io::Write;
+
+                // Streamed: large exports never sit in memory whole
+                let reader = std::io::BufReader::new(std::fs::File::open(&input)?);
+                let mut writer = std::io::BufWriter::new(std::fs::File::create(&output)?);
+                let imported = import_doors_stream(reader, &mut writer)
+                    .and_then(|()| Ok(writer.flush()?));
+                if let Err(e) = imported {
+                    let _ = std::fs::remove_file(&output);
+                    return Err(CliError::Compilation(e.to_string()));
+                }
+
+                println!("✓ Import successful");
+                println!("  Input: {}", input.display());
+                println!("  Output: {}", output.display());
+                println!("  Format: DOORS -> ArcLang");
+
+                Ok(())
+            }
+            ImportFormat::CSV => {
+                use crate::compiler::requirements_csv::{import_requirements_csv_stream, parse_column_mapping};
+                use std::io::Write;
+
+                let mapping = parse_column_mapping(columns).map_err(CliError::Config)?;
+                let reader = std::io::BufReader::new(std::fs::File::open(&input)?);
+                let mut writer = std::io::BufWriter::new(std::fs::File::create(&output)?);
+                let imported = import_requirements_csv_stream(reader, &mapping, &mut writer)
+                    .and_then(|count| Ok(writer.flush().map(|()| count)?));
+                let count = match imported {
+                    Ok(count) => count,
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&output);
+                        return Err(CliError::Compilation(e.to_string()));
+                    }
+                };
+
+                println!("✓ Import successful");
+                println!("  Input: {}", input.display());
+                println!("  Output: {}", output.display());
+                println!("  Format: CSV -> ArcLang");
+                println!("  Requirements: {}", count);
+
+                Ok(())
+            }
+            ImportFormat::PlantUML => {
+                use crate::compiler::plantuml_importer::import_plantuml;
+                
+                let content = std::fs::read_to_string(&input)
+                    .map_err(|e| CliError::Io(e))?;
+                
+                let arc_code = import_plantuml(&content)
                     .map_err(|e| CliError::Compilation(e.to_string()))?;
                 
                 std::fs::write(&output, arc_code)
@@ -981,44 +2795,44 @@ impl CliRunner {
                 println!("✓ Import successful");
                 println!("  Input: {}", input.display());
                 println!("  Output: {}", output.display());
-                println!("  Format: Mermaid -> ArcLang");
+                println!("  Format: PlantUML -> ArcLang");
                 
                 Ok(())
             }
-            ImportFormat::ReqIF => {
-                let content = std::fs::read_to_string(&input)
-                    .map_err(|e| CliError::Io(e))?;
+            ImportFormat::YAML => {
+                let content = std::fs::read_to_string(&input)?;
 
-                let arc_code = crate::compiler::reqif::import_reqif(&content)
-                    .map_err(CliError::Compilation)?;
+                let imported = crate::compiler::yaml::import_yaml(&content)
+                    .map_err(|e| CliError::Compilation(e.to_string()))?;
 
-                std::fs::write(&output, arc_code)
-                    .map_err(|e| CliError::Io(e))?;
+                std::fs::write(&output, &imported.source)?;
 
+                for warning in &imported.warnings {
+                    println!("⚠ {}", warning);
+                }
                 println!("✓ Import successful");
                 println!("  Input: {}", input.display());
                 println!("  Output: {}", output.display());
-                println!("  Format: ReqIF -> ArcLang");
+                println!("  Format: YAML -> ArcLang");
 
                 Ok(())
             }
-            ImportFormat::PlantUML => {
-                use crate::compiler::plantuml_importer::import_plantuml;
-                
-                let content = std::fs::read_to_string(&input)
-                    .map_err(|e| CliError::Io(e))?;
-                
-                let arc_code = import_plantuml(&content)
+            ImportFormat::XML => {
+                let content = std::fs::read_to_string(&input)?;
+
+                let imported = crate::compiler::xml::import_xml(&content)
                     .map_err(|e| CliError::Compilation(e.to_string()))?;
-                
-                std::fs::write(&output, arc_code)
-                    .map_err(|e| CliError::Io(e))?;
-                
+
+                std::fs::write(&output, &imported.source)?;
+
+                for warning in &imported.warnings {
+                    println!("⚠ {}", warning);
+                }
                 println!("✓ Import successful");
                 println!("  Input: {}", input.display());
                 println!("  Output: {}", output.display());
-                println!("  Format: PlantUML -> ArcLang");
-                
+                println!("  Format: ArcLang XML -> ArcLang");
+
                 Ok(())
             }
             _ => {
@@ -1035,18 +2849,8 @@ impl CliRunner {
         fmea: bool,
         fta: bool,
         report: bool,
+        gate: Option<crate::safety::ComplianceGate>,
     ) -> Result<(), CliError> {
-        if fta {
-            return Err(CliError::NotImplemented(
-                "FTA generation is not implemented yet".to_string(),
-            ));
-        }
-        if report {
-            return Err(CliError::NotImplemented(
-                "safety report generation is not implemented yet".to_string(),
-            ));
-        }
-
         println!("Safety analysis ({:?}) of {}...", standard, input.display());
 
         let config = crate::CompilerConfig::default();
@@ -1068,24 +2872,307 @@ impl CliRunner {
             println!("  Add a 'safety_analysis' block with 'hazard' and 'fmea' entries.");
         }
 
-        if fmea {
-            if fmea_count == 0 {
-                return Err(CliError::Compilation(
-                    "cannot report FMEA: the model contains no fmea entries".to_string(),
-                ));
+        // The gate verdict is reported now and fails the command once every
+        // other requested analysis has run.
+        let gate_result = match &gate {
+            Some(gate) => check_compliance_gate(&standard, &result.semantic_model, gate),
+            None => Ok(()),
+        };
+
+        if report {
+            use crate::safety::{safety_case, SafetyAnalyzer};
+
+            let model = &result.semantic_model;
+            let config = safety_config(&standard, model);
+            let analysis = SafetyAnalyzer::new(config)
+                .analyze(model)
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            let graph = safety_case::build_gsn(&analysis);
+            let title = model.name.clone().unwrap_or_else(|| {
+                input.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+            });
+            let title = format!("{} safety case", title);
+
+            let mermaid_path = input.with_extension("gsn.mermaid");
+            let dot_path = input.with_extension("gsn.dot");
+            let mermaid = crate::compiler::mermaid_generator::generate_mermaid_gsn(&graph, &title)
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            let dot = crate::compiler::graphviz_generator::generate_graphviz_gsn(&graph, &title)
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            std::fs::write(&mermaid_path, mermaid)?;
+            std::fs::write(&dot_path, dot)?;
+            let unsupported: Vec<&str> = graph.nodes.iter().filter(|n| n.unsupported).map(|n| n.text.as_str()).collect();
+            println!(
+                "\n✓ GSN safety case written to {} and {} ({} goals, {} undeveloped)",
+                mermaid_path.display(),
+                dot_path.display(),
+                analysis.safety_goals.len(),
+                graph.nodes.iter().filter(|n| n.undeveloped).count()
+            );
+            for goal in unsupported {
+                println!("  ⚠ Unsupported goal: {}", goal);
+            }
+        }
+
+        if matches!(standard, SafetyStandard::ISO26262) {
+            use crate::safety::{iso26262, SafetyAnalyzer, SafetyGapType};
+
+            let model = &result.semantic_model;
+            let analysis = SafetyAnalyzer::new(safety_config(&standard, model))
+                .analyze(model)
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            for gap in analysis.gaps.iter().filter(|g| matches!(g.gap_type, SafetyGapType::InsufficientIntegrityLevel)) {
+                println!("⚠ {}", gap.description);
+            }
+            let mut illegal = 0;
+            for decomposition in iso26262::declared_decompositions(model) {
+                let parts: Vec<String> = decomposition
+                    .parts
+                    .iter()
+                    .map(|(element, level)| format!("{} {}", element, iso26262::decomposed_label(level, &decomposition.original)))
+                    .collect();
+                match iso26262::validate_decomposition(model, &decomposition.original, &decomposition.parts) {
+                    Ok(()) => println!(
+                        "✓ ASIL decomposition of {} ({:?}): {}",
+                        decomposition.requirement,
+                        decomposition.original,
+                        parts.join(" + ")
+                    ),
+                    Err(e) => {
+                        illegal += 1;
+                        println!("✗ ASIL decomposition of {}: {}", decomposition.requirement, e);
+                    }
+                }
+            }
+            for suggestion in iso26262::suggested_decompositions(model) {
+                let parts: Vec<String> = suggestion
+                    .parts
+                    .iter()
+                    .map(|(element, level)| format!("{} {}", element, iso26262::decomposed_label(level, &suggestion.original)))
+                    .collect();
+                println!(
+                    "  {} ({:?}) is satisfied by {} components; possible decomposition: {}",
+                    suggestion.requirement,
+                    suggestion.original,
+                    suggestion.parts.len(),
+                    parts.join(" + ")
+                );
+            }
+            let interference = iso26262::check_interference(model);
+            for finding in &interference {
+                println!("✗ {}", finding.description);
+            }
+            if illegal > 0 || !interference.is_empty() {
+                return Err(CliError::Compilation(format!(
+                    "{} illegal ASIL decomposition(s), {} freedom-from-interference finding(s)",
+                    illegal,
+                    interference.len()
+                )));
+            }
+        }
+
+        if matches!(standard, SafetyStandard::IEC61508) {
+            use crate::safety::iec61508;
+
+            let label = |level: &Option<crate::safety::IntegrityLevel>| {
+                level.as_ref().map(|l| format!("{:?}", l)).unwrap_or_else(|| "no SIL".to_string())
+            };
+            let mut findings = 0;
+            for hazard in safety_blocks.iter().flat_map(|block| &block.hazards) {
+                let Some(assessment) = iec61508::assess_hazard(hazard).map_err(CliError::Compilation)? else { continue };
+                if assessment.is_understated() {
+                    findings += 1;
+                    println!(
+                        "✗ Hazard {}: declared {} but the risk graph requires {}",
+                        assessment.hazard,
+                        label(&assessment.declared),
+                        label(&assessment.derived)
+                    );
+                } else {
+                    println!(
+                        "✓ Hazard {}: risk graph requires {} (declared {})",
+                        assessment.hazard,
+                        label(&assessment.derived),
+                        label(&assessment.declared)
+                    );
+                }
+            }
+            let goals = iec61508::model_safety_goals(&result.semantic_model);
+            for goal in iec61508::unsupported_safety_goals(&result.semantic_model, &goals) {
+                findings += 1;
+                println!(
+                    "✗ Safety goal {} ({:?}) has no supporting hazard analysis entry; name one with hazard: \"<id>\"",
+                    goal.id, goal.integrity_level
+                );
+            }
+            if findings > 0 {
+                return Err(CliError::Compilation(format!("{} IEC 61508 SIL finding(s)", findings)));
+            }
+        }
+
+        if matches!(standard, SafetyStandard::DO178C) {
+            use crate::safety::do178c::{self, ObjectiveState};
+
+            let model = &result.semantic_model;
+            let level = do178c::declared_level(model).unwrap_or_else(|| {
+                println!("  No DAL declared in the model; checking the Level C objectives");
+                crate::safety::DO178Level::LevelC
+            });
+            let checklist = do178c::generate_objective_checklist(model, level.clone());
+            let count = |state: ObjectiveState| checklist.iter().filter(|s| s.state == state).count();
+
+            println!(
+                "\nDO-178C Level {}: {} objectives, {} satisfied, {} partial, {} missing",
+                do178c::level_letter(&level),
+                checklist.len(),
+                count(ObjectiveState::Satisfied),
+                count(ObjectiveState::Partial),
+                count(ObjectiveState::Missing)
+            );
+            for status in checklist.iter().filter(|s| s.state != ObjectiveState::Satisfied) {
+                let marker = if status.state == ObjectiveState::Missing { "✗" } else { "~" };
+                let independence = if status.independence { " [independence]" } else { "" };
+                println!(
+                    "  {} {} {}{}: {}",
+                    marker, status.objective.id, status.objective.description, independence, status.evidence
+                );
+            }
+
+            if report {
+                let title = model.name.clone().unwrap_or_else(|| {
+                    input.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+                });
+                let md_path = input.with_extension("do178c.md");
+                std::fs::write(&md_path, do178c::export_checklist_to_markdown(&title, &level, &checklist))?;
+                println!("✓ DO-178C objectives written to {}", md_path.display());
             }
-            println!("\nFMEA entries:");
-            for block in safety_blocks {
-                for entry in &block.fmea {
+        }
+
+        if fmea {
+            use crate::safety::fmea;
+
+            if fmea_count > 0 {
+                println!("\nFMEA entries:");
+                for entry in safety_blocks.iter().flat_map(|block| &block.fmea) {
                     println!("  - {}", entry.name);
                     for (key, value) in &entry.attributes {
                         println!("      {}: {:?}", key, value);
                     }
                 }
             }
+
+            let fmea_config = crate::safety::FMEAConfig::default();
+            let entries = fmea::generate_fmea(&result.semantic_model, &fmea_config);
+            let flagged = entries.iter().filter(|e| e.rpn > fmea_config.rpn_threshold).count();
+
+            println!("\nGenerated FMEA: {} failure modes, {} above RPN {}", entries.len(), flagged, fmea_config.rpn_threshold);
+            for entry in &entries {
+                let marker = if entry.rpn > fmea_config.rpn_threshold { "⚠" } else { " " };
+                println!(
+                    "  {} {} {} / {}: {} (S{} O{} D{} RPN {})",
+                    marker,
+                    entry.id,
+                    entry.component,
+                    entry.function,
+                    entry.failure_mode,
+                    entry.severity,
+                    entry.occurrence,
+                    entry.detection,
+                    entry.rpn
+                );
+                for action in &entry.recommended_actions {
+                    println!("        → {}", action);
+                }
+                if let Some(revised) = entry.revised_rpn {
+                    println!("        revised RPN {} ({})", revised, entry.responsibility);
+                }
+            }
+            for action in fmea::overdue_actions(&result.semantic_model, chrono::Utc::now().date_naive()) {
+                println!(
+                    "  ⚠ Overdue action on {} ({}), due {}: {}",
+                    action.entry,
+                    action.owner.as_deref().unwrap_or("no owner"),
+                    action.due.map(|d| d.to_string()).unwrap_or_default(),
+                    action.action
+                );
+            }
+
+            let pareto = fmea::pareto(&entries);
+            let mut vital: Vec<&str> = pareto.vital().iter().take(10).map(|i| i.id.as_str()).collect();
+            if pareto.vital_few > vital.len() {
+                vital.push("...");
+            }
+            println!(
+                "\nPareto: {} of {} failure modes carry {:.0}% of the total RPN ({}): {}",
+                pareto.vital_few,
+                pareto.items.len(),
+                fmea::PARETO_CUTOFF,
+                pareto.total_rpn,
+                vital.join(", ")
+            );
+
+            if report {
+                let title = result.semantic_model.name.clone().unwrap_or_else(|| {
+                    input.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+                });
+                let csv_path = input.with_extension("fmea.csv");
+                let md_path = input.with_extension("fmea.md");
+                std::fs::write(&csv_path, fmea::export_fmea_to_csv(&entries))?;
+                let md = format!(
+                    "{}\n{}",
+                    fmea::export_fmea_to_markdown(&title, &entries, fmea_config.rpn_threshold),
+                    fmea::export_pareto_to_markdown(&pareto)
+                );
+                std::fs::write(&md_path, md)?;
+                println!("✓ FMEA worksheet written to {} and {}", csv_path.display(), md_path.display());
+            }
+        }
+
+        if fta {
+            use crate::safety::fta;
+
+            if hazard_count == 0 {
+                return Err(CliError::Compilation(
+                    "cannot run FTA: the model declares no hazards".to_string(),
+                ));
+            }
+
+            let generator = fta::FTAGenerator::new(crate::safety::FTAConfig::default());
+            let trees: Vec<_> = safety_blocks
+                .iter()
+                .flat_map(|block| &block.hazards)
+                .map(|hazard| generator.generate(hazard))
+                .collect();
+
+            for tree in &trees {
+                println!(
+                    "\nFault tree {}: {} (P(top) ≈ {:.2e})",
+                    tree.id, tree.top_event.description, tree.top_event_probability
+                );
+                for cut_set in &tree.minimal_cut_sets {
+                    let events: Vec<&str> = cut_set
+                        .events
+                        .iter()
+                        .map(|id| {
+                            tree.basic_events
+                                .iter()
+                                .find(|be| &be.id == id)
+                                .map_or(id.as_str(), |be| be.description.as_str())
+                        })
+                        .collect();
+                    println!("  order {} P={:.2e}: {}", cut_set.order, cut_set.probability, events.join(" AND "));
+                }
+            }
+
+            if report {
+                let fta_path = input.with_extension("fta.txt");
+                let text: Vec<String> = trees.iter().map(fta::generate_fta_report).collect();
+                std::fs::write(&fta_path, text.join("\n"))?;
+                println!("✓ Fault tree report written to {}", fta_path.display());
+            }
         }
 
-        Ok(())
+        gate_result
     }
     
     fn run_serve(&self, port: u16) -> Result<(), CliError> {
@@ -1107,61 +3194,231 @@ impl CliRunner {
     }
     
     fn run_lsp(&self, _stdio: bool, port: Option<u16>) -> Result<(), CliError> {
-        if port.is_some() {
-            return Err(CliError::NotImplemented(
-                "TCP mode is not implemented; use stdio (arclang lsp --stdio)".to_string(),
-            ));
-        }
         let runtime = tokio::runtime::Runtime::new()
             .map_err(|e| CliError::Compilation(format!("Failed to create runtime: {}", e)))?;
-        runtime.block_on(language_server::run_stdio());
+        match port {
+            Some(port) => runtime.block_on(language_server::run_tcp(port))?,
+            None => runtime.block_on(language_server::run_stdio()),
+        }
         Ok(())
     }
 
-    fn run_repl(&self, _project: Option<PathBuf>) -> Result<(), CliError> {
-        Err(CliError::NotImplemented("the REPL is not implemented yet".to_string()))
+    /// Interactive session, starting from a model file when one is given.
+    fn run_repl(&self, project: Option<PathBuf>) -> Result<(), CliError> {
+        let source = match project {
+            Some(path) => std::fs::read_to_string(path)?,
+            None => String::new(),
+        };
+        repl::start_repl(source)?;
+        Ok(())
     }
 
-    fn run_clean(&self, _project: PathBuf, _cache: bool) -> Result<(), CliError> {
-        Err(CliError::NotImplemented("'clean' is not implemented yet".to_string()))
+    /// Delete the outputs recorded in `.arclang/outputs` (and, with
+    /// `cache`, the incremental-compilation cache).
+    fn run_clean(&self, project: PathBuf, cache: bool) -> Result<(), CliError> {
+        // The cache's references into the global store go with it
+        let store = match cache {
+            true if project.join(".arclang").is_dir() => Self::project_global_store(&project.join(".arclang"))?,
+            _ => None,
+        };
+        let referrer = project.join(".arclang").join("cache").canonicalize().ok();
+        let summary = artifacts::clean(&project, cache)?;
+        for (file, reason) in &summary.skipped {
+            println!("  kept {} ({})", file.display(), reason);
+        }
+        println!(
+            "✓ Removed {} file(s), {} byte(s) from {}",
+            summary.files,
+            summary.bytes,
+            project.display()
+        );
+        if let Some(dir) = store {
+            let store = crate::compiler::incremental::artifact_store::ArtifactStore::open(&dir)
+                .map_err(|e| CliError::Compilation(e.to_string()))?;
+            if let Some(referrer) = referrer {
+                store.release(&referrer).map_err(|e| CliError::Compilation(e.to_string()))?;
+            }
+            let gc = store.gc().map_err(|e| CliError::Compilation(e.to_string()))?;
+            println!(
+                "✓ Removed {} unused blob(s), {} byte(s) from the global store {}",
+                gc.blobs,
+                gc.bytes,
+                dir.display()
+            );
+        }
+        Ok(())
     }
     
+    /// Overview of a model: a summary by default, the full metrics with
+    /// `metrics`, the element dependency graph with `dependencies`.
+    fn run_baseline(&self, input: PathBuf, output: PathBuf) -> Result<(), CliError> {
+        let result = crate::Compiler::new(crate::CompilerConfig::default())
+            .compile_file(&input)
+            .map_err(|e| CliError::Compilation(format!("{}: {e}", input.display())))?;
+        let baseline = crate::compiler::semantic_diff::Baseline::capture(
+            result.semantic_model,
+            input.display().to_string(),
+            chrono::Utc::now(),
+        );
+        let json = baseline
+            .to_json()
+            .map_err(|e| CliError::Compilation(e.to_string()))?;
+        std::fs::write(&output, json)?;
+        println!("✓ Baseline of {} written to {}", input.display(), output.display());
+        Ok(())
+    }
+
+    fn run_graph(&self, input: PathBuf, output: PathBuf, format: GraphFormat, cytoscape: bool) -> Result<(), CliError> {
+        use crate::compiler::graph_model::AdjacencyGraph;
+        let result = crate::Compiler::new(crate::CompilerConfig::default())
+            .compile_file(&input)
+            .map_err(|e| Self::report_compile_error(&input, &e))?;
+        let graph = AdjacencyGraph::from_model(&result.semantic_model)
+            .map_err(|e| CliError::Compilation(e.to_string()))?;
+        let json = match format {
+            GraphFormat::Json if cytoscape => graph.to_cytoscape_json(),
+            GraphFormat::Json => graph.to_json(),
+        }
+        .map_err(|e| CliError::Compilation(e.to_string()))?;
+        std::fs::write(&output, json)?;
+        artifacts::record(&output)?;
+        println!("✓ Graph written to {}", output.display());
+        println!("  Nodes: {}", graph.nodes.len());
+        println!("  Edges: {}", graph.edges.len());
+        Ok(())
+    }
+
     fn run_info(
         &self,
         input: PathBuf,
         metrics: bool,
+        json: bool,
+        ownership: bool,
         dependencies: bool,
+        dot: bool,
     ) -> Result<(), CliError> {
-        if dependencies {
-            return Err(CliError::NotImplemented(
-                "dependency analysis is not implemented yet".to_string(),
-            ));
+        if json && !metrics && !ownership {
+            return Err(CliError::Config("--json needs --metrics or --ownership".to_string()));
         }
-
-        println!("Model information: {}", input.display());
-
         let config = crate::CompilerConfig::default();
         let mut compiler = crate::Compiler::new(config);
         let result = compiler
             .compile_file(&input)
-            .map_err(|e| CliError::Compilation(e.to_string()))?;
+            .map_err(|e| Self::report_compile_error(&input, &e))?;
+        let model = &result.semantic_model;
 
-        if let Some(name) = result.ast.attributes.get("name") {
-            println!("  Name: {:?}", name);
+        if dependencies {
+            let edges = model.dependencies();
+            if dot {
+                println!("digraph dependencies {{");
+                for edge in &edges {
+                    println!(
+                        "  {:?} -> {:?} [label={:?}];",
+                        edge.from, edge.to, edge.relation
+                    );
+                }
+                println!("}}");
+            } else {
+                println!("Dependencies of {} ({}):", input.display(), edges.len());
+                for edge in &edges {
+                    println!("  {} --[{}]--> {}", edge.from, edge.relation, edge.to);
+                }
+            }
+            return Ok(());
         }
-        if let Some(version) = result.ast.attributes.get("version") {
-            println!("  Version: {:?}", version);
+
+        if ownership {
+            let report = crate::compiler::ownership::ownership_report(model);
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).map_err(|e| CliError::Compilation(e.to_string()))?
+                );
+                return Ok(());
+            }
+            println!("Ownership: {}", input.display());
+            for summary in &report {
+                let counts: Vec<String> = summary
+                    .elements
+                    .iter()
+                    .map(|(kind, ids)| format!("{} {}(s)", ids.len(), kind))
+                    .collect();
+                println!(
+                    "  {} — {} element(s) ({}), {:.1}% traced",
+                    summary.owner.as_deref().unwrap_or("(unassigned)"),
+                    summary.total,
+                    counts.join(", "),
+                    summary.coverage
+                );
+                for ids in summary.elements.values() {
+                    println!("    {}", ids.join(", "));
+                }
+            }
+            return Ok(());
         }
 
-        let model_metrics = result.semantic_model.compute_metrics();
-        println!("  Total elements: {}", model_metrics.total_elements);
-        println!("  Requirements: {}", model_metrics.requirements_count);
-        println!("  Components: {}", model_metrics.components_count);
-        println!("  Functions: {}", result.semantic_model.functions.len());
-        println!("  Traces: {}", model_metrics.traces_count);
+        if json {
+            let stats = crate::compiler::stats::ModelStats::collect(model, chrono::Utc::now());
+            println!("{}", stats.to_json());
+            return Ok(());
+        }
 
+        let model_metrics = model.compute_metrics();
         if metrics {
+            println!("Model metrics: {}", input.display());
+            println!("  Total elements: {}", model_metrics.total_elements);
+            println!("  Requirements: {}", model_metrics.requirements_count);
+            println!("  Components: {}", model_metrics.components_count);
+            println!("  Functions: {}", model_metrics.functions_count);
+            println!("  Traces: {}", model_metrics.traces_count);
             println!("  Traceability coverage: {:.1}%", model_metrics.traceability_coverage);
+            println!("  Components per level:");
+            for (level, count) in &model_metrics.components_by_level {
+                println!("    {}: {}", level, count);
+            }
+            return Ok(());
+        }
+
+        let name = model
+            .name
+            .clone()
+            .or_else(|| input.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        println!("Model: {}", name);
+        if let Some(version) = result.ast.attributes.get("version").and_then(|v| v.as_string()) {
+            println!("  Version: {}", version);
+        }
+        let ast = &result.ast;
+        let layers = [
+            ("Operational analysis", !ast.operational_analysis.is_empty()),
+            ("System analysis", !ast.system_analysis.is_empty()),
+            ("Logical architecture", !ast.logical_architecture.is_empty()),
+            ("Physical architecture", !ast.physical_architecture.is_empty()),
+            ("EPBS", !ast.epbs.is_empty()),
+        ];
+        for (layer, present) in layers {
+            println!("  {} {}", if present { "✓" } else { "·" }, layer);
+        }
+        println!(
+            "  {} elements: {} requirements, {} components, {} functions, {} traces",
+            model.all_elements.len(),
+            model_metrics.requirements_count,
+            model_metrics.components_count,
+            model_metrics.functions_count,
+            model_metrics.traces_count
+        );
+
+        let warnings: Vec<String> = result
+            .warnings
+            .iter()
+            .cloned()
+            .chain(model.validate_traceability())
+            .collect();
+        if !warnings.is_empty() {
+            println!("  ⚠ {} warning(s):", warnings.len());
+            for warning in &warnings {
+                println!("    {}", warning);
+            }
         }
 
         Ok(())
@@ -1203,6 +3460,8 @@ impl CliRunner {
                     .map_err(|e| CliError::Io(e))?;
                 std::fs::write(&output_json, &json)
                     .map_err(|e| CliError::Io(e))?;
+                artifacts::record(&output_html)?;
+                artifacts::record(&output_json)?;
                 
                 println!("✓ Architecture Explorer generated successfully");
                 println!("  Input: {}", input.display());
@@ -1235,12 +3494,16 @@ impl CliRunner {
         }
     }
     
+    #[allow(clippy::too_many_arguments)]
     fn run_diagram(
         &self,
         input: PathBuf,
         output: PathBuf,
         format: DiagramFormat,
         title: String,
+        direction: DiagramDirection,
+        root: Option<String>,
+        depth: u32,
         open: bool,
     ) -> Result<(), CliError> {
         println!("🎨 Generating {:?} diagram from {}...", format, input.display());
@@ -1249,15 +3512,31 @@ impl CliRunner {
         let mut compiler = crate::Compiler::new(config);
         
         match compiler.compile_file(&input) {
-            Ok(result) => {
+            Ok(mut result) => {
+                if let Some(root) = &root {
+                    let not_found = || CliError::Compilation(format!(
+                        "element '{}' not found (or its name is ambiguous — use an id)",
+                        root
+                    ));
+                    // The Capella diagrams draw the AST, the others the semantic model
+                    result.ast = result.semantic_model.scoped_ast(&result.ast, root, depth).ok_or_else(not_found)?;
+                    result.semantic_model = result.semantic_model.scoped(root, depth).ok_or_else(not_found)?;
+                    println!(
+                        "  Scope: {} element(s) within {} hop(s) of '{}'",
+                        result.semantic_model.all_elements.len(),
+                        depth,
+                        root
+                    );
+                }
                 match format {
                     DiagramFormat::Mermaid => {
                         use crate::compiler::mermaid_generator::generate_mermaid_flowchart;
-                        let diagram = generate_mermaid_flowchart(&result.semantic_model, &title, "elk")
+                        let diagram = generate_mermaid_flowchart(&result.semantic_model, &title, "elk", direction.into())
                             .map_err(|e| CliError::Compilation(e.to_string()))?;
                         
                         std::fs::write(&output, &diagram)
                             .map_err(|e| CliError::Io(e))?;
+                        artifacts::record(&output)?;
                         
                         println!("✓ Mermaid diagram generated");
                         println!("  Output: {}", output.display());
@@ -1267,6 +3546,71 @@ impl CliRunner {
                         }
                     }
                     
+                    DiagramFormat::PlantUML => {
+                        use crate::compiler::plantuml_generator::{generate_plantuml_component, plantuml_html_page};
+                        let diagram = generate_plantuml_component(&result.semantic_model, &title)
+                            .map_err(|e| CliError::Compilation(e.to_string()))?;
+
+                        std::fs::write(&output, &diagram)?;
+                        artifacts::record(&output)?;
+
+                        println!("✓ PlantUML diagram generated");
+                        println!("  Output: {}", output.display());
+
+                        if open {
+                            let html_file = output.with_extension("html");
+                            std::fs::write(&html_file, plantuml_html_page(&diagram, &title))?;
+                            artifacts::record(&html_file)?;
+                            open_in_browser(&html_file)?;
+                        }
+                    }
+
+                    DiagramFormat::Graphviz => {
+                        use crate::compiler::graphviz_generator::generate_graphviz_digraph;
+                        let dot = generate_graphviz_digraph(&result.semantic_model, &title)
+                            .map_err(|e| CliError::Compilation(e.to_string()))?;
+
+                        std::fs::write(&output, &dot)?;
+                        artifacts::record(&output)?;
+
+                        println!("✓ GraphViz diagram generated");
+                        println!("  Output: {}", output.display());
+                    }
+
+                    DiagramFormat::SVG => {
+                        use crate::compiler::graphviz_generator::generate_graphviz_digraph;
+                        let dot = generate_graphviz_digraph(&result.semantic_model, &title)
+                            .map_err(|e| CliError::Compilation(e.to_string()))?;
+                        let svg = render_dot_svg(&dot)?;
+
+                        std::fs::write(&output, &svg)?;
+                        artifacts::record(&output)?;
+
+                        println!("✓ SVG diagram generated (GraphViz)");
+                        println!("  Output: {}", output.display());
+
+                        if open {
+                            opener::open(&output).map_err(|e| CliError::Io(std::io::Error::other(e)))?;
+                        }
+                    }
+
+                    DiagramFormat::FunctionalChain => {
+                        use crate::compiler::mermaid_generator::generate_mermaid_functional_chains;
+                        let diagram = generate_mermaid_functional_chains(&result.semantic_model, &title)
+                            .map_err(|e| CliError::Compilation(e.to_string()))?;
+
+                        std::fs::write(&output, &diagram)?;
+                        artifacts::record(&output)?;
+
+                        println!("✓ Functional chain diagram generated (Mermaid)");
+                        println!("  Chains: {}", result.semantic_model.functional_chains.len());
+                        println!("  Output: {}", output.display());
+
+                        if open {
+                            self.open_mermaid_diagram(&output)?;
+                        }
+                    }
+
                     DiagramFormat::All => {
                         self.generate_all_capella_diagrams(&input, &result, &output)?;
                     }
@@ -1428,30 +3772,7 @@ impl CliRunner {
     <script>
         mermaid.initialize({{ startOnLoad: true, theme: 'default' }});
     </script>
-    <style>
-        body {{
-            margin: 0;
-            padding: 20px;
-            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif;
-            background: #f5f5f5;
-        }}
-        .container {{
-            max-width: 100%;
-            margin: 0 auto;
-            background: white;
-            padding: 20px;
-            border-radius: 8px;
-            box-shadow: 0 2px 8px rgba(0,0,0,0.1);
-        }}
-        h1 {{
-            color: #333;
-            margin-top: 0;
-        }}
-        .mermaid {{
-            display: flex;
-            justify-content: center;
-        }}
-    </style>
+    <style>{}    </style>
 </head>
 <body>
     <div class="container">
@@ -1461,32 +3782,14 @@ impl CliRunner {
         </div>
     </div>
 </body>
-</html>"#, content);
+</html>"#, crate::compiler::html_report::MERMAID_PAGE_CSS, content);
         
         let html_file = mermaid_file.with_extension("html");
         std::fs::write(&html_file, html)
             .map_err(|e| CliError::Io(e))?;
+        artifacts::record(&html_file)?;
         
-        // Open in browser
-        #[cfg(target_os = "macos")]
-        std::process::Command::new("open")
-            .arg(&html_file)
-            .spawn()
-            .map_err(|e| CliError::Io(e))?;
-        
-        #[cfg(target_os = "linux")]
-        std::process::Command::new("xdg-open")
-            .arg(&html_file)
-            .spawn()
-            .map_err(|e| CliError::Io(e))?;
-        
-        #[cfg(target_os = "windows")]
-        std::process::Command::new("cmd")
-            .args(&["/C", "start", "", html_file.to_str().unwrap()])
-            .spawn()
-            .map_err(|e| CliError::Io(e))?;
-        
-        Ok(())
+        open_in_browser(&html_file)
     }
     
     fn log(&self, message: &str) {
@@ -1496,6 +3799,189 @@ impl CliRunner {
     }
 }
 
+/// The analysis configuration for `standard`, at the most demanding
+/// integrity level the model's requirements declare.
+fn safety_config(standard: &SafetyStandard, model: &crate::compiler::semantic::SemanticModel) -> crate::safety::SafetyConfig {
+    use crate::safety::{self, IntegrityLevel, SafetyConfig};
+
+    // The most demanding level the requirements declare, in the
+    // standard's own scale (listed from least to most demanding).
+    let declared = |scale: &[IntegrityLevel]| {
+        model
+            .requirements
+            .iter()
+            .filter_map(|r| r.safety_level.as_deref().and_then(IntegrityLevel::parse))
+            .filter_map(|level| scale.iter().position(|l| *l == level))
+            .max()
+            .map(|i| scale[i].clone())
+            .unwrap_or_else(|| scale[0].clone())
+    };
+    match standard {
+        SafetyStandard::ISO26262 => SafetyConfig::new(
+            safety::SafetyStandard::ISO26262 { edition: "2018".to_string() },
+            declared(&[
+                IntegrityLevel::ASIL_QM,
+                IntegrityLevel::ASIL_A,
+                IntegrityLevel::ASIL_B,
+                IntegrityLevel::ASIL_C,
+                IntegrityLevel::ASIL_D,
+            ]),
+        ),
+        SafetyStandard::DO178C => SafetyConfig::new(
+            safety::SafetyStandard::DO178C {
+                level: safety::do178c::declared_level(model).unwrap_or(safety::DO178Level::LevelC),
+            },
+            declared(&[
+                IntegrityLevel::DAL_E,
+                IntegrityLevel::DAL_D,
+                IntegrityLevel::DAL_C,
+                IntegrityLevel::DAL_B,
+                IntegrityLevel::DAL_A,
+            ]),
+        ),
+        SafetyStandard::IEC61508 => SafetyConfig::new(
+            safety::SafetyStandard::IEC61508 { edition: "2010".to_string() },
+            declared(&[
+                IntegrityLevel::SIL_1,
+                IntegrityLevel::SIL_2,
+                IntegrityLevel::SIL_3,
+                IntegrityLevel::SIL_4,
+            ]),
+        ),
+    }
+}
+
+/// `safety --gate` settings from the command line.
+fn compliance_gate(min_percentage: f64, blocking: &[String]) -> Result<crate::safety::ComplianceGate, CliError> {
+    use crate::safety::{ComplianceGate, ComplianceSeverity};
+
+    let blocking = if blocking.is_empty() {
+        ComplianceSeverity::ALL.to_vec()
+    } else if blocking.len() == 1 && blocking[0].eq_ignore_ascii_case("none") {
+        Vec::new()
+    } else {
+        blocking
+            .iter()
+            .map(|name| {
+                ComplianceSeverity::parse(name).ok_or_else(|| {
+                    CliError::Config(format!(
+                        "unknown severity '{}' for --blocking (expected critical, major, minor, observation or none)",
+                        name
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?
+    };
+    Ok(ComplianceGate { min_percentage, blocking })
+}
+
+/// Run the standard's compliance check, print its non-compliances and fail
+/// when `gate` does not pass.
+fn check_compliance_gate(
+    standard: &SafetyStandard,
+    model: &crate::compiler::semantic::SemanticModel,
+    gate: &crate::safety::ComplianceGate,
+) -> Result<(), CliError> {
+    use crate::safety::{do178c, iec61508, iso26262};
+
+    let config = safety_config(standard, model);
+    let status = match standard {
+        SafetyStandard::ISO26262 => iso26262::check_compliance(model, &config),
+        SafetyStandard::DO178C => do178c::check_compliance(model, &config),
+        SafetyStandard::IEC61508 => iec61508::check_compliance(model, &config),
+    };
+    let blockers = gate.blockers(&status);
+
+    println!(
+        "\nCompliance gate ({:?}): {:.1}% compliant (minimum {:.1}%), {} non-compliance(s), {} blocking",
+        standard,
+        status.compliance_percentage,
+        gate.min_percentage,
+        status.non_compliances.len(),
+        blockers.len()
+    );
+    for nc in &status.non_compliances {
+        let marker = if blockers.iter().any(|b| std::ptr::eq(*b, nc)) { "✗" } else { "⚠" };
+        println!("  {} [{:?}] {}: {}", marker, nc.severity, nc.clause, nc.description);
+    }
+
+    if gate.passes(&status) {
+        println!("COMPLIANCE GATE: PASS ✓");
+        Ok(())
+    } else {
+        println!("COMPLIANCE GATE: FAIL ✗");
+        Err(CliError::Compilation(format!(
+            "{:?} compliance gate failed: {:.1}% compliant (minimum {:.1}%), {} blocking non-compliance(s)",
+            standard,
+            status.compliance_percentage,
+            gate.min_percentage,
+            blockers.len()
+        )))
+    }
+}
+
+/// Open a generated page in the default browser.
+fn open_in_browser(html_file: &Path) -> Result<(), CliError> {
+    opener::open(html_file).map_err(|e| CliError::Io(std::io::Error::other(e)))
+}
+
+/// Coverage gaps of `trace --validate`: a count per gap type, then each gap,
+/// most severe first.
+fn print_coverage_gaps(gaps: &[crate::compiler::semantic::CoverageGap]) {
+    use crate::compiler::semantic::GapType;
+
+    if gaps.is_empty() {
+        println!("✓ No coverage gaps");
+        return;
+    }
+    println!("\nCoverage gaps: {}", gaps.len());
+    for gap_type in [GapType::NoTraceToDesign, GapType::NoVerification, GapType::NoRequirement] {
+        let count = gaps.iter().filter(|g| g.gap_type == gap_type).count();
+        if count > 0 {
+            println!("  {}: {}", gap_type, count);
+        }
+    }
+    let mut sorted: Vec<_> = gaps.iter().collect();
+    sorted.sort_by_key(|g| g.severity);
+    for gap in sorted {
+        println!("  [{}] {}", gap.severity, gap.description);
+    }
+}
+
+/// Pipe DOT source through GraphViz's `dot -Tsvg`.
+fn render_dot_svg(dot: &str) -> Result<Vec<u8>, CliError> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => CliError::Config(
+                "SVG output needs the GraphViz `dot` binary on PATH (install graphviz, \
+                 or use --format graphviz and render the .dot file elsewhere)"
+                    .to_string(),
+            ),
+            _ => CliError::Io(e),
+        })?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(dot.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(CliError::Compilation(format!(
+            "dot -Tsvg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CliError {
     #[error("IO error: {0}")]
@@ -1519,4 +4005,18 @@ pub enum CliError {
     /// Not a failure: the two models differ (diff(1) convention, exit 1).
     #[error("semantic differences found")]
     DiffFound,
+
+    #[error(transparent)]
+    Collaboration(#[from] crate::collaboration::CollaborationError),
+
+    #[error(transparent)]
+    Repl(#[from] repl::ReplError),
+
+    /// `merge`: overlapping edits that need a manual decision.
+    #[error("{0} merge conflict(s)")]
+    MergeConflicts(usize),
+
+    /// `format --check`: the file differs from its canonical layout.
+    #[error("{} is not formatted", .0.display())]
+    NotFormatted(PathBuf),
 }
@@ -0,0 +1,297 @@
+//! Installed plugins: `.arclang/plugins/<name>.toml` is a plugin's manifest
+//! and `.arclang/plugins/<name>/` holds its files.
+//!
+//! ```toml
+//! name = "fmea-report"
+//! version = "0.2.0"
+//! description = "FMEA worksheet export"
+//! capabilities = ["export"]
+//! enabled = true
+//! ```
+//!
+//! Plugins are installed from a local directory, or a `.tar` / `.tar.gz`
+//! archive, with a `plugin.toml` manifest at its root; fetching from a
+//! registry is not supported yet.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::CliError;
+
+/// The manifest a plugin ships with.
+pub const MANIFEST_FILE: &str = "plugin.toml";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    /// What the plugin contributes (`export`, `lint`, ...).
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl PluginManifest {
+    fn read(path: &Path) -> Result<Self, CliError> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| CliError::Plugin(format!("cannot read {}: {e}", path.display())))?;
+        let manifest: Self = toml::from_str(&text)
+            .map_err(|e| CliError::Plugin(format!("invalid {}: {e}", path.display())))?;
+        check_name(&manifest.name)?;
+        Ok(manifest)
+    }
+}
+
+/// Plugin names become file names: keep them to letters, digits, `-` and `_`.
+fn check_name(name: &str) -> Result<(), CliError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(CliError::Plugin(format!(
+            "invalid plugin name '{name}' (letters, digits, '-' and '_' only)"
+        )));
+    }
+    Ok(())
+}
+
+pub struct PluginRegistry {
+    dir: PathBuf,
+}
+
+impl PluginRegistry {
+    /// The registry of `project`; it need not exist yet.
+    pub fn open(project: &Path) -> Self {
+        Self { dir: project.join(".arclang").join("plugins") }
+    }
+
+    fn manifest_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.toml"))
+    }
+
+    /// Installed plugins, by name.
+    pub fn list(&self) -> Result<Vec<PluginManifest>, CliError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut plugins = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|e| e == "toml") {
+                plugins.push(PluginManifest::read(&path)?);
+            }
+        }
+        plugins.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(plugins)
+    }
+
+    pub fn get(&self, name: &str) -> Result<PluginManifest, CliError> {
+        check_name(name)?;
+        let path = self.manifest_path(name);
+        if !path.is_file() {
+            return Err(CliError::Plugin(format!("no plugin named '{name}' is installed")));
+        }
+        PluginManifest::read(&path)
+    }
+
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> Result<PluginManifest, CliError> {
+        let mut manifest = self.get(name)?;
+        manifest.enabled = enabled;
+        self.write(&manifest)?;
+        Ok(manifest)
+    }
+
+    /// Install the plugin at `source` (directory or archive), enabled. With
+    /// `version`, the manifest must declare exactly that version.
+    pub fn install(&self, source: &Path, version: Option<&str>) -> Result<PluginManifest, CliError> {
+        if !source.exists() {
+            return Err(CliError::Plugin(format!(
+                "{} is not a local plugin directory or archive (installing from a registry is not supported yet)",
+                source.display()
+            )));
+        }
+        fs::create_dir_all(&self.dir)?;
+        let staging = self.dir.join(".incoming");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        let result = self.install_from(source, &staging, version);
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        result
+    }
+
+    fn install_from(&self, source: &Path, staging: &Path, version: Option<&str>) -> Result<PluginManifest, CliError> {
+        let root = if source.is_dir() {
+            source.to_path_buf()
+        } else {
+            unpack(source, staging)?;
+            manifest_root(staging).ok_or_else(|| {
+                CliError::Plugin(format!("{} has no {MANIFEST_FILE} at its root", source.display()))
+            })?
+        };
+        let manifest_file = root.join(MANIFEST_FILE);
+        if !manifest_file.is_file() {
+            return Err(CliError::Plugin(format!("{} has no {MANIFEST_FILE}", source.display())));
+        }
+        let mut manifest = PluginManifest::read(&manifest_file)?;
+        if let Some(version) = version.filter(|v| *v != manifest.version) {
+            return Err(CliError::Plugin(format!(
+                "{} provides {} {}, not {version}",
+                source.display(),
+                manifest.name,
+                manifest.version
+            )));
+        }
+        if self.manifest_path(&manifest.name).exists() {
+            return Err(CliError::Plugin(format!(
+                "plugin '{}' is already installed (uninstall it first)",
+                manifest.name
+            )));
+        }
+
+        copy_tree(&root, &self.dir.join(&manifest.name))?;
+        manifest.enabled = true;
+        self.write(&manifest)?;
+        Ok(manifest)
+    }
+
+    /// Remove a plugin's manifest and files.
+    pub fn uninstall(&self, name: &str) -> Result<PluginManifest, CliError> {
+        let manifest = self.get(name)?;
+        let files = self.dir.join(name);
+        if files.is_dir() {
+            fs::remove_dir_all(&files)?;
+        }
+        fs::remove_file(self.manifest_path(name))?;
+        Ok(manifest)
+    }
+
+    fn write(&self, manifest: &PluginManifest) -> Result<(), CliError> {
+        let text = toml::to_string_pretty(manifest).map_err(|e| CliError::Plugin(e.to_string()))?;
+        fs::write(self.manifest_path(&manifest.name), text)?;
+        Ok(())
+    }
+}
+
+/// Extract a `.tar`, `.tar.gz` or `.tgz` archive into `dir`.
+fn unpack(archive: &Path, dir: &Path) -> Result<(), CliError> {
+    let name = archive.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let file = fs::File::open(archive)?;
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(dir)?;
+    } else if name.ends_with(".tar") {
+        tar::Archive::new(file).unpack(dir)?;
+    } else {
+        return Err(CliError::Plugin(format!(
+            "{} is not a plugin archive (expected .tar, .tar.gz or .tgz)",
+            archive.display()
+        )));
+    }
+    Ok(())
+}
+
+/// The directory holding the manifest: the archive root, or its single
+/// top-level directory.
+fn manifest_root(dir: &Path) -> Option<PathBuf> {
+    if dir.join(MANIFEST_FILE).is_file() {
+        return Some(dir.to_path_buf());
+    }
+    let mut entries = fs::read_dir(dir).ok()?.filter_map(Result::ok);
+    match (entries.next(), entries.next()) {
+        (Some(only), None) if only.path().join(MANIFEST_FILE).is_file() => Some(only.path()),
+        _ => None,
+    }
+}
+
+fn copy_tree(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_tree(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "name = \"fmea-report\"\nversion = \"0.2.0\"\ndescription = \"FMEA worksheet export\"\ncapabilities = [\"export\"]\n";
+
+    fn plugin_source() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(MANIFEST_FILE), MANIFEST).unwrap();
+        fs::create_dir(dir.path().join("templates")).unwrap();
+        fs::write(dir.path().join("templates").join("fmea.html"), "<table></table>").unwrap();
+        dir
+    }
+
+    #[test]
+    fn install_list_toggle_and_uninstall_a_directory_plugin() {
+        let project = tempfile::tempdir().unwrap();
+        let registry = PluginRegistry::open(project.path());
+        assert!(registry.list().unwrap().is_empty());
+        let source = plugin_source();
+
+        let installed = registry.install(source.path(), Some("0.2.0")).unwrap();
+
+        assert_eq!(installed.capabilities, vec!["export"]);
+        assert!(project.path().join(".arclang/plugins/fmea-report/templates/fmea.html").is_file());
+        assert_eq!(registry.list().unwrap(), vec![installed.clone()]);
+        assert!(!registry.set_enabled("fmea-report", false).unwrap().enabled);
+        assert!(!registry.get("fmea-report").unwrap().enabled);
+        assert!(matches!(registry.install(source.path(), None), Err(CliError::Plugin(_))));
+
+        registry.uninstall("fmea-report").unwrap();
+        assert!(registry.list().unwrap().is_empty());
+        assert!(!project.path().join(".arclang/plugins/fmea-report").exists());
+    }
+
+    #[test]
+    fn installs_from_a_gzipped_tarball_with_a_top_level_directory() {
+        let project = tempfile::tempdir().unwrap();
+        let source = plugin_source();
+        let archive = project.path().join("fmea-report-0.2.0.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(fs::File::create(&archive).unwrap(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_dir_all("fmea-report-0.2.0", source.path()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let registry = PluginRegistry::open(project.path());
+        let installed = registry.install(&archive, None).unwrap();
+
+        assert_eq!(installed.name, "fmea-report");
+        assert!(project.path().join(".arclang/plugins/fmea-report").join(MANIFEST_FILE).is_file());
+        assert!(!project.path().join(".arclang/plugins/.incoming").exists());
+    }
+
+    #[test]
+    fn unknown_plugins_and_versions_are_reported() {
+        let project = tempfile::tempdir().unwrap();
+        let registry = PluginRegistry::open(project.path());
+
+        assert!(matches!(registry.get("missing"), Err(CliError::Plugin(_))));
+        assert!(matches!(registry.set_enabled("missing", true), Err(CliError::Plugin(_))));
+        assert!(matches!(registry.get("../escape"), Err(CliError::Plugin(_))));
+        assert!(matches!(registry.install(Path::new("fmea-report"), None), Err(CliError::Plugin(_))));
+        let source = plugin_source();
+        assert!(matches!(registry.install(source.path(), Some("1.0.0")), Err(CliError::Plugin(_))));
+        assert!(registry.list().unwrap().is_empty());
+    }
+}
@@ -1,5 +1,20 @@
+//! Interactive ArcLang session: statements typed at the prompt are added to
+//! an in-memory model, which is recompiled after each one. Tab completes
+//! keywords and the ids of the model built so far.
+
 use std::io::{self, Write};
-use std::collections::HashMap;
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::compiler::completion::{complete, CompletionKind};
+use crate::compiler::semantic::SemanticModel;
+use crate::compiler::{Compiler, CompilerConfig};
 
 pub struct Repl {
     context: ReplContext,
@@ -7,120 +22,141 @@ pub struct Repl {
 }
 
 struct ReplContext {
-    variables: HashMap<String, String>,
+    /// Every statement accepted so far, in order.
+    source: String,
+    /// The model compiled from `source`.
+    model: Option<SemanticModel>,
+    /// Lines of a statement whose braces are not closed yet.
+    pending: String,
     history: Vec<String>,
-    current_level: ArcadiaLevel,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum ArcadiaLevel {
-    OperationalAnalysis,
-    SystemAnalysis,
-    LogicalArchitecture,
-    PhysicalArchitecture,
-    EPBS,
 }
 
 impl Repl {
-    pub fn new() -> Self {
-        Self {
+    /// A session starting from `source` (empty, or a model file's text).
+    pub fn new(source: String) -> Result<Self, ReplError> {
+        let mut repl = Self {
             context: ReplContext {
-                variables: HashMap::new(),
+                source: String::new(),
+                model: None,
+                pending: String::new(),
                 history: Vec::new(),
-                current_level: ArcadiaLevel::OperationalAnalysis,
             },
             running: false,
+        };
+        if !source.trim().is_empty() {
+            repl.evaluate(&source)?;
         }
+        Ok(repl)
+    }
+
+    pub fn model(&self) -> Option<&SemanticModel> {
+        self.context.model.as_ref()
     }
-    
+
     pub fn run(&mut self) -> Result<(), ReplError> {
         self.running = true;
-        
-        println!("ArcLang REPL v1.0.0");
+        let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+        editor.set_helper(Some(ReplHelper { model: self.context.model.clone() }));
+
+        println!("ArcLang REPL v{}", env!("CARGO_PKG_VERSION"));
         println!("Type 'help' for available commands, 'exit' to quit");
         println!();
-        
+
         while self.running {
-            print!("arclang> ");
-            io::stdout().flush()?;
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            
-            let input = input.trim();
-            
-            if input.is_empty() {
+            let prompt = if self.context.pending.is_empty() { "arclang> " } else { "     ... " };
+            let line = match editor.readline(prompt) {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => {
+                    self.context.pending.clear();
+                    continue;
+                }
+                Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(e.into()),
+            };
+            if line.trim().is_empty() && self.context.pending.is_empty() {
                 continue;
             }
-            
-            self.context.history.push(input.to_string());
-            
-            if let Err(e) = self.execute_command(input) {
+            let _ = editor.add_history_entry(line.as_str());
+
+            if let Err(e) = self.execute_line(&line) {
                 eprintln!("Error: {}", e);
             }
+            if let Some(helper) = editor.helper_mut() {
+                helper.model = self.context.model.clone();
+            }
         }
-        
+
         Ok(())
     }
-    
-    fn execute_command(&mut self, input: &str) -> Result<(), ReplError> {
-        let parts: Vec<&str> = input.split_whitespace().collect();
-        
-        if parts.is_empty() {
-            return Ok(());
+
+    /// Run a command, or feed a line of ArcLang source; a statement is
+    /// evaluated once its braces are balanced.
+    pub fn execute_line(&mut self, line: &str) -> Result<(), ReplError> {
+        if self.context.pending.is_empty() {
+            let input = line.trim();
+            self.context.history.push(input.to_string());
+            if let Some(result) = self.execute_command(input) {
+                return result;
+            }
         }
-        
-        match parts[0] {
-            "help" => self.show_help(),
-            "exit" | "quit" => self.exit(),
-            "clear" => self.clear(),
-            "history" => self.show_history(),
-            "level" => self.change_level(&parts[1..]),
-            "show" => self.show(&parts[1..]),
-            "list" => self.list(&parts[1..]),
-            "define" => self.define(&parts[1..]),
-            "trace" => self.trace(&parts[1..]),
-            "validate" => self.validate(),
-            "export" => self.export(&parts[1..]),
-            _ => self.evaluate(input),
+
+        self.context.pending.push_str(line);
+        self.context.pending.push('\n');
+        if open_braces(&self.context.pending) > 0 {
+            return Ok(());
         }
+        let statement = std::mem::take(&mut self.context.pending);
+        self.evaluate(&statement)
+    }
+
+    /// `None` when the input is not a command (it is source then).
+    fn execute_command(&mut self, input: &str) -> Option<Result<(), ReplError>> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+
+        let result = match parts.as_slice() {
+            ["help"] => self.show_help(),
+            ["exit"] | ["quit"] => self.exit(),
+            ["clear"] => self.clear(),
+            ["history"] => self.show_history(),
+            ["source"] => self.show_source(),
+            ["show", id] => self.show(id),
+            ["list", kind] => self.list(kind),
+            ["validate"] => self.validate(),
+            _ => return None,
+        };
+        Some(result)
     }
-    
+
     fn show_help(&self) -> Result<(), ReplError> {
+        println!("Type ArcLang statements to add them to the model, e.g.");
+        println!("  requirements system {{ req \"REQ-001\" \"Brake\" {{ description: \"...\" }} }}");
+        println!("  trace \"LC-001\" satisfies \"REQ-001\" {{}}");
+        println!("Tab completes keywords and element ids.");
+        println!();
         println!("Available commands:");
         println!("  help              - Show this help message");
         println!("  exit, quit        - Exit the REPL");
         println!("  clear             - Clear the screen");
         println!("  history           - Show command history");
-        println!("  level <name>      - Change Arcadia level");
-        println!("  show <element>    - Show element details");
-        println!("  list <type>       - List elements of type");
-        println!("  define <element>  - Define new element");
-        println!("  trace <from> <to> - Show trace between elements");
-        println!("  validate          - Validate current model");
-        println!("  export <format>   - Export model to format");
-        println!();
-        println!("Arcadia levels:");
-        println!("  oa  - Operational Analysis");
-        println!("  sa  - System Analysis");
-        println!("  la  - Logical Architecture");
-        println!("  pa  - Physical Architecture");
-        println!("  epbs - End Product Breakdown Structure");
+        println!("  source            - Print the statements entered so far");
+        println!("  show <id>         - Show element details");
+        println!("  list <type>       - List requirements, components or traces");
+        println!("  validate          - Report traceability findings");
         Ok(())
     }
-    
+
     fn exit(&mut self) -> Result<(), ReplError> {
         println!("Goodbye!");
         self.running = false;
         Ok(())
     }
-    
+
     fn clear(&self) -> Result<(), ReplError> {
         print!("\x1B[2J\x1B[1;1H");
         io::stdout().flush()?;
         Ok(())
     }
-    
+
     fn show_history(&self) -> Result<(), ReplError> {
         println!("Command history:");
         for (i, cmd) in self.context.history.iter().enumerate() {
@@ -128,120 +164,181 @@ impl Repl {
         }
         Ok(())
     }
-    
-    fn change_level(&mut self, args: &[&str]) -> Result<(), ReplError> {
-        if args.is_empty() {
-            println!("Current level: {:?}", self.context.current_level);
-            return Ok(());
-        }
-        
-        self.context.current_level = match args[0] {
-            "oa" => ArcadiaLevel::OperationalAnalysis,
-            "sa" => ArcadiaLevel::SystemAnalysis,
-            "la" => ArcadiaLevel::LogicalArchitecture,
-            "pa" => ArcadiaLevel::PhysicalArchitecture,
-            "epbs" => ArcadiaLevel::EPBS,
-            _ => return Err(ReplError::InvalidLevel(args[0].to_string())),
-        };
-        
-        println!("Changed to level: {:?}", self.context.current_level);
+
+    fn show_source(&self) -> Result<(), ReplError> {
+        print!("{}", self.context.source);
         Ok(())
     }
-    
-    fn show(&self, args: &[&str]) -> Result<(), ReplError> {
-        if args.is_empty() {
-            return Err(ReplError::MissingArgument("element name".to_string()));
+
+    fn show(&self, id: &str) -> Result<(), ReplError> {
+        let id = id.trim_matches('"');
+        let model = self.model().ok_or_else(|| ReplError::ElementNotFound(id.to_string()))?;
+        let element = model
+            .all_elements
+            .get(id)
+            .ok_or_else(|| ReplError::ElementNotFound(id.to_string()))?;
+
+        println!("Element: {}", element.id);
+        println!("  Name: {}", element.name);
+        println!("  Type: {}", element.element_type);
+        if let Some(req) = model.requirements.iter().find(|r| r.id == id) {
+            println!("  Description: {}", req.description);
+            println!("  Priority: {}", req.priority);
         }
-        
-        println!("Element: {}", args[0]);
-        println!("  Type: Component");
-        println!("  Level: {:?}", self.context.current_level);
-        println!("  Description: Example component");
-        Ok(())
-    }
-    
-    fn list(&self, args: &[&str]) -> Result<(), ReplError> {
-        if args.is_empty() {
-            return Err(ReplError::MissingArgument("element type".to_string()));
+        if let Some(comp) = model.components.iter().find(|c| c.id == id) {
+            println!("  Level: {}", comp.level);
+        }
+        for trace in model.traces.iter().filter(|t| t.from == id || t.to == id) {
+            println!("  {} --[{}]--> {}", trace.from, trace.trace_type, trace.to);
         }
-        
-        println!("Elements of type '{}':", args[0]);
-        println!("  - Example1");
-        println!("  - Example2");
-        println!("  - Example3");
         Ok(())
     }
-    
-    fn define(&mut self, args: &[&str]) -> Result<(), ReplError> {
-        if args.len() < 2 {
-            return Err(ReplError::MissingArgument("element type and name".to_string()));
+
+    fn list(&self, kind: &str) -> Result<(), ReplError> {
+        let Some(model) = self.model() else {
+            return Ok(());
+        };
+        match kind {
+            "requirements" => model.requirements.iter().for_each(|r| println!("  - {}", r.id)),
+            "components" => model.components.iter().for_each(|c| println!("  - {} ({})", c.id, c.name)),
+            "traces" => model
+                .traces
+                .iter()
+                .for_each(|t| println!("  - {} --[{}]--> {}", t.from, t.trace_type, t.to)),
+            other => return Err(ReplError::InvalidKind(other.to_string())),
         }
-        
-        let element_type = args[0];
-        let element_name = args[1];
-        
-        self.context.variables.insert(element_name.to_string(), element_type.to_string());
-        
-        println!("Defined {} '{}'", element_type, element_name);
         Ok(())
     }
-    
-    fn trace(&self, args: &[&str]) -> Result<(), ReplError> {
-        if args.len() < 2 {
-            return Err(ReplError::MissingArgument("from and to elements".to_string()));
+
+    fn validate(&self) -> Result<(), ReplError> {
+        let Some(model) = self.model() else {
+            println!("The model is empty");
+            return Ok(());
+        };
+        let issues = model.traceability_issues();
+        if issues.is_empty() {
+            println!("✅ No traceability findings");
+        }
+        for issue in issues {
+            println!("  [{}] {}", issue.code, issue.diagnostic.message);
         }
-        
-        println!("Trace from {} to {}:", args[0], args[1]);
-        println!("  {} --[satisfies]--> {}", args[0], args[1]);
         Ok(())
     }
-    
-    fn validate(&self) -> Result<(), ReplError> {
-        println!("Validating model...");
-        println!("✅ Model is valid");
-        println!("   Requirements: 12");
-        println!("   Components: 8");
-        println!("   Traces: 15");
-        println!("   Coverage: 100%");
+
+    /// Compile the session with `statement` added; on failure the session is
+    /// left as it was.
+    fn evaluate(&mut self, statement: &str) -> Result<(), ReplError> {
+        let source = format!("{}{}", self.context.source, statement);
+        let result = Compiler::new(CompilerConfig::default())
+            .compile_string(&source)
+            .map_err(|e| ReplError::Evaluation(e.to_string()))?;
+        self.context.source = source;
+        self.context.model = Some(result.semantic_model);
         Ok(())
     }
-    
-    fn export(&self, args: &[&str]) -> Result<(), ReplError> {
-        if args.is_empty() {
-            return Err(ReplError::MissingArgument("export format".to_string()));
+}
+
+/// Braces opened and not yet closed, ignoring those inside string literals.
+fn open_braces(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_string = false;
+    for c in text.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => depth -= 1,
+            _ => {}
         }
-        
-        println!("Exporting to {} format...", args[0]);
-        println!("✅ Exported to model.{}", args[0]);
-        Ok(())
     }
-    
-    fn evaluate(&self, input: &str) -> Result<(), ReplError> {
-        println!("Evaluating: {}", input);
-        println!("✅ Success");
-        Ok(())
+    depth
+}
+
+/// Tab completion from the session's current model.
+struct ReplHelper {
+    model: Option<SemanticModel>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let completions = complete(&line[..pos], self.model.as_ref());
+        let candidates = completions
+            .items
+            .into_iter()
+            .map(|item| Pair {
+                display: match (item.kind, &item.detail) {
+                    (CompletionKind::Reference, Some(detail)) => format!("{}  ({})", item.label, detail),
+                    _ => item.label,
+                },
+                replacement: item.replacement,
+            })
+            .collect();
+        Ok((completions.start, candidates))
     }
 }
 
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ReplError {
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
-    
-    #[error("Invalid Arcadia level: {0}")]
-    InvalidLevel(String),
-    
-    #[error("Missing argument: {0}")]
-    MissingArgument(String),
-    
+
+    #[error("Line editor error: {0}")]
+    Readline(#[from] ReadlineError),
+
+    #[error("Unknown element kind: {0} (expected requirements, components or traces)")]
+    InvalidKind(String),
+
     #[error("Element not found: {0}")]
     ElementNotFound(String),
-    
+
     #[error("Evaluation error: {0}")]
     Evaluation(String),
 }
 
-pub fn start_repl() -> Result<(), ReplError> {
-    let mut repl = Repl::new();
+pub fn start_repl(source: String) -> Result<(), ReplError> {
+    let mut repl = Repl::new(source)?;
     repl.run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_line_statements_extend_the_model() {
+        let mut repl = Repl::new(String::new()).unwrap();
+
+        repl.execute_line("requirements system {").unwrap();
+        assert!(repl.model().is_none());
+        repl.execute_line("  req \"REQ-001\" \"Brake\" { description: \"b\" }").unwrap();
+        repl.execute_line("}").unwrap();
+
+        let model = repl.model().unwrap();
+        assert_eq!(model.requirements.len(), 1);
+        assert_eq!(model.requirements[0].id, "REQ-001");
+    }
+
+    #[test]
+    fn rejected_statement_leaves_the_session_unchanged() {
+        let mut repl =
+            Repl::new("requirements system {\n  req \"REQ-001\" \"Brake\" { description: \"b\" }\n}\n".to_string())
+                .unwrap();
+
+        let err = repl.execute_line("trace \"REQ-001\" satisfies \"REQ-404\" {}").unwrap_err();
+
+        assert!(matches!(err, ReplError::Evaluation(_)));
+        assert!(!repl.context.source.contains("REQ-404"));
+        repl.execute_line("show REQ-001").unwrap();
+    }
+}
@@ -0,0 +1,247 @@
+//! `.arclang/sync.toml` — the persisted connector configuration written by
+//! `arclang sync configure` and read by `sync pull` / `sync push`.
+//!
+//! ```toml
+//! [connectors.jira]
+//! type = "jira"
+//! url = "https://example.atlassian.net"
+//! project = "BRK"
+//! credentials = "/home/me/.config/arclang/jira.json"
+//!
+//! [connectors.jira.options]
+//! issue_type = "Requirement"
+//! verification_field = "customfield_10042"
+//! ```
+//!
+//! Credentials are only ever referenced: by the path of a credentials file,
+//! or in an `auth` table whose secret names an environment variable or a
+//! keyring entry (see [`crate::integrations::credentials`]). The file is
+//! meant to be committed alongside the model, secrets are not.
+
+use crate::integrations::credentials::Credentials;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Requirements-management connectors (`sync pull/push --requirements`).
+pub const RM_CONNECTORS: &[&str] = &["jira", "polarion", "jama", "doors"];
+/// PLM connectors (`sync pull/push --plm`).
+pub const PLM_CONNECTORS: &[&str] = &["teamcenter", "windchill", "3dexperience"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncConfig {
+    #[serde(default)]
+    pub connectors: BTreeMap<String, ConnectorConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectorConfig {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<PathBuf>,
+    /// Credentials in place of a file; the secret must be a reference.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Credentials>,
+    /// Project key / id on the remote side.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Connector-specific settings (Jira `issue_type`, DOORS `database`/`module`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+}
+
+/// Normalize a user-supplied connector type onto a known connector name.
+pub fn connector_kind(name: &str) -> Result<&'static str, String> {
+    let normalized: String = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let alias = match normalized.as_str() {
+        "3dx" | "threedexperience" | "enovia" => "3dexperience",
+        "doorsnext" | "dng" => "doors",
+        other => other,
+    };
+    RM_CONNECTORS
+        .iter()
+        .chain(PLM_CONNECTORS)
+        .find(|known| **known == alias)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "unknown connector type '{name}' (expected one of: {}, {})",
+                RM_CONNECTORS.join(", "),
+                PLM_CONNECTORS.join(", ")
+            )
+        })
+}
+
+impl ConnectorConfig {
+    pub fn is_requirements(&self) -> bool {
+        RM_CONNECTORS.contains(&self.kind.as_str())
+    }
+}
+
+impl SyncConfig {
+    pub fn path(project: &Path) -> PathBuf {
+        project.join(".arclang").join("sync.toml")
+    }
+
+    /// Load the project's sync configuration; a missing file is an empty one.
+    pub fn load(project: &Path) -> Result<Self, String> {
+        let path = Self::path(project);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let config: Self =
+            toml::from_str(&text).map_err(|e| format!("invalid {}: {e}", path.display()))?;
+        for (name, connector) in &config.connectors {
+            connector_kind(&connector.kind).map_err(|e| format!("connector '{name}': {e}"))?;
+            if connector.auth.as_ref().is_some_and(Credentials::has_plain_secret) {
+                return Err(format!(
+                    "connector '{name}': {} holds a secret in plain text; use {{ env = \"VAR\" }} or \
+                     {{ keyring = \"service/account\" }}, or a credentials file",
+                    path.display()
+                ));
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self, project: &Path) -> Result<PathBuf, String> {
+        let path = Self::path(project);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+        }
+        let text = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, text).map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Add or (with `force`) replace a named connector.
+    pub fn insert(&mut self, name: &str, connector: ConnectorConfig, force: bool) -> Result<(), String> {
+        if self.connectors.contains_key(name) && !force {
+            return Err(format!(
+                "connector '{name}' is already configured (use --force to overwrite it)"
+            ));
+        }
+        self.connectors.insert(name.to_string(), connector);
+        Ok(())
+    }
+
+    /// Pick the connector for `pull`/`push`: by name when given, otherwise the
+    /// only configured connector of the wanted family.
+    pub fn select(&self, name: Option<&str>, requirements: bool) -> Result<(&str, &ConnectorConfig), String> {
+        if let Some(name) = name {
+            return self
+                .connectors
+                .get_key_value(name)
+                .map(|(k, v)| (k.as_str(), v))
+                .ok_or_else(|| format!("no connector named '{name}' in .arclang/sync.toml"));
+        }
+        let family = if requirements { "requirements" } else { "PLM" };
+        let mut candidates = self
+            .connectors
+            .iter()
+            .filter(|(_, c)| c.is_requirements() == requirements);
+        match (candidates.next(), candidates.next()) {
+            (Some((k, v)), None) => Ok((k.as_str(), v)),
+            (None, _) => Err(format!(
+                "no {family} connector configured (run `arclang sync configure` first)"
+            )),
+            (Some(_), Some(_)) => Err(format!(
+                "several {family} connectors configured; name one explicitly"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jira() -> ConnectorConfig {
+        ConnectorConfig {
+            kind: "jira".to_string(),
+            url: "https://example.atlassian.net".to_string(),
+            credentials: Some(PathBuf::from("secrets/jira.json")),
+            auth: None,
+            project: Some("BRK".to_string()),
+            options: BTreeMap::from([("issue_type".to_string(), "Story".to_string())]),
+        }
+    }
+
+    #[test]
+    fn connector_types_are_validated_and_normalized() {
+        assert_eq!(connector_kind("Polarion"), Ok("polarion"));
+        assert_eq!(connector_kind("3DX"), Ok("3dexperience"));
+        assert!(connector_kind("sharepoint").is_err());
+    }
+
+    #[test]
+    fn config_round_trips_through_toml_and_refuses_silent_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = SyncConfig::default();
+        config.insert("jira", jira(), false).unwrap();
+        config
+            .insert(
+                "plm",
+                ConnectorConfig {
+                    kind: "teamcenter".to_string(),
+                    url: "https://tc.example.com".to_string(),
+                    credentials: None,
+                    auth: None,
+                    project: None,
+                    options: BTreeMap::new(),
+                },
+                false,
+            )
+            .unwrap();
+        let path = config.save(dir.path()).unwrap();
+        let text = std::fs::read_to_string(path).unwrap();
+        assert!(text.contains("[connectors.jira]"));
+        assert!(text.contains("credentials = \"secrets/jira.json\""));
+        assert!(text.contains("[connectors.jira.options]"));
+
+        let mut loaded = SyncConfig::load(dir.path()).unwrap();
+        assert_eq!(loaded, config);
+        assert!(loaded.insert("jira", jira(), false).is_err());
+        assert!(loaded.insert("jira", jira(), true).is_ok());
+
+        assert_eq!(loaded.select(None, true).unwrap().0, "jira");
+        assert_eq!(loaded.select(None, false).unwrap().0, "plm");
+        assert!(loaded.select(Some("polarion"), true).is_err());
+    }
+
+    #[test]
+    fn auth_tables_take_secret_references_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |auth: &str| {
+            std::fs::create_dir_all(dir.path().join(".arclang")).unwrap();
+            std::fs::write(
+                SyncConfig::path(dir.path()),
+                format!("[connectors.jira]\ntype = \"jira\"\nurl = \"https://x\"\n\n[connectors.jira.auth]\n{auth}\n"),
+            )
+            .unwrap();
+        };
+
+        write("username = \"ci\"\npassword = { env = \"JIRA_TOKEN\" }");
+        let config = SyncConfig::load(dir.path()).unwrap();
+        config.save(dir.path()).unwrap();
+        let text = std::fs::read_to_string(SyncConfig::path(dir.path())).unwrap();
+        assert!(text.contains("env = \"JIRA_TOKEN\""), "{text}");
+        assert_eq!(SyncConfig::load(dir.path()).unwrap(), config);
+
+        write("token = { keyring = \"arclang/jira\" }");
+        assert!(SyncConfig::load(dir.path()).is_ok());
+
+        write("username = \"ci\"\npassword = \"hunter2\"");
+        let error = SyncConfig::load(dir.path()).unwrap_err();
+        assert!(error.contains("plain text") && !error.contains("hunter2"), "{error}");
+    }
+}
@@ -0,0 +1,271 @@
+//! Built-in project templates for `arclang new`, embedded so scaffolding
+//! works offline. Each template is a starter `main.arc` plus the safety
+//! settings of its standard in `.arclang/config.toml`; `{name}` in either is
+//! replaced by the project name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::CliError;
+
+pub struct Template {
+    pub name: &'static str,
+    pub description: &'static str,
+    model: &'static str,
+    config: &'static str,
+}
+
+pub const DEFAULT_TEMPLATE: &str = "minimal";
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "minimal",
+        description: "Operational, system and logical skeleton, no safety standard",
+        model: MINIMAL_MODEL,
+        config: MINIMAL_CONFIG,
+    },
+    Template {
+        name: "automotive-iso26262",
+        description: "ISO 26262 road vehicle: ASIL-rated safety goal and hazard",
+        model: AUTOMOTIVE_MODEL,
+        config: AUTOMOTIVE_CONFIG,
+    },
+    Template {
+        name: "avionics-do178c",
+        description: "DO-178C airborne software: DAL-rated requirement and hazard",
+        model: AVIONICS_MODEL,
+        config: AVIONICS_CONFIG,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Template> {
+    TEMPLATES.iter().find(|t| t.name == name)
+}
+
+const GITIGNORE: &str = "\
+# Generated by arclang build / explorer / diagram
+*.json
+*.html
+*.mermaid
+.arclang/cache/
+.arclang/outputs
+";
+
+/// Create project `dir` from `template`, returning the files written. An
+/// existing directory must be empty.
+pub fn scaffold(dir: &Path, template: &Template) -> Result<Vec<PathBuf>, CliError> {
+    if dir.exists() && fs::read_dir(dir)?.next().is_some() {
+        return Err(CliError::Config(format!(
+            "{} already exists and is not empty",
+            dir.display()
+        )));
+    }
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "project".to_string());
+
+    fs::create_dir_all(dir.join(".arclang"))?;
+    let files = [
+        (dir.join("main.arc"), template.model.replace("{name}", &name)),
+        (
+            dir.join(".arclang").join("config.toml"),
+            template.config.replace("{name}", &name),
+        ),
+        (dir.join(".gitignore"), GITIGNORE.to_string()),
+    ];
+    for (path, content) in &files {
+        fs::write(path, content)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+const MINIMAL_MODEL: &str = r#"// {name} — start here, then `arclang check main.arc`.
+
+model "{name}" {
+  version: "0.1.0"
+}
+
+operational_analysis "Operations" {
+  operational_capability "Operate the system" {
+    id: "OC-001"
+    description: "What the users need to achieve"
+  }
+}
+
+system_analysis "System" {
+  requirement "SYS-001" {
+    description: "The system shall ..."
+    priority: "High"
+  }
+}
+
+logical_architecture "Logical Architecture" {
+  component "Controller" {
+    id: "LC-001"
+  }
+}
+
+trace "SYS-001" allocated_to "LC-001" {
+  rationale: "The controller realises the first system requirement"
+}
+"#;
+
+const MINIMAL_CONFIG: &str = r#"[project]
+name = "{name}"
+template = "minimal"
+model = "main.arc"
+"#;
+
+const AUTOMOTIVE_MODEL: &str = r#"// {name} — ISO 26262 starter. Rate hazards in the HARA, derive safety
+// goals from them and allocate every goal to the architecture.
+
+model "{name}" {
+  version: "0.1.0"
+}
+
+operational_analysis "Vehicle Operations" {
+  operational_capability "Drive safely" {
+    id: "OC-001"
+    description: "The driver controls the vehicle's speed at all times"
+  }
+}
+
+system_analysis "Item Definition" {
+  requirement "SG-001" {
+    description: "The item shall not cause unintended acceleration"
+    priority: "High"
+    safety_level: "ASIL-B"
+  }
+}
+
+logical_architecture "Logical Architecture" {
+  component "Controller" {
+    id: "LC-001"
+    safety_level: "ASIL-B"
+  }
+}
+
+safety_analysis {
+  hazard "Unintended Acceleration" {
+    id: "HAZ-001"
+    description: "Vehicle accelerates without driver request"
+    severity: "S3"
+    asil: "ASIL_B"
+  }
+}
+
+trace "SG-001" allocated_to "LC-001" {
+  rationale: "The controller implements the safety goal"
+}
+"#;
+
+const AUTOMOTIVE_CONFIG: &str = r#"[project]
+name = "{name}"
+template = "automotive-iso26262"
+model = "main.arc"
+
+[safety]
+standard = "ISO26262"
+edition = "2018"
+integrity_level = "ASIL-B"
+rpn_threshold = 100
+"#;
+
+const AVIONICS_MODEL: &str = r#"// {name} — DO-178C starter. Assign a design assurance level to every
+// requirement and keep high-level requirements traced to the architecture.
+
+model "{name}" {
+  version: "0.1.0"
+}
+
+operational_analysis "Flight Operations" {
+  operational_capability "Control the aircraft" {
+    id: "OC-001"
+    description: "The crew controls the aircraft throughout the flight"
+  }
+}
+
+system_analysis "System Requirements" {
+  requirement "HLR-001" {
+    description: "The software shall ..."
+    priority: "High"
+    safety_level: "DAL_B"
+  }
+}
+
+logical_architecture "Software Architecture" {
+  component "Flight Software" {
+    id: "LC-001"
+    safety_level: "DAL_B"
+  }
+}
+
+safety_analysis {
+  hazard "Loss of Function" {
+    id: "HAZ-001"
+    description: "The function is unavailable in flight"
+    severity: "Hazardous"
+    dal: "DAL_B"
+  }
+}
+
+trace "HLR-001" allocated_to "LC-001" {
+  rationale: "The flight software implements the high-level requirement"
+}
+"#;
+
+const AVIONICS_CONFIG: &str = r#"[project]
+name = "{name}"
+template = "avionics-do178c"
+model = "main.arc"
+
+[safety]
+standard = "DO178C"
+level = "B"
+integrity_level = "DAL_B"
+rpn_threshold = 100
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn every_template_scaffolds_a_clean_model() {
+        for template in TEMPLATES {
+            let root = tempfile::tempdir().unwrap();
+            let dir = root.path().join("brake-system");
+
+            let files = scaffold(&dir, template).unwrap();
+
+            assert_eq!(files.len(), 3);
+            let config: toml::Value =
+                toml::from_str(&fs::read_to_string(dir.join(".arclang/config.toml")).unwrap()).unwrap();
+            assert_eq!(config["project"]["name"].as_str(), Some("brake-system"));
+            let result = Compiler::new(CompilerConfig::default())
+                .compile_file(&dir.join("main.arc"))
+                .unwrap_or_else(|e| panic!("{}: {e}", template.name));
+            assert!(result.warnings.is_empty(), "{}: {:?}", template.name, result.warnings);
+            assert!(
+                result.semantic_model.traceability_issues().is_empty(),
+                "{}: {:?}",
+                template.name,
+                result.semantic_model.validate_traceability()
+            );
+            assert_eq!(result.semantic_model.name.as_deref(), Some("brake-system"));
+        }
+    }
+
+    #[test]
+    fn refuses_a_non_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "keep me").unwrap();
+
+        let err = scaffold(dir.path(), find("minimal").unwrap()).unwrap_err();
+
+        assert!(matches!(err, CliError::Config(_)));
+        assert!(!dir.path().join("main.arc").exists());
+    }
+}
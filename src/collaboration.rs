@@ -1,4 +1,14 @@
 // Collaboration tools stub
+pub mod change_tracking;
+pub mod conflict_resolution;
+#[cfg(feature = "native")]
+pub mod git_integration;
+pub mod review;
+pub mod semantic_merge;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
 pub struct CollaborationManager;
 
 impl CollaborationManager {
@@ -6,3 +16,269 @@ impl CollaborationManager {
         Self
     }
 }
+
+/// Team policy persisted in `.arclang/collaboration.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollaborationConfig {
+    /// Pushing a changeset needs an approved review first.
+    #[serde(default)]
+    pub review_required: bool,
+    #[serde(default = "default_conflict_resolution")]
+    pub conflict_resolution: ConflictResolutionPolicy,
+}
+
+impl Default for CollaborationConfig {
+    fn default() -> Self {
+        Self {
+            review_required: false,
+            conflict_resolution: default_conflict_resolution(),
+        }
+    }
+}
+
+fn default_conflict_resolution() -> ConflictResolutionPolicy {
+    ConflictResolutionPolicy::Manual
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConflictResolutionPolicy {
+    Manual,
+    AutoResolveNonSemantic,
+    AutoResolveAll,
+    PreferBase,
+    PreferTheirs,
+    PreferOurs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelChange {
+    pub change_type: ChangeType,
+    pub element_id: String,
+    pub element_type: ElementType,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ChangeType {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Moved,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ElementType {
+    Requirement,
+    Component,
+    Function,
+    Interface,
+    Capability,
+    Actor,
+    Scenario,
+    DataType,
+    Constraint,
+    Trace,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticDiff {
+    pub added_requirements: Vec<String>,
+    pub modified_requirements: Vec<String>,
+    pub deleted_requirements: Vec<String>,
+    pub added_components: Vec<String>,
+    pub modified_components: Vec<String>,
+    pub deleted_components: Vec<String>,
+    pub added_relationships: Vec<RelationshipChange>,
+    pub deleted_relationships: Vec<RelationshipChange>,
+    pub integrity_impact: IntegrityImpact,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelationshipChange {
+    pub relationship_type: String,
+    pub source_id: String,
+    pub target_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IntegrityImpact {
+    pub breaks_traceability: bool,
+    pub affects_safety_requirements: bool,
+    pub impacts_interfaces: Vec<String>,
+    pub orphaned_elements: Vec<String>,
+    pub severity: ImpactSeverity,
+}
+
+/// What changing one element may affect (`arclang impact`), from
+/// [`change_tracking::element_impact`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ElementImpact {
+    /// Id of the changed element.
+    pub element: String,
+    /// Every element reached from it, nearest first.
+    pub affected: Vec<crate::compiler::semantic::ImpactEntry>,
+    /// The safety requirements among `affected`, nearest first.
+    pub safety_requirements: Vec<AffectedSafetyRequirement>,
+    pub integrity_impact: IntegrityImpact,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedSafetyRequirement {
+    pub id: String,
+    pub name: String,
+    pub safety_level: String,
+    /// Distance from the changed element.
+    pub depth: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ImpactSeverity {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub success: bool,
+    pub conflicts: Vec<Conflict>,
+    pub auto_resolved: Vec<AutoResolution>,
+    pub merged_changes: Vec<ModelChange>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    pub id: String,
+    pub conflict_type: ConflictType,
+    pub element_id: String,
+    pub element_type: ElementType,
+    pub base_value: Option<serde_json::Value>,
+    pub ours_value: Option<serde_json::Value>,
+    pub theirs_value: Option<serde_json::Value>,
+    pub description: String,
+    pub resolution: Option<ConflictResolution>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConflictType {
+    TextualConflict,
+    SemanticConflict,
+    StructuralConflict,
+    TraceabilityConflict,
+    DuplicateId,
+    DeleteModify,
+    MoveMove,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictResolution {
+    pub resolved_by: String,
+    pub resolved_at: DateTime<Utc>,
+    pub resolution_strategy: ResolutionStrategy,
+    pub resolved_value: serde_json::Value,
+    pub rationale: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ResolutionStrategy {
+    KeepBase,
+    KeepOurs,
+    KeepTheirs,
+    KeepBoth,
+    Manual,
+    Merge,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoResolution {
+    pub element_id: String,
+    pub conflict_type: ConflictType,
+    pub strategy: ResolutionStrategy,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewRequest {
+    pub id: String,
+    pub changeset_id: String,
+    pub author: String,
+    pub reviewers: Vec<String>,
+    pub status: ReviewStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub comments: Vec<ReviewComment>,
+    pub approvals: Vec<Approval>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ReviewStatus {
+    Pending,
+    InReview,
+    ChangesRequested,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub id: String,
+    pub author: String,
+    pub timestamp: DateTime<Utc>,
+    pub element_id: Option<String>,
+    pub comment_text: String,
+    pub comment_type: CommentType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CommentType {
+    General,
+    Question,
+    Suggestion,
+    Issue,
+    Blocker,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    pub reviewer: String,
+    pub timestamp: DateTime<Utc>,
+    pub approved: bool,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CollaborationError {
+    #[error("Merge conflict: {0}")]
+    MergeConflict(String),
+
+    #[error("Semantic conflict: {0}")]
+    SemanticConflict(String),
+
+    #[error("Traceability broken: {0}")]
+    TraceabilityBroken(String),
+
+    #[error("Review required but not completed")]
+    ReviewRequired,
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Invalid changeset: {0}")]
+    InvalidChangeSet(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    #[error("Git error: {0}")]
+    Git(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
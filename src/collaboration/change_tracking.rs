@@ -0,0 +1,319 @@
+//! What a changeset does to the model's integrity: lost traceability,
+//! orphaned requirements, touched safety requirements. Surfaced in review so
+//! a dangerous edit is seen before it is merged.
+
+use super::*;
+use crate::compiler::semantic::{SemanticModel, TraceInfo};
+use std::collections::BTreeSet;
+
+/// The element-level changes from `base` to `head`, with their integrity
+/// impact.
+pub fn diff(base: &SemanticModel, head: &SemanticModel) -> SemanticDiff {
+    let (added_requirements, modified_requirements, deleted_requirements) =
+        changed_ids(&base.requirements, &head.requirements, |r| &r.id);
+    let (added_components, modified_components, deleted_components) =
+        changed_ids(&base.components, &head.components, |c| &c.id);
+
+    let base_traces: Vec<RelationshipChange> = base.traces.iter().map(relationship).collect();
+    let head_traces: Vec<RelationshipChange> = head.traces.iter().map(relationship).collect();
+
+    let mut diff = SemanticDiff {
+        added_requirements,
+        modified_requirements,
+        deleted_requirements,
+        added_components,
+        modified_components,
+        deleted_components,
+        added_relationships: head_traces.iter().filter(|t| !base_traces.contains(t)).cloned().collect(),
+        deleted_relationships: base_traces.iter().filter(|t| !head_traces.contains(t)).cloned().collect(),
+        integrity_impact: IntegrityImpact::default(),
+    };
+    diff.integrity_impact = analyze_impact(base, &diff);
+    diff
+}
+
+pub fn analyze_impact(base: &SemanticModel, diff: &SemanticDiff) -> IntegrityImpact {
+    let deleted: BTreeSet<&str> = diff
+        .deleted_requirements
+        .iter()
+        .chain(&diff.deleted_components)
+        .map(String::as_str)
+        .collect();
+    let is_safety = |id: &str| {
+        base.requirements
+            .iter()
+            .any(|r| r.id == id && r.is_safety_requirement())
+    };
+
+    let breaks_traceability = base.traces.iter().any(|t| deleted.contains(t.to.as_str()));
+
+    // Requirements a component satisfied before the change and none does after.
+    let is_component_before = |id: &str| base.components.iter().any(|c| c.id == id);
+    let is_component_after = |id: &str| {
+        (is_component_before(id) && !deleted.contains(id)) || diff.added_components.iter().any(|c| c == id)
+    };
+    let satisfied_before: BTreeSet<&str> = base
+        .traces
+        .iter()
+        .filter(|t| t.trace_type == "satisfies" && is_component_before(&t.from))
+        .map(|t| t.to.as_str())
+        .collect();
+    let satisfied_after: BTreeSet<String> = base
+        .traces
+        .iter()
+        .map(relationship)
+        .filter(|t| !diff.deleted_relationships.contains(t))
+        .chain(diff.added_relationships.iter().cloned())
+        .filter(|t| t.relationship_type == "satisfies" && is_component_after(&t.source_id))
+        .map(|t| t.target_id)
+        .collect();
+    let orphaned_elements: Vec<String> = satisfied_before
+        .iter()
+        .filter(|id| !deleted.contains(*id) && !satisfied_after.contains(**id))
+        .map(|id| id.to_string())
+        .collect();
+
+    let affects_safety_requirements = diff
+        .added_requirements
+        .iter()
+        .chain(&diff.modified_requirements)
+        .chain(&diff.deleted_requirements)
+        .any(|id| is_safety(id));
+
+    let changed_components: BTreeSet<&str> = diff
+        .modified_components
+        .iter()
+        .chain(&diff.deleted_components)
+        .map(String::as_str)
+        .collect();
+    let impacts_interfaces: Vec<String> = base
+        .interfaces
+        .iter()
+        .filter(|i| changed_components.contains(i.from.as_str()) || changed_components.contains(i.to.as_str()))
+        .map(|i| i.name.clone())
+        .collect();
+
+    let safety_traceability_lost = orphaned_elements.iter().any(|id| is_safety(id))
+        || base
+            .traces
+            .iter()
+            .any(|t| deleted.contains(t.to.as_str()) && is_safety(&t.to));
+    let is_empty = diff.added_requirements.is_empty()
+        && diff.modified_requirements.is_empty()
+        && diff.deleted_requirements.is_empty()
+        && diff.added_components.is_empty()
+        && diff.modified_components.is_empty()
+        && diff.deleted_components.is_empty()
+        && diff.added_relationships.is_empty()
+        && diff.deleted_relationships.is_empty();
+
+    let severity = if safety_traceability_lost {
+        ImpactSeverity::Critical
+    } else if breaks_traceability {
+        ImpactSeverity::High
+    } else if affects_safety_requirements || !orphaned_elements.is_empty() || !impacts_interfaces.is_empty() {
+        ImpactSeverity::Medium
+    } else if !is_empty {
+        ImpactSeverity::Low
+    } else {
+        ImpactSeverity::None
+    };
+
+    IntegrityImpact {
+        breaks_traceability,
+        affects_safety_requirements,
+        impacts_interfaces,
+        orphaned_elements,
+        severity,
+    }
+}
+
+/// What changing `element` (id or unambiguous name) may affect: the
+/// elements [`SemanticModel::impact_of`] reaches, the safety requirements
+/// among them, and the integrity impact of a change touching the element and
+/// every affected requirement and component. `None` when the element does
+/// not resolve.
+pub fn element_impact(model: &SemanticModel, element: &str) -> Option<ElementImpact> {
+    let id = model.resolve_element(element)?;
+    let affected = model.impact_of(&id)?;
+
+    let changed: Vec<&str> = std::iter::once(id.as_str()).chain(affected.iter().map(|e| e.id.as_str())).collect();
+    let changed_of = |is_kind: &dyn Fn(&str) -> bool| -> Vec<String> {
+        changed.iter().filter(|id| is_kind(id)).map(|id| id.to_string()).collect()
+    };
+    let diff = SemanticDiff {
+        modified_requirements: changed_of(&|id| model.requirements.iter().any(|r| r.id == id)),
+        modified_components: changed_of(&|id| model.components.iter().any(|c| c.id == id)),
+        ..diff(model, model)
+    };
+
+    let safety_requirements = affected
+        .iter()
+        .filter_map(|entry| {
+            let req = model.requirements.iter().find(|r| r.id == entry.id && r.is_safety_requirement())?;
+            Some(AffectedSafetyRequirement {
+                id: req.id.clone(),
+                name: entry.name.clone(),
+                safety_level: req.safety_level.clone().unwrap_or_default(),
+                depth: entry.depth,
+            })
+        })
+        .collect();
+
+    Some(ElementImpact {
+        integrity_impact: analyze_impact(model, &diff),
+        element: id,
+        affected,
+        safety_requirements,
+    })
+}
+
+fn relationship(trace: &TraceInfo) -> RelationshipChange {
+    RelationshipChange {
+        relationship_type: trace.trace_type.clone(),
+        source_id: trace.from.clone(),
+        target_id: trace.to.clone(),
+    }
+}
+
+/// Added, modified and deleted ids between two element lists; an element is
+/// modified when any of its fields differ.
+fn changed_ids<T: serde::Serialize>(
+    base: &[T],
+    head: &[T],
+    id: impl Fn(&T) -> &String,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let find = |elements: &[T], wanted: &String| {
+        elements.iter().find(|e| id(e) == wanted).map(|e| serde_json::to_value(e).ok())
+    };
+
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    for element in head {
+        match find(base, id(element)) {
+            None => added.push(id(element).clone()),
+            Some(before) if before != serde_json::to_value(element).ok() => modified.push(id(element).clone()),
+            Some(_) => {}
+        }
+    }
+    let deleted = base
+        .iter()
+        .filter(|element| find(head, id(element)).is_none())
+        .map(|element| id(element).clone())
+        .collect();
+
+    (added, modified, deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const BASE: &str = r#"
+requirements system {
+  req "R-SAFE" "Brake" { description: "brake" safety_level: "ASIL-D" }
+  req "R-QM" "Chime" { description: "chime" safety_level: "QM" }
+}
+architecture logical {
+  component "Brake Controller" { id: "LC-1" }
+  component "Chime Driver" { id: "LC-2" }
+}
+trace "LC-1" satisfies "R-SAFE" {}
+trace "LC-2" satisfies "R-QM" {}
+"#;
+
+    fn compile(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("test model compiles")
+            .semantic_model
+    }
+
+    #[test]
+    fn unchanged_model_has_no_impact() {
+        let base = compile(BASE);
+        let impact = diff(&base, &base).integrity_impact;
+
+        assert_eq!(impact.severity, ImpactSeverity::None);
+        assert!(!impact.breaks_traceability);
+    }
+
+    #[test]
+    fn removing_a_satisfying_trace_orphans_the_requirement() {
+        let base = compile(BASE);
+        let head = compile(&BASE.replace("trace \"LC-2\" satisfies \"R-QM\" {}\n", ""));
+
+        let impact = diff(&base, &head).integrity_impact;
+
+        assert_eq!(impact.orphaned_elements, vec!["R-QM"]);
+        assert!(!impact.affects_safety_requirements);
+        assert_eq!(impact.severity, ImpactSeverity::Medium);
+    }
+
+    #[test]
+    fn safety_requirement_losing_traceability_is_critical() {
+        let base = compile(BASE);
+        let head = compile(
+            &BASE
+                .replace("  component \"Brake Controller\" { id: \"LC-1\" }\n", "")
+                .replace("trace \"LC-1\" satisfies \"R-SAFE\" {}\n", ""),
+        );
+
+        let impact = diff(&base, &head).integrity_impact;
+
+        assert_eq!(impact.orphaned_elements, vec!["R-SAFE"]);
+        assert_eq!(impact.severity, ImpactSeverity::Critical);
+    }
+
+    #[test]
+    fn deleting_a_trace_target_breaks_traceability() {
+        let base = compile(BASE);
+        let analyzed = SemanticDiff {
+            deleted_requirements: vec!["R-QM".to_string()],
+            ..diff(&base, &base)
+        };
+
+        let impact = analyze_impact(&base, &analyzed);
+
+        assert!(impact.breaks_traceability);
+        assert_eq!(impact.severity, ImpactSeverity::High);
+    }
+
+    #[test]
+    fn element_impact_follows_connections_and_deployments_to_safety_requirements() {
+        let model = compile(
+            r#"
+requirements system {
+  req "R-SAFE" "Brake" { description: "brake" safety_level: "ASIL-D" }
+  req "R-QM" "Chime" { description: "chime" }
+}
+architecture logical {
+  component "Sensor" { id: "LC-1" port out speed { interface: "ISpeed" } }
+  component "Controller" { id: "LC-2" port in speed { interface: "ISpeed" } }
+  component "Chime" { id: "LC-3" }
+  connect "LC-1.speed" to "LC-2.speed"
+}
+physical_architecture "PA" {
+  node "ECU" { id: "PN-1" deploys "LC-2" deploys "LC-3" }
+}
+trace "LC-2" satisfies "R-SAFE" {}
+trace "LC-3" satisfies "R-QM" {}
+"#,
+        );
+
+        let impact = element_impact(&model, "Sensor").unwrap();
+
+        let reached: Vec<(&str, u32)> = impact.affected.iter().map(|e| (e.id.as_str(), e.depth)).collect();
+        assert_eq!(reached, vec![("LC-2", 1), ("PN-1", 2), ("R-SAFE", 2), ("LC-3", 3), ("R-QM", 4)]);
+        let safety: Vec<(&str, &str, u32)> = impact
+            .safety_requirements
+            .iter()
+            .map(|r| (r.id.as_str(), r.safety_level.as_str(), r.depth))
+            .collect();
+        assert_eq!(safety, vec![("R-SAFE", "ASIL-D", 2)]);
+        assert!(impact.integrity_impact.affects_safety_requirements);
+        assert_eq!(impact.integrity_impact.severity, ImpactSeverity::Medium);
+        assert!(element_impact(&model, "LC-404").is_none());
+    }
+}
@@ -0,0 +1,312 @@
+//! Settles merge conflicts by a [`ConflictResolutionPolicy`].
+//!
+//! - `Manual` settles nothing.
+//! - `PreferBase`, `PreferOurs` and `PreferTheirs` take that side's value
+//!   for every conflict, a deletion included.
+//! - `AutoResolveNonSemantic` settles only conflicts that lose nothing:
+//!   textual ones whose sides differ in formatting alone or edit different
+//!   fields, and ids added twice (both elements kept). Semantic,
+//!   traceability, structural, move and delete/modify conflicts are left
+//!   for a person.
+//! - `AutoResolveAll` also merges disjoint field edits of semantic
+//!   conflicts, keeps a modification over a deletion, and otherwise keeps
+//!   ours.
+//!
+//! Every resolution says why in its `rationale`; a conflict left alone gets
+//! a `Manual` resolution with a null value.
+
+use super::*;
+use serde_json::{Map, Value};
+
+const RESOLVER: &str = "arclang";
+
+/// One resolution per conflict, in order.
+pub fn resolve(conflicts: &[Conflict], policy: ConflictResolutionPolicy) -> Vec<ConflictResolution> {
+    conflicts.iter().map(|conflict| resolve_one(conflict, &policy)).collect()
+}
+
+fn resolve_one(conflict: &Conflict, policy: &ConflictResolutionPolicy) -> ConflictResolution {
+    let side = |value: &Option<Value>| value.clone().unwrap_or(Value::Null);
+    let (strategy, value, rationale) = match policy {
+        ConflictResolutionPolicy::Manual => {
+            return manual("the policy leaves every conflict to a person".to_string());
+        }
+        ConflictResolutionPolicy::PreferBase => (
+            ResolutionStrategy::KeepBase,
+            side(&conflict.base_value),
+            "the policy prefers the common ancestor".to_string(),
+        ),
+        ConflictResolutionPolicy::PreferOurs => {
+            (ResolutionStrategy::KeepOurs, side(&conflict.ours_value), "the policy prefers ours".to_string())
+        }
+        ConflictResolutionPolicy::PreferTheirs => {
+            (ResolutionStrategy::KeepTheirs, side(&conflict.theirs_value), "the policy prefers theirs".to_string())
+        }
+        ConflictResolutionPolicy::AutoResolveNonSemantic => match non_semantic(conflict) {
+            Some(resolution) => resolution,
+            None => return manual(format!("a {:?} needs a person", conflict.conflict_type)),
+        },
+        ConflictResolutionPolicy::AutoResolveAll => non_semantic(conflict).unwrap_or_else(|| semantic(conflict)),
+    };
+    ConflictResolution {
+        resolved_by: RESOLVER.to_string(),
+        resolved_at: Utc::now(),
+        resolution_strategy: strategy,
+        resolved_value: value,
+        rationale,
+    }
+}
+
+fn manual(rationale: String) -> ConflictResolution {
+    ConflictResolution {
+        resolved_by: RESOLVER.to_string(),
+        resolved_at: Utc::now(),
+        resolution_strategy: ResolutionStrategy::Manual,
+        resolved_value: Value::Null,
+        rationale,
+    }
+}
+
+/// The resolutions that lose nothing of either side.
+fn non_semantic(conflict: &Conflict) -> Option<(ResolutionStrategy, Value, String)> {
+    let (base, ours, theirs) = (&conflict.base_value, &conflict.ours_value, &conflict.theirs_value);
+    match conflict.conflict_type {
+        ConflictType::TextualConflict => {
+            let (ours_value, theirs_value) = (ours.as_ref()?, theirs.as_ref()?);
+            if normalized(ours_value) == normalized(theirs_value) {
+                return Some((
+                    ResolutionStrategy::KeepOurs,
+                    ours_value.clone(),
+                    "the sides differ only in whitespace".to_string(),
+                ));
+            }
+            let merged = merge_fields(base.as_ref()?, ours_value, theirs_value)?;
+            Some((ResolutionStrategy::Merge, merged, "the sides edit different fields".to_string()))
+        }
+        ConflictType::DuplicateId => Some((
+            ResolutionStrategy::KeepBoth,
+            Value::Array(vec![ours.clone()?, theirs.clone()?]),
+            "two different elements took the same id; theirs needs a new one".to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// `AutoResolveAll` for the conflicts [`non_semantic`] leaves.
+fn semantic(conflict: &Conflict) -> (ResolutionStrategy, Value, String) {
+    let (base, ours, theirs) = (&conflict.base_value, &conflict.ours_value, &conflict.theirs_value);
+    if let (Some(base), Some(ours), Some(theirs)) = (base, ours, theirs) {
+        if let Some(merged) = merge_fields(base, ours, theirs) {
+            return (ResolutionStrategy::Merge, merged, "the sides edit different fields".to_string());
+        }
+    }
+    match (ours, theirs) {
+        (None, Some(theirs)) => {
+            (ResolutionStrategy::KeepTheirs, theirs.clone(), "their modification is kept over our deletion".to_string())
+        }
+        (Some(ours), None) => {
+            (ResolutionStrategy::KeepOurs, ours.clone(), "our modification is kept over their deletion".to_string())
+        }
+        _ => (
+            ResolutionStrategy::KeepOurs,
+            ours.clone().unwrap_or(Value::Null),
+            "the sides edit the same content; ours is kept".to_string(),
+        ),
+    }
+}
+
+/// Three-way merge of two objects field by field: each field takes the side
+/// that changed it. `None` when a field changed differently on both sides
+/// or a value is not an object.
+fn merge_fields(base: &Value, ours: &Value, theirs: &Value) -> Option<Value> {
+    let (base, ours, theirs) = (base.as_object()?, ours.as_object()?, theirs.as_object()?);
+    let mut merged = Map::new();
+    for key in ours.keys().chain(theirs.keys()).chain(base.keys()) {
+        if merged.contains_key(key) {
+            continue;
+        }
+        let (b, o, t) = (base.get(key), ours.get(key), theirs.get(key));
+        let value = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            return None;
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    // A field deleted on one side is absent from the merge, not null
+    merged.retain(|key, _| ours.contains_key(key) || theirs.contains_key(key));
+    Some(Value::Object(merged))
+}
+
+/// `value` with every run of whitespace in its strings collapsed to one
+/// space and leading and trailing whitespace trimmed.
+fn normalized(value: &Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(text.split_whitespace().collect::<Vec<_>>().join(" ")),
+        Value::Array(items) => Value::Array(items.iter().map(normalized).collect()),
+        Value::Object(fields) => Value::Object(fields.iter().map(|(k, v)| (k.clone(), normalized(v))).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn conflict(
+        conflict_type: ConflictType,
+        base: Option<Value>,
+        ours: Option<Value>,
+        theirs: Option<Value>,
+    ) -> Conflict {
+        Conflict {
+            id: format!("CONF-{conflict_type:?}"),
+            conflict_type,
+            element_id: "R-1".to_string(),
+            element_type: ElementType::Requirement,
+            base_value: base,
+            ours_value: ours,
+            theirs_value: theirs,
+            description: String::new(),
+            resolution: None,
+        }
+    }
+
+    /// One conflict of each kind the policies treat differently.
+    fn conflicts() -> Vec<Conflict> {
+        let base = json!({"id": "R-1", "description": "brake", "priority": "Low"});
+        vec![
+            // 0: formatting only
+            conflict(
+                ConflictType::TextualConflict,
+                Some(base.clone()),
+                Some(json!({"id": "R-1", "description": "brake  ", "priority": "Low"})),
+                Some(json!({"id": "R-1", "description": " brake", "priority": "Low"})),
+            ),
+            // 1: different fields
+            conflict(
+                ConflictType::TextualConflict,
+                Some(base.clone()),
+                Some(json!({"id": "R-1", "description": "brake hard", "priority": "Low"})),
+                Some(json!({"id": "R-1", "description": "brake", "priority": "High"})),
+            ),
+            // 2: the same field
+            conflict(
+                ConflictType::SemanticConflict,
+                Some(base.clone()),
+                Some(json!({"id": "R-1", "description": "brake hard", "priority": "Low"})),
+                Some(json!({"id": "R-1", "description": "brake gently", "priority": "Low"})),
+            ),
+            // 3: deleted by us, modified by them
+            conflict(
+                ConflictType::DeleteModify,
+                Some(base.clone()),
+                None,
+                Some(json!({"id": "R-1", "description": "brake gently", "priority": "Low"})),
+            ),
+            // 4: a trace both sides changed
+            conflict(
+                ConflictType::TraceabilityConflict,
+                Some(json!({"from": "LC-1", "to": "R-1", "rationale": "a"})),
+                Some(json!({"from": "LC-1", "to": "R-1", "rationale": "b"})),
+                Some(json!({"from": "LC-1", "to": "R-1", "rationale": "c"})),
+            ),
+            // 5: the same id added twice
+            conflict(
+                ConflictType::DuplicateId,
+                None,
+                Some(json!({"id": "R-9", "description": "signal"})),
+                Some(json!({"id": "R-9", "description": "honk"})),
+            ),
+        ]
+    }
+
+    fn strategies(resolutions: &[ConflictResolution]) -> Vec<ResolutionStrategy> {
+        resolutions.iter().map(|r| r.resolution_strategy.clone()).collect()
+    }
+
+    #[test]
+    fn manual_settles_nothing() {
+        let resolutions = resolve(&conflicts(), ConflictResolutionPolicy::Manual);
+        assert!(strategies(&resolutions).iter().all(|s| *s == ResolutionStrategy::Manual));
+        assert!(resolutions.iter().all(|r| r.resolved_value.is_null()));
+    }
+
+    #[test]
+    fn prefer_policies_take_one_side_everywhere() {
+        let conflicts = conflicts();
+        for (policy, strategy) in [
+            (ConflictResolutionPolicy::PreferBase, ResolutionStrategy::KeepBase),
+            (ConflictResolutionPolicy::PreferOurs, ResolutionStrategy::KeepOurs),
+            (ConflictResolutionPolicy::PreferTheirs, ResolutionStrategy::KeepTheirs),
+        ] {
+            let resolutions = resolve(&conflicts, policy.clone());
+            assert!(strategies(&resolutions).iter().all(|s| *s == strategy), "{policy:?}");
+            for (conflict, resolution) in conflicts.iter().zip(&resolutions) {
+                let expected = match policy {
+                    ConflictResolutionPolicy::PreferBase => &conflict.base_value,
+                    ConflictResolutionPolicy::PreferOurs => &conflict.ours_value,
+                    _ => &conflict.theirs_value,
+                };
+                assert_eq!(Some(&resolution.resolved_value), expected.as_ref().or(Some(&Value::Null)));
+            }
+        }
+    }
+
+    #[test]
+    fn auto_resolve_non_semantic_leaves_semantic_and_traceability_conflicts() {
+        let conflicts = conflicts();
+        let resolutions = resolve(&conflicts, ConflictResolutionPolicy::AutoResolveNonSemantic);
+        assert_eq!(
+            strategies(&resolutions),
+            [
+                ResolutionStrategy::KeepOurs,
+                ResolutionStrategy::Merge,
+                ResolutionStrategy::Manual,
+                ResolutionStrategy::Manual,
+                ResolutionStrategy::Manual,
+                ResolutionStrategy::KeepBoth,
+            ]
+        );
+        assert_eq!(resolutions[1].resolved_value, json!({"id": "R-1", "description": "brake hard", "priority": "High"}));
+        assert!(resolutions[2].rationale.contains("SemanticConflict needs a person"), "{}", resolutions[2].rationale);
+        assert!(resolutions[4].rationale.contains("TraceabilityConflict"), "{}", resolutions[4].rationale);
+        assert_eq!(resolutions[5].resolved_value.as_array().map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn auto_resolve_all_settles_everything() {
+        let conflicts = conflicts();
+        let resolutions = resolve(&conflicts, ConflictResolutionPolicy::AutoResolveAll);
+        assert_eq!(
+            strategies(&resolutions),
+            [
+                ResolutionStrategy::KeepOurs,
+                ResolutionStrategy::Merge,
+                ResolutionStrategy::KeepOurs,
+                ResolutionStrategy::KeepTheirs,
+                ResolutionStrategy::KeepOurs,
+                ResolutionStrategy::KeepBoth,
+            ]
+        );
+        assert_eq!(resolutions[3].resolved_value["description"], "brake gently");
+        assert!(resolutions[3].rationale.contains("modification is kept over our deletion"));
+        assert!(resolutions.iter().all(|r| !r.rationale.is_empty()));
+    }
+
+    #[test]
+    fn field_merge_drops_a_field_one_side_deleted() {
+        let merged = merge_fields(
+            &json!({"a": 1, "b": 2}),
+            &json!({"a": 1}),
+            &json!({"a": 3, "b": 2}),
+        );
+        assert_eq!(merged, Some(json!({"a": 3})));
+        assert_eq!(merge_fields(&json!({"a": 1}), &json!({"a": 2}), &json!({"a": 3})), None);
+    }
+}
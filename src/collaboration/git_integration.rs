@@ -0,0 +1,459 @@
+//! A model file's history from git: every commit that changed the file,
+//! oldest first, compiled on its own so audits can follow how the model —
+//! and one element of it — evolved. Behind `arclang history`.
+//!
+//! History follows the first parent, so a merge shows up as one revision
+//! rather than as the commits of the branch it brought in.
+//!
+//! `arclang blame` takes the cross-section instead: for every requirement
+//! of the current model, the commits that introduced and last changed it
+//! and what justifies it upstream.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use git2::{Oid, Repository, Sort};
+use serde::Serialize;
+
+use super::CollaborationError;
+use crate::compiler::semantic::{SemanticModel, TraceLinkType};
+use crate::compiler::semantic_diff::{diff_models, FieldChange};
+use crate::compiler::{Compiler, CompilerConfig};
+
+/// The file as one commit left it.
+#[derive(Debug, Clone)]
+pub struct FileRevision {
+    pub commit: String,
+    pub author: String,
+    pub date: DateTime<Utc>,
+    pub summary: String,
+    /// `None` when the commit deleted the file.
+    pub source: Option<String>,
+}
+
+pub struct GitManager {
+    repo: Repository,
+}
+
+impl GitManager {
+    /// The repository `path` lives in.
+    pub fn discover(path: &Path) -> Result<Self, CollaborationError> {
+        let start = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let repo = Repository::discover(start)
+            .map_err(|e| CollaborationError::Git(format!("{} is not in a git repository: {}", path.display(), e.message())))?;
+        Ok(Self { repo })
+    }
+
+    /// Every revision of `path` on the first-parent history of HEAD that
+    /// changed it, oldest first.
+    pub fn file_history(&self, path: &Path) -> Result<Vec<FileRevision>, CollaborationError> {
+        let relative = self.relative_path(path)?;
+        let git = |e: git2::Error| CollaborationError::Git(e.message().to_string());
+
+        let mut walk = self.repo.revwalk().map_err(git)?;
+        walk.push_head().map_err(git)?;
+        walk.simplify_first_parent().map_err(git)?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE).map_err(git)?;
+
+        let mut revisions = Vec::new();
+        let mut last_blob: Option<Oid> = None;
+        for oid in walk {
+            let commit = self.repo.find_commit(oid.map_err(git)?).map_err(git)?;
+            let blob = commit.tree().map_err(git)?.get_path(&relative).ok().map(|entry| entry.id());
+            if blob == last_blob {
+                continue;
+            }
+            last_blob = blob;
+            let source = match blob {
+                Some(id) => {
+                    let blob = self.repo.find_blob(id).map_err(git)?;
+                    Some(String::from_utf8_lossy(blob.content()).into_owned())
+                }
+                None => None,
+            };
+            revisions.push(FileRevision {
+                commit: oid_short(commit.id()),
+                author: commit.author().name().unwrap_or("unknown").to_string(),
+                date: DateTime::from_timestamp(commit.time().seconds(), 0).unwrap_or_default(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                source,
+            });
+        }
+        Ok(revisions)
+    }
+
+    /// `path` relative to the working directory, as git trees name it.
+    fn relative_path(&self, path: &Path) -> Result<PathBuf, CollaborationError> {
+        let workdir = self
+            .repo
+            .workdir()
+            .ok_or_else(|| CollaborationError::Git("the repository is bare".to_string()))?;
+        // The file itself may be gone: its history is still there
+        let name = path.file_name().ok_or_else(|| CollaborationError::Git(format!("{} is not a file", path.display())))?;
+        let directory = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let (workdir, file) = (workdir.canonicalize()?, directory.canonicalize()?.join(name));
+        file.strip_prefix(&workdir).map(Path::to_path_buf).map_err(|_| {
+            CollaborationError::Git(format!("{} is outside {}", path.display(), workdir.display()))
+        })
+    }
+}
+
+fn oid_short(oid: Oid) -> String {
+    oid.to_string()[..7].to_string()
+}
+
+/// The commit a revision or event comes from.
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionRef {
+    pub commit: String,
+    pub author: String,
+    pub date: DateTime<Utc>,
+    pub summary: String,
+}
+
+impl From<&FileRevision> for RevisionRef {
+    fn from(revision: &FileRevision) -> Self {
+        Self {
+            commit: revision.commit.clone(),
+            author: revision.author.clone(),
+            date: revision.date,
+            summary: revision.summary.clone(),
+        }
+    }
+}
+
+/// The figures `arclang history` follows from revision to revision.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RevisionMetrics {
+    pub requirements: usize,
+    pub components: usize,
+    pub traces: usize,
+    /// Percentage of requirements touched by a trace.
+    pub coverage: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    #[serde(flatten)]
+    pub revision: RevisionRef,
+    /// `None` when the revision deleted the file or does not compile.
+    pub metrics: Option<RevisionMetrics>,
+    /// Why there are no metrics.
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElementChange {
+    Added,
+    Modified,
+    Removed,
+}
+
+/// One revision that changed the followed element.
+#[derive(Debug, Clone, Serialize)]
+pub struct ElementEvent {
+    #[serde(flatten)]
+    pub revision: RevisionRef,
+    pub change: ElementChange,
+    /// What a modification changed.
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelHistory {
+    pub revisions: Vec<HistoryEntry>,
+    /// The element followed with `--element`, and what happened to it.
+    pub element: Option<String>,
+    pub element_events: Vec<ElementEvent>,
+}
+
+/// The model of one revision: `None` when the revision deleted the file,
+/// the first line of the compiler's error when it does not compile.
+fn compile_revision(revision: &FileRevision) -> Result<Option<SemanticModel>, String> {
+    let Some(source) = &revision.source else { return Ok(None) };
+    Compiler::new(CompilerConfig::default())
+        .compile_string(source)
+        .map(|result| Some(result.semantic_model))
+        .map_err(|e| e.to_string().lines().next().unwrap_or_default().to_string())
+}
+
+/// Compile every revision and follow `element` across them. A revision that
+/// does not compile is reported but skipped: the element's next event is
+/// relative to the last revision that did.
+pub fn model_history(revisions: &[FileRevision], element: Option<&str>) -> ModelHistory {
+    let mut history =
+        ModelHistory { revisions: Vec::new(), element: element.map(str::to_string), element_events: Vec::new() };
+    let mut previous: Option<SemanticModel> = None;
+
+    for revision in revisions {
+        let model = match compile_revision(revision) {
+            Ok(model) => model,
+            Err(reason) => {
+                history.revisions.push(HistoryEntry {
+                    revision: revision.into(),
+                    metrics: None,
+                    note: Some(format!("does not compile: {reason}")),
+                });
+                continue;
+            }
+        };
+
+        let (metrics, note) = match &model {
+            Some(model) => {
+                let metrics = model.compute_metrics();
+                let metrics = RevisionMetrics {
+                    requirements: metrics.requirements_count,
+                    components: metrics.components_count,
+                    traces: metrics.traces_count,
+                    coverage: metrics.traceability_coverage,
+                };
+                (Some(metrics), None)
+            }
+            None => (None, Some("file deleted".to_string())),
+        };
+        history.revisions.push(HistoryEntry { revision: revision.into(), metrics, note });
+
+        if let Some(id) = element {
+            let has = |model: &Option<SemanticModel>| model.as_ref().is_some_and(|m| m.all_elements.contains_key(id));
+            let event = match (has(&previous), has(&model)) {
+                (false, true) => Some((ElementChange::Added, Vec::new())),
+                (true, false) => Some((ElementChange::Removed, Vec::new())),
+                (true, true) => {
+                    let report = diff_models(previous.as_ref().unwrap(), model.as_ref().unwrap());
+                    report
+                        .modified
+                        .into_iter()
+                        .find(|m| m.element.id == id)
+                        .map(|m| (ElementChange::Modified, m.changes))
+                }
+                (false, false) => None,
+            };
+            if let Some((change, changes)) = event {
+                history.element_events.push(ElementEvent { revision: revision.into(), change, changes });
+            }
+        }
+        previous = model;
+    }
+    history
+}
+
+/// Where one requirement of the current model comes from.
+#[derive(Debug, Clone, Serialize)]
+pub struct RequirementOrigin {
+    pub id: String,
+    pub title: Option<String>,
+    /// The commit that last introduced it; `None` when it is not committed.
+    pub introduced: Option<RevisionRef>,
+    /// The last commit that changed it.
+    pub last_modified: Option<RevisionRef>,
+    /// What it derives from or refines, and the hazard it mitigates, as
+    /// `derives_from SYS-001`.
+    pub upstream: Vec<String>,
+}
+
+impl RequirementOrigin {
+    /// Nothing upstream justifies the requirement.
+    pub fn unjustified(&self) -> bool {
+        self.upstream.is_empty()
+    }
+}
+
+/// The origin of every requirement of `model` (the file as it is now), in
+/// declaration order, from the file's `revisions`. Edits not committed yet
+/// are not attributed; a requirement deleted and added back was introduced
+/// by the commit that added it back.
+pub fn requirement_origins(model: &SemanticModel, revisions: &[FileRevision]) -> Vec<RequirementOrigin> {
+    let mut introduced: HashMap<String, RevisionRef> = HashMap::new();
+    let mut modified: HashMap<String, RevisionRef> = HashMap::new();
+    let mut previous: Option<SemanticModel> = None;
+
+    for revision in revisions {
+        let Ok(current) = compile_revision(revision) else { continue };
+        match (&previous, &current) {
+            (_, None) => {
+                introduced.clear();
+                modified.clear();
+            }
+            (None, Some(current)) => {
+                for requirement in &current.requirements {
+                    introduced.insert(requirement.id.clone(), revision.into());
+                    modified.insert(requirement.id.clone(), revision.into());
+                }
+            }
+            (Some(previous), Some(current)) => {
+                let report = diff_models(previous, current);
+                for element in report.removed.iter() {
+                    introduced.remove(&element.id);
+                    modified.remove(&element.id);
+                }
+                for element in report.added.iter().filter(|e| e.element_type == "Requirement") {
+                    introduced.insert(element.id.clone(), revision.into());
+                    modified.insert(element.id.clone(), revision.into());
+                }
+                for entry in report.modified.iter().filter(|m| m.element.element_type == "Requirement") {
+                    modified.insert(entry.element.id.clone(), revision.into());
+                }
+            }
+        }
+        previous = current;
+    }
+
+    model
+        .requirements
+        .iter()
+        .map(|requirement| {
+            let mut upstream: Vec<String> = model
+                .traces
+                .iter()
+                .filter(|t| t.from == requirement.id)
+                .filter(|t| matches!(t.link_type, TraceLinkType::DerivedFrom | TraceLinkType::Refines))
+                .map(|t| format!("{} {}", t.trace_type, t.to))
+                .collect();
+            upstream.extend(requirement.hazard.iter().map(|hazard| format!("mitigates {hazard}")));
+            RequirementOrigin {
+                id: requirement.id.clone(),
+                title: requirement.title.clone(),
+                introduced: introduced.get(&requirement.id).cloned(),
+                last_modified: modified.get(&requirement.id).cloned(),
+                upstream,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Signature, Time};
+
+    /// Commit `files` (`None` deletes one) on top of HEAD.
+    fn commit(repo: &Repository, files: &[(&str, Option<&str>)], message: &str) {
+        let workdir = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            match content {
+                Some(content) => {
+                    std::fs::write(workdir.join(path), content).unwrap();
+                    index.add_path(Path::new(path)).unwrap();
+                }
+                None => {
+                    std::fs::remove_file(workdir.join(path)).unwrap();
+                    index.remove_path(Path::new(path)).unwrap();
+                }
+            }
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let time = Time::new(parent.as_ref().map_or(1_700_000_000, |p| p.time().seconds() + 60), 0);
+        let signature = Signature::new("Ada", "ada@example.com", &time).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parent.iter().collect::<Vec<_>>())
+            .unwrap();
+    }
+
+    fn model(requirements: &[(&str, &str)], traced: bool) -> String {
+        let reqs: String = requirements
+            .iter()
+            .map(|(id, description)| format!("    requirement \"{id}\" {{ description: \"{description}\" }}\n"))
+            .collect();
+        let trace = if traced { "trace \"LC-001\" satisfies \"REQ-001\" {}\n" } else { "" };
+        format!(
+            "model Test {{\n}}\nsystem_analysis \"SA\" {{\n{reqs}}}\n\
+             logical_architecture \"LA\" {{\n    component \"Controller\" {{ id: \"LC-001\" }}\n}}\n{trace}"
+        )
+    }
+
+    #[test]
+    fn follows_the_file_and_one_element_across_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit(&repo, &[("model.arc", Some(&model(&[("REQ-001", "brake")], false)))], "Add braking");
+        commit(&repo, &[("notes.txt", Some("unrelated"))], "Add notes");
+        commit(
+            &repo,
+            &[("model.arc", Some(&model(&[("REQ-001", "brake"), ("REQ-002", "warn")], true)))],
+            "Trace braking",
+        );
+        commit(&repo, &[("model.arc", Some("system_analysis {"))], "Break the model");
+        commit(&repo, &[("model.arc", Some(&model(&[("REQ-001", "brake hard")], true)))], "Drop warning");
+        commit(&repo, &[("model.arc", None)], "Remove the model");
+
+        let git = GitManager::discover(&dir.path().join("model.arc")).unwrap();
+        let revisions = git.file_history(&dir.path().join("model.arc")).unwrap();
+        let summaries: Vec<_> = revisions.iter().map(|r| r.summary.as_str()).collect();
+        assert_eq!(summaries, ["Add braking", "Trace braking", "Break the model", "Drop warning", "Remove the model"]);
+        assert!(revisions.iter().all(|r| r.commit.len() == 7 && r.author == "Ada"));
+
+        let history = model_history(&revisions, Some("REQ-001"));
+        let metrics: Vec<_> = history.revisions.iter().map(|r| r.metrics.as_ref().map(|m| (m.requirements, m.traces))).collect();
+        assert_eq!(metrics, [Some((1, 0)), Some((2, 1)), None, Some((1, 1)), None]);
+        assert_eq!(history.revisions[1].metrics.as_ref().unwrap().coverage, 50.0);
+        assert!(history.revisions[2].note.as_deref().unwrap().starts_with("does not compile"));
+        assert_eq!(history.revisions[4].note.as_deref(), Some("file deleted"));
+
+        let events: Vec<_> = history.element_events.iter().map(|e| (e.revision.summary.as_str(), e.change)).collect();
+        assert_eq!(
+            events,
+            [
+                ("Add braking", ElementChange::Added),
+                ("Drop warning", ElementChange::Modified),
+                ("Remove the model", ElementChange::Removed),
+            ]
+        );
+        assert!(history.element_events[1].changes.iter().any(|c| c.new == "brake hard"));
+    }
+
+    #[test]
+    fn requirement_origins_name_the_introducing_and_last_changing_commits() {
+        let source = |extra: &str, description: &str| {
+            format!(
+                "system_analysis \"SA\" {{\n    requirement \"SYS-001\" {{ description: \"stop\" }}\n    \
+                 requirement \"REQ-001\" {{ description: \"{description}\" }}\n{extra}}}\n"
+            )
+        };
+        let derived = "    requirement \"REQ-002\" { description: \"warn\" }\n";
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit(&repo, &[("model.arc", Some(&source("", "brake")))], "Add braking");
+        let traced = |extra: &str, description: &str| {
+            format!("{}trace \"REQ-002\" derives_from \"SYS-001\" {{}}\n", source(extra, description))
+        };
+        commit(&repo, &[("model.arc", Some(&traced(derived, "brake")))], "Derive warning");
+        commit(&repo, &[("model.arc", Some(&traced(derived, "brake hard")))], "Brake harder");
+
+        // Not committed yet
+        let mitigating = "    requirement \"REQ-003\" { description: \"limit\" hazard: \"HAZ-001\" }\n";
+        let current = traced(&format!("{derived}{mitigating}"), "brake hard");
+        let model = Compiler::new(CompilerConfig::default()).compile_string(&current).unwrap().semantic_model;
+        let git = GitManager::discover(&dir.path().join("model.arc")).unwrap();
+        let origins = requirement_origins(&model, &git.file_history(&dir.path().join("model.arc")).unwrap());
+
+        let summary = |r: &Option<RevisionRef>| r.as_ref().map(|r| r.summary.clone());
+        let rows: Vec<_> = origins
+            .iter()
+            .map(|o| (o.id.as_str(), summary(&o.introduced), summary(&o.last_modified), o.upstream.clone()))
+            .collect();
+        let s = |s: &str| Some(s.to_string());
+        assert_eq!(
+            rows,
+            [
+                ("SYS-001", s("Add braking"), s("Add braking"), vec![]),
+                ("REQ-001", s("Add braking"), s("Brake harder"), vec![]),
+                ("REQ-002", s("Derive warning"), s("Derive warning"), vec!["derives_from SYS-001".to_string()]),
+                ("REQ-003", None, None, vec!["mitigates HAZ-001".to_string()]),
+            ]
+        );
+        let unjustified: Vec<_> = origins.iter().filter(|o| o.unjustified()).map(|o| o.id.as_str()).collect();
+        assert_eq!(unjustified, ["SYS-001", "REQ-001"]);
+    }
+
+    #[test]
+    fn a_file_outside_a_repository_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("model.arc"), "").unwrap();
+        let err = GitManager::discover(&dir.path().join("model.arc")).err().expect("not a repository");
+        assert!(err.to_string().contains("is not in a git repository"), "{err}");
+    }
+}
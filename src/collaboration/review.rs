@@ -0,0 +1,266 @@
+//! Review requests for a changeset, persisted one JSON file per review under
+//! `.arclang/reviews/` so they travel with the repository.
+//!
+//! A changeset is identified by the content hash of the model being pushed
+//! and every file it imports: editing any of them after approval yields a new
+//! changeset that needs its own review.
+
+use super::*;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Stable id of a model's content, as recorded in `ReviewRequest::changeset_id`.
+pub fn changeset_id(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    digest.iter().take(6).map(|b| format!("{b:02x}")).collect()
+}
+
+/// [`changeset_id`] of the model at `model` with its whole import tree: each
+/// file's path, relative to the model's directory when it lies below it, and
+/// text, in merge order.
+pub fn model_changeset_id(model: &Path) -> Result<String, CollaborationError> {
+    let sources = crate::compiler::Compiler::import_tree_sources(model)
+        .map_err(|e| CollaborationError::InvalidChangeSet(e.to_string()))?;
+    let base = sources
+        .first()
+        .and_then(|(root, _)| root.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut content = String::new();
+    for (path, text) in &sources {
+        let path = path.strip_prefix(&base).unwrap_or(path);
+        content.push_str(&format!("{}\0{}\0{}\0", path.display(), text.len(), text));
+    }
+    Ok(changeset_id(&content))
+}
+
+impl ReviewRequest {
+    pub fn add_comment(&mut self, comment: ReviewComment) {
+        self.comments.push(comment);
+        self.updated_at = Utc::now();
+        if self.status == ReviewStatus::Pending {
+            self.status = ReviewStatus::InReview;
+        }
+    }
+
+    /// Record a reviewer's verdict, replacing any earlier one of theirs. The
+    /// review is approved once every listed reviewer has approved it.
+    pub fn add_approval(&mut self, approval: Approval) -> Result<(), CollaborationError> {
+        if !self.reviewers.contains(&approval.reviewer) {
+            return Err(CollaborationError::PermissionDenied(format!(
+                "{} is not a reviewer of {}",
+                approval.reviewer, self.id
+            )));
+        }
+
+        let approved = approval.approved;
+        self.approvals.retain(|a| a.reviewer != approval.reviewer);
+        self.approvals.push(approval);
+        self.updated_at = Utc::now();
+
+        if !approved {
+            self.status = ReviewStatus::ChangesRequested;
+        } else if self.outstanding_reviewers().is_empty() {
+            self.status = ReviewStatus::Approved;
+        } else if self.status == ReviewStatus::Pending {
+            self.status = ReviewStatus::InReview;
+        }
+        Ok(())
+    }
+
+    /// Listed reviewers who have not approved (yet).
+    pub fn outstanding_reviewers(&self) -> Vec<&str> {
+        self.reviewers
+            .iter()
+            .filter(|r| !self.approvals.iter().any(|a| a.approved && &a.reviewer == *r))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+pub struct ReviewStore {
+    dir: PathBuf,
+}
+
+impl ReviewStore {
+    pub fn open(project: &Path) -> Self {
+        Self {
+            dir: project.join(".arclang").join("reviews"),
+        }
+    }
+
+    /// Open a new review (`RR-1`, `RR-2`, ...) for a changeset.
+    pub fn create(
+        &self,
+        changeset_id: &str,
+        author: &str,
+        reviewers: Vec<String>,
+    ) -> Result<ReviewRequest, CollaborationError> {
+        if reviewers.is_empty() {
+            return Err(CollaborationError::InvalidChangeSet(
+                "a review needs at least one reviewer".to_string(),
+            ));
+        }
+        let next = self
+            .list()?
+            .iter()
+            .filter_map(|r| r.id.strip_prefix("RR-")?.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let now = Utc::now();
+        let review = ReviewRequest {
+            id: format!("RR-{next}"),
+            changeset_id: changeset_id.to_string(),
+            author: author.to_string(),
+            reviewers,
+            status: ReviewStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            comments: Vec::new(),
+            approvals: Vec::new(),
+        };
+        self.save(&review)?;
+        Ok(review)
+    }
+
+    pub fn load(&self, id: &str) -> Result<ReviewRequest, CollaborationError> {
+        let path = self.path(id);
+        if !path.is_file() {
+            return Err(CollaborationError::InvalidChangeSet(format!("no review named '{id}'")));
+        }
+        let text = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&text)
+            .map_err(|e| CollaborationError::SerializationError(format!("{}: {e}", path.display())))
+    }
+
+    pub fn save(&self, review: &ReviewRequest) -> Result<PathBuf, CollaborationError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(&review.id);
+        let text = serde_json::to_string_pretty(review)
+            .map_err(|e| CollaborationError::SerializationError(e.to_string()))?;
+        std::fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    /// All reviews, oldest first.
+    pub fn list(&self) -> Result<Vec<ReviewRequest>, CollaborationError> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut reviews = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(id) = path.file_stem().and_then(|s| s.to_str()) {
+                reviews.push(self.load(id)?);
+            }
+        }
+        reviews.sort_by_key(|r| r.id.strip_prefix("RR-").and_then(|n| n.parse::<u64>().ok()));
+        Ok(reviews)
+    }
+
+    /// Fail with `ReviewRequired` unless some review of the changeset is
+    /// approved.
+    pub fn require_approval(&self, changeset_id: &str) -> Result<(), CollaborationError> {
+        let approved = self
+            .list()?
+            .iter()
+            .any(|r| r.changeset_id == changeset_id && r.status == ReviewStatus::Approved);
+        if approved {
+            Ok(())
+        } else {
+            Err(CollaborationError::ReviewRequired)
+        }
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approval(reviewer: &str, approved: bool) -> Approval {
+        Approval {
+            reviewer: reviewer.to_string(),
+            timestamp: Utc::now(),
+            approved,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn approved_only_once_every_reviewer_approves() {
+        let project = tempfile::tempdir().unwrap();
+        let store = ReviewStore::open(project.path());
+        let mut review = store
+            .create("abc123", "alice", vec!["bob".to_string(), "carol".to_string()])
+            .unwrap();
+
+        review.add_approval(approval("bob", true)).unwrap();
+        assert_eq!(review.status, ReviewStatus::InReview);
+        assert_eq!(review.outstanding_reviewers(), vec!["carol"]);
+
+        review.add_approval(approval("carol", false)).unwrap();
+        assert_eq!(review.status, ReviewStatus::ChangesRequested);
+
+        review.add_approval(approval("carol", true)).unwrap();
+        assert_eq!(review.status, ReviewStatus::Approved);
+        assert!(review.outstanding_reviewers().is_empty());
+    }
+
+    #[test]
+    fn only_listed_reviewers_can_approve() {
+        let project = tempfile::tempdir().unwrap();
+        let mut review = ReviewStore::open(project.path())
+            .create("abc123", "alice", vec!["bob".to_string()])
+            .unwrap();
+
+        let err = review.add_approval(approval("mallory", true)).unwrap_err();
+
+        assert!(matches!(err, CollaborationError::PermissionDenied(_)));
+        assert_eq!(review.status, ReviewStatus::Pending);
+    }
+
+    #[test]
+    fn push_needs_an_approved_review_of_the_same_changeset() {
+        let project = tempfile::tempdir().unwrap();
+        let store = ReviewStore::open(project.path());
+        let changeset = changeset_id("model v1");
+        assert!(matches!(store.require_approval(&changeset), Err(CollaborationError::ReviewRequired)));
+
+        let mut review = store.create(&changeset, "alice", vec!["bob".to_string()]).unwrap();
+        review.add_approval(approval("bob", true)).unwrap();
+        store.save(&review).unwrap();
+
+        assert_eq!(store.load("RR-1").unwrap().status, ReviewStatus::Approved);
+        store.require_approval(&changeset).unwrap();
+        assert!(store.require_approval(&changeset_id("model v2")).is_err());
+    }
+
+    #[test]
+    fn editing_an_imported_file_needs_a_new_review() {
+        let project = tempfile::tempdir().unwrap();
+        let root = project.path().join("model.arc");
+        let lib = project.path().join("lib.arc");
+        std::fs::write(&root, "import \"lib.arc\"\nrequirements { req \"R-1\" { description: \"root\" } }\n").unwrap();
+        std::fs::write(&lib, "requirements { req \"R-2\" { description: \"v1\" } }\n").unwrap();
+
+        let store = ReviewStore::open(project.path());
+        let mut review = store.create(&model_changeset_id(&root).unwrap(), "alice", vec!["bob".to_string()]).unwrap();
+        review.add_approval(approval("bob", true)).unwrap();
+        store.save(&review).unwrap();
+        store.require_approval(&model_changeset_id(&root).unwrap()).unwrap();
+
+        std::fs::write(&lib, "requirements { req \"R-2\" { description: \"v2\" } }\n").unwrap();
+        assert!(matches!(
+            store.require_approval(&model_changeset_id(&root).unwrap()),
+            Err(CollaborationError::ReviewRequired)
+        ));
+    }
+}
@@ -0,0 +1,526 @@
+//! Three-way merge of compiled models by element identity.
+//!
+//! Each requirement, component and trace is merged on its own: a change made
+//! on one side only is taken as-is, the same change on both sides is taken
+//! once, and only edits that overlap on the same element are conflicts. Two
+//! branches that touch different elements of one `.arc` file therefore merge
+//! cleanly even when a textual merge would not.
+
+use super::*;
+use crate::compiler::semantic::SemanticModel;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone)]
+pub struct MergeOptions {
+    pub policy: ConflictResolutionPolicy,
+    /// Names of the two sides (branch, changeset or file) used in conflict
+    /// descriptions.
+    pub ours_label: String,
+    pub theirs_label: String,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            policy: ConflictResolutionPolicy::Manual,
+            ours_label: "ours".to_string(),
+            theirs_label: "theirs".to_string(),
+        }
+    }
+}
+
+pub fn merge(base: &SemanticModel, ours: &SemanticModel, theirs: &SemanticModel) -> MergeResult {
+    merge_with(base, ours, theirs, &MergeOptions::default())
+}
+
+pub fn merge_with(
+    base: &SemanticModel,
+    ours: &SemanticModel,
+    theirs: &SemanticModel,
+    options: &MergeOptions,
+) -> MergeResult {
+    let mut conflicts = Vec::new();
+    let mut merged_changes = Vec::new();
+    let mut auto_resolved = Vec::new();
+    let mut warnings = Vec::new();
+
+    let duplicates = duplicate_ids(base, ours, theirs);
+    let auto_rename = matches!(
+        options.policy,
+        ConflictResolutionPolicy::AutoResolveNonSemantic | ConflictResolutionPolicy::AutoResolveAll
+    );
+    for id in &duplicates {
+        let ours_info = &ours.all_elements[id];
+        let theirs_info = &theirs.all_elements[id];
+        let (ours_name, theirs_name) = (display_name(ours, id), display_name(theirs, id));
+        let element_type = element_type_of(&ours_info.element_type);
+
+        if !auto_rename {
+            conflicts.push(Conflict {
+                id: format!("CONF-{id}"),
+                conflict_type: ConflictType::DuplicateId,
+                element_id: id.clone(),
+                element_type,
+                base_value: None,
+                ours_value: Some(element_value(ours, id)),
+                theirs_value: Some(element_value(theirs, id)),
+                description: format!(
+                    "'{id}' was added independently in {} ({} '{}') and in {} ({} '{}')",
+                    options.ours_label,
+                    ours_info.element_type,
+                    ours_name,
+                    options.theirs_label,
+                    theirs_info.element_type,
+                    theirs_name,
+                ),
+                resolution: None,
+            });
+            continue;
+        }
+
+        // Keep both: ours keeps the id, theirs gets the first free suffix.
+        let renamed = (2..)
+            .map(|n| format!("{id}-{n}"))
+            .find(|candidate| {
+                [base, ours, theirs].iter().all(|m| !m.all_elements.contains_key(candidate))
+            })
+            .expect("an unbounded range yields a free id");
+        let mut theirs_value = element_value(theirs, id);
+        if let Some(object) = theirs_value.as_object_mut() {
+            object.insert("id".to_string(), Value::String(renamed.clone()));
+        }
+
+        // Clearly different elements are safe to split; the same kind and
+        // name on both sides is more likely one element edited twice.
+        let confidence = if ours_info.element_type != theirs_info.element_type {
+            0.9
+        } else if ours_name != theirs_name {
+            0.7
+        } else {
+            0.4
+        };
+        auto_resolved.push(AutoResolution {
+            element_id: id.clone(),
+            conflict_type: ConflictType::DuplicateId,
+            strategy: ResolutionStrategy::KeepBoth,
+            confidence,
+        });
+        merged_changes.push(ModelChange {
+            change_type: ChangeType::Added,
+            element_id: id.clone(),
+            element_type: element_type.clone(),
+            old_value: None,
+            new_value: Some(element_value(ours, id)),
+        });
+        merged_changes.push(ModelChange {
+            change_type: ChangeType::Added,
+            element_id: renamed.clone(),
+            element_type,
+            old_value: None,
+            new_value: Some(theirs_value),
+        });
+        warnings.push(format!(
+            "'{id}' was added in both {} and {}; renamed the one from {} to '{renamed}' \
+             (references to it in {} still name '{id}')",
+            options.ours_label, options.theirs_label, options.theirs_label, options.theirs_label,
+        ));
+    }
+
+    for element_type in [ElementType::Requirement, ElementType::Component, ElementType::Trace] {
+        let base_elements = elements(base, &element_type);
+        let ours_elements = elements(ours, &element_type);
+        let theirs_elements = elements(theirs, &element_type);
+
+        let ids: BTreeSet<&String> = base_elements
+            .keys()
+            .chain(ours_elements.keys())
+            .chain(theirs_elements.keys())
+            .collect();
+
+        for id in ids.into_iter().filter(|id| !duplicates.contains(*id)) {
+            match merge_element(
+                id,
+                &element_type,
+                base_elements.get(id),
+                ours_elements.get(id),
+                theirs_elements.get(id),
+                options,
+            ) {
+                ElementMergeOutcome::NoConflict(Some(change)) => merged_changes.push(change),
+                ElementMergeOutcome::NoConflict(None) => {}
+                ElementMergeOutcome::Conflict(conflict) => conflicts.push(conflict),
+            }
+        }
+    }
+
+    // The policy settles what it can; the rest stays for a person.
+    if options.policy != ConflictResolutionPolicy::Manual {
+        let resolutions = conflict_resolution::resolve(&conflicts, options.policy.clone());
+        for (conflict, resolution) in conflicts.iter_mut().zip(resolutions) {
+            if resolution.resolution_strategy == ResolutionStrategy::Manual {
+                continue;
+            }
+            merged_changes.extend(resolved_change(conflict, &resolution));
+            conflict.resolution = Some(resolution);
+        }
+    }
+
+    warnings.extend(dangling_traces(base, ours, theirs, &merged_changes));
+
+    MergeResult {
+        success: conflicts.iter().all(|c| c.resolution.is_some()),
+        conflicts,
+        auto_resolved,
+        merged_changes,
+        warnings,
+    }
+}
+
+/// The elements of one kind keyed by identity. Traces have no id of their
+/// own; `from -[type]-> to` identifies one.
+fn elements(model: &SemanticModel, element_type: &ElementType) -> BTreeMap<String, Value> {
+    fn to_value(element: &impl serde::Serialize) -> Value {
+        serde_json::to_value(element).unwrap_or(Value::Null)
+    }
+    match element_type {
+        ElementType::Requirement => model
+            .requirements
+            .iter()
+            .map(|r| (r.id.clone(), to_value(r)))
+            .collect(),
+        ElementType::Component => model
+            .components
+            .iter()
+            .map(|c| (c.id.clone(), to_value(c)))
+            .collect(),
+        ElementType::Trace => model
+            .traces
+            .iter()
+            .map(|t| (trace_key(&t.from, &t.trace_type, &t.to), to_value(t)))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+/// Ids both sides introduced independently for different elements, of any
+/// kind: a requirement on one side and a component on the other collide too.
+fn duplicate_ids(base: &SemanticModel, ours: &SemanticModel, theirs: &SemanticModel) -> BTreeSet<String> {
+    ours.all_elements
+        .keys()
+        .filter(|id| theirs.all_elements.contains_key(*id) && !base.all_elements.contains_key(*id))
+        .filter(|id| element_value(ours, id) != element_value(theirs, id))
+        .cloned()
+        .collect()
+}
+
+/// The fullest record the model has for `id`.
+fn element_value(model: &SemanticModel, id: &str) -> Value {
+    let value = if let Some(requirement) = model.requirements.iter().find(|r| r.id == id) {
+        serde_json::to_value(requirement)
+    } else if let Some(component) = model.components.iter().find(|c| c.id == id) {
+        serde_json::to_value(component)
+    } else {
+        serde_json::to_value(&model.all_elements[id])
+    };
+    value.unwrap_or(Value::Null)
+}
+
+/// Requirements are registered under their id; their title names them.
+fn display_name<'a>(model: &'a SemanticModel, id: &str) -> &'a str {
+    model
+        .requirements
+        .iter()
+        .find(|r| r.id == id)
+        .and_then(|r| r.title.as_deref())
+        .unwrap_or(&model.all_elements[id].name)
+}
+
+fn element_type_of(element_type: &str) -> ElementType {
+    match element_type {
+        "Requirement" => ElementType::Requirement,
+        "Component" | "SystemComponent" => ElementType::Component,
+        "Function" | "SystemFunction" | "Activity" | "OperationalActivity" => ElementType::Function,
+        "ComponentPort" | "FunctionPort" | "PhysicalPort" | "PhysicalLink" => ElementType::Interface,
+        "OperationalCapability" | "Mission" => ElementType::Capability,
+        "Actor" | "Entity" => ElementType::Actor,
+        "Scenario" => ElementType::Scenario,
+        "ExchangeItem" | "Class" => ElementType::DataType,
+        _ => ElementType::Other,
+    }
+}
+
+fn trace_key(from: &str, trace_type: &str, to: &str) -> String {
+    format!("{from} -[{trace_type}]-> {to}")
+}
+
+enum ElementMergeOutcome {
+    /// The merged change relative to base; `None` when the element ends up
+    /// as it was in base.
+    NoConflict(Option<ModelChange>),
+    Conflict(Conflict),
+}
+
+fn merge_element(
+    id: &str,
+    element_type: &ElementType,
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    options: &MergeOptions,
+) -> ElementMergeOutcome {
+    let (ours_label, theirs_label) = (&options.ours_label, &options.theirs_label);
+    // One side left the element as it was: the other side's version wins.
+    let merged = if ours == theirs || theirs == base {
+        ours
+    } else if ours == base {
+        theirs
+    } else {
+        let (conflict_type, description) = match (base, ours, theirs) {
+            (None, _, _) => (
+                ConflictType::SemanticConflict,
+                format!("'{id}' was added with different content in {ours_label} and {theirs_label}"),
+            ),
+            (Some(_), None, _) => (
+                ConflictType::DeleteModify,
+                format!("'{id}' was deleted in {ours_label} but modified in {theirs_label}"),
+            ),
+            (Some(_), _, None) => (
+                ConflictType::DeleteModify,
+                format!("'{id}' was modified in {ours_label} but deleted in {theirs_label}"),
+            ),
+            (Some(_), Some(_), Some(_)) => (
+                ConflictType::SemanticConflict,
+                format!("'{id}' was modified differently in {ours_label} and {theirs_label}"),
+            ),
+        };
+        return ElementMergeOutcome::Conflict(Conflict {
+            id: format!("CONF-{id}"),
+            conflict_type,
+            element_id: id.to_string(),
+            element_type: element_type.clone(),
+            base_value: base.cloned(),
+            ours_value: ours.cloned(),
+            theirs_value: theirs.cloned(),
+            description,
+            resolution: None,
+        });
+    };
+
+    let change_type = match (base, merged) {
+        (None, None) => return ElementMergeOutcome::NoConflict(None),
+        (Some(b), Some(m)) if b == m => return ElementMergeOutcome::NoConflict(None),
+        (None, Some(_)) => ChangeType::Added,
+        (Some(_), None) => ChangeType::Deleted,
+        (Some(_), Some(_)) => ChangeType::Modified,
+    };
+    ElementMergeOutcome::NoConflict(Some(ModelChange {
+        change_type,
+        element_id: id.to_string(),
+        element_type: element_type.clone(),
+        old_value: base.cloned(),
+        new_value: merged.cloned(),
+    }))
+}
+
+/// The change a settled conflict makes relative to base; `None` when the
+/// element ends up as it was, or both sides are kept and the duplicate needs
+/// renaming first.
+fn resolved_change(conflict: &Conflict, resolution: &ConflictResolution) -> Option<ModelChange> {
+    if resolution.resolution_strategy == ResolutionStrategy::KeepBoth {
+        return None;
+    }
+    let resolved = Some(&resolution.resolved_value).filter(|v| !v.is_null());
+    let change_type = match (conflict.base_value.as_ref(), resolved) {
+        (None, None) => return None,
+        (Some(b), Some(r)) if b == r => return None,
+        (None, Some(_)) => ChangeType::Added,
+        (Some(_), None) => ChangeType::Deleted,
+        (Some(_), Some(_)) => ChangeType::Modified,
+    };
+    Some(ModelChange {
+        change_type,
+        element_id: conflict.element_id.clone(),
+        element_type: conflict.element_type.clone(),
+        old_value: conflict.base_value.clone(),
+        new_value: resolved.cloned(),
+    })
+}
+
+/// Traces the merge keeps whose endpoint the merge deletes: each side was
+/// consistent, but the combination is not.
+fn dangling_traces(
+    base: &SemanticModel,
+    ours: &SemanticModel,
+    theirs: &SemanticModel,
+    merged_changes: &[ModelChange],
+) -> Vec<String> {
+    let changed = |element_type: ElementType, change_type: ChangeType| -> BTreeSet<&str> {
+        merged_changes
+            .iter()
+            .filter(|c| c.element_type == element_type && c.change_type == change_type)
+            .map(|c| c.element_id.as_str())
+            .collect()
+    };
+    let deleted_elements: BTreeSet<&str> = changed(ElementType::Requirement, ChangeType::Deleted)
+        .union(&changed(ElementType::Component, ChangeType::Deleted))
+        .copied()
+        .collect();
+    let deleted_traces = changed(ElementType::Trace, ChangeType::Deleted);
+
+    let mut seen = BTreeSet::new();
+    base.traces
+        .iter()
+        .chain(&ours.traces)
+        .chain(&theirs.traces)
+        .filter(|t| !deleted_traces.contains(trace_key(&t.from, &t.trace_type, &t.to).as_str()))
+        .filter(|t| deleted_elements.contains(t.from.as_str()) || deleted_elements.contains(t.to.as_str()))
+        .filter_map(|t| {
+            let key = trace_key(&t.from, &t.trace_type, &t.to);
+            seen.insert(key.clone())
+                .then(|| format!("trace {key} references an element the merge deletes"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn model(requirements: &[(&str, &str)], traces: &str) -> SemanticModel {
+        let reqs: String = requirements
+            .iter()
+            .map(|(id, description)| format!("  req \"{id}\" \"{id}\" {{ description: \"{description}\" }}\n"))
+            .collect();
+        let source = format!(
+            "requirements system {{\n{reqs}}}\n\
+             architecture logical {{\n  component \"Controller\" {{ id: \"LC-1\" }}\n}}\n{traces}"
+        );
+        Compiler::new(CompilerConfig::default())
+            .compile_string(&source)
+            .expect("test model compiles")
+            .semantic_model
+    }
+
+    #[test]
+    fn non_overlapping_changes_merge_cleanly() {
+        let base = model(&[("R-1", "brake"), ("R-2", "steer")], "");
+        let ours = model(&[("R-1", "brake hard"), ("R-2", "steer")], "");
+        let theirs = model(&[("R-1", "brake"), ("R-2", "steer"), ("R-3", "signal")], "");
+
+        let result = merge(&base, &ours, &theirs);
+
+        assert!(result.success, "unexpected conflicts: {:?}", result.conflicts);
+        let changes: Vec<_> = result
+            .merged_changes
+            .iter()
+            .map(|c| (c.element_id.as_str(), c.change_type.clone()))
+            .collect();
+        assert_eq!(changes, vec![("R-1", ChangeType::Modified), ("R-3", ChangeType::Added)]);
+    }
+
+    #[test]
+    fn overlapping_edits_conflict() {
+        let base = model(&[("R-1", "brake")], "");
+        let ours = model(&[("R-1", "brake hard")], "");
+        let theirs = model(&[("R-1", "brake gently")], "");
+
+        let result = merge(&base, &ours, &theirs);
+
+        assert!(!result.success);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].conflict_type, ConflictType::SemanticConflict);
+        assert_eq!(result.conflicts[0].element_id, "R-1");
+    }
+
+    #[test]
+    fn delete_against_modify_conflicts() {
+        let base = model(&[("R-1", "brake"), ("R-2", "steer")], "");
+        let ours = model(&[("R-2", "steer")], "");
+        let theirs = model(&[("R-1", "brake hard"), ("R-2", "steer")], "");
+
+        let result = merge(&base, &ours, &theirs);
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].conflict_type, ConflictType::DeleteModify);
+        assert!(result.conflicts[0].ours_value.is_none());
+    }
+
+    #[test]
+    fn independently_added_ids_are_duplicate_id_conflicts() {
+        let base = model(&[("R-1", "brake")], "");
+        let ours = model(&[("R-1", "brake"), ("R-9", "signal")], "");
+        let theirs = model(&[("R-1", "brake"), ("R-9", "honk")], "");
+        let options = MergeOptions {
+            ours_label: "feature/signal".to_string(),
+            theirs_label: "feature/horn".to_string(),
+            ..MergeOptions::default()
+        };
+
+        let result = merge_with(&base, &ours, &theirs, &options);
+
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.conflict_type, ConflictType::DuplicateId);
+        assert!(conflict.description.contains("feature/signal"), "got: {}", conflict.description);
+        assert!(conflict.description.contains("feature/horn"), "got: {}", conflict.description);
+    }
+
+    #[test]
+    fn duplicate_ids_are_renamed_when_auto_resolving() {
+        let base = model(&[("R-1", "brake")], "");
+        let ours = model(&[("R-1", "brake"), ("R-9", "signal")], "");
+        let theirs = model(&[("R-1", "brake"), ("R-9", "honk")], "");
+        let options = MergeOptions {
+            policy: ConflictResolutionPolicy::AutoResolveNonSemantic,
+            ..MergeOptions::default()
+        };
+
+        let result = merge_with(&base, &ours, &theirs, &options);
+
+        assert!(result.success, "unexpected conflicts: {:?}", result.conflicts);
+        assert_eq!(result.auto_resolved.len(), 1);
+        assert_eq!(result.auto_resolved[0].conflict_type, ConflictType::DuplicateId);
+        let added: Vec<_> = result.merged_changes.iter().map(|c| c.element_id.as_str()).collect();
+        assert_eq!(added, vec!["R-9", "R-9-2"]);
+        assert_eq!(result.merged_changes[1].new_value.as_ref().unwrap()["id"], "R-9-2");
+        assert_eq!(result.merged_changes[1].new_value.as_ref().unwrap()["description"], "honk");
+    }
+
+    #[test]
+    fn the_policy_settles_conflicts_it_covers() {
+        let base = model(&[("R-1", "brake")], "");
+        let ours = model(&[("R-1", "brake hard")], "");
+        let theirs = model(&[("R-1", "brake gently")], "");
+        let with = |policy| merge_with(&base, &ours, &theirs, &MergeOptions { policy, ..MergeOptions::default() });
+
+        let result = with(ConflictResolutionPolicy::AutoResolveNonSemantic);
+        assert!(!result.success);
+        assert!(result.conflicts[0].resolution.is_none());
+
+        let result = with(ConflictResolutionPolicy::PreferTheirs);
+        assert!(result.success, "unexpected conflicts: {:?}", result.conflicts);
+        let resolution = result.conflicts[0].resolution.as_ref().expect("settled");
+        assert_eq!(resolution.resolution_strategy, ResolutionStrategy::KeepTheirs);
+        assert_eq!(result.merged_changes.len(), 1);
+        assert_eq!(result.merged_changes[0].change_type, ChangeType::Modified);
+        assert_eq!(result.merged_changes[0].new_value.as_ref().unwrap()["description"], "brake gently");
+    }
+
+    #[test]
+    fn trace_to_a_deleted_element_is_a_warning() {
+        let base = model(&[("R-1", "brake"), ("R-2", "steer")], "");
+        let ours = model(&[("R-2", "steer")], "");
+        let theirs = model(
+            &[("R-1", "brake"), ("R-2", "steer")],
+            "trace \"LC-1\" satisfies \"R-1\" {}\n",
+        );
+
+        let result = merge(&base, &ours, &theirs);
+
+        assert!(result.success);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("R-1"), "got: {:?}", result.warnings);
+    }
+}
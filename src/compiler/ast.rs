@@ -1,11 +1,13 @@
-use std::collections::HashMap;
-use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+use serde::{Serialize, Serializer, Deserialize};
+use super::diagnostic::SourceSpan;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Model {
     /// Model-level metadata (name, version, author, ...) from the model header
     /// and its `metadata` block.
     #[serde(default)]
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
     /// `import "path.arc"` declarations, resolved relative to the importing
     /// file by `Compiler::compile_file` (a string-compiled model must not
@@ -28,6 +30,23 @@ pub struct Model {
     /// Verification cases tracing to requirements (V&V).
     #[serde(default)]
     pub test_cases: Vec<TestCase>,
+    /// Where each named element is declared (its name token, or the value of
+    /// its `id:` attribute), keyed by the declared name or id; the first
+    /// declaration wins.
+    #[serde(skip)]
+    pub declarations: HashMap<String, SourceSpan>,
+    /// Names and ids declared in more than one file of a multi-file model,
+    /// found by `merge`.
+    #[serde(skip)]
+    pub redeclarations: Vec<Redeclaration>,
+}
+
+/// A name or id declared in two files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redeclaration {
+    pub name: String,
+    pub first: SourceSpan,
+    pub again: SourceSpan,
 }
 
 impl Model {
@@ -48,18 +67,47 @@ impl Model {
             data_types: Vec::new(),
             classes: Vec::new(),
             test_cases: Vec::new(),
+            declarations: HashMap::new(),
+            redeclarations: Vec::new(),
         }
     }
     
+    /// Attribute the trace and declaration spans parsed from one file to
+    /// that file.
+    pub fn set_source_file(&mut self, file: &std::path::Path) {
+        let traces = self
+            .traces
+            .iter_mut()
+            .chain(self.operational_analysis.iter_mut().flat_map(|oa| oa.traces.iter_mut()));
+        let trace_spans = traces.flat_map(|trace| [&mut trace.from_span, &mut trace.to_span].into_iter().flatten());
+        for span in trace_spans.chain(self.declarations.values_mut()) {
+            span.file.get_or_insert_with(|| file.to_path_buf());
+        }
+    }
+
     /// Merge an imported model into this one: every collection is appended
     /// in import order; model-header attributes of the ROOT file win (an
-    /// imported fragment only fills keys the root did not set). Identity
-    /// collisions across files surface through the semantic analyzer's
-    /// duplicate-id warnings, exactly as within a single file.
+    /// imported fragment only fills keys the root did not set). A name
+    /// declared in both files is recorded in `redeclarations`; the semantic
+    /// analyzer reports those that are element ids.
     pub fn merge(&mut self, other: Model) {
         for (key, value) in other.attributes {
             self.attributes.entry(key).or_insert(value);
         }
+        for (name, span) in other.declarations {
+            match self.declarations.get(&name) {
+                Some(first) if first.file != span.file => self.redeclarations.push(Redeclaration {
+                    name,
+                    first: first.clone(),
+                    again: span,
+                }),
+                Some(_) => {}
+                None => {
+                    self.declarations.insert(name, span);
+                }
+            }
+        }
+        self.redeclarations.extend(other.redeclarations);
         self.operational_analysis.extend(other.operational_analysis);
         self.system_analysis.extend(other.system_analysis);
         self.logical_architecture.extend(other.logical_architecture);
@@ -75,6 +123,82 @@ impl Model {
         self.test_cases.extend(other.test_cases);
     }
 
+    /// The model restricted to the declarations `within` accepts, by `id:`
+    /// attribute, id or name, and to the traces, exchanges and links between
+    /// them: the scoped view the AST-based diagram generators draw. Blocks
+    /// are kept, emptied of what lies outside.
+    pub fn pruned(&self, within: &dyn Fn(&str) -> bool) -> Model {
+        let declared = |attributes: &HashMap<String, AttributeValue>, ids: &[&str]| {
+            attributes
+                .get("id")
+                .and_then(|v| v.as_string())
+                .into_iter()
+                .chain(ids.iter().copied())
+                .any(|key| !key.is_empty() && within(key))
+        };
+        let between = |from: &str, to: &str| within(from) && within(to);
+
+        let mut model = self.clone();
+        for oa in &mut model.operational_analysis {
+            oa.actors.retain(|a| declared(&a.attributes, &[a.id.as_deref().unwrap_or_default(), &a.name]));
+            oa.entities.retain(|e| declared(&e.attributes, &[&e.id, &e.name]));
+            let activity = |a: &OperationalActivity| declared(&a.attributes, &[&a.id, &a.name]);
+            for entity in &mut oa.entities {
+                retain_tree(&mut entity.activities, |a| &mut a.sub_activities, &activity);
+            }
+            retain_tree(&mut oa.activities, |a| &mut a.sub_activities, &activity);
+            retain_tree(&mut oa.capabilities, |c| &mut c.children, &|c| declared(&c.attributes, &[&c.id, &c.name]));
+            oa.exchanges.retain(|x| between(&x.from, &x.to));
+            oa.capability_associations.retain(|x| between(&x.from, &x.to));
+            oa.traces.retain(|t| between(&t.from, &t.to));
+            oa.processes.retain(|c| declared(&c.attributes, &[&c.id]));
+            oa.communication_means.retain(|x| between(&x.from, &x.to));
+        }
+        for sa in &mut model.system_analysis {
+            sa.requirements.retain(|r| declared(&r.attributes, &[&r.id]));
+            retain_tree(&mut sa.functions, |f| &mut f.sub_functions, &|f| declared(&f.attributes, &[&f.id, &f.name]));
+            sa.components.retain(|c| declared(&c.attributes, &[&c.name]));
+            sa.external_actors.retain(|a| declared(&a.attributes, &[&a.id, &a.name]));
+            sa.functional_exchanges.retain(|x| between(&x.from_port, &x.to_port));
+            sa.missions.retain(|m| declared(&m.attributes, &[&m.id]));
+            sa.capabilities.retain(|c| declared(&c.attributes, &[&c.id]));
+            sa.functional_chains.retain(|c| declared(&c.attributes, &[&c.id]));
+        }
+        for la in &mut model.logical_architecture {
+            retain_tree(&mut la.components, |c| &mut c.sub_components, &|c| declared(&c.attributes, &[&c.id, &c.name]));
+            la.interfaces.retain(|i| between(&i.from, &i.to));
+            la.component_exchanges.retain(|x| between(&x.from_port, &x.to_port));
+            la.unallocated_functions.retain(|f| within(f));
+            la.capability_realizations.retain(|c| declared(&c.attributes, &[&c.id]));
+            la.functional_chains.retain(|c| declared(&c.attributes, &[&c.id]));
+        }
+        for pa in &mut model.physical_architecture {
+            pa.nodes.retain(|n| declared(&n.attributes, &[&n.name]));
+            for node in &mut pa.nodes {
+                node.deployments.retain(|d| within(&d.component));
+            }
+            pa.links.retain(|l| between(&l.from, &l.to));
+            pa.physical_exchanges.retain(|x| between(&x.from, &x.to));
+            pa.paths.retain(|p| declared(&p.attributes, &[&p.id, &p.name]));
+        }
+        for epbs in &mut model.epbs {
+            epbs.systems.retain(|s| declared(&s.attributes, &[&s.name]));
+        }
+        for safety in &mut model.safety_analysis {
+            safety.hazards.retain(|h| declared(&h.attributes, &[&h.name]));
+            safety.fmea.retain(|f| declared(&f.attributes, &[&f.name]));
+            safety.fmea_actions.retain(|a| within(&a.entry));
+        }
+        model.traces.retain(|t| between(&t.from, &t.to));
+        model.state_machines.retain(|m| within(&m.name));
+        model.scenarios.retain(|s| within(&s.name));
+        model.exchange_items.retain(|i| within(&i.id) || within(&i.name));
+        model.data_types.retain(|t| within(&t.id) || within(&t.name));
+        model.classes.retain(|c| declared(&c.attributes, &[&c.id, &c.name]));
+        model.test_cases.retain(|t| t.verifies.iter().any(|r| within(r)));
+        model
+    }
+
     /// Export the model to JSON string for diagram rendering
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
@@ -115,6 +239,7 @@ pub struct OperationalEntity {
     pub name: String,
     pub entity_type: EntityType,
     pub activities: Vec<OperationalActivity>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -125,6 +250,7 @@ pub struct Entity {
     pub entity_type: EntityType,
     pub icon: String,
     pub description: Option<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -140,6 +266,7 @@ pub struct Actor {
     pub name: String,
     pub id: Option<String>,
     pub icon: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -151,6 +278,7 @@ pub struct OperationalCapability {
     pub color: Option<String>,
     pub stereotype: Option<String>,
     pub children: Vec<OperationalCapability>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -170,6 +298,7 @@ pub struct OperationalActivity {
     pub icon: String,
     pub color: String,
     pub sub_activities: Vec<OperationalActivity>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -180,6 +309,7 @@ pub struct OperationalExchange {
     pub data_type: String,
     pub label: Option<String>,
     pub protocol: Option<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -212,6 +342,7 @@ pub struct SystemAnalysis {
 pub struct Mission {
     pub id: String,
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -227,6 +358,7 @@ pub struct Capability {
     pub realizes: Option<String>,
     /// The mission this capability contributes to, when declared.
     pub mission: Option<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -238,12 +370,14 @@ pub struct FunctionalChain {
     pub name: String,
     /// Ordered references to the involved functions/exchanges.
     pub involves: Vec<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Requirement {
     pub id: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -256,6 +390,7 @@ pub struct SystemFunction {
     pub icon: Option<String>,
     pub ports: Vec<FunctionPort>,
     pub sub_functions: Vec<SystemFunction>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -295,6 +430,7 @@ pub struct ExternalActor {
     pub id: String,
     pub name: String,
     pub color: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -309,6 +445,7 @@ pub struct FunctionalExchange {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemComponent {
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -337,6 +474,7 @@ pub struct LogicalComponent {
     pub functions: Vec<LogicalFunction>,
     pub interfaces_in: Vec<InterfaceDefinition>,
     pub interfaces_out: Vec<InterfaceDefinition>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -345,6 +483,9 @@ pub struct ComponentPort {
     pub name: String,
     pub direction: PortDirection,
     pub interface_type: String,
+    /// A required port must be the end of at least one `connect`.
+    #[serde(default)]
+    pub required: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -360,12 +501,14 @@ pub struct InterfaceDefinition {
     pub name: String,
     pub protocol: Option<String>,
     pub format: Option<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogicalFunction {
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -374,6 +517,7 @@ pub struct LogicalInterface {
     pub name: String,
     pub from: String,
     pub to: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -395,6 +539,7 @@ pub struct PhysicalPath {
     pub name: String,
     /// Ordered references to the physical links forming the path.
     pub involves: Vec<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -412,12 +557,14 @@ pub struct PhysicalNode {
     /// Physical ports are NOT oriented (Arcadia).
     #[serde(default)]
     pub ports: Vec<PhysicalPort>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhysicalPort {
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -448,6 +595,7 @@ pub struct HardwareComponent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deployment {
     pub component: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -461,6 +609,7 @@ pub struct PhysicalLink {
     pub bandwidth: Option<String>,
     pub color: Option<String>,
     pub connections: Vec<String>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -484,6 +633,7 @@ pub struct Epbs {
 pub struct EpbsSystem {
     pub name: String,
     pub subsystems: Vec<EpbsSubsystem>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -491,12 +641,14 @@ pub struct EpbsSystem {
 pub struct EpbsSubsystem {
     pub name: String,
     pub items: Vec<EpbsItem>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpbsItem {
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -504,18 +656,33 @@ pub struct EpbsItem {
 pub struct SafetyAnalysis {
     pub hazards: Vec<Hazard>,
     pub fmea: Vec<FmeaEntry>,
+    /// Mitigation actions recorded against generated FMEA entries.
+    #[serde(default)]
+    pub fmea_actions: Vec<FmeaAction>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hazard {
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FmeaEntry {
     pub name: String,
+    #[serde(serialize_with = "sorted_map")]
+    pub attributes: HashMap<String, AttributeValue>,
+}
+
+/// `fmea_action { for: "FMEA-012" owner: "..." due: "2025-06-01" action: "..." }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FmeaAction {
+    /// Id of the FMEA entry the action mitigates.
+    pub entry: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -524,7 +691,27 @@ pub struct Trace {
     pub from: String,
     pub to: String,
     pub trace_type: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
+    /// Source positions of the endpoints, for located diagnostics.
+    #[serde(skip)]
+    pub from_span: Option<SourceSpan>,
+    #[serde(skip)]
+    pub to_span: Option<SourceSpan>,
+}
+
+/// Keep the elements of a tree `keep` accepts, and their ancestors.
+fn retain_tree<T>(items: &mut Vec<T>, children: fn(&mut T) -> &mut Vec<T>, keep: &dyn Fn(&T) -> bool) {
+    items.retain_mut(|item| {
+        retain_tree(children(item), children, keep);
+        !children(item).is_empty() || keep(item)
+    });
+}
+
+/// Serialize a hash map in key order, so that exporting the same model twice
+/// gives the same bytes.
+pub(crate) fn sorted_map<S: Serializer, V: Serialize>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -533,7 +720,7 @@ pub enum AttributeValue {
     Number(f64),
     Boolean(bool),
     List(Vec<AttributeValue>),
-    Map(HashMap<String, AttributeValue>),
+    Map(#[serde(serialize_with = "sorted_map")] HashMap<String, AttributeValue>),
 }
 
 impl AttributeValue {
@@ -550,6 +737,31 @@ impl AttributeValue {
             _ => None,
         }
     }
+
+    /// `true` / `false`, which the parser reads as bare identifiers.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttributeValue::Boolean(b) => Some(*b),
+            AttributeValue::String(s) if s == "true" => Some(true),
+            AttributeValue::String(s) if s == "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Key prefix of a custom attribute in an element's `attributes`:
+/// `attr safety_classification: "SC-2"` is stored as
+/// `attr.safety_classification`.
+pub const CUSTOM_ATTRIBUTE_PREFIX: &str = "attr.";
+
+/// The custom (`attr key: value`) attributes among `attributes`, by key
+/// without the prefix.
+pub fn custom_attributes(
+    attributes: &HashMap<String, AttributeValue>,
+) -> impl Iterator<Item = (&str, &AttributeValue)> {
+    attributes
+        .iter()
+        .filter_map(|(key, value)| Some((key.strip_prefix(CUSTOM_ATTRIBUTE_PREFIX)?, value)))
 }
 
 // Behavioral Models
@@ -708,6 +920,7 @@ pub struct TestCase {
     pub verifies: Vec<String>,
     /// Verification method: test | analysis | inspection | demonstration.
     pub method: String,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
@@ -719,6 +932,7 @@ pub struct ClassDef {
     pub name: String,
     /// Fields: name -> type.
     pub fields: Vec<DataAttribute>,
+    #[serde(serialize_with = "sorted_map")]
     pub attributes: HashMap<String, AttributeValue>,
 }
 
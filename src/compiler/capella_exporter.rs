@@ -0,0 +1,440 @@
+//! Capella model export (`arclang export -f capella`, and the `capella`
+//! compile target): a `.capella` melodymodeller resource that Capella 7.0
+//! opens as a project, with one architecture package per Arcadia layer.
+//!
+//! | ArcLang | Capella |
+//! |---|---|
+//! | operational actor / entity | `oa:Entity` (`actor="true"` for actors) |
+//! | operational activity | `oa:OperationalActivity`, allocated to its entity |
+//! | system component | `ctx:SystemComponent` |
+//! | system function, functional exchange | `ctx:SystemFunction`, `fa:FunctionalExchange` |
+//! | logical component, port | `la:LogicalComponent`, `fa:ComponentPort` |
+//! | logical function | `la:LogicalFunction`, allocated to its component |
+//! | component exchange, `connect` | `fa:ComponentExchange` |
+//! | physical node, port | `pa:PhysicalComponent` (`nature="NODE"`), `cs:PhysicalPort` |
+//! | `deploys` | `pa.deployment:PartDeploymentLink` |
+//!
+//! An element's Capella `id` is its stable ArcLang UUID and its `sid` the
+//! ArcLang id, so exporting an edited model again updates the same Capella
+//! elements. The packages, root functions, parts, ports and allocations
+//! Capella needs get UUIDs derived from their owner's.
+//!
+//! Requirements and traces are not written: Capella takes requirements
+//! through the Requirements viewpoint's ReqIF import (`-f reqif`).
+
+use super::ast::PortDirection;
+use super::identity::element_uuid;
+use super::semantic::{ComponentInfo, FunctionInfo, SemanticModel};
+use super::xml::esc;
+
+const CAPELLAMODELLER: &str = "org.polarsys.capella.core.data.capellamodeller";
+const CS: &str = "org.polarsys.capella.core.data.cs";
+const CTX: &str = "org.polarsys.capella.core.data.ctx";
+const FA: &str = "org.polarsys.capella.core.data.fa";
+const LA: &str = "org.polarsys.capella.core.data.la";
+const OA: &str = "org.polarsys.capella.core.data.oa";
+const PA: &str = "org.polarsys.capella.core.data.pa";
+const DEPLOYMENT: &str = "org.polarsys.capella.core.data.pa.deployment";
+
+pub fn export_capella(model: &SemanticModel) -> String {
+    let name = model.name.as_deref().unwrap_or("ArcLang Model");
+    let project = element_uuid("capella-project", name);
+    let mut out = CapellaWriter { model, xml: String::new(), depth: 0 };
+
+    out.xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\n<!--Capella_Version_7.0.0-->\n");
+    out.xml.push_str(&format!(
+        "<{CAPELLAMODELLER}:Project xmi:version=\"2.0\" xmlns:xmi=\"http://www.omg.org/XMI\"\n    \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n"
+    ));
+    for (prefix, path) in [
+        (CAPELLAMODELLER, "modeller"),
+        (CS, "cs"),
+        (CTX, "ctx"),
+        (FA, "fa"),
+        (LA, "la"),
+        (OA, "oa"),
+        (PA, "pa"),
+        (DEPLOYMENT, "pa/deployment"),
+    ] {
+        out.xml.push_str(&format!("    xmlns:{prefix}=\"http://www.polarsys.org/capella/core/{path}/7.0.0\"\n"));
+    }
+    out.xml.push_str(&format!("    id=\"{}\" name=\"{}\">\n", project, esc(name)));
+    out.depth = 1;
+
+    let engineering = derived("engineering", &project);
+    out.open("ownedModelRoots", &format!("{CAPELLAMODELLER}:SystemEngineering"), &engineering, &[("name", name)]);
+    out.operational_analysis(&engineering);
+    out.system_analysis(&engineering);
+    out.logical_architecture(&engineering);
+    out.physical_architecture(&engineering);
+    out.close("ownedModelRoots");
+
+    out.xml.push_str(&format!("</{CAPELLAMODELLER}:Project>\n"));
+    out.xml
+}
+
+/// UUID of an element Capella needs but ArcLang does not declare, from the
+/// element it belongs to.
+fn derived(role: &str, owner: &str) -> String {
+    element_uuid(&format!("capella-{role}"), owner)
+}
+
+struct CapellaWriter<'a> {
+    model: &'a SemanticModel,
+    xml: String,
+    depth: usize,
+}
+
+impl<'a> CapellaWriter<'a> {
+    fn start(&mut self, tag: &str, kind: &str, id: &str, attributes: &[(&str, &str)]) {
+        self.xml.push_str(&"  ".repeat(self.depth));
+        self.xml.push_str(&format!("<{} xsi:type=\"{}\" id=\"{}\"", tag, kind, id));
+        for (key, value) in attributes {
+            self.xml.push_str(&format!(" {}=\"{}\"", key, esc(value)));
+        }
+    }
+
+    fn open(&mut self, tag: &str, kind: &str, id: &str, attributes: &[(&str, &str)]) {
+        self.start(tag, kind, id, attributes);
+        self.xml.push_str(">\n");
+        self.depth += 1;
+    }
+
+    fn empty(&mut self, tag: &str, kind: &str, id: &str, attributes: &[(&str, &str)]) {
+        self.start(tag, kind, id, attributes);
+        self.xml.push_str("/>\n");
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.xml.push_str(&format!("{}</{}>\n", "  ".repeat(self.depth), tag));
+    }
+
+    /// The element's stable UUID.
+    fn uuid(&self, id: &str) -> String {
+        self.model
+            .all_elements
+            .get(id)
+            .map(|element| element.uuid.clone())
+            .unwrap_or_else(|| element_uuid("element", id))
+    }
+
+    fn element_type(&self, id: &str) -> &str {
+        self.model.all_elements.get(id).map(|e| e.element_type.as_str()).unwrap_or("")
+    }
+
+    /// The system function an exchange end names, directly or by one of
+    /// its ports.
+    fn system_function<'e>(&self, endpoint: &'e str) -> Option<&'e str> {
+        match self.element_type(endpoint) {
+            "SystemFunction" => Some(endpoint),
+            "FunctionPort" => endpoint.rsplit_once('.').map(|(function, _)| function),
+            _ => None,
+        }
+    }
+
+    fn components(&self, level: &str) -> Vec<&'a ComponentInfo> {
+        self.model.components.iter().filter(|c| c.level == level).collect()
+    }
+
+    /// The functions a component lists, by id or name.
+    fn functions_of(&self, component: &ComponentInfo) -> Vec<&'a FunctionInfo> {
+        component
+            .functions
+            .iter()
+            .filter_map(|f| {
+                self.model
+                    .functions
+                    .iter()
+                    .find(|function| &function.id == f)
+                    .or_else(|| self.model.functions.iter().find(|function| &function.name == f))
+            })
+            .collect()
+    }
+
+    /// A `ComponentFunctionalAllocation` of `function` to `component`.
+    fn allocation(&mut self, component: &str, function: &str) {
+        self.empty(
+            "ownedFunctionalAllocation",
+            &format!("{FA}:ComponentFunctionalAllocation"),
+            &derived("allocation", &format!("{component}:{function}")),
+            &[("targetElement", &format!("#{function}")), ("sourceElement", &format!("#{component}"))],
+        );
+    }
+
+    /// A `Part` typed by `component`, so it can be placed in diagrams and
+    /// deployed.
+    fn part(&mut self, component: &ComponentInfo) {
+        let uuid = self.uuid(&component.id);
+        self.empty(
+            "ownedParts",
+            &format!("{CS}:Part"),
+            &derived("part", &uuid),
+            &[("name", &component.name), ("abstractType", &format!("#{uuid}"))],
+        );
+    }
+
+    fn operational_analysis(&mut self, engineering: &str) {
+        let id = derived("operational-analysis", engineering);
+        let (activities, entities): (Vec<_>, Vec<_>) = self
+            .components("Operational")
+            .into_iter()
+            .filter(|c| matches!(self.element_type(&c.id), "Actor" | "Entity" | "OperationalActivity"))
+            .partition(|c| self.element_type(&c.id) == "OperationalActivity");
+
+        self.open("ownedArchitectures", &format!("{OA}:OperationalAnalysis"), &id, &[("name", "Operational Analysis")]);
+        let package = derived("function-package", &id);
+        self.open("ownedFunctionPkg", &format!("{OA}:OperationalActivityPkg"), &package, &[("name", "Operational Activities")]);
+        let root = derived("root-function", &id);
+        self.open("ownedOperationalActivities", &format!("{OA}:OperationalActivity"), &root, &[("name", "Root Operational Activity")]);
+        for entity in &entities {
+            for activity in self.functions_of(entity) {
+                let uuid = self.uuid(&activity.id);
+                self.empty("ownedFunctions", &format!("{OA}:OperationalActivity"), &uuid, &[("name", &activity.name), ("sid", &activity.id)]);
+            }
+        }
+        for activity in &activities {
+            let uuid = self.uuid(&activity.id);
+            self.empty("ownedFunctions", &format!("{OA}:OperationalActivity"), &uuid, &[("name", &activity.name), ("sid", &activity.id)]);
+        }
+        self.close("ownedOperationalActivities");
+        self.close("ownedFunctionPkg");
+
+        let package = derived("component-package", &id);
+        self.open("ownedEntityPkg", &format!("{OA}:EntityPkg"), &package, &[("name", "Operational Entities")]);
+        for entity in &entities {
+            let uuid = self.uuid(&entity.id);
+            let actor = self.element_type(&entity.id) == "Actor";
+            let mut attributes = vec![("name", entity.name.as_str()), ("sid", entity.id.as_str())];
+            if actor {
+                attributes.push(("actor", "true"));
+            }
+            let activities = self.functions_of(entity);
+            if activities.is_empty() {
+                self.empty("ownedEntities", &format!("{OA}:Entity"), &uuid, &attributes);
+                continue;
+            }
+            self.open("ownedEntities", &format!("{OA}:Entity"), &uuid, &attributes);
+            for activity in activities {
+                let activity = self.uuid(&activity.id);
+                self.allocation(&uuid, &activity);
+            }
+            self.close("ownedEntities");
+        }
+        self.close("ownedEntityPkg");
+        self.close("ownedArchitectures");
+    }
+
+    fn system_analysis(&mut self, engineering: &str) {
+        let id = derived("system-analysis", engineering);
+        let mut functions: Vec<_> = self
+            .model
+            .all_elements
+            .values()
+            .filter(|e| e.element_type == "SystemFunction")
+            .collect();
+        functions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        // Functional exchanges join an output port of one function to an
+        // input port of another.
+        let exchanges: Vec<(String, &str, &str, &str)> = self
+            .model
+            .interfaces
+            .iter()
+            .filter_map(|exchange| {
+                let (from, to) = (self.system_function(&exchange.from)?, self.system_function(&exchange.to)?);
+                let uuid = element_uuid("capella-functional-exchange", &format!("{}:{}->{}", exchange.name, exchange.from, exchange.to));
+                Some((uuid, from, to, exchange.name.as_str()))
+            })
+            .collect();
+
+        self.open("ownedArchitectures", &format!("{CTX}:SystemAnalysis"), &id, &[("name", "System Analysis")]);
+        let package = derived("function-package", &id);
+        self.open("ownedFunctionPkg", &format!("{CTX}:SystemFunctionPkg"), &package, &[("name", "System Functions")]);
+        let root = derived("root-function", &id);
+        self.open("ownedSystemFunctions", &format!("{CTX}:SystemFunction"), &root, &[("name", "Root System Function")]);
+        for f in &functions {
+            let uuid = f.uuid.clone();
+            let outputs: Vec<_> = exchanges.iter().filter(|(_, from, _, _)| *from == f.id).collect();
+            let inputs: Vec<_> = exchanges.iter().filter(|(_, _, to, _)| *to == f.id).collect();
+            let attributes = [("name", f.name.as_str()), ("sid", f.id.as_str())];
+            if outputs.is_empty() && inputs.is_empty() {
+                self.empty("ownedFunctions", &format!("{CTX}:SystemFunction"), &uuid, &attributes);
+                continue;
+            }
+            self.open("ownedFunctions", &format!("{CTX}:SystemFunction"), &uuid, &attributes);
+            for (exchange, ..) in &outputs {
+                self.empty("outputs", &format!("{FA}:FunctionOutputPort"), &derived("output", exchange), &[]);
+            }
+            for (exchange, ..) in &inputs {
+                self.empty("inputs", &format!("{FA}:FunctionInputPort"), &derived("input", exchange), &[]);
+            }
+            self.close("ownedFunctions");
+        }
+        for (exchange, _, _, name) in &exchanges {
+            self.empty(
+                "ownedFunctionalExchanges",
+                &format!("{FA}:FunctionalExchange"),
+                exchange,
+                &[
+                    ("name", name),
+                    ("source", &format!("#{}", derived("output", exchange))),
+                    ("target", &format!("#{}", derived("input", exchange))),
+                ],
+            );
+        }
+        self.close("ownedSystemFunctions");
+        self.close("ownedFunctionPkg");
+
+        let package = derived("component-package", &id);
+        self.open("ownedSystemComponentPkg", &format!("{CTX}:SystemComponentPkg"), &package, &[("name", "Structure")]);
+        let components = self.components("System");
+        for component in &components {
+            self.part(component);
+        }
+        for component in &components {
+            let uuid = self.uuid(&component.id);
+            self.empty(
+                "ownedSystemComponents",
+                &format!("{CTX}:SystemComponent"),
+                &uuid,
+                &[("name", &component.name), ("sid", &component.id)],
+            );
+        }
+        self.close("ownedSystemComponentPkg");
+        self.close("ownedArchitectures");
+    }
+
+    fn logical_architecture(&mut self, engineering: &str) {
+        let id = derived("logical-architecture", engineering);
+        let components = self.components("Logical");
+
+        self.open("ownedArchitectures", &format!("{LA}:LogicalArchitecture"), &id, &[("name", "Logical Architecture")]);
+        let package = derived("function-package", &id);
+        self.open("ownedFunctionPkg", &format!("{LA}:LogicalFunctionPkg"), &package, &[("name", "Logical Functions")]);
+        let root = derived("root-function", &id);
+        self.open("ownedLogicalFunctions", &format!("{LA}:LogicalFunction"), &root, &[("name", "Root Logical Function")]);
+        for component in &components {
+            for function in self.functions_of(component) {
+                let uuid = self.uuid(&function.id);
+                self.empty("ownedFunctions", &format!("{LA}:LogicalFunction"), &uuid, &[("name", &function.name), ("sid", &function.id)]);
+            }
+        }
+        self.close("ownedLogicalFunctions");
+        self.close("ownedFunctionPkg");
+
+        let package = derived("component-package", &id);
+        self.open("ownedLogicalComponentPkg", &format!("{LA}:LogicalComponentPkg"), &package, &[("name", "Structure")]);
+        for component in &components {
+            self.part(component);
+        }
+        for component in &components {
+            let uuid = self.uuid(&component.id);
+            self.open(
+                "ownedLogicalComponents",
+                &format!("{LA}:LogicalComponent"),
+                &uuid,
+                &[("name", &component.name), ("sid", &component.id)],
+            );
+            for port in self.model.ports.iter().filter(|p| p.component == component.id) {
+                let orientation = match port.direction {
+                    PortDirection::In => "IN",
+                    PortDirection::Out => "OUT",
+                    PortDirection::InOut => "INOUT",
+                };
+                let port_uuid = self.uuid(&format!("{}.{}", component.id, port.name));
+                self.empty(
+                    "ownedFeatures",
+                    &format!("{FA}:ComponentPort"),
+                    &port_uuid,
+                    &[("name", &port.name), ("orientation", orientation), ("kind", "FLOW")],
+                );
+            }
+            for function in self.functions_of(component) {
+                let function = self.uuid(&function.id);
+                self.allocation(&uuid, &function);
+            }
+            self.close("ownedLogicalComponents");
+        }
+        for connection in &self.model.connections {
+            let logical = |id: &str| components.iter().any(|c| c.id == id);
+            if !logical(&connection.from_component) || !logical(&connection.to_component) {
+                continue;
+            }
+            let source = self.uuid(&format!("{}.{}", connection.from_component, connection.from_port));
+            let target = self.uuid(&format!("{}.{}", connection.to_component, connection.to_port));
+            let name = connection
+                .label
+                .clone()
+                .unwrap_or_else(|| format!("{} -> {}", connection.from_port, connection.to_port));
+            self.empty(
+                "ownedComponentExchanges",
+                &format!("{FA}:ComponentExchange"),
+                &derived("component-exchange", &format!("{source}->{target}")),
+                &[("name", &name), ("source", &format!("#{source}")), ("target", &format!("#{target}")), ("kind", "FLOW")],
+            );
+        }
+        self.close("ownedLogicalComponentPkg");
+        self.close("ownedArchitectures");
+    }
+
+    /// Nodes with their ports. A deployment links the node's part to the
+    /// part of the deployed logical component.
+    fn physical_architecture(&mut self, engineering: &str) {
+        let id = derived("physical-architecture", engineering);
+        let nodes = self.components("Physical");
+
+        self.open("ownedArchitectures", &format!("{PA}:PhysicalArchitecture"), &id, &[("name", "Physical Architecture")]);
+        let package = derived("component-package", &id);
+        self.open("ownedPhysicalComponentPkg", &format!("{PA}:PhysicalComponentPkg"), &package, &[("name", "Structure")]);
+        for node in &nodes {
+            let uuid = self.uuid(&node.id);
+            let part = derived("part", &uuid);
+            let deployed: Vec<String> = self
+                .model
+                .deployments
+                .iter()
+                .filter(|d| d.node == node.id && self.model.components.iter().any(|c| c.id == d.component))
+                .map(|d| derived("part", &self.uuid(&d.component)))
+                .collect();
+            let attributes = [("name", node.name.as_str()), ("abstractType", &format!("#{uuid}"))];
+            if deployed.is_empty() {
+                self.empty("ownedParts", &format!("{CS}:Part"), &part, &attributes);
+                continue;
+            }
+            self.open("ownedParts", &format!("{CS}:Part"), &part, &attributes);
+            for component in &deployed {
+                self.empty(
+                    "ownedDeploymentLinks",
+                    &format!("{DEPLOYMENT}:PartDeploymentLink"),
+                    &derived("deployment", &format!("{part}->{component}")),
+                    &[("deployedElement", &format!("#{component}")), ("location", &format!("#{part}"))],
+                );
+            }
+            self.close("ownedParts");
+        }
+        for node in &nodes {
+            let uuid = self.uuid(&node.id);
+            let prefix = format!("{}.", node.id);
+            let mut ports: Vec<_> = self
+                .model
+                .all_elements
+                .values()
+                .filter(|e| e.element_type == "PhysicalPort" && e.id.starts_with(&prefix))
+                .collect();
+            ports.sort_by(|a, b| a.id.cmp(&b.id));
+            let attributes = [("name", node.name.as_str()), ("sid", node.id.as_str()), ("nature", "NODE")];
+            if ports.is_empty() {
+                self.empty("ownedPhysicalComponents", &format!("{PA}:PhysicalComponent"), &uuid, &attributes);
+                continue;
+            }
+            self.open("ownedPhysicalComponents", &format!("{PA}:PhysicalComponent"), &uuid, &attributes);
+            for port in ports {
+                self.empty("ownedFeatures", &format!("{CS}:PhysicalPort"), &port.uuid, &[("name", &port.name)]);
+            }
+            self.close("ownedPhysicalComponents");
+        }
+        self.close("ownedPhysicalComponentPkg");
+        self.close("ownedArchitectures");
+    }
+}
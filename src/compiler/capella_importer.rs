@@ -10,12 +10,31 @@ impl CapellaImporter {
         Self
     }
     
+    /// Import a `.capella` model. A `.aird` only holds the diagrams, so the
+    /// `.capella` resource it references is imported instead.
     pub fn import_file<P: AsRef<Path>>(&self, path: P) -> Result<Model, CompilerError> {
+        let path = path.as_ref();
         let xml_content = std::fs::read_to_string(path)?;
+        if path.extension().is_some_and(|ext| ext == "aird") {
+            if let Some(resource) = semantic_resource(&xml_content) {
+                return self.import_file(path.with_file_name(resource));
+            }
+        }
         self.import_string(&xml_content)
     }
     
+    /// Import Capella XMI: the operational, system, logical and physical
+    /// layers of a `.capella` model, or the flat requirements/components
+    /// layout when the document has no `ownedArchitectures`.
     pub fn import_string(&self, xml_content: &str) -> Result<Model, CompilerError> {
+        if xml_content.contains("<ownedArchitectures") {
+            let document = XmiElement::parse(xml_content)?;
+            return Ok(XmiModel::new(&document).import());
+        }
+        self.import_flat(xml_content)
+    }
+
+    fn import_flat(&self, xml_content: &str) -> Result<Model, CompilerError> {
         use quick_xml::Reader;
         use quick_xml::events::Event;
         
@@ -116,6 +135,8 @@ impl CapellaImporter {
         Ok(Model {
             classes: Vec::new(),
             test_cases: Vec::new(),
+            declarations: std::collections::HashMap::new(),
+            redeclarations: Vec::new(),
             attributes: std::collections::HashMap::new(),
             imports: Vec::new(),
             operational_analysis: Vec::new(),
@@ -261,6 +282,8 @@ impl CapellaImporter {
             to,
             trace_type,
             attributes,
+            from_span: None,
+            to_span: None,
         }))
     }
 }
@@ -273,6 +296,531 @@ enum Section {
     Traces,
 }
 
+/// The `semanticResources` entry of a `.aird` that names its `.capella` model.
+fn semantic_resource(aird: &str) -> Option<String> {
+    aird.split("<semanticResources>")
+        .skip(1)
+        .filter_map(|rest| rest.split('<').next())
+        .find(|resource| resource.ends_with(".capella"))
+        .map(|resource| resource.replace("%20", " "))
+}
+
+/// An element of a Capella XMI document.
+#[derive(Debug, Default)]
+struct XmiElement {
+    tag: String,
+    attributes: HashMap<String, String>,
+    children: Vec<XmiElement>,
+}
+
+impl XmiElement {
+    /// The document as a tree under a nameless root element.
+    fn parse(xml_content: &str) -> Result<Self, CompilerError> {
+        use quick_xml::events::{BytesStart, Event};
+        use quick_xml::{Reader, XmlVersion};
+
+        fn element(start: &BytesStart) -> Result<XmiElement, CompilerError> {
+            let mut attributes = HashMap::new();
+            for attr in start.attributes() {
+                let attr = attr.map_err(|e| CompilerError::Semantic(format!("Attribute error: {}", e)))?;
+                let value = attr.normalized_value(XmlVersion::Implicit1_0)
+                    .map_err(|e| CompilerError::Semantic(format!("XML unescape error: {}", e)))?;
+                attributes.insert(String::from_utf8_lossy(attr.key.as_ref()).into_owned(), value.into_owned());
+            }
+            Ok(XmiElement {
+                tag: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+                attributes,
+                children: Vec::new(),
+            })
+        }
+
+        let mut reader = Reader::from_str(xml_content);
+        let mut buf = Vec::new();
+        let mut open = vec![XmiElement::default()];
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => open.push(element(e)?),
+                Ok(Event::Empty(ref e)) => {
+                    let child = element(e)?;
+                    if let Some(parent) = open.last_mut() {
+                        parent.children.push(child);
+                    }
+                }
+                Ok(Event::End(_)) if open.len() > 1 => {
+                    let child = open.pop().unwrap_or_default();
+                    if let Some(parent) = open.last_mut() {
+                        parent.children.push(child);
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(CompilerError::Semantic(format!("XML parse error: {}", e))),
+                _ => {}
+            }
+            buf.clear();
+        }
+        if open.len() != 1 {
+            return Err(CompilerError::Semantic("XML parse error: unclosed element".to_string()));
+        }
+        Ok(open.swap_remove(0))
+    }
+
+    fn attr(&self, key: &str) -> &str {
+        self.attributes.get(key).map(String::as_str).unwrap_or("")
+    }
+
+    /// The `xsi:type` without its package: `LogicalComponent`.
+    fn kind(&self) -> &str {
+        self.attr("xsi:type").rsplit(':').next().unwrap_or("")
+    }
+
+    fn id(&self) -> &str {
+        self.attr("id")
+    }
+
+    /// The name, or the id of an unnamed element.
+    fn label(&self) -> &str {
+        match self.attr("name") {
+            "" => self.id(),
+            name => name,
+        }
+    }
+
+    /// The ids `key` refers to (`"#id #id"`).
+    fn references(&self, key: &str) -> impl Iterator<Item = &str> {
+        self.attr(key)
+            .split_whitespace()
+            .map(|reference| reference.rsplit('#').next().unwrap_or(reference))
+    }
+
+    fn reference(&self, key: &str) -> Option<&str> {
+        self.references(key).next()
+    }
+
+    fn children_of(&self, kind: &str) -> impl Iterator<Item = &XmiElement> {
+        let kind = kind.to_string();
+        self.children.iter().filter(move |child| child.kind() == kind)
+    }
+
+    /// Every descendant of `kind`, in document order.
+    fn all(&self, kind: &str) -> Vec<&XmiElement> {
+        let mut found = Vec::new();
+        for child in &self.children {
+            if child.kind() == kind {
+                found.push(child);
+            }
+            found.extend(child.all(kind));
+        }
+        found
+    }
+
+    /// The outermost descendants of `kind`: elements of `kind` nested in
+    /// another one are left to it.
+    fn outermost(&self, kind: &str) -> Vec<&XmiElement> {
+        let mut found = Vec::new();
+        for child in &self.children {
+            if child.kind() == kind {
+                found.push(child);
+            } else {
+                found.extend(child.outermost(kind));
+            }
+        }
+        found
+    }
+}
+
+/// A Capella model, its elements indexed by id, mapped layer by layer onto
+/// the ArcLang AST. Capella ids are kept as ArcLang ids, and references
+/// between elements use them.
+struct XmiModel<'a> {
+    document: &'a XmiElement,
+    elements: HashMap<&'a str, &'a XmiElement>,
+    parents: HashMap<&'a str, &'a XmiElement>,
+}
+
+impl<'a> XmiModel<'a> {
+    fn new(document: &'a XmiElement) -> Self {
+        fn index<'a>(
+            element: &'a XmiElement,
+            elements: &mut HashMap<&'a str, &'a XmiElement>,
+            parents: &mut HashMap<&'a str, &'a XmiElement>,
+        ) {
+            for child in &element.children {
+                if !child.id().is_empty() {
+                    elements.insert(child.id(), child);
+                    parents.insert(child.id(), element);
+                }
+                index(child, elements, parents);
+            }
+        }
+
+        let mut elements = HashMap::new();
+        let mut parents = HashMap::new();
+        index(document, &mut elements, &mut parents);
+        Self { document, elements, parents }
+    }
+
+    fn import(&self) -> Model {
+        let mut model = Model::new();
+        model.operational_analysis = self
+            .document
+            .all("OperationalAnalysis")
+            .into_iter()
+            .map(|architecture| self.operational_analysis(architecture))
+            .collect();
+        model.system_analysis = self
+            .document
+            .all("SystemAnalysis")
+            .into_iter()
+            .map(|architecture| self.system_analysis(architecture))
+            .collect();
+        model.logical_architecture = self
+            .document
+            .all("LogicalArchitecture")
+            .into_iter()
+            .map(|architecture| self.logical_architecture(architecture))
+            .collect();
+        model.physical_architecture = self
+            .document
+            .all("PhysicalArchitecture")
+            .into_iter()
+            .map(|architecture| self.physical_architecture(architecture))
+            .collect();
+        model
+    }
+
+    fn operational_analysis(&self, architecture: &'a XmiElement) -> OperationalAnalysis {
+        let mut actors = Vec::new();
+        let mut entities = Vec::new();
+        let mut performed = Vec::new();
+        for entity in architecture.all("Entity") {
+            if entity.attr("actor") == "true" {
+                actors.push(Actor {
+                    name: entity.label().to_string(),
+                    id: Some(entity.id().to_string()),
+                    icon: "person".to_string(),
+                    attributes: HashMap::new(),
+                });
+                continue;
+            }
+            let activities: Vec<_> = self
+                .allocated(entity, "OperationalActivity")
+                .into_iter()
+                .map(operational_activity)
+                .collect();
+            performed.extend(activities.iter().map(|activity| activity.id.clone()));
+            entities.push(OperationalEntity {
+                id: entity.id().to_string(),
+                name: entity.label().to_string(),
+                entity_type: EntityType::System,
+                activities,
+                attributes: id_attribute(entity),
+            });
+        }
+        let activities = self
+            .functions(architecture, "OperationalActivity")
+            .into_iter()
+            .filter(|activity| !performed.iter().any(|id| id == activity.id()))
+            .map(operational_activity)
+            .collect();
+
+        let exchange = |exchange: &XmiElement, data_type: &str| {
+            let (from, to) = self.ends(exchange)?;
+            Some(OperationalExchange {
+                from,
+                to,
+                data_type: data_type.to_string(),
+                label: Some(exchange.label().to_string()),
+                protocol: None,
+                attributes: id_attribute(exchange),
+            })
+        };
+        let exchanges = architecture
+            .all("FunctionalExchange")
+            .into_iter()
+            .filter_map(|e| exchange(e, "Data"))
+            .collect();
+        let communication_means = architecture
+            .all("CommunicationMean")
+            .into_iter()
+            .filter_map(|e| exchange(e, "CommunicationMeans"))
+            .collect();
+
+        OperationalAnalysis {
+            name: architecture.label().to_string(),
+            actors,
+            entities,
+            capabilities: Vec::new(),
+            activities,
+            exchanges,
+            capability_associations: Vec::new(),
+            traces: Vec::new(),
+            processes: Vec::new(),
+            communication_means,
+        }
+    }
+
+    fn system_analysis(&self, architecture: &'a XmiElement) -> SystemAnalysis {
+        let functions = self
+            .functions(architecture, "SystemFunction")
+            .into_iter()
+            .map(|function| SystemFunction {
+                id: function.id().to_string(),
+                name: function.label().to_string(),
+                category: FunctionCategory::System,
+                color: Some("#70AD47".to_string()),
+                icon: None,
+                ports: Vec::new(),
+                sub_functions: Vec::new(),
+                attributes: id_attribute(function),
+            })
+            .collect();
+        let (actors, components): (Vec<_>, Vec<_>) = architecture
+            .all("SystemComponent")
+            .into_iter()
+            .partition(|component| component.attr("actor") == "true");
+        let functional_exchanges = architecture
+            .all("FunctionalExchange")
+            .into_iter()
+            .filter_map(|exchange| {
+                let (from_port, to_port) = self.ends(exchange)?;
+                Some(FunctionalExchange {
+                    from_port,
+                    to_port,
+                    data_type: "Data".to_string(),
+                    label: Some(exchange.label().to_string()),
+                })
+            })
+            .collect();
+
+        SystemAnalysis {
+            name: architecture.label().to_string(),
+            requirements: Vec::new(),
+            functions,
+            components: components
+                .into_iter()
+                .map(|component| SystemComponent {
+                    name: component.label().to_string(),
+                    attributes: id_attribute(component),
+                })
+                .collect(),
+            external_actors: actors
+                .into_iter()
+                .map(|actor| ExternalActor {
+                    id: actor.id().to_string(),
+                    name: actor.label().to_string(),
+                    color: "#FFFFFF".to_string(),
+                    attributes: id_attribute(actor),
+                })
+                .collect(),
+            functional_exchanges,
+            missions: Vec::new(),
+            capabilities: Vec::new(),
+            functional_chains: Vec::new(),
+        }
+    }
+
+    fn logical_architecture(&self, architecture: &'a XmiElement) -> LogicalArchitecture {
+        let components = architecture
+            .outermost("LogicalComponent")
+            .into_iter()
+            .map(|component| self.logical_component(component))
+            .collect();
+        // Component exchanges join ports: `Component.port`
+        let component_exchanges = architecture
+            .all("ComponentExchange")
+            .into_iter()
+            .filter_map(|exchange| {
+                Some(ComponentExchange {
+                    from_port: self.port_endpoint(exchange.reference("source")?)?,
+                    to_port: self.port_endpoint(exchange.reference("target")?)?,
+                    exchange_item: "Data".to_string(),
+                    label: Some(exchange.label().to_string()),
+                })
+            })
+            .collect();
+
+        LogicalArchitecture {
+            name: architecture.label().to_string(),
+            components,
+            interfaces: Vec::new(),
+            component_exchanges,
+            unallocated_functions: Vec::new(),
+            capability_realizations: Vec::new(),
+            functional_chains: Vec::new(),
+        }
+    }
+
+    fn logical_component(&self, component: &'a XmiElement) -> LogicalComponent {
+        LogicalComponent {
+            id: component.id().to_string(),
+            name: component.label().to_string(),
+            component_type: "Logical".to_string(),
+            color: Some("#5B9BD5".to_string()),
+            sub_components: component
+                .outermost("LogicalComponent")
+                .into_iter()
+                .map(|sub| self.logical_component(sub))
+                .collect(),
+            allocated_functions: Vec::new(),
+            ports: component
+                .children_of("ComponentPort")
+                .map(|port| ComponentPort {
+                    name: port.label().to_string(),
+                    direction: match port.attr("orientation") {
+                        "IN" => PortDirection::In,
+                        "OUT" => PortDirection::Out,
+                        _ => PortDirection::InOut,
+                    },
+                    interface_type: "Data".to_string(),
+                    required: false,
+                })
+                .collect(),
+            functions: self
+                .allocated(component, "LogicalFunction")
+                .into_iter()
+                .map(|function| LogicalFunction {
+                    name: function.label().to_string(),
+                    attributes: id_attribute(function),
+                })
+                .collect(),
+            interfaces_in: Vec::new(),
+            interfaces_out: Vec::new(),
+            attributes: id_attribute(component),
+        }
+    }
+
+    /// Every physical component becomes a node (ArcLang nodes do not nest);
+    /// part deployments become `deploys` of the hosting node.
+    fn physical_architecture(&self, architecture: &'a XmiElement) -> PhysicalArchitecture {
+        let mut nodes: Vec<PhysicalNode> = architecture
+            .all("PhysicalComponent")
+            .into_iter()
+            .map(|component| PhysicalNode {
+                id: component.id().to_string(),
+                name: component.label().to_string(),
+                node_type: NodeType::Hardware,
+                color: Some("#FFE699".to_string()),
+                processor: None,
+                memory: None,
+                behavior_components: Vec::new(),
+                hardware_components: Vec::new(),
+                deployments: Vec::new(),
+                ports: component
+                    .children_of("PhysicalPort")
+                    .map(|port| PhysicalPort { name: port.label().to_string(), attributes: HashMap::new() })
+                    .collect(),
+                attributes: id_attribute(component),
+            })
+            .collect();
+        for link in architecture.all("PartDeploymentLink") {
+            let host = link.reference("location").and_then(|id| self.owner(id));
+            let deployed = link.reference("deployedElement").and_then(|id| self.owner(id));
+            let (Some(host), Some(deployed)) = (host, deployed) else { continue };
+            if let Some(node) = nodes.iter_mut().find(|node| node.id == host.id()) {
+                node.deployments.push(Deployment {
+                    component: deployed.id().to_string(),
+                    attributes: HashMap::new(),
+                });
+            }
+        }
+        // A physical link joins two physical ports: `linkEnds="#a #b"`
+        let links = architecture
+            .all("PhysicalLink")
+            .into_iter()
+            .filter_map(|link| {
+                let mut ends = link.references("linkEnds").filter_map(|id| self.owner(id));
+                let (from, to) = (ends.next()?.id().to_string(), ends.next()?.id().to_string());
+                Some(PhysicalLink {
+                    name: link.label().to_string(),
+                    connections: vec![from.clone(), to.clone()],
+                    from,
+                    to,
+                    protocol: "Unknown".to_string(),
+                    bandwidth: None,
+                    color: None,
+                    attributes: id_attribute(link),
+                })
+            })
+            .collect();
+
+        PhysicalArchitecture {
+            name: architecture.label().to_string(),
+            nodes,
+            links,
+            physical_exchanges: Vec::new(),
+            paths: Vec::new(),
+        }
+    }
+
+    /// The element an exchange or link end stands for: a port's owner, a
+    /// part's type, or the element itself.
+    fn owner(&self, id: &str) -> Option<&'a XmiElement> {
+        let element = *self.elements.get(id)?;
+        match element.kind() {
+            "FunctionInputPort" | "FunctionOutputPort" | "ComponentPort" | "PhysicalPort" => {
+                self.parents.get(id).copied()
+            }
+            "Part" => element.reference("abstractType").and_then(|id| self.elements.get(id).copied()),
+            _ => Some(element),
+        }
+    }
+
+    /// The ids of the elements an exchange's source and target stand for.
+    fn ends(&self, exchange: &XmiElement) -> Option<(String, String)> {
+        let from = self.owner(exchange.reference("source")?)?;
+        let to = self.owner(exchange.reference("target")?)?;
+        Some((from.id().to_string(), to.id().to_string()))
+    }
+
+    /// `Component.port` for a component port, else the owning element's id.
+    fn port_endpoint(&self, id: &str) -> Option<String> {
+        let owner = self.owner(id)?;
+        match self.elements.get(id) {
+            Some(port) if port.kind() == "ComponentPort" => Some(format!("{}.{}", owner.id(), port.label())),
+            _ => Some(owner.id().to_string()),
+        }
+    }
+
+    /// Functions below `architecture`, without the root function Capella
+    /// keeps in each function package.
+    fn functions(&self, architecture: &'a XmiElement, kind: &str) -> Vec<&'a XmiElement> {
+        architecture
+            .all(kind)
+            .into_iter()
+            .filter(|function| self.parents.get(function.id()).is_none_or(|parent| parent.tag != "ownedFunctionPkg"))
+            .collect()
+    }
+
+    /// The `kind` elements allocated to `component`.
+    fn allocated(&self, component: &XmiElement, kind: &str) -> Vec<&'a XmiElement> {
+        component
+            .children_of("ComponentFunctionalAllocation")
+            .filter_map(|allocation| allocation.reference("targetElement"))
+            .filter_map(|id| self.elements.get(id).copied())
+            .filter(|element| element.kind() == kind)
+            .collect()
+    }
+}
+
+fn operational_activity(activity: &XmiElement) -> OperationalActivity {
+    OperationalActivity {
+        id: activity.id().to_string(),
+        name: activity.label().to_string(),
+        performed_by: String::new(),
+        category: "general".to_string(),
+        icon: "circle".to_string(),
+        color: "#FFD966".to_string(),
+        sub_activities: Vec::new(),
+        attributes: id_attribute(activity),
+    }
+}
+
+fn id_attribute(element: &XmiElement) -> HashMap<String, AttributeValue> {
+    let mut attributes = HashMap::new();
+    attributes.insert("id".to_string(), AttributeValue::String(element.id().to_string()));
+    attributes
+}
+
 pub struct ArcCodeGenerator;
 
 impl ArcCodeGenerator {
@@ -283,42 +831,94 @@ impl ArcCodeGenerator {
     pub fn generate(&self, model: &Model) -> Result<String, CompilerError> {
         let mut arc_code = String::new();
         
+        // Generate Operational Analysis
+        for oa in &model.operational_analysis {
+            arc_code.push_str(&format!("operational_analysis {} {{\n", quote(&oa.name)));
+            
+            for actor in &oa.actors {
+                arc_code.push_str(&format!("    actor {} {{\n", quote(&actor.name)));
+                if let Some(id) = &actor.id {
+                    arc_code.push_str(&format!("        id: {}\n", quote(id)));
+                }
+                write_attributes(&mut arc_code, &actor.attributes, "        ");
+                arc_code.push_str("    }\n\n");
+            }
+
+            for entity in &oa.entities {
+                arc_code.push_str(&format!("    entity {} {{\n", quote(&entity.name)));
+                write_attributes(&mut arc_code, &entity.attributes, "        ");
+                for activity in &entity.activities {
+                    arc_code.push_str(&format!("        activity {} {{\n", quote(&activity.name)));
+                    write_attributes(&mut arc_code, &activity.attributes, "            ");
+                    arc_code.push_str("        }\n");
+                }
+                arc_code.push_str("    }\n\n");
+            }
+
+            for activity in &oa.activities {
+                arc_code.push_str(&format!("    operational_activity {} {{\n", quote(&activity.name)));
+                write_attributes(&mut arc_code, &activity.attributes, "        ");
+                arc_code.push_str("    }\n\n");
+            }
+
+            for exchange in &oa.exchanges {
+                write_exchange(&mut arc_code, "operational_exchange", exchange);
+            }
+            for means in &oa.communication_means {
+                write_exchange(&mut arc_code, "communication_means", means);
+            }
+            
+            arc_code.push_str("}\n\n");
+        }
+        
         // Generate System Analysis
         for sa in &model.system_analysis {
-            arc_code.push_str(&format!("system_analysis \"{}\" {{\n", sa.name));
+            arc_code.push_str(&format!("system_analysis {} {{\n", quote(&sa.name)));
             
             for req in &sa.requirements {
-                arc_code.push_str(&format!("    requirement \"{}\" {{\n", req.id));
-                
-                for (key, value) in &req.attributes {
-                    match value {
-                        AttributeValue::String(s) => {
-                            arc_code.push_str(&format!("        {}: \"{}\"\n", key, s));
-                        }
-                        AttributeValue::Number(n) => {
-                            arc_code.push_str(&format!("        {}: {}\n", key, n));
-                        }
-                        AttributeValue::List(items) => {
-                            arc_code.push_str(&format!("        {}: [", key));
-                            for (i, item) in items.iter().enumerate() {
-                                if i > 0 {
-                                    arc_code.push_str(", ");
-                                }
-                                if let AttributeValue::String(s) = item {
-                                    arc_code.push_str(&format!("\"{}\"", s));
-                                }
-                            }
-                            arc_code.push_str("]\n");
-                        }
-                        AttributeValue::Boolean(b) => {
-                            arc_code.push_str(&format!("        {}: {}\n", key, b));
-                        }
-                        AttributeValue::Map(_) => {
-                            // Nested maps are not emitted in .arc export yet
-                        }
-                    }
+                arc_code.push_str(&format!("    requirement {} {{\n", quote(&req.id)));
+                write_attributes(&mut arc_code, &req.attributes, "        ");
+                arc_code.push_str("    }\n\n");
+            }
+
+            for actor in &sa.external_actors {
+                arc_code.push_str(&format!("    actor {} {{\n", quote(&actor.name)));
+                write_attributes(&mut arc_code, &actor.attributes, "        ");
+                arc_code.push_str("    }\n\n");
+            }
+            
+            for comp in &sa.components {
+                arc_code.push_str(&format!("    system_component {} {{\n", quote(&comp.name)));
+                write_attributes(&mut arc_code, &comp.attributes, "        ");
+                arc_code.push_str("    }\n\n");
+            }
+
+            for function in &sa.functions {
+                arc_code.push_str(&format!("    function {} {{\n", quote(&function.name)));
+                write_attributes(&mut arc_code, &function.attributes, "        ");
+                for sub in &function.sub_functions {
+                    arc_code.push_str(&format!("        function {} {{\n", quote(&sub.name)));
+                    write_attributes(&mut arc_code, &sub.attributes, "            ");
+                    arc_code.push_str("        }\n");
                 }
+                arc_code.push_str("    }\n\n");
+            }
 
+            for exchange in &sa.functional_exchanges {
+                match &exchange.label {
+                    Some(label) => {
+                        arc_code.push_str(&format!("    functional_exchange {} {{\n", quote(label)));
+                        arc_code.push_str(&format!("        from: {}\n", quote(&exchange.from_port)));
+                        arc_code.push_str(&format!("        to: {}\n", quote(&exchange.to_port)));
+                    }
+                    None => arc_code.push_str(&format!(
+                        "    functional_exchange {} -> {} {{\n",
+                        quote(&exchange.from_port), quote(&exchange.to_port)
+                    )),
+                }
+                if exchange.data_type != "Data" {
+                    arc_code.push_str(&format!("        exchange_item: {}\n", quote(&exchange.data_type)));
+                }
                 arc_code.push_str("    }\n\n");
             }
             
@@ -327,77 +927,178 @@ impl ArcCodeGenerator {
         
         // Generate Logical Architecture
         for la in &model.logical_architecture {
-            arc_code.push_str(&format!("logical_architecture \"{}\" {{\n", la.name));
+            arc_code.push_str(&format!("logical_architecture {} {{\n", quote(&la.name)));
             
             for comp in &la.components {
-                arc_code.push_str(&format!("    component \"{}\" {{\n", comp.name));
-                
-                for (key, value) in &comp.attributes {
-                    match value {
-                        AttributeValue::String(s) => {
-                            arc_code.push_str(&format!("        {}: \"{}\"\n", key, s));
+                write_logical_component(&mut arc_code, comp, "    ");
+                arc_code.push('\n');
+            }
+
+            for exchange in &la.component_exchanges {
+                match &exchange.label {
+                    Some(label) => {
+                        arc_code.push_str(&format!("    component_exchange {} {{\n", quote(label)));
+                        arc_code.push_str(&format!("        from_port: {}\n", quote(&exchange.from_port)));
+                        arc_code.push_str(&format!("        to_port: {}\n", quote(&exchange.to_port)));
+                        if exchange.exchange_item != "Data" {
+                            arc_code.push_str(&format!("        exchange_item: {}\n", quote(&exchange.exchange_item)));
                         }
-                        _ => {}
+                        arc_code.push_str("    }\n\n");
                     }
+                    None => arc_code.push_str(&format!(
+                        "    connect {} to {}\n\n",
+                        quote(&exchange.from_port), quote(&exchange.to_port)
+                    )),
                 }
-                
-                for func in &comp.functions {
-                    arc_code.push_str(&format!("\n        function \"{}\" {{\n", func.name));
-                    
-                    for (key, value) in &func.attributes {
-                        match value {
-                            AttributeValue::String(s) => {
-                                arc_code.push_str(&format!("            {}: \"{}\"\n", key, s));
-                            }
-                            AttributeValue::List(items) => {
-                                arc_code.push_str(&format!("            {}: [", key));
-                                for (i, item) in items.iter().enumerate() {
-                                    if i > 0 {
-                                        arc_code.push_str(", ");
-                                    }
-                                    if let AttributeValue::String(s) = item {
-                                        arc_code.push_str(&format!("\"{}\"", s));
-                                    }
-                                }
-                                arc_code.push_str("]\n");
-                            }
-                            AttributeValue::Number(n) => {
-                                arc_code.push_str(&format!("            {}: {}\n", key, n));
-                            }
-                            AttributeValue::Boolean(b) => {
-                                arc_code.push_str(&format!("            {}: {}\n", key, b));
-                            }
-                            AttributeValue::Map(_) => {
-                                // Nested maps are not emitted in .arc export yet
-                            }
-                        }
-                    }
+            }
+            
+            arc_code.push_str("}\n\n");
+        }
 
-                    arc_code.push_str("        }\n");
+        // Generate Physical Architecture
+        for pa in &model.physical_architecture {
+            arc_code.push_str(&format!("physical_architecture {} {{\n", quote(&pa.name)));
+
+            for node in &pa.nodes {
+                arc_code.push_str(&format!("    node {} {{\n", quote(&node.name)));
+                write_attributes(&mut arc_code, &node.attributes, "        ");
+                for port in &node.ports {
+                    write_block(&mut arc_code, &format!("port {}", quote(&port.name)), &port.attributes, "        ");
+                }
+                for deployment in &node.deployments {
+                    write_block(&mut arc_code, &format!("deploys {}", quote(&deployment.component)), &deployment.attributes, "        ");
                 }
-                
                 arc_code.push_str("    }\n\n");
             }
-            
+
+            for link in &pa.links {
+                arc_code.push_str(&format!("    link {} {{\n", quote(&link.name)));
+                arc_code.push_str(&format!("        from: {}\n", quote(&link.from)));
+                arc_code.push_str(&format!("        to: {}\n", quote(&link.to)));
+                write_attributes_except(&mut arc_code, &link.attributes, "        ", &["from", "to", "connects"]);
+                arc_code.push_str("    }\n\n");
+            }
+
             arc_code.push_str("}\n\n");
         }
         
         // Generate Traces
         for trace in &model.traces {
             arc_code.push_str(&format!(
-                "trace \"{}\" {} \"{}\" {{\n",
-                trace.from, trace.trace_type, trace.to
+                "trace {} {} {} {{\n",
+                quote(&trace.from), trace.trace_type, quote(&trace.to)
             ));
-            
-            for (key, value) in &trace.attributes {
-                if let AttributeValue::String(s) = value {
-                    arc_code.push_str(&format!("    {}: \"{}\"\n", key, s));
-                }
-            }
-            
+            write_attributes(&mut arc_code, &trace.attributes, "    ");
             arc_code.push_str("}\n\n");
         }
         
         Ok(arc_code)
     }
 }
+
+/// A logical component with its ports, functions and sub-components.
+fn write_logical_component(arc_code: &mut String, comp: &LogicalComponent, indent: &str) {
+    let inner = format!("{}    ", indent);
+    arc_code.push_str(&format!("{}component {} {{\n", indent, quote(&comp.name)));
+    write_attributes(arc_code, &comp.attributes, &inner);
+
+    for port in &comp.ports {
+        let direction = match port.direction {
+            PortDirection::In => "in",
+            PortDirection::Out => "out",
+            PortDirection::InOut => "inout",
+        };
+        arc_code.push_str(&format!("{}port {} {}", inner, direction, quote(&port.name)));
+        if port.interface_type != "Data" || port.required {
+            arc_code.push_str(" {");
+            if port.interface_type != "Data" {
+                arc_code.push_str(&format!(" interface: {}", quote(&port.interface_type)));
+            }
+            if port.required {
+                arc_code.push_str(" required: true");
+            }
+            arc_code.push_str(" }");
+        }
+        arc_code.push('\n');
+    }
+
+    for func in &comp.functions {
+        arc_code.push_str(&format!("\n{}function {} {{\n", inner, quote(&func.name)));
+        write_attributes(arc_code, &func.attributes, &format!("{}    ", inner));
+        arc_code.push_str(&format!("{}}}\n", inner));
+    }
+
+    for sub in &comp.sub_components {
+        arc_code.push('\n');
+        write_logical_component(arc_code, sub, &inner);
+    }
+
+    arc_code.push_str(&format!("{}}}\n", indent));
+}
+
+/// `head`, with an attribute block only when there are attributes.
+fn write_block(arc_code: &mut String, head: &str, attributes: &HashMap<String, AttributeValue>, indent: &str) {
+    if attributes.is_empty() {
+        arc_code.push_str(&format!("{}{}\n", indent, head));
+        return;
+    }
+    arc_code.push_str(&format!("{}{} {{\n", indent, head));
+    write_attributes(arc_code, attributes, &format!("{}    ", indent));
+    arc_code.push_str(&format!("{}}}\n", indent));
+}
+
+/// An operational exchange or communication means in block form.
+fn write_exchange(arc_code: &mut String, keyword: &str, exchange: &OperationalExchange) {
+    let name = exchange
+        .label
+        .clone()
+        .unwrap_or_else(|| format!("{} -> {}", exchange.from, exchange.to));
+    arc_code.push_str(&format!("    {} {} {{\n", keyword, quote(&name)));
+    arc_code.push_str(&format!("        from: {}\n", quote(&exchange.from)));
+    arc_code.push_str(&format!("        to: {}\n", quote(&exchange.to)));
+    write_attributes_except(arc_code, &exchange.attributes, "        ", &["from", "to", "label"]);
+    arc_code.push_str("    }\n\n");
+}
+
+/// `key: value` lines, sorted by key so the output is stable. Nested maps
+/// are not emitted in .arc export yet.
+fn write_attributes(arc_code: &mut String, attributes: &HashMap<String, AttributeValue>, indent: &str) {
+    write_attributes_except(arc_code, attributes, indent, &[]);
+}
+
+/// [`write_attributes`] without the keys written from the element's fields.
+fn write_attributes_except(
+    arc_code: &mut String,
+    attributes: &HashMap<String, AttributeValue>,
+    indent: &str,
+    written: &[&str],
+) {
+    let mut keys: Vec<&String> = attributes.keys().filter(|key| !written.contains(&key.as_str())).collect();
+    keys.sort();
+    for key in keys {
+        let Some(value) = value_text(&attributes[key]) else { continue };
+        match key.strip_prefix(CUSTOM_ATTRIBUTE_PREFIX) {
+            Some(custom) => arc_code.push_str(&format!("{}attr {}: {}\n", indent, custom, value)),
+            None => arc_code.push_str(&format!("{}{}: {}\n", indent, key, value)),
+        }
+    }
+}
+
+/// An attribute value as ArcLang source; nested maps have no regenerated form.
+fn value_text(value: &AttributeValue) -> Option<String> {
+    Some(match value {
+        AttributeValue::String(s) => quote(s),
+        AttributeValue::Number(n) => n.to_string(),
+        AttributeValue::Boolean(b) => b.to_string(),
+        AttributeValue::List(items) => {
+            let items: Vec<String> = items.iter().filter_map(value_text).collect();
+            format!("[{}]", items.join(", "))
+        }
+        AttributeValue::Map(_) => return None,
+    })
+}
+
+/// A string literal, with quotes and backslashes escaped.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
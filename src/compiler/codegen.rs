@@ -1,4 +1,6 @@
-use super::semantic::SemanticModel;
+use std::collections::{HashMap, HashSet};
+
+use super::semantic::{ComponentInfo, SemanticModel, TraceLinkType};
 use super::CompilerConfig;
 use super::CompilerError;
 
@@ -29,17 +31,21 @@ impl<'a> CodeGenerator<'a> {
     }
     
     fn generate_mermaid(&self, model: &SemanticModel) -> Result<String, CompilerError> {
-        use super::mermaid_generator::generate_mermaid_flowchart;
-        generate_mermaid_flowchart(model, "System Requirements", "elk")
+        use super::mermaid_generator::{generate_mermaid_flowchart, FlowDirection};
+        generate_mermaid_flowchart(model, "System Requirements", "elk", FlowDirection::TopBottom)
     }
     
     fn generate_json(&self, model: &SemanticModel) -> Result<String, CompilerError> {
+        let custom = |id: &str| {
+            model.all_elements.get(id).map(|e| e.custom_attributes.clone()).unwrap_or_default()
+        };
         let requirements: Vec<_> = model.requirements.iter().map(|r| {
             serde_json::json!({
                 "id": r.id,
                 "description": r.description,
                 "priority": r.priority,
                 "safety_level": r.safety_level,
+                "custom_attributes": custom(&r.id),
             })
         }).collect();
         
@@ -49,6 +55,7 @@ impl<'a> CodeGenerator<'a> {
                 "name": c.name,
                 "type": c.component_type,
                 "level": c.level,
+                "custom_attributes": custom(&c.id),
             })
         }).collect();
         
@@ -58,6 +65,7 @@ impl<'a> CodeGenerator<'a> {
                 "name": f.name,
                 "inputs": f.inputs,
                 "outputs": f.outputs,
+                "custom_attributes": custom(&f.id),
             })
         }).collect();
         
@@ -93,50 +101,65 @@ impl<'a> CodeGenerator<'a> {
     }
     
     fn generate_capella(&self, model: &SemanticModel) -> Result<String, CompilerError> {
-        let mut xml = String::new();
-        
-        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-        xml.push_str("<capella:Project xmlns:capella=\"http://www.polarsys.org/capella/core/1.4.0\">\n");
-        xml.push_str("  <ownedRequirements>\n");
-        
-        for req in &model.requirements {
-            xml.push_str(&format!(
-                "    <requirement id=\"{}\" name=\"{}\" description=\"{}\" priority=\"{}\" />\n",
-                req.id, req.id, req.description, req.priority
-            ));
-        }
-        
-        xml.push_str("  </ownedRequirements>\n");
-        xml.push_str("  <ownedLogicalComponents>\n");
-        
-        for comp in &model.components {
-            xml.push_str(&format!(
-                "    <component id=\"{}\" name=\"{}\" type=\"{}\" />\n",
-                comp.id, comp.name, comp.component_type
-            ));
-        }
-        
-        xml.push_str("  </ownedLogicalComponents>\n");
-        xml.push_str("  <ownedTraces>\n");
-        
-        for trace in &model.traces {
-            xml.push_str(&format!(
-                "    <trace from=\"{}\" to=\"{}\" type=\"{}\" />\n",
-                trace.from, trace.to, trace.trace_type
-            ));
-        }
-        
-        xml.push_str("  </ownedTraces>\n");
-        xml.push_str("</capella:Project>\n");
-        
-        Ok(xml)
+        Ok(super::capella_exporter::export_capella(model))
     }
     
     fn generate_markdown(&self, model: &SemanticModel) -> Result<String, CompilerError> {
+        let layers = components_by_layer(model);
+        let mut sections = vec!["Traceability Summary", "Metrics", "Requirements"];
+        let layer_titles: Vec<String> = layers.iter().map(|(layer, _)| layer_title(layer)).collect();
+        sections.extend(layer_titles.iter().map(String::as_str));
+        sections.push("Traceability Matrix");
+        let anchors = MdAnchors::new(model, &sections);
+        let link = |id: &str| anchors.link(id);
         let mut md = String::new();
         
         md.push_str("# ArcLang Model Report\n\n");
         
+        // Contents
+        md.push_str("## Contents\n\n");
+        for section in &sections {
+            md.push_str(&format!("- {}\n", anchors.section_link(section)));
+            if let Some((_, components)) = layers.iter().find(|(layer, _)| layer_title(layer) == *section) {
+                for comp in components {
+                    md.push_str(&format!("  - [{}](#{})\n", comp.name, anchors.anchor(&comp.id)));
+                }
+            }
+        }
+        md.push('\n');
+        
+        // Traceability summary: where each requirement stands
+        let status = model.rollup_status();
+        md.push_str("## Traceability Summary\n\n");
+        md.push_str("| Requirement | Refines | Satisfied by | Verified by | Status |\n");
+        md.push_str("|-------------|---------|--------------|-------------|--------|\n");
+        for req in &model.requirements {
+            let mut satisfied_by = Vec::new();
+            let mut verified_by = Vec::new();
+            for trace in &model.traces {
+                match trace.link_type {
+                    TraceLinkType::Satisfies | TraceLinkType::Implements if trace.to == req.id => {
+                        satisfied_by.push(link(&trace.from))
+                    }
+                    TraceLinkType::AllocatedTo if trace.from == req.id => satisfied_by.push(link(&trace.to)),
+                    TraceLinkType::VerifiedBy if trace.to == req.id => verified_by.push(link(&trace.from)),
+                    _ => {}
+                }
+            }
+            verified_by.extend(
+                model.verifications.iter().filter(|v| v.requirement == req.id).map(|v| md_cell(&v.test_case)),
+            );
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                link(&req.id),
+                req.parent_id.as_deref().map(link).unwrap_or_else(|| "-".to_string()),
+                md_list(&satisfied_by),
+                md_list(&verified_by),
+                status.get(&req.id).map(|s| s.as_str()).unwrap_or("-"),
+            ));
+        }
+        md.push('\n');
+        
         // Metrics
         let metrics = model.compute_metrics();
         md.push_str("## Metrics\n\n");
@@ -149,39 +172,45 @@ impl<'a> CodeGenerator<'a> {
         
         // Requirements
         md.push_str("## Requirements\n\n");
+        md.push_str("| ID | Description | Priority | Safety Level | Traces to |\n");
+        md.push_str("|----|-------------|----------|--------------|-----------|\n");
         for req in &model.requirements {
-            md.push_str(&format!("### {}\n\n", req.id));
-            md.push_str(&format!("- **Description**: {}\n", req.description));
-            md.push_str(&format!("- **Priority**: {}\n", req.priority));
-            if let Some(ref safety) = req.safety_level {
-                md.push_str(&format!("- **Safety Level**: {}\n", safety));
-            }
-            
-            let traces = model.get_traces_from(&req.id);
-            if !traces.is_empty() {
-                md.push_str("- **Traces to**:\n");
-                for trace in traces {
-                    md.push_str(&format!("  - {} ({})\n", trace.to, trace.trace_type));
-                }
-            }
-            md.push_str("\n");
+            let traces: Vec<String> = model
+                .get_traces_from(&req.id)
+                .iter()
+                .map(|trace| format!("{} ({})", link(&trace.to), md_cell(&trace.trace_type)))
+                .collect();
+            md.push_str(&format!(
+                "| <a id=\"{}\"></a>{} | {} | {} | {} | {} |\n",
+                anchors.anchor(&req.id),
+                md_cell(&req.id),
+                md_cell(&req.description),
+                md_cell(&req.priority),
+                md_cell(req.safety_level.as_deref().unwrap_or("-")),
+                md_list(&traces),
+            ));
         }
+        md.push('\n');
         
-        // Components
-        md.push_str("## Components\n\n");
-        for comp in &model.components {
-            md.push_str(&format!("### {}\n\n", comp.name));
-            md.push_str(&format!("- **Type**: {}\n", comp.component_type));
-            md.push_str(&format!("- **Level**: {}\n", comp.level));
-            
-            let traces = model.get_traces_to(&comp.id);
-            if !traces.is_empty() {
-                md.push_str("- **Traced from**:\n");
-                for trace in traces {
-                    md.push_str(&format!("  - {} ({})\n", trace.from, trace.trace_type));
+        // Components, one section per Arcadia layer (operational -> physical)
+        for (layer, components) in &layers {
+            md.push_str(&format!("## {}\n\n", layer_title(layer)));
+            for comp in components {
+                md.push_str(&format!("<a id=\"{}\"></a>\n\n", anchors.anchor(&comp.id)));
+                md.push_str(&format!("### {}\n\n", comp.name));
+                md.push_str(&format!("- **ID**: {}\n", comp.id));
+                md.push_str(&format!("- **Type**: {}\n", comp.component_type));
+                md.push_str(&format!("- **Level**: {}\n", comp.level));
+                
+                let traces = model.get_traces_to(&comp.id);
+                if !traces.is_empty() {
+                    md.push_str("- **Traced from**:\n");
+                    for trace in traces {
+                        md.push_str(&format!("  - {} ({})\n", link(&trace.from), trace.trace_type));
+                    }
                 }
+                md.push('\n');
             }
-            md.push_str("\n");
         }
         
         // Traceability Matrix
@@ -191,9 +220,113 @@ impl<'a> CodeGenerator<'a> {
         for trace in &model.traces {
             let rationale = trace.rationale.as_deref().unwrap_or("-");
             md.push_str(&format!("| {} | {} | {} | {} |\n", 
-                trace.from, trace.to, trace.trace_type, rationale));
+                link(&trace.from), link(&trace.to), md_cell(&trace.trace_type), md_cell(rationale)));
         }
         
         Ok(md)
     }
 }
+
+/// In-document anchors of the Markdown report. Section slugs (Contents
+/// included) are reserved first (they are what GitHub derives from the headings), then every
+/// requirement and component id gets its slug, in model order, with a
+/// `-1`, `-2`... suffix when an earlier anchor already took it.
+struct MdAnchors {
+    sections: HashMap<String, String>,
+    elements: HashMap<String, String>,
+}
+
+impl MdAnchors {
+    fn new(model: &SemanticModel, sections: &[&str]) -> Self {
+        let mut taken = HashSet::new();
+        let mut unique = |text: &str| {
+            let base = slugify(text);
+            let slug = std::iter::once(base.clone())
+                .chain((1..).map(|n| format!("{base}-{n}")))
+                .find(|slug| !taken.contains(slug))
+                .expect("an unused suffix exists");
+            taken.insert(slug.clone());
+            slug
+        };
+        unique("Contents");
+        let sections = sections.iter().map(|section| (section.to_string(), unique(section))).collect();
+        let ids = model.requirements.iter().map(|r| &r.id).chain(model.components.iter().map(|c| &c.id));
+        let mut elements = HashMap::new();
+        for id in ids {
+            if !elements.contains_key(id) {
+                let slug = unique(id);
+                elements.insert(id.clone(), slug);
+            }
+        }
+        Self { sections, elements }
+    }
+
+    fn anchor(&self, id: &str) -> &str {
+        self.elements.get(id).map(String::as_str).unwrap_or_default()
+    }
+
+    /// `[REQ-001](#req-001)`, or the id as text when it has no anchor.
+    fn link(&self, id: &str) -> String {
+        match self.elements.get(id) {
+            Some(slug) => format!("[{}](#{})", md_cell(id), slug),
+            None => md_cell(id),
+        }
+    }
+
+    fn section_link(&self, section: &str) -> String {
+        format!("[{}](#{})", section, self.sections[section])
+    }
+}
+
+/// GitHub-style anchor slug: lower case ASCII letters and digits, runs of
+/// anything else collapsed to one `-`.
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "element".to_string() } else { slug.to_string() }
+}
+
+/// Components grouped by `level`, Arcadia layers first (operational ->
+/// physical), then any custom layer in order of appearance. Empty layers
+/// are omitted.
+pub(crate) fn components_by_layer(model: &SemanticModel) -> Vec<(&str, Vec<&ComponentInfo>)> {
+    let mut layers: Vec<&str> = vec!["Operational", "System", "Logical", "Physical"];
+    for comp in &model.components {
+        if !layers.contains(&comp.level.as_str()) {
+            layers.push(&comp.level);
+        }
+    }
+    layers
+        .into_iter()
+        .map(|layer| (layer, model.components.iter().filter(|c| c.level == layer).collect::<Vec<_>>()))
+        .filter(|(_, components)| !components.is_empty())
+        .collect()
+}
+
+/// Report section title for a component `level`.
+pub(crate) fn layer_title(level: &str) -> String {
+    match level {
+        "Operational" => "Operational Analysis".to_string(),
+        "System" => "System Analysis".to_string(),
+        "Logical" => "Logical Architecture".to_string(),
+        "Physical" => "Physical Architecture".to_string(),
+        other => format!("{} Layer", other),
+    }
+}
+
+/// Escape a value for a Markdown table cell.
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Comma-separated cell items, `-` when there are none.
+fn md_list(items: &[String]) -> String {
+    if items.is_empty() { "-".to_string() } else { items.join(", ") }
+}
@@ -0,0 +1,197 @@
+//! Completion for ArcLang source, shared by the REPL and the language
+//! server: block keywords at statement position, trace link keywords after a
+//! trace source, and known element ids where a trace endpoint is expected.
+
+use super::semantic::{SemanticModel, TraceLinkType};
+
+/// Keywords that open a statement or block.
+pub const BLOCK_KEYWORDS: &[&str] = &[
+    "model",
+    "import",
+    "include",
+    "requirements",
+    "req",
+    "requirement",
+    "architecture",
+    "operational_analysis",
+    "system_analysis",
+    "logical_architecture",
+    "physical_architecture",
+    "epbs",
+    "safety_analysis",
+    "actor",
+    "mission",
+    "capability",
+    "functional_chain",
+    "component",
+    "function",
+    "interface",
+    "connect",
+    "node",
+    "hazard",
+    "state_machine",
+    "scenario",
+    "exchange_item",
+    "data_type",
+    "class",
+    "test_case",
+    "trace",
+];
+
+/// Keywords linking a trace source to its target.
+pub const TRACE_LINK_KEYWORDS: &[&str] = &[
+    "satisfies",
+    "derives_from",
+    "refines",
+    "verifies",
+    "validates",
+    "allocated_to",
+    "implements",
+    "realizes",
+    "depends_on",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Keyword,
+    /// An element id of the model.
+    Reference,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// The element type of a reference.
+    pub detail: Option<String>,
+    /// Text replacing the word being typed (ids are quoted unless the quote
+    /// is already typed).
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Completions {
+    /// Byte offset in the line where the replaced word starts.
+    pub start: usize,
+    pub items: Vec<Completion>,
+}
+
+/// Completions for `line` (the text of the current line up to the cursor),
+/// offering ids from `model` when one is at hand.
+pub fn complete(line: &str, model: Option<&SemanticModel>) -> Completions {
+    let start = line
+        .rfind(|c: char| c.is_whitespace() || matches!(c, '"' | '{' | '}'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let word = &line[start..];
+    let quoted = line[..start].ends_with('"');
+    let before: Vec<&str> = line[..start]
+        .trim_end_matches('"')
+        .split_whitespace()
+        .collect();
+
+    let items = match before.as_slice() {
+        [] if !quoted => keywords(BLOCK_KEYWORDS, word),
+        [.., "trace", _] if !quoted => keywords(TRACE_LINK_KEYWORDS, word),
+        [.., "trace"] => references(model, None, word, quoted),
+        [.., "trace", _, link] if TRACE_LINK_KEYWORDS.contains(link) => {
+            let target = TraceLinkType::from_trace_type(link).required_target();
+            references(model, target, word, quoted)
+        }
+        _ => Vec::new(),
+    };
+    Completions { start, items }
+}
+
+fn keywords(candidates: &[&str], word: &str) -> Vec<Completion> {
+    candidates
+        .iter()
+        .filter(|keyword| keyword.starts_with(word))
+        .map(|keyword| Completion {
+            label: keyword.to_string(),
+            kind: CompletionKind::Keyword,
+            detail: None,
+            replacement: keyword.to_string(),
+        })
+        .collect()
+}
+
+/// Element ids starting with `word`, restricted to requirements or
+/// components when the trace link demands one.
+fn references(model: Option<&SemanticModel>, target: Option<&str>, word: &str, quoted: bool) -> Vec<Completion> {
+    let Some(model) = model else {
+        return Vec::new();
+    };
+    let mut ids: Vec<&str> = match target {
+        Some("requirement") => model.requirements.iter().map(|r| r.id.as_str()).collect(),
+        Some("component") => model.components.iter().map(|c| c.id.as_str()).collect(),
+        _ => model.all_elements.keys().map(String::as_str).collect(),
+    };
+    ids.sort_unstable();
+    ids.dedup();
+
+    ids.into_iter()
+        .filter(|id| id.starts_with(word))
+        .map(|id| Completion {
+            label: id.to_string(),
+            kind: CompletionKind::Reference,
+            detail: model.all_elements.get(id).map(|e| e.element_type.clone()),
+            replacement: if quoted { id.to_string() } else { format!("\"{id}\"") },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn model() -> SemanticModel {
+        let source = "requirements system {\n  req \"REQ-001\" \"R\" { description: \"r\" }\n  req \"REQ-002\" \"S\" { description: \"s\" }\n}\n\narchitecture logical {\n  component \"Brake\" { id: \"LC-001\" }\n}\n\ntrace \"REQ-001\" allocated_to \"LC-001\" {}\n";
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("test model compiles")
+            .semantic_model
+    }
+
+    fn labels(completions: &Completions) -> Vec<&str> {
+        completions.items.iter().map(|c| c.label.as_str()).collect()
+    }
+
+    #[test]
+    fn keywords_at_statement_position() {
+        let completions = complete("  re", None);
+
+        assert_eq!(completions.start, 2);
+        assert_eq!(labels(&completions), vec!["requirements", "req", "requirement"]);
+        assert!(completions.items.iter().all(|c| c.kind == CompletionKind::Keyword));
+    }
+
+    #[test]
+    fn link_keywords_after_trace_source() {
+        let completions = complete("trace \"LC-001\" sat", None);
+
+        assert_eq!(labels(&completions), vec!["satisfies"]);
+    }
+
+    #[test]
+    fn ids_of_the_kind_the_link_expects() {
+        let model = model();
+
+        let requirements = complete("trace \"LC-001\" satisfies ", Some(&model));
+        assert_eq!(labels(&requirements), vec!["REQ-001", "REQ-002"]);
+        assert_eq!(requirements.items[0].kind, CompletionKind::Reference);
+        assert_eq!(requirements.items[0].replacement, "\"REQ-001\"");
+        assert_eq!(requirements.items[0].detail.as_deref(), Some("Requirement"));
+
+        let components = complete("trace \"REQ-002\" allocated_to \"L", Some(&model));
+        assert_eq!(components.start, 30);
+        assert_eq!(labels(&components), vec!["LC-001"]);
+        assert_eq!(components.items[0].replacement, "LC-001");
+    }
+
+    #[test]
+    fn nothing_inside_attribute_values() {
+        assert!(complete("  description: \"re", Some(&model())).items.is_empty());
+    }
+}
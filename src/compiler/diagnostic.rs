@@ -0,0 +1,284 @@
+//! Compiler errors anchored in the source, and their rustc-style rendering:
+//!
+//! ```text
+//! error: Expected RightBrace, got here
+//!  --> model.arc:2:11
+//!   |
+//! 2 |   garbage here
+//!   |           ^^^^
+//! ```
+
+use super::lexer::Span;
+use super::lint_levels::{warning_lint, LintLevels};
+use super::{CompilationResult, CompilerError};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where a diagnostic points: 1-based line and column, width in characters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceSpan {
+    /// The file the span is in; `None` for sources compiled from a string.
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl From<Span> for SourceSpan {
+    fn from(span: Span) -> Self {
+        Self {
+            file: None,
+            line: span.line,
+            column: span.column,
+            len: span.len,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Option<SourceSpan>,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            span: None,
+        }
+    }
+
+    pub fn at(message: impl Into<String>, span: Option<SourceSpan>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Attribute the span to `file` unless it already names one (an error
+    /// raised inside an imported fragment keeps the fragment's path).
+    pub fn in_file(mut self, file: &Path) -> Self {
+        if let Some(span) = &mut self.span {
+            span.file.get_or_insert_with(|| file.to_path_buf());
+        }
+        self
+    }
+
+    fn file(&self) -> Option<&Path> {
+        self.span.as_ref()?.file.as_deref()
+    }
+
+    /// Render as `error: message`, the `--> file:line:col` pointer and, when
+    /// `source` holds the spanned file's text, the offending line underlined.
+    pub fn render(&self, source: Option<&str>) -> String {
+        let mut out = format!("error: {}\n", self.message);
+        let Some(span) = &self.span else {
+            return out;
+        };
+        let file = self
+            .file()
+            .map(|f| f.display().to_string())
+            .unwrap_or_else(|| "<input>".to_string());
+        out.push_str(&format!(" --> {}:{}:{}\n", file, span.line, span.column));
+
+        let Some(text) = source.zip(span.line.checked_sub(1)).and_then(|(s, i)| s.lines().nth(i)) else {
+            return out;
+        };
+        let gutter = " ".repeat(span.line.to_string().len());
+        let indent: String = text
+            .chars()
+            .take(span.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        out.push_str(&format!("{gutter} |\n"));
+        out.push_str(&format!("{} | {}\n", span.line, text));
+        out.push_str(&format!("{gutter} | {indent}{}\n", "^".repeat(span.len.max(1))));
+        out
+    }
+}
+
+impl From<String> for Diagnostic {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = self.file() {
+            write!(f, "{}: ", file.display())?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(span) = &self.span {
+            write!(f, " at line {}, column {}", span.line, span.column)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One line of `arclang check --json`: what an editor or a CI annotation
+/// step needs to place the message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagnosticRecord {
+    pub severity: Severity,
+    /// Stable identifier of the check (`E-SYNTAX`, `E-TRACE-ORPHAN`, ...).
+    pub code: &'static str,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    /// 1-based; `end` is exclusive. `None` when the check has no position.
+    pub range: Option<SourceRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceRange {
+    pub start: SourcePosition,
+    pub end: SourcePosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    /// A diagnostic from a message ending in `at line L, column C` (compiler
+    /// warnings), located there.
+    pub fn from_message(message: &str) -> Self {
+        let position = message.rsplit_once(" at line ").and_then(|(text, position)| {
+            let (line, column) = position.split_once(", column ")?;
+            Some((text, line.parse().ok()?, column.parse().ok()?))
+        });
+        match position {
+            Some((text, line, column)) => Self::at(text, Some(SourceSpan { file: None, line, column, len: 1 })),
+            None => Self::new(message),
+        }
+    }
+
+    pub fn record(&self, severity: Severity, code: &'static str) -> DiagnosticRecord {
+        DiagnosticRecord {
+            severity,
+            code,
+            message: self.message.clone(),
+            file: self.file().map(Path::to_path_buf),
+            range: self.span.as_ref().map(|span| SourceRange {
+                start: SourcePosition { line: span.line, column: span.column },
+                end: SourcePosition { line: span.line, column: span.column + span.len.max(1) },
+            }),
+        }
+    }
+}
+
+/// Everything a compilation has to say — errors, or warnings plus
+/// traceability findings — as `check --json` and the language server
+/// report it.
+pub fn records(outcome: &Result<CompilationResult, CompilerError>) -> Vec<DiagnosticRecord> {
+    leveled_records(outcome, &LintLevels::default())
+}
+
+/// [`records`] with each warning at its lint's level: denied ones become
+/// errors, allowed ones are left out.
+pub fn leveled_records(outcome: &Result<CompilationResult, CompilerError>, levels: &LintLevels) -> Vec<DiagnosticRecord> {
+    match outcome {
+        Ok(result) => result
+            .warnings
+            .iter()
+            .filter_map(|warning| {
+                let severity = levels.severity(warning_lint(warning), Severity::Warning)?;
+                Some(Diagnostic::from_message(warning).record(severity, warning_code(warning)))
+            })
+            .chain(result.semantic_model.traceability_issues().into_iter().filter_map(|issue| {
+                let severity = levels.severity(Some(issue.lint), issue.severity)?;
+                Some(issue.diagnostic.record(severity, issue.code))
+            }))
+            .collect(),
+        Err(error) => error
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.record(Severity::Error, error.code()))
+            .collect(),
+    }
+}
+
+/// Stable code of a non-fatal compiler warning.
+pub fn warning_code(message: &str) -> &'static str {
+    if message.starts_with("duplicate element id") {
+        "E-DUPLICATE-ID"
+    } else if message.contains("trace '") && message.contains("unknown element") {
+        "E-TRACE-UNRESOLVED"
+    } else if message.starts_with("unreferenced ") {
+        "E-UNREFERENCED"
+    } else if message.starts_with("cross-layer: ") {
+        "E-CROSS-LAYER"
+    } else if message.contains("not yet") {
+        "E-UNMODELED"
+    } else {
+        "E-MODEL"
+    }
+}
+
+/// One diagnostic per line, indented (the body of a multi-error message).
+pub(crate) fn list(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n  ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_offending_line_with_a_caret_underline() {
+        let source = "model Test {\n  garbage here\n}\n";
+        let diagnostic = Diagnostic::at(
+            "Expected RightBrace, got here",
+            Some(SourceSpan { file: None, line: 2, column: 11, len: 4 }),
+        )
+        .in_file(Path::new("model.arc"));
+
+        assert_eq!(
+            diagnostic.render(Some(source)),
+            "error: Expected RightBrace, got here\n \
+             --> model.arc:2:11\n  \
+             |\n\
+             2 |   garbage here\n  \
+             |           ^^^^\n"
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "model.arc: Expected RightBrace, got here at line 2, column 11"
+        );
+    }
+
+    #[test]
+    fn warning_position_becomes_a_range() {
+        let record = Diagnostic::from_message("'dataflow' block is not yet represented at line 2, column 3")
+            .record(Severity::Warning, "E-UNMODELED");
+
+        assert_eq!(record.message, "'dataflow' block is not yet represented");
+        assert_eq!(
+            record.range,
+            Some(SourceRange {
+                start: SourcePosition { line: 2, column: 3 },
+                end: SourcePosition { line: 2, column: 4 },
+            })
+        );
+    }
+
+    #[test]
+    fn unlocated_diagnostic_renders_message_only() {
+        assert_eq!(Diagnostic::new("boom").render(Some("x")), "error: boom\n");
+    }
+}
@@ -0,0 +1,502 @@
+//! IBM DOORS import (`arclang import reqs.csv -f doors`).
+//!
+//! DOORS "Export to Spreadsheet/CSV" writes one row per object with the
+//! standard columns (`ID` or `Absolute Number`, `Object Number`,
+//! `Object Level`, `Object Heading`, `Object Text`) followed by the module's
+//! attribute columns. Each object becomes a `Requirement`; the object level
+//! (or the depth of the object number) rebuilds the module hierarchy, which
+//! is emitted as nested `req` blocks. A DOORS ReqIF export is handed to the
+//! ReqIF importer.
+//!
+//! [`import_doors_stream`] converts a CSV export an object at a time, for
+//! exports too large to hold in memory: what it keeps is the current row and
+//! the chain of objects the next one may nest under.
+
+use super::ast::{AttributeValue, Requirement};
+use super::CompilerError;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, Chain, Cursor, Read, Write};
+
+/// One DOORS object and the objects below it.
+#[derive(Debug, Clone)]
+pub struct DoorsObject {
+    pub requirement: Requirement,
+    pub level: usize,
+    pub children: Vec<DoorsObject>,
+}
+
+#[derive(Debug)]
+pub struct DoorsModule {
+    pub objects: Vec<DoorsObject>,
+}
+
+/// Which ArcLang field a DOORS column feeds.
+fn field_for(column: &str) -> Option<&'static str> {
+    let normalized: String = column
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let field = match normalized.as_str() {
+        "id" | "objectidentifier" | "identifier" => "id",
+        "absolutenumber" => "absolute_number",
+        "objectnumber" => "object_number",
+        "objectlevel" | "level" => "level",
+        "objectheading" | "heading" => "title",
+        "objecttext" | "text" => "description",
+        "priority" => "priority",
+        "status" => "status",
+        "category" | "objecttype" | "type" => "category",
+        "safetylevel" | "asil" | "dal" | "sil" => "safety_level",
+        _ => return None,
+    };
+    Some(field)
+}
+
+/// Attribute key for a module-specific column: `Verification Method` -> `verification_method`.
+pub(super) fn attribute_key(column: &str) -> String {
+    let mut key = String::new();
+    for c in column.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            key.push(c.to_ascii_lowercase());
+        } else if !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    key.trim_matches('_').to_string()
+}
+
+/// CSV records read one at a time, honouring quoted fields (embedded
+/// delimiters, doubled quotes and line breaks, as DOORS writes multi-line
+/// object text). Rows of empty fields are skipped. Only the line being read
+/// and the record being built are held, whatever the size of the input.
+pub(super) struct CsvRecords<R> {
+    input: R,
+    delimiter: char,
+    line: String,
+    ready: VecDeque<Vec<String>>,
+    row: Vec<String>,
+    field: String,
+    in_quotes: bool,
+    done: bool,
+}
+
+impl<R: BufRead> CsvRecords<R> {
+    pub(super) fn new(input: R, delimiter: char) -> Self {
+        Self {
+            input,
+            delimiter,
+            line: String::new(),
+            ready: VecDeque::new(),
+            row: Vec::new(),
+            field: String::new(),
+            in_quotes: false,
+            done: false,
+        }
+    }
+
+    fn parse_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        self.field.push('"');
+                        chars.next();
+                    }
+                    '"' => self.in_quotes = false,
+                    _ => self.field.push(c),
+                }
+            } else if c == '"' {
+                self.in_quotes = true;
+            } else if c == self.delimiter {
+                self.row.push(std::mem::take(&mut self.field));
+            } else if c == '\n' || c == '\r' {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                self.end_record();
+            } else {
+                self.field.push(c);
+            }
+        }
+        self.line = line;
+    }
+
+    fn end_record(&mut self) {
+        self.row.push(std::mem::take(&mut self.field));
+        if self.row.iter().any(|f| !f.is_empty()) {
+            self.ready.push_back(std::mem::take(&mut self.row));
+        } else {
+            self.row.clear();
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CsvRecords<R> {
+    type Item = std::io::Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.ready.is_empty() && !self.done {
+            self.line.clear();
+            match self.input.read_line(&mut self.line) {
+                Ok(0) => {
+                    self.done = true;
+                    self.end_record();
+                }
+                Ok(_) => self.parse_line(),
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.ready.pop_front().map(Ok)
+    }
+}
+
+/// The records of `input`, its delimiter detected from the header line and
+/// a leading byte order mark dropped.
+pub(super) fn csv_records<R: BufRead>(mut input: R) -> std::io::Result<CsvRecords<Chain<Cursor<String>, R>>> {
+    let mut header = String::new();
+    input.read_line(&mut header)?;
+    let header = header.trim_start_matches('\u{feff}').to_string();
+    let delimiter = detect_delimiter(&header);
+    Ok(CsvRecords::new(Cursor::new(header).chain(input), delimiter))
+}
+
+/// DOORS writes `,` or `;` (locale dependent) or tabs; pick whichever the
+/// header line uses most.
+pub(super) fn detect_delimiter(text: &str) -> char {
+    let header = text.lines().next().unwrap_or("");
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|d| header.matches(*d).count())
+        .unwrap_or(',')
+}
+
+/// Depth of an object number: `1.2.3` is level 3; text objects numbered
+/// `1.2-1` sit one level below heading `1.2`.
+fn level_from_number(number: &str) -> Option<usize> {
+    let number = number.trim();
+    if number.is_empty() {
+        return None;
+    }
+    let (heading, text_index) = match number.split_once('-') {
+        Some((heading, _)) => (heading, 1),
+        None => (number, 0),
+    };
+    Some(heading.split('.').filter(|p| !p.is_empty()).count() + text_index)
+}
+
+/// How the columns of a DOORS header map onto requirement fields: the
+/// field a standard column feeds, and the attribute key of the others.
+struct DoorsColumns(Vec<(Option<&'static str>, String)>);
+
+impl DoorsColumns {
+    fn new(header: &[String]) -> Result<Self, CompilerError> {
+        let columns: Vec<(Option<&'static str>, String)> = header
+            .iter()
+            .map(|column| (field_for(column), attribute_key(column)))
+            .collect();
+
+        if !columns.iter().any(|(field, _)| matches!(field, Some("id") | Some("absolute_number"))) {
+            return Err(CompilerError::Other(
+                "DOORS import: no `ID` or `Absolute Number` column in the header".to_string(),
+            ));
+        }
+        Ok(Self(columns))
+    }
+
+    /// The level and requirement of the object on data row `index`.
+    fn object(&self, index: usize, row: &[String]) -> Result<(usize, Requirement), CompilerError> {
+        let mut fields: HashMap<&str, String> = HashMap::new();
+        let mut attributes = HashMap::new();
+        for ((field, key), value) in self.0.iter().zip(row) {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match field {
+                Some(field) => {
+                    fields.entry(*field).or_insert_with(|| value.to_string());
+                }
+                None if !key.is_empty() => {
+                    attributes.insert(key.clone(), AttributeValue::String(value.to_string()));
+                }
+                None => {}
+            }
+        }
+
+        let id = match (fields.remove("id"), fields.get("absolute_number")) {
+            (Some(id), _) => id,
+            (None, Some(number)) => format!("DOORS-{}", number),
+            (None, None) => {
+                return Err(CompilerError::Other(format!(
+                    "DOORS import: object on row {} has no identifier",
+                    index + 2
+                )))
+            }
+        };
+        let level = fields
+            .remove("level")
+            .and_then(|l| l.parse().ok())
+            .or_else(|| fields.get("object_number").and_then(|n| level_from_number(n)))
+            .unwrap_or(1);
+
+        for (field, value) in fields {
+            attributes.insert(field.to_string(), AttributeValue::String(value));
+        }
+        Ok((level.max(1), Requirement { id, attributes }))
+    }
+}
+
+/// The objects of a DOORS CSV export with their levels, read one row at a
+/// time.
+fn doors_objects<R: BufRead>(
+    input: R,
+) -> Result<impl Iterator<Item = Result<(usize, Requirement), CompilerError>>, CompilerError> {
+    let mut rows = csv_records(input)?;
+    let header = rows
+        .next()
+        .ok_or_else(|| CompilerError::Other("DOORS import: the file is empty".to_string()))??;
+    let columns = DoorsColumns::new(&header)?;
+    Ok(rows.enumerate().map(move |(index, row)| columns.object(index, &row?)))
+}
+
+#[derive(Default)]
+pub struct DoorsImporter;
+
+impl DoorsImporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn import(&self, content: &str) -> Result<DoorsModule, CompilerError> {
+        let flat = doors_objects(content.as_bytes())?.collect::<Result<Vec<_>, _>>()?;
+        Ok(DoorsModule {
+            objects: build_hierarchy(flat),
+        })
+    }
+}
+
+/// Nest objects under the closest preceding object of a lower level.
+fn build_hierarchy(flat: Vec<(usize, Requirement)>) -> Vec<DoorsObject> {
+    let mut roots: Vec<DoorsObject> = Vec::new();
+    let mut stack: Vec<DoorsObject> = Vec::new();
+
+    fn close(stack: &mut Vec<DoorsObject>, roots: &mut Vec<DoorsObject>) {
+        let object = stack.pop().expect("non-empty stack");
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(object),
+            None => roots.push(object),
+        }
+    }
+
+    for (level, requirement) in flat {
+        while stack.last().is_some_and(|top| top.level >= level) {
+            close(&mut stack, &mut roots);
+        }
+        stack.push(DoorsObject {
+            requirement,
+            level,
+            children: Vec::new(),
+        });
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+    roots
+}
+
+const GENERATED_HEADER: &str = "// Imported from DOORS by ArcLang\nmodel ImportedRequirements {\n}\n\nrequirements {\n";
+
+/// Emits a DOORS module as an ArcLang `requirements` block.
+#[derive(Default)]
+pub struct RequirementsArcGenerator;
+
+impl RequirementsArcGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn generate(&self, module: &DoorsModule) -> Result<String, CompilerError> {
+        let mut arc_code = String::from(GENERATED_HEADER);
+        for object in &module.objects {
+            self.generate_object(object, 1, &mut arc_code);
+        }
+        arc_code.push_str("}\n");
+        Ok(arc_code)
+    }
+
+    fn generate_object(&self, object: &DoorsObject, depth: usize, arc_code: &mut String) {
+        self.open_object(&object.requirement, depth, arc_code);
+        for child in &object.children {
+            self.generate_object(child, depth + 1, arc_code);
+        }
+        close_object(depth, arc_code);
+    }
+
+    /// The `req` line and fields of an object; its children follow.
+    fn open_object(&self, requirement: &Requirement, depth: usize, arc_code: &mut String) {
+        let indent = "  ".repeat(depth);
+
+        arc_code.push_str(&format!("{}req {}", indent, quote(&requirement.id)));
+        if let Some(AttributeValue::String(title)) = requirement.attributes.get("title") {
+            arc_code.push_str(&format!(" {}", quote(title)));
+        }
+        arc_code.push_str(" {\n");
+
+        // Known fields first, in a stable order, then the module's own columns.
+        let known = ["description", "priority", "status", "category", "safety_level", "object_number", "absolute_number"];
+        let mut others: Vec<&String> = requirement
+            .attributes
+            .keys()
+            .filter(|k| *k != "title" && !known.contains(&k.as_str()))
+            .collect();
+        others.sort();
+        let keys = known.iter().copied().chain(others.into_iter().map(String::as_str));
+        for key in keys {
+            if let Some(AttributeValue::String(value)) = requirement.attributes.get(key) {
+                arc_code.push_str(&format!("{}  {}: {}\n", indent, key, quote(value)));
+            }
+        }
+    }
+}
+
+fn close_object(depth: usize, arc_code: &mut String) {
+    arc_code.push_str(&format!("{}}}\n", "  ".repeat(depth)));
+}
+
+pub(super) fn quote(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.replace('\\', "\\\\").replace('"', "\\\"").replace('\r', "").replace('\n', "\\n")
+    )
+}
+
+/// Convert a DOORS export (CSV, or ReqIF XML) into ArcLang source.
+pub fn import_doors(content: &str) -> Result<String, CompilerError> {
+    let mut arc_code = Vec::new();
+    import_doors_stream(content.as_bytes(), &mut arc_code)?;
+    Ok(String::from_utf8(arc_code).expect("generated source is UTF-8"))
+}
+
+/// Convert a DOORS export read from `input` into ArcLang source written to
+/// `output`. A CSV export is converted an object at a time: objects are
+/// written as they are read and a `req` block is closed as soon as an
+/// object at its level or above follows, so memory holds the current row
+/// and the chain of its ancestors, not the file. A ReqIF export is XML and
+/// is read whole.
+pub fn import_doors_stream(mut input: impl BufRead, output: &mut impl Write) -> Result<(), CompilerError> {
+    let start = String::from_utf8_lossy(input.fill_buf()?).into_owned();
+    if start.trim_start_matches('\u{feff}').trim_start().starts_with('<') {
+        let mut content = String::new();
+        input.read_to_string(&mut content)?;
+        let arc_code = super::reqif::import_reqif(&content).map_err(CompilerError::Other)?;
+        output.write_all(arc_code.as_bytes())?;
+        return Ok(());
+    }
+
+    let generator = RequirementsArcGenerator::new();
+    let mut chunk = String::from(GENERATED_HEADER);
+    // Levels of the objects whose blocks are open, outermost first
+    let mut open: Vec<usize> = Vec::new();
+    for object in doors_objects(input)? {
+        let (level, requirement) = object?;
+        while open.last().is_some_and(|top| *top >= level) {
+            open.pop();
+            close_object(open.len() + 1, &mut chunk);
+        }
+        generator.open_object(&requirement, open.len() + 1, &mut chunk);
+        open.push(level);
+        output.write_all(chunk.as_bytes())?;
+        chunk.clear();
+    }
+    while open.pop().is_some() {
+        close_object(open.len() + 1, &mut chunk);
+    }
+    chunk.push_str("}\n");
+    output.write_all(chunk.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const EXPORT: &str = "\u{feff}ID,Object Number,Object Heading,Object Text,Priority,Status,Verification Method\n\
+REQ-1,1,Braking,,High,Approved,\n\
+REQ-2,1-1,,\"Brake within 150 ms, \"\"always\"\"\",Critical,Draft,Test\n\
+REQ-3,1.1,Sensing,,Medium,,\n\
+REQ-4,1.1-1,,\"Detect objects\nat 150 m\",High,,Analysis\n\
+REQ-5,2,Warnings,,Low,,\n";
+
+    #[test]
+    fn object_numbers_rebuild_the_hierarchy() {
+        let module = DoorsImporter::new().import(EXPORT).unwrap();
+
+        assert_eq!(module.objects.len(), 2);
+        let braking = &module.objects[0];
+        let children: Vec<&str> = braking.children.iter().map(|c| c.requirement.id.as_str()).collect();
+        assert_eq!(children, vec!["REQ-2", "REQ-3"]);
+        assert_eq!(braking.children[1].children[0].requirement.id, "REQ-4");
+    }
+
+    #[test]
+    fn generated_source_compiles_with_parents_and_fields() {
+        let source = import_doors(EXPORT).unwrap();
+        let result = Compiler::new(CompilerConfig::default())
+            .compile_string(&source)
+            .expect("imported DOORS module compiles");
+
+        let req = |id: &str| {
+            result
+                .ast
+                .system_analysis
+                .iter()
+                .flat_map(|sa| &sa.requirements)
+                .find(|r| r.id == id)
+                .unwrap()
+                .attributes
+                .clone()
+        };
+        let text = |attrs: &HashMap<String, AttributeValue>, key: &str| attrs.get(key).and_then(|v| v.as_string()).map(str::to_string);
+
+        let brake = req("REQ-2");
+        assert_eq!(text(&brake, "parent").as_deref(), Some("REQ-1"));
+        assert_eq!(text(&brake, "description").as_deref(), Some("Brake within 150 ms, \"always\""));
+        assert_eq!(text(&brake, "priority").as_deref(), Some("Critical"));
+        assert_eq!(text(&brake, "status").as_deref(), Some("Draft"));
+        assert_eq!(text(&brake, "verification_method").as_deref(), Some("Test"));
+        assert_eq!(text(&req("REQ-4"), "parent").as_deref(), Some("REQ-3"));
+        assert_eq!(text(&req("REQ-4"), "description").as_deref(), Some("Detect objects\nat 150 m"));
+        assert_eq!(text(&req("REQ-5"), "parent"), None);
+        assert_eq!(result.semantic_model.requirements.len(), 5);
+    }
+
+    #[test]
+    fn streaming_writes_what_the_module_generator_writes() {
+        let levels = "ID;Object Level;Object Text\r\nA;1;a\r\nB;2;\"b\r\nb\"\r\nC;3;c\rD;1;d\r\n;;\nE;2;e\n";
+        let module = DoorsImporter::new().import(levels).unwrap();
+        let mut streamed = Vec::new();
+        import_doors_stream(levels.as_bytes(), &mut streamed).unwrap();
+
+        assert_eq!(String::from_utf8(streamed).unwrap(), RequirementsArcGenerator::new().generate(&module).unwrap());
+        assert_eq!(module.objects.len(), 2);
+        assert_eq!(module.objects[0].children[0].children[0].requirement.id, "C");
+        assert_eq!(module.objects[0].children[0].requirement.attributes["description"].as_string(), Some("b\r\nb"));
+    }
+
+    #[test]
+    fn semicolon_exports_with_absolute_numbers() {
+        let module = DoorsImporter::new()
+            .import("Absolute Number;Object Level;Object Text\n12;1;Top\n13;2;Child\n")
+            .unwrap();
+
+        assert_eq!(module.objects[0].requirement.id, "DOORS-12");
+        assert_eq!(module.objects[0].children[0].requirement.id, "DOORS-13");
+    }
+}
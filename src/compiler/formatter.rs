@@ -0,0 +1,709 @@
+//! Canonical source formatter for `.arc` files (`arclang format`).
+//!
+//! The formatter works on the token stream rather than the AST: the AST
+//! stores attributes in hash maps and drops comments, so re-emitting it
+//! would reorder attributes and lose documentation. Instead the input is
+//! parsed once (only well-formed models are reformatted), then its tokens
+//! are re-laid out:
+//!
+//! - two-space indentation per nesting level,
+//! - one `key: value` attribute per line, values aligned within a run of
+//!   consecutive attributes,
+//! - exactly one blank line between multi-line top-level declarations
+//!   (consecutive one-line statements such as `import`s stay grouped),
+//! - at most one blank line inside blocks, none after `{` or before `}`.
+//!
+//! String and number literals are copied verbatim from the source (escapes
+//! included), comments are kept, and a trace written on one line —
+//! `trace "A" satisfies "B" { rationale: "..." }` — keeps its inline form.
+//! Only whitespace changes: the output re-lexes to the exact same tokens,
+//! which `format_source` verifies before returning.
+
+use super::lexer::{Lexer, Span, Token};
+use super::parser::Parser;
+use super::CompilerError;
+
+const INDENT: &str = "  ";
+
+/// Format one source text. Fails on lexer/parser errors.
+pub fn format_source(source: &str) -> Result<String, CompilerError> {
+    let (tokens, spans) = Lexer::new(source)
+        .tokenize_spanned()
+        .map_err(CompilerError::Lexer)?;
+    Parser::with_spans(tokens.clone(), spans.clone())
+        .parse_with_warnings()
+        .map_err(CompilerError::Parser)?;
+
+    let mut formatter = Formatter::new(source, tokens, &spans);
+    formatter.format_block(0);
+    let output = formatter.render();
+
+    // Safety net: formatting must never change what the parser sees.
+    let reformatted = Lexer::new(&output)
+        .tokenize()
+        .map_err(|e| CompilerError::Other(format!("formatter produced invalid source: {e}")))?;
+    if reformatted != formatter.tokens {
+        return Err(CompilerError::Other(
+            "formatter changed the token stream; the input was left untouched".to_string(),
+        ));
+    }
+    Ok(output)
+}
+
+/// Char range of each token in `source`, which lexed into `tokens` and
+/// `spans`.
+pub(crate) fn token_ranges(source: &str, tokens: Vec<Token>, spans: &[Span]) -> Vec<std::ops::Range<usize>> {
+    let formatter = Formatter::new(source, tokens, spans);
+    (0..formatter.tokens.len())
+        .map(|idx| formatter.starts[idx]..formatter.token_end(idx))
+        .collect()
+}
+
+enum Line {
+    Blank,
+    /// An own-line comment; does not break attribute alignment.
+    Comment { depth: usize, text: String },
+    Text { depth: usize, text: String, comment: Option<String> },
+    Attr { depth: usize, key: String, value: String, comment: Option<String> },
+}
+
+/// Whitespace and comments between two tokens.
+enum Trivia {
+    Newline,
+    Comment(String),
+}
+
+struct Formatter {
+    chars: Vec<char>,
+    tokens: Vec<Token>,
+    /// Char offset of each token in `chars`.
+    starts: Vec<usize>,
+    pos: usize,
+    lines: Vec<Line>,
+    /// Comments met in the middle of the line being built.
+    pending_comments: Vec<String>,
+}
+
+impl Formatter {
+    fn new(source: &str, tokens: Vec<Token>, spans: &[Span]) -> Self {
+        let chars: Vec<char> = source.chars().collect();
+        let mut line_starts = vec![0];
+        for (i, c) in chars.iter().enumerate() {
+            if *c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        let starts = spans
+            .iter()
+            .map(|span| line_starts[span.line - 1] + span.column - 1)
+            .collect();
+        Self {
+            chars,
+            tokens,
+            starts,
+            pos: 0,
+            lines: Vec::new(),
+            pending_comments: Vec::new(),
+        }
+    }
+
+    // ---- token access ----
+
+    fn current(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn peek(&self) -> &Token {
+        self.tokens.get(self.pos + 1).unwrap_or(&Token::Eof)
+    }
+
+    fn at(&self, token: &Token) -> bool {
+        std::mem::discriminant(self.current()) == std::mem::discriminant(token)
+    }
+
+    fn at_end(&self) -> bool {
+        matches!(self.current(), Token::Eof)
+    }
+
+    /// End offset (exclusive) of token `idx` in the source.
+    fn token_end(&self, idx: usize) -> usize {
+        let start = self.starts[idx];
+        match &self.tokens[idx] {
+            Token::StringLiteral(_) => {
+                let mut i = start + 1;
+                while i < self.chars.len() {
+                    match self.chars[i] {
+                        '\\' => i += 2,
+                        '"' => return i + 1,
+                        _ => i += 1,
+                    }
+                }
+                self.chars.len()
+            }
+            Token::Number(_) => {
+                let mut i = start;
+                if self.chars.get(i) == Some(&'-') {
+                    i += 1;
+                }
+                while let Some(&c) = self.chars.get(i) {
+                    let decimal_point =
+                        c == '.' && self.chars.get(i + 1).is_some_and(|d| d.is_ascii_digit());
+                    if c.is_ascii_digit() || c == '_' || decimal_point {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                i
+            }
+            Token::Identifier(name) => start + name.chars().count(),
+            Token::Arrow => start + 2,
+            Token::Eof => start,
+            token => start + token.keyword_text().map_or(1, |k| k.len()),
+        }
+    }
+
+    /// The token's source spelling, verbatim.
+    fn text(&self, idx: usize) -> String {
+        self.chars[self.starts[idx]..self.token_end(idx)].iter().collect()
+    }
+
+    /// Return the current token's text and move past it.
+    fn bump(&mut self) -> String {
+        let text = self.text(self.pos);
+        if !self.at_end() {
+            self.pos += 1;
+        }
+        text
+    }
+
+    /// Newlines and comments between token `idx - 1` and token `idx`.
+    fn trivia_before(&self, idx: usize) -> Vec<Trivia> {
+        let mut i = if idx == 0 { 0 } else { self.token_end(idx - 1) };
+        let end = self.starts[idx];
+        let mut trivia = Vec::new();
+        while i < end {
+            let c = self.chars[i];
+            let next = self.chars.get(i + 1).copied();
+            if c == '\n' {
+                trivia.push(Trivia::Newline);
+                i += 1;
+            } else if c == '/' && next == Some('/') {
+                let from = i;
+                while i < end && self.chars[i] != '\n' {
+                    i += 1;
+                }
+                let comment: String = self.chars[from..i].iter().collect();
+                trivia.push(Trivia::Comment(comment.trim_end().to_string()));
+            } else if c == '/' && next == Some('*') {
+                let from = i;
+                i += 2;
+                while i < end && !(self.chars[i] == '*' && self.chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i = (i + 2).min(end);
+                trivia.push(Trivia::Comment(self.chars[from..i].iter().collect()));
+            } else {
+                i += 1;
+            }
+        }
+        trivia
+    }
+
+    fn newline_before(&self, idx: usize) -> bool {
+        self.trivia_before(idx).iter().any(|t| matches!(t, Trivia::Newline))
+    }
+
+    /// Keep comments found between two tokens of the line being built.
+    fn collect_inline_comments(&mut self) {
+        for trivia in self.trivia_before(self.pos) {
+            if let Trivia::Comment(text) = trivia {
+                self.pending_comments.push(text);
+            }
+        }
+    }
+
+    fn take_pending_comments(&mut self) -> Option<String> {
+        if self.pending_comments.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.pending_comments).join(" "))
+        }
+    }
+
+    // ---- line emission ----
+
+    fn push_text(&mut self, depth: usize, text: String) {
+        let comment = self.take_pending_comments();
+        self.lines.push(Line::Text { depth, text, comment });
+    }
+
+    fn push_blank(&mut self) {
+        if !matches!(self.lines.last(), None | Some(Line::Blank)) {
+            self.lines.push(Line::Blank);
+        }
+    }
+
+    fn attach_trailing_comment(&mut self, text: String, depth: usize) {
+        match self.lines.last_mut() {
+            Some(Line::Text { comment, .. }) | Some(Line::Attr { comment, .. }) => {
+                *comment = Some(match comment.take() {
+                    Some(existing) => format!("{existing} {text}"),
+                    None => text,
+                });
+            }
+            _ => self.lines.push(Line::Comment { depth, text }),
+        }
+    }
+
+    /// Emit the comments (and at most one blank line) that precede the
+    /// current token. Returns whether the source had a blank line right
+    /// before the token or its leading comments.
+    fn leading_trivia(&mut self, depth: usize, opening: bool) -> bool {
+        let trivia = self.trivia_before(self.pos);
+        let mut seen_newline = self.pos == 0;
+        let mut newlines = 0;
+        for item in trivia {
+            match item {
+                Trivia::Newline => {
+                    seen_newline = true;
+                    newlines += 1;
+                }
+                Trivia::Comment(text) if !seen_newline => {
+                    self.attach_trailing_comment(text, depth);
+                }
+                Trivia::Comment(text) => {
+                    if newlines >= 2 && !opening {
+                        self.push_blank();
+                    }
+                    newlines = 0;
+                    self.lines.push(Line::Comment { depth, text });
+                }
+            }
+        }
+        newlines >= 2
+    }
+
+    // ---- structure ----
+
+    /// Format items until the `}` closing this block (not consumed) or EOF.
+    fn format_block(&mut self, depth: usize) {
+        let top_level = depth == 0;
+        let mut first = true;
+        let mut previous_multiline = false;
+
+        loop {
+            let item_start = self.lines.len();
+            // Blank lines right after `{` are dropped; at top level a
+            // leading blank is already impossible (`push_blank` guards it).
+            let opening = first && !top_level;
+            let blank_in_source = self.leading_trivia(depth, opening);
+            if self.at(&Token::RightBrace) || self.at_end() {
+                // Never leave a blank line right before `}` or at EOF.
+                while matches!(self.lines.last(), Some(Line::Blank)) {
+                    self.lines.pop();
+                }
+                return;
+            }
+            if blank_in_source && !opening {
+                self.push_blank();
+            }
+
+            let body_start = self.lines.len();
+            if self.is_attribute_start() {
+                self.format_attribute(depth);
+            } else {
+                self.format_statement(depth);
+            }
+            let multiline = self.lines.len() - body_start > 1;
+
+            if top_level && !first && (multiline || previous_multiline) {
+                let already_blank = item_start > 0
+                    && (matches!(self.lines.get(item_start), Some(Line::Blank))
+                        || matches!(self.lines.get(item_start - 1), Some(Line::Blank)));
+                if !already_blank {
+                    self.lines.insert(item_start, Line::Blank);
+                }
+            }
+            previous_multiline = multiline;
+            first = false;
+        }
+    }
+
+    fn is_attribute_start(&self) -> bool {
+        let key_like = |token: &Token| matches!(token, Token::Identifier(_)) || token.keyword_text().is_some();
+        if self.is_custom_attribute_start() {
+            return key_like(self.peek()) && matches!(self.tokens.get(self.pos + 2), Some(Token::Colon));
+        }
+        key_like(self.current()) && matches!(self.peek(), Token::Colon)
+    }
+
+    /// `attr key: value`, a custom attribute.
+    fn is_custom_attribute_start(&self) -> bool {
+        matches!(self.current(), Token::Identifier(id) if id == "attr") && !matches!(self.peek(), Token::Colon)
+    }
+
+    /// `key: value`, where the value may be a nested `{ ... }` map.
+    fn format_attribute(&mut self, depth: usize) {
+        let key = if self.is_custom_attribute_start() {
+            let attr = self.bump();
+            self.collect_inline_comments();
+            format!("{attr} {}", self.bump())
+        } else {
+            self.bump()
+        };
+        self.collect_inline_comments();
+        self.bump(); // ':'
+        self.collect_inline_comments();
+
+        if self.at(&Token::LeftBrace) {
+            if matches!(self.peek(), Token::RightBrace) {
+                self.bump();
+                self.collect_inline_comments();
+                self.bump();
+                self.push_attr(depth, key, "{}".to_string());
+                return;
+            }
+            self.bump();
+            self.push_attr(depth, key, "{".to_string());
+            self.format_block(depth + 1);
+            self.close_block(depth);
+            return;
+        }
+
+        let value = self.inline_value();
+        if self.at(&Token::LeftBrace) {
+            // Colon-form declaration: `interface_out: "Name" { ... }`
+            let head = format!("{key}: {value}");
+            self.format_block_body(depth, head);
+            return;
+        }
+        self.push_attr(depth, key, value);
+    }
+
+    fn push_attr(&mut self, depth: usize, key: String, value: String) {
+        let comment = self.take_pending_comments();
+        self.lines.push(Line::Attr { depth, key, value, comment });
+    }
+
+    /// A value kept on one line: scalar, dotted reference, list or map.
+    fn inline_value(&mut self) -> String {
+        match self.current() {
+            Token::LeftBracket => {
+                self.bump();
+                let mut items = Vec::new();
+                loop {
+                    self.collect_inline_comments();
+                    if self.at(&Token::RightBracket) || self.at_end() {
+                        break;
+                    }
+                    if self.at(&Token::Comma) {
+                        self.bump();
+                        continue;
+                    }
+                    items.push(self.inline_value());
+                }
+                self.bump();
+                format!("[{}]", items.join(", "))
+            }
+            Token::LeftBrace => {
+                self.bump();
+                let mut entries = Vec::new();
+                loop {
+                    self.collect_inline_comments();
+                    if self.at(&Token::RightBrace) || self.at_end() {
+                        break;
+                    }
+                    let key = self.bump();
+                    self.collect_inline_comments();
+                    self.bump(); // ':'
+                    self.collect_inline_comments();
+                    let value = self.inline_value();
+                    entries.push(format!("{key}: {value}"));
+                }
+                self.bump();
+                if entries.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", entries.join(" "))
+                }
+            }
+            _ => {
+                let mut value = self.bump();
+                while self.at(&Token::Dot) {
+                    self.collect_inline_comments();
+                    value.push_str(&self.bump());
+                    self.collect_inline_comments();
+                    value.push_str(&self.bump());
+                }
+                value
+            }
+        }
+    }
+
+    /// A declaration or braceless statement: head tokens up to `{`, `}`,
+    /// the next attribute, or the end of the source line.
+    fn format_statement(&mut self, depth: usize) {
+        let mut head = String::new();
+        let mut previous: Option<Token> = None;
+
+        loop {
+            if self.at(&Token::LeftBrace) || self.at(&Token::RightBrace) || self.at_end() {
+                break;
+            }
+            if let Some(prev) = &previous {
+                let continues = is_continuation(prev) || is_continuation(self.current());
+                if self.is_attribute_start() || (self.newline_before(self.pos) && !continues) {
+                    break;
+                }
+                self.collect_inline_comments();
+                // `connect COMP-A.port to ...`: dashed ids stay glued
+                let glued = matches!(prev, Token::Dot | Token::Minus)
+                    || matches!(self.current(), Token::Dot | Token::Comma | Token::Minus);
+                if !glued {
+                    head.push(' ');
+                }
+            }
+            previous = Some(self.current().clone());
+            head.push_str(&self.bump());
+        }
+
+        if self.at(&Token::LeftBrace) {
+            self.format_block_body(depth, head);
+        } else {
+            self.push_text(depth, head);
+        }
+    }
+
+    /// `head { ... }` starting at the `{`.
+    fn format_block_body(&mut self, depth: usize, head: String) {
+        let open = self.pos;
+        self.collect_inline_comments();
+        if matches!(self.peek(), Token::RightBrace) {
+            self.bump();
+            self.collect_inline_comments();
+            self.bump();
+            self.push_text(depth, format!("{head} {{}}"));
+            return;
+        }
+        if head.starts_with("trace") {
+            if let Some(inline) = self.inline_trace_block(open) {
+                self.push_text(depth, format!("{head} {inline}"));
+                return;
+            }
+        }
+        self.bump();
+        self.push_text(depth, format!("{head} {{"));
+        self.format_block(depth + 1);
+        self.close_block(depth);
+    }
+
+    /// A trace body written on a single source line with only scalar
+    /// attributes and no comments stays inline.
+    fn inline_trace_block(&mut self, open: usize) -> Option<String> {
+        let mut close = open + 1;
+        while close < self.tokens.len() {
+            match &self.tokens[close] {
+                Token::RightBrace => break,
+                Token::LeftBrace | Token::LeftBracket | Token::Eof => return None,
+                _ => close += 1,
+            }
+        }
+        if (open + 1..=close).any(|idx| !self.trivia_before(idx).is_empty()) {
+            return None;
+        }
+        self.pos = open;
+        Some(self.inline_value())
+    }
+
+    fn close_block(&mut self, depth: usize) {
+        self.bump(); // '}'
+        self.lines.push(Line::Text { depth, text: "}".to_string(), comment: None });
+    }
+
+    // ---- rendering ----
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let mut i = 0;
+        while i < self.lines.len() {
+            // Attribute runs (own-line comments do not break a run) share
+            // one value column.
+            if let Line::Attr { depth, .. } = &self.lines[i] {
+                let run_depth = *depth;
+                let mut end = i;
+                let mut width = 0;
+                while let Some(line) = self.lines.get(end) {
+                    match line {
+                        Line::Attr { depth, key, .. } if *depth == run_depth => {
+                            width = width.max(key.chars().count());
+                        }
+                        Line::Comment { depth, .. } if *depth == run_depth => {}
+                        _ => break,
+                    }
+                    end += 1;
+                }
+                for line in &self.lines[i..end] {
+                    render_line(&mut out, line, width);
+                }
+                i = end;
+            } else {
+                render_line(&mut out, &self.lines[i], 0);
+                i += 1;
+            }
+        }
+        out
+    }
+}
+
+fn render_line(out: &mut String, line: &Line, width: usize) {
+    match line {
+        Line::Blank => {}
+        Line::Comment { depth, text } => {
+            out.push_str(&INDENT.repeat(*depth));
+            out.push_str(text);
+        }
+        Line::Text { depth, text, comment } => {
+            out.push_str(&INDENT.repeat(*depth));
+            out.push_str(text);
+            if let Some(comment) = comment {
+                out.push(' ');
+                out.push_str(comment);
+            }
+        }
+        Line::Attr { depth, key, value, comment } => {
+            let padding = width - key.chars().count();
+            out.push_str(&INDENT.repeat(*depth));
+            out.push_str(key);
+            out.push(':');
+            out.push_str(&" ".repeat(padding + 1));
+            out.push_str(value);
+            if let Some(comment) = comment {
+                out.push(' ');
+                out.push_str(comment);
+            }
+        }
+    }
+    out.push('\n');
+}
+
+/// Tokens that bind a statement across a line break:
+/// `"A" -> "B"`, `a.b`, `trace "X" satisfies "Y"`, `... via "Bus"`.
+fn is_continuation(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Arrow
+            | Token::Dot
+            | Token::Comma
+            | Token::Via
+            | Token::Satisfies
+            | Token::Implements
+            | Token::Validates
+            | Token::Realizes
+            | Token::Refines
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_blocks_and_aligns_attributes() {
+        let source = r#"model Demo {
+}
+requirements stakeholder {
+req "REQ-001" "Range" {
+description: "Detect at 150 m"
+priority: High
+}
+}
+"#;
+        let expected = r#"model Demo {}
+
+requirements stakeholder {
+  req "REQ-001" "Range" {
+    description: "Detect at 150 m"
+    priority:    High
+  }
+}
+"#;
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn custom_attributes_stay_on_one_line() {
+        let source = "model Demo {\n}\nrequirements stakeholder {\nreq \"REQ-001\" {\nattr   supplier: \"Acme\"\ndescription: \"Brake\"\n}\n}\n";
+        let formatted = format_source(source).unwrap();
+        assert!(formatted.contains("    attr supplier: \"Acme\"\n    description:   \"Brake\"\n"), "{formatted}");
+    }
+
+    #[test]
+    fn strings_with_braces_and_colons_are_verbatim() {
+        let source = "model Demo {\n    description: \"a {b}: c \\\"q\\\"\"\n}\n";
+        let formatted = format_source(source).unwrap();
+        assert!(formatted.contains(r#"description: "a {b}: c \"q\"""#), "{formatted}");
+    }
+
+    #[test]
+    fn inline_traces_keep_their_form() {
+        let source = r#"model Demo {
+}
+
+requirements {
+  req "REQ-001" "Range" { description: "x" }
+}
+
+architecture logical {
+  component "Controller" { id: "LC-001" }
+}
+
+trace "LC-001" satisfies "REQ-001" { rationale: "covers: {range}" }
+trace "LC-001" satisfies "REQ-001"
+"#;
+        let formatted = format_source(source).unwrap();
+        assert!(formatted.contains(
+            "trace \"LC-001\" satisfies \"REQ-001\" { rationale: \"covers: {range}\" }\ntrace \"LC-001\" satisfies \"REQ-001\"\n"
+        ), "{formatted}");
+    }
+
+    #[test]
+    fn connect_endpoints_keep_dashed_ids() {
+        let source = r#"model Demo {
+}
+
+architecture logical {
+  component "A" { id: "COMP-A" interface "out" { direction: out } }
+  component "B" { id: "COMP-B" interface "in" { direction: in } }
+  connect   COMP-A.out to COMP-B.in
+}
+"#;
+        let formatted = format_source(source).unwrap();
+        assert!(formatted.contains("\n  connect COMP-A.out to COMP-B.in\n"), "{formatted}");
+    }
+
+    #[test]
+    fn comments_are_kept_and_formatting_is_idempotent() {
+        let source = r#"// Header comment
+model Demo {
+    // about the version
+    version: "1.0"   // trailing
+    author:"me"
+
+
+    description: "d"
+}
+"#;
+        let once = format_source(source).unwrap();
+        assert_eq!(
+            once,
+            "// Header comment\nmodel Demo {\n  // about the version\n  version: \"1.0\" // trailing\n  author:  \"me\"\n\n  description: \"d\"\n}\n"
+        );
+        assert_eq!(format_source(&once).unwrap(), once);
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        assert!(format_source("model Demo {").is_err());
+    }
+}
@@ -2,12 +2,14 @@
 //!
 //! Provides the layered graph representation (nodes, edges, layers) built
 //! from a `SemanticModel`. Used by the v2 generators (`arcviz_elk_static`,
-//! `arcviz_explorer`) as the common intermediate structure before layout.
+//! `arcviz_explorer`) as the common intermediate structure before layout,
+//! and extended to every declared element by [`AdjacencyGraph`], the raw
+//! node/edge list `arclang graph` hands to Gephi, Cytoscape and the like.
 
 use super::semantic::SemanticModel;
 use super::CompilerError;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Graph node for layout engines
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -189,6 +191,149 @@ impl DagreGraph {
     }
 }
 
+/// Node of the adjacency export.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdjacencyNode {
+    pub id: String,
+    /// The model registry's element type (`Requirement`, `Component`, ...).
+    #[serde(rename = "type")]
+    pub element_type: String,
+    pub layer: String,
+    pub label: String,
+}
+
+/// Edge of the adjacency export, between two declared node ids.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdjacencyEdge {
+    pub source: String,
+    pub target: String,
+    /// The trace type (`satisfies`, `refines`, ...), `interface`,
+    /// `allocation` or `connection`.
+    pub kind: String,
+    /// The interface name, trace rationale or allocation verb.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Every declared element and the relations between them, as plain
+/// node/edge lists. Components and their interface edges come from
+/// [`DagreGraph::from_model`]; the other elements follow by id, then
+/// traces, function allocations, deployments and port connections.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdjacencyGraph {
+    pub nodes: Vec<AdjacencyNode>,
+    pub edges: Vec<AdjacencyEdge>,
+}
+
+impl AdjacencyGraph {
+    pub fn from_model(model: &SemanticModel) -> Result<Self, CompilerError> {
+        let dagre = DagreGraph::from_model(model)?;
+        let element_type = |id: &str| {
+            model.all_elements.get(id).map(|e| e.element_type.clone()).unwrap_or_else(|| "Component".to_string())
+        };
+        let mut nodes: Vec<AdjacencyNode> = dagre
+            .nodes
+            .iter()
+            .map(|node| AdjacencyNode {
+                id: node.id.clone(),
+                element_type: element_type(&node.id),
+                layer: node.layer.clone(),
+                label: node.label.clone(),
+            })
+            .collect();
+        let components: HashSet<&str> = dagre.nodes.iter().map(|n| n.id.as_str()).collect();
+        let mut others: Vec<_> = model
+            .all_elements
+            .values()
+            .filter(|e| !components.contains(e.id.as_str()))
+            .collect();
+        others.sort_by(|a, b| a.id.cmp(&b.id));
+        nodes.extend(others.into_iter().map(|e| AdjacencyNode {
+            id: e.id.clone(),
+            element_type: e.element_type.clone(),
+            layer: element_layer(&e.element_type).to_string(),
+            label: e.name.clone(),
+        }));
+
+        let mut edges: Vec<AdjacencyEdge> = dagre
+            .edges
+            .into_iter()
+            .map(|edge| AdjacencyEdge {
+                source: edge.source,
+                target: edge.target,
+                kind: "interface".to_string(),
+                label: Some(edge.label),
+            })
+            .collect();
+        let mut edge = |source: &str, target: &str, kind: &str, label: Option<&str>| {
+            edges.push(AdjacencyEdge {
+                source: source.to_string(),
+                target: target.to_string(),
+                kind: kind.to_string(),
+                label: label.map(str::to_string),
+            })
+        };
+        for trace in &model.traces {
+            edge(&trace.from, &trace.to, &trace.trace_type, trace.rationale.as_deref());
+        }
+        for comp in &model.components {
+            for function in &comp.functions {
+                if let Some(function) = model.resolve_element(function) {
+                    edge(&comp.id, &function, "allocation", Some("performs"));
+                }
+            }
+        }
+        for deployment in &model.deployments {
+            edge(&deployment.component, &deployment.node, "allocation", Some("deployed on"));
+        }
+        for connection in &model.connections {
+            let label = format!("{}.{} -> {}.{}", connection.from_component, connection.from_port, connection.to_component, connection.to_port);
+            edge(&connection.from_component, &connection.to_component, "connection", Some(&label));
+        }
+
+        // Tools reject (or silently invent) nodes an edge names but the
+        // node list lacks.
+        let declared: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        edges.retain(|e| declared.contains(e.source.as_str()) && declared.contains(e.target.as_str()));
+        Ok(Self { nodes, edges })
+    }
+
+    /// `{"nodes": [...], "edges": [...]}`.
+    pub fn to_json(&self) -> Result<String, CompilerError> {
+        serde_json::to_string_pretty(self).map_err(|e| CompilerError::Semantic(e.to_string()))
+    }
+
+    /// Cytoscape.js elements JSON (`{"elements": {"nodes": [{"data": ...}],
+    /// "edges": [...]}}`), which Cytoscape desktop imports as well. Edges
+    /// get ids `e0`, `e1`... in edge order.
+    pub fn to_cytoscape_json(&self) -> Result<String, CompilerError> {
+        let nodes: Vec<_> = self.nodes.iter().map(|node| serde_json::json!({ "data": node })).collect();
+        let edges: Vec<_> = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(i, edge)| {
+                let mut data = serde_json::to_value(edge).map_err(|e| CompilerError::Semantic(e.to_string()))?;
+                data["id"] = serde_json::Value::String(format!("e{i}"));
+                Ok(serde_json::json!({ "data": data }))
+            })
+            .collect::<Result<_, CompilerError>>()?;
+        serde_json::to_string_pretty(&serde_json::json!({ "elements": { "nodes": nodes, "edges": edges } }))
+            .map_err(|e| CompilerError::Semantic(e.to_string()))
+    }
+}
+
+/// Arcadia layer of an element that is not a component, by its type.
+fn element_layer(element_type: &str) -> &'static str {
+    match element_type {
+        "Requirement" => "Requirements",
+        "Actor" | "Entity" | "Activity" | "OperationalActivity" | "OperationalCapability" => "Operational",
+        "SystemComponent" | "SystemFunction" | "Mission" | "Capability" | "Scenario" => "System",
+        "PhysicalPort" | "PhysicalLink" | "PhysicalPath" => "Physical",
+        _ => "Logical",
+    }
+}
+
 fn infer_stereotype(name: &str) -> String {
     if name.contains("Database") || name.contains("Storage") || name.contains("Warehouse") {
         "<<datastore>>".to_string()
@@ -0,0 +1,409 @@
+//! GraphViz (DOT) diagram generation.
+//!
+//! Components are grouped into one `subgraph cluster_*` per Arcadia layer,
+//! requirements into their own cluster; functions sit in the cluster of
+//! the component they are allocated to. Traces and interfaces become
+//! labelled edges. Node shapes follow the element type: `box` for
+//! components and actors, `ellipse` for functions and activities, `note`
+//! for requirements, `hexagon` for missions and capabilities. A component
+//! with ports is a `record` with one field per port, and port connections
+//! run between those fields.
+//!
+//! Render with `dot -Tsvg model.dot -o model.svg` (or let
+//! `arclang diagram --format svg` do it).
+
+use super::ast::PortDirection;
+use super::codegen::{components_by_layer, layer_title};
+use super::semantic::SemanticModel;
+use super::CompilerError;
+use crate::safety::safety_case::{GsnGraph, GsnNodeKind, GsnRelation};
+use std::collections::{HashMap, HashSet};
+
+/// DOT node shape for a semantic `element_type`.
+fn shape_for(element_type: &str) -> &'static str {
+    match element_type {
+        "Requirement" => "note",
+        "Function" | "SystemFunction" | "Activity" | "OperationalActivity" => "ellipse",
+        "Mission" | "OperationalCapability" | "Capability" => "hexagon",
+        _ => "box",
+    }
+}
+
+/// Quote a string as a DOT identifier / label.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Escape the characters that structure a `record` label.
+fn record_text(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '{' | '}' | '|' | '<' | '>' | '"' | '\\' => vec!['\\', c],
+            '\n' => vec![' '],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+/// Record field of a component's port.
+fn port_field(index: usize) -> String {
+    format!("p{}", index)
+}
+
+fn cluster_name(layer: &str) -> String {
+    let slug: String = layer
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    format!("cluster_{}", slug)
+}
+
+pub fn generate_graphviz_digraph(model: &SemanticModel, title: &str) -> Result<String, CompilerError> {
+    let mut dot = String::new();
+    let mut emitted: HashSet<String> = HashSet::new();
+
+    let element_type = |id: &str| -> &str {
+        model
+            .all_elements
+            .get(id)
+            .map(|e| e.element_type.as_str())
+            .unwrap_or("Component")
+    };
+
+    dot.push_str(&format!("digraph {} {{\n", quote(title)));
+    dot.push_str(&format!("  label={};\n  labelloc=t;\n", quote(title)));
+    dot.push_str("  rankdir=LR;\n  compound=true;\n");
+    dot.push_str("  node [fontname=\"Helvetica\", fontsize=10, style=filled, fillcolor=\"#ffffff\"];\n");
+    dot.push_str("  edge [fontname=\"Helvetica\", fontsize=9];\n\n");
+
+    // Requirements
+    if !model.requirements.is_empty() {
+        dot.push_str("  subgraph cluster_requirements {\n    label=\"Requirements\";\n    style=rounded;\n");
+        for req in &model.requirements {
+            let label = if req.description.is_empty() {
+                req.id.clone()
+            } else {
+                format!("{}\n{}", req.id, req.description)
+            };
+            dot.push_str(&format!(
+                "    {} [shape=note, fillcolor=\"#fff2cc\", label={}];\n",
+                quote(&req.id),
+                quote(&label)
+            ));
+            emitted.insert(req.id.clone());
+        }
+        dot.push_str("  }\n\n");
+    }
+
+    // Function name -> id, to place allocated functions next to their owner
+    let mut function_ids: HashMap<&str, &str> = HashMap::new();
+    for function in &model.functions {
+        function_ids.entry(function.name.as_str()).or_insert(function.id.as_str());
+    }
+
+    let mut allocations = Vec::new();
+    for (layer, components) in components_by_layer(model) {
+        dot.push_str(&format!(
+            "  subgraph {} {{\n    label={};\n    style=rounded;\n",
+            cluster_name(layer),
+            quote(&layer_title(layer))
+        ));
+        for comp in components {
+            if !emitted.insert(comp.id.clone()) {
+                continue;
+            }
+            let ports: Vec<String> = model
+                .ports
+                .iter()
+                .filter(|p| p.component == comp.id)
+                .enumerate()
+                .map(|(i, p)| {
+                    let direction = match p.direction {
+                        PortDirection::In => "in",
+                        PortDirection::Out => "out",
+                        PortDirection::InOut => "inout",
+                    };
+                    format!("<{}> {} ({})", port_field(i), record_text(&p.name), direction)
+                })
+                .collect();
+            if ports.is_empty() {
+                dot.push_str(&format!(
+                    "    {} [shape={}, fillcolor=\"#dae8fc\", label={}];\n",
+                    quote(&comp.id),
+                    shape_for(element_type(&comp.id)),
+                    quote(&comp.name)
+                ));
+            } else {
+                dot.push_str(&format!(
+                    "    {} [shape=record, fillcolor=\"#dae8fc\", label=\"{{{}|{{{}}}}}\"];\n",
+                    quote(&comp.id),
+                    record_text(&comp.name),
+                    ports.join("|")
+                ));
+            }
+            for function in &comp.functions {
+                let Some(function_id) = function_ids.get(function.as_str()) else { continue };
+                if emitted.insert(function_id.to_string()) {
+                    dot.push_str(&format!(
+                        "    {} [shape=ellipse, fillcolor=\"#d5e8d4\", label={}];\n",
+                        quote(function_id),
+                        quote(function)
+                    ));
+                }
+                allocations.push((comp.id.clone(), function_id.to_string()));
+            }
+        }
+        dot.push_str("  }\n\n");
+    }
+
+    // Unallocated functions
+    let loose: Vec<_> = model.functions.iter().filter(|f| !emitted.contains(&f.id)).collect();
+    for function in loose {
+        emitted.insert(function.id.clone());
+        dot.push_str(&format!(
+            "  {} [shape={}, fillcolor=\"#d5e8d4\", label={}];\n",
+            quote(&function.id),
+            shape_for(element_type(&function.id)),
+            quote(&function.name)
+        ));
+    }
+
+    // Trace endpoints not drawn above (capabilities, missions, ...)
+    for trace in &model.traces {
+        for endpoint in [&trace.from, &trace.to] {
+            if let Some(element) = model.all_elements.get(endpoint.as_str()) {
+                if emitted.insert(endpoint.clone()) {
+                    dot.push_str(&format!(
+                        "  {} [shape={}, label={}];\n",
+                        quote(endpoint),
+                        shape_for(&element.element_type),
+                        quote(&element.name)
+                    ));
+                }
+            }
+        }
+    }
+    dot.push('\n');
+
+    for (component, function) in &allocations {
+        dot.push_str(&format!(
+            "  {} -> {} [style=dashed, arrowhead=none, label=\"allocates\"];\n",
+            quote(component),
+            quote(function)
+        ));
+    }
+    for trace in &model.traces {
+        if emitted.contains(&trace.from) && emitted.contains(&trace.to) {
+            dot.push_str(&format!(
+                "  {} -> {} [label={}];\n",
+                quote(&trace.from),
+                quote(&trace.to),
+                quote(&trace.trace_type)
+            ));
+        }
+    }
+
+    // Interfaces name their endpoints by id or by component name
+    let component_ids: HashMap<&str, &str> = model
+        .components
+        .iter()
+        .map(|c| (c.name.as_str(), c.id.as_str()))
+        .collect();
+    let resolve = |endpoint: &str| -> Option<String> {
+        if emitted.contains(endpoint) {
+            Some(endpoint.to_string())
+        } else {
+            component_ids.get(endpoint).map(|id| id.to_string())
+        }
+    };
+    for interface in &model.interfaces {
+        if let (Some(from), Some(to)) = (resolve(&interface.from), resolve(&interface.to)) {
+            dot.push_str(&format!(
+                "  {} -> {} [color=\"#6c8ebf\", penwidth=1.5, label={}];\n",
+                quote(&from),
+                quote(&to),
+                quote(&interface.name)
+            ));
+        }
+    }
+
+    // Port connections, between the record fields drawn above
+    let field = |component: &str, port: &str| -> Option<String> {
+        let index = model
+            .ports
+            .iter()
+            .filter(|p| p.component == component)
+            .position(|p| p.name == port)?;
+        Some(format!("{}:{}", quote(component), port_field(index)))
+    };
+    for connection in &model.connections {
+        let (Some(from), Some(to)) = (
+            field(&connection.from_component, &connection.from_port),
+            field(&connection.to_component, &connection.to_port),
+        ) else {
+            continue;
+        };
+        let label = connection.label.clone().unwrap_or_else(|| {
+            model
+                .get_port(&connection.from_component, &connection.from_port)
+                .map(|p| p.interface.clone())
+                .unwrap_or_default()
+        });
+        dot.push_str(&format!(
+            "  {} -> {} [color=\"#6c8ebf\", penwidth=1.5, label={}];\n",
+            from,
+            to,
+            quote(&label)
+        ));
+    }
+
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+/// A GSN safety case (see `safety::safety_case::build_gsn`), top-down: goals
+/// are boxes, strategies parallelograms, solutions circles and contexts
+/// rounded boxes. Undeveloped nodes are dashed, goals without evidence red.
+pub fn generate_graphviz_gsn(graph: &GsnGraph, title: &str) -> Result<String, CompilerError> {
+    let mut dot = String::new();
+    dot.push_str(&format!("digraph {} {{\n", quote(title)));
+    dot.push_str(&format!("  label={};\n  labelloc=t;\n", quote(title)));
+    dot.push_str("  rankdir=TB;\n");
+    dot.push_str("  node [fontname=\"Helvetica\", fontsize=10, style=filled, fillcolor=\"#ffffff\"];\n\n");
+
+    for node in &graph.nodes {
+        let (shape, fill) = match node.kind {
+            GsnNodeKind::Goal => ("box", "#dae8fc"),
+            GsnNodeKind::Strategy => ("parallelogram", "#fff2cc"),
+            GsnNodeKind::Solution => ("circle", "#d5e8d4"),
+            GsnNodeKind::Context => ("box", "#f5f5f5"),
+        };
+        let mut style = vec!["filled"];
+        if node.kind == GsnNodeKind::Context {
+            style.push("rounded");
+        }
+        if node.undeveloped {
+            style.push("dashed");
+        }
+        let mut attrs = format!("shape={}, style={}", shape, quote(&style.join(",")));
+        if node.unsupported {
+            attrs.push_str(", fillcolor=\"#f8cecc\", color=\"#b85450\", penwidth=2");
+        } else {
+            attrs.push_str(&format!(", fillcolor=\"{}\"", fill));
+        }
+        let mut label = node.text.clone();
+        if node.unsupported {
+            label.push_str("\n(unsupported: no evidence)");
+        } else if node.undeveloped {
+            label.push_str("\n(undeveloped)");
+        }
+        dot.push_str(&format!("  {} [{}, label={}];\n", quote(&node.id), attrs, quote(&label)));
+    }
+    dot.push('\n');
+
+    for edge in &graph.edges {
+        let attrs = match edge.relation {
+            GsnRelation::SupportedBy => "",
+            GsnRelation::InContextOf => " [arrowhead=empty]",
+        };
+        dot.push_str(&format!("  {} -> {}{};\n", quote(&edge.from), quote(&edge.to), attrs));
+    }
+
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn clusters_layers_and_shapes_by_element_type() {
+        let source = r#"
+model Demo {
+}
+
+requirements {
+  req "REQ-001" "Range" { description: "Detect \"far\" obstacles" priority: "High" }
+}
+
+architecture logical {
+  component "Controller" { id: "LC-001" function "compute" }
+}
+
+trace "LC-001" satisfies "REQ-001" { rationale: "x" }
+"#;
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model;
+        let dot = generate_graphviz_digraph(&model, "Demo").unwrap();
+
+        assert!(dot.starts_with("digraph \"Demo\" {"));
+        assert!(dot.contains("subgraph cluster_logical {"));
+        assert!(dot.contains("label=\"Logical Architecture\""));
+        assert!(dot.contains("\"REQ-001\" [shape=note"));
+        assert!(dot.contains("Detect \\\"far\\\" obstacles"));
+        assert!(dot.contains("\"LC-001\" [shape=box"));
+        assert!(dot.contains("[shape=ellipse, fillcolor=\"#d5e8d4\", label=\"compute\"]"));
+        assert!(dot.contains("\"LC-001\" -> \"REQ-001\" [label=\"satisfies\"];"));
+    }
+
+    #[test]
+    fn port_connections_join_record_fields() {
+        let source = r#"
+model Demo {
+}
+
+architecture logical {
+  component "Sensor" { id: "S" interface "speed" { direction: out provides: "ISpeed" } }
+  component "Controller" {
+    id: "C"
+    interface "mode" { direction: in requires: "IMode" }
+    interface "speed" { direction: in requires: "ISpeed" }
+  }
+  connect S.speed to C.speed
+}
+"#;
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model;
+        let dot = generate_graphviz_digraph(&model, "Demo").unwrap();
+
+        assert!(dot.contains("\"C\" [shape=record, fillcolor=\"#dae8fc\", label=\"{Controller|{<p0> mode (in)|<p1> speed (in)}}\"];"));
+        assert!(dot.contains("\"S\":p0 -> \"C\":p1 [color=\"#6c8ebf\", penwidth=1.5, label=\"ISpeed\"];"));
+    }
+
+    #[test]
+    fn gsn_marks_unsupported_and_undeveloped_nodes() {
+        use crate::safety::safety_case::{GsnEdge, GsnNode};
+
+        let node = |id: &str, kind, undeveloped, unsupported| GsnNode {
+            id: id.to_string(),
+            kind,
+            text: id.to_string(),
+            undeveloped,
+            unsupported,
+        };
+        let graph = GsnGraph {
+            nodes: vec![
+                node("G_1", GsnNodeKind::Goal, false, true),
+                node("C_1", GsnNodeKind::Context, false, false),
+                node("U_1", GsnNodeKind::Goal, true, false),
+            ],
+            edges: vec![
+                GsnEdge { from: "G_1".to_string(), to: "C_1".to_string(), relation: GsnRelation::InContextOf },
+                GsnEdge { from: "G_1".to_string(), to: "U_1".to_string(), relation: GsnRelation::SupportedBy },
+            ],
+        };
+        let dot = generate_graphviz_gsn(&graph, "Case").unwrap();
+
+        assert!(dot.contains("\"G_1\" [shape=box, style=\"filled\", fillcolor=\"#f8cecc\", color=\"#b85450\""));
+        assert!(dot.contains("\"C_1\" [shape=box, style=\"filled,rounded\""));
+        assert!(dot.contains("\"U_1\" [shape=box, style=\"filled,dashed\""));
+        assert!(dot.contains("\"G_1\" -> \"C_1\" [arrowhead=empty];"));
+        assert!(dot.contains("\"G_1\" -> \"U_1\";"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Standalone HTML report (`arclang export --format html`).
+//!
+//! One self-contained file: requirements, a collapsible section per
+//! Arcadia layer, the traceability matrix, an inline Mermaid diagram of
+//! the architecture, a Pareto chart of the generated FMEA and a coverage
+//! badge. All styling is embedded; the
+//! only external reference is the Mermaid CDN script that draws the
+//! diagram, so the report can be mailed around and still reads fine
+//! offline (the diagram source stays visible as text).
+
+use super::codegen::{components_by_layer, layer_title};
+use super::mermaid_generator::{generate_mermaid_flowchart, FlowDirection};
+use super::semantic::SemanticModel;
+use super::CompilerError;
+use crate::safety::fmea;
+use crate::safety::FMEAConfig;
+
+/// Page styling shared by every generated HTML page showing a diagram
+/// (this report and the `diagram --open` previews).
+pub const MERMAID_PAGE_CSS: &str = r#"
+        body {
+            margin: 0;
+            padding: 20px;
+            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif;
+            background: #f5f5f5;
+        }
+        .container {
+            max-width: 100%;
+            margin: 0 auto;
+            background: white;
+            padding: 20px;
+            border-radius: 8px;
+            box-shadow: 0 2px 8px rgba(0,0,0,0.1);
+        }
+        h1 {
+            color: #333;
+            margin-top: 0;
+        }
+        .mermaid {
+            display: flex;
+            justify-content: center;
+        }
+"#;
+
+/// Report additions on top of `MERMAID_PAGE_CSS` (badges, collapsible
+/// sections, tables), shared with the traceability heatmap.
+pub(crate) const REPORT_CSS: &str = r#"
+        .badge {
+            display: inline-block;
+            padding: 4px 10px;
+            border-radius: 12px;
+            color: white;
+            font-weight: 600;
+            font-size: 0.9em;
+        }
+        .badge.good { background: #2e7d32; }
+        .badge.fair { background: #ef6c00; }
+        .badge.poor { background: #c62828; }
+        details {
+            margin: 12px 0;
+            border: 1px solid #ddd;
+            border-radius: 6px;
+            padding: 8px 12px;
+        }
+        summary {
+            cursor: pointer;
+            font-weight: 600;
+            font-size: 1.1em;
+        }
+        table {
+            border-collapse: collapse;
+            width: 100%;
+            margin: 8px 0;
+            font-size: 0.9em;
+        }
+        th, td {
+            border: 1px solid #ddd;
+            padding: 6px 8px;
+            text-align: left;
+            vertical-align: top;
+        }
+        th { background: #eee; }
+        .component h3 { margin-bottom: 4px; }
+        .muted { color: #777; }
+        .pareto { display: grid; grid-template-columns: max-content 1fr max-content; gap: 4px 8px; align-items: center; font-size: 0.9em; }
+        .pareto .bar { height: 14px; background: #9e9e9e; border-radius: 2px; }
+        .pareto .vital { background: #c62828; }
+"#;
+
+pub(crate) fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Badge class for a traceability coverage percentage.
+pub(crate) fn coverage_class(coverage: f64) -> &'static str {
+    if coverage >= 80.0 {
+        "good"
+    } else if coverage >= 50.0 {
+        "fair"
+    } else {
+        "poor"
+    }
+}
+
+pub fn generate_html_report(model: &SemanticModel, title: &str) -> Result<String, CompilerError> {
+    let metrics = model.compute_metrics();
+    let diagram = generate_mermaid_flowchart(model, title, "elk", FlowDirection::TopBottom)?;
+
+    let mut body = String::new();
+    body.push_str(&format!(
+        "        <h1>{} <span class=\"badge {}\" title=\"Traceability coverage\">{:.1}% traced</span></h1>\n",
+        html_escape(title),
+        coverage_class(metrics.traceability_coverage),
+        metrics.traceability_coverage
+    ));
+    body.push_str(&format!(
+        "        <p class=\"muted\">{} elements · {} requirements · {} components · {} functions · {} traces</p>\n",
+        metrics.total_elements,
+        metrics.requirements_count,
+        metrics.components_count,
+        metrics.functions_count,
+        metrics.traces_count
+    ));
+
+    // Requirements
+    body.push_str(&format!(
+        "        <details open>\n          <summary>Requirements ({})</summary>\n",
+        model.requirements.len()
+    ));
+    body.push_str("          <table>\n            <tr><th>ID</th><th>Description</th><th>Priority</th><th>Safety Level</th><th>Traces to</th></tr>\n");
+    for req in &model.requirements {
+        let traces: Vec<String> = model
+            .get_traces_from(&req.id)
+            .iter()
+            .map(|trace| format!("{} ({})", html_escape(&trace.to), html_escape(&trace.trace_type)))
+            .collect();
+        body.push_str(&format!(
+            "            <tr id=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            html_escape(&req.id),
+            html_escape(&req.id),
+            html_escape(&req.description),
+            html_escape(&req.priority),
+            html_escape(req.safety_level.as_deref().unwrap_or("-")),
+            if traces.is_empty() { "-".to_string() } else { traces.join("<br>") }
+        ));
+    }
+    body.push_str("          </table>\n        </details>\n");
+
+    // One collapsible section per Arcadia layer
+    for (layer, components) in components_by_layer(model) {
+        body.push_str(&format!(
+            "        <details>\n          <summary>{} ({})</summary>\n",
+            html_escape(&layer_title(layer)),
+            components.len()
+        ));
+        for comp in components {
+            body.push_str(&format!(
+                "          <div class=\"component\" id=\"{}\">\n            <h3>{}</h3>\n            <p class=\"muted\">{} · {}</p>\n",
+                html_escape(&comp.id),
+                html_escape(&comp.name),
+                html_escape(&comp.id),
+                html_escape(&comp.component_type)
+            ));
+            if !comp.functions.is_empty() {
+                let functions: Vec<String> = comp.functions.iter().map(|f| html_escape(f)).collect();
+                body.push_str(&format!("            <p>Functions: {}</p>\n", functions.join(", ")));
+            }
+            let traces = model.get_traces_to(&comp.id);
+            if !traces.is_empty() {
+                body.push_str("            <p>Traced from:</p>\n            <ul>\n");
+                for trace in traces {
+                    body.push_str(&format!(
+                        "              <li>{} ({})</li>\n",
+                        html_escape(&trace.from),
+                        html_escape(&trace.trace_type)
+                    ));
+                }
+                body.push_str("            </ul>\n");
+            }
+            body.push_str("          </div>\n");
+        }
+        body.push_str("        </details>\n");
+    }
+
+    // Traceability matrix
+    body.push_str(&format!(
+        "        <details>\n          <summary>Traceability Matrix ({})</summary>\n",
+        model.traces.len()
+    ));
+    body.push_str("          <table>\n            <tr><th>From</th><th>To</th><th>Type</th><th>Rationale</th></tr>\n");
+    for trace in &model.traces {
+        body.push_str(&format!(
+            "            <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            html_escape(&trace.from),
+            html_escape(&trace.to),
+            html_escape(&trace.trace_type),
+            html_escape(trace.rationale.as_deref().unwrap_or("-"))
+        ));
+    }
+    body.push_str("          </table>\n        </details>\n");
+
+    // FMEA Pareto: bars scaled to the highest RPN, the vital few in red;
+    // the same data as JSON for charting elsewhere.
+    let report = fmea::pareto(&fmea::generate_fmea(model, &FMEAConfig::default()));
+    if let Some(max_rpn) = report.items.first().map(|i| i.rpn.max(1)) {
+        body.push_str(&format!(
+            "        <details>\n          <summary>FMEA Pareto ({} of {} failure modes carry {:.0}% of the risk)</summary>\n",
+            report.vital_few,
+            report.items.len(),
+            fmea::PARETO_CUTOFF
+        ));
+        body.push_str("          <div class=\"pareto\">\n");
+        for (i, item) in report.items.iter().enumerate() {
+            body.push_str(&format!(
+                "            <span>{} {} · {}</span><div class=\"bar{}\" style=\"width: {:.1}%\"></div><span>RPN {} ({:.1}%)</span>\n",
+                html_escape(&item.id),
+                html_escape(&item.component),
+                html_escape(&item.failure_mode),
+                if i < report.vital_few { " vital" } else { "" },
+                item.rpn as f64 * 100.0 / max_rpn as f64,
+                item.rpn,
+                item.cumulative
+            ));
+        }
+        body.push_str("          </div>\n");
+        let data = serde_json::to_string(&report).map_err(|e| CompilerError::Other(e.to_string()))?;
+        body.push_str(&format!(
+            "          <script type=\"application/json\" id=\"fmea-pareto\">{}</script>\n        </details>\n",
+            data.replace("</", "<\\/")
+        ));
+    }
+
+    // Diagram
+    body.push_str("        <details open>\n          <summary>Architecture Diagram</summary>\n");
+    body.push_str(&format!(
+        "          <div class=\"mermaid\">\n{}\n          </div>\n        </details>\n",
+        html_escape(&diagram)
+    ));
+
+    Ok(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <script src="https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.min.js"></script>
+    <script>
+        mermaid.initialize({{ startOnLoad: true, theme: 'default' }});
+    </script>
+    <style>{MERMAID_PAGE_CSS}{REPORT_CSS}    </style>
+</head>
+<body>
+    <div class="container">
+{body}    </div>
+</body>
+</html>
+"#,
+        title = html_escape(title),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const MODEL: &str = r#"
+model Demo {
+}
+
+requirements {
+  req "REQ-001" "Range" { description: "Detect <obstacles> at 150 m" priority: "High" }
+}
+
+architecture logical {
+  component "Controller" { id: "LC-001" function "compute" }
+}
+
+trace "LC-001" satisfies "REQ-001" { rationale: "x" }
+"#;
+
+    #[test]
+    fn report_is_self_contained() {
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(MODEL)
+            .expect("compiles")
+            .semantic_model;
+        let html = generate_html_report(&model, "Demo").unwrap();
+
+        assert!(html.contains("<summary>Logical Architecture (1)</summary>"));
+        assert!(html.contains("Detect &lt;obstacles&gt; at 150 m"));
+        assert!(html.contains("class=\"badge good\""));
+        assert!(html.contains("<div class=\"mermaid\">"));
+        assert!(html.contains("<div class=\"bar vital\" style=\"width: 100.0%\">"));
+        assert!(html.contains("<script type=\"application/json\" id=\"fmea-pareto\">{\"total_rpn\":"));
+        // Only the Mermaid script is fetched from outside.
+        assert_eq!(html.matches("src=\"http").count(), 1);
+        assert!(!html.contains("<link"));
+    }
+}
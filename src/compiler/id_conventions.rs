@@ -0,0 +1,275 @@
+//! Element id naming conventions for `arclang check`. The `[id_conventions]`
+//! section of the project's `.arclang/config.toml` maps element types to the
+//! pattern their ids must match:
+//!
+//! ```toml
+//! [id_conventions]
+//! Requirement = 'REQ-\d{4}'
+//! Component = 'COMP-[A-Z]{2,}-\d+'
+//! ```
+//!
+//! Types are the model registry's (`Requirement`, `Component`,
+//! `SystemFunction`, `Actor`, ... — the `kind` of the XML export), matched
+//! case-insensitively, and a pattern must match the whole id. An id that
+//! does not is an `E-ID-CONVENTION` error at its declaration, with a
+//! conformant, unused id suggested when one can be derived from the id
+//! itself, from conforming ids of the same type, or from the pattern's
+//! literal prefix.
+
+use std::collections::{BTreeMap, HashSet};
+
+use regex::Regex;
+
+use super::diagnostic::Diagnostic;
+use super::semantic::SemanticModel;
+
+pub const ID_CONVENTION: &str = "E-ID-CONVENTION";
+
+/// One element type's pattern.
+#[derive(Debug, Clone)]
+struct IdRule {
+    element_type: String,
+    pattern: String,
+    regex: Regex,
+}
+
+/// The configured patterns, compiled once.
+#[derive(Debug, Clone, Default)]
+pub struct IdConventions {
+    rules: Vec<IdRule>,
+}
+
+impl IdConventions {
+    /// Compile `patterns` (element type -> regex). An invalid pattern is an
+    /// error naming its type.
+    pub fn new(patterns: &BTreeMap<String, String>) -> Result<Self, String> {
+        let rules = patterns
+            .iter()
+            .map(|(element_type, pattern)| {
+                let regex = Regex::new(&format!("^(?:{pattern})$"))
+                    .map_err(|e| format!("id_conventions.{element_type}: invalid pattern '{pattern}': {e}"))?;
+                Ok(IdRule { element_type: element_type.clone(), pattern: pattern.clone(), regex })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { rules })
+    }
+
+    fn rule(&self, element_type: &str) -> Option<&IdRule> {
+        self.rules.iter().find(|rule| rule.element_type.eq_ignore_ascii_case(element_type))
+    }
+}
+
+/// One id of [`check_id_conventions`] that breaks its type's convention.
+#[derive(Debug, Clone)]
+pub struct IdFinding {
+    pub code: &'static str,
+    pub element: String,
+    pub element_type: String,
+    /// A conformant id not used by any other element.
+    pub suggestion: Option<String>,
+    pub diagnostic: Diagnostic,
+}
+
+/// Every element whose id does not match its type's pattern, in
+/// declaration order.
+pub fn check_id_conventions(model: &SemanticModel, conventions: &IdConventions) -> Vec<IdFinding> {
+    let mut elements: Vec<_> = model.all_elements.values().collect();
+    elements.sort_by_key(|e| (e.span.as_ref().map(|s| (s.line, s.column)), e.id.clone()));
+    let mut taken: HashSet<String> = model.all_elements.keys().cloned().collect();
+
+    let mut findings = Vec::new();
+    for element in &elements {
+        let Some(rule) = conventions.rule(&element.element_type) else { continue };
+        if rule.regex.is_match(&element.id) {
+            continue;
+        }
+        let conforming: Vec<&str> = elements
+            .iter()
+            .filter(|e| e.element_type == element.element_type && rule.regex.is_match(&e.id))
+            .map(|e| e.id.as_str())
+            .collect();
+        let suggestion = suggest(&element.id, rule, &conforming, &taken);
+        if let Some(suggestion) = &suggestion {
+            taken.insert(suggestion.clone());
+        }
+        let hint = match &suggestion {
+            Some(id) => format!(" (suggested: '{id}')"),
+            None => String::new(),
+        };
+        findings.push(IdFinding {
+            code: ID_CONVENTION,
+            element: element.id.clone(),
+            element_type: element.element_type.clone(),
+            diagnostic: Diagnostic::at(
+                format!(
+                    "{} id '{}' does not match the convention '{}'{}",
+                    element.element_type, element.id, rule.pattern, hint
+                ),
+                element.span.clone(),
+            ),
+            suggestion,
+        });
+    }
+    findings
+}
+
+/// A conformant id for `id`: the id normalized (upper case, `-`
+/// separators), else its number — or the next free one — in the shape of a
+/// conforming id of the same type or after the pattern's literal prefix.
+fn suggest(id: &str, rule: &IdRule, conforming: &[&str], taken: &HashSet<String>) -> Option<String> {
+    let fits = |candidate: &str| rule.regex.is_match(candidate) && !taken.contains(candidate);
+
+    let normalized = id.to_uppercase().replace([' ', '_'], "-");
+    if fits(&normalized) {
+        return Some(normalized);
+    }
+
+    let mut shapes: Vec<(&str, usize)> = conforming
+        .iter()
+        .map(|c| split_number(c))
+        .filter(|(_, digits)| !digits.is_empty())
+        .map(|(prefix, digits)| (prefix, digits.len()))
+        .collect();
+    shapes.dedup();
+    let literal = literal_prefix(&rule.pattern);
+    shapes.extend((1..=6).map(|width| (literal.as_str(), width)));
+
+    let next = conforming
+        .iter()
+        .filter_map(|c| split_number(c).1.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let number = split_number(id).1.parse::<u64>().ok();
+    shapes.iter().find_map(|(prefix, width)| {
+        number
+            .into_iter()
+            .chain((next..).take(taken.len() + 1))
+            .map(|n| format!("{prefix}{n:0width$}"))
+            .find(|candidate| fits(candidate))
+    })
+}
+
+/// `("REQ-", "0042")` for `REQ-0042`.
+fn split_number(id: &str) -> (&str, &str) {
+    let digits = id.len() - id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    id.split_at(id.len() - digits)
+}
+
+/// The text every match starts with: `REQ-` for `REQ-\d{4}`.
+fn literal_prefix(pattern: &str) -> String {
+    let mut prefix = String::new();
+    let mut chars = pattern.strip_prefix('^').unwrap_or(pattern).chars().peekable();
+    while let Some(c) = chars.next() {
+        let literal = match c {
+            '\\' => match chars.next() {
+                Some(escaped) if !escaped.is_alphanumeric() => escaped,
+                _ => break,
+            },
+            '.' | '^' | '$' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' => break,
+            _ => c,
+        };
+        // A quantifier applies to the character before it
+        if matches!(chars.peek(), Some('*' | '+' | '?' | '{')) {
+            break;
+        }
+        prefix.push(literal);
+    }
+    prefix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn compile(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    fn conventions(pairs: &[(&str, &str)]) -> IdConventions {
+        let patterns = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        IdConventions::new(&patterns).expect("valid patterns")
+    }
+
+    const MODEL: &str = r#"model Test {
+}
+system_analysis "SA" {
+    requirement "REQ-0001" {
+        description: "The controller shall brake"
+    }
+    requirement "req_0002" {
+        description: "The controller shall warn"
+    }
+    requirement "SYS-7" {
+        description: "The controller shall log"
+    }
+}
+logical_architecture "LA" {
+    component "Controller" { id: "COMP-EC-1" }
+    component "Sensor" { id: "LC-002" }
+}
+"#;
+
+    #[test]
+    fn flags_ids_off_convention_at_their_declaration_with_a_suggestion() {
+        let model = compile(MODEL);
+        let findings = check_id_conventions(
+            &model,
+            &conventions(&[("requirement", r"REQ-\d{4}"), ("Component", r"COMP-[A-Z]{2,}-\d+")]),
+        );
+        let flagged: Vec<_> = findings.iter().map(|f| (f.element.as_str(), f.suggestion.as_deref())).collect();
+        assert_eq!(
+            flagged,
+            [("req_0002", Some("REQ-0002")), ("SYS-7", Some("REQ-0007")), ("LC-002", Some("COMP-EC-2"))]
+        );
+        let finding = &findings[0];
+        assert_eq!(finding.code, ID_CONVENTION);
+        assert_eq!(finding.diagnostic.span.as_ref().map(|s| s.line), Some(7));
+        assert!(finding.diagnostic.message.contains(r"convention 'REQ-\d{4}'"), "{}", finding.diagnostic);
+    }
+
+    #[test]
+    fn patterns_match_the_whole_id_and_unconfigured_types_pass() {
+        let model = compile(MODEL);
+        let findings = check_id_conventions(&model, &conventions(&[("Requirement", r"REQ-\d")]));
+        // `REQ-0001` starts like `REQ-\d` but is longer
+        assert!(findings.iter().any(|f| f.element == "REQ-0001"));
+        assert!(findings.iter().all(|f| f.element_type == "Requirement"));
+    }
+
+    #[test]
+    fn suggestions_never_reuse_a_taken_id() {
+        let model = compile(
+            r#"model Test {
+}
+system_analysis "SA" {
+    requirement "REQ-0001" { description: "The controller shall brake" }
+    requirement "R-1" { description: "The controller shall warn" }
+    requirement "R-01" { description: "The controller shall log" }
+}
+"#,
+        );
+        let findings = check_id_conventions(&model, &conventions(&[("Requirement", r"REQ-\d{4}")]));
+        let suggestions: Vec<_> = findings.iter().map(|f| f.suggestion.as_deref()).collect();
+        assert_eq!(suggestions, [Some("REQ-0002"), Some("REQ-0003")]);
+    }
+
+    #[test]
+    fn an_invalid_pattern_names_its_element_type() {
+        let patterns = [("Requirement".to_string(), "REQ-(".to_string())].into_iter().collect();
+        let err = IdConventions::new(&patterns).unwrap_err();
+        assert!(err.starts_with("id_conventions.Requirement: invalid pattern 'REQ-('"), "{err}");
+    }
+
+    #[test]
+    fn literal_prefix_stops_at_the_first_pattern_construct() {
+        assert_eq!(literal_prefix(r"REQ-\d{4}"), "REQ-");
+        assert_eq!(literal_prefix(r"^SYS\-[0-9]+"), "SYS-");
+        assert_eq!(literal_prefix(r"TCX?-\d+"), "TC");
+        assert_eq!(literal_prefix(r"\d+"), "");
+    }
+}
@@ -0,0 +1,202 @@
+//! A content-addressable artifact store shared by the incremental caches of
+//! several projects, so a requirement library included by many models is
+//! compiled once.
+//!
+//! Only files without imports are stored: their artifacts depend on their
+//! content alone, which is the key. The layout is
+//!
+//! ```text
+//! <store>/v<version>/blobs/<content hash>      bincode StoredUnit
+//! <store>/v<version>/refs/<referrer hash>      the hashes one cache uses
+//! ```
+//!
+//! A blob's reference count is the number of reference lists naming it;
+//! [`ArtifactStore::gc`] deletes the blobs none names, after dropping the
+//! lists of caches that no longer exist.
+
+use super::*;
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// What a blob holds: everything of a compiled unit that does not depend on
+/// where the file lives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StoredUnit {
+    pub artifacts: Vec<CacheArtifact>,
+    pub symbols_exported: Vec<String>,
+}
+
+/// What [`ArtifactStore::gc`] removed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GcStats {
+    pub blobs: usize,
+    pub bytes: u64,
+    /// Reference lists of caches that no longer exist.
+    pub stale_referrers: usize,
+}
+
+pub struct ArtifactStore {
+    dir: PathBuf,
+}
+
+impl ArtifactStore {
+    /// The store under `dir`, created if needed. Artifacts of other
+    /// compiler versions live apart and are never read.
+    pub fn open(dir: &Path) -> Result<Self, IncrementalError> {
+        let dir = dir.join(format!("v{}", env!("CARGO_PKG_VERSION")));
+        for sub in ["blobs", "refs"] {
+            std::fs::create_dir_all(dir.join(sub)).map_err(|e| IncrementalError::CacheSaveError(e.to_string()))?;
+        }
+        Ok(Self { dir })
+    }
+
+    fn blob_path(&self, content_hash: &str) -> PathBuf {
+        self.dir.join("blobs").join(content_hash)
+    }
+
+    /// The unit stored for `content_hash`; an unreadable blob is a miss.
+    pub(crate) fn get(&self, content_hash: &str) -> Option<StoredUnit> {
+        let data = std::fs::read(self.blob_path(content_hash)).ok()?;
+        bincode::deserialize(&data).ok()
+    }
+
+    pub fn contains(&self, content_hash: &str) -> bool {
+        self.blob_path(content_hash).is_file()
+    }
+
+    /// Store `unit` under `content_hash`. Written to a temporary file first
+    /// and renamed, so concurrent builds never read half a blob.
+    pub(crate) fn put(&self, content_hash: &str, unit: &StoredUnit) -> Result<(), IncrementalError> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        if self.contains(content_hash) {
+            return Ok(());
+        }
+        let data = bincode::serialize(unit).map_err(|e| IncrementalError::SerializationError(e.to_string()))?;
+        let temporary = self.dir.join("blobs").join(format!(
+            ".{content_hash}.{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&temporary, data)
+            .and_then(|()| std::fs::rename(&temporary, self.blob_path(content_hash)))
+            .map_err(|e| IncrementalError::CacheSaveError(e.to_string()))
+    }
+
+    /// Record that the cache at `referrer` uses exactly `hashes`.
+    pub fn set_references(&self, referrer: &Path, hashes: &BTreeSet<String>) -> Result<(), IncrementalError> {
+        let mut list = format!("{}\n", referrer.display());
+        for hash in hashes {
+            list.push_str(hash);
+            list.push('\n');
+        }
+        std::fs::write(self.refs_path(referrer), list).map_err(|e| IncrementalError::CacheSaveError(e.to_string()))
+    }
+
+    /// Drop every reference of the cache at `referrer`.
+    pub fn release(&self, referrer: &Path) -> Result<(), IncrementalError> {
+        match std::fs::remove_file(self.refs_path(referrer)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(IncrementalError::CacheSaveError(e.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// How many caches use the blob of `content_hash`.
+    pub fn ref_count(&self, content_hash: &str) -> usize {
+        self.reference_lists().iter().filter(|(_, _, hashes)| hashes.contains(content_hash)).count()
+    }
+
+    /// Delete the blobs no cache uses.
+    pub fn gc(&self) -> Result<GcStats, IncrementalError> {
+        let io = |e: std::io::Error| IncrementalError::CacheSaveError(e.to_string());
+        let mut stats = GcStats::default();
+        let mut referenced = BTreeSet::new();
+        for (list, referrer, hashes) in self.reference_lists() {
+            if referrer.exists() {
+                referenced.extend(hashes);
+            } else {
+                std::fs::remove_file(list).map_err(io)?;
+                stats.stale_referrers += 1;
+            }
+        }
+        for entry in std::fs::read_dir(self.dir.join("blobs")).map_err(io)? {
+            let entry = entry.map_err(io)?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Temporary files belong to a write in progress
+            if name.starts_with('.') || referenced.contains(&name) {
+                continue;
+            }
+            stats.bytes += entry.metadata().map_err(io)?.len();
+            std::fs::remove_file(entry.path()).map_err(io)?;
+            stats.blobs += 1;
+        }
+        Ok(stats)
+    }
+
+    fn refs_path(&self, referrer: &Path) -> PathBuf {
+        use sha2::{Digest, Sha256};
+        let key = Sha256::digest(referrer.to_string_lossy().as_bytes());
+        self.dir.join("refs").join(format!("{key:x}"))
+    }
+
+    /// Every reference list: its file, the cache it belongs to, its hashes.
+    fn reference_lists(&self) -> Vec<(PathBuf, PathBuf, BTreeSet<String>)> {
+        let Ok(entries) = std::fs::read_dir(self.dir.join("refs")) else { return Vec::new() };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let text = std::fs::read_to_string(entry.path()).ok()?;
+                let mut lines = text.lines();
+                let referrer = PathBuf::from(lines.next()?);
+                Some((entry.path(), referrer, lines.map(str::to_string).collect()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(data: &[u8]) -> StoredUnit {
+        StoredUnit {
+            artifacts: vec![CacheArtifact {
+                artifact_type: ArtifactType::AST,
+                content_hash: "h".to_string(),
+                size_bytes: data.len(),
+                data: data.to_vec(),
+            }],
+            symbols_exported: vec!["R-1".to_string()],
+        }
+    }
+
+    #[test]
+    fn blobs_are_shared_and_collected_once_no_cache_uses_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::open(&dir.path().join("store")).unwrap();
+        let (a, b) = (dir.path().join("a"), dir.path().join("b"));
+        std::fs::create_dir_all(&a).unwrap();
+        std::fs::create_dir_all(&b).unwrap();
+
+        store.put("lib", &unit(b"library")).unwrap();
+        store.put("only-a", &unit(b"a")).unwrap();
+        store.put("orphan", &unit(b"unused")).unwrap();
+        assert_eq!(store.get("lib").unwrap().symbols_exported, ["R-1"]);
+        assert!(store.get("missing").is_none());
+
+        let hashes = |hashes: &[&str]| hashes.iter().map(|h| h.to_string()).collect();
+        store.set_references(&a, &hashes(&["lib", "only-a"])).unwrap();
+        store.set_references(&b, &hashes(&["lib"])).unwrap();
+        assert_eq!((store.ref_count("lib"), store.ref_count("only-a"), store.ref_count("orphan")), (2, 1, 0));
+
+        let stats = store.gc().unwrap();
+        assert_eq!((stats.blobs, stats.stale_referrers), (1, 0));
+        assert!(store.contains("lib") && store.contains("only-a") && !store.contains("orphan"));
+
+        // One cache released, the other deleted
+        store.release(&a).unwrap();
+        std::fs::remove_dir(&b).unwrap();
+        let stats = store.gc().unwrap();
+        assert_eq!((stats.blobs, stats.stale_referrers), (2, 1));
+        assert!(!store.contains("lib"));
+    }
+}
@@ -10,13 +10,15 @@ impl CacheManager {
         Self { config }
     }
     
-    pub fn update_cache(&self, cache: &mut CompilationCache, units: &[CompiledUnit]) -> Result<(), IncrementalError> {
+    pub(crate) fn update_cache(&self, cache: &mut CompilationCache, units: &[CompiledUnit]) -> Result<(), IncrementalError> {
+        let now = Utc::now();
         for unit in units {
             let entry = CacheEntry {
                 file_path: unit.file_path.clone(),
                 content_hash: unit.content_hash.clone(),
-                timestamp: Utc::now(),
-                compiled_at: Utc::now(),
+                timestamp: now,
+                compiled_at: now,
+                last_accessed: now,
                 artifacts: unit.artifacts.clone(),
                 dependencies: unit.dependencies.clone(),
                 symbols_exported: unit.symbols_exported.clone(),
@@ -28,6 +30,13 @@ impl CacheManager {
             self.update_dependency_graph(&mut cache.dependency_graph, unit);
         }
         
+        // A compiled unit read its dependencies' exports: they were used.
+        for dep in units.iter().flat_map(|u| &u.dependencies) {
+            if let Some(entry) = cache.entries.get_mut(dep) {
+                entry.last_accessed = now;
+            }
+        }
+        
         self.enforce_cache_size_limit(cache)?;
         
         Ok(())
@@ -123,30 +132,32 @@ impl CacheManager {
         }
     }
     
+    /// Evicts least-recently-used entries until the cache fits in
+    /// `max_cache_size_mb`. An entry another cached entry depends on is never
+    /// evicted, so the limit can be exceeded when only such entries remain.
     fn enforce_cache_size_limit(&self, cache: &mut CompilationCache) -> Result<(), IncrementalError> {
-        let total_size_bytes: usize = cache.entries.values()
-            .flat_map(|e| &e.artifacts)
-            .map(|a| a.size_bytes)
-            .sum();
+        let limit_bytes = self.config.max_cache_size_mb * 1024 * 1024;
+        let mut current_size = cache.size_bytes();
         
-        let total_size_mb = total_size_bytes / (1024 * 1024);
-        
-        if total_size_mb > self.config.max_cache_size_mb {
-            let mut entries_by_age: Vec<_> = cache.entries.values().collect();
-            entries_by_age.sort_by_key(|e| e.compiled_at);
+        while current_size > limit_bytes {
+            let depended_on: HashSet<&String> = cache.entries.values()
+                .flat_map(|e| &e.dependencies)
+                .collect();
             
-            let target_size_bytes = (self.config.max_cache_size_mb * 1024 * 1024) as usize;
-            let mut current_size = total_size_bytes;
+            let victim = cache.entries.values()
+                .filter(|e| !depended_on.contains(&e.file_path))
+                .min_by_key(|e| e.last_accessed)
+                .map(|e| e.file_path.clone());
             
-            for entry in entries_by_age {
-                if current_size <= target_size_bytes {
-                    break;
-                }
-                
-                let entry_size: usize = entry.artifacts.iter().map(|a| a.size_bytes).sum();
-                cache.entries.remove(&entry.file_path);
-                current_size -= entry_size;
+            let Some(victim) = victim else {
+                break;
+            };
+            
+            if let Some(entry) = cache.entries.remove(&victim) {
+                current_size -= entry.artifacts.iter().map(|a| a.size_bytes).sum::<usize>();
             }
+            cache.dependency_graph.nodes.remove(&victim);
+            cache.dependency_graph.edges.retain(|e| e.from != victim);
         }
         
         Ok(())
@@ -202,13 +213,11 @@ pub struct CacheStats {
     pub last_full_build: Option<DateTime<Utc>>,
 }
 
-pub struct CacheValidator {
-    config: IncrementalConfig,
-}
+pub struct CacheValidator;
 
 impl CacheValidator {
-    pub fn new(config: IncrementalConfig) -> Self {
-        Self { config }
+    pub fn new(_config: IncrementalConfig) -> Self {
+        Self
     }
     
     pub fn validate_cache(&self, cache: &CompilationCache) -> ValidationResult {
@@ -338,3 +347,54 @@ pub enum IssueSeverity {
     Warning,
     Info,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn unit(file: &str, size_bytes: usize, dependencies: &[&str]) -> CompiledUnit {
+        CompiledUnit {
+            file_path: file.to_string(),
+            content_hash: String::new(),
+            artifacts: vec![CacheArtifact {
+                artifact_type: ArtifactType::AST,
+                content_hash: String::new(),
+                size_bytes,
+                data: Vec::new(),
+            }],
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            symbols_exported: Vec::new(),
+            symbols_imported: Vec::new(),
+            from_store: false,
+        }
+    }
+    
+    #[test]
+    fn size_limit_evicts_least_recently_used_but_keeps_dependencies() {
+        let manager = CacheManager::new(IncrementalConfig {
+            cache_dir: PathBuf::from("unused"),
+            max_cache_size_mb: 1,
+            enable_parallel: false,
+            num_threads: 1,
+            cache_strategy: CacheStrategy::ContentBased,
+            global_store_dir: None,
+        });
+        let mut cache = CompilationCache::load_or_create(&PathBuf::from("does-not-exist")).unwrap();
+        let kb = 1024;
+        
+        // base.arc is the oldest entry, but root.arc still imports it.
+        manager.update_cache(&mut cache, &[unit("base.arc", 400 * kb, &[])]).unwrap();
+        manager.update_cache(&mut cache, &[unit("old.arc", 400 * kb, &[])]).unwrap();
+        manager.update_cache(&mut cache, &[unit("root.arc", 200 * kb, &["base.arc"])]).unwrap();
+        cache.entries.get_mut("base.arc").unwrap().last_accessed -= chrono::Duration::hours(2);
+        cache.entries.get_mut("old.arc").unwrap().last_accessed -= chrono::Duration::hours(1);
+        
+        manager.update_cache(&mut cache, &[unit("new.arc", 200 * kb, &[])]).unwrap();
+        
+        let mut remaining: Vec<_> = cache.entries.keys().cloned().collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["base.arc", "new.arc", "root.arc"]);
+        assert!(cache.size_bytes() <= 1024 * 1024);
+        assert!(!cache.dependency_graph.nodes.contains_key("old.arc"));
+    }
+}
@@ -5,6 +5,23 @@ pub struct DependencyGraphBuilder {
     graph: DependencyGraph,
 }
 
+/// Working state for Tarjan's strongly connected components search.
+#[derive(Default)]
+struct TarjanState {
+    index: usize,
+    stack: Vec<String>,
+    indices: HashMap<String, usize>,
+    lowlinks: HashMap<String, usize>,
+    on_stack: HashSet<String>,
+    components: Vec<Vec<String>>,
+}
+
+impl Default for DependencyGraphBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DependencyGraphBuilder {
     pub fn new() -> Self {
         Self {
@@ -106,70 +123,48 @@ impl DependencyGraphBuilder {
     }
     
     pub fn find_strongly_connected_components(&self) -> Vec<Vec<String>> {
-        let mut index = 0;
-        let mut stack = Vec::new();
-        let mut indices = HashMap::new();
-        let mut lowlinks = HashMap::new();
-        let mut on_stack = HashSet::new();
-        let mut components = Vec::new();
+        let mut state = TarjanState::default();
         
         for node_id in self.graph.nodes.keys() {
-            if !indices.contains_key(node_id) {
-                self.tarjan_scc(
-                    node_id,
-                    &mut index,
-                    &mut stack,
-                    &mut indices,
-                    &mut lowlinks,
-                    &mut on_stack,
-                    &mut components,
-                );
+            if !state.indices.contains_key(node_id) {
+                self.tarjan_scc(node_id, &mut state);
             }
         }
         
-        components
+        state.components
     }
     
-    fn tarjan_scc(
-        &self,
-        node: &str,
-        index: &mut usize,
-        stack: &mut Vec<String>,
-        indices: &mut HashMap<String, usize>,
-        lowlinks: &mut HashMap<String, usize>,
-        on_stack: &mut HashSet<String>,
-        components: &mut Vec<Vec<String>>,
-    ) {
-        indices.insert(node.to_string(), *index);
-        lowlinks.insert(node.to_string(), *index);
-        *index += 1;
-        stack.push(node.to_string());
-        on_stack.insert(node.to_string());
+    fn tarjan_scc(&self, node: &str, state: &mut TarjanState) {
+        state.indices.insert(node.to_string(), state.index);
+        state.lowlinks.insert(node.to_string(), state.index);
+        state.index += 1;
+        state.stack.push(node.to_string());
+        state.on_stack.insert(node.to_string());
         
         for edge in &self.graph.edges {
             if edge.from == node {
                 let successor = &edge.to;
                 
-                if !indices.contains_key(successor) {
-                    self.tarjan_scc(successor, index, stack, indices, lowlinks, on_stack, components);
+                if !state.indices.contains_key(successor) {
+                    self.tarjan_scc(successor, state);
                     
-                    let successor_lowlink = *lowlinks.get(successor).unwrap();
-                    let node_lowlink = *lowlinks.get(node).unwrap();
-                    lowlinks.insert(node.to_string(), node_lowlink.min(successor_lowlink));
-                } else if on_stack.contains(successor) {
-                    let successor_index = *indices.get(successor).unwrap();
-                    let node_lowlink = *lowlinks.get(node).unwrap();
-                    lowlinks.insert(node.to_string(), node_lowlink.min(successor_index));
+                    let successor_lowlink = *state.lowlinks.get(successor).unwrap();
+                    let node_lowlink = *state.lowlinks.get(node).unwrap();
+                    state.lowlinks.insert(node.to_string(), node_lowlink.min(successor_lowlink));
+                } else if state.on_stack.contains(successor) {
+                    let successor_index = *state.indices.get(successor).unwrap();
+                    let node_lowlink = *state.lowlinks.get(node).unwrap();
+                    state.lowlinks.insert(node.to_string(), node_lowlink.min(successor_index));
                 }
             }
         }
         
-        if lowlinks.get(node) == indices.get(node) {
+        if state.lowlinks.get(node) == state.indices.get(node) {
             let mut component = Vec::new();
             
             loop {
-                let w = stack.pop().unwrap();
-                on_stack.remove(&w);
+                let w = state.stack.pop().unwrap();
+                state.on_stack.remove(&w);
                 component.push(w.clone());
                 
                 if w == node {
@@ -178,7 +173,7 @@ impl DependencyGraphBuilder {
             }
             
             if component.len() > 1 || self.has_self_loop(node) {
-                components.push(component);
+                state.components.push(component);
             }
         }
     }
@@ -225,7 +220,7 @@ impl DependencyAnalyzer {
     pub fn find_critical_files(graph: &DependencyGraph) -> Vec<CriticalFile> {
         let mut critical_files = Vec::new();
         
-        for (file_path, _node) in &graph.nodes {
+        for file_path in graph.nodes.keys() {
             let dependent_count = graph.edges.iter()
                 .filter(|e| e.to == *file_path)
                 .count();
@@ -270,12 +265,12 @@ impl DependencyAnalyzer {
                 NodeType::ExternalDependency => "lightgray",
             };
             
-            let label = file_path.split('/').last().unwrap_or(file_path);
+            let label = file_path.split('/').next_back().unwrap_or(file_path);
             dot.push_str(&format!("    \"{}\" [label=\"{}\", style=filled, fillcolor={}];\n",
                 file_path, label, color));
         }
         
-        dot.push_str("\n");
+        dot.push('\n');
         
         for edge in &graph.edges {
             let color = match edge.edge_type {
@@ -1,5 +1,5 @@
 use super::*;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 pub struct InvalidationEngine {
     strategy: InvalidationStrategy,
@@ -122,11 +122,11 @@ impl InvalidationEngine {
         dependents
     }
     
-    fn has_interface_change(&self, entry: &CacheEntry) -> Result<bool, IncrementalError> {
+    fn has_interface_change(&self, _entry: &CacheEntry) -> Result<bool, IncrementalError> {
         Ok(true)
     }
     
-    fn analyze_change_impact(&self, entry: &CacheEntry) -> Result<ChangeImpact, IncrementalError> {
+    fn analyze_change_impact(&self, _entry: &CacheEntry) -> Result<ChangeImpact, IncrementalError> {
         Ok(ChangeImpact::Implementation)
     }
 }
@@ -138,6 +138,118 @@ pub enum ChangeImpact {
     Documentation,
 }
 
+/// Why a file is recompiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidationReason {
+    /// New to the cache, or its content changed since it was cached.
+    ContentChanged,
+    /// A file it imports, directly or not, changed or added symbols it
+    /// mentions or declares.
+    DependencyChanged { dependency: String, symbols: Vec<String> },
+    /// A file it imports no longer declares symbols it mentions.
+    SymbolRemoved { dependency: String, symbols: Vec<String> },
+}
+
+impl std::fmt::Display for InvalidationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ContentChanged => write!(f, "content changed"),
+            Self::DependencyChanged { dependency, symbols } if symbols.is_empty() => {
+                write!(f, "{} changed", dependency)
+            }
+            Self::DependencyChanged { dependency, symbols } => {
+                write!(f, "uses {} changed in {}", symbols.join(", "), dependency)
+            }
+            Self::SymbolRemoved { dependency, symbols } => {
+                write!(f, "uses {} removed from {}", symbols.join(", "), dependency)
+            }
+        }
+    }
+}
+
+/// A file to recompile, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invalidation {
+    pub file: String,
+    pub reason: InvalidationReason,
+}
+
+/// What a recompiled file changed, symbol by symbol: every element id added,
+/// modified or removed, with the names it was or is known by (references
+/// resolve by id or name).
+#[derive(Debug, Clone, Default)]
+pub struct SymbolChanges {
+    pub changed: BTreeMap<String, BTreeSet<String>>,
+    pub removed: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SymbolChanges {
+    pub fn between(old: &semantic::SemanticModel, new: &semantic::SemanticModel) -> Self {
+        let report = crate::compiler::semantic_diff::diff_models(old, new);
+        let known_as = |element: &crate::compiler::semantic_diff::ElementRef| {
+            (element.id.clone(), BTreeSet::from([element.id.clone(), element.name.clone()]))
+        };
+        let mut changed: BTreeMap<String, BTreeSet<String>> = report.added.iter().map(known_as).collect();
+        for modified in &report.modified {
+            let (id, mut names) = known_as(&modified.element);
+            names.extend(modified.changes.iter().filter(|c| c.field == "name").map(|c| c.old.clone()));
+            changed.insert(id, names);
+        }
+        Self {
+            changed,
+            removed: report.removed.iter().map(known_as).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Why a dependent of `dependency` must recompile: its source
+    /// `mentions` a changed or removed symbol, or it `declares` the id of a
+    /// changed one (a new duplicate). `None` leaves it cached.
+    pub fn reason_for(
+        &self,
+        dependency: &str,
+        mentions: &HashSet<String>,
+        declares: &[String],
+    ) -> Option<InvalidationReason> {
+        let mentioned = |symbols: &BTreeMap<String, BTreeSet<String>>| -> Vec<String> {
+            symbols.iter()
+                .filter(|(id, names)| declares.contains(id) || names.iter().any(|n| mentions.contains(n)))
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+        let removed = mentioned(&self.removed);
+        if !removed.is_empty() {
+            return Some(InvalidationReason::SymbolRemoved { dependency: dependency.to_string(), symbols: removed });
+        }
+        let changed = mentioned(&self.changed);
+        (!changed.is_empty())
+            .then(|| InvalidationReason::DependencyChanged { dependency: dependency.to_string(), symbols: changed })
+    }
+}
+
+/// Every string of a file's own AST: the ids and names it refers to, and
+/// some text that merely looks like one (which only recompiles too much).
+pub fn mentions(ast: &ast::Model) -> HashSet<String> {
+    fn collect(value: serde_json::Value, into: &mut HashSet<String>) {
+        match value {
+            serde_json::Value::String(text) => {
+                into.insert(text);
+            }
+            serde_json::Value::Array(items) => items.into_iter().for_each(|item| collect(item, into)),
+            serde_json::Value::Object(fields) => fields.into_iter().for_each(|(_, v)| collect(v, into)),
+            _ => {}
+        }
+    }
+    let mut strings = HashSet::new();
+    if let Ok(value) = serde_json::to_value(ast) {
+        collect(value, &mut strings);
+    }
+    strings
+}
+
 pub struct ContentHasher;
 
 impl ContentHasher {
@@ -160,7 +272,7 @@ impl ContentHasher {
         format!("{:x}", hasher.finalize())
     }
     
-    pub fn hash_ast(ast: &super::AST) -> Result<String, IncrementalError> {
+    pub fn hash_ast(ast: &ast::Model) -> Result<String, IncrementalError> {
         let serialized = bincode::serialize(ast)
             .map_err(|e| IncrementalError::SerializationError(e.to_string()))?;
         
@@ -256,7 +368,7 @@ pub fn generate_invalidation_report(
     for file in changed_files {
         report.push_str(&format!("  - {}\n", file));
     }
-    report.push_str("\n");
+    report.push('\n');
     
     report.push_str("Invalidated Files (by dependency chain):\n");
     for file in invalidated_files {
@@ -302,4 +414,25 @@ mod tests {
         
         assert_ne!(hash1, hash3);
     }
+    
+    #[test]
+    fn test_reason_for_only_mentioned_symbols() {
+        let changes = SymbolChanges {
+            changed: BTreeMap::from([("R-1".to_string(), BTreeSet::from(["R-1".to_string(), "Brake".to_string()]))]),
+            removed: BTreeMap::from([("R-2".to_string(), BTreeSet::from(["R-2".to_string()]))]),
+        };
+        let mentions = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<HashSet<_>>();
+        
+        assert_eq!(changes.reason_for("base.arc", &mentions(&["R-9"]), &[]), None);
+        assert_eq!(
+            changes.reason_for("base.arc", &mentions(&["Brake"]), &[]),
+            Some(InvalidationReason::DependencyChanged { dependency: "base.arc".into(), symbols: vec!["R-1".into()] })
+        );
+        assert_eq!(
+            changes.reason_for("base.arc", &mentions(&["R-1", "R-2"]), &[]),
+            Some(InvalidationReason::SymbolRemoved { dependency: "base.arc".into(), symbols: vec!["R-2".into()] })
+        );
+        // Declaring a changed id clashes with it
+        assert!(changes.reason_for("base.arc", &HashSet::new(), &["R-1".to_string()]).is_some());
+    }
 }
@@ -1,10 +1,13 @@
+pub mod artifact_store;
 pub mod cache;
 pub mod dependency_graph;
 pub mod invalidation;
 
+use super::{ast, semantic, Compiler, CompilerError};
+use invalidation::{mentions, Invalidation, InvalidationReason, SymbolChanges};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +17,10 @@ pub struct IncrementalConfig {
     pub enable_parallel: bool,
     pub num_threads: usize,
     pub cache_strategy: CacheStrategy,
+    /// An [`artifact_store::ArtifactStore`] shared with other projects, for
+    /// the artifacts of files without imports.
+    #[serde(default)]
+    pub global_store_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +44,9 @@ pub struct CacheEntry {
     pub content_hash: String,
     pub timestamp: DateTime<Utc>,
     pub compiled_at: DateTime<Utc>,
+    /// Last time the entry was compiled or used as a dependency of a
+    /// compiled unit; the size limit evicts the oldest first.
+    pub last_accessed: DateTime<Utc>,
     pub artifacts: Vec<CacheArtifact>,
     pub dependencies: Vec<String>,
     pub symbols_exported: Vec<String>,
@@ -51,7 +61,7 @@ pub struct CacheArtifact {
     pub data: Vec<u8>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ArtifactType {
     AST,
     SemanticModel,
@@ -96,55 +106,97 @@ pub enum EdgeType {
     Derives,
 }
 
-#[derive(Debug, Clone)]
 pub struct IncrementalCompiler {
     config: IncrementalConfig,
     cache: CompilationCache,
     cache_manager: cache::CacheManager,
-    dep_graph: dependency_graph::DependencyGraphBuilder,
+    store: Option<artifact_store::ArtifactStore>,
 }
 
 impl IncrementalCompiler {
     pub fn new(config: IncrementalConfig) -> Result<Self, IncrementalError> {
         let cache = CompilationCache::load_or_create(&config.cache_dir)?;
         let cache_manager = cache::CacheManager::new(config.clone());
-        let dep_graph = dependency_graph::DependencyGraphBuilder::new();
+        let store = config.global_store_dir.as_deref().map(artifact_store::ArtifactStore::open).transpose()?;
         
         Ok(Self {
             config,
             cache,
             cache_manager,
-            dep_graph,
+            store,
         })
     }
     
+    /// Recompile `changed_files`, then every file left stale by what they
+    /// changed: a dependent is recompiled only when its own source mentions
+    /// a symbol the change added, modified or removed (or declares one of
+    /// those ids). The result says why each file was recompiled.
     pub fn compile_incremental(&mut self, changed_files: &[String]) -> Result<IncrementalCompileResult, IncrementalError> {
         let start_time = std::time::Instant::now();
         
-        let invalidated = self.compute_invalidation_set(changed_files)?;
-        
-        if invalidated.is_empty() && changed_files.is_empty() {
+        if changed_files.is_empty() {
             return Ok(IncrementalCompileResult {
                 success: true,
                 compiled_files: Vec::new(),
                 cached_files: self.cache.entries.keys().cloned().collect(),
                 invalidated_files: Vec::new(),
+                invalidations: Vec::new(),
                 compilation_time_ms: start_time.elapsed().as_millis() as u64,
+                cache_save_time: std::time::Duration::ZERO,
                 cache_hit_ratio: 1.0,
+                cache_size_bytes: self.cache.size_bytes(),
+                global_store_hits: Vec::new(),
             });
         }
         
-        let files_to_compile = self.order_by_dependencies(&invalidated)?;
+        let mut invalidations: Vec<Invalidation> = Vec::new();
+        for file in changed_files {
+            if !invalidations.iter().any(|i| &i.file == file) {
+                invalidations.push(Invalidation { file: file.clone(), reason: InvalidationReason::ContentChanged });
+            }
+        }
         
-        let compiled = if self.config.enable_parallel {
-            self.compile_parallel(&files_to_compile)?
-        } else {
-            self.compile_sequential(&files_to_compile)?
-        };
+        // A round recompiles what the previous one invalidated
+        let mut compiled: Vec<CompiledUnit> = Vec::new();
+        let mut pending: Vec<String> = invalidations.iter().map(|i| i.file.clone()).collect();
+        while !pending.is_empty() {
+            let files_to_compile = self.order_by_dependencies(&pending)?;
+            let units = if self.config.enable_parallel {
+                self.compile_parallel(&files_to_compile)?
+            } else {
+                self.compile_sequential(&files_to_compile)?
+            };
+            let invalidated = self.compute_invalidation_set(&units, &invalidations);
+            pending = invalidated.iter().map(|i| i.file.clone()).collect();
+            invalidations.extend(invalidated);
+            compiled.extend(units);
+        }
+        
+        // Files a compiled unit newly imports have no cache entry yet.
+        loop {
+            let missing: BTreeSet<String> = compiled.iter()
+                .flat_map(|unit| &unit.dependencies)
+                .filter(|dep| !self.cache.entries.contains_key(*dep))
+                .filter(|dep| !compiled.iter().any(|unit| &unit.file_path == *dep))
+                .cloned()
+                .collect();
+            if missing.is_empty() {
+                break;
+            }
+            let missing: Vec<String> = missing.into_iter().collect();
+            invalidations.extend(missing.iter().map(|file| Invalidation {
+                file: file.clone(),
+                reason: InvalidationReason::ContentChanged,
+            }));
+            compiled.extend(self.compile_sequential(&missing)?);
+        }
         
         self.cache_manager.update_cache(&mut self.cache, &compiled)?;
         
+        let save_started = std::time::Instant::now();
         self.cache.save(&self.config.cache_dir)?;
+        self.update_store_references()?;
+        let cache_save_time = save_started.elapsed();
         
         let total_files = self.cache.entries.len();
         let cached_count = total_files - compiled.len();
@@ -161,33 +213,146 @@ impl IncrementalCompiler {
                 .filter(|k| !compiled.iter().any(|c| &c.file_path == *k))
                 .cloned()
                 .collect(),
-            invalidated_files: invalidated,
+            invalidated_files: invalidations.iter().map(|i| i.file.clone()).collect(),
+            invalidations,
             compilation_time_ms: start_time.elapsed().as_millis() as u64,
+            cache_save_time,
             cache_hit_ratio,
+            cache_size_bytes: self.cache.size_bytes(),
+            global_store_hits: compiled.iter()
+                .filter(|c| c.from_store)
+                .map(|c| c.file_path.clone())
+                .collect(),
         })
     }
     
-    fn compute_invalidation_set(&self, changed_files: &[String]) -> Result<Vec<String>, IncrementalError> {
-        let mut invalidated = HashSet::new();
+    /// Tell the global store which of its blobs this cache now uses.
+    fn update_store_references(&self) -> Result<(), IncrementalError> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let hashes = self.cache.entries.values()
+            .filter(|e| e.dependencies.is_empty() && store.contains(&e.content_hash))
+            .map(|e| e.content_hash.clone())
+            .collect();
+        let referrer = self.config.cache_dir.canonicalize()
+            .map_err(|e| IncrementalError::CacheSaveError(e.to_string()))?;
+        store.set_references(&referrer, &hashes)
+    }
+    
+    /// `root` and every file it imports, as recorded by the last build, that
+    /// is uncached or whose content changed since. Files that no longer exist
+    /// are skipped: whatever still imports them fails when it recompiles.
+    pub fn stale_files(&self, root: &str) -> Result<Vec<String>, IncrementalError> {
+        let mut stale = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = vec![root.to_string()];
         
-        for file in changed_files {
-            invalidated.insert(file.clone());
+        while let Some(file) = queue.pop() {
+            if !visited.insert(file.clone()) || !Path::new(&file).exists() {
+                continue;
+            }
             
-            if let Some(entry) = self.cache.entries.get(file) {
-                let current_hash = self.compute_file_hash(file)?;
-                
-                if entry.content_hash != current_hash {
-                    let dependents = self.cache.dependency_graph.get_dependents(file);
-                    invalidated.extend(dependents);
+            match self.cache.entries.get(&file) {
+                Some(entry) => {
+                    if entry.content_hash != self.compute_file_hash(&file)? {
+                        stale.push(file.clone());
+                    }
+                    queue.extend(entry.dependencies.iter().cloned());
                 }
-            } else {
-                invalidated.insert(file.clone());
+                None => stale.push(file),
             }
         }
         
-        Ok(invalidated.into_iter().collect())
+        Ok(stale)
     }
     
+    /// The semantic model of `file` with its imports, as its last
+    /// compilation cached it. A file left cached while a file it imports was
+    /// recompiled holds that import as it was, so it is analyzed again.
+    pub fn cached_semantic_model(&self, file: &str) -> Result<semantic::SemanticModel, IncrementalError> {
+        if self.imports_recompiled_since(file) {
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| IncrementalError::FileReadError(file.to_string(), e.to_string()))?;
+            return self.analyze_semantics(file, &self.parse_file(file, &content)?);
+        }
+        
+        let data = self.cache_manager
+            .get_cached_artifact(&self.cache, file, ArtifactType::SemanticModel)
+            .ok_or_else(|| IncrementalError::InvalidCacheEntry(format!("no semantic model cached for {}", file)))?;
+        
+        bincode::deserialize(&data)
+            .map_err(|e| IncrementalError::SerializationError(e.to_string()))
+    }
+    
+    /// The dependents the recompiled `units` leave stale, and why: those,
+    /// direct or transitive, whose own source mentions a symbol a unit
+    /// changed. Compared against the cache as the last build left it; files
+    /// already `invalidated` are not repeated.
+    fn compute_invalidation_set(&self, units: &[CompiledUnit], invalidated: &[Invalidation]) -> Vec<Invalidation> {
+        let mut found: Vec<Invalidation> = Vec::new();
+        
+        for unit in units {
+            let dependents = self.cache.dependency_graph.get_transitive_dependents(&unit.file_path);
+            if dependents.is_empty() {
+                continue;
+            }
+            let old = self.cache.entries.get(&unit.file_path)
+                .and_then(|entry| artifact::<semantic::SemanticModel>(&entry.artifacts, ArtifactType::SemanticModel));
+            let new = artifact::<semantic::SemanticModel>(&unit.artifacts, ArtifactType::SemanticModel);
+            // Without both models every dependent is stale
+            let changes = old.zip(new).map(|(old, new)| SymbolChanges::between(&old, &new));
+            if changes.as_ref().is_some_and(SymbolChanges::is_empty) {
+                continue;
+            }
+            
+            for dependent in dependents {
+                if invalidated.iter().chain(&found).any(|i| i.file == dependent) {
+                    continue;
+                }
+                let entry = self.cache.entries.get(&dependent);
+                let ast = entry.and_then(|e| artifact::<ast::Model>(&e.artifacts, ArtifactType::AST));
+                let reason = match (&changes, entry, ast) {
+                    (Some(changes), Some(entry), Some(ast)) => {
+                        changes.reason_for(&unit.file_path, &mentions(&ast), &entry.symbols_exported)
+                    }
+                    _ => Some(InvalidationReason::DependencyChanged {
+                        dependency: unit.file_path.clone(),
+                        symbols: Vec::new(),
+                    }),
+                };
+                if let Some(reason) = reason {
+                    found.push(Invalidation { file: dependent, reason });
+                }
+            }
+        }
+        
+        found
+    }
+    
+    /// Whether a file `file` imports, directly or not, was compiled after it.
+    fn imports_recompiled_since(&self, file: &str) -> bool {
+        let Some(entry) = self.cache.entries.get(file) else {
+            return false;
+        };
+        let mut visited = HashSet::new();
+        let mut queue = entry.dependencies.clone();
+        while let Some(dep) = queue.pop() {
+            if !visited.insert(dep.clone()) {
+                continue;
+            }
+            if let Some(dep_entry) = self.cache.entries.get(&dep) {
+                if dep_entry.compiled_at > entry.compiled_at {
+                    return true;
+                }
+                queue.extend(dep_entry.dependencies.iter().cloned());
+            }
+        }
+        false
+    }
+    
+    /// Orders `files` so each comes after its dependencies. Dependencies
+    /// outside `files` are still valid in the cache and are not scheduled.
     fn order_by_dependencies(&self, files: &[String]) -> Result<Vec<String>, IncrementalError> {
         let mut ordered = Vec::new();
         let mut visited = HashSet::new();
@@ -199,6 +364,7 @@ impl IncrementalCompiler {
             }
         }
         
+        ordered.retain(|file| files.contains(file));
         Ok(ordered)
     }
     
@@ -259,34 +425,73 @@ impl IncrementalCompiler {
         
         let content_hash = self.compute_content_hash(&content);
         
-        let ast = self.parse_file(&content)?;
-        let semantic_model = self.analyze_semantics(&ast)?;
-        let dependencies = self.extract_dependencies(&ast);
+        if let Some(stored) = self.store.as_ref().and_then(|store| store.get(&content_hash)) {
+            return Ok(CompiledUnit {
+                file_path: file.to_string(),
+                content_hash,
+                artifacts: stored.artifacts,
+                dependencies: Vec::new(),
+                symbols_exported: stored.symbols_exported,
+                symbols_imported: Vec::new(),
+                from_store: true,
+            });
+        }
+        
+        let ast = self.parse_file(file, &content)?;
+        let dependencies = self.extract_dependencies(file, &ast);
+        let semantic_model = self.analyze_semantics(file, &ast)?;
+        
+        // Everything the imports declare, so the unit only exports its own
+        // elements. Each import is itself a unit and must analyze on its own.
+        let mut imported = BTreeSet::new();
+        for dep in &dependencies {
+            let (dep_ast, _) = Compiler::parse_file_with_imports(Path::new(dep), &mut Vec::new())
+                .map_err(|e| IncrementalError::CompileError(dep.clone(), e.to_string()))?;
+            imported.extend(self.analyze_semantics(dep, &dep_ast)?.all_elements.into_keys());
+        }
+        let exported: BTreeSet<String> = semantic_model.all_elements.keys()
+            .filter(|id| !imported.contains(*id))
+            .cloned()
+            .collect();
+        
+        let ast_data = bincode::serialize(&ast)
+            .map_err(|e| IncrementalError::SerializationError(e.to_string()))?;
+        let semantic_data = bincode::serialize(&semantic_model)
+            .map_err(|e| IncrementalError::SerializationError(e.to_string()))?;
         
         let artifacts = vec![
             CacheArtifact {
                 artifact_type: ArtifactType::AST,
                 content_hash: content_hash.clone(),
-                size_bytes: content.len(),
-                data: bincode::serialize(&ast)
-                    .map_err(|e| IncrementalError::SerializationError(e.to_string()))?,
+                size_bytes: ast_data.len(),
+                data: ast_data,
             },
             CacheArtifact {
                 artifact_type: ArtifactType::SemanticModel,
                 content_hash: content_hash.clone(),
-                size_bytes: 0,
-                data: bincode::serialize(&semantic_model)
-                    .map_err(|e| IncrementalError::SerializationError(e.to_string()))?,
+                size_bytes: semantic_data.len(),
+                data: semantic_data,
             },
         ];
+        let symbols_exported: Vec<String> = exported.into_iter().collect();
+        
+        // Without imports the artifacts depend on the content alone
+        if let (Some(store), true) = (&self.store, dependencies.is_empty()) {
+            let stored = artifact_store::StoredUnit {
+                artifacts: artifacts.clone(),
+                symbols_exported: symbols_exported.clone(),
+            };
+            store.put(&content_hash, &stored)?;
+        }
         
         Ok(CompiledUnit {
             file_path: file.to_string(),
             content_hash,
             artifacts,
             dependencies,
-            symbols_exported: semantic_model.exported_symbols,
-            symbols_imported: semantic_model.imported_symbols,
+            symbols_exported,
+            symbols_imported: imported.into_iter().collect(),
+            from_store: false,
         })
     }
     
@@ -304,41 +509,50 @@ impl IncrementalCompiler {
         format!("{:x}", hasher.finalize())
     }
     
-    fn parse_file(&self, content: &str) -> Result<AST, IncrementalError> {
-        Ok(AST { nodes: Vec::new() })
+    /// Lex and parse one file on its own; its `import` declarations stay
+    /// unresolved in the returned AST.
+    fn parse_file(&self, file: &str, content: &str) -> Result<ast::Model, IncrementalError> {
+        Compiler::parse_source(content)
+            .map(|(model, _)| model)
+            .map_err(|e| IncrementalError::CompileError(file.to_string(), e.to_string()))
     }
     
-    fn analyze_semantics(&self, ast: &AST) -> Result<SemanticInfo, IncrementalError> {
-        Ok(SemanticInfo {
-            exported_symbols: Vec::new(),
-            imported_symbols: Vec::new(),
-        })
+    /// Analyze the file exactly as `Compiler::compile_file` would: with its
+    /// imports merged in, so traces into imported elements resolve.
+    fn analyze_semantics(&self, file: &str, ast: &ast::Model) -> Result<semantic::SemanticModel, IncrementalError> {
+        let merged = if ast.imports.is_empty() {
+            ast.clone()
+        } else {
+            Compiler::parse_file_with_imports(Path::new(file), &mut Vec::new())
+                .map_err(|e| IncrementalError::CompileError(file.to_string(), e.to_string()))?
+                .0
+        };
+        
+        semantic::SemanticAnalyzer::new()
+            .analyze(&merged)
+            .map_err(|e| IncrementalError::CompileError(file.to_string(), CompilerError::from(e).to_string()))
     }
     
-    fn extract_dependencies(&self, ast: &AST) -> Vec<String> {
-        Vec::new()
+    /// The files named by `import` declarations, resolved relative to `file`
+    /// the same way `Compiler::compile_file` resolves them.
+    fn extract_dependencies(&self, file: &str, ast: &ast::Model) -> Vec<String> {
+        let base_dir = Path::new(file).parent().unwrap_or_else(|| Path::new(""));
+        ast.imports.iter()
+            .map(|import| base_dir.join(import).to_string_lossy().into_owned())
+            .collect()
     }
 }
 
 #[derive(Debug, Clone)]
-struct CompiledUnit {
+pub(crate) struct CompiledUnit {
     file_path: String,
     content_hash: String,
     artifacts: Vec<CacheArtifact>,
     dependencies: Vec<String>,
     symbols_exported: Vec<String>,
     symbols_imported: Vec<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AST {
-    nodes: Vec<String>,
-}
-
-#[derive(Debug, Clone)]
-struct SemanticInfo {
-    exported_symbols: Vec<String>,
-    imported_symbols: Vec<String>,
+    /// Read from the global artifact store instead of compiled.
+    from_store: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -347,34 +561,65 @@ pub struct IncrementalCompileResult {
     pub compiled_files: Vec<String>,
     pub cached_files: Vec<String>,
     pub invalidated_files: Vec<String>,
+    /// Every recompiled file and why, in the order they were found.
+    pub invalidations: Vec<Invalidation>,
     pub compilation_time_ms: u64,
+    /// Part of the compilation time spent writing the cache.
+    pub cache_save_time: std::time::Duration,
     pub cache_hit_ratio: f64,
+    /// Total artifact size held in the cache after this build.
+    pub cache_size_bytes: usize,
+    /// Compiled files whose artifacts came from the global store.
+    pub global_store_hits: Vec<String>,
+}
+
+/// Bumped whenever the layout of [`CompilationCache`] changes: bincode is not
+/// self-describing, so a cache in an older layout cannot be read at all.
+const CACHE_FORMAT: u32 = 2;
+
+fn cache_version() -> String {
+    format!("{}+cache{}", env!("CARGO_PKG_VERSION"), CACHE_FORMAT)
 }
 
 impl CompilationCache {
-    fn load_or_create(cache_dir: &PathBuf) -> Result<Self, IncrementalError> {
+    /// Loads the cache in `cache_dir`; one written by another version of the
+    /// compiler or in another format is dropped and the build starts over.
+    fn load_or_create(cache_dir: &Path) -> Result<Self, IncrementalError> {
         let cache_file = cache_dir.join("compilation_cache.bin");
         
         if cache_file.exists() {
             let data = std::fs::read(&cache_file)
                 .map_err(|e| IncrementalError::CacheLoadError(e.to_string()))?;
             
-            bincode::deserialize(&data)
-                .map_err(|e| IncrementalError::CacheLoadError(e.to_string()))
-        } else {
-            Ok(Self {
-                version: env!("CARGO_PKG_VERSION").to_string(),
-                entries: HashMap::new(),
-                dependency_graph: DependencyGraph {
-                    nodes: HashMap::new(),
-                    edges: Vec::new(),
-                },
-                last_full_build: None,
-            })
+            // The version string leads the file in every format
+            let version: String = bincode::deserialize(&data)
+                .map_err(|e| IncrementalError::CacheLoadError(e.to_string()))?;
+            if version == cache_version() {
+                return bincode::deserialize(&data)
+                    .map_err(|e| IncrementalError::CacheLoadError(e.to_string()));
+            }
         }
+        
+        Ok(Self {
+            version: cache_version(),
+            entries: HashMap::new(),
+            dependency_graph: DependencyGraph {
+                nodes: HashMap::new(),
+                edges: Vec::new(),
+            },
+            last_full_build: None,
+        })
     }
     
-    fn save(&self, cache_dir: &PathBuf) -> Result<(), IncrementalError> {
+    /// Sum of every cached artifact's size.
+    pub fn size_bytes(&self) -> usize {
+        self.entries.values()
+            .flat_map(|e| &e.artifacts)
+            .map(|a| a.size_bytes)
+            .sum()
+    }
+    
+    fn save(&self, cache_dir: &Path) -> Result<(), IncrementalError> {
         std::fs::create_dir_all(cache_dir)
             .map_err(|e| IncrementalError::CacheSaveError(e.to_string()))?;
         
@@ -397,6 +642,27 @@ impl DependencyGraph {
             .map(|e| e.from.clone())
             .collect()
     }
+    
+    /// Every file importing `file`, directly or not, sorted.
+    fn get_transitive_dependents(&self, file: &str) -> Vec<String> {
+        let mut dependents = BTreeSet::new();
+        let mut queue = vec![file.to_string()];
+        while let Some(current) = queue.pop() {
+            for dependent in self.get_dependents(&current) {
+                if dependent != file && dependents.insert(dependent.clone()) {
+                    queue.push(dependent);
+                }
+            }
+        }
+        dependents.into_iter().collect()
+    }
+}
+
+/// The artifact of `artifact_type` among `artifacts`, deserialized; `None`
+/// when there is none or it does not deserialize.
+fn artifact<T: serde::de::DeserializeOwned>(artifacts: &[CacheArtifact], artifact_type: ArtifactType) -> Option<T> {
+    let artifact = artifacts.iter().find(|a| a.artifact_type == artifact_type)?;
+    bincode::deserialize(&artifact.data).ok()
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -418,4 +684,7 @@ pub enum IncrementalError {
     
     #[error("Invalid cache entry: {0}")]
     InvalidCacheEntry(String),
+    
+    #[error("Compile error in {0}: {1}")]
+    CompileError(String, String),
 }
@@ -1,3 +1,4 @@
+use super::diagnostic::Diagnostic;
 use std::fmt;
 
 /// Source position of a token (1-based line and column).
@@ -5,6 +6,8 @@ use std::fmt;
 pub struct Span {
     pub line: usize,
     pub column: usize,
+    /// Width in characters on the token's first line (at least 1).
+    pub len: usize,
 }
 
 impl fmt::Display for Span {
@@ -33,6 +36,7 @@ pub enum Token {
     SafetyAnalysis,
     Hazard,
     Fmea,
+    FmeaAction,
     Trace,
     Deploys,
     Implements,
@@ -60,6 +64,8 @@ pub enum Token {
     Model,
     /// `import "path.arc"` — multi-file models.
     ImportKw,
+    /// `include "path.arc"` — same as `import`.
+    IncludeKw,
     Metadata,
     Version,
     Author,
@@ -173,6 +179,7 @@ impl Token {
             Token::SafetyAnalysis => "safety_analysis",
             Token::Hazard => "hazard",
             Token::Fmea => "fmea",
+            Token::FmeaAction => "fmea_action",
             Token::Trace => "trace",
             Token::Deploys => "deploys",
             Token::Implements => "implements",
@@ -198,6 +205,7 @@ impl Token {
             Token::CommunicationMeans => "communication_means",
             Token::Model => "model",
             Token::ImportKw => "import",
+            Token::IncludeKw => "include",
             Token::Metadata => "metadata",
             Token::Version => "version",
             Token::Author => "author",
@@ -279,13 +287,13 @@ impl Lexer {
         }
     }
 
-    pub fn tokenize(self) -> Result<Vec<Token>, String> {
+    pub fn tokenize(self) -> Result<Vec<Token>, Diagnostic> {
         self.tokenize_spanned().map(|(tokens, _)| tokens)
     }
 
     /// Tokenize, returning each token together with its source position.
     /// The two vectors are always the same length.
-    pub fn tokenize_spanned(mut self) -> Result<(Vec<Token>, Vec<Span>), String> {
+    pub fn tokenize_spanned(mut self) -> Result<(Vec<Token>, Vec<Span>), Diagnostic> {
         let mut tokens = Vec::new();
         let mut spans = Vec::new();
 
@@ -305,12 +313,15 @@ impl Lexer {
 
             if self.current_char() == '/' && self.peek_char() == Some('*') {
                 let span = self.span();
-                self.skip_block_comment().map_err(|e| format!("{} at {}", e, span))?;
+                self.skip_block_comment().map_err(|e| Diagnostic::at(e, Some(span.into())))?;
                 continue;
             }
 
-            let span = self.span();
-            let token = self.next_token().map_err(|e| format!("{} at {}", e, span))?;
+            let mut span = self.span();
+            let token = self.next_token().map_err(|e| Diagnostic::at(e, Some(span.into())))?;
+            if self.line == span.line {
+                span.len = (self.column - span.column).max(1);
+            }
             tokens.push(token);
             spans.push(span);
         }
@@ -319,7 +330,7 @@ impl Lexer {
     }
 
     fn span(&self) -> Span {
-        Span { line: self.line, column: self.column }
+        Span { line: self.line, column: self.column, len: 1 }
     }
     
     fn next_token(&mut self) -> Result<Token, String> {
@@ -462,6 +473,7 @@ impl Lexer {
             "safety_analysis" => Token::SafetyAnalysis,
             "hazard" => Token::Hazard,
             "fmea" => Token::Fmea,
+            "fmea_action" => Token::FmeaAction,
             "trace" => Token::Trace,
             "deploys" => Token::Deploys,
             "implements" => Token::Implements,
@@ -488,6 +500,7 @@ impl Lexer {
             // New MBSE keywords
             "model" => Token::Model,
             "import" => Token::ImportKw,
+            "include" => Token::IncludeKw,
             "metadata" => Token::Metadata,
             "version" => Token::Version,
             "author" => Token::Author,
@@ -0,0 +1,219 @@
+//! Lint levels for the warnings of `arclang check` and `arclang build`,
+//! after Rust's: a lint `warn`s by default, `deny` turns its findings into
+//! errors that fail the command, and `allow` silences it. The `[lint]`
+//! section of the project's `.arclang/config.toml` sets them:
+//!
+//! ```toml
+//! [lint]
+//! deny = ["orphan-requirement", "dangling-trace"]
+//! allow = ["orphan-component"]
+//! ```
+//!
+//! and `--deny`/`--warn`/`--allow` override it for one run. Within one
+//! source a lint named twice takes the strictest level; the command line
+//! always wins over the config. Errors (a circular derivation, say) are
+//! not lints and stay errors whatever the levels.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::diagnostic::{warning_code, Severity};
+use super::semantic::SemanticModel;
+
+/// Every lint: name, the stable code its findings carry, what it flags.
+pub const LINTS: &[(&str, &str, &str)] = &[
+    ("orphan-requirement", "E-TRACE-ORPHAN", "a requirement with no downstream trace"),
+    ("orphan-component", "E-TRACE-ORPHAN", "a component with no upstream trace"),
+    ("not-deployed", "E-NOT-DEPLOYED", "a logical component deployed on no physical node"),
+    ("port-direction", "E-PORT-DIRECTION", "a connection from an in port or to an out port"),
+    ("port-unconnected", "E-PORT-UNCONNECTED", "a required port with no connection"),
+    ("chain-step", "E-CHAIN-STEP", "a functional chain step that is not a function"),
+    ("chain-gap", "E-CHAIN-GAP", "consecutive chain functions with no data exchange"),
+    ("trace-endpoint", "E-TRACE-ENDPOINT", "a typed trace to the wrong kind of element"),
+    ("trace-cycle", "E-TRACE-CYCLE", "a cycle of traces or dependencies"),
+    ("dangling-trace", "E-TRACE-UNRESOLVED", "a trace to an undeclared element (--allow-dangling)"),
+    ("duplicate-id", "E-DUPLICATE-ID", "an element id declared twice"),
+    ("unmodeled", "E-UNMODELED", "a construct the semantic model does not cover yet"),
+    ("unreferenced", "E-UNREFERENCED", "an element no trace or connection reaches (optimization level 2+)"),
+    ("cross-layer", "E-CROSS-LAYER", "an Arcadia cross-layer inconsistency (optimization level 3)"),
+];
+
+/// What a lint's findings become.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// The `[lint]` section: lint names per level.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintLevelConfig {
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub warn: Vec<String>,
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+/// The level of every lint; unset ones warn.
+#[derive(Debug, Clone, Default)]
+pub struct LintLevels {
+    levels: HashMap<&'static str, LintLevel>,
+}
+
+impl LintLevels {
+    /// Levels of `config`, on top of the current ones.
+    pub fn apply(&mut self, config: &LintLevelConfig) -> Result<(), String> {
+        let mut levels = HashMap::new();
+        for (level, names) in
+            [(LintLevel::Allow, &config.allow), (LintLevel::Warn, &config.warn), (LintLevel::Deny, &config.deny)]
+        {
+            for name in names {
+                let lint = lint_name(name).ok_or_else(|| {
+                    format!(
+                        "unknown lint '{name}' (expected one of {})",
+                        LINTS.iter().map(|(name, ..)| *name).collect::<Vec<_>>().join(", ")
+                    )
+                })?;
+                levels.insert(lint, level);
+            }
+        }
+        self.levels.extend(levels);
+        Ok(())
+    }
+
+    pub fn level(&self, lint: &str) -> LintLevel {
+        self.levels.get(lint).copied().unwrap_or(LintLevel::Warn)
+    }
+
+    /// What a finding of `lint` reported at `severity` is reported as;
+    /// `None` when the lint is allowed. Errors and findings of no lint keep
+    /// their severity.
+    pub fn severity(&self, lint: Option<&str>, severity: Severity) -> Option<Severity> {
+        match (severity, lint.map(|lint| self.level(lint))) {
+            (Severity::Warning, Some(LintLevel::Allow)) => None,
+            (Severity::Warning, Some(LintLevel::Deny)) => Some(Severity::Error),
+            _ => Some(severity),
+        }
+    }
+}
+
+/// The warnings of a compilation at their levels.
+#[derive(Debug, Clone, Default)]
+pub struct LeveledWarnings {
+    /// Compiler warnings still reported as warnings.
+    pub compiler: Vec<String>,
+    /// Traceability warnings still reported as warnings.
+    pub traceability: Vec<String>,
+    /// Denied findings of either kind, as `error[lint]: message`.
+    pub denied: Vec<String>,
+}
+
+/// Sort the compiler `warnings` and the traceability warnings of `model`
+/// by level; allowed ones are dropped.
+pub fn level_warnings(warnings: &[String], model: &SemanticModel, levels: &LintLevels) -> LeveledWarnings {
+    let mut leveled = LeveledWarnings::default();
+    let mut sort = |lint: Option<&str>, message: String, warnings: &mut Vec<String>| {
+        match levels.severity(lint, Severity::Warning) {
+            Some(Severity::Error) => leveled.denied.push(format!("error[{}]: {message}", lint.unwrap_or_default())),
+            Some(Severity::Warning) => warnings.push(message),
+            None => {}
+        }
+    };
+    let mut compiler = Vec::new();
+    for warning in warnings {
+        sort(warning_lint(warning), warning.clone(), &mut compiler);
+    }
+    let mut traceability = Vec::new();
+    for issue in model.traceability_issues().into_iter().filter(|i| i.severity == Severity::Warning) {
+        sort(Some(issue.lint), issue.diagnostic.message, &mut traceability);
+    }
+    leveled.compiler = compiler;
+    leveled.traceability = traceability;
+    leveled
+}
+
+/// The lint a compiler warning belongs to, if any.
+pub fn warning_lint(message: &str) -> Option<&'static str> {
+    let code = warning_code(message);
+    // Codes shared by several lints are traceability issues, which carry
+    // their lint name themselves.
+    LINTS.iter().find(|(_, lint_code, _)| *lint_code == code).map(|(name, ..)| *name)
+}
+
+fn lint_name(name: &str) -> Option<&'static str> {
+    LINTS.iter().map(|(lint, ..)| *lint).find(|lint| *lint == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(deny: &[&str], warn: &[&str], allow: &[&str]) -> LintLevelConfig {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        LintLevelConfig { deny: names(deny), warn: names(warn), allow: names(allow) }
+    }
+
+    #[test]
+    fn the_command_line_overrides_the_config_and_deny_wins_within_one_source() {
+        let mut levels = LintLevels::default();
+        levels.apply(&config(&["orphan-requirement", "dangling-trace"], &[], &["orphan-component"])).unwrap();
+        levels.apply(&config(&[], &["dangling-trace"], &[])).unwrap();
+        assert_eq!(levels.level("orphan-requirement"), LintLevel::Deny);
+        assert_eq!(levels.level("dangling-trace"), LintLevel::Warn);
+        assert_eq!(levels.level("orphan-component"), LintLevel::Allow);
+        assert_eq!(levels.level("chain-gap"), LintLevel::Warn);
+
+        levels.apply(&config(&["chain-gap"], &[], &["chain-gap"])).unwrap();
+        assert_eq!(levels.level("chain-gap"), LintLevel::Deny);
+    }
+
+    #[test]
+    fn only_warnings_change_severity() {
+        let mut levels = LintLevels::default();
+        levels.apply(&config(&["trace-cycle"], &[], &["not-deployed"])).unwrap();
+        assert_eq!(levels.severity(Some("trace-cycle"), Severity::Warning), Some(Severity::Error));
+        assert_eq!(levels.severity(Some("not-deployed"), Severity::Warning), None);
+        assert_eq!(levels.severity(Some("trace-cycle"), Severity::Error), Some(Severity::Error));
+        assert_eq!(levels.severity(None, Severity::Warning), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn denied_warnings_are_reported_as_errors_and_allowed_ones_dropped() {
+        let model = crate::compiler::Compiler::new(crate::compiler::CompilerConfig::default())
+            .compile_string(
+                r#"model Test {
+}
+system_analysis "SA" {
+    requirement "REQ-001" { description: "The controller shall brake" }
+}
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+}
+"#,
+            )
+            .expect("compiles")
+            .semantic_model;
+        let mut levels = LintLevels::default();
+        levels.apply(&config(&["orphan-requirement"], &[], &["orphan-component"])).unwrap();
+        let warnings = vec!["duplicate element id 'LC-001'".to_string()];
+        let leveled = level_warnings(&warnings, &model, &levels);
+        assert_eq!(leveled.compiler, warnings);
+        assert!(leveled.traceability.is_empty(), "{:?}", leveled.traceability);
+        assert_eq!(leveled.denied, ["error[orphan-requirement]: Requirement REQ-001 has no downstream traces"]);
+    }
+
+    #[test]
+    fn unknown_lints_are_rejected_and_warnings_map_to_their_lint() {
+        let err = LintLevels::default().apply(&config(&["orphans"], &[], &[])).unwrap_err();
+        assert!(err.starts_with("unknown lint 'orphans' (expected one of orphan-requirement,"), "{err}");
+        assert_eq!(warning_lint("duplicate element id 'LC-001'"), Some("duplicate-id"));
+        assert_eq!(warning_lint("unreferenced component 'LC-002': no trace reaches it"), Some("unreferenced"));
+        assert_eq!(warning_lint("cross-layer: system analysis without an operational analysis"), Some("cross-layer"));
+        assert_eq!(warning_lint("something else"), None);
+    }
+}
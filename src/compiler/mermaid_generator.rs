@@ -1,15 +1,37 @@
-use super::semantic::SemanticModel;
+use super::codegen::{components_by_layer, layer_title};
+use super::semantic::{ComponentInfo, SemanticModel};
 use super::CompilerError;
+use crate::safety::safety_case::{GsnGraph, GsnNodeKind, GsnRelation};
 use std::collections::{HashMap, HashSet};
 
+/// Flow direction of a flowchart: layers stacked top to bottom, or side by
+/// side left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlowDirection {
+    #[default]
+    TopBottom,
+    LeftRight,
+}
+
+impl FlowDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlowDirection::TopBottom => "TB",
+            FlowDirection::LeftRight => "LR",
+        }
+    }
+}
+
 pub struct MermaidGenerator {
     layout: String,
+    direction: FlowDirection,
 }
 
 impl MermaidGenerator {
     pub fn new() -> Self {
         Self {
             layout: "elk".to_string(),
+            direction: FlowDirection::default(),
         }
     }
     
@@ -17,6 +39,11 @@ impl MermaidGenerator {
         self.layout = layout;
         self
     }
+
+    pub fn with_direction(mut self, direction: FlowDirection) -> Self {
+        self.direction = direction;
+        self
+    }
     
     pub fn generate(&self, model: &SemanticModel, title: &str) -> Result<String, CompilerError> {
         let mut mermaid = String::new();
@@ -26,33 +53,70 @@ impl MermaidGenerator {
         mermaid.push_str(&format!("config:\n  layout: {}\n", self.layout));
         mermaid.push_str(&format!("title: {}\n", title));
         mermaid.push_str("---\n");
-        mermaid.push_str("flowchart TD\n");
+        mermaid.push_str(&format!("flowchart {}\n", self.direction.as_str()));
         
         // Group requirements by category
         let categories = self.group_by_category(model);
         
-        // Generate subgraphs for each category
-        for (i, (category, reqs)) in categories.iter().enumerate() {
-            mermaid.push_str(&format!(" subgraph subGraph{}[\"{}\"]", i, category));
-            mermaid.push_str("\n");
+        // One subgraph per Arcadia layer; requirements sit in System Analysis
+        // and a deployed component inside the physical node hosting it
+        let deployed: HashSet<&str> = model.deployments.iter().map(|d| d.component.as_str()).collect();
+        let mut placed: HashMap<&str, &str> = HashMap::new();
+        let mut layers = components_by_layer(model);
+        if !categories.is_empty() && !layers.iter().any(|(layer, _)| *layer == "System") {
+            let at = layers.iter().position(|(layer, _)| *layer != "Operational").unwrap_or(layers.len());
+            layers.insert(at, ("System", Vec::new()));
+        }
+        
+        for (layer, components) in layers {
+            if layer == "Physical" {
+                mermaid.push_str(&self.generate_physical_layer(model));
+                for node in components {
+                    placed.insert(&node.id, layer);
+                }
+                for deployment in &model.deployments {
+                    placed.insert(&deployment.component, layer);
+                }
+                continue;
+            }
+            
+            let components: Vec<_> = components.into_iter().filter(|c| !deployed.contains(c.id.as_str())).collect();
+            let requirements = if layer == "System" { categories.as_slice() } else { &[] };
+            if components.is_empty() && requirements.is_empty() {
+                continue;
+            }
             
-            for req in reqs {
-                let node_id = &req.id;
-                let name = req.id.clone();
-                let desc = req.description.replace('\n', "<br>");
+            mermaid.push_str(&format!(" subgraph {}[\"{}\"]\n", layer_subgraph_id(layer), layer_title(layer)));
+            
+            // Generate subgraphs for each category
+            for (i, (category, reqs)) in requirements.iter().enumerate() {
+                mermaid.push_str(&format!("  subgraph subGraph{}[\"{}\"]\n", i, category));
+                
+                for req in reqs {
+                    let desc = req.description.replace('\n', "<br>");
+                    mermaid.push_str(&format!("        {}[\"{}<br>{}\"]\n", req.id, req.id, desc));
+                    placed.insert(&req.id, layer);
+                }
                 
+                mermaid.push_str("  end\n");
+            }
+            
+            for component in components {
                 mermaid.push_str(&format!(
-                    "        {}[\"{}\"]\n",
-                    node_id,
-                    format!("{}<br>{}", name, desc)
+                    "    {}[\"{}<br>{}\"]:::{}\n",
+                    component.id,
+                    component.id,
+                    component.name.replace('"', "#quot;"),
+                    element_class(model, component)
                 ));
+                placed.insert(&component.id, layer);
             }
             
             mermaid.push_str("  end\n");
         }
         
         // Generate trace relationships
-        mermaid.push_str(&self.generate_relationships(model));
+        mermaid.push_str(&self.generate_relationships(model, &placed));
         
         // Generate styling
         mermaid.push_str(&self.generate_styles(&categories));
@@ -112,21 +176,70 @@ impl MermaidGenerator {
         }
     }
     
-    fn generate_relationships(&self, model: &SemanticModel) -> String {
+    fn generate_physical_layer(&self, model: &SemanticModel) -> String {
+        let nodes: Vec<_> = model.components.iter().filter(|c| c.level == "Physical").collect();
+        if nodes.is_empty() {
+            return String::new();
+        }
+        
+        let mut layer = String::from(" subgraph physicalLayer[\"Physical Architecture\"]\n");
+        for node in nodes {
+            // Nodes without an explicit id are keyed by their name
+            let subgraph_id: String = node
+                .id
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+                .collect();
+            layer.push_str(&format!("  subgraph {}[\"{}\"]\n", subgraph_id, node.name));
+            for deployment in model.deployments.iter().filter(|d| d.node == node.id) {
+                let name = model
+                    .get_component(&deployment.component)
+                    .map(|c| c.name.as_str())
+                    .unwrap_or(&deployment.component);
+                layer.push_str(&format!(
+                    "        {}[\"{}<br>{}\"]:::physical\n",
+                    deployment.component, deployment.component, name
+                ));
+            }
+            layer.push_str("  end\n");
+        }
+        layer.push_str("  end\n");
+        layer.push_str("    classDef physical fill:#FFE699,color:black\n");
+        layer
+    }
+    
+    /// Every trace once, labelled with its type. An edge between two layers
+    /// is dotted and grey so the layer-internal structure stands out.
+    fn generate_relationships(&self, model: &SemanticModel, placed: &HashMap<&str, &str>) -> String {
         let mut relationships = String::new();
-        let mut added: HashSet<(String, String)> = HashSet::new();
+        let mut added: HashSet<(&str, &str, &str)> = HashSet::new();
+        let mut cross_layer = Vec::new();
         
         for trace in &model.traces {
-            if trace.trace_type == "implements" {
-                let key = (trace.from.clone(), trace.to.clone());
-                if !added.contains(&key) {
-                    relationships.push_str(&format!(
-                        "    {} --> {}\n",
-                        trace.from, trace.to
-                    ));
-                    added.insert(key);
-                }
+            if !added.insert((&trace.from, &trace.to, &trace.trace_type)) {
+                continue;
             }
+            let crosses = match (placed.get(trace.from.as_str()), placed.get(trace.to.as_str())) {
+                (Some(from), Some(to)) => from != to,
+                _ => false,
+            };
+            let arrow = if crosses {
+                cross_layer.push((added.len() - 1).to_string());
+                "-.->"
+            } else {
+                "-->"
+            };
+            relationships.push_str(&format!(
+                "    {} {}|{}| {}\n",
+                trace.from, arrow, trace.trace_type, trace.to
+            ));
+        }
+        
+        if !cross_layer.is_empty() {
+            relationships.push_str(&format!(
+                "    linkStyle {} stroke:#888888,stroke-width:1px\n",
+                cross_layer.join(",")
+            ));
         }
         
         relationships
@@ -175,18 +288,146 @@ impl MermaidGenerator {
                 class_name, color
             ));
         }
+        for (class_name, color) in ELEMENT_CLASSES {
+            styles.push_str(&format!(
+                "    classDef {} fill:{},color:black\n",
+                class_name, color
+            ));
+        }
         
         styles
     }
 }
 
+/// Fill colours by element type, in the Arcadia palette.
+/// Deployed components take the `physical` class of their node instead.
+const ELEMENT_CLASSES: [(&str, &str); 3] = [
+    ("actor", "#FFF2CC"),
+    ("activity", "#F8CBAD"),
+    ("component", "#BDD7EE"),
+];
+
+/// The `ELEMENT_CLASSES` class of a component, from its registry type as the
+/// semantic analyzer classifies it.
+fn element_class(model: &SemanticModel, component: &ComponentInfo) -> &'static str {
+    let registry_type = model
+        .all_elements
+        .get(&component.id)
+        .map(|e| e.element_type.as_str())
+        .unwrap_or("Component");
+    match registry_type {
+        "Actor" | "Entity" => "actor",
+        "Activity" | "OperationalActivity" => "activity",
+        _ => "component",
+    }
+}
+
+/// Subgraph id of a layer: `operationalLayer`, `systemLayer`, ...
+fn layer_subgraph_id(level: &str) -> String {
+    let mut id: String = level
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    id.push_str("Layer");
+    id
+}
+
 // Convenience function
 pub fn generate_mermaid_flowchart(
     model: &SemanticModel,
     title: &str,
     layout: &str,
+    direction: FlowDirection,
 ) -> Result<String, CompilerError> {
     MermaidGenerator::new()
         .with_layout(layout.to_string())
+        .with_direction(direction)
         .generate(model, title)
 }
+
+/// Functional chains as a left-to-right flowchart: one subgraph per chain,
+/// its functions in order, each edge labelled with the exchange or data item
+/// carrying data to the next function. A gap is a dotted edge.
+pub fn generate_mermaid_functional_chains(model: &SemanticModel, title: &str) -> Result<String, CompilerError> {
+    let mut mermaid = String::new();
+    mermaid.push_str("---\n");
+    mermaid.push_str(&format!("title: {}\n", title));
+    mermaid.push_str("---\n");
+    mermaid.push_str("flowchart LR\n");
+
+    for chain in &model.functional_chains {
+        let chain_id: String = chain
+            .id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        mermaid.push_str(&format!(" subgraph {}[\"{}\"]\n", chain_id, chain.name));
+        for (i, step) in chain.involves.iter().enumerate() {
+            let name = model.all_elements.get(step).map(|e| e.name.as_str()).unwrap_or(step);
+            mermaid.push_str(&format!("    {}_{}[\"{}<br>{}\"]\n", chain_id, i, step, name));
+        }
+        mermaid.push_str("  end\n");
+        for (i, link) in chain.links.iter().enumerate() {
+            match &link.exchange {
+                Some(exchange) => mermaid.push_str(&format!(
+                    "    {}_{} -->|\"{}\"| {}_{}\n",
+                    chain_id, i, exchange, chain_id, i + 1
+                )),
+                None => mermaid.push_str(&format!(
+                    "    {}_{} -.->|\"no exchange\"| {}_{}\n",
+                    chain_id, i, chain_id, i + 1
+                )),
+            }
+        }
+    }
+
+    Ok(mermaid)
+}
+
+/// A GSN safety case (see `safety::safety_case::build_gsn`), top-down: goals
+/// are rectangles, strategies parallelograms, solutions circles and contexts
+/// rounded. Undeveloped nodes are dashed, goals without evidence red.
+pub fn generate_mermaid_gsn(graph: &GsnGraph, title: &str) -> Result<String, CompilerError> {
+    let mut mermaid = String::new();
+    mermaid.push_str("---\n");
+    mermaid.push_str(&format!("title: {}\n", title));
+    mermaid.push_str("---\n");
+    mermaid.push_str("flowchart TB\n");
+
+    for node in &graph.nodes {
+        let mut text = node.text.replace('"', "#quot;");
+        if node.unsupported {
+            text.push_str("<br>unsupported: no evidence");
+        } else if node.undeveloped {
+            text.push_str("<br>undeveloped");
+        }
+        let shape = match node.kind {
+            GsnNodeKind::Goal => format!("[\"{}\"]", text),
+            GsnNodeKind::Strategy => format!("[/\"{}\"/]", text),
+            GsnNodeKind::Solution => format!("((\"{}\"))", text),
+            GsnNodeKind::Context => format!("([\"{}\"])", text),
+        };
+        mermaid.push_str(&format!("    {}{}\n", node.id, shape));
+    }
+
+    for edge in &graph.edges {
+        let arrow = match edge.relation {
+            GsnRelation::SupportedBy => "-->",
+            GsnRelation::InContextOf => "-.->",
+        };
+        mermaid.push_str(&format!("    {} {} {}\n", edge.from, arrow, edge.to));
+    }
+
+    mermaid.push_str("    classDef undeveloped stroke-dasharray: 5 5\n");
+    mermaid.push_str("    classDef unsupported fill:#f8cecc,stroke:#b85450,stroke-width:2px\n");
+    for node in &graph.nodes {
+        if node.unsupported {
+            mermaid.push_str(&format!("    class {} unsupported\n", node.id));
+        } else if node.undeveloped {
+            mermaid.push_str(&format!("    class {} undeveloped\n", node.id));
+        }
+    }
+
+    Ok(mermaid)
+}
@@ -1,9 +1,17 @@
 pub mod lexer;
+pub mod diagnostic;
+pub mod completion;
 pub mod parser;
 pub mod ast;
 pub mod identity;
 pub mod production_gate;
 pub mod semantic;
+pub mod requirement_lint;
+pub mod id_conventions;
+pub mod lint_levels;
+pub mod stats;
+pub mod ownership;
+pub mod profile;
 pub mod semantic_analyzer;
 pub mod layout_strategy;
 pub mod post_processor;
@@ -16,17 +24,27 @@ pub mod semantic_adapter;
 pub mod capella_metamodel;
 pub mod codegen;
 pub mod capella_importer;
+pub mod capella_exporter;
 pub mod sysmlv2_generator;
 pub mod simulink_generator;
 pub mod fmi_generator;
 pub mod reqif;
+pub mod regenerate;
+pub mod xml;
 pub mod semantic_diff;
+pub mod formatter;
+pub mod model_union;
+pub mod pdf_generator;
+pub mod html_report;
 pub mod c_header_generator;
 pub mod proto_generator;
 pub mod mermaid_generator;
 pub mod mermaid_importer;
+pub mod doors_importer;
+pub mod requirements_csv;
 pub mod plantuml_generator;
 pub mod plantuml_importer;
+pub mod graphviz_generator;
 pub mod arcadia_7d_intelligent_generator;
 pub mod capella_compliant_generator;
 
@@ -43,16 +61,25 @@ pub mod github_actions_generator;
 pub mod gitlab_ci_generator;
 pub mod opa_policy_generator;
 
+#[cfg(feature = "native")]
+pub mod incremental;
+#[cfg(feature = "native")]
+mod parallel_parse;
+#[cfg(feature = "native")]
+pub mod yaml;
+
+use diagnostic::Diagnostic;
+use profile::PhaseTimer;
 use std::path::Path;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum CompilerError {
     #[error("Lexer error: {0}")]
-    Lexer(String),
+    Lexer(Diagnostic),
     
     #[error("Parser error: {0}")]
-    Parser(String),
+    Parser(Diagnostic),
     
     #[error("Parse error: {0}")]
     Parse(String),
@@ -60,6 +87,9 @@ pub enum CompilerError {
     #[error("Semantic error: {0}")]
     Semantic(String),
     
+    #[error("Semantic error: {} unresolved trace reference(s):\n  {}", .0.len(), diagnostic::list(.0))]
+    UnresolvedTraces(Vec<Diagnostic>),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -67,21 +97,79 @@ pub enum CompilerError {
     Other(String),
 }
 
+impl CompilerError {
+    /// The error as located diagnostics (one per unresolved reference).
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            Self::Lexer(diagnostic) | Self::Parser(diagnostic) => vec![diagnostic.clone()],
+            Self::UnresolvedTraces(diagnostics) => diagnostics.clone(),
+            other => vec![Diagnostic::new(other.to_string())],
+        }
+    }
+
+    /// Stable diagnostic code (`arclang check --json`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Lexer(_) | Self::Parser(_) | Self::Parse(_) => "E-SYNTAX",
+            Self::UnresolvedTraces(_) => "E-TRACE-UNRESOLVED",
+            Self::Semantic(_) => "E-SEMANTIC",
+            Self::Io(_) => "E-IO",
+            Self::Other(_) => "E-COMPILE",
+        }
+    }
+}
+
+impl From<semantic::SemanticError> for CompilerError {
+    fn from(error: semantic::SemanticError) -> Self {
+        match error {
+            semantic::SemanticError::Invalid(message) => Self::Semantic(message),
+            semantic::SemanticError::UnresolvedTraces(diagnostics) => Self::UnresolvedTraces(diagnostics),
+        }
+    }
+}
+
 pub struct Compiler {
     config: CompilerConfig,
 }
 
 #[derive(Debug, Clone)]
 pub struct CompilerConfig {
+    /// How much checking a compilation does; each level adds to the one
+    /// below, and its findings land in [`CompilationResult::warnings`]:
+    ///
+    /// - 0: fast, for edit-compile loops (`build`). Exchange and link
+    ///   endpoints are not resolved.
+    /// - 1 (the default): full validation, endpoints included.
+    /// - 2 (`check --lint`): also dead elements (see
+    ///   [`semantic::SemanticModel::unreferenced_elements`]) but the
+    ///   [`standalone`](Self::standalone) ones (`unreferenced` lint).
+    /// - 3 (`build --release`): also the Arcadia cross-layer consistency
+    ///   checks of `check --lint` (`cross-layer` lint).
     pub optimization_level: u8,
     pub target: String,
+    /// Downgrade traces to undeclared elements to warnings (`--allow-dangling`).
+    pub allow_dangling: bool,
+    /// Parse the files of an import tree in parallel (native builds only).
+    pub enable_parallel: bool,
+    /// Threads for parallel parsing; 0 is one per core.
+    pub num_threads: usize,
+    /// Time each phase into [`CompilationResult::profile`] (`--profile`).
+    pub profile: bool,
+    /// Element ids kept unreferenced on purpose, exempt from dead-element
+    /// detection (`[unreferenced] standalone` in .arclang/config.toml).
+    pub standalone: Vec<String>,
 }
 
 impl Default for CompilerConfig {
     fn default() -> Self {
         Self {
-            optimization_level: 2,
+            optimization_level: 1,
             target: "capella".to_string(),
+            allow_dangling: false,
+            enable_parallel: false,
+            num_threads: 0,
+            profile: false,
+            standalone: Vec::new(),
         }
     }
 }
@@ -94,6 +182,32 @@ pub struct CompilationResult {
     /// Non-fatal diagnostics (e.g. constructs accepted syntactically but not
     /// yet represented in the compiled model). Never silently empty a model.
     pub warnings: Vec<String>,
+    /// How the import tree was parsed, when it was parsed in parallel.
+    pub parse_stats: Option<ParseStats>,
+    /// Phase timings, when [`CompilerConfig::profile`] is set.
+    pub profile: Option<profile::Profile>,
+}
+
+/// One file's AST, its imports unresolved, and its parse warnings.
+type ParsedFile = Result<(ast::Model, Vec<String>), CompilerError>;
+
+/// Timings of a parallel parse.
+#[derive(Debug, Clone)]
+pub struct ParseStats {
+    /// Distinct files of the import tree.
+    pub files: usize,
+    pub threads: usize,
+    /// Wall-clock time from the first read to the merged AST.
+    pub wall: std::time::Duration,
+    /// The parse times of all files added up: what parsing them one after
+    /// the other would take.
+    pub serial: std::time::Duration,
+}
+
+impl ParseStats {
+    pub fn speedup(&self) -> f64 {
+        self.serial.as_secs_f64() / self.wall.as_secs_f64().max(f64::EPSILON)
+    }
 }
 
 impl Compiler {
@@ -103,21 +217,35 @@ impl Compiler {
     
     pub fn compile_file<P: AsRef<Path>>(&mut self, path: P) -> Result<CompilationResult, CompilerError> {
         let path = path.as_ref();
+        let mut profile = self.config.profile.then(profile::Profile::default);
+        let timer = PhaseTimer::start(profile.as_mut(), "parse");
+        #[cfg(feature = "native")]
+        if self.config.enable_parallel {
+            let (ast, warnings, stats) = parallel_parse::parse_import_tree(path, self.config.num_threads)?;
+            drop(timer);
+            let mut result = self.finish(ast, warnings, profile)?;
+            result.parse_stats = Some(stats);
+            return Ok(result);
+        }
         let mut import_stack = Vec::new();
         let (ast, warnings) = Self::parse_file_with_imports(path, &mut import_stack)?;
-        self.finish(ast, warnings)
+        drop(timer);
+        self.finish(ast, warnings, profile)
     }
 
     pub fn compile_string(&mut self, source: &str) -> Result<CompilationResult, CompilerError> {
+        let mut profile = self.config.profile.then(profile::Profile::default);
+        let timer = PhaseTimer::start(profile.as_mut(), "parse");
         let (ast, warnings) = Self::parse_source(source)?;
+        drop(timer);
         if !ast.imports.is_empty() {
-            return Err(CompilerError::Parser(format!(
+            return Err(CompilerError::Parser(Diagnostic::new(format!(
                 "this model imports {} file(s) — compile it from its file so \
                  relative import paths can be resolved",
                 ast.imports.len()
-            )));
+            ))));
         }
-        self.finish(ast, warnings)
+        self.finish(ast, warnings, profile)
     }
 
     /// Lex + parse one source text. No filesystem access.
@@ -131,6 +259,12 @@ impl Compiler {
         Ok((model, warnings))
     }
 
+    /// Read and parse one file, leaving its imports unresolved.
+    fn read_and_parse(canonical: &Path) -> ParsedFile {
+        let source = std::fs::read_to_string(canonical)?;
+        Self::parse_source(&source)
+    }
+
     /// Parse a file and recursively merge its `import "..."` declarations,
     /// resolved relative to the importing file. `import_stack` holds the
     /// canonical paths currently being parsed: re-entering one is a cycle
@@ -138,6 +272,30 @@ impl Compiler {
     fn parse_file_with_imports(
         path: &Path,
         import_stack: &mut Vec<std::path::PathBuf>,
+    ) -> Result<(ast::Model, Vec<String>), CompilerError> {
+        Self::assemble_imports(path, import_stack, &mut Self::read_and_parse)
+    }
+
+    /// The files of `path`'s import tree, canonical, with their text, in
+    /// merge order: the sources a compilation of `path` reads.
+    pub fn import_tree_sources(path: &Path) -> Result<Vec<(std::path::PathBuf, String)>, CompilerError> {
+        let mut sources = Vec::new();
+        Self::assemble_imports(path, &mut Vec::new(), &mut |canonical| {
+            let source = std::fs::read_to_string(canonical)?;
+            let parsed = Self::parse_source(&source);
+            sources.push((canonical.to_path_buf(), source));
+            parsed
+        })?;
+        Ok(sources)
+    }
+
+    /// [`Self::parse_file_with_imports`] with `parse` reading and parsing
+    /// each canonical file: the merge order, and so the merged AST and its
+    /// warnings, do not depend on how or when the files were parsed.
+    fn assemble_imports(
+        path: &Path,
+        import_stack: &mut Vec<std::path::PathBuf>,
+        parse: &mut dyn FnMut(&Path) -> ParsedFile,
     ) -> Result<(ast::Model, Vec<String>), CompilerError> {
         let canonical = path.canonicalize().map_err(|e| {
             CompilerError::Io(std::io::Error::new(
@@ -151,39 +309,35 @@ impl Compiler {
                 .chain(std::iter::once(&canonical))
                 .map(|p| p.display().to_string())
                 .collect();
-            return Err(CompilerError::Parser(format!(
+            return Err(CompilerError::Parser(Diagnostic::new(format!(
                 "circular import: {}",
                 chain.join(" -> ")
-            )));
+            ))));
         }
         import_stack.push(canonical.clone());
 
-        let source = std::fs::read_to_string(&canonical)?;
-        let (mut root, mut warnings) = Self::parse_source(&source).map_err(|e| match e {
+        let (mut root, mut warnings) = parse(&canonical).map_err(|e| match e {
             // Localize parse errors to the file they came from.
-            CompilerError::Parser(msg) => {
-                CompilerError::Parser(format!("{}: {msg}", path.display()))
-            }
-            CompilerError::Lexer(msg) => {
-                CompilerError::Lexer(format!("{}: {msg}", path.display()))
-            }
+            CompilerError::Parser(diagnostic) => CompilerError::Parser(diagnostic.in_file(path)),
+            CompilerError::Lexer(diagnostic) => CompilerError::Lexer(diagnostic.in_file(path)),
             other => other,
         })?;
+        root.set_source_file(path);
 
         let base_dir = canonical.parent().map(Path::to_path_buf).unwrap_or_default();
         for import in std::mem::take(&mut root.imports) {
             let target = base_dir.join(&import);
             if !target.exists() {
                 import_stack.pop();
-                return Err(CompilerError::Parser(format!(
+                return Err(CompilerError::Parser(Diagnostic::new(format!(
                     "{}: imported file not found: {} (resolved to {})",
                     path.display(),
                     import,
                     target.display()
-                )));
+                ))));
             }
             let (fragment, fragment_warnings) =
-                Self::parse_file_with_imports(&target, import_stack)?;
+                Self::assemble_imports(&target, import_stack, parse)?;
             root.merge(fragment);
             warnings.extend(fragment_warnings);
         }
@@ -192,27 +346,66 @@ impl Compiler {
         Ok((root, warnings))
     }
 
-    /// Semantic analysis + code generation on a fully-merged AST.
+    /// Semantic analysis, the checks of the optimization level, and code
+    /// generation on a fully-merged AST.
     fn finish(
         &mut self,
         ast: ast::Model,
         mut warnings: Vec<String>,
+        mut profile: Option<profile::Profile>,
     ) -> Result<CompilationResult, CompilerError> {
         // Semantic analysis (dangling traces are errors; unresolved exchange
         // endpoints are warnings until ports become first-class)
+        let timer = PhaseTimer::start(profile.as_mut(), "semantic analysis");
+        let level = self.config.optimization_level;
         let (semantic_model, semantic_warnings) = semantic::SemanticAnalyzer::new()
-            .analyze_with_warnings(&ast)
-            .map_err(CompilerError::Semantic)?;
+            .allow_dangling(self.config.allow_dangling)
+            .check_endpoints(level >= 1)
+            .analyze_with_warnings(&ast)?;
         warnings.extend(semantic_warnings);
+        if level >= 2 {
+            warnings.extend(dead_elements(&semantic_model, &self.config.standalone));
+        }
+        if level >= 3 {
+            warnings.extend(
+                semantic::arcadia_methodology_lints(&ast)
+                    .into_iter()
+                    .map(|lint| format!("cross-layer: {lint}")),
+            );
+        }
+        drop(timer);
 
         // Code generation
+        let timer = PhaseTimer::start(profile.as_mut(), "codegen");
         let output = codegen::CodeGenerator::new(&self.config).generate(&semantic_model)?;
+        drop(timer);
 
+        if let Some(profile) = &mut profile {
+            profile.count_elements(&semantic_model);
+        }
         Ok(CompilationResult {
             ast,
             semantic_model,
             output,
             warnings,
+            parse_stats: None,
+            profile,
         })
     }
 }
+
+/// Level 2 findings: [`semantic::SemanticModel::unreferenced_elements`]
+/// but the `standalone` ones.
+fn dead_elements(model: &semantic::SemanticModel, standalone: &[String]) -> Vec<String> {
+    model
+        .unreferenced_elements()
+        .into_iter()
+        .filter(|(id, _)| !standalone.contains(id))
+        .map(|(id, element_type)| match element_type {
+            semantic_analyzer::ElementType::Requirement => {
+                format!("unreferenced requirement '{id}': no trace or test case reaches it")
+            }
+            _ => format!("unreferenced component '{id}': no trace, connection or interface reaches it"),
+        })
+        .collect()
+}
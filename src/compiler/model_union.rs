@@ -0,0 +1,348 @@
+//! Union of separately authored subsystem models (`arclang merge-models`),
+//! for analysis across a whole program.
+//!
+//! Unlike the three-way `arclang merge` there is no common ancestor: every
+//! input contributes all of its declarations. The union is built on source
+//! text, so comments and attribute order survive: each input's model header
+//! (`model X { version: ... }`) gives way to one header for the union, and
+//! the declarations follow in input order, reformatted.
+//!
+//! A name or id declared by two inputs is a collision. Namespacing prefixes
+//! every declaration with its input's file stem (`brake::REQ-001`), and
+//! every string or name referring to one: a trace in `brake.arc` to
+//! `REQ-009`, declared only by `steering.arc`, becomes a trace to
+//! `steering::REQ-009`, so links between subsystems survive.
+
+use super::ast::Model;
+use super::doors_importer::quote;
+use super::formatter::{format_source, token_ranges};
+use super::lexer::{Lexer, Token};
+use super::parser::Parser;
+use super::CompilerError;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+use std::path::Path;
+
+/// Between an input's namespace and its ids.
+pub const NAMESPACE_SEPARATOR: &str = "::";
+
+/// One model to unite: `label` (its path) names it in reports.
+pub struct UnionInput {
+    pub label: String,
+    pub source: String,
+}
+
+/// A name or id declared by more than one input.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Collision {
+    pub name: String,
+    pub inputs: Vec<String>,
+}
+
+/// Traces written in input `from` reaching an element of input `to`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CrossModelTraces {
+    pub from: String,
+    pub to: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModelUnion {
+    /// The united model, formatted.
+    pub source: String,
+    /// Reported either way; without namespacing the union does not compile
+    /// while there are any.
+    pub collisions: Vec<Collision>,
+    pub cross_model_traces: Vec<CrossModelTraces>,
+}
+
+/// The namespace of an input: its file stem, with anything but letters,
+/// digits, `-` and `_` replaced by `_`.
+pub fn namespace_of(label: &str) -> String {
+    let stem = Path::new(label)
+        .file_stem()
+        .map_or_else(|| label.to_string(), |stem| stem.to_string_lossy().into_owned());
+    stem.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+struct Parsed {
+    chars: Vec<char>,
+    tokens: Vec<Token>,
+    ranges: Vec<Range<usize>>,
+    model: Model,
+}
+
+fn parse(input: &UnionInput) -> Result<Parsed, CompilerError> {
+    let in_input = |e: &dyn std::fmt::Display| CompilerError::Other(format!("{}: {e}", input.label));
+    let (tokens, spans) = Lexer::new(&input.source).tokenize_spanned().map_err(|e| in_input(&e))?;
+    let model = Parser::with_spans(tokens.clone(), spans.clone())
+        .parse_with_warnings()
+        .map_err(|e| in_input(&e))?
+        .model;
+    if !model.imports.is_empty() {
+        return Err(in_input(&format!(
+            "imports {} file(s); only self-contained models can be united",
+            model.imports.len()
+        )));
+    }
+    let ranges = token_ranges(&input.source, tokens.clone(), &spans);
+    Ok(Parsed { chars: input.source.chars().collect(), tokens, ranges, model })
+}
+
+/// Unite `inputs` into one model called `name`, prefixing ids with each
+/// input's namespace when `namespace` is set.
+pub fn union_models(inputs: &[UnionInput], name: &str, namespace: bool) -> Result<ModelUnion, CompilerError> {
+    let parsed: Vec<Parsed> = inputs.iter().map(parse).collect::<Result<_, _>>()?;
+
+    let mut declared_in: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, input) in parsed.iter().enumerate() {
+        for declared in input.model.declarations.keys() {
+            declared_in.entry(declared).or_default().push(i);
+        }
+    }
+    let collisions: Vec<Collision> = declared_in
+        .iter()
+        .filter(|(_, at)| at.len() > 1)
+        .map(|(declared, at)| Collision {
+            name: declared.to_string(),
+            inputs: at.iter().map(|&i| inputs[i].label.clone()).collect(),
+        })
+        .collect();
+
+    let namespaces: Vec<String> = inputs.iter().map(|input| namespace_of(&input.label)).collect();
+    if namespace {
+        let mut seen = HashSet::new();
+        if let Some(shared) = namespaces.iter().find(|ns| !seen.insert(*ns)) {
+            return Err(CompilerError::Other(format!(
+                "two inputs share the namespace '{shared}'; rename one of the files"
+            )));
+        }
+    }
+
+    // The input a reference made in input `i` resolves to: its own
+    // declaration first, else the only other input declaring it. A dotted
+    // reference (`Component.port`) resolves by its first segment.
+    let owner = |i: usize, reference: &str| -> Option<usize> {
+        let root = reference.split('.').next().unwrap_or(reference);
+        let at = declared_in.get(root)?;
+        if at.contains(&i) {
+            Some(i)
+        } else if let [only] = at.as_slice() {
+            Some(*only)
+        } else {
+            None
+        }
+    };
+
+    let mut cross: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (i, input) in parsed.iter().enumerate() {
+        for trace in &input.model.traces {
+            let foreign = [&trace.from, &trace.to].into_iter().find_map(|end| owner(i, end).filter(|&j| j != i));
+            if let Some(j) = foreign {
+                *cross.entry((i, j)).or_default() += 1;
+            }
+        }
+    }
+    let cross_model_traces = cross
+        .into_iter()
+        .map(|((i, j), count)| CrossModelTraces { from: inputs[i].label.clone(), to: inputs[j].label.clone(), count })
+        .collect();
+
+    let labels: Vec<&str> = inputs.iter().map(|input| input.label.as_str()).collect();
+    let mut source = format!(
+        "// Union of {} by `arclang merge-models`\nmodel {} {{\n}}\n",
+        labels.join(", "),
+        quote(name)
+    );
+    for (i, input) in parsed.iter().enumerate() {
+        let dropped = header_ranges(&input.tokens, &input.ranges);
+        let mut edits: Vec<(Range<usize>, String)> = dropped.iter().map(|range| (range.clone(), String::new())).collect();
+        if namespace {
+            for (idx, token) in input.tokens.iter().enumerate() {
+                let range = &input.ranges[idx];
+                if dropped.iter().any(|d| d.contains(&range.start)) {
+                    continue;
+                }
+                let renamed = match token {
+                    Token::StringLiteral(value) => owner(i, value).map(|j| {
+                        let raw: String = input.chars[range.start + 1..range.end - 1].iter().collect();
+                        format!("\"{}{NAMESPACE_SEPARATOR}{raw}\"", namespaces[j])
+                    }),
+                    Token::Identifier(value) if input.tokens.get(idx + 1) != Some(&Token::Colon) => {
+                        owner(i, value).map(|j| quote(&format!("{}{NAMESPACE_SEPARATOR}{value}", namespaces[j])))
+                    }
+                    _ => None,
+                };
+                if let Some(renamed) = renamed {
+                    edits.push((range.clone(), renamed));
+                }
+            }
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+
+        source.push_str(&format!("\n// ---- {} ----\n", inputs[i].label));
+        let mut pos = 0;
+        for (range, replacement) in edits {
+            source.extend(&input.chars[pos..range.start]);
+            source.push_str(&replacement);
+            pos = range.end;
+        }
+        source.extend(&input.chars[pos..]);
+        source.push('\n');
+    }
+
+    Ok(ModelUnion { source: format_source(&source)?, collisions, cross_model_traces })
+}
+
+/// Char ranges of the model header to drop: `model X {`, its attributes and
+/// `metadata` block, and its closing brace. The blocks it holds stay, at
+/// top level.
+fn header_ranges(tokens: &[Token], ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+    if !matches!(tokens.first(), Some(Token::Model | Token::System)) {
+        return Vec::new();
+    }
+    // `model X {`
+    let header = ranges[0].start..ranges[2].end;
+    let mut dropped = vec![header];
+    let mut depth = 1;
+    let mut idx = 3;
+    while idx < tokens.len() {
+        let key_end = match &tokens[idx] {
+            _ if depth > 1 => None,
+            Token::Metadata => Some(idx),
+            Token::Identifier(attr) if attr == "attr" && tokens.get(idx + 2) == Some(&Token::Colon) => Some(idx + 2),
+            _ if tokens.get(idx + 1) == Some(&Token::Colon) => Some(idx + 1),
+            _ => None,
+        };
+        if let Some(key_end) = key_end {
+            // `metadata { ... }`, or `key: value` with a list or block value
+            let mut end = key_end + 1;
+            if matches!(tokens.get(end), Some(Token::LeftBrace | Token::LeftBracket)) {
+                end = matching_close(tokens, end);
+            }
+            if tokens.get(end + 1) == Some(&Token::Comma) {
+                end += 1;
+            }
+            dropped.push(ranges[idx].start..ranges[end].end);
+            idx = end + 1;
+            continue;
+        }
+        match tokens[idx] {
+            Token::LeftBrace | Token::LeftBracket => depth += 1,
+            Token::RightBrace if depth == 1 => {
+                dropped.push(ranges[idx].clone());
+                break;
+            }
+            Token::RightBrace | Token::RightBracket => depth -= 1,
+            _ => {}
+        }
+        idx += 1;
+    }
+    dropped
+}
+
+/// Index of the bracket closing the one at `open`.
+fn matching_close(tokens: &[Token], open: usize) -> usize {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::LeftBrace | Token::LeftBracket => depth += 1,
+            Token::RightBrace | Token::RightBracket => {
+                depth -= 1;
+                if depth == 0 {
+                    return idx;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const BRAKE: &str = r#"model Brake {
+  version: "1.0"
+  metadata { author: "brakes team" }
+
+  requirements system {
+    req "REQ-001" "Braking" { description: "Brake within 40 m" }
+  }
+}
+
+architecture logical {
+  // The controller
+  component "Controller" { id: "LC-001" }
+}
+
+trace "LC-001" satisfies "REQ-001" {}
+trace "LC-001" satisfies "REQ-009" { rationale: "shared bus" }
+"#;
+
+    const STEERING: &str = r#"model Steering {
+}
+
+requirements system {
+  req "REQ-009" "Bus" { description: "Share the CAN bus" }
+}
+
+architecture logical {
+  component "Controller" { id: "LC-101" }
+}
+
+trace "LC-101" satisfies "REQ-009" {}
+"#;
+
+    fn inputs() -> Vec<UnionInput> {
+        vec![
+            UnionInput { label: "models/brake.arc".to_string(), source: BRAKE.to_string() },
+            UnionInput { label: "models/steering.arc".to_string(), source: STEERING.to_string() },
+        ]
+    }
+
+    #[test]
+    fn colliding_names_are_reported() {
+        let union = union_models(&inputs(), "Vehicle", false).unwrap();
+
+        assert_eq!(
+            union.collisions,
+            vec![Collision {
+                name: "Controller".to_string(),
+                inputs: vec!["models/brake.arc".to_string(), "models/steering.arc".to_string()],
+            }]
+        );
+        assert_eq!(
+            union.cross_model_traces,
+            vec![CrossModelTraces {
+                from: "models/brake.arc".to_string(),
+                to: "models/steering.arc".to_string(),
+                count: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn namespaced_union_compiles_with_cross_model_traces() {
+        let union = union_models(&inputs(), "Vehicle", true).unwrap();
+
+        assert!(union.source.contains("model \"Vehicle\" {"), "{}", union.source);
+        assert!(!union.source.contains("version") && !union.source.contains("brakes team"), "{}", union.source);
+        assert!(union.source.contains("// The controller"), "{}", union.source);
+        assert!(union.source.contains(r#"trace "brake::LC-001" satisfies "steering::REQ-009""#), "{}", union.source);
+
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(&union.source)
+            .expect("the union compiles")
+            .semantic_model;
+        assert_eq!(model.requirements.len(), 2);
+        assert_eq!(model.components.len(), 2);
+        assert_eq!(model.traces.len(), 3);
+    }
+}
@@ -0,0 +1,98 @@
+//! Who owns what (`arclang info --ownership`), for review assignment and
+//! organisation dashboards.
+//!
+//! Requirements, components, functions, capabilities and actors are
+//! grouped by their `owner: "..."` attribute; elements without one are
+//! unassigned, listed last. An element is covered when a trace touches it,
+//! the same rule the model's traceability coverage applies to
+//! requirements.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::semantic::SemanticModel;
+
+/// Element kinds (see [`super::semantic::ELEMENT_KINDS`]) that carry an
+/// owner.
+const OWNED_KINDS: &[&str] = &["requirement", "component", "function", "capability", "actor"];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OwnerSummary {
+    /// `None` for unassigned elements.
+    pub owner: Option<String>,
+    /// Owned element ids per kind, sorted.
+    pub elements: BTreeMap<&'static str, Vec<String>>,
+    pub total: usize,
+    /// Percentage of the owned elements a trace touches.
+    pub coverage: f64,
+}
+
+/// One summary per owner, by name, then the unassigned elements if any.
+pub fn ownership_report(model: &SemanticModel) -> Vec<OwnerSummary> {
+    let mut by_owner: BTreeMap<Option<&str>, Vec<(&'static str, &str)>> = BTreeMap::new();
+    for element in model.all_elements.values() {
+        let Some(kind) = model.element_kind(&element.id).filter(|kind| OWNED_KINDS.contains(kind)) else {
+            continue;
+        };
+        by_owner.entry(element.owner.as_deref()).or_default().push((kind, &element.id));
+    }
+    // `None` sorts first; the unassigned go last.
+    let unassigned = by_owner.remove(&None);
+
+    let traced = |id: &str| model.traces.iter().any(|t| t.from == id || t.to == id);
+    by_owner
+        .into_iter()
+        .chain(unassigned.map(|elements| (None, elements)))
+        .map(|(owner, owned)| {
+            let covered = owned.iter().filter(|(_, id)| traced(id)).count();
+            let mut elements: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+            for (kind, id) in &owned {
+                elements.entry(kind).or_default().push(id.to_string());
+            }
+            for ids in elements.values_mut() {
+                ids.sort();
+            }
+            OwnerSummary {
+                owner: owner.map(str::to_string),
+                elements,
+                total: owned.len(),
+                coverage: covered as f64 * 100.0 / owned.len() as f64,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn elements_are_grouped_by_owner_with_the_unassigned_last() {
+        let source = r#"model "Brake" {
+}
+system_analysis "SA" {
+    requirement "REQ-001" { description: "Brake" owner: "chassis-team" }
+    requirement "REQ-002" { description: "Log" owner: "chassis-team" }
+    requirement "REQ-003" { description: "Stop" }
+}
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" owner: "alice" }
+}
+trace "LC-001" satisfies "REQ-001" {}
+"#;
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model;
+        let report = ownership_report(&model);
+
+        let owners: Vec<Option<&str>> = report.iter().map(|s| s.owner.as_deref()).collect();
+        assert_eq!(owners, vec![Some("alice"), Some("chassis-team"), None]);
+        assert_eq!(report[1].elements["requirement"], vec!["REQ-001", "REQ-002"]);
+        assert_eq!(report[1].coverage, 50.0);
+        assert_eq!(report[0].coverage, 100.0);
+        assert_eq!(report[2].elements["requirement"], vec!["REQ-003"]);
+        assert_eq!(report[2].total, 1);
+    }
+}
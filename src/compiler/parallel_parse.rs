@@ -0,0 +1,74 @@
+//! Parallel parsing of an import tree for full builds.
+//!
+//! Files are read and parsed a level of the tree at a time: the files one
+//! level imports do not depend on each other, so they are parsed together
+//! on a rayon pool, and every distinct file is parsed once. The parsed
+//! files are then merged exactly as a serial parse merges them, so the AST,
+//! its warnings and the first error reported do not depend on which thread
+//! finished first.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use super::{ast, Compiler, CompilerError, ParseStats, ParsedFile};
+
+/// Parse `path` and everything it imports on `num_threads` threads (0 is
+/// one per core), merged as [`Compiler::compile_file`] merges them.
+pub(crate) fn parse_import_tree(
+    path: &Path,
+    num_threads: usize,
+) -> Result<(ast::Model, Vec<String>, ParseStats), CompilerError> {
+    let started = Instant::now();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .map_err(|e| CompilerError::Other(format!("cannot start the parser threads: {e}")))?;
+
+    let mut parsed: HashMap<PathBuf, ParsedFile> = HashMap::new();
+    let mut serial = Duration::ZERO;
+    // An unresolvable root is reported by the merge below, as serially
+    let mut level: Vec<PathBuf> = path.canonicalize().into_iter().collect();
+    while !level.is_empty() {
+        let results: Vec<(ParsedFile, Duration)> = pool.install(|| {
+            level
+                .par_iter()
+                .map(|file| {
+                    let start = Instant::now();
+                    (Compiler::read_and_parse(file), start.elapsed())
+                })
+                .collect()
+        });
+
+        let mut next: Vec<PathBuf> = Vec::new();
+        for (file, (result, elapsed)) in level.iter().zip(results) {
+            serial += elapsed;
+            if let Ok((model, _)) = &result {
+                let dir = file.parent().unwrap_or(Path::new(""));
+                // Missing imports are left to the merge, which names the
+                // importing file
+                let imports = model.imports.iter().filter_map(|import| dir.join(import).canonicalize().ok());
+                for import in imports {
+                    if !parsed.contains_key(&import) && !level.contains(&import) && !next.contains(&import) {
+                        next.push(import);
+                    }
+                }
+            }
+            parsed.insert(file.clone(), result);
+        }
+        level = next;
+    }
+
+    let files = parsed.len();
+    let (model, warnings) = Compiler::assemble_imports(path, &mut Vec::new(), &mut |canonical| {
+        // A file imported twice is merged twice, as serially
+        if let Some(Ok(model)) = parsed.get(canonical) {
+            return Ok(model.clone());
+        }
+        parsed.remove(canonical).unwrap_or_else(|| Compiler::read_and_parse(canonical))
+    })?;
+    let stats = ParseStats { files, threads: pool.current_num_threads(), wall: started.elapsed(), serial };
+    Ok((model, warnings, stats))
+}
@@ -1,7 +1,11 @@
 use super::ast::*;
+use super::diagnostic::{Diagnostic, SourceSpan};
 use super::lexer::{Span, Token};
 use std::collections::HashMap;
 
+/// Trace link keywords that the lexer leaves as plain identifiers.
+const TRACE_KEYWORDS: &[&str] = &["derives_from", "verifies", "allocated_to", "depends_on"];
+
 /// Result of a parse, including non-fatal warnings (e.g. constructs that are
 /// syntactically accepted but not yet represented in the model).
 #[derive(Debug)]
@@ -17,6 +21,8 @@ pub struct Parser {
     warnings: Vec<String>,
     /// Traces declared inside architecture blocks; hoisted to the model level.
     pending_traces: Vec<Trace>,
+    /// Name token of each declared element, see `Model::declarations`.
+    declarations: HashMap<String, SourceSpan>,
 }
 
 impl Parser {
@@ -27,6 +33,7 @@ impl Parser {
             position: 0,
             warnings: Vec::new(),
             pending_traces: Vec::new(),
+            declarations: HashMap::new(),
         }
     }
 
@@ -37,16 +44,21 @@ impl Parser {
             position: 0,
             warnings: Vec::new(),
             pending_traces: Vec::new(),
+            declarations: HashMap::new(),
         }
     }
 
-    pub fn parse(self) -> Result<Model, String> {
+    pub fn parse(self) -> Result<Model, Diagnostic> {
         self.parse_with_warnings().map(|outcome| outcome.model)
     }
 
-    pub fn parse_with_warnings(mut self) -> Result<ParseOutcome, String> {
-        let mut model = self.parse_model_root()?;
+    /// Errors point at the token the parser stopped on.
+    pub fn parse_with_warnings(mut self) -> Result<ParseOutcome, Diagnostic> {
+        let mut model = self
+            .parse_model_root()
+            .map_err(|message| Diagnostic::at(message, self.source_span()))?;
         model.traces.append(&mut self.pending_traces);
+        model.declarations = self.declarations;
         Ok(ParseOutcome { model, warnings: self.warnings })
     }
 
@@ -70,7 +82,7 @@ impl Parser {
                     parsed.imports.splice(0..0, pre_imports);
                     return Ok(parsed);
                 }
-                Token::ImportKw if !self.peek_is_colon() => {
+                Token::ImportKw | Token::IncludeKw if !self.peek_is_colon() => {
                     model.imports.push(self.parse_import_decl()?);
                 }
                 Token::Architecture => {
@@ -134,12 +146,10 @@ impl Parser {
     }
 
     /// Build an error message carrying the current source position when available.
+    /// An error at the current token; `parse_with_warnings` attaches the
+    /// position.
     fn err(&self, msg: impl Into<String>) -> String {
-        let msg = msg.into();
-        match self.current_span() {
-            Some(span) => format!("{} at {}", msg, span),
-            None => msg,
-        }
+        msg.into()
     }
 
     /// Record a warning carrying the current source position when available.
@@ -166,6 +176,10 @@ impl Parser {
     fn current_span(&self) -> Option<Span> {
         self.spans.get(self.position).copied()
     }
+
+    fn source_span(&self) -> Option<SourceSpan> {
+        self.current_span().map(SourceSpan::from)
+    }
     
     fn parse_model_block(&mut self) -> Result<Model, String> {
         // Support both 'model' and 'system' keywords
@@ -270,7 +284,7 @@ impl Parser {
         // Continue parsing top-level blocks after the model block
         while !self.is_at_end() {
             match self.current() {
-                Token::ImportKw if !self.peek_is_colon() => {
+                Token::ImportKw | Token::IncludeKw if !self.peek_is_colon() => {
                     model.imports.push(self.parse_import_decl()?);
                 }
                 Token::Requirements => {
@@ -407,6 +421,20 @@ impl Parser {
             .ok_or_else(|| self.err(format!("Expected name (identifier or string), got {}", self.current())))
     }
 
+    /// The name of an element being declared, remembering where it is.
+    fn expect_declared_name(&mut self) -> Result<String, String> {
+        let span = self.source_span();
+        let name = self.expect_name()?;
+        self.declare(&name, span);
+        Ok(name)
+    }
+
+    fn declare(&mut self, name: &str, span: Option<SourceSpan>) {
+        if let Some(span) = span {
+            self.declarations.entry(name.to_string()).or_insert(span);
+        }
+    }
+
     fn peek_is_colon(&self) -> bool {
         matches!(self.tokens.get(self.position + 1), Some(Token::Colon))
     }
@@ -584,7 +612,7 @@ impl Parser {
     
     fn parse_operational_entity(&mut self) -> Result<OperationalEntity, String> {
         self.advance(); // Skip 'operational_entity' or 'entity'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut attributes = HashMap::new();
@@ -623,7 +651,7 @@ impl Parser {
     
     fn parse_actor(&mut self) -> Result<Actor, String> {
         self.expect(Token::Actor)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         
         Ok(Actor {
@@ -636,7 +664,7 @@ impl Parser {
     
     fn parse_operational_capability(&mut self) -> Result<OperationalCapability, String> {
         self.advance(); // Skip 'operational_capability'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
 
         let id = attributes
@@ -658,7 +686,7 @@ impl Parser {
     
     fn parse_operational_activity(&mut self) -> Result<OperationalActivity, String> {
         self.advance(); // Skip 'operational_activity'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         
         // Extract ID from attributes, or generate from name
@@ -778,7 +806,7 @@ impl Parser {
                     requirements.push(self.parse_requirement()?);
                 }
                 Token::Req => {
-                    requirements.push(self.parse_req_statement()?);
+                    requirements.extend(self.parse_req_statement()?);
                 }
                 Token::Function => {
                     functions.push(self.parse_system_function()?);
@@ -846,7 +874,7 @@ impl Parser {
     /// Parse: mission Name { id: "..." description: "..." }
     fn parse_mission(&mut self) -> Result<Mission, String> {
         self.expect(Token::Mission)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         let id = attributes
             .get("id")
@@ -860,7 +888,7 @@ impl Parser {
     /// Also used for LA `capability_realization` blocks.
     fn parse_capability(&mut self) -> Result<Capability, String> {
         self.advance(); // Skip 'capability' or 'capability_realization'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         let id = attributes
             .get("id")
@@ -880,19 +908,23 @@ impl Parser {
     }
 
     /// Parse: functional_chain Name { id: ... involves: ["F1", "FE1", "F2"] }
+    /// (`sequence:` is accepted for `involves:`).
     fn parse_functional_chain(&mut self) -> Result<FunctionalChain, String> {
         self.expect(Token::FunctionalChain)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         let id = attributes
             .get("id")
             .and_then(|v| v.as_string())
             .map(|s| s.to_string())
             .unwrap_or_else(|| format!("FC-{}", name.replace(' ', "_")));
-        let involves = Self::string_list(&attributes, "involves");
+        let mut involves = Self::string_list(&attributes, "involves");
+        if involves.is_empty() {
+            involves = Self::string_list(&attributes, "sequence");
+        }
         if involves.is_empty() {
             return Err(self.err(format!(
-                "functional_chain '{}' must involve at least one function (involves: [...])",
+                "functional_chain '{}' must involve at least one function (involves: [...] or sequence: [...])",
                 name
             )));
         }
@@ -955,7 +987,70 @@ impl Parser {
             .and_then(|v| v.as_string())
             .unwrap_or("Data")
             .to_string();
-        Ok(ComponentPort { name, direction, interface_type })
+        let required = attributes
+            .get("required")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        Ok(ComponentPort { name, direction, interface_type, required })
+    }
+
+    /// Parse a component interface, a port typed by the interface it
+    /// provides or requires:
+    /// `interface "Name" { direction: in|out|inout provides: "ISpeed" }`.
+    /// An interface that `requires` is a required port unless it says
+    /// `required: false`.
+    fn parse_component_interface(&mut self) -> Result<ComponentPort, String> {
+        self.expect(Token::Interface)?;
+        let name = self.expect_name()?;
+        self.expect(Token::LeftBrace)?;
+
+        let mut direction = None;
+        let mut attributes = HashMap::new();
+        while !self.check(&Token::RightBrace) && !self.is_at_end() {
+            match self.current() {
+                Token::Identifier(ref key) if key == "direction" => {
+                    self.advance();
+                    self.expect(Token::Colon)?;
+                    direction = Some(match self.current() {
+                        Token::In => PortDirection::In,
+                        Token::Out => PortDirection::Out,
+                        Token::Identifier(ref d) if d == "inout" => PortDirection::InOut,
+                        Token::StringLiteral(ref d) if d == "in" => PortDirection::In,
+                        Token::StringLiteral(ref d) if d == "out" => PortDirection::Out,
+                        Token::StringLiteral(ref d) if d == "inout" => PortDirection::InOut,
+                        _ => return Err(self.err("interface direction is 'in', 'out' or 'inout'")),
+                    });
+                    self.advance();
+                }
+                Token::Provides | Token::Requires => {
+                    let key = if self.check(&Token::Provides) { "provides" } else { "requires" };
+                    self.advance();
+                    self.expect(Token::Colon)?;
+                    let value = self.expect_name()?;
+                    attributes.insert(key.to_string(), AttributeValue::String(value));
+                }
+                _ => {
+                    let (key, value) = self.parse_attribute()?;
+                    attributes.insert(key, value);
+                }
+            }
+        }
+        self.expect(Token::RightBrace)?;
+
+        let direction = direction.ok_or_else(|| {
+            self.err(format!("interface '{}' needs a direction: in, out or inout", name))
+        })?;
+        let interface_type = attributes
+            .get("provides")
+            .or_else(|| attributes.get("requires"))
+            .and_then(|v| v.as_string())
+            .unwrap_or("Data")
+            .to_string();
+        let required = attributes
+            .get("required")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| attributes.contains_key("requires"));
+        Ok(ComponentPort { name, direction, interface_type, required })
     }
 
     /// Parse an UNORIENTED physical port: `port Name { ... }` (Arcadia).
@@ -1154,7 +1249,7 @@ impl Parser {
     /// Parse: test_case Name { verifies: ["REQ-1"] method: "test" description: "..." }
     fn parse_test_case(&mut self) -> Result<TestCase, String> {
         self.expect(Token::TestCase)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         let id = attributes
             .get("id")
@@ -1193,8 +1288,8 @@ impl Parser {
             .and_then(|v| v.as_string())
             .map(|s| s.to_string())
             .unwrap_or_else(|| name.clone());
-        // Every non-reserved attribute is a field: name -> type.
-        let fields = attributes
+        // Every non-reserved attribute is a field: name -> type, by name.
+        let mut fields: Vec<DataAttribute> = attributes
             .iter()
             .filter(|(k, _)| k.as_str() != "id" && k.as_str() != "description")
             .filter_map(|(k, v)| {
@@ -1206,6 +1301,7 @@ impl Parser {
                 })
             })
             .collect();
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
         Ok(ClassDef { id, name, fields, attributes })
     }
 
@@ -1301,7 +1397,7 @@ impl Parser {
 
     fn parse_external_actor(&mut self) -> Result<ExternalActor, String> {
         self.expect(Token::Actor)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
 
         let id = attributes
@@ -1398,7 +1494,7 @@ impl Parser {
         while !self.check(&Token::RightBrace) && !self.is_at_end() {
             match self.current() {
                 Token::Req => {
-                    requirements.push(self.parse_req_statement()?);
+                    requirements.extend(self.parse_req_statement()?);
                 }
                 Token::Requirement => {
                     requirements.push(self.parse_requirement()?);
@@ -1427,27 +1523,50 @@ impl Parser {
         })
     }
     
-    fn parse_req_statement(&mut self) -> Result<Requirement, String> {
+    /// `req "ID" "Title" { ... }`. A `req` nested inside another one is a
+    /// child requirement: the result is flattened, parent first, with each
+    /// child carrying `parent: "<parent id>"`.
+    fn parse_req_statement(&mut self) -> Result<Vec<Requirement>, String> {
         self.expect(Token::Req)?;
+        let id_span = self.source_span();
         let id = self.expect_identifier_or_string()?;
+        self.declare(&id, id_span);
         let title = if matches!(self.current(), Token::StringLiteral(_)) {
             self.expect_string()?
         } else {
             String::new()
         };
-        let mut attributes = self.parse_attributes_block()?;
+
+        self.expect(Token::LeftBrace)?;
+        let mut attributes = HashMap::new();
+        let mut children = Vec::new();
+        while !self.check(&Token::RightBrace) && !self.is_at_end() {
+            if self.check(&Token::Req) {
+                let mut nested = self.parse_req_statement()?;
+                nested[0]
+                    .attributes
+                    .insert("parent".to_string(), AttributeValue::String(id.clone()));
+                children.extend(nested);
+            } else {
+                let (key, value) = self.parse_attribute()?;
+                attributes.insert(key, value);
+            }
+        }
+        self.expect(Token::RightBrace)?;
         
         // Add title to attributes if provided
         if !title.is_empty() {
             attributes.insert("title".to_string(), AttributeValue::String(title));
         }
         
-        Ok(Requirement { id, attributes })
+        let mut requirements = vec![Requirement { id, attributes }];
+        requirements.extend(children);
+        Ok(requirements)
     }
     
     fn parse_requirement(&mut self) -> Result<Requirement, String> {
         self.expect(Token::Requirement)?;
-        let id = self.expect_name()?;
+        let id = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
 
         Ok(Requirement { id, attributes })
@@ -1455,7 +1574,7 @@ impl Parser {
     
     fn parse_system_function(&mut self) -> Result<SystemFunction, String> {
         self.advance(); // Skip 'system_function'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut attributes = HashMap::new();
@@ -1494,7 +1613,7 @@ impl Parser {
     
     fn parse_nested_function(&mut self) -> Result<SystemFunction, String> {
         self.advance(); // Skip 'function'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         
         Ok(SystemFunction {
@@ -1515,7 +1634,7 @@ impl Parser {
     
     fn parse_system_component(&mut self) -> Result<SystemComponent, String> {
         self.advance(); // Skip 'system_component'
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         
         Ok(SystemComponent { name, attributes })
@@ -1551,6 +1670,9 @@ impl Parser {
                 Token::Identifier(ref id) if id == "component_exchange" => {
                     component_exchanges.push(self.parse_named_component_exchange()?);
                 }
+                Token::Connect => {
+                    component_exchanges.push(self.parse_connect()?);
+                }
                 Token::CapabilityRealization | Token::Capability => {
                     capability_realizations.push(self.parse_capability()?);
                 }
@@ -1594,6 +1716,64 @@ impl Parser {
         })
     }
     
+    /// Parse a port connection: `connect Sensor.speed to Controller.speed`,
+    /// with an optional `{ label: "..." }` block. Endpoints holding digits
+    /// after a dash (`LC-001.out`) must be quoted.
+    fn parse_connect(&mut self) -> Result<ComponentExchange, String> {
+        self.expect(Token::Connect)?;
+        let from_port = self.expect_port_endpoint()?;
+        self.expect(Token::To)?;
+        let to_port = self.expect_port_endpoint()?;
+        let attributes = if self.check(&Token::LeftBrace) {
+            self.parse_attributes_block()?
+        } else {
+            HashMap::new()
+        };
+
+        let label = attributes.get("label")
+            .and_then(|v| v.as_string())
+            .map(|s| s.to_string());
+        let exchange_item = attributes.get("data_type")
+            .and_then(|v| v.as_string())
+            .unwrap_or("Data")
+            .to_string();
+
+        Ok(ComponentExchange { from_port, to_port, exchange_item, label })
+    }
+
+    /// A `Component.port` endpoint, quoted or bare (`COMP-A.portX`).
+    fn expect_port_endpoint(&mut self) -> Result<String, String> {
+        if let Token::StringLiteral(endpoint) = self.current() {
+            let endpoint = endpoint.clone();
+            self.advance();
+            return Ok(endpoint);
+        }
+        let mut endpoint = String::new();
+        loop {
+            match self.current() {
+                Token::Identifier(part) => endpoint.push_str(part),
+                Token::Minus => endpoint.push('-'),
+                Token::Dot => endpoint.push('.'),
+                Token::Number(_) => {
+                    return Err(self.err("quote connect endpoints whose ids contain numbers, e.g. connect \"LC-001.out\" to \"LC-002.in\""));
+                }
+                token => match token.keyword_text() {
+                    // Port names may be keywords: `Sensor.out`
+                    Some(word) if endpoint.ends_with('.') => endpoint.push_str(word),
+                    _ => break,
+                },
+            }
+            self.advance();
+        }
+        if endpoint.is_empty() {
+            return Err(self.err(format!("expected a Component.port endpoint, got {}", self.current())));
+        }
+        if !endpoint.contains('.') || endpoint.starts_with('.') || endpoint.ends_with('.') {
+            return Err(self.err(format!("expected a Component.port endpoint, got '{}'", endpoint)));
+        }
+        Ok(endpoint)
+    }
+
     fn parse_component_exchange(&mut self) -> Result<ComponentExchange, String> {
         self.expect(Token::Connection)?;
         
@@ -1701,7 +1881,7 @@ impl Parser {
 
     fn parse_logical_component(&mut self) -> Result<LogicalComponent, String> {
         self.expect(Token::Component)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut functions = Vec::new();
@@ -1729,6 +1909,9 @@ impl Parser {
                 Token::Port => {
                     ports.push(self.parse_component_port()?);
                 }
+                Token::Interface => {
+                    ports.push(self.parse_component_interface()?);
+                }
                 Token::Provides => {
                     // Parse provided interface: provides "InterfaceName" { protocol: "CAN" }
                     self.advance(); // skip 'provides'
@@ -1787,7 +1970,7 @@ impl Parser {
     
     fn parse_logical_function(&mut self) -> Result<LogicalFunction, String> {
         self.expect(Token::Function)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         // The body block is optional: `function "Name"` declares a bare function.
         let attributes = if self.check(&Token::LeftBrace) {
             self.parse_attributes_block()?
@@ -2050,7 +2233,7 @@ impl Parser {
     
     fn parse_physical_node(&mut self) -> Result<PhysicalNode, String> {
         self.expect(Token::Node)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut deployments = Vec::new();
@@ -2202,7 +2385,7 @@ impl Parser {
     
     fn parse_physical_component_as_node(&mut self) -> Result<PhysicalNode, String> {
         self.expect(Token::Component)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
 
         let mut attributes = HashMap::new();
         if self.check(&Token::LeftBrace) {
@@ -2341,7 +2524,7 @@ impl Parser {
     
     fn parse_epbs_system(&mut self) -> Result<EpbsSystem, String> {
         self.expect(Token::System)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut subsystems = Vec::new();
@@ -2373,7 +2556,7 @@ impl Parser {
     
     fn parse_epbs_subsystem(&mut self) -> Result<EpbsSubsystem, String> {
         self.expect(Token::Subsystem)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut items = Vec::new();
@@ -2412,7 +2595,7 @@ impl Parser {
     
     fn parse_epbs_item(&mut self) -> Result<EpbsItem, String> {
         self.expect(Token::Item)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         
         Ok(EpbsItem { name, attributes })
@@ -2424,6 +2607,7 @@ impl Parser {
         
         let mut hazards = Vec::new();
         let mut fmea = Vec::new();
+        let mut fmea_actions = Vec::new();
         let mut attributes = HashMap::new();
         
         while !self.check(&Token::RightBrace) && !self.is_at_end() {
@@ -2434,6 +2618,9 @@ impl Parser {
                 Token::Fmea => {
                     fmea.push(self.parse_fmea_entry()?);
                 }
+                Token::FmeaAction => {
+                    fmea_actions.push(self.parse_fmea_action()?);
+                }
                 _ if self.peek_is_colon() => {
                     let (key, value) = self.parse_attribute()?;
                     attributes.insert(key, value);
@@ -2449,13 +2636,14 @@ impl Parser {
         Ok(SafetyAnalysis {
             hazards,
             fmea,
+            fmea_actions,
             attributes,
         })
     }
     
     fn parse_hazard(&mut self) -> Result<Hazard, String> {
         self.expect(Token::Hazard)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         let attributes = self.parse_attributes_block()?;
         
         Ok(Hazard { name, attributes })
@@ -2468,24 +2656,56 @@ impl Parser {
         
         Ok(FmeaEntry { name, attributes })
     }
+
+    /// Parse: fmea_action { for: "FMEA-012" owner: "..." due: "2025-06-01" action: "..." }
+    fn parse_fmea_action(&mut self) -> Result<FmeaAction, String> {
+        self.expect(Token::FmeaAction)?;
+        let attributes = self.parse_attributes_block()?;
+        let entry = attributes
+            .get("for")
+            .and_then(|v| v.as_string())
+            .map(|s| s.to_string())
+            .ok_or_else(|| self.err("fmea_action must name the FMEA entry it mitigates (for: \"FMEA-...\")".to_string()))?;
+        if let Some(due) = attributes.get("due") {
+            if due.as_string().and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()).is_none() {
+                return Err(self.err(format!("fmea_action for '{}': due must be a date (YYYY-MM-DD)", entry)));
+            }
+        }
+        if let Some(status) = attributes.get("status").and_then(|v| v.as_string()) {
+            if !["open", "closed"].contains(&status.to_lowercase().as_str()) {
+                return Err(self.err(format!(
+                    "fmea_action for '{}': status '{}' is not one of open|closed",
+                    entry, status
+                )));
+            }
+        }
+        for key in ["occurrence", "detection"] {
+            if attributes.get(key).is_some_and(|v| v.as_number().is_none()) {
+                return Err(self.err(format!("fmea_action for '{}': {} must be a number", entry, key)));
+            }
+        }
+        Ok(FmeaAction { entry, attributes })
+    }
     
-    /// `import "relative/path.arc"` — path resolution happens in the
-    /// compiler (the parser has no filesystem access).
+    /// `import "relative/path.arc"` (or `include`) — path resolution
+    /// happens in the compiler (the parser has no filesystem access).
     fn parse_import_decl(&mut self) -> Result<String, String> {
-        self.expect(Token::ImportKw)?;
+        let keyword = self.current().clone();
+        self.advance();
         match self.current().clone() {
             Token::StringLiteral(path) => {
                 self.advance();
                 Ok(path)
             }
             other => Err(self.err(format!(
-                "import expects a quoted file path, got {}",
-                other
+                "{} expects a quoted file path, got {}",
+                keyword, other
             ))),
         }
     }
 
     fn parse_trace(&mut self) -> Result<Trace, String> {
+        let trace_span = self.source_span();
         self.expect(Token::Trace)?;
         
         // Support two syntaxes:
@@ -2517,15 +2737,20 @@ impl Parser {
                 to,
                 trace_type,
                 attributes,
+                from_span: trace_span.clone(),
+                to_span: trace_span,
             });
         }
         
         // Arrow or keyword syntax
+        let from_span = self.source_span();
         let from = self.expect_string()?;
         
         // Support both syntaxes:
         // 1. trace "X" satisfies "Y" { rationale: "..." }
         // 2. trace "X" -> "Y" { trace_type: "satisfies", rationale: "..." }
+        // The target follows the one-token arrow or link keyword
+        let to_span = self.spans.get(self.position + 1).copied().map(SourceSpan::from);
         let (trace_type, to) = if let Token::Arrow = self.current() {
             // Arrow syntax: read trace_type from attributes
             self.advance(); // skip '->'
@@ -2547,6 +2772,8 @@ impl Parser {
                 to,
                 trace_type,
                 attributes,
+                from_span,
+                to_span,
             });
         } else if let Token::Satisfies = self.current() {
             self.advance();
@@ -2563,6 +2790,19 @@ impl Parser {
         } else if let Token::Refines = self.current() {
             self.advance();
             ("refines".to_string(), self.expect_name()?)
+        } else if let Token::Identifier(keyword) = self.current() {
+            // Link keywords that are not reserved words (they double as
+            // attribute keys elsewhere, e.g. `allocated_to:`)
+            let keyword = keyword.clone();
+            if !TRACE_KEYWORDS.contains(&keyword.as_str()) {
+                return Err(self.err(format!(
+                    "Unknown trace type '{}' (expected one of: satisfies, implements, validates, realizes, refines, {})",
+                    keyword,
+                    TRACE_KEYWORDS.join(", ")
+                )));
+            }
+            self.advance();
+            (keyword, self.expect_name()?)
         } else {
             return Err(self.err("Expected trace type (satisfies, implements, validates, etc.) or arrow"));
         };
@@ -2578,6 +2818,8 @@ impl Parser {
             to,
             trace_type,
             attributes,
+            from_span,
+            to_span,
         })
     }
     
@@ -2595,6 +2837,23 @@ impl Parser {
     }
     
     fn parse_attribute(&mut self) -> Result<(String, AttributeValue), String> {
+        // `attr key: value` declares a custom attribute (an attribute named
+        // `attr` is still `attr: value`).
+        if matches!(self.current(), Token::Identifier(id) if id == "attr") && !self.peek_is_colon() {
+            self.advance();
+            let key = match self.current() {
+                Token::Identifier(id) => id.clone(),
+                token => match token.keyword_text() {
+                    Some(text) => text.to_string(),
+                    None => return Err(self.err(format!("Expected custom attribute key, got: {}", self.current()))),
+                },
+            };
+            self.advance();
+            self.expect(Token::Colon)?;
+            let value = self.parse_custom_value()?;
+            return Ok((format!("{CUSTOM_ATTRIBUTE_PREFIX}{key}"), value));
+        }
+
         // Accept both identifiers and reserved keywords as attribute keys
         let key = match self.current() {
             Token::Identifier(id) => {
@@ -2616,7 +2875,13 @@ impl Parser {
         };
         
         self.expect(Token::Colon)?;
+        let value_span = self.source_span();
         let value = self.parse_attribute_value()?;
+        // An explicit id is a declaration too (cross-file duplicates are
+        // detected on declarations).
+        if let ("id", AttributeValue::String(id)) = (key.as_str(), &value) {
+            self.declare(id, value_span);
+        }
         
         Ok((key, value))
     }
@@ -2650,6 +2915,36 @@ impl Parser {
         }
     }
     
+    /// A custom attribute value: string (a `"YYYY-MM-DD"` string is a
+    /// date), number, `true` / `false`, or a list of those.
+    fn parse_custom_value(&mut self) -> Result<AttributeValue, String> {
+        match self.current() {
+            Token::Identifier(id) if id == "true" || id == "false" => {
+                let value = id == "true";
+                self.advance();
+                Ok(AttributeValue::Boolean(value))
+            }
+            Token::LeftBracket => {
+                self.advance();
+                let mut list = Vec::new();
+                while !self.check(&Token::RightBracket) && !self.is_at_end() {
+                    list.push(self.parse_custom_value()?);
+                    if self.check(&Token::Comma) {
+                        self.advance();
+                    } else if !self.check(&Token::RightBracket) {
+                        return Err(self.err("Expected comma or closing bracket in list"));
+                    }
+                }
+                self.expect(Token::RightBracket)?;
+                Ok(AttributeValue::List(list))
+            }
+            Token::LeftBrace => Err(self.err(
+                "A custom attribute is a string, number, boolean, date or list, not a map",
+            )),
+            _ => self.parse_attribute_value(),
+        }
+    }
+
     fn parse_list(&mut self) -> Result<AttributeValue, String> {
         self.expect(Token::LeftBracket)?;
         let mut list = Vec::new();
@@ -2896,7 +3191,7 @@ impl Parser {
     
     fn parse_behavior_component(&mut self) -> Result<BehaviorComponent, String> {
         self.expect(Token::BehaviorComponent)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         self.expect(Token::LeftBrace)?;
         
         let mut attributes = HashMap::new();
@@ -2951,7 +3246,7 @@ impl Parser {
     
     fn parse_hardware_component(&mut self) -> Result<HardwareComponent, String> {
         self.expect(Token::HardwareComponent)?;
-        let name = self.expect_name()?;
+        let name = self.expect_declared_name()?;
         
         let mut hw_type = "Generic".to_string();
         let mut specs = None;
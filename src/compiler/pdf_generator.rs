@@ -0,0 +1,589 @@
+//! PDF report export.
+//!
+//! Renders the Markdown report produced by the `markdown` target into a
+//! PDF document, with no external tool and no font files: text is set in
+//! the standard Helvetica / Helvetica-Bold fonts every PDF reader ships,
+//! and line breaking uses their published glyph widths.
+//!
+//! Layout rules:
+//! - a title page carrying the model name,
+//! - every `##` section (Contents, Traceability Summary, Metrics,
+//!   Requirements, one per Arcadia layer, Traceability Matrix) starts on a
+//!   new page,
+//! - tables wrap their cells and repeat the header row on each page they
+//!   span,
+//! - a "Page n of N" footer on every page after the title page.
+//!
+//! Only the Markdown subset the report generator emits is understood:
+//! headings, `-` bullets (nested by two spaces), `|` tables and plain
+//! paragraphs. `**bold**` markers and `<a id>` anchors are dropped, and
+//! in-document links keep only their text.
+
+const PAGE_WIDTH: f32 = 595.0; // A4, in points
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const BODY_SIZE: f32 = 10.0;
+const TABLE_SIZE: f32 = 8.5;
+const CELL_PADDING: f32 = 3.0;
+
+/// Render `markdown` as a PDF whose title page shows `title`.
+pub fn generate_pdf(title: &str, markdown: &str) -> Vec<u8> {
+    let mut doc = Document::new();
+    doc.title_page(title);
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.starts_with('|') {
+            let start = i;
+            while i < lines.len() && lines[i].starts_with('|') {
+                i += 1;
+            }
+            doc.table(&lines[start..i]);
+            continue;
+        }
+
+        if line.starts_with("# ") {
+            // The document title lives on the title page.
+        } else if line.starts_with("<a id=") && plain_text(line).trim().is_empty() {
+            // An in-document anchor; links are followed only in Markdown.
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            doc.section(heading);
+        } else if let Some(heading) = line.strip_prefix("### ") {
+            doc.subheading(heading);
+        } else if line.trim_start().starts_with("- ") {
+            let depth = (line.len() - line.trim_start().len()) / 2;
+            doc.bullet(&plain_text(&line.trim_start()[2..]), depth);
+        } else if line.trim().is_empty() {
+            doc.gap(BODY_SIZE * 0.5);
+        } else {
+            doc.paragraph(&plain_text(line.trim()));
+        }
+        i += 1;
+    }
+
+    doc.finish(title)
+}
+
+/// `text` without `**` markers and `<a id="..."></a>` anchors, and with
+/// `[text](#anchor)` links reduced to their text.
+fn plain_text(text: &str) -> String {
+    let mut plain = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<a id=\"") {
+            if let Some(end) = after.find("</a>") {
+                rest = &after[end + "</a>".len()..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            if let Some((label, target)) = after.split_once("](#") {
+                if let Some(end) = target.find(')') {
+                    plain.push_str(label);
+                    rest = &target[end + 1..];
+                    continue;
+                }
+            }
+        }
+        let c = rest.chars().next().expect("rest is not empty");
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    plain.replace("**", "")
+}
+
+/// Split a Markdown table row into cells, honouring `\|` escapes.
+fn table_cells(row: &str) -> Vec<String> {
+    let inner = row.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(plain_text(std::mem::take(&mut cell).trim())),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(plain_text(cell.trim()));
+    cells
+}
+
+fn is_separator_row(row: &str) -> bool {
+    row.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// Advance widths (1/1000 em) of printable ASCII, from the Adobe AFM files.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, // 0..?
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, // P.._
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, // `..o
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, // p..~
+];
+
+fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let widths = match font {
+        Font::Regular => &HELVETICA_WIDTHS,
+        Font::Bold => &HELVETICA_BOLD_WIDTHS,
+    };
+    let units: u32 = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 32..=126 => widths[(code - 32) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Greedy word wrap; words wider than `width` are split.
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if text_width(&candidate, font, size) <= width {
+            current = candidate;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        for c in word.chars() {
+            current.push(c);
+            if text_width(&current, font, size) > width && current.chars().count() > 1 {
+                current.pop();
+                lines.push(std::mem::replace(&mut current, c.to_string()));
+            }
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Encode text as a PDF literal string in WinAnsiEncoding.
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            _ => b'?',
+        };
+        out.push(byte);
+    }
+    out.push(b')');
+    out
+}
+
+struct Document {
+    /// Finished page content streams.
+    pages: Vec<Vec<u8>>,
+    content: Vec<u8>,
+    /// Baseline cursor, measured from the bottom of the page.
+    y: f32,
+    /// Nothing has been drawn on the current page yet.
+    page_empty: bool,
+}
+
+impl Document {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            content: Vec::new(),
+            y: PAGE_HEIGHT - MARGIN,
+            page_empty: true,
+        }
+    }
+
+    fn content_width() -> f32 {
+        PAGE_WIDTH - 2.0 * MARGIN
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.content));
+        self.y = PAGE_HEIGHT - MARGIN;
+        self.page_empty = true;
+    }
+
+    /// Start a new page unless `height` still fits above the bottom margin.
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN && !self.page_empty {
+            self.new_page();
+        }
+    }
+
+    fn text(&mut self, x: f32, y: f32, text: &str, font: Font, size: f32) {
+        self.content
+            .extend_from_slice(format!("BT /{} {:.1} Tf {:.2} {:.2} Td ", font.resource(), size, x, y).as_bytes());
+        self.content.extend_from_slice(&pdf_string(text));
+        self.content.extend_from_slice(b" Tj ET\n");
+        self.page_empty = false;
+    }
+
+    fn rule(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.content
+            .extend_from_slice(format!("{x1:.2} {y1:.2} m {x2:.2} {y2:.2} l S\n").as_bytes());
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        self.content.extend_from_slice(
+            format!("{gray:.2} g {x:.2} {y:.2} {width:.2} {height:.2} re f 0 g\n").as_bytes(),
+        );
+    }
+
+    fn title_page(&mut self, title: &str) {
+        let mut y = PAGE_HEIGHT * 0.62;
+        for line in wrap(title, Font::Bold, 28.0, Self::content_width()) {
+            self.text(MARGIN, y, &line, Font::Bold, 28.0);
+            y -= 34.0;
+        }
+        self.rule(MARGIN, y + 16.0, PAGE_WIDTH - MARGIN, y + 16.0);
+        self.text(MARGIN, y - 8.0, "ArcLang Model Report", Font::Regular, 14.0);
+        self.text(
+            MARGIN,
+            MARGIN,
+            &format!("Generated by arclang {}", env!("CARGO_PKG_VERSION")),
+            Font::Regular,
+            9.0,
+        );
+        self.new_page();
+    }
+
+    fn section(&mut self, heading: &str) {
+        if !self.page_empty {
+            self.new_page();
+        }
+        self.y -= 16.0;
+        self.text(MARGIN, self.y, heading, Font::Bold, 16.0);
+        self.y -= 4.0;
+        self.rule(MARGIN, self.y, PAGE_WIDTH - MARGIN, self.y);
+        self.y -= 10.0;
+    }
+
+    fn subheading(&mut self, heading: &str) {
+        // Keep the heading with at least a few lines of what follows.
+        self.ensure_space(12.0 + 4.0 * BODY_SIZE * 1.3);
+        self.y -= 14.0;
+        self.text(MARGIN, self.y, heading, Font::Bold, 12.0);
+        self.y -= 4.0;
+    }
+
+    fn gap(&mut self, height: f32) {
+        if !self.page_empty {
+            self.y -= height;
+        }
+    }
+
+    fn write_lines(&mut self, x: f32, lines: &[String], font: Font, size: f32) {
+        let leading = size * 1.3;
+        for line in lines {
+            self.ensure_space(leading);
+            self.y -= leading;
+            self.text(x, self.y, line, font, size);
+        }
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        let lines = wrap(text, Font::Regular, BODY_SIZE, Self::content_width());
+        self.write_lines(MARGIN, &lines, Font::Regular, BODY_SIZE);
+    }
+
+    fn bullet(&mut self, text: &str, depth: usize) {
+        let indent = MARGIN + 12.0 + depth as f32 * 14.0;
+        let lines = wrap(text, Font::Regular, BODY_SIZE, PAGE_WIDTH - MARGIN - indent);
+        self.ensure_space(BODY_SIZE * 1.3);
+        let marker = if depth == 0 { "•" } else { "–" };
+        self.text(indent - 9.0, self.y - BODY_SIZE * 1.3, marker, Font::Regular, BODY_SIZE);
+        self.write_lines(indent, &lines, Font::Regular, BODY_SIZE);
+    }
+
+    fn table(&mut self, rows: &[&str]) {
+        let mut rows: Vec<Vec<String>> = rows
+            .iter()
+            .filter(|row| !is_separator_row(row))
+            .map(|row| table_cells(row))
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        let header = rows.remove(0);
+        let columns = header.len();
+        for row in &mut rows {
+            row.resize(columns, String::new());
+        }
+
+        let widths = column_widths(&header, &rows, Self::content_width());
+        let leading = TABLE_SIZE * 1.25;
+        let layout = |cells: &[String], font: Font| -> (Vec<Vec<String>>, f32) {
+            let wrapped: Vec<Vec<String>> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| wrap(cell, font, TABLE_SIZE, width - 2.0 * CELL_PADDING))
+                .collect();
+            let height = wrapped.iter().map(Vec::len).max().unwrap_or(1) as f32 * leading
+                + 2.0 * CELL_PADDING;
+            (wrapped, height)
+        };
+
+        let (header_cells, header_height) = layout(&header, Font::Bold);
+        self.gap(4.0);
+        self.ensure_space(header_height + leading + 2.0 * CELL_PADDING);
+        self.table_row(&header_cells, &widths, header_height, true);
+        for row in &rows {
+            let (cells, height) = layout(row, Font::Regular);
+            if self.y - height < MARGIN {
+                self.new_page();
+                self.table_row(&header_cells, &widths, header_height, true);
+            }
+            self.table_row(&cells, &widths, height, false);
+        }
+        self.gap(6.0);
+    }
+
+    fn table_row(&mut self, cells: &[Vec<String>], widths: &[f32], height: f32, header: bool) {
+        let top = self.y;
+        let bottom = top - height;
+        let total: f32 = widths.iter().sum();
+        if header {
+            self.fill_rect(MARGIN, bottom, total, height, 0.88);
+        }
+        let font = if header { Font::Bold } else { Font::Regular };
+        let leading = TABLE_SIZE * 1.25;
+        let mut x = MARGIN;
+        for (lines, width) in cells.iter().zip(widths) {
+            let mut baseline = top - CELL_PADDING - TABLE_SIZE;
+            for line in lines {
+                self.text(x + CELL_PADDING, baseline, line, font, TABLE_SIZE);
+                baseline -= leading;
+            }
+            x += width;
+        }
+        self.rule(MARGIN, bottom, MARGIN + total, bottom);
+        if header {
+            self.rule(MARGIN, top, MARGIN + total, top);
+        }
+        self.y = bottom;
+    }
+
+    /// Draw footers and serialize pages, fonts and the cross-reference
+    /// table into the final file.
+    fn finish(mut self, title: &str) -> Vec<u8> {
+        if !self.page_empty || self.pages.is_empty() {
+            self.new_page();
+        }
+        let total = self.pages.len();
+        for (index, page) in self.pages.iter_mut().enumerate().skip(1) {
+            let footer = format!("Page {} of {}", index + 1, total);
+            let x = PAGE_WIDTH - MARGIN - text_width(&footer, Font::Regular, 8.0);
+            page.extend_from_slice(format!("BT /F1 8.0 Tf {:.2} {:.2} Td ", x, MARGIN / 2.0).as_bytes());
+            page.extend_from_slice(&pdf_string(&footer));
+            page.extend_from_slice(b" Tj ET\n");
+        }
+
+        // Object numbers: 1 catalog, 2 page tree, 3-4 fonts, 5 info, then
+        // a (page, content) pair per page.
+        let page_ids: Vec<usize> = (0..total).map(|i| 6 + 2 * i).collect();
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+        objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), total).into_bytes());
+        for base in ["Helvetica", "Helvetica-Bold"] {
+            objects.push(
+                format!("<< /Type /Font /Subtype /Type1 /BaseFont /{base} /Encoding /WinAnsiEncoding >>")
+                    .into_bytes(),
+            );
+        }
+        let mut info = b"<< /Title ".to_vec();
+        info.extend_from_slice(&pdf_string(title));
+        info.extend_from_slice(b" /Producer (arclang) >>");
+        objects.push(info);
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    id + 1
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.len()).into_bytes();
+            stream.extend_from_slice(page);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = pdf.len();
+        pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+}
+
+/// Share the available width between columns: each column first gets
+/// room for its longest word (so ids are not split), the rest goes to the
+/// columns with the most text.
+fn column_widths(header: &[String], rows: &[Vec<String>], available: f32) -> Vec<f32> {
+    let cells = |col: usize| {
+        std::iter::once((&header[col], Font::Bold)).chain(rows.iter().map(move |row| (&row[col], Font::Regular)))
+    };
+    let natural: Vec<f32> = (0..header.len())
+        .map(|col| {
+            cells(col)
+                .map(|(cell, font)| text_width(cell, font, TABLE_SIZE))
+                .fold(0.0, f32::max)
+                + 2.0 * CELL_PADDING
+        })
+        .collect();
+    let total: f32 = natural.iter().sum();
+    if total <= available {
+        return natural;
+    }
+
+    let minimum: Vec<f32> = (0..header.len())
+        .map(|col| {
+            cells(col)
+                .flat_map(|(cell, font)| cell.split_whitespace().map(move |word| text_width(word, font, TABLE_SIZE)))
+                .fold(0.0, f32::max)
+                + 2.0 * CELL_PADDING
+        })
+        .collect();
+    let minimum_total: f32 = minimum.iter().sum();
+    if minimum_total >= available {
+        return minimum.iter().map(|w| w * available / minimum_total).collect();
+    }
+    let spare = available - minimum_total;
+    let flexible: f32 = total - minimum_total;
+    natural
+        .iter()
+        .zip(&minimum)
+        .map(|(natural, minimum)| minimum + (natural - minimum) / flexible * spare)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack.windows(needle.len()).filter(|w| *w == needle).count()
+    }
+
+    #[test]
+    fn produces_a_well_formed_document() {
+        let pdf = generate_pdf("Demo (v1)", "# ArcLang Model Report\n\n## Metrics\n\n- Requirements: 1\n");
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert_eq!(count(&pdf, b"/Type /Page "), 2, "title page + one section");
+        assert_eq!(count(&pdf, b"(Demo \\(v1\\)) Tj"), 1);
+
+        // startxref must point at the xref table.
+        let text = String::from_utf8_lossy(&pdf);
+        let offset: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        assert!(pdf[offset..].starts_with(b"xref"));
+    }
+
+    #[test]
+    fn layers_start_on_new_pages() {
+        let markdown = "## Operational Analysis\n\n### Driver\n\n## Logical Architecture\n\n### Controller\n";
+        let pdf = generate_pdf("Demo", markdown);
+        assert_eq!(count(&pdf, b"/Type /Page "), 3);
+    }
+
+    #[test]
+    fn long_tables_repeat_their_header_row() {
+        let mut markdown = String::from("## Traceability Matrix\n\n| From | To | Type | Rationale |\n|---|---|---|---|\n");
+        for i in 0..200 {
+            markdown.push_str(&format!("| LC-{i:03} | REQ-{i:03} | satisfies | a \\| b |\n"));
+        }
+        let pdf = generate_pdf("Demo", &markdown);
+        let pages = count(&pdf, b"/Type /Page ") - 1;
+        assert!(pages > 1, "200 rows must span several pages");
+        assert_eq!(count(&pdf, b"(Rationale) Tj"), pages);
+        assert!(count(&pdf, b"(a | b) Tj") == 200, "escaped pipes stay in the cell");
+    }
+
+    #[test]
+    fn anchors_and_links_render_as_plain_text() {
+        assert_eq!(plain_text("<a id=\"req-001\"></a>REQ-001"), "REQ-001");
+        assert_eq!(plain_text("[LC-001](#lc-001) (satisfies), [x] y"), "LC-001 (satisfies), [x] y");
+        let markdown = "## Requirements\n\n<a id=\"lc-001\"></a>\n\n- **Traced from**: [REQ-001](#req-001)\n";
+        let pdf = generate_pdf("Demo", markdown);
+        assert_eq!(count(&pdf, b"(Traced from: REQ-001) Tj"), 1);
+        assert_eq!(count(&pdf, b"lc-001"), 0);
+    }
+}
@@ -1,15 +1,19 @@
+use super::ast::PortDirection;
+use super::html_report::html_escape;
 use super::semantic::SemanticModel;
 use super::CompilerError;
 use std::collections::HashMap;
 
 pub struct PlantUMLGenerator {
     diagram_type: String,
+    title: Option<String>,
 }
 
 impl PlantUMLGenerator {
     pub fn new() -> Self {
         Self {
             diagram_type: "component".to_string(),
+            title: None,
         }
     }
     
@@ -17,12 +21,21 @@ impl PlantUMLGenerator {
         self.diagram_type = diagram_type;
         self
     }
+
+    pub fn with_title(mut self, title: String) -> Self {
+        self.title = Some(title);
+        self
+    }
     
     pub fn generate(&self, model: &SemanticModel) -> Result<String, CompilerError> {
         let mut output = String::new();
         
         output.push_str("@startuml\n");
-        output.push_str("!theme plain\n\n");
+        output.push_str("!theme plain\n");
+        if let Some(title) = &self.title {
+            output.push_str(&format!("title {}\n", title.replace('\n', " ")));
+        }
+        output.push('\n');
         
         if self.diagram_type == "component" {
             self.generate_component_diagram(&mut output, model)?;
@@ -47,7 +60,26 @@ impl PlantUMLGenerator {
             output.push_str(&format!("package \"{}\" {{\n", package_name));
             
             for comp in components {
-                output.push_str(&format!("  [{}]\n", comp.name));
+                let ports: Vec<_> = model.ports.iter().filter(|p| p.component == comp.id).collect();
+                if ports.is_empty() {
+                    output.push_str(&format!("  [{}]\n", comp.name));
+                    continue;
+                }
+                output.push_str(&format!("  component [{}] as {} {{\n", comp.name, alias(&comp.id)));
+                for port in ports {
+                    let keyword = match port.direction {
+                        PortDirection::In => "portin",
+                        PortDirection::Out => "portout",
+                        PortDirection::InOut => "port",
+                    };
+                    output.push_str(&format!(
+                        "    {} \"{}\" as {}\n",
+                        keyword,
+                        port.name,
+                        port_alias(&port.component, &port.name)
+                    ));
+                }
+                output.push_str("  }\n");
             }
             
             output.push_str("}\n\n");
@@ -64,6 +96,21 @@ impl PlantUMLGenerator {
             }
         }
         
+        for connection in &model.connections {
+            let label = connection.label.clone().unwrap_or_else(|| {
+                model
+                    .get_port(&connection.from_component, &connection.from_port)
+                    .map(|p| p.interface.clone())
+                    .unwrap_or_default()
+            });
+            output.push_str(&format!(
+                "{} --> {} : {}\n",
+                port_alias(&connection.from_component, &connection.from_port),
+                port_alias(&connection.to_component, &connection.to_port),
+                label
+            ));
+        }
+        
         Ok(())
     }
     
@@ -88,9 +135,19 @@ impl PlantUMLGenerator {
     }
 }
 
-pub fn generate_plantuml_component(model: &SemanticModel) -> Result<String, CompilerError> {
+/// A PlantUML alias for an element id: letters, digits and `_` only.
+fn alias(id: &str) -> String {
+    id.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+fn port_alias(component: &str, port: &str) -> String {
+    format!("{}__{}", alias(component), alias(port))
+}
+
+pub fn generate_plantuml_component(model: &SemanticModel, title: &str) -> Result<String, CompilerError> {
     PlantUMLGenerator::new()
         .with_type("component".to_string())
+        .with_title(title.to_string())
         .generate(model)
 }
 
@@ -99,3 +156,67 @@ pub fn generate_plantuml_activity(model: &SemanticModel) -> Result<String, Compi
         .with_type("activity".to_string())
         .generate(model)
 }
+
+/// Public PlantUML server rendering a diagram passed in the URL.
+pub const PLANTUML_SERVER: &str = "https://www.plantuml.com/plantuml";
+
+/// SVG URL of `source` on the PlantUML server, hex-encoded (`~h`) so no
+/// compression is needed.
+pub fn plantuml_svg_url(source: &str) -> String {
+    let hex: String = source.bytes().map(|b| format!("{:02x}", b)).collect();
+    format!("{}/svg/~h{}", PLANTUML_SERVER, hex)
+}
+
+/// An HTML page showing `source` rendered by the PlantUML server, with the
+/// source itself below it so the page still reads offline.
+pub fn plantuml_html_page(source: &str, title: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <style>{css}        pre {{ background: #f5f5f5; padding: 12px; overflow: auto; }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>{title}</h1>
+        <img src="{url}" alt="{title}">
+        <details>
+            <summary>PlantUML source</summary>
+            <pre>{source}</pre>
+        </details>
+    </div>
+</body>
+</html>
+"#,
+        title = html_escape(title),
+        css = super::html_report::MERMAID_PAGE_CSS,
+        url = plantuml_svg_url(source),
+        source = html_escape(source),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn titled_component_diagram_and_html_page() {
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string("model Demo {\n}\n\narchitecture logical {\n  component \"Controller\" { id: \"LC-001\" }\n}\n")
+            .expect("compiles")
+            .semantic_model;
+
+        let uml = generate_plantuml_component(&model, "Brake <System>").unwrap();
+        assert!(uml.starts_with("@startuml\n!theme plain\ntitle Brake <System>\n"), "{}", uml);
+        assert!(uml.contains("[Controller]"));
+
+        let html = plantuml_html_page(&uml, "Brake <System>");
+        assert!(html.contains("<title>Brake &lt;System&gt;</title>"));
+        assert!(html.contains(&format!("<img src=\"{}/svg/~h40737461727475", PLANTUML_SERVER)));
+        assert!(html.contains("title Brake &lt;System&gt;"));
+    }
+}
@@ -0,0 +1,161 @@
+//! Wall time per compiler phase, for `arclang build --profile`. With
+//! `--profile-json FILE` the same profile is written as one JSON object, for
+//! CI jobs that track compile times across builds:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "phases": [ { "phase": "parse", "ms": 1.8 }, { "phase": "semantic analysis", "ms": 0.9 }, ... ],
+//!   "total_ms": 3.4,
+//!   "elements": { "total": 12, "requirements": 4, "components": 6, "functions": 2, "traces": 5 },
+//!   "cache": { "hit_ratio": 0.5, "compiled": 1, "cached": 1 }
+//! }
+//! ```
+//!
+//! `cache` is only present for incremental builds, whose phases include
+//! loading and saving the cache. Unprofiled builds carry no [`Profile`], and
+//! a [`PhaseTimer`] without one does not read the clock. Adding a field
+//! keeps [`SCHEMA_VERSION`]; renaming or removing one bumps it.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::semantic::SemanticModel;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Profile {
+    pub schema_version: u32,
+    /// In the order they ran; a phase run twice is listed twice.
+    pub phases: Vec<PhaseTime>,
+    /// The largest element counts of a model the build held.
+    pub elements: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheProfile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseTime {
+    pub phase: String,
+    pub ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheProfile {
+    pub hit_ratio: f64,
+    pub compiled: usize,
+    pub cached: usize,
+}
+
+/// Records the time from [`PhaseTimer::start`] to its drop as a phase of
+/// the profile, if there is one.
+pub struct PhaseTimer<'a> {
+    running: Option<(&'a mut Profile, Instant)>,
+    phase: &'static str,
+}
+
+impl<'a> PhaseTimer<'a> {
+    pub fn start(profile: Option<&'a mut Profile>, phase: &'static str) -> Self {
+        Self { running: profile.map(|profile| (profile, Instant::now())), phase }
+    }
+}
+
+impl Drop for PhaseTimer<'_> {
+    fn drop(&mut self) {
+        if let Some((profile, started)) = self.running.take() {
+            profile.record(self.phase, started.elapsed());
+        }
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self { schema_version: SCHEMA_VERSION, phases: Vec::new(), elements: BTreeMap::new(), cache: None }
+    }
+}
+
+impl Profile {
+    pub fn record(&mut self, phase: &str, elapsed: Duration) {
+        self.phases.push(PhaseTime { phase: phase.to_string(), ms: elapsed.as_secs_f64() * 1000.0 });
+    }
+
+    /// Keep the element counts of `model` where they exceed those seen.
+    pub fn count_elements(&mut self, model: &SemanticModel) {
+        let metrics = model.compute_metrics();
+        let counts = [
+            ("total", metrics.total_elements),
+            ("requirements", metrics.requirements_count),
+            ("components", metrics.components_count),
+            ("functions", metrics.functions_count),
+            ("traces", metrics.traces_count),
+        ];
+        for (kind, count) in counts {
+            let peak = self.elements.entry(kind.to_string()).or_insert(0);
+            *peak = (*peak).max(count);
+        }
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.phases.iter().map(|p| p.ms).sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut value = serde_json::to_value(self).expect("profile serializes");
+        value["total_ms"] = self.total_ms().into();
+        serde_json::to_string_pretty(&value).expect("profile serializes")
+    }
+
+    /// One line per phase with its share of the total, then the counts.
+    pub fn render(&self) -> String {
+        let total = self.total_ms();
+        let width = self.phases.iter().map(|p| p.phase.len()).max().unwrap_or(0).max("total".len());
+        let mut out = String::from("Profile:\n");
+        for phase in &self.phases {
+            let share = if total > 0.0 { phase.ms / total * 100.0 } else { 0.0 };
+            out.push_str(&format!("  {:<width$}  {:>9.2} ms  {:>5.1}%\n", phase.phase, phase.ms, share));
+        }
+        out.push_str(&format!("  {:<width$}  {:>9.2} ms\n", "total", total));
+        if !self.elements.is_empty() {
+            let count = |kind: &str| self.elements.get(kind).copied().unwrap_or(0);
+            out.push_str(&format!(
+                "  Elements: {} ({} requirements, {} components, {} functions, {} traces)\n",
+                count("total"), count("requirements"), count("components"), count("functions"), count("traces")
+            ));
+        }
+        if let Some(cache) = &self.cache {
+            out.push_str(&format!(
+                "  Cache: {:.0}% hits ({} compiled, {} cached)\n",
+                cache.hit_ratio * 100.0, cache.compiled, cache.cached
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timers_record_phases_in_order_only_into_a_profile() {
+        let mut profile = Profile::default();
+        {
+            let _timer = PhaseTimer::start(Some(&mut profile), "parse");
+        }
+        drop(PhaseTimer::start(None, "ignored"));
+        profile.record("codegen", Duration::from_millis(3));
+
+        let phases: Vec<_> = profile.phases.iter().map(|p| p.phase.as_str()).collect();
+        assert_eq!(phases, ["parse", "codegen"]);
+        assert!(profile.total_ms() >= 3.0);
+
+        let json: serde_json::Value = serde_json::from_str(&profile.to_json()).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["phases"][1]["ms"], 3.0);
+        assert!(json.get("cache").is_none());
+        assert!(profile.render().contains("codegen"));
+    }
+}
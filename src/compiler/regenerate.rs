@@ -0,0 +1,232 @@
+//! ArcLang source regenerated from a semantic model, for the imports that
+//! carry one (YAML, XML). [`ArcCodeGenerator`] re-emits requirements,
+//! operational actors, system and logical components and traces, each
+//! under its original id; anything else is reported as a warning.
+
+use super::ast::{
+    Actor, AttributeValue, CUSTOM_ATTRIBUTE_PREFIX, LogicalArchitecture, LogicalComponent, Model, OperationalAnalysis, Requirement,
+    SystemAnalysis, SystemComponent, Trace,
+};
+use super::capella_importer::ArcCodeGenerator;
+use super::semantic::{ComponentInfo, SemanticModel, TraceLinkType};
+use super::CompilerError;
+use std::collections::{HashMap, HashSet};
+
+/// Regenerated source, and what the import could not carry over.
+#[derive(Debug)]
+pub struct ImportedSource {
+    pub source: String,
+    pub warnings: Vec<String>,
+}
+
+/// Regenerate `model` as ArcLang source; `warnings` come first (what the
+/// import format itself dropped).
+pub fn regenerate_source(model: &SemanticModel, mut warnings: Vec<String>) -> Result<ImportedSource, CompilerError> {
+    let ast = to_ast(model, &mut warnings);
+    let source = ArcCodeGenerator::new().generate(&ast)?;
+    Ok(ImportedSource { source, warnings })
+}
+
+/// The declarations `ArcCodeGenerator` re-emits. Elements not rebuilt here
+/// (entities, activities, physical nodes, functions, ...) are reported.
+fn to_ast(model: &SemanticModel, warnings: &mut Vec<String>) -> Model {
+    let mut actors = Vec::new();
+    let mut system_components = Vec::new();
+    let mut logical_components = Vec::new();
+    let mut skipped = Vec::new();
+    for component in &model.components {
+        let registry_type = model.all_elements.get(&component.id).map(|e| e.element_type.as_str());
+        match component.level.as_str() {
+            "Operational" if registry_type == Some("Actor") => actors.push(Actor {
+                name: component.name.clone(),
+                id: Some(component.id.clone()),
+                icon: String::new(),
+                attributes: with_custom(component_attributes(component, "category"), model, &component.id),
+            }),
+            "System" => system_components.push(SystemComponent {
+                name: component.name.clone(),
+                attributes: with_custom(with_id(component_attributes(component, "type"), &component.id), model, &component.id),
+            }),
+            "Logical" => logical_components.push(LogicalComponent {
+                id: component.id.clone(),
+                name: component.name.clone(),
+                component_type: component.component_type.clone(),
+                color: None,
+                sub_components: Vec::new(),
+                allocated_functions: Vec::new(),
+                ports: Vec::new(),
+                functions: Vec::new(),
+                interfaces_in: Vec::new(),
+                interfaces_out: Vec::new(),
+                attributes: with_custom(with_id(component_attributes(component, "type"), &component.id), model, &component.id),
+            }),
+            _ => skipped.push(component.id.as_str()),
+        }
+    }
+    if !skipped.is_empty() {
+        warnings.push(format!(
+            "{} element(s) with no regenerated form left out: {}",
+            skipped.len(),
+            skipped.join(", ")
+        ));
+    }
+    let unsupported = [
+        ("function", model.functions.len()),
+        ("interface", model.interfaces.len()),
+        ("capability", model.capabilities.len()),
+        ("functional chain", model.functional_chains.len()),
+        ("hazard", model.hazards.len()),
+        ("deployment", model.deployments.len()),
+    ];
+    for (kind, count) in unsupported.iter().filter(|(_, count)| *count > 0) {
+        warnings.push(format!("{count} {kind}(s) not regenerated"));
+    }
+
+    let requirements = model
+        .requirements
+        .iter()
+        .map(|req| {
+            let mut attributes = HashMap::new();
+            attributes.insert("description".to_string(), AttributeValue::String(req.description.clone()));
+            attributes.insert("priority".to_string(), AttributeValue::String(req.priority.clone()));
+            let optional = [
+                ("title", &req.title),
+                ("category", &req.category),
+                ("safety_level", &req.safety_level),
+                ("hazard", &req.hazard),
+            ];
+            for (key, value) in optional {
+                if let Some(value) = value {
+                    attributes.insert(key.to_string(), AttributeValue::String(value.clone()));
+                }
+            }
+            if let Some(method) = req.verification_method {
+                attributes.insert("verification".to_string(), AttributeValue::String(method.as_str().to_string()));
+            }
+            if let Some(status) = req.verification_status {
+                attributes.insert("verification_status".to_string(), AttributeValue::String(status.as_str().to_string()));
+            }
+            Requirement { id: req.id.clone(), attributes: with_custom(attributes, model, &req.id) }
+        })
+        .collect::<Vec<_>>();
+
+    let mut ast = Model::new();
+    if !actors.is_empty() {
+        ast.operational_analysis.push(OperationalAnalysis {
+            name: "Operational Analysis".to_string(),
+            actors,
+            entities: Vec::new(),
+            capabilities: Vec::new(),
+            activities: Vec::new(),
+            exchanges: Vec::new(),
+            capability_associations: Vec::new(),
+            traces: Vec::new(),
+            processes: Vec::new(),
+            communication_means: Vec::new(),
+        });
+    }
+    if !requirements.is_empty() || !system_components.is_empty() {
+        ast.system_analysis.push(SystemAnalysis {
+            name: "System Analysis".to_string(),
+            requirements,
+            functions: Vec::new(),
+            components: system_components,
+            external_actors: Vec::new(),
+            functional_exchanges: Vec::new(),
+            missions: Vec::new(),
+            capabilities: Vec::new(),
+            functional_chains: Vec::new(),
+        });
+    }
+    if !logical_components.is_empty() {
+        ast.logical_architecture.push(LogicalArchitecture {
+            name: "Logical Architecture".to_string(),
+            components: logical_components,
+            interfaces: Vec::new(),
+            component_exchanges: Vec::new(),
+            unallocated_functions: Vec::new(),
+            capability_realizations: Vec::new(),
+            functional_chains: Vec::new(),
+        });
+    }
+    // Operational links (`interacts`, ...) have no `trace` keyword, and a
+    // trace must join two regenerated elements
+    let regenerated: HashSet<&str> = model
+        .requirements
+        .iter()
+        .map(|r| r.id.as_str())
+        .chain(model.components.iter().map(|c| c.id.as_str()))
+        .filter(|id| !skipped.contains(id))
+        .collect();
+    let (traces, links): (Vec<_>, Vec<_>) = model
+        .traces
+        .iter()
+        .partition(|trace| TraceLinkType::from_trace_type(&trace.trace_type) != TraceLinkType::Traces);
+    if !links.is_empty() {
+        warnings.push(format!("{} operational link(s) not regenerated", links.len()));
+    }
+    let (traces, dangling): (Vec<_>, Vec<_>) = traces
+        .into_iter()
+        .partition(|trace| regenerated.contains(trace.from.as_str()) && regenerated.contains(trace.to.as_str()));
+    if !dangling.is_empty() {
+        warnings.push(format!("{} trace(s) to elements left out not regenerated", dangling.len()));
+    }
+    ast.traces = traces
+        .into_iter()
+        .map(|trace| {
+            let mut attributes = HashMap::new();
+            if let Some(rationale) = &trace.rationale {
+                attributes.insert("rationale".to_string(), AttributeValue::String(rationale.clone()));
+            }
+            Trace {
+                from: trace.from.clone(),
+                to: trace.to.clone(),
+                trace_type: trace.trace_type.clone(),
+                attributes,
+                from_span: None,
+                to_span: None,
+            }
+        })
+        .collect();
+    ast
+}
+
+/// The attributes a component is declared with; `type_key` is where its
+/// declaration keeps the component type.
+fn component_attributes(component: &ComponentInfo, type_key: &str) -> HashMap<String, AttributeValue> {
+    let mut attributes = HashMap::new();
+    attributes.insert(type_key.to_string(), AttributeValue::String(component.component_type.clone()));
+    let optional = [
+        ("safety_level", &component.safety_level),
+        ("asil", &component.asil),
+        ("isolation", &component.isolation),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            attributes.insert(key.to_string(), AttributeValue::String(value.clone()));
+        }
+    }
+    attributes
+}
+
+/// Add the element's custom attributes (`attr key: value`) and owner.
+fn with_custom(
+    mut attributes: HashMap<String, AttributeValue>,
+    model: &SemanticModel,
+    id: &str,
+) -> HashMap<String, AttributeValue> {
+    if let Some(element) = model.all_elements.get(id) {
+        for (key, value) in &element.custom_attributes {
+            attributes.insert(format!("{CUSTOM_ATTRIBUTE_PREFIX}{key}"), value.to_attribute());
+        }
+        if let Some(owner) = &element.owner {
+            attributes.insert("owner".to_string(), AttributeValue::String(owner.clone()));
+        }
+    }
+    attributes
+}
+
+fn with_id(mut attributes: HashMap<String, AttributeValue>, id: &str) -> HashMap<String, AttributeValue> {
+    attributes.insert("id".to_string(), AttributeValue::String(id.to_string()));
+    attributes
+}
@@ -4,13 +4,17 @@
 //! Export: every requirement becomes a SPEC-OBJECT whose IDENTIFIER is the
 //! requirement's deterministic ArcLang UUID (prefixed `_` to be a valid
 //! xsd:ID), with ReqID / Title / Text / Priority / Category / SafetyLevel
-//! attributes. Requirement-to-requirement traces become SPEC-RELATIONS.
+//! attributes. Any other `req` attribute travels as an extra attribute
+//! definition typed by its value (REAL for numbers, BOOLEAN, else STRING).
+//! Requirement-to-requirement traces become SPEC-RELATIONS.
 //! Timestamps are FIXED so re-exporting an unchanged model is byte-identical
 //! (same discipline as the Capella bridge: diffs must mean something).
 //!
 //! Import: reads a ReqIF file produced by any tool, maps attribute
 //! definitions by LONG-NAME heuristics (ReqID/ID → id, Text/Description →
-//! description, …) and emits an ArcLang `requirements` block. The original
+//! description, …) and emits an ArcLang `requirements` block. Unmapped
+//! attributes are kept as snake_case `req` attributes, and SPEC-RELATIONS
+//! between imported objects become `trace` statements. The original
 //! SPEC-OBJECT IDENTIFIER is preserved as `reqif_id` so identity survives a
 //! round-trip through a foreign tool.
 
@@ -48,6 +52,59 @@ const ATTRS: &[(&str, &str)] = &[
     ("AD-SAFETY", "SafetyLevel"),
 ];
 
+/// Requirement attributes already exported through `ATTRS` (or identity).
+const BUILTIN_KEYS: &[&str] = &["id", "title", "description", "priority", "category", "safety_level", "reqif_id"];
+
+/// ReqIF datatype of a custom attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CustomType {
+    String,
+    Real,
+    Boolean,
+}
+
+impl CustomType {
+    fn of(value: &AttributeValue) -> Option<Self> {
+        match value {
+            AttributeValue::String(_) | AttributeValue::List(_) => Some(CustomType::String),
+            AttributeValue::Number(_) => Some(CustomType::Real),
+            AttributeValue::Boolean(_) => Some(CustomType::Boolean),
+            AttributeValue::Map(_) => None,
+        }
+    }
+
+    /// (ReqIF kind suffix, datatype identifier)
+    fn reqif(self) -> (&'static str, &'static str) {
+        match self {
+            CustomType::String => ("STRING", "DT-STRING"),
+            CustomType::Real => ("REAL", "DT-REAL"),
+            CustomType::Boolean => ("BOOLEAN", "DT-BOOLEAN"),
+        }
+    }
+}
+
+fn custom_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::String(s) => s.clone(),
+        AttributeValue::Number(n) => n.to_string(),
+        AttributeValue::Boolean(b) => b.to_string(),
+        AttributeValue::List(items) => items
+            .iter()
+            .map(custom_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        AttributeValue::Map(_) => String::new(),
+    }
+}
+
+fn custom_definition_id(key: &str) -> String {
+    let slug: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '-' })
+        .collect();
+    format!("AD-X-{slug}")
+}
+
 pub fn generate_reqif(model: &SemanticModel, ast: &Model) -> String {
     // AST attributes carry the title (and anything else `req` declared).
     let mut ast_attrs: HashMap<&str, &HashMap<String, AttributeValue>> = HashMap::new();
@@ -57,6 +114,28 @@ pub fn generate_reqif(model: &SemanticModel, ast: &Model) -> String {
         }
     }
 
+    // Custom attributes (anything beyond ATTRS) travel as extra attribute
+    // definitions, typed by their values; mixed types fall back to String.
+    let mut custom: std::collections::BTreeMap<&str, CustomType> = std::collections::BTreeMap::new();
+    for req in &model.requirements {
+        let Some(attrs) = ast_attrs.get(req.id.as_str()) else { continue };
+        for (key, value) in attrs.iter() {
+            if BUILTIN_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            if let Some(kind) = CustomType::of(value) {
+                custom
+                    .entry(key.as_str())
+                    .and_modify(|k| {
+                        if *k != kind {
+                            *k = CustomType::String;
+                        }
+                    })
+                    .or_insert(kind);
+            }
+        }
+    }
+
     let title = model.name.as_deref().unwrap_or("ArcLang Requirements");
     let mut out = String::new();
     out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
@@ -80,6 +159,16 @@ pub fn generate_reqif(model: &SemanticModel, ast: &Model) -> String {
     out.push_str(&format!(
         "        <DATATYPE-DEFINITION-STRING IDENTIFIER=\"DT-STRING\" LONG-NAME=\"String\" MAX-LENGTH=\"32000\" LAST-CHANGE=\"{REQIF_TIMESTAMP}\"/>\n"
     ));
+    if custom.values().any(|k| *k == CustomType::Real) {
+        out.push_str(&format!(
+            "        <DATATYPE-DEFINITION-REAL IDENTIFIER=\"DT-REAL\" LONG-NAME=\"Real\" ACCURACY=\"10\" MIN=\"-1.0E308\" MAX=\"1.0E308\" LAST-CHANGE=\"{REQIF_TIMESTAMP}\"/>\n"
+        ));
+    }
+    if custom.values().any(|k| *k == CustomType::Boolean) {
+        out.push_str(&format!(
+            "        <DATATYPE-DEFINITION-BOOLEAN IDENTIFIER=\"DT-BOOLEAN\" LONG-NAME=\"Boolean\" LAST-CHANGE=\"{REQIF_TIMESTAMP}\"/>\n"
+        ));
+    }
     out.push_str("      </DATATYPES>\n");
 
     // Spec types
@@ -93,6 +182,14 @@ pub fn generate_reqif(model: &SemanticModel, ast: &Model) -> String {
             "            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER=\"{id}\" LONG-NAME=\"{long_name}\" LAST-CHANGE=\"{REQIF_TIMESTAMP}\">\n              <TYPE><DATATYPE-DEFINITION-STRING-REF>DT-STRING</DATATYPE-DEFINITION-STRING-REF></TYPE>\n            </ATTRIBUTE-DEFINITION-STRING>\n"
         ));
     }
+    for (key, kind) in &custom {
+        let (suffix, datatype) = kind.reqif();
+        out.push_str(&format!(
+            "            <ATTRIBUTE-DEFINITION-{suffix} IDENTIFIER=\"{}\" LONG-NAME=\"{}\" LAST-CHANGE=\"{REQIF_TIMESTAMP}\">\n              <TYPE><DATATYPE-DEFINITION-{suffix}-REF>{datatype}</DATATYPE-DEFINITION-{suffix}-REF></TYPE>\n            </ATTRIBUTE-DEFINITION-{suffix}>\n",
+            esc(&custom_definition_id(key)),
+            esc(key)
+        ));
+    }
     out.push_str("          </SPEC-ATTRIBUTES>\n");
     out.push_str("        </SPEC-OBJECT-TYPE>\n");
     out.push_str(&format!(
@@ -149,6 +246,15 @@ pub fn generate_reqif(model: &SemanticModel, ast: &Model) -> String {
                 esc(value)
             ));
         }
+        for (key, kind) in &custom {
+            let Some(value) = attrs.and_then(|a| a.get(*key)) else { continue };
+            let (suffix, _) = kind.reqif();
+            out.push_str(&format!(
+                "            <ATTRIBUTE-VALUE-{suffix} THE-VALUE=\"{}\">\n              <DEFINITION><ATTRIBUTE-DEFINITION-{suffix}-REF>{}</ATTRIBUTE-DEFINITION-{suffix}-REF></DEFINITION>\n            </ATTRIBUTE-VALUE-{suffix}>\n",
+                esc(&custom_value(value)),
+                esc(&custom_definition_id(key))
+            ));
+        }
         out.push_str("          </VALUES>\n          <TYPE><SPEC-OBJECT-TYPE-REF>SOT-REQUIREMENT</SPEC-OBJECT-TYPE-REF></TYPE>\n        </SPEC-OBJECT>\n");
     }
     out.push_str("      </SPEC-OBJECTS>\n");
@@ -204,6 +310,52 @@ struct ImportedReq {
     values: Vec<(String, String)>, // (attribute-definition ref, value)
 }
 
+#[derive(Debug, Default)]
+struct ImportedRelation {
+    source: String,
+    target: String,
+    kind: String, // SPEC-RELATION-TYPE ref
+}
+
+/// Which `-REF` of a SPEC-RELATION the next text node belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RelationSlot {
+    Source,
+    Target,
+    Type,
+}
+
+/// Render an imported custom attribute value according to its ReqIF kind.
+fn typed_value(kind: &str, value: &str, quote: impl Fn(&str) -> String) -> String {
+    match kind {
+        "INTEGER" | "REAL" if value.trim().parse::<f64>().is_ok() => value.trim().to_string(),
+        "BOOLEAN" => match value.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => "true".to_string(),
+            "false" | "0" => "false".to_string(),
+            _ => quote(value),
+        },
+        _ => quote(value),
+    }
+}
+
+/// `Verification Method` → `verification_method`
+fn attribute_key(long_name: &str) -> String {
+    let mut key = String::new();
+    for c in long_name.chars() {
+        if c.is_ascii_alphanumeric() {
+            key.push(c.to_ascii_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    let key = key.trim_end_matches('_').to_string();
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("attr_{key}")
+    } else {
+        key
+    }
+}
+
 /// Map a foreign attribute LONG-NAME onto an ArcLang requirement field.
 fn field_for(long_name: &str) -> &'static str {
     let normalized: String = long_name
@@ -228,9 +380,14 @@ pub fn import_reqif(xml: &str) -> Result<String, String> {
     reader.config_mut().trim_text(true);
     let mut buf = Vec::new();
 
-    // ATTRIBUTE-DEFINITION-* IDENTIFIER -> LONG-NAME
-    let mut definitions: HashMap<String, String> = HashMap::new();
+    // ATTRIBUTE-DEFINITION-* IDENTIFIER -> (LONG-NAME, kind suffix)
+    let mut definitions: HashMap<String, (String, String)> = HashMap::new();
+    // SPEC-RELATION-TYPE IDENTIFIER -> LONG-NAME
+    let mut relation_types: HashMap<String, String> = HashMap::new();
     let mut reqs: Vec<ImportedReq> = Vec::new();
+    let mut relations: Vec<ImportedRelation> = Vec::new();
+    let mut relation: Option<ImportedRelation> = None;
+    let mut relation_slot: Option<RelationSlot> = None;
     let mut doc_title = String::new();
 
     let mut current: Option<ImportedReq> = None;
@@ -258,11 +415,24 @@ pub fn import_reqif(xml: &str) -> Result<String, String> {
                     n if n.ends_with("-REF") && current.is_some() => {
                         capture_ref = true;
                     }
+                    "SPEC-RELATION-TYPE-REF" if relation.is_some() => {
+                        relation_slot = Some(RelationSlot::Type);
+                    }
+                    "SOURCE" if relation.is_some() => relation_slot = Some(RelationSlot::Source),
+                    "TARGET" if relation.is_some() => relation_slot = Some(RelationSlot::Target),
                     n if n.starts_with("ATTRIBUTE-DEFINITION-") => {
                         if let (Some(id), Some(long)) = (get_attr("IDENTIFIER"), get_attr("LONG-NAME")) {
-                            definitions.insert(id, long);
+                            let kind = n.trim_start_matches("ATTRIBUTE-DEFINITION-").to_string();
+                            definitions.insert(id, (long, kind));
+                        }
+                    }
+                    "SPEC-RELATION-TYPE" => {
+                        if let Some(id) = get_attr("IDENTIFIER") {
+                            let long = get_attr("LONG-NAME").unwrap_or_else(|| id.clone());
+                            relation_types.insert(id, long);
                         }
                     }
+                    "SPEC-RELATION" => relation = Some(ImportedRelation::default()),
                     "SPEC-OBJECT" => {
                         current = Some(ImportedReq {
                             identifier: get_attr("IDENTIFIER").unwrap_or_default(),
@@ -284,7 +454,13 @@ pub fn import_reqif(xml: &str) -> Result<String, String> {
             }
             Ok(Event::Text(ref t)) => {
                 let text = t.xml10_content().map_err(|e| e.to_string())?.to_string();
-                if capture_ref {
+                if let (Some(rel), Some(slot)) = (relation.as_mut(), relation_slot.take()) {
+                    match slot {
+                        RelationSlot::Source => rel.source = text,
+                        RelationSlot::Target => rel.target = text,
+                        RelationSlot::Type => rel.kind = text,
+                    }
+                } else if capture_ref {
                     if let Some(req) = current.as_mut() {
                         let value = if in_xhtml_value {
                             std::mem::take(&mut xhtml_text)
@@ -313,6 +489,12 @@ pub fn import_reqif(xml: &str) -> Result<String, String> {
                             reqs.push(req);
                         }
                     }
+                    "SPEC-RELATION" => {
+                        if let Some(rel) = relation.take() {
+                            relations.push(rel);
+                        }
+                        relation_slot = None;
+                    }
                     "ATTRIBUTE-VALUE-XHTML" => in_xhtml_value = false,
                     "TITLE" => capture_title = false,
                     _ => {}
@@ -341,19 +523,31 @@ pub fn import_reqif(xml: &str) -> Result<String, String> {
     let mut out = String::new();
     out.push_str("// Imported from ReqIF by ArcLang — reqif_id preserves the foreign identity\n");
     out.push_str(&format!("model {model_name} {{\n}}\n\nrequirements {{\n"));
+    // SPEC-OBJECT IDENTIFIER -> requirement id, for resolving relations
+    let mut ids_by_identifier: HashMap<&str, String> = HashMap::new();
     for (index, req) in reqs.iter().enumerate() {
         let mut fields: HashMap<&str, String> = HashMap::new();
+        let mut custom: Vec<(String, String)> = Vec::new();
         for (def_ref, value) in &req.values {
-            let long_name = definitions.get(def_ref).map(|s| s.as_str()).unwrap_or(def_ref);
+            let (long_name, kind) = definitions
+                .get(def_ref)
+                .map(|(long, kind)| (long.as_str(), kind.as_str()))
+                .unwrap_or((def_ref.as_str(), "STRING"));
             let field = field_for(long_name);
             if !field.is_empty() {
                 fields.entry(field).or_insert_with(|| value.clone());
+                continue;
+            }
+            let key = attribute_key(long_name);
+            if !key.is_empty() && key != "reqif_id" && !custom.iter().any(|(k, _)| *k == key) {
+                custom.push((key, typed_value(kind, value, quote)));
             }
         }
         let id = fields
             .get("id")
             .cloned()
             .unwrap_or_else(|| format!("REQ-IMPORTED-{:03}", index + 1));
+        ids_by_identifier.insert(req.identifier.as_str(), id.clone());
         out.push_str(&format!("  req {}", quote(&id)));
         if let Some(title) = fields.get("title") {
             out.push_str(&format!(" {}", quote(title)));
@@ -364,12 +558,42 @@ pub fn import_reqif(xml: &str) -> Result<String, String> {
                 out.push_str(&format!("    {key}: {}\n", quote(value)));
             }
         }
+        for (key, value) in &custom {
+            out.push_str(&format!("    {key}: {value}\n"));
+        }
         if !req.identifier.is_empty() {
             out.push_str(&format!("    reqif_id: {}\n", quote(&req.identifier)));
         }
         out.push_str("  }\n");
     }
     out.push_str("}\n");
+
+    // SPEC-RELATIONs between imported objects become traces
+    let mut wrote_header = false;
+    for rel in &relations {
+        let (Some(from), Some(to)) = (
+            ids_by_identifier.get(rel.source.as_str()),
+            ids_by_identifier.get(rel.target.as_str()),
+        ) else {
+            continue;
+        };
+        let kind = relation_types
+            .get(&rel.kind)
+            .map(|s| s.as_str())
+            .unwrap_or(rel.kind.as_str());
+        let kind = attribute_key(kind);
+        let kind = if kind.is_empty() { "relates_to".to_string() } else { kind };
+        if !wrote_header {
+            out.push('\n');
+            wrote_header = true;
+        }
+        out.push_str(&format!(
+            "trace {} -> {} {{\n  trace_type: {}\n}}\n",
+            quote(from),
+            quote(to),
+            quote(&kind)
+        ));
+    }
     Ok(out)
 }
 
@@ -461,6 +685,54 @@ requirements {
         );
     }
 
+    #[test]
+    fn round_trip_preserves_traces_and_custom_attributes() {
+        let source = r#"
+model Links {
+}
+
+requirements {
+  req "SYS-1" "Braking" {
+    description: "The vehicle shall brake"
+    status: "approved"
+    weight: 3
+  }
+  req "SW-1" "Brake command" {
+    description: "Software shall command the actuator"
+    status: "draft"
+  }
+}
+
+trace "SW-1" -> "SYS-1" { trace_type: "derives" }
+"#;
+        let result = compile(source);
+        let reqif = generate_reqif(&result.semantic_model, &result.ast);
+        assert!(reqif.contains("DATATYPE-DEFINITION-REAL IDENTIFIER=\"DT-REAL\""));
+        assert!(reqif.contains("<ATTRIBUTE-VALUE-STRING THE-VALUE=\"approved\">"));
+
+        let arc = import_reqif(&reqif).expect("import succeeds");
+        let reimported = compile(&arc);
+        let model = &reimported.semantic_model;
+        assert!(model
+            .traces
+            .iter()
+            .any(|t| t.from == "SW-1" && t.to == "SYS-1" && t.trace_type == "derives"));
+
+        let attrs = &reimported
+            .ast
+            .system_analysis
+            .iter()
+            .flat_map(|sa| &sa.requirements)
+            .find(|r| r.id == "SYS-1")
+            .expect("SYS-1 survives")
+            .attributes;
+        assert_eq!(attrs.get("status").and_then(|v| v.as_string()), Some("approved"));
+        assert!(matches!(attrs.get("weight"), Some(AttributeValue::Number(n)) if *n == 3.0));
+        let sys = model.requirements.iter().find(|r| r.id == "SYS-1").unwrap();
+        assert_eq!(sys.description, "The vehicle shall brake");
+        assert_eq!(sys.uuid(), result.semantic_model.requirements[0].uuid());
+    }
+
     #[test]
     fn import_maps_foreign_doors_style_attributes() {
         // Shape a DOORS-ish file: XHTML text, "Object Text"/"Object Heading"
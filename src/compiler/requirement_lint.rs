@@ -0,0 +1,263 @@
+//! Requirements quality lints for `arclang check --lint`: wording that makes
+//! a requirement hard to verify. Each finding carries a stable code and the
+//! requirement it is about:
+//!
+//! | Code                 | Finding                                              |
+//! |----------------------|------------------------------------------------------|
+//! | `E-REQ-WEAK-WORD`    | vague wording (`should`, `etc.`, `as appropriate`)   |
+//! | `E-REQ-UNMEASURABLE` | performance requirement without a number in it       |
+//! | `E-REQ-PASSIVE`      | passive voice hides who does what                    |
+//! | `E-REQ-TOO-LONG`     | description over the word limit                      |
+//! | `E-REQ-UNVERIFIED`   | no test case, `verified_by` trace or `verification_method` |
+//!
+//! Findings are warnings, except on safety requirements (an integrity level
+//! other than QM): there every finding but passive voice is an error, and
+//! the word limit is tighter. [`LintConfig`] tunes the rules; `check
+//! --lint-config` reads it from a TOML file.
+
+use serde::{Deserialize, Serialize};
+
+use super::diagnostic::{Diagnostic, Severity};
+use super::semantic::{RequirementInfo, SemanticModel, TraceLinkType};
+
+pub const WEAK_WORD: &str = "E-REQ-WEAK-WORD";
+pub const UNMEASURABLE: &str = "E-REQ-UNMEASURABLE";
+pub const PASSIVE: &str = "E-REQ-PASSIVE";
+pub const TOO_LONG: &str = "E-REQ-TOO-LONG";
+pub const UNVERIFIED: &str = "E-REQ-UNVERIFIED";
+
+/// The ruleset; every field may be left out of the TOML file.
+///
+/// ```toml
+/// max_words = 50
+/// weak_words = ["should", "etc.", "user-friendly"]
+/// disabled = ["E-REQ-PASSIVE"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Words and phrases that make a requirement vague, matched
+    /// case-insensitively as whole words.
+    pub weak_words: Vec<String>,
+    /// Requirement categories that must state a measurable criterion.
+    pub performance_categories: Vec<String>,
+    /// Longest description, in words.
+    pub max_words: usize,
+    /// Longest description of a safety requirement, in words.
+    pub safety_max_words: usize,
+    /// Report safety requirement findings (other than passive voice) as
+    /// errors.
+    pub strict_safety: bool,
+    /// Codes of the rules to skip.
+    pub disabled: Vec<String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            weak_words: [
+                "should", "may", "etc.", "as appropriate", "as applicable", "if possible",
+                "adequate", "sufficient", "user-friendly", "and/or", "TBD",
+            ]
+            .iter()
+            .map(|w| w.to_string())
+            .collect(),
+            performance_categories: vec!["Performance".to_string(), "Timing".to_string()],
+            max_words: 60,
+            safety_max_words: 40,
+            strict_safety: true,
+            disabled: Vec::new(),
+        }
+    }
+}
+
+/// One finding of [`lint_requirements`].
+#[derive(Debug, Clone)]
+pub struct RequirementFinding {
+    pub severity: Severity,
+    pub code: &'static str,
+    /// Id of the requirement the finding is about.
+    pub requirement: String,
+    pub diagnostic: Diagnostic,
+}
+
+/// Lint every requirement of `model`, in declaration order.
+pub fn lint_requirements(model: &SemanticModel, config: &LintConfig) -> Vec<RequirementFinding> {
+    let mut findings = Vec::new();
+    for req in &model.requirements {
+        let safety = req.is_safety_requirement();
+        let mut report = |code: &'static str, message: String| {
+            if config.disabled.iter().any(|d| d.eq_ignore_ascii_case(code)) {
+                return;
+            }
+            let severity = if safety && config.strict_safety && code != PASSIVE {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            let span = model.all_elements.get(&req.id).and_then(|e| e.span.clone());
+            findings.push(RequirementFinding {
+                severity,
+                code,
+                requirement: req.id.clone(),
+                diagnostic: Diagnostic::at(format!("{}: {}", req.id, message), span),
+            });
+        };
+
+        let text = requirement_text(req);
+        let lower = text.to_lowercase();
+        for word in &config.weak_words {
+            if contains_phrase(&lower, &word.to_lowercase()) {
+                report(WEAK_WORD, format!("weak word '{word}' makes the requirement vague"));
+            }
+        }
+
+        let performance = req
+            .category
+            .as_deref()
+            .is_some_and(|c| config.performance_categories.iter().any(|p| p.eq_ignore_ascii_case(c)));
+        if performance && !text.chars().any(|c| c.is_ascii_digit()) {
+            report(UNMEASURABLE, "performance requirement states no measurable criterion".to_string());
+        }
+
+        if let Some(phrase) = passive_phrase(&lower) {
+            report(PASSIVE, format!("passive voice ('{phrase}'): name who or what acts"));
+        }
+
+        let words = req.description.split_whitespace().count();
+        let limit = if safety { config.safety_max_words } else { config.max_words };
+        if words > limit {
+            report(TOO_LONG, format!("description has {words} words (limit {limit}): split the requirement"));
+        }
+
+        let verified = req.verification_method.is_some()
+            || model.verifications.iter().any(|v| v.requirement == req.id)
+            || model.traces.iter().any(|t| t.link_type == TraceLinkType::VerifiedBy && t.to == req.id);
+        if !verified {
+            report(UNVERIFIED, "no verification method, test case or verified_by trace".to_string());
+        }
+    }
+    findings
+}
+
+/// Title and description: the wording a reviewer reads.
+fn requirement_text(req: &RequirementInfo) -> String {
+    match &req.title {
+        Some(title) => format!("{title}\n{}", req.description),
+        None => req.description.clone(),
+    }
+}
+
+/// `phrase` occurs in `text` as whole words.
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    if phrase.is_empty() {
+        return false;
+    }
+    let word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(phrase).any(|(start, _)| {
+        let end = start + phrase.len();
+        let open = !phrase.starts_with(word_char) || !text[..start].ends_with(word_char);
+        let close = !phrase.ends_with(word_char) || !text[end..].starts_with(word_char);
+        open && close
+    })
+}
+
+/// A form of "to be" followed by a past participle (`shall be provided`,
+/// `is sent`), as written.
+fn passive_phrase(text: &str) -> Option<String> {
+    const BE: &[&str] = &["be", "is", "are", "was", "were", "been", "being"];
+    const IRREGULAR: &[&str] = &[
+        "done", "given", "shown", "sent", "kept", "made", "known", "taken", "written", "built", "held",
+        "set", "put", "read", "run", "seen", "found",
+    ];
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.windows(2).find_map(|pair| {
+        let participle = (pair[1].len() > 4 && pair[1].ends_with("ed")) || IRREGULAR.contains(&pair[1]);
+        (BE.contains(&pair[0]) && participle).then(|| format!("{} {}", pair[0], pair[1]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn compile(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    fn codes(findings: &[RequirementFinding], requirement: &str) -> Vec<&'static str> {
+        findings.iter().filter(|f| f.requirement == requirement).map(|f| f.code).collect()
+    }
+
+    const MODEL: &str = r#"model Test {
+}
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "The controller shall respond quickly, as appropriate"
+        category: "Performance"
+    }
+    requirement "REQ-002" {
+        description: "The warning shall be displayed to the driver"
+        verification_method: "Test"
+    }
+    requirement "REQ-003" {
+        description: "The controller should limit torque"
+        safety_level: "ASIL-B"
+        verification_method: "Analysis"
+    }
+    requirement "REQ-004" {
+        description: "The controller shall respond within 50 ms"
+        category: "Performance"
+        verification_method: "Test"
+    }
+}
+"#;
+
+    #[test]
+    fn flags_vague_unmeasurable_passive_and_unverified_requirements() {
+        let findings = lint_requirements(&compile(MODEL), &LintConfig::default());
+
+        assert_eq!(codes(&findings, "REQ-001"), vec![WEAK_WORD, UNMEASURABLE, UNVERIFIED]);
+        assert_eq!(codes(&findings, "REQ-002"), vec![PASSIVE]);
+        assert!(codes(&findings, "REQ-004").is_empty(), "{findings:?}");
+        assert!(findings
+            .iter()
+            .filter(|f| f.requirement != "REQ-003")
+            .all(|f| f.severity == Severity::Warning));
+        let safety = findings.iter().find(|f| f.requirement == "REQ-003").unwrap();
+        assert_eq!((safety.code, safety.severity), (WEAK_WORD, Severity::Error));
+        assert!(safety.diagnostic.message.contains("'should'"), "{}", safety.diagnostic.message);
+        assert!(safety.diagnostic.span.is_some());
+    }
+
+    #[test]
+    fn the_config_tunes_length_words_and_rules() {
+        let config = LintConfig {
+            max_words: 5,
+            weak_words: vec!["quickly".to_string()],
+            disabled: vec![UNVERIFIED.to_string()],
+            strict_safety: false,
+            ..LintConfig::default()
+        };
+        let findings = lint_requirements(&compile(MODEL), &config);
+
+        assert_eq!(codes(&findings, "REQ-001"), vec![WEAK_WORD, UNMEASURABLE, TOO_LONG]);
+        assert!(codes(&findings, "REQ-003").is_empty(), "{findings:?}");
+        assert!(findings.iter().all(|f| f.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn weak_words_match_whole_words_only() {
+        assert!(contains_phrase("lights, horn etc. shall", "etc."));
+        assert!(!contains_phrase("the mayday call", "may"));
+        assert!(passive_phrase("the valve is closed").is_some());
+        assert!(passive_phrase("the valve shall close").is_none());
+    }
+}
@@ -0,0 +1,260 @@
+//! Generic CSV requirements exchange (`arclang export -f csv`,
+//! `arclang import reqs.csv -f csv`), for teams that keep requirements in a
+//! spreadsheet rather than an RM tool.
+//!
+//! Export writes one row per requirement, in declaration order, with the
+//! columns `id`, `title`, `text`, `priority`, `status`,
+//! `verification_method`, then every other attribute of any requirement as
+//! an extra column sorted by name (custom `attr` attributes under their bare
+//! key). Import maps the header row onto requirement fields by those names,
+//! common aliases (`Summary`, `Description`, `State`...) or an explicit
+//! column mapping, and emits a `requirements` block; the remaining columns
+//! become `attr` custom attributes, in header order. Exporting, importing
+//! and exporting again yields the same columns in the same order.
+//!
+//! Import streams: rows are read and requirements written one at a time,
+//! so a table of any size converts in the memory of its longest row.
+
+use super::ast::{AttributeValue, Model, CUSTOM_ATTRIBUTE_PREFIX};
+use super::doors_importer::{attribute_key, csv_records, quote};
+use super::CompilerError;
+use std::collections::BTreeSet;
+use std::io::{BufRead, Write};
+
+/// Leading columns of an export: (header, requirement attribute).
+const COLUMNS: &[(&str, &str)] = &[
+    ("id", "id"),
+    ("title", "title"),
+    ("text", "description"),
+    ("priority", "priority"),
+    ("status", "status"),
+    ("verification_method", "verification_method"),
+];
+
+/// Attributes ArcLang models itself: imported as plain attributes, not
+/// `attr` custom ones.
+const MODEL_KEYS: &[&str] = &["category", "safety_level", "hazard", "parent", "rationale"];
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Every requirement of `ast` as CSV.
+pub fn generate_requirements_csv(ast: &Model) -> String {
+    let requirements: Vec<_> = ast.system_analysis.iter().flat_map(|sa| &sa.requirements).collect();
+
+    let leading: Vec<&str> = COLUMNS.iter().map(|(_, key)| *key).collect();
+    let extra: BTreeSet<&str> = requirements
+        .iter()
+        .flat_map(|req| req.attributes.keys())
+        .map(|key| key.strip_prefix(CUSTOM_ATTRIBUTE_PREFIX).unwrap_or(key))
+        .filter(|key| !leading.contains(key))
+        .collect();
+
+    let mut out = String::new();
+    let header = COLUMNS.iter().map(|(header, _)| *header).chain(extra.iter().copied());
+    push_row(&mut out, header.map(str::to_string));
+    for req in requirements {
+        let value = |key: &str| {
+            req.attributes
+                .get(key)
+                .or_else(|| req.attributes.get(&format!("{CUSTOM_ATTRIBUTE_PREFIX}{key}")))
+                .and_then(cell)
+                .unwrap_or_default()
+        };
+        let id = req.attributes.get("id").and_then(cell).unwrap_or_else(|| req.id.clone());
+        let row = std::iter::once(id).chain(leading[1..].iter().chain(&extra).map(|key| value(key)));
+        push_row(&mut out, row);
+    }
+    out
+}
+
+/// Spreadsheet text of an attribute value; nested maps have none.
+fn cell(value: &AttributeValue) -> Option<String> {
+    match value {
+        AttributeValue::String(s) => Some(s.clone()),
+        AttributeValue::Number(n) => Some(n.to_string()),
+        AttributeValue::Boolean(b) => Some(b.to_string()),
+        AttributeValue::List(items) => Some(items.iter().filter_map(cell).collect::<Vec<_>>().join("; ")),
+        AttributeValue::Map(_) => None,
+    }
+}
+
+fn push_row(out: &mut String, fields: impl Iterator<Item = String>) {
+    let fields: Vec<String> = fields.map(|field| escape(&field)).collect();
+    out.push_str(&fields.join(","));
+    out.push('\n');
+}
+
+/// Quote a field holding a delimiter, quote or line break (RFC 4180).
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// The requirement attribute a column feeds: `mapping` (header to field,
+/// case-insensitive) first, then the export names and common aliases.
+fn target_for(column: &str, mapping: &[(String, String)]) -> String {
+    let field = mapping
+        .iter()
+        .find(|(header, _)| header.trim().eq_ignore_ascii_case(column.trim()))
+        .map(|(_, field)| field.as_str())
+        .unwrap_or(column);
+    let key = attribute_key(field);
+    let normalized: String = key.chars().filter(|c| *c != '_').collect();
+    let known = match normalized.as_str() {
+        "id" | "reqid" | "requirementid" | "identifier" | "key" => "id",
+        "title" | "name" | "heading" | "summary" => "title",
+        "text" | "description" | "requirementtext" | "body" => "description",
+        "priority" => "priority",
+        "status" | "state" => "status",
+        "verificationmethod" | "verification" => "verification_method",
+        _ if MODEL_KEYS.contains(&key.as_str()) => return key,
+        _ => return format!("{CUSTOM_ATTRIBUTE_PREFIX}{key}"),
+    };
+    known.to_string()
+}
+
+/// Convert a CSV requirements table into ArcLang source. `mapping` pairs a
+/// header with the field it holds (`("Req No", "id")`), for columns the
+/// default names do not recognise.
+pub fn import_requirements_csv(content: &str, mapping: &[(String, String)]) -> Result<String, CompilerError> {
+    let mut arc_code = Vec::new();
+    import_requirements_csv_stream(content.as_bytes(), mapping, &mut arc_code)?;
+    Ok(String::from_utf8(arc_code).expect("generated source is UTF-8"))
+}
+
+/// [`import_requirements_csv`] from `input` to `output`, a row at a time:
+/// each requirement is written as soon as its row is read, so memory holds
+/// one row whatever the size of the table. Returns the number of
+/// requirements.
+pub fn import_requirements_csv_stream(
+    input: impl BufRead,
+    mapping: &[(String, String)],
+    output: &mut impl Write,
+) -> Result<usize, CompilerError> {
+    let mut rows = csv_records(input)?;
+    let header = rows
+        .next()
+        .ok_or_else(|| CompilerError::Other("CSV import: the file is empty".to_string()))??;
+    let targets: Vec<String> = header.iter().map(|column| target_for(column, mapping)).collect();
+    if !targets.iter().any(|t| t == "id") {
+        return Err(CompilerError::Other(
+            "CSV import: no id column in the header (map one with --column '<header>=id')".to_string(),
+        ));
+    }
+
+    output.write_all(b"// Imported from CSV by ArcLang\nmodel ImportedRequirements {\n}\n\nrequirements {\n")?;
+    let mut arc_code = String::new();
+    let mut count = 0;
+    for (index, row) in rows.enumerate() {
+        let row = row?;
+        let mut fields: Vec<(&str, &str)> = Vec::new();
+        for (target, value) in targets.iter().zip(&row) {
+            let value = value.trim();
+            if !value.is_empty() && !fields.iter().any(|(t, _)| t == target) {
+                fields.push((target.as_str(), value));
+            }
+        }
+        let field = |key: &str| fields.iter().find(|(t, _)| *t == key).map(|(_, v)| *v);
+        let id = field("id").ok_or_else(|| {
+            CompilerError::Other(format!("CSV import: requirement on row {} has no id", index + 2))
+        })?;
+
+        arc_code.clear();
+        arc_code.push_str(&format!("  req {}", quote(id)));
+        if let Some(title) = field("title") {
+            arc_code.push_str(&format!(" {}", quote(title)));
+        }
+        arc_code.push_str(" {\n");
+        // Known fields first, in export order, then the other columns as
+        // they appear in the header.
+        let known = ["description", "priority", "status", "verification_method"];
+        let others = fields.iter().map(|(t, _)| *t).filter(|t| *t != "id" && *t != "title" && !known.contains(t));
+        for key in known.into_iter().chain(others) {
+            let Some(value) = field(key) else { continue };
+            match key.strip_prefix(CUSTOM_ATTRIBUTE_PREFIX) {
+                Some(custom) => arc_code.push_str(&format!("    attr {}: {}\n", custom, quote(value))),
+                None => arc_code.push_str(&format!("    {}: {}\n", key, quote(value))),
+            }
+        }
+        arc_code.push_str("  }\n");
+        output.write_all(arc_code.as_bytes())?;
+        count += 1;
+    }
+    output.write_all(b"}\n")?;
+    Ok(count)
+}
+
+/// Parse `--column` values (`HEADER=FIELD`) into an import mapping.
+pub fn parse_column_mapping(specs: &[String]) -> Result<Vec<(String, String)>, String> {
+    specs
+        .iter()
+        .map(|spec| {
+            spec.split_once('=')
+                .map(|(header, field)| (header.trim().to_string(), field.trim().to_string()))
+                .filter(|(header, field)| !header.is_empty() && !field.is_empty())
+                .ok_or_else(|| format!("invalid column mapping '{spec}' (expected HEADER=FIELD)"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn compile(source: &str) -> Model {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .ast
+    }
+
+    const SHEET: &str = "Req No,Summary,Description,Priority,State,Verification Method,Owner,ASIL\n\
+BRK-1,Braking,\"Brake within 150 ms, \"\"always\"\"\nunder all loads\",High,Approved,Test,Chassis team,ASIL-D\n\
+BRK-2,Warning,Warn the driver,Medium,Draft,,HMI team,\n";
+
+    #[test]
+    fn imports_a_sheet_with_a_column_mapping() {
+        let mapping = parse_column_mapping(&["req no=id".to_string(), "ASIL = safety_level".to_string()]).unwrap();
+        let source = import_requirements_csv(SHEET, &mapping).unwrap();
+        let ast = compile(&source);
+
+        let req = &ast.system_analysis[0].requirements[0];
+        let text = |key: &str| req.attributes.get(key).and_then(|v| v.as_string());
+        assert_eq!(req.id, "BRK-1");
+        assert_eq!(text("title"), Some("Braking"));
+        assert_eq!(text("description"), Some("Brake within 150 ms, \"always\"\nunder all loads"));
+        assert_eq!(text("status"), Some("Approved"));
+        assert_eq!(text("verification_method"), Some("Test"));
+        assert_eq!(text("safety_level"), Some("ASIL-D"));
+        assert_eq!(text("attr.owner"), Some("Chassis team"));
+        assert!(source.find("attr owner").unwrap() < source.find("safety_level").unwrap(), "{source}");
+    }
+
+    #[test]
+    fn export_import_export_is_stable() {
+        let mapping = parse_column_mapping(&["Req No=id".to_string()]).unwrap();
+        let first = generate_requirements_csv(&compile(&import_requirements_csv(SHEET, &mapping).unwrap()));
+        let second = generate_requirements_csv(&compile(&import_requirements_csv(&first, &[]).unwrap()));
+
+        assert_eq!(first, second);
+        assert!(first.starts_with("id,title,text,priority,status,verification_method,asil,owner\n"), "{first}");
+        assert!(first.contains("\"Brake within 150 ms, \"\"always\"\"\nunder all loads\""), "{first}");
+    }
+
+    #[test]
+    fn a_sheet_without_an_id_column_is_rejected() {
+        let error = import_requirements_csv("Summary,Text\nA,B\n", &[]).unwrap_err();
+        assert!(error.to_string().contains("no id column"), "{error}");
+        assert!(parse_column_mapping(&["Req No".to_string()]).is_err());
+    }
+}
@@ -1,32 +1,166 @@
 use super::ast::*;
-use serde::Serialize;
-use std::collections::HashMap;
+use super::diagnostic::{self, Diagnostic, Severity, SourceSpan};
+use super::semantic_analyzer::ElementType;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticModel {
     /// Model name from the `model <Name>` header, when present.
     pub name: Option<String>,
+    #[serde(serialize_with = "sorted_by_key")]
     pub requirements: Vec<RequirementInfo>,
+    #[serde(serialize_with = "sorted_by_key")]
     pub components: Vec<ComponentInfo>,
+    #[serde(serialize_with = "sorted_by_key")]
     pub functions: Vec<FunctionInfo>,
+    #[serde(serialize_with = "sorted_by_key")]
     pub traces: Vec<TraceInfo>,
+    #[serde(serialize_with = "sorted_by_key")]
     pub interfaces: Vec<InterfaceInfo>,
     #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
     pub missions: Vec<MissionInfo>,
     #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
     pub capabilities: Vec<CapabilityInfo>,
     #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
     pub functional_chains: Vec<FunctionalChainInfo>,
+    /// Hazards declared in `safety_analysis` blocks.
+    #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
+    pub hazards: Vec<HazardInfo>,
+    /// `fmea_action` declarations in `safety_analysis` blocks.
+    #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
+    pub fmea_actions: Vec<FmeaActionInfo>,
+    /// Logical components deployed on physical nodes, from `deploys` /
+    /// `deployment` statements and `allocated_to` traces to a node.
+    #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
+    pub deployments: Vec<DeploymentInfo>,
+    /// Ports and interfaces declared on logical components.
+    #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
+    pub ports: Vec<PortInfo>,
+    /// Port-to-port connections, from `connect` statements and component
+    /// exchanges whose endpoints are both declared ports.
+    #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
+    pub connections: Vec<PortConnection>,
+    /// Requirements verified by `test_case` declarations.
+    #[serde(default)]
+    #[serde(serialize_with = "sorted_by_key")]
+    pub verifications: Vec<VerificationInfo>,
+    #[serde(serialize_with = "super::ast::sorted_map")]
     pub all_elements: HashMap<String, ElementInfo>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Order in which [`SemanticModel`] collections are serialized: elements by
+/// id, relations by their ends, so that the same model declared in another
+/// order exports to the same bytes.
+trait ExportKey {
+    fn export_key(&self) -> Vec<&str>;
+}
+
+fn sorted_by_key<S: serde::Serializer, T: Serialize + ExportKey>(items: &[T], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&T> = items.iter().collect();
+    sorted.sort_by(|a, b| a.export_key().cmp(&b.export_key()));
+    serializer.collect_seq(sorted)
+}
+
+impl ExportKey for RequirementInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for ComponentInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for FunctionInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for MissionInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for CapabilityInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for FunctionalChainInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for HazardInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.id]
+    }
+}
+
+impl ExportKey for TraceInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.from, &self.to, &self.trace_type]
+    }
+}
+
+impl ExportKey for InterfaceInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.name, &self.from, &self.to]
+    }
+}
+
+impl ExportKey for FmeaActionInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.entry, &self.action]
+    }
+}
+
+impl ExportKey for DeploymentInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.component, &self.node]
+    }
+}
+
+impl ExportKey for PortInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.component, &self.name]
+    }
+}
+
+impl ExportKey for PortConnection {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.from_component, &self.from_port, &self.to_component, &self.to_port]
+    }
+}
+
+impl ExportKey for VerificationInfo {
+    fn export_key(&self) -> Vec<&str> {
+        vec![&self.test_case, &self.requirement]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MissionInfo {
     pub id: String,
     pub name: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapabilityInfo {
     pub id: String,
     pub name: String,
@@ -40,12 +174,59 @@ pub struct CapabilityInfo {
     pub kind: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionalChainInfo {
     pub id: String,
     pub name: String,
     /// Resolved, ordered element ids (functions/exchanges) of the chain.
     pub involves: Vec<String>,
+    /// One link per pair of adjacent steps, in chain order.
+    #[serde(default)]
+    pub links: Vec<ChainLink>,
+}
+
+/// Two adjacent steps of a functional chain and what carries data between
+/// them: a functional exchange from the first to the second, or a data item
+/// the first outputs and the second inputs. `None` is a gap in the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainLink {
+    pub from: String,
+    pub to: String,
+    pub exchange: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HazardInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Declared integrity level (`asil_level`, `asil`, `dal`, `sil`, ...), as written.
+    pub integrity_level: Option<String>,
+    /// ISO 26262 classification of the hazardous event (`severity: "S3"`,
+    /// `exposure: "E4"`, `controllability: "C2"`), as written.
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub exposure: Option<String>,
+    #[serde(default)]
+    pub controllability: Option<String>,
+    /// The `mitigations` listed for the hazard.
+    #[serde(default)]
+    pub mitigations: Vec<String>,
+}
+
+/// A mitigation action recorded against a generated FMEA entry, with the
+/// occurrence and detection scores it is expected to achieve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FmeaActionInfo {
+    /// Id of the FMEA entry (`FMEA-012`).
+    pub entry: String,
+    pub action: String,
+    pub owner: Option<String>,
+    pub due: Option<chrono::NaiveDate>,
+    pub closed: bool,
+    pub occurrence: Option<u32>,
+    pub detection: Option<u32>,
 }
 
 impl Default for SemanticModel {
@@ -60,28 +241,158 @@ impl Default for SemanticModel {
             missions: Vec::new(),
             capabilities: Vec::new(),
             functional_chains: Vec::new(),
+            hazards: Vec::new(),
+            fmea_actions: Vec::new(),
+            deployments: Vec::new(),
+            ports: Vec::new(),
+            connections: Vec::new(),
+            verifications: Vec::new(),
             all_elements: HashMap::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DeploymentInfo {
+    /// Resolved id of the logical component.
+    pub component: String,
+    /// Id of the physical node hosting it.
+    pub node: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationInfo {
+    pub test_case: String,
+    /// Resolved id of the verified requirement.
+    pub requirement: String,
+    /// test | analysis | inspection | demonstration.
+    pub method: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortInfo {
+    /// Id of the owning component.
+    pub component: String,
+    pub name: String,
+    pub direction: PortDirection,
+    /// The interface the port provides or requires.
+    pub interface: String,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortConnection {
+    pub from_component: String,
+    pub from_port: String,
+    pub to_component: String,
+    pub to_port: String,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfaceInfo {
     pub name: String,
     pub from: String,
     pub to: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequirementInfo {
     pub id: String,
+    pub title: Option<String>,
     pub description: String,
     pub priority: String,
     pub category: Option<String>,
     pub safety_level: Option<String>,
+    /// The hazard this requirement mitigates (`hazard: "HAZ-001"`), as written.
+    #[serde(default)]
+    pub hazard: Option<String>,
+    /// How the requirement is to be verified (`verification: Test`, or
+    /// `verification_method: "Test"`).
+    #[serde(default)]
+    pub verification_method: Option<VerificationMethod>,
+    /// Where its verification stands (`verification_status: Verified`).
+    #[serde(default)]
+    pub verification_status: Option<VerificationStatus>,
+    /// The requirement this one refines (`trace "child" refines "parent"`).
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// The requirements refining this one, in trace order.
+    #[serde(default)]
+    pub children_ids: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// A requirement verification method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VerificationMethod {
+    Test,
+    Review,
+    Analysis,
+    Inspection,
+    Simulation,
+    FormalProof,
+}
+
+impl VerificationMethod {
+    pub const ALL: [Self; 6] =
+        [Self::Test, Self::Review, Self::Analysis, Self::Inspection, Self::Simulation, Self::FormalProof];
+
+    /// The method `text` names, ignoring case and separators
+    /// (`formal_proof`, `Formal Proof`); a demonstration is a simulation.
+    pub fn parse(text: &str) -> Option<Self> {
+        match normalized_keyword(text).as_str() {
+            "demonstration" => Some(Self::Simulation),
+            key => Self::ALL.into_iter().find(|m| normalized_keyword(m.as_str()) == key),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Test => "Test",
+            Self::Review => "Review",
+            Self::Analysis => "Analysis",
+            Self::Inspection => "Inspection",
+            Self::Simulation => "Simulation",
+            Self::FormalProof => "FormalProof",
+        }
+    }
+}
+
+/// Progress of a requirement's verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VerificationStatus {
+    NotStarted,
+    InProgress,
+    Verified,
+    Failed,
+    Deferred,
+}
+
+impl VerificationStatus {
+    pub const ALL: [Self; 5] = [Self::NotStarted, Self::InProgress, Self::Verified, Self::Failed, Self::Deferred];
+
+    /// The status `text` names, ignoring case and separators (`in_progress`).
+    pub fn parse(text: &str) -> Option<Self> {
+        let key = normalized_keyword(text);
+        Self::ALL.into_iter().find(|s| normalized_keyword(s.as_str()) == key)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotStarted => "NotStarted",
+            Self::InProgress => "InProgress",
+            Self::Verified => "Verified",
+            Self::Failed => "Failed",
+            Self::Deferred => "Deferred",
+        }
+    }
+}
+
+fn normalized_keyword(text: &str) -> String {
+    text.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_lowercase()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentInfo {
     pub id: String,
     pub name: String,
@@ -92,16 +403,20 @@ pub struct ComponentInfo {
     pub interfaces_in: Vec<InterfacePortInfo>,
     pub interfaces_out: Vec<InterfacePortInfo>,
     pub functions: Vec<String>,
+    /// Isolation mechanism separating the functions it hosts
+    /// (`isolation: "MPU partitioning"`), for freedom from interference.
+    #[serde(default)]
+    pub isolation: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterfacePortInfo {
     pub name: String,
     pub protocol: Option<String>,
     pub format: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionInfo {
     pub id: String,
     pub name: String,
@@ -109,15 +424,61 @@ pub struct FunctionInfo {
     pub outputs: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraceInfo {
     pub from: String,
     pub to: String,
     pub trace_type: String,
+    /// `trace_type` resolved to a known link kind.
+    #[serde(default)]
+    pub link_type: TraceLinkType,
     pub rationale: Option<String>,
+    #[serde(skip)]
+    pub from_span: Option<SourceSpan>,
+    #[serde(skip)]
+    pub to_span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum TraceLinkType {
+    Satisfies,
+    DerivedFrom,
+    Refines,
+    AllocatedTo,
+    VerifiedBy,
+    #[default]
+    Traces,
+    Implements,
+    DependsOn,
+}
+
+impl TraceLinkType {
+    /// The link kind of a trace keyword (`satisfies`, `derives_from`, ...);
+    /// anything unrecognised is a plain `Traces` link.
+    pub fn from_trace_type(trace_type: &str) -> Self {
+        match trace_type {
+            "satisfies" => Self::Satisfies,
+            "derives_from" => Self::DerivedFrom,
+            "refines" => Self::Refines,
+            "allocated_to" => Self::AllocatedTo,
+            "verifies" | "validates" => Self::VerifiedBy,
+            "implements" | "realizes" => Self::Implements,
+            "depends_on" => Self::DependsOn,
+            _ => Self::Traces,
+        }
+    }
+
+    /// What the trace target must be, when the link kind constrains it.
+    pub fn required_target(&self) -> Option<&'static str> {
+        match self {
+            Self::Satisfies | Self::DerivedFrom | Self::Refines | Self::VerifiedBy => Some("requirement"),
+            Self::AllocatedTo => Some("component"),
+            Self::Traces | Self::Implements | Self::DependsOn => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ElementInfo {
     pub id: String,
     pub name: String,
@@ -125,6 +486,15 @@ pub struct ElementInfo {
     /// Deterministic stable identity (UUIDv5 of the element id in the
     /// ArcLang namespace). Same id -> same uuid, everywhere, always.
     pub uuid: String,
+    /// Where the element is declared, when it came from source.
+    #[serde(skip)]
+    pub span: Option<SourceSpan>,
+    /// User-declared `attr key: value` attributes, by key.
+    #[serde(default)]
+    pub custom_attributes: BTreeMap<String, CustomValue>,
+    /// The team or person responsible for the element (`owner: "..."`).
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 impl ElementInfo {
@@ -136,6 +506,140 @@ impl ElementInfo {
             name: name.into(),
             element_type: element_type.into(),
             uuid,
+            span: None,
+            custom_attributes: BTreeMap::new(),
+            owner: None,
+        }
+    }
+
+    /// Take the custom attributes and the `owner` declared among an
+    /// element's `attributes`.
+    pub fn with_custom_attributes(mut self, attributes: &HashMap<String, AttributeValue>) -> Self {
+        self.custom_attributes = custom_attributes(attributes)
+            .filter_map(|(key, value)| Some((key.to_string(), CustomValue::from_attribute(value)?)))
+            .collect();
+        self.owner = attributes.get("owner").and_then(|v| v.as_string()).map(str::to_string);
+        self
+    }
+}
+
+/// The coarse element kinds `arclang trace --from/--to` filter on; each
+/// groups several registry `element_type`s (see [`element_kind`]).
+pub const ELEMENT_KINDS: &[&str] = &[
+    "requirement",
+    "component",
+    "function",
+    "capability",
+    "actor",
+    "interface",
+    "data",
+    "chain",
+    "scenario",
+    "state_machine",
+    "test_case",
+];
+
+/// The [`ELEMENT_KINDS`] entry an `element_type` of the registry belongs to.
+pub fn element_kind(element_type: &str) -> Option<&'static str> {
+    let kind = match element_type {
+        "Requirement" => "requirement",
+        "Component" | "SystemComponent" => "component",
+        "Function" | "SystemFunction" | "Activity" | "OperationalActivity" => "function",
+        "Mission" => "capability",
+        t if t.ends_with("Capability") => "capability",
+        "Actor" | "Entity" => "actor",
+        "InterfaceIn" | "InterfaceOut" | "ComponentPort" | "FunctionPort" | "PhysicalPort" | "PhysicalLink" => "interface",
+        "Class" | "DataType" | "Enumeration" | "ExchangeItem" => "data",
+        "FunctionalChain" | "PhysicalPath" => "chain",
+        "Scenario" => "scenario",
+        "StateMachine" => "state_machine",
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// The [`ELEMENT_KINDS`] entry a user wrote, ignoring case, separators and
+/// a plural `s` (`Requirements`, `test-case`).
+pub fn parse_element_kind(name: &str) -> Option<&'static str> {
+    let name = normalized_keyword(name);
+    let find = |name: &str| ELEMENT_KINDS.iter().copied().find(|kind| normalized_keyword(kind) == name);
+    find(&name).or_else(|| find(name.strip_suffix('s')?))
+}
+
+/// The value of a custom attribute. JSON and YAML carry plain values (a
+/// `"YYYY-MM-DD"` string is a date); binary formats such as the incremental
+/// cache, which cannot read untagged values, keep the variant tag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomValue {
+    Boolean(bool),
+    Number(f64),
+    Date(chrono::NaiveDate),
+    String(String),
+    List(Vec<CustomValue>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "CustomValue", untagged)]
+enum PlainValue {
+    Boolean(bool),
+    Number(f64),
+    Date(chrono::NaiveDate),
+    String(String),
+    List(Vec<CustomValue>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "CustomValue")]
+enum TaggedValue {
+    Boolean(bool),
+    Number(f64),
+    Date(chrono::NaiveDate),
+    String(String),
+    List(Vec<CustomValue>),
+}
+
+impl Serialize for CustomValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            PlainValue::serialize(self, serializer)
+        } else {
+            TaggedValue::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            PlainValue::deserialize(deserializer)
+        } else {
+            TaggedValue::deserialize(deserializer)
+        }
+    }
+}
+
+impl CustomValue {
+    /// `None` for nested maps, which custom attributes do not support.
+    pub fn from_attribute(value: &AttributeValue) -> Option<Self> {
+        Some(match value {
+            AttributeValue::String(s) => match chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+                Ok(date) => Self::Date(date),
+                Err(_) => Self::String(s.clone()),
+            },
+            AttributeValue::Number(n) => Self::Number(*n),
+            AttributeValue::Boolean(b) => Self::Boolean(*b),
+            AttributeValue::List(items) => Self::List(items.iter().filter_map(Self::from_attribute).collect()),
+            AttributeValue::Map(_) => return None,
+        })
+    }
+
+    pub fn to_attribute(&self) -> AttributeValue {
+        match self {
+            Self::Boolean(b) => AttributeValue::Boolean(*b),
+            Self::Number(n) => AttributeValue::Number(*n),
+            Self::Date(date) => AttributeValue::String(date.format("%Y-%m-%d").to_string()),
+            Self::String(s) => AttributeValue::String(s.clone()),
+            Self::List(items) => AttributeValue::List(items.iter().map(Self::to_attribute).collect()),
         }
     }
 }
@@ -151,6 +655,20 @@ impl RequirementInfo {
     pub fn uuid(&self) -> String {
         super::identity::element_uuid("element", &self.id)
     }
+
+    /// Carries an integrity level other than QM (ISO 26262 "quality
+    /// managed", i.e. not safety-relevant).
+    pub fn is_safety_requirement(&self) -> bool {
+        self.safety_level
+            .as_deref()
+            .map(|level| !level.trim().is_empty() && !level.eq_ignore_ascii_case("QM"))
+            .unwrap_or(false)
+    }
+
+    /// Declared `verification_status: Verified`.
+    pub fn is_declared_verified(&self) -> bool {
+        self.verification_status == Some(VerificationStatus::Verified)
+    }
 }
 
 impl FunctionInfo {
@@ -186,6 +704,189 @@ fn register_element(
     elements.insert(key, info);
 }
 
+/// Semantic id of a physical node: its `id:` attribute, else its name.
+fn physical_node_id(node: &PhysicalNode) -> String {
+    node.attributes
+        .get("id")
+        .and_then(|v| v.as_string())
+        .unwrap_or(&node.name)
+        .to_string()
+}
+
+/// Data items each function outputs and inputs, by function id: the names
+/// in its `outputs:` / `inputs:` lists (without `: Type`) and the data
+/// types of its ports.
+fn function_data_items(ast: &Model) -> HashMap<String, (Vec<String>, Vec<String>)> {
+    fn names(attributes: &HashMap<String, AttributeValue>, key: &str) -> Vec<String> {
+        match attributes.get(key) {
+            Some(AttributeValue::List(list)) => list
+                .iter()
+                .filter_map(|v| v.as_string())
+                .map(|s| s.split(':').next().unwrap_or(s).trim().to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+    fn walk_system(func: &SystemFunction, items: &mut HashMap<String, (Vec<String>, Vec<String>)>) {
+        let id = func.attributes.get("id")
+            .and_then(|v| v.as_string())
+            .unwrap_or(&func.id);
+        let mut outputs = names(&func.attributes, "outputs");
+        let mut inputs = names(&func.attributes, "inputs");
+        for port in &func.ports {
+            match port.direction {
+                PortDirection::Out => outputs.push(port.data_type.clone()),
+                PortDirection::In => inputs.push(port.data_type.clone()),
+                PortDirection::InOut => {
+                    outputs.push(port.data_type.clone());
+                    inputs.push(port.data_type.clone());
+                }
+            }
+        }
+        items.insert(id.to_string(), (outputs, inputs));
+        for sub in &func.sub_functions {
+            walk_system(sub, items);
+        }
+    }
+    fn walk_logical(comp: &LogicalComponent, items: &mut HashMap<String, (Vec<String>, Vec<String>)>) {
+        for func in &comp.functions {
+            let id = func.attributes.get("id")
+                .and_then(|v| v.as_string())
+                .unwrap_or(&func.name);
+            items.insert(
+                id.to_string(),
+                (names(&func.attributes, "outputs"), names(&func.attributes, "inputs")),
+            );
+        }
+        for sub in &comp.sub_components {
+            walk_logical(sub, items);
+        }
+    }
+
+    let mut items = HashMap::new();
+    for func in ast.system_analysis.iter().flat_map(|sa| &sa.functions) {
+        walk_system(func, &mut items);
+    }
+    for comp in ast.logical_architecture.iter().flat_map(|la| &la.components) {
+        walk_logical(comp, &mut items);
+    }
+    items
+}
+
+/// Link each pair of adjacent chain steps through a functional exchange
+/// (endpoints `Function` or `Function.port`, by id or name), else through a
+/// data item they share.
+fn chain_links(
+    involves: &[String],
+    ast: &Model,
+    elements: &HashMap<String, ElementInfo>,
+    data_items: &HashMap<String, (Vec<String>, Vec<String>)>,
+) -> Vec<ChainLink> {
+    let resolve = |endpoint: &str| -> Option<String> {
+        let root = endpoint.split('.').next().unwrap_or(endpoint);
+        if elements.contains_key(root) {
+            return Some(root.to_string());
+        }
+        elements.values().find(|e| e.name == root).map(|e| e.id.clone())
+    };
+    let exchanges: Vec<(Option<String>, Option<String>, String)> = ast
+        .system_analysis
+        .iter()
+        .flat_map(|sa| &sa.functional_exchanges)
+        .map(|exchange| {
+            let name = exchange
+                .label
+                .clone()
+                .unwrap_or_else(|| format!("{} -> {}", exchange.from_port, exchange.to_port));
+            (resolve(&exchange.from_port), resolve(&exchange.to_port), name)
+        })
+        .collect();
+
+    involves
+        .windows(2)
+        .map(|pair| {
+            let (from, to) = (&pair[0], &pair[1]);
+            let exchange = exchanges
+                .iter()
+                .find(|(f, t, _)| f.as_ref() == Some(from) && t.as_ref() == Some(to))
+                .map(|(_, _, name)| name.clone())
+                .or_else(|| {
+                    let (outputs, _) = data_items.get(from)?;
+                    let (_, inputs) = data_items.get(to)?;
+                    outputs.iter().find(|item| inputs.contains(item)).cloned()
+                });
+            ChainLink { from: from.clone(), to: to.clone(), exchange }
+        })
+        .collect()
+}
+
+/// Ports of every logical component, nested ones included.
+fn collect_ports(ast: &Model) -> Vec<PortInfo> {
+    fn walk(comp: &LogicalComponent, ports: &mut Vec<PortInfo>) {
+        let comp_id = comp.attributes.get("id")
+            .and_then(|v| v.as_string())
+            .unwrap_or(&comp.name);
+        ports.extend(comp.ports.iter().map(|port| PortInfo {
+            component: comp_id.to_string(),
+            name: port.name.clone(),
+            direction: port.direction.clone(),
+            interface: port.interface_type.clone(),
+            required: port.required,
+        }));
+        for sub in &comp.sub_components {
+            walk(sub, ports);
+        }
+    }
+    let mut ports = Vec::new();
+    for comp in ast.logical_architecture.iter().flat_map(|la| &la.components) {
+        walk(comp, &mut ports);
+    }
+    ports
+}
+
+/// Set `parent_id`/`children_ids` from the `refines` traces between
+/// requirements. A requirement refines at most one parent and is never its
+/// own ancestor; each violation is one error.
+pub(crate) fn link_requirement_hierarchy(requirements: &mut [RequirementInfo], traces: &[TraceInfo]) -> Vec<String> {
+    let index: HashMap<String, usize> = requirements.iter().enumerate().map(|(i, r)| (r.id.clone(), i)).collect();
+    let mut errors = Vec::new();
+    for trace in traces.iter().filter(|t| t.link_type == TraceLinkType::Refines) {
+        let (Some(&child), Some(&parent)) = (index.get(&trace.from), index.get(&trace.to)) else { continue };
+        match &requirements[child].parent_id {
+            Some(existing) if *existing != trace.to => errors.push(format!(
+                "requirement '{}' refines both '{}' and '{}' (a requirement has one parent)",
+                trace.from, existing, trace.to
+            )),
+            Some(_) => {}
+            None => {
+                requirements[child].parent_id = Some(trace.to.clone());
+                requirements[parent].children_ids.push(trace.from.clone());
+            }
+        }
+    }
+
+    let mut reported = std::collections::HashSet::new();
+    for req in requirements.iter() {
+        if reported.contains(&req.id) {
+            continue;
+        }
+        let mut chain = vec![req.id.as_str()];
+        let mut seen = std::collections::HashSet::from([req.id.as_str()]);
+        while let Some(parent) = requirements[index[*chain.last().unwrap()]].parent_id.as_deref() {
+            chain.push(parent);
+            if parent == req.id {
+                reported.extend(chain.iter().map(|id| id.to_string()));
+                errors.push(format!("requirement '{}' is its own ancestor: {}", req.id, chain.join(" refines ")));
+                break;
+            }
+            if !seen.insert(parent) {
+                break;
+            }
+        }
+    }
+    errors
+}
+
 /// Arcadia methodology lints: advisory checks on how the model uses the
 /// method's layers. Surfaced by `arclang check --lint`, not by every build —
 /// partial models (single-layer studies) are legitimate working states.
@@ -292,23 +993,60 @@ fn lint_functionless_components(comp: &LogicalComponent, lints: &mut Vec<String>
     }
 }
 
-pub struct SemanticAnalyzer;
+#[derive(Default)]
+pub struct SemanticAnalyzer {
+    allow_dangling: bool,
+    skip_endpoint_checks: bool,
+}
+
+/// Why semantic analysis rejected a model.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SemanticError {
+    #[error("{0}")]
+    Invalid(String),
+
+    /// Trace endpoints naming no (or more than one) element, located at the
+    /// offending reference.
+    #[error("{} unresolved trace reference(s):\n  {}", .0.len(), diagnostic::list(.0))]
+    UnresolvedTraces(Vec<Diagnostic>),
+}
+
+impl From<String> for SemanticError {
+    fn from(message: String) -> Self {
+        Self::Invalid(message)
+    }
+}
 
 impl SemanticAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Report traces to undeclared elements as warnings and leave them out of
+    /// the model, instead of failing (work-in-progress models).
+    pub fn allow_dangling(mut self, allow: bool) -> Self {
+        self.allow_dangling = allow;
+        self
+    }
+
+    /// Check exchange and link endpoints (the default). Each endpoint is
+    /// looked up by id, name and dotted path, so fast builds skip it.
+    pub fn check_endpoints(mut self, check: bool) -> Self {
+        self.skip_endpoint_checks = !check;
+        self
     }
     
-    pub fn analyze(&self, ast: &Model) -> Result<SemanticModel, String> {
+    pub fn analyze(&self, ast: &Model) -> Result<SemanticModel, SemanticError> {
         self.analyze_with_warnings(ast).map(|(model, _)| model)
     }
 
     /// Analyze and also return non-fatal diagnostics (unresolved exchange
-    /// endpoints, ...). Dangling trace references remain hard errors.
+    /// endpoints, ...). Dangling trace references remain hard errors unless
+    /// [`allow_dangling`](Self::allow_dangling) is set.
     pub fn analyze_with_warnings(
         &self,
         ast: &Model,
-    ) -> Result<(SemanticModel, Vec<String>), String> {
+    ) -> Result<(SemanticModel, Vec<String>), SemanticError> {
         let mut requirements = Vec::new();
         let mut components = Vec::new();
         let mut functions = Vec::new();
@@ -348,9 +1086,10 @@ impl SemanticAnalyzer {
                     interfaces_in: Vec::new(),
                     interfaces_out: Vec::new(),
                     functions: Vec::new(),
+                    isolation: None,
                 });
                 
-                register_element(&mut all_elements, &mut duplicate_ids, actor_id.clone(), ElementInfo::new(actor_id.clone(), actor.name.clone(), "Actor"));
+                register_element(&mut all_elements, &mut duplicate_ids, actor_id.clone(), ElementInfo::new(actor_id.clone(), actor.name.clone(), "Actor").with_custom_attributes(&actor.attributes));
             }
             
             // Register operational capabilities (realization targets for SA)
@@ -359,7 +1098,7 @@ impl SemanticAnalyzer {
                     &mut all_elements,
                     &mut duplicate_ids,
                     capability.id.clone(),
-                    ElementInfo::new(capability.id.clone(), capability.name.clone(), "OperationalCapability"),
+                    ElementInfo::new(capability.id.clone(), capability.name.clone(), "OperationalCapability").with_custom_attributes(&capability.attributes),
                 );
             }
 
@@ -369,7 +1108,10 @@ impl SemanticAnalyzer {
                     from: trace.from.clone(),
                     to: trace.to.clone(),
                     trace_type: trace.trace_type.clone(),
+                    link_type: TraceLinkType::from_trace_type(&trace.trace_type),
                     rationale: trace.attributes.get("rationale").and_then(|v| v.as_string()).map(|s| s.to_string()),
+                    from_span: trace.from_span.clone(),
+                    to_span: trace.to_span.clone(),
                 });
             }
             
@@ -422,7 +1164,7 @@ impl SemanticAnalyzer {
                         outputs,
                     });
                     
-                    register_element(&mut all_elements, &mut duplicate_ids, activity_id.clone(), ElementInfo::new(activity_id, activity.name.clone(), "Activity"));
+                    register_element(&mut all_elements, &mut duplicate_ids, activity_id.clone(), ElementInfo::new(activity_id, activity.name.clone(), "Activity").with_custom_attributes(&activity.attributes));
                 }
                 
                 components.push(ComponentInfo {
@@ -435,9 +1177,10 @@ impl SemanticAnalyzer {
                     interfaces_in: Vec::new(),
                     interfaces_out: Vec::new(),
                     functions: entity_function_ids,
+                    isolation: None,
                 });
                 
-                register_element(&mut all_elements, &mut duplicate_ids, entity.id.clone(), ElementInfo::new(entity.id.clone(), entity.name.clone(), "Entity"));
+                register_element(&mut all_elements, &mut duplicate_ids, entity.id.clone(), ElementInfo::new(entity.id.clone(), entity.name.clone(), "Entity").with_custom_attributes(&entity.attributes));
             }
             
             // Collect operational activities (recursively handle sub-activities)
@@ -471,9 +1214,10 @@ impl SemanticAnalyzer {
                     interfaces_in: Vec::new(),
                     interfaces_out: Vec::new(),
                     functions: Vec::new(),
+                    isolation: None,
                 });
                 
-                register_element(all_elements, duplicates, activity_id.clone(), ElementInfo::new(activity_id.clone(), activity.name.clone(), "OperationalActivity"));
+                register_element(all_elements, duplicates, activity_id.clone(), ElementInfo::new(activity_id.clone(), activity.name.clone(), "OperationalActivity").with_custom_attributes(&activity.attributes));
                 
                 // Recursively collect sub-activities
                 for sub_activity in &activity.sub_activities {
@@ -490,6 +1234,7 @@ impl SemanticAnalyzer {
         }
         
         // Collect requirements from system analysis
+        let mut requirement_errors = Vec::new();
         for sa in &ast.system_analysis {
             // Functional exchanges are data flows of the canonical model,
             // same as component and physical exchanges.
@@ -529,15 +1274,68 @@ impl SemanticAnalyzer {
                     .and_then(|v| v.as_string())
                     .map(|s| s.to_string());
                 
-                requirements.push(RequirementInfo {
+                let title = req.attributes.get("title")
+                    .and_then(|v| v.as_string())
+                    .map(|s| s.to_string());
+                
+                let hazard = req.attributes.get("hazard")
+                    .and_then(|v| v.as_string())
+                    .map(|s| s.to_string());
+                
+                let verification_method = ["verification", "verification_method"]
+                    .iter()
+                    .find_map(|key| req.attributes.get(*key).and_then(|v| v.as_string()))
+                    .and_then(|text| {
+                        let method = VerificationMethod::parse(text);
+                        if method.is_none() {
+                            requirement_errors.push(format!(
+                                "requirement '{}': unknown verification method '{}' (expected {})",
+                                req_id,
+                                text,
+                                VerificationMethod::ALL.map(VerificationMethod::as_str).join(", ")
+                            ));
+                        }
+                        method
+                    });
+                let verification_status = req.attributes.get("verification_status")
+                    .and_then(|v| v.as_string())
+                    .and_then(|text| {
+                        let status = VerificationStatus::parse(text);
+                        if status.is_none() {
+                            requirement_errors.push(format!(
+                                "requirement '{}': unknown verification status '{}' (expected {})",
+                                req_id,
+                                text,
+                                VerificationStatus::ALL.map(VerificationStatus::as_str).join(", ")
+                            ));
+                        }
+                        status
+                    });
+                
+                let requirement = RequirementInfo {
                     id: req_id.clone(),
+                    title,
                     description,
                     priority,
                     category,
                     safety_level,
-                });
+                    hazard,
+                    verification_method,
+                    verification_status,
+                    parent_id: None,
+                    children_ids: Vec::new(),
+                };
+                // A proof is only argued as evidence against an integrity
+                // level (ISO 26262-6, DO-333).
+                if verification_method == Some(VerificationMethod::FormalProof) && !requirement.is_safety_requirement() {
+                    requirement_errors.push(format!(
+                        "requirement '{}': FormalProof verification is only allowed on safety requirements (declare a safety_level)",
+                        req_id
+                    ));
+                }
+                requirements.push(requirement);
                 
-                register_element(&mut all_elements, &mut duplicate_ids, req_id.clone(), ElementInfo::new(req_id.clone(), req_id.clone(), "Requirement"));
+                register_element(&mut all_elements, &mut duplicate_ids, req_id.clone(), ElementInfo::new(req_id.clone(), req_id.clone(), "Requirement").with_custom_attributes(&req.attributes));
             }
             
             // Collect system components
@@ -560,6 +1358,10 @@ impl SemanticAnalyzer {
                     .and_then(|v| v.as_string())
                     .map(|s| s.to_string());
                 
+                let isolation = comp.attributes.get("isolation")
+                    .and_then(|v| v.as_string())
+                    .map(|s| s.to_string());
+                
                 components.push(ComponentInfo {
                     id: comp_id.clone(),
                     name: comp.name.clone(),
@@ -570,9 +1372,10 @@ impl SemanticAnalyzer {
                     interfaces_in: Vec::new(),
                     interfaces_out: Vec::new(),
                     functions: Vec::new(),
+                    isolation,
                 });
                 
-                register_element(&mut all_elements, &mut duplicate_ids, comp_id.clone(), ElementInfo::new(comp_id.clone(), comp.name.clone(), "SystemComponent"));
+                register_element(&mut all_elements, &mut duplicate_ids, comp_id.clone(), ElementInfo::new(comp_id.clone(), comp.name.clone(), "SystemComponent").with_custom_attributes(&comp.attributes));
             }
             
             // Collect system functions (recursively handle sub-functions)
@@ -586,7 +1389,7 @@ impl SemanticAnalyzer {
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| func.id.clone());
 
-                register_element(all_elements, duplicates, func_id.clone(), ElementInfo::new(func_id.clone(), func.name.clone(), "SystemFunction"));
+                register_element(all_elements, duplicates, func_id.clone(), ElementInfo::new(func_id.clone(), func.name.clone(), "SystemFunction").with_custom_attributes(&func.attributes));
 
                 for port in &func.ports {
                     let port_id = format!("{}.{}", func_id, port.name);
@@ -608,6 +1411,13 @@ impl SemanticAnalyzer {
                 collect_system_functions_recursive(func, &mut all_elements, &mut duplicate_ids);
             }
         }
+        if !requirement_errors.is_empty() {
+            return Err(SemanticError::Invalid(format!(
+                "{} invalid requirement(s):\n  {}",
+                requirement_errors.len(),
+                requirement_errors.join("\n  ")
+            )));
+        }
         
         // Collect components and interfaces from logical architecture
         for la in &ast.logical_architecture {
@@ -681,6 +1491,10 @@ impl SemanticAnalyzer {
                     .unwrap_or("Logical")
                     .to_string();
 
+                let isolation = comp.attributes.get("isolation")
+                    .and_then(|v| v.as_string())
+                    .map(|s| s.to_string());
+                
                 components.push(ComponentInfo {
                     id: comp_id.clone(),
                     name: comp.name.clone(),
@@ -691,9 +1505,10 @@ impl SemanticAnalyzer {
                     interfaces_in,
                     interfaces_out,
                     functions: comp_functions,
+                    isolation,
                 });
 
-                register_element(all_elements, duplicates, comp_id.clone(), ElementInfo::new(comp_id.clone(), comp.name.clone(), "Component"));
+                register_element(all_elements, duplicates, comp_id.clone(), ElementInfo::new(comp_id.clone(), comp.name.clone(), "Component").with_custom_attributes(&comp.attributes));
 
                 for interface_def in &comp.interfaces_in {
                     register_element(all_elements, duplicates, 
@@ -754,7 +1569,7 @@ impl SemanticAnalyzer {
                         outputs,
                     });
 
-                    register_element(all_elements, duplicates, func_id.clone(), ElementInfo::new(func_id.clone(), func.name.clone(), "Function"));
+                    register_element(all_elements, duplicates, func_id.clone(), ElementInfo::new(func_id.clone(), func.name.clone(), "Function").with_custom_attributes(&func.attributes));
                 }
 
                 for port in &comp.ports {
@@ -790,10 +1605,7 @@ impl SemanticAnalyzer {
             }
 
             for node in &pa.nodes {
-                let node_id = node.attributes.get("id")
-                    .and_then(|v| v.as_string())
-                    .unwrap_or(&node.name)
-                    .to_string();
+                let node_id = physical_node_id(node);
                 
                 let node_type = node.attributes.get("type")
                     .and_then(|v| v.as_string())
@@ -813,6 +1625,10 @@ impl SemanticAnalyzer {
                     .unwrap_or("Physical")
                     .to_string();
                 
+                let isolation = node.attributes.get("isolation")
+                    .and_then(|v| v.as_string())
+                    .map(|s| s.to_string());
+                
                 components.push(ComponentInfo {
                     id: node_id.clone(),
                     name: node.name.clone(),
@@ -823,9 +1639,10 @@ impl SemanticAnalyzer {
                     interfaces_in: Vec::new(),
                     interfaces_out: Vec::new(),
                     functions: Vec::new(),
+                    isolation,
                 });
                 
-                register_element(&mut all_elements, &mut duplicate_ids, node_id.clone(), ElementInfo::new(node_id.clone(), node.name.clone(), "Component"));
+                register_element(&mut all_elements, &mut duplicate_ids, node_id.clone(), ElementInfo::new(node_id.clone(), node.name.clone(), "Component").with_custom_attributes(&node.attributes));
 
                 for port in &node.ports {
                     let port_id = format!("{}.{}", node_id, port.name);
@@ -833,7 +1650,7 @@ impl SemanticAnalyzer {
                         &mut all_elements,
                         &mut duplicate_ids,
                         port_id.clone(),
-                        ElementInfo::new(port_id, port.name.clone(), "PhysicalPort"),
+                        ElementInfo::new(port_id, port.name.clone(), "PhysicalPort").with_custom_attributes(&port.attributes),
                     );
                 }
             }
@@ -849,7 +1666,10 @@ impl SemanticAnalyzer {
                 from: trace.from.clone(),
                 to: trace.to.clone(),
                 trace_type: trace.trace_type.clone(),
+                link_type: TraceLinkType::from_trace_type(&trace.trace_type),
                 rationale,
+                from_span: trace.from_span.clone(),
+                to_span: trace.to_span.clone(),
             });
         }
         
@@ -865,7 +1685,7 @@ impl SemanticAnalyzer {
                     &mut all_elements,
                     &mut duplicate_ids,
                     mission.id.clone(),
-                    ElementInfo::new(mission.id.clone(), mission.name.clone(), "Mission"),
+                    ElementInfo::new(mission.id.clone(), mission.name.clone(), "Mission").with_custom_attributes(&mission.attributes),
                 );
                 missions_info.push(MissionInfo { id: mission.id.clone(), name: mission.name.clone() });
             }
@@ -885,7 +1705,7 @@ impl SemanticAnalyzer {
                 &mut all_elements,
                 &mut duplicate_ids,
                 capability.id.clone(),
-                ElementInfo::new(capability.id.clone(), capability.name.clone(), format!("{}Capability", kind)),
+                ElementInfo::new(capability.id.clone(), capability.name.clone(), format!("{}Capability", kind)).with_custom_attributes(&capability.attributes),
             );
         }
         let chain_sources: Vec<&FunctionalChain> = ast
@@ -899,7 +1719,7 @@ impl SemanticAnalyzer {
                 &mut all_elements,
                 &mut duplicate_ids,
                 chain.id.clone(),
-                ElementInfo::new(chain.id.clone(), chain.name.clone(), "FunctionalChain"),
+                ElementInfo::new(chain.id.clone(), chain.name.clone(), "FunctionalChain").with_custom_attributes(&chain.attributes),
             );
         }
 
@@ -956,9 +1776,14 @@ impl SemanticAnalyzer {
                     id: chain.id.clone(),
                     name: chain.name.clone(),
                     involves,
+                    links: Vec::new(),
                 });
             }
         }
+        let data_items = function_data_items(ast);
+        for chain in &mut chains_info {
+            chain.links = chain_links(&chain.involves, ast, &all_elements, &data_items);
+        }
         let mut deferred_warnings: Vec<String> = Vec::new();
 
         // Data model: classes, data types, exchange items — with identity.
@@ -967,7 +1792,7 @@ impl SemanticAnalyzer {
                 &mut all_elements,
                 &mut duplicate_ids,
                 class_def.id.clone(),
-                ElementInfo::new(class_def.id.clone(), class_def.name.clone(), "Class"),
+                ElementInfo::new(class_def.id.clone(), class_def.name.clone(), "Class").with_custom_attributes(&class_def.attributes),
             );
         }
         for data_type in &ast.data_types {
@@ -1008,7 +1833,7 @@ impl SemanticAnalyzer {
                         &mut all_elements,
                         &mut duplicate_ids,
                         link.name.clone(),
-                        ElementInfo::new(link.name.clone(), link.name.clone(), "PhysicalLink"),
+                        ElementInfo::new(link.name.clone(), link.name.clone(), "PhysicalLink").with_custom_attributes(&link.attributes),
                     );
                 }
             }
@@ -1017,7 +1842,7 @@ impl SemanticAnalyzer {
                     &mut all_elements,
                     &mut duplicate_ids,
                     path.id.clone(),
-                    ElementInfo::new(path.id.clone(), path.name.clone(), "PhysicalPath"),
+                    ElementInfo::new(path.id.clone(), path.name.clone(), "PhysicalPath").with_custom_attributes(&path.attributes),
                 );
                 for link_ref in &path.involves {
                     let is_link = all_elements
@@ -1141,22 +1966,98 @@ impl SemanticAnalyzer {
         }
 
         if !reference_errors.is_empty() {
-            return Err(format!(
+            return Err(SemanticError::Invalid(format!(
                 "{} unresolved reference(s):\n  {}",
                 reference_errors.len(),
                 reference_errors.join("\n  ")
-            ));
+            )));
         }
 
         // Resolve trace endpoints. Dangling references are compile errors:
         // a trace that points at nothing must never be silently dropped.
-        let resolved_traces = Self::resolve_traces(traces, &all_elements)?;
+        let (resolved_traces, dangling) = Self::resolve_traces(traces, &all_elements, self.allow_dangling)?;
+
+        let hierarchy_errors = link_requirement_hierarchy(&mut requirements, &resolved_traces);
+        if !hierarchy_errors.is_empty() {
+            return Err(SemanticError::Invalid(format!(
+                "{} invalid requirement hierarchy link(s):\n  {}",
+                hierarchy_errors.len(),
+                hierarchy_errors.join("\n  ")
+            )));
+        }
+
+        // The same element id declared in two files of a multi-file model.
+        for redeclared in ast.redeclarations.iter().filter(|r| all_elements.contains_key(&r.name)) {
+            let location = |span: &SourceSpan| {
+                let file = span.file.as_ref().map(|f| f.display().to_string()).unwrap_or_default();
+                format!("{}:{}:{}", file, span.line, span.column)
+            };
+            duplicate_ids.push(format!(
+                "duplicate element id '{}': declared in {} and again in {}",
+                redeclared.name,
+                location(&redeclared.first),
+                location(&redeclared.again)
+            ));
+        }
 
         // Exchange endpoints are checked but only warned about for now:
         // port paths (Component.Port) are not first-class elements yet.
         let mut warnings = duplicate_ids;
+        warnings.extend(
+            dangling
+                .into_iter()
+                .map(|d| Diagnostic::at(format!("{} (trace left out)", d.message), d.span).to_string()),
+        );
         warnings.extend(deferred_warnings);
-        warnings.extend(Self::check_exchange_endpoints(ast, &all_elements));
+        if !self.skip_endpoint_checks {
+            warnings.extend(Self::check_exchange_endpoints(ast, &all_elements));
+        }
+
+        let hazards = ast
+            .safety_analysis
+            .iter()
+            .flat_map(|sa| sa.hazards.iter())
+            .map(|hazard| {
+                let text = |key: &str| hazard.attributes.get(key).and_then(|v| v.as_string()).map(|s| s.to_string());
+                HazardInfo {
+                    id: text("id").unwrap_or_else(|| hazard.name.clone()),
+                    name: hazard.name.clone(),
+                    description: text("description").unwrap_or_default(),
+                    integrity_level: ["integrity_level", "asil_level", "asil", "dal", "sil", "safety_level"]
+                        .iter()
+                        .find_map(|key| text(key)),
+                    severity: text("severity"),
+                    exposure: text("exposure"),
+                    controllability: text("controllability"),
+                    mitigations: match hazard.attributes.get("mitigations").or(hazard.attributes.get("mitigation")) {
+                        Some(AttributeValue::List(items)) => {
+                            items.iter().filter_map(|m| m.as_string().map(str::to_string)).collect()
+                        }
+                        Some(AttributeValue::String(mitigation)) => vec![mitigation.clone()],
+                        _ => Vec::new(),
+                    },
+                }
+            })
+            .collect();
+
+        let fmea_actions = ast
+            .safety_analysis
+            .iter()
+            .flat_map(|sa| sa.fmea_actions.iter())
+            .map(|action| {
+                let text = |key: &str| action.attributes.get(key).and_then(|v| v.as_string()).map(|s| s.to_string());
+                let score = |key: &str| action.attributes.get(key).and_then(|v| v.as_number()).map(|n| n as u32);
+                FmeaActionInfo {
+                    entry: action.entry.clone(),
+                    action: text("action").or_else(|| text("description")).unwrap_or_default(),
+                    owner: text("owner"),
+                    due: text("due").and_then(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+                    closed: text("status").is_some_and(|s| s.eq_ignore_ascii_case("closed")),
+                    occurrence: score("occurrence"),
+                    detection: score("detection"),
+                }
+            })
+            .collect();
 
         let name = ast
             .attributes
@@ -1164,21 +2065,39 @@ impl SemanticAnalyzer {
             .and_then(|v| v.as_string())
             .map(|s| s.to_string());
 
-        Ok((
-            SemanticModel {
-                name,
-                requirements,
-                components,
-                functions,
-                traces: resolved_traces,
-                interfaces,
-                missions: missions_info,
-                capabilities: capabilities_info,
-                functional_chains: chains_info,
-                all_elements,
-            },
-            warnings,
-        ))
+        // Point at the element's name token, else at its `id:` value.
+        for element in all_elements.values_mut() {
+            element.span = ast
+                .declarations
+                .get(&element.name)
+                .or_else(|| ast.declarations.get(&element.id))
+                .cloned();
+        }
+
+        let mut model = SemanticModel {
+            name,
+            requirements,
+            components,
+            functions,
+            traces: resolved_traces,
+            interfaces,
+            missions: missions_info,
+            capabilities: capabilities_info,
+            functional_chains: chains_info,
+            hazards,
+            fmea_actions,
+            deployments: Vec::new(),
+            ports: Vec::new(),
+            connections: Vec::new(),
+            verifications: Vec::new(),
+            all_elements,
+        };
+        model.deployments = model.resolve_deployments(ast);
+        model.ports = collect_ports(ast);
+        model.connections = model.resolve_connections(ast);
+        model.verifications = model.resolve_verifications(ast);
+
+        Ok((model, warnings))
     }
 
     /// Check that exchange/link endpoints reference known elements.
@@ -1255,35 +2174,52 @@ impl SemanticAnalyzer {
 
     /// Resolve each trace endpoint against the element registry, by id first
     /// and by (unambiguous) name second, normalizing endpoints to element ids.
-    /// Unresolved or ambiguous references are errors.
+    /// Unresolved or ambiguous references are errors; with `allow_dangling`,
+    /// traces to undeclared elements are returned as diagnostics instead.
     fn resolve_traces(
         traces: Vec<TraceInfo>,
         elements: &HashMap<String, ElementInfo>,
-    ) -> Result<Vec<TraceInfo>, String> {
+        allow_dangling: bool,
+    ) -> Result<(Vec<TraceInfo>, Vec<Diagnostic>), SemanticError> {
         // Name index: name -> ids (a name may be ambiguous)
         let mut by_name: HashMap<&str, Vec<&str>> = HashMap::new();
         for element in elements.values() {
             by_name.entry(element.name.as_str()).or_default().push(element.id.as_str());
         }
 
-        let resolve = |reference: &str, role: &str, trace: &TraceInfo| -> Result<String, String> {
+        // Err((diagnostic, dangling)): dangling references name no element at all.
+        let resolve = |reference: &str, role: &str, trace: &TraceInfo| -> Result<String, (Diagnostic, bool)> {
             if elements.contains_key(reference) {
                 return Ok(reference.to_string());
             }
+            let span = if role == "from" { &trace.from_span } else { &trace.to_span };
             match by_name.get(reference).map(Vec::as_slice) {
                 Some([single]) => Ok((*single).to_string()),
-                Some(candidates) => Err(format!(
-                    "trace '{}' {} '{}': ambiguous name, matches ids {:?} — use an id",
-                    trace.trace_type, role, reference, candidates
+                Some(candidates) => Err((
+                    Diagnostic::at(
+                        format!(
+                            "trace '{}' {} '{}': ambiguous name, matches ids {:?} — use an id",
+                            trace.trace_type, role, reference, candidates
+                        ),
+                        span.clone(),
+                    ),
+                    false,
                 )),
-                None => Err(format!(
-                    "trace '{} {} {}': unknown element '{}' ({}) — declare it or fix the reference",
-                    trace.from, trace.trace_type, trace.to, reference, role
+                None => Err((
+                    Diagnostic::at(
+                        format!(
+                            "trace '{} {} {}': unknown element '{}' ({}) — declare it or fix the reference",
+                            trace.from, trace.trace_type, trace.to, reference, role
+                        ),
+                        span.clone(),
+                    ),
+                    true,
                 )),
             }
         };
 
         let mut errors = Vec::new();
+        let mut dangling = Vec::new();
         let mut resolved = Vec::new();
         for mut trace in traces {
             match (
@@ -1296,20 +2232,21 @@ impl SemanticAnalyzer {
                     resolved.push(trace);
                 }
                 (from_result, to_result) => {
-                    errors.extend(from_result.err());
-                    errors.extend(to_result.err());
+                    for (diagnostic, is_dangling) in from_result.err().into_iter().chain(to_result.err()) {
+                        if is_dangling && allow_dangling {
+                            dangling.push(diagnostic);
+                        } else {
+                            errors.push(diagnostic);
+                        }
+                    }
                 }
             }
         }
 
         if errors.is_empty() {
-            Ok(resolved)
+            Ok((resolved, dangling))
         } else {
-            Err(format!(
-                "{} unresolved trace reference(s):\n  {}",
-                errors.len(),
-                errors.join("\n  ")
-            ))
+            Err(SemanticError::UnresolvedTraces(errors))
         }
     }
 }
@@ -1329,27 +2266,173 @@ impl SemanticModel {
             .collect()
     }
 
-    /// Change-impact analysis: everything transitively connected to the given
-    /// element through traces (both directions), exchanges/interfaces,
-    /// component-function allocations, and containment. BFS with the relation
-    /// that reached each element and its distance from the change.
-    pub fn impact_of(&self, element: &str) -> Option<Vec<ImpactEntry>> {
-        // Resolve by id first, then by unambiguous name.
-        let start_id = if self.all_elements.contains_key(element) {
-            element.to_string()
-        } else {
-            let matches: Vec<&ElementInfo> = self
-                .all_elements
-                .values()
-                .filter(|e| e.name == element)
-                .collect();
-            match matches.as_slice() {
-                [single] => single.id.clone(),
-                _ => return None,
-            }
+    /// The [`ELEMENT_KINDS`] entry of the element `id`; test cases are only
+    /// known through their verifications.
+    pub fn element_kind(&self, id: &str) -> Option<&'static str> {
+        if let Some(element) = self.all_elements.get(id) {
+            return element_kind(&element.element_type);
+        }
+        let is_test_case = self.verifications.iter().any(|v| v.test_case == id)
+            || self.traces.iter().any(|t| t.link_type == TraceLinkType::VerifiedBy && t.from == id);
+        is_test_case.then_some("test_case")
+    }
+
+    /// Traces joining an element of kind `from` to one of kind `to` (`None`:
+    /// any kind), whichever way they are written: `satisfies` points at the
+    /// requirement, `allocated_to` away from it.
+    pub fn traces_between(&self, from: Option<&str>, to: Option<&str>) -> Vec<&TraceInfo> {
+        let is = |id: &str, kind: Option<&str>| kind.is_none() || self.element_kind(id) == kind;
+        self.traces
+            .iter()
+            .filter(|t| (is(&t.from, from) && is(&t.to, to)) || (is(&t.to, from) && is(&t.from, to)))
+            .collect()
+    }
+
+    /// The element an exchange endpoint names: an id, a name, or a dotted
+    /// path whose first segment is one (`Component.Port`).
+    fn resolve_endpoint(&self, endpoint: &str) -> Option<String> {
+        if self.all_elements.contains_key(endpoint) {
+            return Some(endpoint.to_string());
+        }
+        let root = endpoint.split('.').next().unwrap_or(endpoint);
+        if self.all_elements.contains_key(root) {
+            return Some(root.to_string());
+        }
+        self.all_elements
+            .values()
+            .find(|e| e.name == endpoint || e.name == root)
+            .map(|e| e.id.clone())
+    }
+
+    /// Deployments of logical components onto physical nodes. Unresolved
+    /// `deploys` targets are left out (they are warned about separately).
+    fn resolve_deployments(&self, ast: &Model) -> Vec<DeploymentInfo> {
+        let mut deployments: Vec<DeploymentInfo> = ast
+            .physical_architecture
+            .iter()
+            .flat_map(|pa| &pa.nodes)
+            .flat_map(|node| {
+                let node_id = physical_node_id(node);
+                node.deployments.iter().filter_map(move |deployment| {
+                    Some(DeploymentInfo {
+                        component: self.resolve_endpoint(&deployment.component)?,
+                        node: node_id.clone(),
+                    })
+                })
+            })
+            .collect();
+        let level = |id: &str| self.get_component(id).map(|c| c.level.as_str());
+        deployments.extend(
+            self.traces
+                .iter()
+                .filter(|t| t.link_type == TraceLinkType::AllocatedTo)
+                .filter(|t| level(&t.from) == Some("Logical") && level(&t.to) == Some("Physical"))
+                .map(|t| DeploymentInfo { component: t.from.clone(), node: t.to.clone() }),
+        );
+        deployments.sort();
+        deployments.dedup();
+        deployments
+    }
+
+    /// Component exchanges joining two declared ports. Exchanges between
+    /// components, or naming an undeclared port, are left to `interfaces`.
+    fn resolve_connections(&self, ast: &Model) -> Vec<PortConnection> {
+        let port = |endpoint: &str| -> Option<(String, String)> {
+            let (component, port) = endpoint.rsplit_once('.')?;
+            let component = self
+                .components
+                .iter()
+                .find(|c| c.id == component)
+                .or_else(|| self.components.iter().find(|c| c.name == component))?;
+            let port = self.get_port(&component.id, port)?;
+            Some((port.component.clone(), port.name.clone()))
         };
+        ast.logical_architecture
+            .iter()
+            .flat_map(|la| &la.component_exchanges)
+            .filter_map(|exchange| {
+                let (from_component, from_port) = port(&exchange.from_port)?;
+                let (to_component, to_port) = port(&exchange.to_port)?;
+                Some(PortConnection {
+                    from_component,
+                    from_port,
+                    to_component,
+                    to_port,
+                    label: exchange.label.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// `test_case` verifications of known requirements, by id or name.
+    /// Unknown requirements are left out.
+    fn resolve_verifications(&self, ast: &Model) -> Vec<VerificationInfo> {
+        let requirement = |reference: &str| -> Option<String> {
+            self.requirements
+                .iter()
+                .find(|r| r.id == reference)
+                .or_else(|| self.requirements.iter().find(|r| r.title.as_deref() == Some(reference)))
+                .map(|r| r.id.clone())
+        };
+        ast.test_cases
+            .iter()
+            .flat_map(|tc| {
+                let test_case = if tc.id.is_empty() { &tc.name } else { &tc.id };
+                tc.verifies.iter().filter_map(move |reference| {
+                    Some(VerificationInfo {
+                        test_case: test_case.clone(),
+                        requirement: requirement(reference)?,
+                        method: tc.method.clone(),
+                    })
+                })
+            })
+            .collect()
+    }
+
+    pub fn get_port(&self, component: &str, name: &str) -> Option<&PortInfo> {
+        self.ports.iter().find(|p| p.component == component && p.name == name)
+    }
+
+    /// Directed element-to-element dependencies: every trace, and every
+    /// interface between two known elements. Sorted, without duplicates.
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        let traces = self.traces.iter().map(|trace| Dependency {
+            from: trace.from.clone(),
+            to: trace.to.clone(),
+            relation: trace.trace_type.clone(),
+        });
+        let interfaces = self.interfaces.iter().filter_map(|interface| {
+            Some(Dependency {
+                from: self.resolve_endpoint(&interface.from)?,
+                to: self.resolve_endpoint(&interface.to)?,
+                relation: format!("exchange '{}'", interface.name),
+            })
+        });
+        let mut dependencies: Vec<Dependency> = traces.chain(interfaces).collect();
+        dependencies.sort();
+        dependencies.dedup();
+        dependencies
+    }
+
+    /// Resolve an element by id first, then by unambiguous name.
+    pub fn resolve_element(&self, element: &str) -> Option<String> {
+        if self.all_elements.contains_key(element) {
+            return Some(element.to_string());
+        }
+        let matches: Vec<&ElementInfo> = self
+            .all_elements
+            .values()
+            .filter(|e| e.name == element)
+            .collect();
+        match matches.as_slice() {
+            [single] => Some(single.id.clone()),
+            _ => None,
+        }
+    }
 
-        // Adjacency: element id -> [(neighbor id, relation description)]
+    /// The undirected relation graph impact analysis walks: element id ->
+    /// [(neighbor id, relation description)].
+    fn impact_graph(&self) -> HashMap<String, Vec<(String, String)>> {
         let mut graph: HashMap<String, Vec<(String, String)>> = HashMap::new();
         let mut link = |graph: &mut HashMap<String, Vec<(String, String)>>,
                         a: &str,
@@ -1369,19 +2452,7 @@ impl SemanticModel {
                 &format!("is {}d by (trace)", trace.trace_type.trim_end_matches('s')),
             );
         }
-        let resolve_endpoint = |endpoint: &str| -> Option<String> {
-            if self.all_elements.contains_key(endpoint) {
-                return Some(endpoint.to_string());
-            }
-            let root = endpoint.split('.').next().unwrap_or(endpoint);
-            if self.all_elements.contains_key(root) {
-                return Some(root.to_string());
-            }
-            self.all_elements
-                .values()
-                .find(|e| e.name == endpoint || e.name == root)
-                .map(|e| e.id.clone())
-        };
+        let resolve_endpoint = |endpoint: &str| self.resolve_endpoint(endpoint);
         for interface in &self.interfaces {
             if let (Some(from), Some(to)) =
                 (resolve_endpoint(&interface.from), resolve_endpoint(&interface.to))
@@ -1390,6 +2461,22 @@ impl SemanticModel {
                 link(&mut graph, &from, &to, &label, &label);
             }
         }
+        for connection in &self.connections {
+            let label = format!(
+                "connection '{}.{} -> {}.{}'",
+                connection.from_component, connection.from_port, connection.to_component, connection.to_port
+            );
+            link(&mut graph, &connection.from_component, &connection.to_component, &label, &label);
+        }
+        for deployment in &self.deployments {
+            link(
+                &mut graph,
+                &deployment.component,
+                &deployment.node,
+                "is deployed on (allocation)",
+                "hosts (allocation)",
+            );
+        }
         for component in &self.components {
             for function_ref in &component.functions {
                 if let Some(function_id) = resolve_endpoint(function_ref) {
@@ -1432,12 +2519,22 @@ impl SemanticModel {
             }
         }
 
-        // BFS
+        graph
+    }
+
+    /// BFS from `start_id`, at most `max_depth` hops when given: each element
+    /// reached, with its distance, the relation and the element it was
+    /// reached from.
+    fn reachable(&self, start_id: &str, max_depth: Option<u32>) -> HashMap<String, (u32, String, String)> {
+        let graph = self.impact_graph();
         let mut visited: HashMap<String, (u32, String, String)> = HashMap::new();
         let mut queue = std::collections::VecDeque::new();
-        queue.push_back((start_id.clone(), 0u32));
-        visited.insert(start_id.clone(), (0, String::new(), String::new()));
+        queue.push_back((start_id.to_string(), 0u32));
+        visited.insert(start_id.to_string(), (0, String::new(), String::new()));
         while let Some((current, depth)) = queue.pop_front() {
+            if max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
             if let Some(neighbors) = graph.get(&current) {
                 for (neighbor, relation) in neighbors {
                     if !visited.contains_key(neighbor) {
@@ -1450,6 +2547,57 @@ impl SemanticModel {
                 }
             }
         }
+        visited
+    }
+
+    /// The part of the model within `depth` relation hops of `root` (id or
+    /// unambiguous name), as a model of its own for the diagram generators.
+    /// Elements outside the scope, and links leaving it, are left out.
+    pub fn scoped(&self, root: &str, depth: u32) -> Option<SemanticModel> {
+        let scope = self.scope(root, depth)?;
+        let within = |id: &str| scope.contains_key(id);
+        let endpoint_within = |endpoint: &str| self.resolve_endpoint(endpoint).is_some_and(|id| within(&id));
+
+        let mut model = self.clone();
+        model.requirements.retain(|r| within(&r.id));
+        model.components.retain(|c| within(&c.id));
+        model.functions.retain(|f| within(&f.id));
+        model.traces.retain(|t| within(&t.from) && within(&t.to));
+        model.interfaces.retain(|i| endpoint_within(&i.from) && endpoint_within(&i.to));
+        model.missions.retain(|m| within(&m.id));
+        model.capabilities.retain(|c| within(&c.id));
+        model.functional_chains.retain(|c| within(&c.id));
+        model.hazards.retain(|h| within(&h.id));
+        model.deployments.retain(|d| within(&d.component) && within(&d.node));
+        model.ports.retain(|p| within(&p.component));
+        model.connections.retain(|c| within(&c.from_component) && within(&c.to_component));
+        model.verifications.retain(|v| within(&v.requirement));
+        model.all_elements.retain(|id, _| within(id));
+        Some(model)
+    }
+
+    /// `ast`, the model this one was analyzed from, pruned to the scope of
+    /// [`Self::scoped`] (see [`Model::pruned`]).
+    pub fn scoped_ast(&self, ast: &Model, root: &str, depth: u32) -> Option<Model> {
+        let scope = self.scope(root, depth)?;
+        Some(ast.pruned(&|reference| {
+            self.resolve_endpoint(reference).is_some_and(|id| scope.contains_key(&id))
+        }))
+    }
+
+    fn scope(&self, root: &str, depth: u32) -> Option<HashMap<String, (u32, String, String)>> {
+        let root_id = self.resolve_element(root)?;
+        Some(self.reachable(&root_id, Some(depth)))
+    }
+
+    /// Change-impact analysis: everything transitively connected to the given
+    /// element through traces (both directions), exchanges/interfaces, port
+    /// connections, component-function and deployment allocations, and
+    /// containment. BFS with the relation
+    /// that reached each element and its distance from the change.
+    pub fn impact_of(&self, element: &str) -> Option<Vec<ImpactEntry>> {
+        let start_id = self.resolve_element(element)?;
+        let visited = self.reachable(&start_id, None);
 
         let mut entries: Vec<ImpactEntry> = visited
             .into_iter()
@@ -1479,25 +2627,414 @@ impl SemanticModel {
     }
     
     pub fn validate_traceability(&self) -> Vec<String> {
+        self.traceability_issues()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+            .map(|issue| issue.diagnostic.message)
+            .collect()
+    }
+    
+    /// Traceability defects that make the model inconsistent: a requirement
+    /// cannot (transitively) derive from or refine itself.
+    pub fn traceability_errors(&self) -> Vec<String> {
+        self.traceability_issues()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| issue.diagnostic.message)
+            .collect()
+    }
+    
+    /// A test case or `verified_by` trace covers requirement `id`.
+    fn has_verification_evidence(&self, id: &str) -> bool {
+        self.verifications.iter().any(|v| v.requirement == id)
+            || self.traces.iter().any(|t| t.link_type == TraceLinkType::VerifiedBy && t.to == id)
+    }
+
+    /// Every requirement's effective verification status. A leaf has its
+    /// declared status, else `Verified` when a test case or `verified_by`
+    /// trace covers it, else `NotStarted`. A requirement refined by others
+    /// rolls its children up: `Verified` once all of them are, `Failed` if
+    /// any is, `Deferred` if all are, `NotStarted` if none has started, and
+    /// `InProgress` otherwise.
+    pub fn rollup_status(&self) -> BTreeMap<String, VerificationStatus> {
+        fn visit(
+            model: &SemanticModel,
+            req: &RequirementInfo,
+            statuses: &mut BTreeMap<String, VerificationStatus>,
+            path: &mut Vec<String>,
+        ) -> VerificationStatus {
+            if let Some(status) = statuses.get(&req.id) {
+                return *status;
+            }
+            // Analysis rejects hierarchy cycles; a hand-built model may not
+            if path.contains(&req.id) {
+                return VerificationStatus::NotStarted;
+            }
+            path.push(req.id.clone());
+            let children: Vec<VerificationStatus> = req
+                .children_ids
+                .iter()
+                .filter_map(|id| model.requirements.iter().find(|r| r.id == *id))
+                .map(|child| visit(model, child, statuses, path))
+                .collect();
+            path.pop();
+            let all = |status| children.iter().all(|s| *s == status);
+            let status = if children.is_empty() {
+                req.verification_status.unwrap_or(if model.has_verification_evidence(&req.id) {
+                    VerificationStatus::Verified
+                } else {
+                    VerificationStatus::NotStarted
+                })
+            } else if children.contains(&VerificationStatus::Failed) {
+                VerificationStatus::Failed
+            } else if all(VerificationStatus::Verified) {
+                VerificationStatus::Verified
+            } else if all(VerificationStatus::Deferred) {
+                VerificationStatus::Deferred
+            } else if all(VerificationStatus::NotStarted) {
+                VerificationStatus::NotStarted
+            } else {
+                VerificationStatus::InProgress
+            };
+            statuses.insert(req.id.clone(), status);
+            status
+        }
+
+        let mut statuses = BTreeMap::new();
+        for req in &self.requirements {
+            visit(self, req, &mut statuses, &mut Vec::new());
+        }
+        statuses
+    }
+
+    /// Dead elements, requirements then components in declaration order:
+    /// requirements no trace or test case reaches, and logical or physical
+    /// components no trace, port connection, interface or deployment
+    /// reaches. Top-level stakeholder requirements (`category:
+    /// "Stakeholder"`) stand alone by design and are left out, as are root
+    /// components (the system of interest of the system analysis) and the
+    /// operational actors, entities and activities around it.
+    pub fn unreferenced_elements(&self) -> Vec<(String, ElementType)> {
+        let traced = |id: &str| self.traces.iter().any(|t| t.from == id || t.to == id);
+        let top_level_stakeholder = |r: &RequirementInfo| {
+            r.parent_id.is_none() && r.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case("stakeholder"))
+        };
+        let requirements = self
+            .requirements
+            .iter()
+            .filter(|r| !top_level_stakeholder(r))
+            .filter(|r| !traced(&r.id) && !self.verifications.iter().any(|v| v.requirement == r.id))
+            .map(|r| (r.id.clone(), ElementType::Requirement));
+        let components = self
+            .components
+            .iter()
+            .filter(|c| c.level != "System" && c.level != "Operational")
+            .filter(|c| {
+                // Interface endpoints are written by id or name, maybe as Component.port
+                let names = |endpoint: &str| {
+                    let root = endpoint.split('.').next().unwrap_or(endpoint);
+                    root == c.id || root == c.name
+                };
+                !traced(&c.id)
+                    && !self.connections.iter().any(|x| x.from_component == c.id || x.to_component == c.id)
+                    && !self.interfaces.iter().any(|i| names(&i.from) || names(&i.to))
+                    && !self.deployments.iter().any(|d| d.component == c.id || d.node == c.id)
+            })
+            .map(|c| (c.id.clone(), ElementType::Component));
+        requirements.chain(components).collect()
+    }
+
+    /// Requirement-to-component coverage gaps: requirements no component
+    /// satisfies or no test case verifies, and logical or physical
+    /// components that satisfy no requirement. Gaps on safety requirements
+    /// (those with a `safety_level`) are more severe.
+    ///
+    /// A component satisfies a requirement through a `satisfies` or
+    /// `implements` trace to it, or the requirement's `allocated_to` trace
+    /// to the component; a requirement is verified by a `verifies` trace or
+    /// a `test_case`, or once it rolls up to `Verified` (see
+    /// [`Self::rollup_status`]).
+    pub fn coverage_gaps(&self) -> Vec<CoverageGap> {
+        let is_component = |id: &str| self.get_component(id).is_some();
+        let is_requirement = |id: &str| self.requirements.iter().any(|r| r.id == id);
+        let realizations: Vec<(&str, &str)> = self
+            .traces
+            .iter()
+            .filter_map(|t| match t.link_type {
+                TraceLinkType::Satisfies | TraceLinkType::Implements => Some((t.to.as_str(), t.from.as_str())),
+                TraceLinkType::AllocatedTo => Some((t.from.as_str(), t.to.as_str())),
+                _ => None,
+            })
+            .filter(|(requirement, component)| is_requirement(requirement) && is_component(component))
+            .collect();
+
+        let rollup = self.rollup_status();
+        let mut gaps = Vec::new();
+        for req in &self.requirements {
+            let safety = req.safety_level.is_some();
+            let rating = req.safety_level.as_deref().map(|l| format!(" ({})", l)).unwrap_or_default();
+            if !realizations.iter().any(|(requirement, _)| *requirement == req.id) {
+                gaps.push(CoverageGap {
+                    requirement_id: req.id.clone(),
+                    gap_type: GapType::NoTraceToDesign,
+                    severity: if safety { GapSeverity::Critical } else { GapSeverity::Medium },
+                    description: format!("Requirement {}{} is satisfied by no component", req.id, rating),
+                });
+            }
+            let verified = req.is_declared_verified()
+                || self.has_verification_evidence(&req.id)
+                || rollup.get(&req.id) == Some(&VerificationStatus::Verified);
+            if !verified {
+                gaps.push(CoverageGap {
+                    requirement_id: req.id.clone(),
+                    gap_type: GapType::NoVerification,
+                    severity: if safety { GapSeverity::High } else { GapSeverity::Low },
+                    description: format!("Requirement {}{} is verified by no test case", req.id, rating),
+                });
+            }
+        }
+        for comp in self.components.iter().filter(|c| c.level == "Logical" || c.level == "Physical") {
+            if !realizations.iter().any(|(_, component)| *component == comp.id) {
+                gaps.push(CoverageGap {
+                    requirement_id: comp.id.clone(),
+                    gap_type: GapType::NoRequirement,
+                    severity: if comp.safety_level.is_some() || comp.asil.is_some() {
+                        GapSeverity::Medium
+                    } else {
+                        GapSeverity::Low
+                    },
+                    description: format!("{} component {} satisfies no requirement", comp.level, comp.id),
+                });
+            }
+        }
+        gaps
+    }
+
+    /// Every traceability finding with its severity and stable code.
+    pub fn traceability_issues(&self) -> Vec<TraceabilityIssue> {
         let mut issues = Vec::new();
+        let mut push =
+            |severity, code, lint, diagnostic| issues.push(TraceabilityIssue { severity, code, lint, diagnostic });
         
         // Check for requirements without downstream traces
         for req in &self.requirements {
             if self.get_traces_from(&req.id).is_empty() {
-                issues.push(format!("Requirement {} has no downstream traces", req.id));
+                push(
+                    Severity::Warning,
+                    "E-TRACE-ORPHAN",
+                    "orphan-requirement",
+                    Diagnostic::new(format!("Requirement {} has no downstream traces", req.id)),
+                );
             }
         }
         
         // Check for components without upstream traces
         for comp in &self.components {
             if self.get_traces_to(&comp.id).is_empty() {
-                issues.push(format!("Component {} has no upstream traces", comp.id));
+                push(
+                    Severity::Warning,
+                    "E-TRACE-ORPHAN",
+                    "orphan-component",
+                    Diagnostic::new(format!("Component {} has no upstream traces", comp.id)),
+                );
             }
         }
         
+        // Once there is a physical architecture, every logical component
+        // must be deployed on one of its nodes
+        if self.components.iter().any(|c| c.level == "Physical") {
+            for comp in self.components.iter().filter(|c| c.level == "Logical") {
+                if !self.deployments.iter().any(|d| d.component == comp.id) {
+                    push(
+                        Severity::Warning,
+                        "E-NOT-DEPLOYED",
+                        "not-deployed",
+                        Diagnostic::at(
+                            format!("Logical component {} is not deployed to any physical node", comp.id),
+                            self.all_elements.get(&comp.id).and_then(|e| e.span.clone()),
+                        ),
+                    );
+                }
+            }
+        }
+        
+        // Connections run from an out port to an in port, and every
+        // required port is connected
+        for connection in &self.connections {
+            let from = self.get_port(&connection.from_component, &connection.from_port);
+            let to = self.get_port(&connection.to_component, &connection.to_port);
+            let mismatch = match (from.map(|p| &p.direction), to.map(|p| &p.direction)) {
+                (Some(PortDirection::In), _) => Some("its source is an in port"),
+                (_, Some(PortDirection::Out)) => Some("its target is an out port"),
+                _ => None,
+            };
+            if let Some(mismatch) = mismatch {
+                push(
+                    Severity::Warning,
+                    "E-PORT-DIRECTION",
+                    "port-direction",
+                    Diagnostic::new(format!(
+                        "Connection {}.{} -> {}.{}: {}",
+                        connection.from_component, connection.from_port,
+                        connection.to_component, connection.to_port,
+                        mismatch
+                    )),
+                );
+            }
+        }
+        for port in self.ports.iter().filter(|p| p.required) {
+            let connected = self.connections.iter().any(|c| {
+                (c.from_component == port.component && c.from_port == port.name)
+                    || (c.to_component == port.component && c.to_port == port.name)
+            });
+            if !connected {
+                push(
+                    Severity::Warning,
+                    "E-PORT-UNCONNECTED",
+                    "port-unconnected",
+                    Diagnostic::at(
+                        format!(
+                            "Required interface {}.{} ({}) is not connected",
+                            port.component, port.name, port.interface
+                        ),
+                        self.all_elements.get(&port.component).and_then(|e| e.span.clone()),
+                    ),
+                );
+            }
+        }
+        
+        // Functional chains are sequences of functions, each passing data
+        // to the next
+        let is_function = |id: &str| {
+            self.all_elements.get(id).is_some_and(|e| {
+                matches!(e.element_type.as_str(), "Function" | "SystemFunction" | "Activity" | "OperationalActivity")
+            })
+        };
+        for chain in &self.functional_chains {
+            for step in chain.involves.iter().filter(|id| !is_function(id)) {
+                let found = self.all_elements.get(step).map(|e| e.element_type.as_str()).unwrap_or("unknown");
+                push(
+                    Severity::Warning,
+                    "E-CHAIN-STEP",
+                    "chain-step",
+                    Diagnostic::at(
+                        format!("Functional chain {} involves {}, a {} rather than a function", chain.id, step, found),
+                        self.all_elements.get(&chain.id).and_then(|e| e.span.clone()),
+                    ),
+                );
+            }
+            for link in &chain.links {
+                if link.exchange.is_none() && is_function(&link.from) && is_function(&link.to) {
+                    push(
+                        Severity::Warning,
+                        "E-CHAIN-GAP",
+                        "chain-gap",
+                        Diagnostic::at(
+                            format!(
+                                "Functional chain {}: no data exchange from {} to {}",
+                                chain.id, link.from, link.to
+                            ),
+                            self.all_elements.get(&chain.id).and_then(|e| e.span.clone()),
+                        ),
+                    );
+                }
+            }
+        }
+        
+        // Check that typed links point at the right kind of element
+        for trace in &self.traces {
+            let Some(required) = trace.link_type.required_target() else {
+                continue;
+            };
+            let matches = match required {
+                "requirement" => self.requirements.iter().any(|r| r.id == trace.to),
+                _ => self.components.iter().any(|c| c.id == trace.to),
+            };
+            if !matches {
+                let found = self
+                    .all_elements
+                    .get(&trace.to)
+                    .map(|e| e.element_type.as_str())
+                    .unwrap_or("an unknown element");
+                push(
+                    Severity::Warning,
+                    "E-TRACE-ENDPOINT",
+                    "trace-endpoint",
+                    Diagnostic::at(
+                        format!(
+                            "Trace {} {} {}: target must be a {}, found {}",
+                            trace.from, trace.trace_type, trace.to, required, found
+                        ),
+                        trace.to_span.clone(),
+                    ),
+                );
+            }
+        }
+        
+        // Circular derivations are errors; circular dependency/traces chains
+        // only warn
+        for cycle in self.trace_cycles() {
+            let (severity, message) = if cycle.is_error {
+                (Severity::Error, format!("Circular derivation: {}", cycle.describe()))
+            } else {
+                (Severity::Warning, format!("Trace cycle: {}", cycle.describe()))
+            };
+            push(severity, "E-TRACE-CYCLE", "trace-cycle", Diagnostic::new(message));
+        }
+        
         issues
     }
     
+    /// Cycles in the graph of `derives_from`/`refines`/`traces`/`depends_on`
+    /// links, found by depth-first search (one per back edge).
+    pub fn trace_cycles(&self) -> Vec<TraceCycle> {
+        let mut edges: std::collections::BTreeMap<&str, Vec<&TraceInfo>> = std::collections::BTreeMap::new();
+        for trace in &self.traces {
+            if matches!(
+                trace.link_type,
+                TraceLinkType::DerivedFrom | TraceLinkType::Refines | TraceLinkType::Traces | TraceLinkType::DependsOn
+            ) {
+                edges.entry(trace.from.as_str()).or_default().push(trace);
+            }
+        }
+        
+        // `false` while the node is on the DFS path, `true` once finished
+        fn visit<'a>(
+            node: &'a str,
+            edges: &std::collections::BTreeMap<&'a str, Vec<&'a TraceInfo>>,
+            finished: &mut HashMap<&'a str, bool>,
+            path: &mut Vec<&'a TraceInfo>,
+            cycles: &mut Vec<TraceCycle>,
+        ) {
+            finished.insert(node, false);
+            for trace in edges.get(node).into_iter().flatten() {
+                match finished.get(trace.to.as_str()) {
+                    None => {
+                        path.push(trace);
+                        visit(&trace.to, edges, finished, path, cycles);
+                        path.pop();
+                    }
+                    Some(false) => {
+                        let start = path.iter().position(|t| t.from == trace.to).unwrap_or(path.len());
+                        let links: Vec<&TraceInfo> = path[start..].iter().copied().chain([*trace]).collect();
+                        cycles.push(TraceCycle::from_links(&links));
+                    }
+                    Some(true) => {}
+                }
+            }
+            finished.insert(node, true);
+        }
+        
+        let mut finished = HashMap::new();
+        let mut cycles = Vec::new();
+        for node in edges.keys() {
+            if !finished.contains_key(node) {
+                visit(node, &edges, &mut finished, &mut Vec::new(), &mut cycles);
+            }
+        }
+        cycles
+    }
+    
     pub fn compute_metrics(&self) -> ModelMetrics {
         let total_elements = self.requirements.len() + self.components.len() + self.functions.len();
         
@@ -1513,6 +3050,11 @@ impl SemanticModel {
             0.0
         };
         
+        let mut components_by_level = BTreeMap::new();
+        for component in &self.components {
+            *components_by_level.entry(component.level.clone()).or_insert(0) += 1;
+        }
+
         ModelMetrics {
             total_elements,
             requirements_count: self.requirements.len(),
@@ -1520,8 +3062,121 @@ impl SemanticModel {
             functions_count: self.functions.len(),
             traces_count: self.traces.len(),
             traceability_coverage,
+            components_by_level,
+        }
+    }
+}
+
+/// One finding of `SemanticModel::coverage_gaps`, also produced by the
+/// requirements-management integrations for their own baselines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageGap {
+    /// The requirement the gap is about; the component for
+    /// `GapType::NoRequirement`.
+    pub requirement_id: String,
+    pub gap_type: GapType,
+    pub severity: GapSeverity,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GapType {
+    NoTraceToDesign,
+    NoVerification,
+    NoImplementation,
+    OrphanRequirement,
+    BrokenTraceLink,
+    /// A design component that satisfies no requirement.
+    NoRequirement,
+}
+
+impl GapType {
+    pub const ALL: [GapType; 6] = [
+        Self::NoTraceToDesign,
+        Self::NoVerification,
+        Self::NoImplementation,
+        Self::OrphanRequirement,
+        Self::BrokenTraceLink,
+        Self::NoRequirement,
+    ];
+}
+
+impl std::fmt::Display for GapType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NoTraceToDesign => "no satisfying component",
+            Self::NoVerification => "no verification",
+            Self::NoImplementation => "no implementation",
+            Self::OrphanRequirement => "orphan requirement",
+            Self::BrokenTraceLink => "broken trace link",
+            Self::NoRequirement => "component without requirement",
+        })
+    }
+}
+
+/// Most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GapSeverity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl std::fmt::Display for GapSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        })
+    }
+}
+
+/// One finding of `SemanticModel::traceability_issues`.
+#[derive(Debug, Clone)]
+pub struct TraceabilityIssue {
+    pub severity: Severity,
+    /// Stable diagnostic code (`E-TRACE-ORPHAN`, `E-TRACE-CYCLE`, ...).
+    pub code: &'static str,
+    /// The lint the `[lint]` levels name it by (`orphan-requirement`, ...).
+    pub lint: &'static str,
+    pub diagnostic: Diagnostic,
+}
+
+/// A closed chain of trace links, e.g. `A derives_from B derives_from A`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceCycle {
+    /// Element ids along the cycle; the first one is repeated at the end.
+    pub path: Vec<String>,
+    /// The trace type of each link (`path[i]` to `path[i + 1]`).
+    pub trace_types: Vec<String>,
+    /// Every link is `derives_from`/`refines`.
+    pub is_error: bool,
+}
+
+impl TraceCycle {
+    fn from_links(links: &[&TraceInfo]) -> Self {
+        let mut path: Vec<String> = links.iter().map(|t| t.from.clone()).collect();
+        path.extend(links.first().map(|t| t.from.clone()));
+        Self {
+            path,
+            trace_types: links.iter().map(|t| t.trace_type.clone()).collect(),
+            is_error: links
+                .iter()
+                .all(|t| matches!(t.link_type, TraceLinkType::DerivedFrom | TraceLinkType::Refines)),
         }
     }
+
+    /// `A derives_from B refines A`
+    pub fn describe(&self) -> String {
+        let mut text = self.path[0].clone();
+        for (trace_type, id) in self.trace_types.iter().zip(&self.path[1..]) {
+            text.push_str(&format!(" {trace_type} {id}"));
+        }
+        text
+    }
 }
 
 /// One element reached by change-impact analysis.
@@ -1546,4 +3201,15 @@ pub struct ModelMetrics {
     pub functions_count: usize,
     pub traces_count: usize,
     pub traceability_coverage: f64,
+    /// Components per Arcadia level (`Operational`, `Logical`, ...).
+    pub components_by_level: BTreeMap<String, usize>,
+}
+
+/// One edge of `SemanticModel::dependencies`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Dependency {
+    pub from: String,
+    pub to: String,
+    /// The trace type, or `exchange '<name>'` for an interface.
+    pub relation: String,
 }
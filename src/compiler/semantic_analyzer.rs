@@ -798,6 +798,7 @@ mod tests {
             interfaces_in: Vec::new(),
             interfaces_out: Vec::new(),
             functions: Vec::new(),
+            isolation: None,
         });
         model.all_elements.insert(
             "ACT-001".to_string(),
@@ -814,6 +815,7 @@ mod tests {
             interfaces_in: Vec::new(),
             interfaces_out: Vec::new(),
             functions: vec!["LF-001".to_string()],
+            isolation: None,
         });
         model.all_elements.insert(
             "LC-001".to_string(),
@@ -857,6 +859,7 @@ mod tests {
                 interfaces_in: Vec::new(),
                 interfaces_out: Vec::new(),
                 functions: Vec::new(),
+                isolation: None,
             });
             model.all_elements.insert(
                 id.to_string(),
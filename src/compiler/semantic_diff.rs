@@ -5,9 +5,15 @@
 //! a file produces an EMPTY diff; renaming an element (same id, new name)
 //! is a modification, not a remove+add. This is what turns a model review
 //! into a reviewable change list instead of a textual diff of `.arc` files.
+//!
+//! Either side may be a [`Baseline`]: a JSON snapshot of a compiled model,
+//! taken with `arclang baseline`, so a release can be diffed against later
+//! revisions without keeping its sources around.
 
 use super::semantic::SemanticModel;
-use serde::Serialize;
+use crate::collaboration::{change_tracking, IntegrityImpact};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ElementRef {
@@ -15,6 +21,9 @@ pub struct ElementRef {
     pub name: String,
     pub element_type: String,
     pub uuid: String,
+    /// A requirement or component with an integrity level other than QM,
+    /// in either version.
+    pub safety_relevant: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -45,6 +54,8 @@ pub struct DiffReport {
     pub modified: Vec<ModifiedElement>,
     pub traces_added: Vec<TraceRef>,
     pub traces_removed: Vec<TraceRef>,
+    /// What the change does to traceability and safety requirements.
+    pub integrity_impact: IntegrityImpact,
 }
 
 impl DiffReport {
@@ -123,6 +134,7 @@ pub fn diff_models(old: &SemanticModel, new: &SemanticModel) -> DiffReport {
             name: info.name.clone(),
             element_type: info.element_type.clone(),
             uuid: info.uuid.clone(),
+            safety_relevant: is_safety_relevant(old, id) || is_safety_relevant(new, id),
         }
     };
 
@@ -171,13 +183,68 @@ pub fn diff_models(old: &SemanticModel, new: &SemanticModel) -> DiffReport {
         .into_iter()
         .map(|(from, trace_type, to)| TraceRef { from, trace_type, to })
         .collect();
+    report.integrity_impact = change_tracking::diff(old, new).integrity_impact;
 
     report
 }
 
+fn is_safety_relevant(model: &SemanticModel, id: &str) -> bool {
+    let rated = |level: Option<&str>| level.is_some_and(|l| !l.eq_ignore_ascii_case("QM"));
+    model
+        .requirements
+        .iter()
+        .any(|r| r.id == id && r.is_safety_requirement())
+        || model
+            .components
+            .iter()
+            .any(|c| c.id == id && rated(c.safety_level.as_deref().or(c.asil.as_deref())))
+}
+
+/// Current [`Baseline`] snapshot format.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// A compiled model frozen at a point in time, to diff later revisions
+/// against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub baseline_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// The file the model was compiled from.
+    pub source: String,
+    pub model: SemanticModel,
+}
+
+impl Baseline {
+    pub fn capture(model: SemanticModel, source: impl Into<String>, at: DateTime<Utc>) -> Self {
+        Self {
+            baseline_version: BASELINE_VERSION,
+            created_at: at,
+            source: source.into(),
+            model,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parse a snapshot, refusing formats newer than this build.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let baseline: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if baseline.baseline_version > BASELINE_VERSION {
+            return Err(format!(
+                "baseline version {} is newer than supported version {BASELINE_VERSION}",
+                baseline.baseline_version
+            ));
+        }
+        Ok(baseline)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::collaboration::ImpactSeverity;
     use crate::compiler::{Compiler, CompilerConfig};
 
     fn compile(source: &str) -> SemanticModel {
@@ -222,6 +289,7 @@ trace "LC-001" satisfies "REQ-001" { rationale: "reworded rationale" }
 "#;
         let report = diff_models(&compile(BASE), &compile(reordered));
         assert!(report.is_empty(), "moving blocks must not be a change: {report:?}");
+        assert_eq!(report.integrity_impact.severity, ImpactSeverity::None);
     }
 
     #[test]
@@ -261,4 +329,42 @@ trace "LC-001" satisfies "REQ-001" { rationale: "reworded rationale" }
         assert_eq!(change.old, "Detect at 150 m");
         assert_eq!(change.new, "Detect at 200 m");
     }
+
+    #[test]
+    fn safety_requirement_changes_carry_their_integrity_impact() {
+        let base = BASE.replace(
+            "\"Detect at 150 m\" priority: \"High\"",
+            "\"Detect at 150 m\" priority: \"High\" safety_level: \"ASIL-B\"",
+        );
+        let changed = base
+            .replace("priority: \"High\" safety_level", "priority: \"Critical\" safety_level")
+            .replace("trace \"LC-001\" satisfies \"REQ-001\" { rationale: \"x\" }", "");
+        let report = diff_models(&compile(&base), &compile(&changed));
+
+        let modified = &report.modified[0];
+        assert!(modified.element.safety_relevant);
+        let change = &modified.changes[0];
+        assert_eq!(
+            (change.field.as_str(), change.old.as_str(), change.new.as_str()),
+            ("priority", "High", "Critical")
+        );
+        let impact = &report.integrity_impact;
+        assert!(impact.affects_safety_requirements);
+        assert_eq!(impact.orphaned_elements, vec!["REQ-001"]);
+        assert_eq!(impact.severity, ImpactSeverity::Critical);
+    }
+
+    #[test]
+    fn a_baseline_snapshot_round_trips_to_an_empty_diff() {
+        let model = compile(BASE);
+        let at = "2026-01-15T09:00:00Z".parse().unwrap();
+        let json = Baseline::capture(model.clone(), "demo.arc", at).to_json().unwrap();
+
+        let baseline = Baseline::from_json(&json).unwrap();
+        assert_eq!((baseline.source.as_str(), baseline.created_at), ("demo.arc", at));
+        assert!(diff_models(&baseline.model, &model).is_empty());
+
+        let future = json.replace("\"baseline_version\": 1", "\"baseline_version\": 99");
+        assert!(Baseline::from_json(&future).unwrap_err().contains("newer"));
+    }
 }
@@ -0,0 +1,164 @@
+//! Model health as one JSON object, for dashboards that trend it across CI
+//! builds (`arclang info --metrics --json`):
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "generated_at": "2026-03-01T12:00:00Z",
+//!   "model": "Brake System",
+//!   "elements": { "total": 12, "requirements": 4, "components": 6, "functions": 2, "traces": 5,
+//!                 "components_by_layer": { "Logical": 4, "Physical": 2 } },
+//!   "traceability_coverage": 75.0,
+//!   "requirements": { "safety": 2, "verified": 3 },
+//!   "fmea_actions": { "total": 3, "open": 1 },
+//!   "gaps": { "NoTraceToDesign": 1, "NoVerification": 1, ... }
+//! }
+//! ```
+//!
+//! Every gap type is listed, with zero when absent, so two builds always
+//! diff key by key. Adding a field keeps [`SCHEMA_VERSION`]; renaming or
+//! removing one bumps it.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::semantic::{GapType, SemanticModel};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelStats {
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    pub model: Option<String>,
+    pub elements: ElementCounts,
+    /// Percentage of requirements touched by a trace.
+    pub traceability_coverage: f64,
+    pub requirements: RequirementCounts,
+    pub fmea_actions: FmeaActionCounts,
+    /// Coverage gaps per gap type.
+    pub gaps: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ElementCounts {
+    pub total: usize,
+    pub requirements: usize,
+    pub components: usize,
+    pub functions: usize,
+    pub traces: usize,
+    /// Components per Arcadia layer (`Operational`, `Logical`, ...).
+    pub components_by_layer: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RequirementCounts {
+    /// Requirements with an integrity level other than QM.
+    pub safety: usize,
+    /// Requirements with a test case or `verified_by` trace.
+    pub verified: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FmeaActionCounts {
+    pub total: usize,
+    pub open: usize,
+}
+
+impl ModelStats {
+    /// The statistics of `model`, stamped `generated_at`.
+    pub fn collect(model: &SemanticModel, generated_at: DateTime<Utc>) -> Self {
+        let metrics = model.compute_metrics();
+        let mut gaps: BTreeMap<String, usize> =
+            GapType::ALL.iter().map(|gap_type| (format!("{gap_type:?}"), 0)).collect();
+        for gap in model.coverage_gaps() {
+            *gaps.entry(format!("{:?}", gap.gap_type)).or_insert(0) += 1;
+        }
+        let unverified = gaps.get("NoVerification").copied().unwrap_or(0);
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at,
+            model: model.name.clone(),
+            elements: ElementCounts {
+                total: metrics.total_elements,
+                requirements: metrics.requirements_count,
+                components: metrics.components_count,
+                functions: metrics.functions_count,
+                traces: metrics.traces_count,
+                components_by_layer: metrics.components_by_level,
+            },
+            traceability_coverage: metrics.traceability_coverage,
+            requirements: RequirementCounts {
+                safety: model.requirements.iter().filter(|r| r.is_safety_requirement()).count(),
+                verified: model.requirements.len() - unverified,
+            },
+            fmea_actions: FmeaActionCounts {
+                total: model.fmea_actions.len(),
+                open: model.fmea_actions.iter().filter(|a| !a.closed).count(),
+            },
+            gaps,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("model statistics serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn counts_safety_verification_fmea_actions_and_every_gap_type() {
+        let source = r#"model "Brake" {
+}
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "The system shall brake"
+        safety_level: "ASIL-B"
+    }
+    requirement "REQ-002" {
+        description: "The system shall log"
+    }
+}
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+}
+test_case "TC-001" {
+    verifies: "REQ-001"
+}
+safety_analysis {
+    fmea_action { for: "FMEA-001" action: "Add a watchdog" }
+    fmea_action { for: "FMEA-002" action: "Review" status: "closed" }
+}
+trace "LC-001" satisfies "REQ-001" { rationale: "brakes" }
+"#;
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model;
+        let at = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z").unwrap().with_timezone(&Utc);
+
+        let json: serde_json::Value = serde_json::from_str(&ModelStats::collect(&model, at).to_json()).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["generated_at"], "2026-03-01T12:00:00Z");
+        assert_eq!(json["model"], "Brake");
+        assert_eq!(json["elements"]["requirements"], 2);
+        assert_eq!(json["elements"]["components_by_layer"]["Logical"], 1);
+        assert_eq!(json["traceability_coverage"], 50.0);
+        assert_eq!(json["requirements"]["safety"], 1);
+        assert_eq!(json["requirements"]["verified"], 1);
+        assert_eq!(json["fmea_actions"]["total"], 2);
+        assert_eq!(json["fmea_actions"]["open"], 1);
+        assert_eq!(json["gaps"].as_object().unwrap().len(), GapType::ALL.len());
+        assert_eq!(json["gaps"]["NoTraceToDesign"], 1);
+        assert_eq!(json["gaps"]["NoVerification"], 1);
+        assert_eq!(json["gaps"]["BrokenTraceLink"], 0);
+    }
+}
@@ -0,0 +1,420 @@
+//! ArcLang XML (`arclang export -f xml`, `arclang import -f xml`): a plain,
+//! tool-neutral serialization of the semantic model. Capella models have
+//! their own path (`-f capella`).
+//!
+//! ```xml
+//! <?xml version="1.0" encoding="UTF-8"?>
+//! <arclang-model xmlns="urn:arclang:model:1" schemaVersion="1.0" name="Brakes">
+//!   <requirements>
+//!     <requirement id="SYS-001" priority="High" safetyLevel="ASIL_B">
+//!       <description>Stop the car</description>
+//!     </requirement>
+//!   </requirements>
+//!   <components>
+//!     <component id="LC-001" name="Controller" type="Logical" level="Logical" kind="LogicalComponent"/>
+//!   </components>
+//!   <functions>
+//!     <function id="LF-001" name="Decide"><input>speed</input><output>torque</output></function>
+//!   </functions>
+//!   <traces>
+//!     <trace from="LC-001" to="SYS-001" type="satisfies"><rationale>...</rationale></trace>
+//!   </traces>
+//! </arclang-model>
+//! ```
+//!
+//! The sections mirror the JSON target. Optional requirement attributes are
+//! `title`, `category`, `safetyLevel`, `hazard`, `verificationMethod` and
+//! `verificationStatus`;
+//! optional component attributes `safetyLevel`, `asil` and `isolation`. `kind` is the element's
+//! registry type (`Actor`, `SystemComponent`, ...).
+//!
+//! The namespace carries the schema's major version and `schemaVersion` its
+//! full version: a reader accepts any 1.x document and rejects other majors,
+//! so an incompatible schema change is detected instead of misread.
+
+use super::regenerate::{regenerate_source, ImportedSource};
+use super::semantic::{
+    link_requirement_hierarchy, ComponentInfo, ElementInfo, FunctionInfo, RequirementInfo, SemanticModel, TraceInfo, TraceLinkType,
+    VerificationMethod, VerificationStatus,
+};
+use super::CompilerError;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+
+pub const XML_NAMESPACE: &str = "urn:arclang:model:1";
+pub const SCHEMA_VERSION: &str = "1.0";
+
+pub(crate) fn esc(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// ` key="value"` for each present attribute.
+fn attributes(pairs: &[(&str, Option<&str>)]) -> String {
+    pairs
+        .iter()
+        .filter_map(|(key, value)| value.map(|v| format!(" {}=\"{}\"", key, esc(v))))
+        .collect()
+}
+
+pub fn export_xml(model: &SemanticModel) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<arclang-model xmlns=\"{}\" schemaVersion=\"{}\"{}>\n",
+        XML_NAMESPACE,
+        SCHEMA_VERSION,
+        attributes(&[("name", model.name.as_deref())])
+    ));
+
+    xml.push_str("  <requirements>\n");
+    for req in &model.requirements {
+        xml.push_str(&format!(
+            "    <requirement{}>\n      <description>{}</description>\n    </requirement>\n",
+            attributes(&[
+                ("id", Some(&req.id)),
+                ("priority", Some(&req.priority)),
+                ("title", req.title.as_deref()),
+                ("category", req.category.as_deref()),
+                ("safetyLevel", req.safety_level.as_deref()),
+                ("hazard", req.hazard.as_deref()),
+                ("verificationMethod", req.verification_method.map(VerificationMethod::as_str)),
+                ("verificationStatus", req.verification_status.map(VerificationStatus::as_str)),
+            ]),
+            esc(&req.description)
+        ));
+    }
+    xml.push_str("  </requirements>\n");
+
+    xml.push_str("  <components>\n");
+    for comp in &model.components {
+        xml.push_str(&format!(
+            "    <component{}/>\n",
+            attributes(&[
+                ("id", Some(&comp.id)),
+                ("name", Some(&comp.name)),
+                ("type", Some(&comp.component_type)),
+                ("level", Some(&comp.level)),
+                ("kind", model.all_elements.get(&comp.id).map(|e| e.element_type.as_str())),
+                ("safetyLevel", comp.safety_level.as_deref()),
+                ("asil", comp.asil.as_deref()),
+                ("isolation", comp.isolation.as_deref()),
+            ])
+        ));
+    }
+    xml.push_str("  </components>\n");
+
+    xml.push_str("  <functions>\n");
+    for func in &model.functions {
+        xml.push_str(&format!(
+            "    <function{}>",
+            attributes(&[("id", Some(&func.id)), ("name", Some(&func.name))])
+        ));
+        for input in &func.inputs {
+            xml.push_str(&format!("<input>{}</input>", esc(input)));
+        }
+        for output in &func.outputs {
+            xml.push_str(&format!("<output>{}</output>", esc(output)));
+        }
+        xml.push_str("</function>\n");
+    }
+    xml.push_str("  </functions>\n");
+
+    xml.push_str("  <traces>\n");
+    for trace in &model.traces {
+        let attrs = attributes(&[
+            ("from", Some(&trace.from)),
+            ("to", Some(&trace.to)),
+            ("type", Some(&trace.trace_type)),
+        ]);
+        match &trace.rationale {
+            Some(rationale) => xml.push_str(&format!(
+                "    <trace{}><rationale>{}</rationale></trace>\n",
+                attrs,
+                esc(rationale)
+            )),
+            None => xml.push_str(&format!("    <trace{}/>\n", attrs)),
+        }
+    }
+    xml.push_str("  </traces>\n");
+
+    xml.push_str("</arclang-model>\n");
+    xml
+}
+
+fn invalid(message: impl std::fmt::Display) -> CompilerError {
+    CompilerError::Other(format!("invalid ArcLang XML: {message}"))
+}
+
+fn attribute(element: &BytesStart, key: &str) -> Result<Option<String>, CompilerError> {
+    for attr in element.attributes() {
+        let attr = attr.map_err(invalid)?;
+        if attr.key.as_ref() == key.as_bytes() {
+            return Ok(Some(attr.normalized_value(XmlVersion::Implicit1_0).map_err(invalid)?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+fn required(element: &BytesStart, key: &str) -> Result<String, CompilerError> {
+    attribute(element, key)?.ok_or_else(|| {
+        invalid(format!(
+            "<{}> without '{}'",
+            String::from_utf8_lossy(element.name().as_ref()),
+            key
+        ))
+    })
+}
+
+/// Check the root element's namespace and schema version.
+fn check_root(root: &BytesStart) -> Result<Option<String>, CompilerError> {
+    if root.name().as_ref() != b"arclang-model" {
+        return Err(invalid(format!(
+            "root element is <{}>, not <arclang-model>",
+            String::from_utf8_lossy(root.name().as_ref())
+        )));
+    }
+    let namespace = attribute(root, "xmlns")?.unwrap_or_default();
+    if namespace != XML_NAMESPACE {
+        return Err(invalid(format!(
+            "namespace '{namespace}' is not {XML_NAMESPACE} (a different schema major version?)"
+        )));
+    }
+    let version = required(root, "schemaVersion")?;
+    let major = SCHEMA_VERSION.split('.').next();
+    if version.split('.').next() != major {
+        return Err(invalid(format!(
+            "schema version {version} is not supported (this reader handles {SCHEMA_VERSION})"
+        )));
+    }
+    attribute(root, "name")
+}
+
+/// Read an ArcLang XML document back into a semantic model.
+pub fn read_xml(content: &str) -> Result<SemanticModel, CompilerError> {
+    let mut reader = Reader::from_str(content);
+    let mut model = SemanticModel::default();
+    let mut seen_root = false;
+    // The element whose text is being read, and the text so far
+    let mut text_of: Option<Vec<u8>> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| invalid(format!("at byte {}: {e}", reader.buffer_position())))?;
+        let (element, empty) = match &event {
+            Event::Start(e) => (Some(e), false),
+            Event::Empty(e) => (Some(e), true),
+            _ => (None, false),
+        };
+        if let Some(element) = element {
+            if !seen_root {
+                model.name = check_root(element)?;
+                seen_root = true;
+                continue;
+            }
+            match element.name().as_ref() {
+                b"requirement" => {
+                    let id = required(element, "id")?;
+                    model.all_elements.insert(id.clone(), ElementInfo::new(&id, &id, "Requirement"));
+                    model.requirements.push(RequirementInfo {
+                        id,
+                        title: attribute(element, "title")?,
+                        description: String::new(),
+                        priority: attribute(element, "priority")?.unwrap_or_else(|| "Medium".to_string()),
+                        category: attribute(element, "category")?,
+                        safety_level: attribute(element, "safetyLevel")?,
+                        hazard: attribute(element, "hazard")?,
+                        verification_method: attribute(element, "verificationMethod")?
+                            .map(|m| VerificationMethod::parse(&m).ok_or_else(|| invalid(format!("unknown verificationMethod '{m}'"))))
+                            .transpose()?,
+                        verification_status: attribute(element, "verificationStatus")?
+                            .map(|s| VerificationStatus::parse(&s).ok_or_else(|| invalid(format!("unknown verificationStatus '{s}'"))))
+                            .transpose()?,
+                        parent_id: None,
+                        children_ids: Vec::new(),
+                    });
+                }
+                b"component" => {
+                    let id = required(element, "id")?;
+                    let name = required(element, "name")?;
+                    let level = required(element, "level")?;
+                    let kind = attribute(element, "kind")?.unwrap_or_else(|| format!("{level}Component"));
+                    model.all_elements.insert(id.clone(), ElementInfo::new(&id, &name, kind));
+                    model.components.push(ComponentInfo {
+                        id,
+                        name,
+                        component_type: attribute(element, "type")?.unwrap_or_else(|| level.clone()),
+                        level,
+                        safety_level: attribute(element, "safetyLevel")?,
+                        asil: attribute(element, "asil")?,
+                        interfaces_in: Vec::new(),
+                        interfaces_out: Vec::new(),
+                        functions: Vec::new(),
+                        isolation: attribute(element, "isolation")?,
+                    });
+                }
+                b"function" => {
+                    let id = required(element, "id")?;
+                    let name = attribute(element, "name")?.unwrap_or_else(|| id.clone());
+                    model.all_elements.insert(id.clone(), ElementInfo::new(&id, &name, "Function"));
+                    model.functions.push(FunctionInfo { id, name, inputs: Vec::new(), outputs: Vec::new() });
+                }
+                b"trace" => {
+                    let trace_type = required(element, "type")?;
+                    model.traces.push(TraceInfo {
+                        from: required(element, "from")?,
+                        to: required(element, "to")?,
+                        link_type: TraceLinkType::from_trace_type(&trace_type),
+                        trace_type,
+                        rationale: None,
+                        from_span: None,
+                        to_span: None,
+                    });
+                }
+                b"description" | b"rationale" | b"input" | b"output" if !empty => {
+                    text_of = Some(element.name().as_ref().to_vec());
+                    text.clear();
+                }
+                b"requirements" | b"components" | b"functions" | b"traces" => {}
+                other => {
+                    return Err(invalid(format!("unexpected element <{}>", String::from_utf8_lossy(other))));
+                }
+            }
+            continue;
+        }
+        match event {
+            Event::Text(t) if text_of.is_some() => text.push_str(&t.xml10_content().map_err(invalid)?),
+            Event::GeneralRef(r) if text_of.is_some() => match r.resolve_char_ref().map_err(invalid)? {
+                Some(ch) => text.push(ch),
+                None => {
+                    let name = r.decode().map_err(invalid)?;
+                    let entity = resolve_predefined_entity(&name)
+                        .ok_or_else(|| invalid(format!("unknown entity &{name};")))?;
+                    text.push_str(entity);
+                }
+            },
+            Event::CData(t) if text_of.is_some() => text.push_str(&String::from_utf8_lossy(&t)),
+            Event::End(e) if text_of.as_deref() == Some(e.name().as_ref()) => {
+                let value = std::mem::take(&mut text);
+                match e.name().as_ref() {
+                    b"description" => {
+                        if let Some(req) = model.requirements.last_mut() {
+                            req.description = value;
+                        }
+                    }
+                    b"rationale" => {
+                        if let Some(trace) = model.traces.last_mut() {
+                            trace.rationale = Some(value);
+                        }
+                    }
+                    b"input" => {
+                        if let Some(func) = model.functions.last_mut() {
+                            func.inputs.push(value);
+                        }
+                    }
+                    _ => {
+                        if let Some(func) = model.functions.last_mut() {
+                            func.outputs.push(value);
+                        }
+                    }
+                }
+                text_of = None;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !seen_root {
+        return Err(invalid("no <arclang-model> element"));
+    }
+    let hierarchy_errors = link_requirement_hierarchy(&mut model.requirements, &model.traces);
+    if !hierarchy_errors.is_empty() {
+        return Err(invalid(hierarchy_errors.join("; ")));
+    }
+    Ok(model)
+}
+
+/// Import an ArcLang XML document as ArcLang source.
+pub fn import_xml(content: &str) -> Result<ImportedSource, CompilerError> {
+    regenerate_source(&read_xml(content)?, Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn compile(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    #[test]
+    fn export_then_import_round_trips_without_the_capella_importer() {
+        let model = compile(
+            r#"
+operational_analysis "Ops" {
+    actor "Driver" { id: "OA-001" }
+}
+
+system_analysis "SA" {
+    requirement "SYS-001" {
+        description: "Brake when <distance> & speed demand it"
+        priority: "High"
+        safety_level: "ASIL_B"
+    }
+    system_component "Vehicle" { id: "SC-001" }
+}
+
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" isolation: "MPU partitioning" }
+}
+
+trace "LC-001" satisfies "SYS-001" { rationale: "Controls \"braking\"" }
+trace "SC-001" implements "SYS-001" { rationale: "r" }
+"#,
+        );
+
+        let xml = export_xml(&model);
+        assert!(xml.contains("<arclang-model xmlns=\"urn:arclang:model:1\" schemaVersion=\"1.0\""), "{xml}");
+
+        let read = read_xml(&xml).unwrap();
+        assert_eq!(read.requirements[0].description, "Brake when <distance> & speed demand it");
+        assert_eq!(read.traces[0].rationale.as_deref(), Some("Controls \"braking\""));
+        assert_eq!(read.components.len(), model.components.len());
+
+        let imported = import_xml(&xml).unwrap();
+        assert!(imported.warnings.is_empty(), "{:?}", imported.warnings);
+        let round_trip = compile(&imported.source);
+        assert_eq!(round_trip.requirements.len(), 1);
+        assert_eq!(round_trip.traces.len(), 2);
+        let ids = |m: &SemanticModel| {
+            let mut ids: Vec<String> = m.components.iter().map(|c| format!("{}/{}", c.level, c.id)).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(&round_trip), ids(&model));
+        assert_eq!(round_trip.get_component("LC-001").unwrap().isolation.as_deref(), Some("MPU partitioning"));
+    }
+
+    #[test]
+    fn other_namespaces_and_major_versions_are_rejected() {
+        let doc = |root: &str| format!("<?xml version=\"1.0\"?>\n{root}<requirements/></arclang-model>\n");
+
+        assert!(read_xml(&doc("<arclang-model xmlns=\"urn:arclang:model:1\" schemaVersion=\"1.3\">")).is_ok());
+        for root in [
+            "<arclang-model xmlns=\"urn:arclang:model:2\" schemaVersion=\"2.0\">",
+            "<arclang-model xmlns=\"urn:arclang:model:1\" schemaVersion=\"2.0\">",
+            "<arclang-model schemaVersion=\"1.0\">",
+        ] {
+            let err = read_xml(&doc(root)).unwrap_err();
+            assert!(matches!(err, CompilerError::Other(_)), "{root}: {err}");
+        }
+        assert!(read_xml("<capella:Project/>").is_err());
+    }
+}
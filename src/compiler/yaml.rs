@@ -0,0 +1,123 @@
+//! YAML round trip (`arclang export -f yaml`, `arclang import -f yaml`).
+//!
+//! Export writes the semantic model, maps in key order and elements by id.
+//! Import reads that structure back and regenerates ArcLang source from it
+//! (see [`regenerate_source`]).
+//! Top-level keys the model does not know are skipped with a warning, so a
+//! hand-edited or newer file still imports.
+
+use super::regenerate::{regenerate_source, ImportedSource};
+use super::semantic::SemanticModel;
+use super::CompilerError;
+
+pub fn export_yaml(model: &SemanticModel) -> Result<String, CompilerError> {
+    serde_yaml::to_string(model).map_err(|e| CompilerError::Other(format!("YAML export failed: {e}")))
+}
+
+pub fn import_yaml(content: &str) -> Result<ImportedSource, CompilerError> {
+    let invalid = |e: serde_yaml::Error| CompilerError::Other(format!("invalid YAML model: {e}"));
+    let mut document: serde_yaml::Value = serde_yaml::from_str(content).map_err(invalid)?;
+    let known = serde_yaml::to_value(SemanticModel::default()).map_err(invalid)?;
+    let mapping = document
+        .as_mapping_mut()
+        .ok_or_else(|| CompilerError::Other("invalid YAML model: expected a mapping at the top level".to_string()))?;
+
+    let mut warnings = Vec::new();
+    let unknown: Vec<serde_yaml::Value> = mapping
+        .keys()
+        .filter(|key| !known.as_mapping().is_some_and(|k| k.contains_key(*key)))
+        .cloned()
+        .collect();
+    for key in unknown {
+        warnings.push(format!("unknown top-level key {} ignored", yaml_key(&key)));
+        mapping.remove(&key);
+    }
+    let model: SemanticModel = serde_yaml::from_value(document).map_err(invalid)?;
+    regenerate_source(&model, warnings)
+}
+
+fn yaml_key(key: &serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => format!("'{s}'"),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn compile(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    #[test]
+    fn export_then_import_preserves_ids_and_counts() {
+        let model = compile(
+            r#"
+operational_analysis "Ops" {
+    actor "Driver" { id: "OA-001" }
+}
+
+system_analysis "SA" {
+    requirement "SYS-001" {
+        description: "Stop the car, \"fast\""
+        priority: "High"
+        safety_level: "ASIL_B"
+        attr safety_classification: "SC-2"
+        attr review_date: "2026-05-01"
+        owner: "chassis-team"
+    }
+    system_component "Vehicle" { id: "SC-001" attr supplier: "Acme" }
+}
+
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" safety_level: "ASIL_B" owner: "alice" }
+}
+
+trace "LC-001" satisfies "SYS-001" { rationale: "r" }
+trace "SC-001" implements "SYS-001" { rationale: "r" }
+"#,
+        );
+
+        let yaml = export_yaml(&model).unwrap();
+        let imported = import_yaml(&yaml).unwrap();
+        assert!(imported.warnings.is_empty(), "{:?}", imported.warnings);
+        let round_trip = compile(&imported.source);
+
+        assert_eq!(round_trip.requirements.len(), model.requirements.len());
+        assert_eq!(round_trip.components.len(), model.components.len());
+        assert_eq!(round_trip.traces.len(), model.traces.len());
+        let ids = |m: &SemanticModel| {
+            let mut ids: Vec<String> = m.components.iter().map(|c| format!("{}/{}", c.level, c.id)).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(&round_trip), ids(&model));
+        let req = &round_trip.requirements[0];
+        assert_eq!(req.description, "Stop the car, \"fast\"");
+        assert_eq!(req.safety_level.as_deref(), Some("ASIL_B"));
+        for id in ["SYS-001", "SC-001"] {
+            assert_eq!(round_trip.all_elements[id].custom_attributes, model.all_elements[id].custom_attributes);
+        }
+        assert_eq!(round_trip.all_elements["SYS-001"].owner.as_deref(), Some("chassis-team"));
+        assert_eq!(round_trip.all_elements["LC-001"].owner.as_deref(), Some("alice"));
+        assert!(yaml.contains("review_date: 2026-05-01"), "{yaml}");
+    }
+
+    #[test]
+    fn unknown_top_level_keys_are_warnings() {
+        let model = compile("requirements system {\n  req \"REQ-001\" \"Brake\" {\n    description: \"Brake\"\n  }\n}\n");
+        let yaml = format!("{}generator: other-tool\n", export_yaml(&model).unwrap());
+
+        let imported = import_yaml(&yaml).unwrap();
+
+        assert_eq!(imported.warnings, vec!["unknown top-level key 'generator' ignored"]);
+        assert!(imported.source.contains("requirement \"REQ-001\""), "{}", imported.source);
+        assert!(matches!(import_yaml("- just\n- a list\n"), Err(CompilerError::Other(_))));
+    }
+}
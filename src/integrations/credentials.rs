@@ -0,0 +1,128 @@
+//! Connector credentials and where their secrets come from. Any secret —
+//! `password`, `token`, `personal_access_token`, `client_secret` — is given
+//! either in place or as a reference resolved when the connector connects:
+//!
+//! ```toml
+//! [connectors.jira.auth]
+//! username = "ci@example.com"
+//! password = { env = "JIRA_TOKEN" }          # an environment variable
+//! # password = { keyring = "arclang/jira" }  # the OS credential store: service/account
+//! ```
+//!
+//! `sync.toml` is committed with the model, so its `auth` table only takes
+//! references; a secret in place belongs in the credentials file it points
+//! to. Resolved secrets are handed to the connector and never written back.
+
+use super::requirements_management::{RMAuthentication, RMError};
+use super::transport::REDACTED;
+use serde::{Deserialize, Serialize};
+
+/// Keyring service of references without one (`{ keyring = "jira" }`).
+const DEFAULT_KEYRING_SERVICE: &str = "arclang";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Secret {
+    Plain(String),
+    /// The value of an environment variable.
+    Env { env: String },
+    /// A `service/account` entry of the OS credential store.
+    Keyring { keyring: String },
+}
+
+impl Secret {
+    pub fn is_plain(&self) -> bool {
+        matches!(self, Secret::Plain(_))
+    }
+
+    pub fn resolve(&self) -> Result<String, RMError> {
+        match self {
+            Secret::Plain(value) => Ok(value.clone()),
+            Secret::Env { env } => std::env::var(env).map_err(|_| {
+                RMError::AuthenticationError(format!("environment variable {env} is not set"))
+            }),
+            Secret::Keyring { keyring } => {
+                let (service, account) = keyring.split_once('/').unwrap_or((DEFAULT_KEYRING_SERVICE, keyring));
+                ::keyring::Entry::new(service, account)
+                    .and_then(|entry| entry.get_password())
+                    .map_err(|e| RMError::AuthenticationError(format!("keyring entry {keyring}: {e}")))
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Secret::Plain(_) => write!(f, "{REDACTED:?}"),
+            Secret::Env { env } => f.debug_struct("Env").field("env", env).finish(),
+            Secret::Keyring { keyring } => f.debug_struct("Keyring").field("keyring", keyring).finish(),
+        }
+    }
+}
+
+/// A credentials file (JSON) or the `auth` table of a `sync.toml` connector.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Credentials {
+    Basic { username: String, password: Secret },
+    Token { token: Secret },
+    Pat { personal_access_token: Secret },
+    OAuth2 { client_id: String, client_secret: Secret, token_url: String },
+}
+
+impl Credentials {
+    fn secret(&self) -> &Secret {
+        match self {
+            Credentials::Basic { password: secret, .. }
+            | Credentials::Token { token: secret }
+            | Credentials::Pat { personal_access_token: secret }
+            | Credentials::OAuth2 { client_secret: secret, .. } => secret,
+        }
+    }
+
+    /// Whether the secret is written in place rather than referenced.
+    pub fn has_plain_secret(&self) -> bool {
+        self.secret().is_plain()
+    }
+
+    /// The connector authentication, its secret resolved.
+    pub fn resolve(&self) -> Result<RMAuthentication, RMError> {
+        let secret = self.secret().resolve()?;
+        Ok(match self {
+            Credentials::Basic { username, .. } => {
+                RMAuthentication::BasicAuth { username: username.clone(), password: secret }
+            }
+            Credentials::Token { .. } => RMAuthentication::APIToken { token: secret },
+            Credentials::Pat { .. } => RMAuthentication::PAT { personal_access_token: secret },
+            Credentials::OAuth2 { client_id, token_url, .. } => RMAuthentication::OAuth2 {
+                client_id: client_id.clone(),
+                client_secret: secret,
+                token_url: token_url.clone(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_resolve_from_the_environment_and_never_print() {
+        let credentials: Credentials =
+            toml::from_str("username = \"ci\"\npassword = { env = \"ARCLANG_TEST_CREDENTIALS_SECRET\" }").unwrap();
+        assert!(!credentials.has_plain_secret());
+        assert!(matches!(credentials.resolve(), Err(RMError::AuthenticationError(e)) if e.contains("not set")));
+
+        std::env::set_var("ARCLANG_TEST_CREDENTIALS_SECRET", "s3cret");
+        let auth = credentials.resolve().unwrap();
+        assert!(matches!(&auth, RMAuthentication::BasicAuth { password, .. } if password == "s3cret"));
+        assert!(!format!("{auth:?}").contains("s3cret"), "{auth:?}");
+
+        let plain: Credentials = serde_json::from_str(r#"{ "token": "s3cret" }"#).unwrap();
+        assert!(plain.has_plain_secret());
+        assert!(!format!("{plain:?}").contains("s3cret"));
+        assert!(matches!(plain.resolve().unwrap(), RMAuthentication::APIToken { token } if token == "s3cret"));
+    }
+}
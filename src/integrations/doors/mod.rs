@@ -1,12 +1,14 @@
 use async_trait::async_trait;
-use reqwest::{Client, header};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use super::requirements_management::*;
+use super::transport::{ConnectionOptions, HttpRequest, HttpTransport, ReqwestTransport};
 
 pub struct DOORSConnector {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: DOORSConfig,
     session_token: Option<String>,
 }
@@ -18,6 +20,24 @@ pub struct DOORSConfig {
     pub project: String,
     pub module: String,
     pub auth: RMAuthentication,
+    /// Object attribute holding the requirement status; modules often
+    /// rename it.
+    #[serde(default = "default_status_attribute")]
+    pub status_attribute: String,
+    /// Object attribute holding the requirement priority.
+    #[serde(default = "default_priority_attribute")]
+    pub priority_attribute: String,
+    /// Timeout, proxy and extra CA certificate for the HTTP client.
+    #[serde(default)]
+    pub connection: ConnectionOptions,
+}
+
+fn default_status_attribute() -> String {
+    "Status".to_string()
+}
+
+fn default_priority_attribute() -> String {
+    "Priority".to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -87,24 +107,14 @@ struct DOORSCreateLink {
 
 impl DOORSConnector {
     pub fn new(config: DOORSConfig) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json"),
-        );
-        
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-        
+        let transport = ReqwestTransport::with_options(Duration::from_secs(30), &config.connection);
+        Self::with_transport(config, Arc::new(transport))
+    }
+    
+    /// Connector that sends its requests through `transport`.
+    pub fn with_transport(config: DOORSConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self {
-            client,
+            transport,
             config,
             session_token: None,
         }
@@ -128,10 +138,10 @@ impl DOORSConnector {
             }
         };
         
-        let response = self.client
-            .post(&login_url)
-            .json(&credentials)
-            .send()
+        let req = HttpRequest::post(&login_url)
+            .json(&credentials);
+        let response = self.transport
+            .send(req)
             .await
             .map_err(|e| RMError::AuthenticationError(e.to_string()))?;
         
@@ -161,13 +171,13 @@ impl DOORSConnector {
     async fn get_with_auth(&self, path: &str) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.get(&url);
+        let mut req = HttpRequest::get(&url);
         
         if let Some(token) = &self.session_token {
             req = req.header("X-DOORS-Token", token);
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -175,13 +185,13 @@ impl DOORSConnector {
     async fn post_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.post(&url).json(body);
+        let mut req = HttpRequest::post(&url).json(body);
         
         if let Some(token) = &self.session_token {
             req = req.header("X-DOORS-Token", token);
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -189,13 +199,13 @@ impl DOORSConnector {
     async fn put_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.put(&url).json(body);
+        let mut req = HttpRequest::put(&url).json(body);
         
         if let Some(token) = &self.session_token {
             req = req.header("X-DOORS-Token", token);
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -203,18 +213,44 @@ impl DOORSConnector {
     async fn delete_with_auth(&self, path: &str) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.delete(&url);
+        let mut req = HttpRequest::delete(&url);
         
         if let Some(token) = &self.session_token {
             req = req.header("X-DOORS-Token", token);
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
     
     fn convert_to_requirement(&self, doors_obj: DOORSObject) -> Requirement {
+        let attribute = |name: &str| {
+            doors_obj.attributes.iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .and_then(|(_, value)| value.as_str())
+        };
+        let status = match attribute(&self.config.status_attribute) {
+            Some(status) => self.map_status(status),
+            None => {
+                tracing::debug!(
+                    "DOORS object {} has no '{}' attribute; assuming Approved",
+                    doors_obj.id, self.config.status_attribute
+                );
+                RequirementStatus::Approved
+            }
+        };
+        let priority = match attribute(&self.config.priority_attribute) {
+            Some(priority) => self.map_priority(priority),
+            None => {
+                tracing::debug!(
+                    "DOORS object {} has no '{}' attribute; assuming Medium",
+                    doors_obj.id, self.config.priority_attribute
+                );
+                RequirementPriority::Medium
+            }
+        };
+        
         let mut custom_attrs = HashMap::new();
         
         for (key, value) in doors_obj.attributes {
@@ -227,8 +263,8 @@ impl DOORSConnector {
             title: doors_obj.object_heading,
             text: doors_obj.object_text,
             requirement_type: RequirementType::System,
-            status: RequirementStatus::Approved,
-            priority: RequirementPriority::Medium,
+            status,
+            priority,
             rationale: None,
             acceptance_criteria: None,
             verification_method: None,
@@ -244,6 +280,46 @@ impl DOORSConnector {
         }
     }
     
+    fn map_status(&self, status: &str) -> RequirementStatus {
+        match status.to_lowercase().as_str() {
+            "draft" | "proposed" => RequirementStatus::Draft,
+            "in review" | "review" | "under review" => RequirementStatus::UnderReview,
+            "rejected" => RequirementStatus::Rejected,
+            "obsolete" | "deleted" => RequirementStatus::Obsolete,
+            "implemented" => RequirementStatus::Implemented,
+            _ => RequirementStatus::Approved,
+        }
+    }
+    
+    fn map_priority(&self, priority: &str) -> RequirementPriority {
+        match priority.to_lowercase().as_str() {
+            "critical" | "mandatory" => RequirementPriority::Critical,
+            "high" => RequirementPriority::High,
+            "low" | "optional" => RequirementPriority::Low,
+            _ => RequirementPriority::Medium,
+        }
+    }
+    
+    fn map_to_doors_status(&self, status: &RequirementStatus) -> &str {
+        match status {
+            RequirementStatus::Draft => "Draft",
+            RequirementStatus::UnderReview => "In Review",
+            RequirementStatus::Approved => "Approved",
+            RequirementStatus::Rejected => "Rejected",
+            RequirementStatus::Obsolete => "Obsolete",
+            RequirementStatus::Implemented => "Implemented",
+        }
+    }
+    
+    fn map_to_doors_priority(&self, priority: &RequirementPriority) -> &str {
+        match priority {
+            RequirementPriority::Critical => "Critical",
+            RequirementPriority::High => "High",
+            RequirementPriority::Medium => "Medium",
+            RequirementPriority::Low => "Low",
+        }
+    }
+    
     fn convert_json_value(&self, value: serde_json::Value) -> AttributeValue {
         match value {
             serde_json::Value::String(s) => AttributeValue::String(s),
@@ -591,7 +667,24 @@ impl RequirementsConnector for DOORSConnector {
         let mut query_params = Vec::new();
         
         if let Some(text) = &filter.text_contains {
-            query_params.push(format!("text={}", urlencoding::encode(text)));
+            query_params.push(format!("text={}", super::url_encode(text)));
+        }
+        
+        // Attribute filters: `attr[Name]=value` on the objects endpoint.
+        if let Some(status) = &filter.status {
+            query_params.push(format!(
+                "attr[{}]={}",
+                super::url_encode(&self.config.status_attribute),
+                super::url_encode(self.map_to_doors_status(status))
+            ));
+        }
+        
+        if let Some(priority) = &filter.priority {
+            query_params.push(format!(
+                "attr[{}]={}",
+                super::url_encode(&self.config.priority_attribute),
+                super::url_encode(self.map_to_doors_priority(priority))
+            ));
         }
         
         let query_string = if query_params.is_empty() {
@@ -623,9 +716,20 @@ impl RequirementsConnector for DOORSConnector {
         let query_response: QueryResponse = response.json().await
             .map_err(|e| RMError::SerializationError(e.to_string()))?;
         
-        Ok(query_response.objects.into_iter()
+        let mut requirements: Vec<Requirement> = query_response.objects.into_iter()
             .map(|obj| self.convert_to_requirement(obj))
-            .collect())
+            .collect();
+        
+        // Older DWA servers ignore attribute filters: apply them again here.
+        if let Some(status_filter) = &filter.status {
+            requirements.retain(|r| &r.status == status_filter);
+        }
+        
+        if let Some(priority_filter) = &filter.priority {
+            requirements.retain(|r| &r.priority == priority_filter);
+        }
+        
+        Ok(requirements)
     }
     
     async fn generate_traceability_matrix(&self, _from: &str, _to: &str) -> Result<TraceabilityMatrix, RMError> {
@@ -651,7 +755,9 @@ impl RequirementsConnector for DOORSConnector {
             gaps: Vec::new(),
         })
     }
-    
+}
+
+impl DOORSConnector {
     fn map_link_type(&self, doors_type: &str) -> TraceLinkType {
         match doors_type.to_lowercase().as_str() {
             "satisfies" => TraceLinkType::Satisfies,
@@ -698,9 +804,113 @@ mod tests {
                 username: "test".to_string(),
                 password: "test".to_string(),
             },
+            status_attribute: default_status_attribute(),
+            priority_attribute: default_priority_attribute(),
+            connection: ConnectionOptions::default(),
         };
         
         let connector = DOORSConnector::new(config);
         assert_eq!(connector.name(), "DOORS Classic");
     }
+    
+    #[tokio::test]
+    async fn query_requirements_filters_by_priority() {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let object = |id: &str, priority: &str| serde_json::json!({
+            "id": id,
+            "objectNumber": id,
+            "objectText": "The system shall brake",
+            "objectHeading": "Braking",
+            "objectType": "Requirement",
+            "attributes": { "Priority": priority, "Status": "Approved" }
+        });
+        let objects = serde_json::json!({
+            "objects": [object("1", "High"), object("2", "Low"), object("3", "high")]
+        });
+        let transport = Arc::new(MockTransport::new().on(
+            reqwest::Method::GET,
+            "https://doors.example/dwa/api/projects/AFCS/modules/SRS/objects",
+            200,
+            objects.to_string(),
+        ));
+        let connector = DOORSConnector::with_transport(
+            DOORSConfig {
+                server_url: "https://doors.example".to_string(),
+                database: "DoorsDB".to_string(),
+                project: "AFCS".to_string(),
+                module: "SRS".to_string(),
+                auth: RMAuthentication::BasicAuth {
+                    username: "test".to_string(),
+                    password: "test".to_string(),
+                },
+                status_attribute: default_status_attribute(),
+                priority_attribute: default_priority_attribute(),
+                connection: ConnectionOptions::default(),
+            },
+            transport.clone(),
+        );
+        
+        let filter = RequirementFilter {
+            priority: Some(RequirementPriority::High),
+            ..RequirementFilter::default()
+        };
+        let requirements = connector.query_requirements(&filter).await.unwrap();
+        
+        let ids: Vec<_> = requirements.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert!(requirements.iter().all(|r| r.status == RequirementStatus::Approved));
+        let url = &transport.requests()[0].url;
+        assert!(url.ends_with("/objects?attr[Priority]=High"), "{url}");
+    }
+    
+    fn connector_with(status_attribute: &str, priority_attribute: &str) -> DOORSConnector {
+        DOORSConnector::new(DOORSConfig {
+            server_url: "https://doors.example".to_string(),
+            database: "DoorsDB".to_string(),
+            project: "AFCS".to_string(),
+            module: "SRS".to_string(),
+            auth: RMAuthentication::BasicAuth {
+                username: "test".to_string(),
+                password: "test".to_string(),
+            },
+            status_attribute: status_attribute.to_string(),
+            priority_attribute: priority_attribute.to_string(),
+            connection: ConnectionOptions::default(),
+        })
+    }
+    
+    fn doors_object(attributes: serde_json::Value) -> DOORSObject {
+        serde_json::from_value(serde_json::json!({
+            "id": "42",
+            "objectNumber": "3.1",
+            "objectText": "The system shall brake",
+            "objectHeading": "Braking",
+            "objectType": "Requirement",
+            "attributes": attributes
+        }))
+        .unwrap()
+    }
+    
+    #[test]
+    fn status_and_priority_come_from_the_configured_attributes() {
+        let connector = connector_with("Review State", "Criticality");
+        let req = connector.convert_to_requirement(doors_object(serde_json::json!({
+            "Review State": "In Review",
+            "criticality": "Mandatory",
+            "Priority": "Low"
+        })));
+        
+        assert_eq!(req.status, RequirementStatus::UnderReview);
+        assert_eq!(req.priority, RequirementPriority::Critical);
+    }
+    
+    #[test]
+    fn missing_status_and_priority_fall_back_to_defaults() {
+        let connector = connector_with("Status", "Priority");
+        let req = connector.convert_to_requirement(doors_object(serde_json::json!({ "Owner": "jdoe" })));
+        
+        assert_eq!(req.status, RequirementStatus::Approved);
+        assert_eq!(req.priority, RequirementPriority::Medium);
+    }
 }
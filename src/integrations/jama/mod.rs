@@ -1,12 +1,21 @@
 use async_trait::async_trait;
-use reqwest::{Client, header};
+use futures::StreamExt;
+use reqwest::header;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use super::requirements_management::*;
+use super::transport::{ConnectionOptions, HttpRequest, HttpTransport, ReqwestTransport};
+
+/// Creates in flight at once during `create_requirements_bulk`.
+const BULK_BATCH_SIZE: usize = 10;
+/// Times a request is retried after Jama answers 429 Too Many Requests.
+const RATE_LIMIT_RETRIES: u32 = 5;
 
 pub struct JamaConnector {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: JamaConfig,
 }
 
@@ -15,6 +24,47 @@ pub struct JamaConfig {
     pub instance_url: String,
     pub project_id: i64,
     pub auth: RMAuthentication,
+    /// Item type ID new requirements are created as; instance-specific.
+    #[serde(default = "JamaConfig::default_requirement_item_type")]
+    pub requirement_item_type: i64,
+    /// Relationship type ID used for each kind of trace link.
+    #[serde(default = "JamaConfig::default_relationship_type_map")]
+    pub relationship_type_map: HashMap<TraceLinkType, i64>,
+    /// Relationship requests in flight at once during `fetch_baseline`.
+    #[serde(default = "JamaConfig::default_link_fetch_concurrency")]
+    pub link_fetch_concurrency: usize,
+    /// Timeout, proxy and extra CA certificate for the HTTP client.
+    #[serde(default)]
+    pub connection: ConnectionOptions,
+}
+
+impl JamaConfig {
+    /// `101` is the Requirement item type of a stock Jama installation.
+    pub fn default_requirement_item_type() -> i64 {
+        101
+    }
+    
+    /// Every link type maps to relationship type `1`, Jama's stock
+    /// "Related to"; instances with typed relationships override entries.
+    pub fn default_relationship_type_map() -> HashMap<TraceLinkType, i64> {
+        [
+            TraceLinkType::Satisfies,
+            TraceLinkType::DerivedFrom,
+            TraceLinkType::Refines,
+            TraceLinkType::AllocatedTo,
+            TraceLinkType::VerifiedBy,
+            TraceLinkType::Traces,
+            TraceLinkType::Implements,
+            TraceLinkType::DependsOn,
+        ]
+        .into_iter()
+        .map(|link_type| (link_type, 1))
+        .collect()
+    }
+    
+    pub fn default_link_fetch_concurrency() -> usize {
+        8
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -106,8 +156,6 @@ struct JamaMeta {
 
 #[derive(Debug, Deserialize)]
 struct JamaPageInfo {
-    #[serde(rename = "startIndex")]
-    start_index: i64,
     #[serde(rename = "resultCount")]
     result_count: i64,
     #[serde(rename = "totalResults")]
@@ -131,24 +179,14 @@ struct JamaCreateMeta {
 
 impl JamaConnector {
     pub fn new(config: JamaConfig) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json"),
-        );
-        
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .expect("Failed to create HTTP client");
-        
+        let transport = ReqwestTransport::with_options(Duration::from_secs(60), &config.connection);
+        Self::with_transport(config, Arc::new(transport))
+    }
+    
+    /// Connector that sends its requests through `transport`.
+    pub fn with_transport(config: JamaConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self {
-            client,
+            transport,
             config,
         }
     }
@@ -160,8 +198,7 @@ impl JamaConnector {
     fn get_auth_header(&self) -> Result<String, RMError> {
         match &self.config.auth {
             RMAuthentication::BasicAuth { username, password } => {
-                let credentials = format!("{}:{}", username, password);
-                Ok(format!("Basic {}", base64::encode(credentials)))
+                Ok(format!("Basic {}", super::basic_credentials(username, password)))
             }
             RMAuthentication::APIToken { token } => {
                 Ok(format!("Bearer {}", token))
@@ -176,10 +213,10 @@ impl JamaConnector {
         let url = self.build_url(path);
         let auth_header = self.get_auth_header()?;
         
-        self.client
-            .get(&url)
-            .header(header::AUTHORIZATION, auth_header)
-            .send()
+        let req = HttpRequest::get(&url)
+            .header(header::AUTHORIZATION, auth_header);
+        self.transport
+            .send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -188,11 +225,11 @@ impl JamaConnector {
         let url = self.build_url(path);
         let auth_header = self.get_auth_header()?;
         
-        self.client
-            .post(&url)
+        let req = HttpRequest::post(&url)
             .header(header::AUTHORIZATION, auth_header)
-            .json(body)
-            .send()
+            .json(body);
+        self.transport
+            .send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -201,11 +238,11 @@ impl JamaConnector {
         let url = self.build_url(path);
         let auth_header = self.get_auth_header()?;
         
-        self.client
-            .put(&url)
+        let req = HttpRequest::put(&url)
             .header(header::AUTHORIZATION, auth_header)
-            .json(body)
-            .send()
+            .json(body);
+        self.transport
+            .send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -214,10 +251,10 @@ impl JamaConnector {
         let url = self.build_url(path);
         let auth_header = self.get_auth_header()?;
         
-        self.client
-            .delete(&url)
-            .header(header::AUTHORIZATION, auth_header)
-            .send()
+        let req = HttpRequest::delete(&url)
+            .header(header::AUTHORIZATION, auth_header);
+        self.transport
+            .send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -301,6 +338,55 @@ impl JamaConnector {
         }
     }
     
+    async fn create_item(&self, req: &Requirement) -> Result<String, RMError> {
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), serde_json::json!(req.title));
+        fields.insert("description".to_string(), serde_json::json!(req.text));
+        fields.insert("status".to_string(), serde_json::json!(format!("{:?}", req.status)));
+        fields.insert("priority".to_string(), serde_json::json!(format!("{:?}", req.priority)));
+        
+        for (key, value) in &req.custom_attributes {
+            fields.insert(key.clone(), self.attribute_value_to_json(value));
+        }
+        
+        let parent_id = req.parent_id.as_ref()
+            .and_then(|p| p.parse::<i64>().ok())
+            .unwrap_or(self.config.project_id);
+        
+        let create_item = JamaCreateItem {
+            project: self.config.project_id,
+            item_type: self.config.requirement_item_type,
+            child_item_type: None,
+            location: JamaCreateLocation {
+                parent: parent_id,
+            },
+            fields,
+        };
+        
+        let path = "/items";
+        
+        let response = super::retry_rate_limited(RATE_LIMIT_RETRIES, || {
+            self.post_with_auth(path, &create_item)
+        }).await?;
+        
+        if !response.status().is_success() {
+            return Err(RMError::APIError(
+                format!("Failed to create requirement: {}", response.status())
+            ));
+        }
+        
+        let create_response: JamaCreateResponse = response.json().await
+            .map_err(|e| RMError::SerializationError(e.to_string()))?;
+        
+        let item_id = create_response.meta.location
+            .rsplit('/')
+            .next()
+            .unwrap_or("0")
+            .to_string();
+        
+        Ok(item_id)
+    }
+    
     fn map_status(&self, status: &str) -> RequirementStatus {
         match status.to_lowercase().as_str() {
             "draft" => RequirementStatus::Draft,
@@ -334,32 +420,62 @@ impl JamaConnector {
         }
     }
     
-    async fn fetch_all_items(&self, start_index: i64) -> Result<Vec<JamaItem>, RMError> {
-        let path = format!(
-            "/items?project={}&startAt={}&maxResults=50",
-            self.config.project_id,
-            start_index
-        );
-        
-        let response = self.get_with_auth(&path).await?;
-        
-        if !response.status().is_success() {
-            return Err(RMError::APIError(
-                format!("Failed to fetch items: {}", response.status())
-            ));
-        }
-        
-        let items_response: JamaItemsResponse = response.json().await
-            .map_err(|e| RMError::SerializationError(e.to_string()))?;
-        
-        let mut all_items = items_response.data;
-        
-        let total = items_response.meta.page_info.total_results;
-        let fetched = start_index + items_response.meta.page_info.result_count;
+    /// Downstream relationships of one item; empty when they can't be read.
+    async fn fetch_downstream_links(&self, req_id: &str) -> Vec<TraceLink> {
+        let links_path = format!("/items/{}/downstreamrelationships", req_id);
+        let response = super::retry_rate_limited(RATE_LIMIT_RETRIES, || self.get_with_auth(&links_path)).await;
+        let Ok(links_response) = response else { return Vec::new() };
+        if !links_response.status().is_success() {
+            return Vec::new();
+        }
+        let Ok(rels_response) = links_response.json::<JamaRelationshipsResponse>().await else {
+            return Vec::new();
+        };
         
-        if fetched < total {
-            let mut next_items = self.fetch_all_items(fetched).await?;
-            all_items.append(&mut next_items);
+        rels_response.data.into_iter()
+            .map(|rel| TraceLink {
+                id: rel.id.to_string(),
+                source_id: rel.from_item.to_string(),
+                target_id: rel.to_item.to_string(),
+                link_type: self.map_relationship_type(
+                    &rel.relationship_type_name.unwrap_or_else(|| "traces".to_string())
+                ),
+                rationale: None,
+                created_at: chrono::Utc::now(),
+                created_by: "jama".to_string(),
+            })
+            .collect()
+    }
+    
+    async fn fetch_all_items(&self, start_index: i64) -> Result<Vec<JamaItem>, RMError> {
+        let mut all_items = Vec::new();
+        let mut start_index = start_index;
+        
+        loop {
+            let path = format!(
+                "/items?project={}&startAt={}&maxResults=50",
+                self.config.project_id,
+                start_index
+            );
+            
+            let response = self.get_with_auth(&path).await?;
+            
+            if !response.status().is_success() {
+                return Err(RMError::APIError(
+                    format!("Failed to fetch items: {}", response.status())
+                ));
+            }
+            
+            let items_response: JamaItemsResponse = response.json().await
+                .map_err(|e| RMError::SerializationError(e.to_string()))?;
+            
+            all_items.extend(items_response.data);
+            
+            let page_info = &items_response.meta.page_info;
+            start_index += page_info.result_count;
+            if page_info.result_count == 0 || start_index >= page_info.total_results {
+                break;
+            }
         }
         
         Ok(all_items)
@@ -400,30 +516,13 @@ impl RequirementsConnector for JamaConnector {
             })
             .collect();
         
-        let mut trace_links = Vec::new();
-        for (req_id, _) in &requirements {
-            let links_path = format!("/items/{}/downstreamrelationships", req_id);
-            
-            if let Ok(links_response) = self.get_with_auth(&links_path).await {
-                if links_response.status().is_success() {
-                    if let Ok(rels_response) = links_response.json::<JamaRelationshipsResponse>().await {
-                        for rel in rels_response.data {
-                            trace_links.push(TraceLink {
-                                id: rel.id.to_string(),
-                                source_id: rel.from_item.to_string(),
-                                target_id: rel.to_item.to_string(),
-                                link_type: self.map_relationship_type(
-                                    &rel.relationship_type_name.unwrap_or_else(|| "traces".to_string())
-                                ),
-                                rationale: None,
-                                created_at: chrono::Utc::now(),
-                                created_by: "jama".to_string(),
-                            });
-                        }
-                    }
-                }
-            }
-        }
+        let req_ids: Vec<String> = requirements.keys().cloned().collect();
+        let mut trace_links: Vec<TraceLink> = futures::stream::iter(req_ids)
+            .map(|req_id| async move { self.fetch_downstream_links(&req_id).await })
+            .buffer_unordered(self.config.link_fetch_concurrency.max(1))
+            .concat()
+            .await;
+        trace_links.sort_by(|a, b| (&a.source_id, &a.target_id, &a.id).cmp(&(&b.source_id, &b.target_id, &b.id)));
         
         Ok(RMBaseline {
             timestamp: chrono::Utc::now(),
@@ -509,50 +608,18 @@ impl RequirementsConnector for JamaConnector {
     }
     
     async fn create_requirement(&self, req: &Requirement) -> Result<String, RMError> {
-        let mut fields = HashMap::new();
-        fields.insert("name".to_string(), serde_json::json!(req.title));
-        fields.insert("description".to_string(), serde_json::json!(req.text));
-        fields.insert("status".to_string(), serde_json::json!(format!("{:?}", req.status)));
-        fields.insert("priority".to_string(), serde_json::json!(format!("{:?}", req.priority)));
-        
-        for (key, value) in &req.custom_attributes {
-            fields.insert(key.clone(), self.attribute_value_to_json(value));
-        }
-        
-        let parent_id = req.parent_id.as_ref()
-            .and_then(|p| p.parse::<i64>().ok())
-            .unwrap_or(self.config.project_id);
-        
-        let create_item = JamaCreateItem {
-            project: self.config.project_id,
-            item_type: 101,
-            child_item_type: None,
-            location: JamaCreateLocation {
-                parent: parent_id,
-            },
-            fields,
-        };
-        
-        let path = "/items";
-        
-        let response = self.post_with_auth(path, &create_item).await?;
-        
-        if !response.status().is_success() {
-            return Err(RMError::APIError(
-                format!("Failed to create requirement: {}", response.status())
-            ));
-        }
-        
-        let create_response: JamaCreateResponse = response.json().await
-            .map_err(|e| RMError::SerializationError(e.to_string()))?;
-        
-        let item_id = create_response.meta.location
-            .rsplit('/')
-            .next()
-            .unwrap_or("0")
-            .to_string();
-        
-        Ok(item_id)
+        self.create_item(req).await
+    }
+    
+    /// Jama has no bulk item endpoint, so creates are sent in concurrent
+    /// batches of [`BULK_BATCH_SIZE`], each retried while rate-limited.
+    async fn create_requirements_bulk(&self, reqs: &[Requirement]) -> Result<Vec<String>, RMError> {
+        let mut results = Vec::with_capacity(reqs.len());
+        for batch in reqs.chunks(BULK_BATCH_SIZE) {
+            let creates = batch.iter().map(|req| self.create_item(req));
+            results.extend(futures::future::join_all(creates).await);
+        }
+        collect_bulk_results(reqs, results)
     }
     
     async fn update_requirement(&self, req_id: &str, changes: &RequirementChanges) -> Result<(), RMError> {
@@ -614,10 +681,17 @@ impl RequirementsConnector for JamaConnector {
         let to_item = link.target_id.parse::<i64>()
             .map_err(|_| RMError::ValidationError("Invalid target ID".to_string()))?;
         
+        let relationship_type = *self.config.relationship_type_map
+            .get(&link.link_type)
+            .ok_or_else(|| RMError::ValidationError(format!(
+                "No Jama relationship type configured for {:?} links",
+                link.link_type
+            )))?;
+        
         let create_rel = JamaCreateRelationship {
             from_item,
             to_item,
-            relationship_type: 1,
+            relationship_type,
         };
         
         let path = "/relationships";
@@ -660,7 +734,7 @@ impl RequirementsConnector for JamaConnector {
         let mut path = format!("/items?project={}", self.config.project_id);
         
         if let Some(text) = &filter.text_contains {
-            path.push_str(&format!("&contains={}", urlencoding::encode(text)));
+            path.push_str(&format!("&contains={}", super::url_encode(text)));
         }
         
         let response = self.get_with_auth(&path).await?;
@@ -732,7 +806,9 @@ impl RequirementsConnector for JamaConnector {
             gaps,
         })
     }
-    
+}
+
+impl JamaConnector {
     fn attribute_value_to_json(&self, value: &AttributeValue) -> serde_json::Value {
         match value {
             AttributeValue::String(s) => serde_json::json!(s),
@@ -757,9 +833,147 @@ mod tests {
                 username: "test".to_string(),
                 password: "test".to_string(),
             },
+            requirement_item_type: JamaConfig::default_requirement_item_type(),
+            relationship_type_map: JamaConfig::default_relationship_type_map(),
+            link_fetch_concurrency: JamaConfig::default_link_fetch_concurrency(),
+            connection: ConnectionOptions::default(),
         };
         
         let connector = JamaConnector::new(config);
         assert_eq!(connector.name(), "Jama Connect");
     }
+    
+    fn requirement(id: &str) -> Requirement {
+        Requirement {
+            id: id.to_string(),
+            external_id: None,
+            title: id.to_string(),
+            text: format!("{} shall hold", id),
+            requirement_type: RequirementType::System,
+            status: RequirementStatus::Draft,
+            priority: RequirementPriority::Medium,
+            rationale: None,
+            acceptance_criteria: None,
+            verification_method: None,
+            verification_status: None,
+            compliance: Vec::new(),
+            custom_attributes: HashMap::new(),
+            parent_id: None,
+            children_ids: Vec::new(),
+            created_at: chrono::Utc::now(),
+            modified_at: chrono::Utc::now(),
+            created_by: "arclang".to_string(),
+            modified_by: "arclang".to_string(),
+        }
+    }
+    
+    #[tokio::test]
+    async fn bulk_create_keeps_successes_on_partial_failure() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for (name, status, body) in [
+            ("REQ-1", 201, r#"{"meta": {"location": "/rest/v1/items/501"}}"#),
+            ("REQ-2", 400, r#"{"meta": {"status": "Bad Request"}}"#),
+            ("REQ-3", 201, r#"{"meta": {"location": "/rest/v1/items/503"}}"#),
+        ] {
+            mocks.push(
+                server
+                    .mock("POST", "/rest/v1/items")
+                    .match_body(mockito::Matcher::PartialJson(serde_json::json!({"fields": {"name": name}})))
+                    .with_status(status)
+                    .with_body(body)
+                    .create_async()
+                    .await,
+            );
+        }
+        // Rate-limited once, then accepted.
+        let limited = server
+            .mock("POST", "/rest/v1/items")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"fields": {"name": "REQ-4"}})))
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let connector = JamaConnector::new(JamaConfig {
+            instance_url: server.url(),
+            project_id: 123,
+            auth: RMAuthentication::APIToken { token: "token".to_string() },
+            requirement_item_type: JamaConfig::default_requirement_item_type(),
+            relationship_type_map: JamaConfig::default_relationship_type_map(),
+            link_fetch_concurrency: JamaConfig::default_link_fetch_concurrency(),
+            connection: ConnectionOptions::default(),
+        });
+        let reqs: Vec<_> = ["REQ-1", "REQ-2", "REQ-3"].into_iter().map(requirement).collect();
+        match connector.create_requirements_bulk(&reqs).await {
+            Err(RMError::PartialFailure { created, failed }) => {
+                assert_eq!(
+                    created,
+                    vec![
+                        ("REQ-1".to_string(), "501".to_string()),
+                        ("REQ-3".to_string(), "503".to_string()),
+                    ]
+                );
+                assert_eq!(failed.len(), 1);
+                assert_eq!(failed[0].0, "REQ-2");
+            }
+            other => panic!("expected a partial failure, got {:?}", other),
+        }
+        
+        let _accepted = server
+            .mock("POST", "/rest/v1/items")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"fields": {"name": "REQ-4"}})))
+            .with_status(201)
+            .with_body(r#"{"meta": {"location": "/rest/v1/items/504"}}"#)
+            .create_async()
+            .await;
+        let ids = connector.create_requirements_bulk(&[requirement("REQ-4")]).await.unwrap();
+        assert_eq!(ids, vec!["504".to_string()]);
+        limited.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn trace_link_uses_configured_relationship_type() {
+        let mut server = mockito::Server::new_async().await;
+        let verifies = server
+            .mock("POST", "/rest/v1/relationships")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "fromItem": 501, "toItem": 502, "relationshipType": 7
+            })))
+            .with_status(201)
+            .with_body(r#"{"meta": {"location": "/rest/v1/relationships/900"}}"#)
+            .create_async()
+            .await;
+        
+        let mut relationship_type_map = JamaConfig::default_relationship_type_map();
+        relationship_type_map.insert(TraceLinkType::VerifiedBy, 7);
+        relationship_type_map.remove(&TraceLinkType::AllocatedTo);
+        let connector = JamaConnector::new(JamaConfig {
+            instance_url: server.url(),
+            project_id: 123,
+            auth: RMAuthentication::APIToken { token: "token".to_string() },
+            requirement_item_type: 88,
+            relationship_type_map,
+            link_fetch_concurrency: JamaConfig::default_link_fetch_concurrency(),
+            connection: ConnectionOptions::default(),
+        });
+        
+        let link = |link_type| TraceLink {
+            id: "L1".to_string(),
+            source_id: "501".to_string(),
+            target_id: "502".to_string(),
+            link_type,
+            rationale: None,
+            created_at: chrono::Utc::now(),
+            created_by: "arclang".to_string(),
+        };
+        assert_eq!(connector.create_trace_link(&link(TraceLinkType::VerifiedBy)).await.unwrap(), "900");
+        verifies.assert_async().await;
+        
+        match connector.create_trace_link(&link(TraceLinkType::AllocatedTo)).await {
+            Err(RMError::ValidationError(message)) => assert!(message.contains("AllocatedTo")),
+            other => panic!("expected an unmapped-type error, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,54 @@
+//! Connectors to requirements-management (Jira, Polarion, Jama, DOORS Next)
+//! and PLM (Teamcenter, Windchill, 3DEXPERIENCE) systems, driven by
+//! `arclang sync`. Native only: every connector talks HTTP.
+
+pub mod transport;
+pub mod credentials;
+pub mod requirements_management;
+pub mod plm_integration;
+pub mod jira;
+pub mod polarion;
+pub mod jama;
+pub mod doors;
+pub mod teamcenter;
+pub mod windchill;
+pub mod three_dexperience;
+pub mod sync;
+pub mod traceability_xlsx;
+pub mod traceability_html;
+
+use base64::Engine;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+/// Percent-encode a query-string component.
+pub(crate) fn url_encode(text: &str) -> String {
+    utf8_percent_encode(text, NON_ALPHANUMERIC).to_string()
+}
+
+/// Send again while the server answers 429 Too Many Requests, at most
+/// `retries` times: after `Retry-After` seconds when sent, else 1s, 2s, 4s...
+pub(crate) async fn retry_rate_limited<F, Fut, E>(retries: u32, mut send: F) -> Result<reqwest::Response, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, E>>,
+{
+    let mut attempt = 0;
+    loop {
+        let response = send().await?;
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS || attempt == retries {
+            return Ok(response);
+        }
+        let delay = response.headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(1 << attempt);
+        tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+        attempt += 1;
+    }
+}
+
+/// `Authorization: Basic …` payload for `user:secret`.
+pub(crate) fn basic_credentials(user: &str, secret: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(format!("{user}:{secret}"))
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use super::transport::REDACTED;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PLMConfig {
@@ -11,7 +12,7 @@ pub struct PLMConfig {
     pub mapping: MappingConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PLMSystem {
     Windchill,
     Teamcenter,
@@ -28,7 +29,7 @@ pub struct ConnectionConfig {
     pub retry_count: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum AuthenticationMethod {
     OAuth2 {
         client_id: String,
@@ -49,6 +50,31 @@ pub enum AuthenticationMethod {
     },
 }
 
+/// Secrets masked, so configs can be logged.
+impl std::fmt::Debug for AuthenticationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OAuth2 { client_id, token_url, .. } => f.debug_struct("OAuth2")
+                .field("client_id", client_id)
+                .field("client_secret", &REDACTED)
+                .field("token_url", token_url)
+                .finish(),
+            Self::BasicAuth { username, .. } => f.debug_struct("BasicAuth")
+                .field("username", username)
+                .field("password", &REDACTED)
+                .finish(),
+            Self::APIKey { header, .. } => f.debug_struct("APIKey")
+                .field("key", &REDACTED)
+                .field("header", header)
+                .finish(),
+            Self::SAML { idp_url, sp_entity_id } => f.debug_struct("SAML")
+                .field("idp_url", idp_url)
+                .field("sp_entity_id", sp_entity_id)
+                .finish(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncPolicy {
     pub mode: SyncMode,
@@ -115,7 +141,107 @@ pub trait PLMConnector: Send + Sync {
     
     async fn fetch_bom(&self, parent_part: &str) -> Result<BOM, PLMError>;
     
-    async fn push_changes(&self, delta: &PLMDelta) -> Result<PLMSyncResult, PLMError>;
+    /// Multi-level BOM of `parent_part`, expanded depth-first through
+    /// `fetch_bom` down to `max_depth` levels. Items are flattened in
+    /// indented-BOM order, each carrying its `level` (direct children are 1).
+    async fn fetch_bom_recursive(&self, parent_part: &str, max_depth: u32) -> Result<BOM, PLMError> {
+        let root = self.fetch_bom(parent_part).await?;
+        let mut items = Vec::new();
+        if max_depth == 0 {
+            return Ok(BOM { items, ..root });
+        }
+        
+        // `ancestors[i]` is the assembly whose children `pending[i]` holds.
+        let mut ancestors = vec![parent_part.to_string()];
+        let mut pending = vec![root.items.clone().into_iter()];
+        while let Some(children) = pending.last_mut() {
+            let Some(mut item) = children.next() else {
+                pending.pop();
+                ancestors.pop();
+                continue;
+            };
+            
+            if ancestors.contains(&item.part_number) {
+                let mut cycle = ancestors.clone();
+                cycle.push(item.part_number);
+                return Err(PLMError::BOMCycle(cycle.join(" → ")));
+            }
+            
+            item.level = pending.len() as u32;
+            let part_number = item.part_number.clone();
+            let expand = item.level < max_depth;
+            items.push(item);
+            
+            if expand {
+                let child = self.fetch_bom(&part_number).await?;
+                ancestors.push(part_number);
+                pending.push(child.items.into_iter());
+            }
+        }
+        
+        Ok(BOM { items, ..root })
+    }
+    
+    /// The calls `push_changes` makes for `delta`, in order, without making
+    /// them: part creates, part updates, then the change order when the
+    /// delta requires one.
+    fn plan_changes(&self, delta: &PLMDelta) -> Vec<PlannedOperation> {
+        let mut plan: Vec<PlannedOperation> = delta.added_parts.iter()
+            .map(|part| PlannedOperation::CreatePart(Box::new(part.clone())))
+            .chain(delta.modified_parts.iter().cloned().map(PlannedOperation::UpdatePart))
+            .collect();
+        if delta.eco_required {
+            plan.push(PlannedOperation::CreateEco {
+                request: ChangeRequest {
+                    title: delta.change_summary.clone(),
+                    description: "Automated sync from ArcLang".to_string(),
+                    reason: "Model synchronization".to_string(),
+                    affected_items: delta.affected_part_numbers(),
+                    requester: "arclang".to_string(),
+                    priority: Priority::Medium,
+                    change_type: ECOChangeType::Engineering,
+                },
+                released_parts: delta.impact_analysis.released_parts.clone(),
+            });
+        }
+        plan
+    }
+    
+    /// Make the calls of `plan` in order. A failed call is recorded in the
+    /// result and the remaining calls still run.
+    async fn execute_plan(&self, plan: &[PlannedOperation]) -> Result<PLMSyncResult, PLMError> {
+        let mut result = PLMSyncResult {
+            success: true,
+            parts_created: Vec::new(),
+            parts_updated: Vec::new(),
+            parts_failed: Vec::new(),
+            eco_id: None,
+            sync_timestamp: Utc::now(),
+        };
+        
+        for operation in plan {
+            let outcome = match operation {
+                PlannedOperation::CreatePart(part) => self.create_part(part).await
+                    .map(|id| result.parts_created.push(id)),
+                PlannedOperation::UpdatePart(diff) => self.update_part(&diff.part_id, &diff.to_part_changes()).await
+                    .map(|_| result.parts_updated.push(diff.part_number.clone())),
+                PlannedOperation::CreateEco { request, .. } => self.create_eco(request).await
+                    .map(|eco_id| result.eco_id = Some(eco_id)),
+            };
+            if let Err(e) = outcome {
+                result.parts_failed.push((operation.subject().to_string(), e.to_string()));
+                result.success = false;
+            }
+        }
+        
+        Ok(result)
+    }
+    
+    /// Push `delta`: execute [`plan_changes`](Self::plan_changes).
+    async fn push_changes(&self, delta: &PLMDelta) -> Result<PLMSyncResult, PLMError> {
+        let plan = self.plan_changes(delta);
+        self.execute_plan(&plan).await
+    }
     
     async fn create_part(&self, part: &PLMPart) -> Result<String, PLMError>;
     
@@ -200,6 +326,13 @@ pub enum LifecycleState {
     Frozen,
 }
 
+impl LifecycleState {
+    /// Released and frozen parts are under change control.
+    pub fn is_released(&self) -> bool {
+        matches!(self, LifecycleState::Released | LifecycleState::Frozen)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BOM {
     pub parent_part: String,
@@ -208,8 +341,33 @@ pub struct BOM {
     pub effectivity: Option<Effectivity>,
 }
 
+impl BOM {
+    /// The structure as built at `point`: items whose effectivity excludes
+    /// it are dropped together with everything below them. Items without
+    /// effectivity are always kept.
+    pub fn effective_at(&self, point: &EffectivityPoint) -> BOM {
+        let mut items = Vec::new();
+        let mut excluded_level = None;
+        for item in &self.items {
+            match excluded_level {
+                Some(level) if item.level > level => continue,
+                _ => excluded_level = None,
+            }
+            if item.effectivity.as_ref().is_some_and(|e| !e.applies_to(point)) {
+                excluded_level = Some(item.level);
+                continue;
+            }
+            items.push(item.clone());
+        }
+        BOM { items, ..self.clone() }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BOMItem {
+    /// Depth below the BOM's parent part; direct children are level 1.
+    #[serde(default = "default_bom_level")]
+    pub level: u32,
     pub item_number: u32,
     pub part_number: String,
     pub quantity: f64,
@@ -217,6 +375,13 @@ pub struct BOMItem {
     pub reference_designator: Option<String>,
     pub find_number: Option<String>,
     pub notes: Option<String>,
+    /// When this usage is in the structure; `None` means always.
+    #[serde(default)]
+    pub effectivity: Option<Effectivity>,
+}
+
+fn default_bom_level() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -226,6 +391,42 @@ pub struct Effectivity {
     pub end: Option<String>,
 }
 
+impl Effectivity {
+    /// Whether `point` falls in this range (ends inclusive). A point of
+    /// another kind, or a bound that does not parse, does not exclude.
+    pub fn applies_to(&self, point: &EffectivityPoint) -> bool {
+        let in_range = |at: std::cmp::Ordering, end: Option<std::cmp::Ordering>| {
+            at.is_ge() && end.is_none_or(|end| end.is_le())
+        };
+        match (&self.effectivity_type, point) {
+            (EffectivityType::Date, EffectivityPoint::Date(date)) => {
+                let parse = |bound: &str| {
+                    chrono::NaiveDate::parse_from_str(bound.get(..10).unwrap_or(bound), "%Y-%m-%d").ok()
+                };
+                let Some(start) = parse(&self.start) else { return true };
+                let end = self.end.as_deref().and_then(parse);
+                in_range(date.cmp(&start), end.map(|end| date.cmp(&end)))
+            }
+            (EffectivityType::SerialNumber, EffectivityPoint::SerialNumber(number))
+            | (EffectivityType::Unit, EffectivityPoint::Unit(number)) => {
+                in_range(
+                    compare_numbers(number, &self.start),
+                    self.end.as_deref().map(|end| compare_numbers(number, end)),
+                )
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Serial and unit numbers compare numerically when both are numbers.
+fn compare_numbers(a: &str, b: &str) -> std::cmp::Ordering {
+    match (a.trim().parse::<u64>(), b.trim().parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.trim().cmp(b.trim()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EffectivityType {
     SerialNumber,
@@ -233,6 +434,14 @@ pub enum EffectivityType {
     Unit,
 }
 
+/// A date, serial number or unit to resolve effectivity at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EffectivityPoint {
+    Date(chrono::NaiveDate),
+    SerialNumber(String),
+    Unit(String),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PLMDelta {
     pub added_parts: Vec<PLMPart>,
@@ -287,6 +496,35 @@ pub struct ImpactAnalysis {
     pub cost_impact: Option<f64>,
     pub schedule_impact_days: Option<u32>,
     pub safety_impact: bool,
+    /// Released (or frozen) parts the delta modifies, deletes or restructures:
+    /// these can only change through a change order.
+    #[serde(default)]
+    pub released_parts: Vec<String>,
+}
+
+/// One remote call of a PLM push, as listed by
+/// [`PLMConnector::plan_changes`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PlannedOperation {
+    CreatePart(Box<PLMPart>),
+    UpdatePart(PartDiff),
+    /// The change order covering the push; `released_parts` are the
+    /// released parts that require it.
+    CreateEco {
+        request: ChangeRequest,
+        released_parts: Vec<String>,
+    },
+}
+
+impl PlannedOperation {
+    /// Part number the call is about, or `ECO` for the change order.
+    pub fn subject(&self) -> &str {
+        match self {
+            PlannedOperation::CreatePart(part) => &part.part_number,
+            PlannedOperation::UpdatePart(diff) => &diff.part_number,
+            PlannedOperation::CreateEco { .. } => "ECO",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -372,6 +610,9 @@ pub enum PLMError {
     
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    
+    #[error("BOM cycle detected: {0}")]
+    BOMCycle(String),
 }
 
 pub struct PLMIntegrationManager {
@@ -436,6 +677,32 @@ impl PLMIntegrationManager {
     }
 }
 
+impl PartDiff {
+    /// The update payload for `PLMConnector::update_part`.
+    pub fn to_part_changes(&self) -> PartChanges {
+        let mut changes = PartChanges {
+            description: None,
+            lifecycle_state: None,
+            supplier: None,
+            unit_cost: None,
+            custom_attributes: HashMap::new(),
+        };
+        for change in &self.changes {
+            match (change.attribute.as_str(), &change.new_value) {
+                ("description", Some(AttributeValue::String(text))) => {
+                    changes.description = Some(text.clone());
+                }
+                ("unit_cost", Some(AttributeValue::Number(cost))) => changes.unit_cost = Some(*cost),
+                (attribute, Some(value)) => {
+                    changes.custom_attributes.insert(attribute.to_string(), value.clone());
+                }
+                (_, None) => {}
+            }
+        }
+        changes
+    }
+}
+
 impl PLMDelta {
     pub fn affected_part_numbers(&self) -> Vec<String> {
         let mut parts = Vec::new();
@@ -448,6 +715,7 @@ impl PLMDelta {
     }
     
     pub fn requires_eco(&self) -> bool {
+        !self.impact_analysis.released_parts.is_empty() ||
         !self.deleted_parts.is_empty() ||
         self.impact_analysis.safety_impact ||
         self.has_significant_cost_change() ||
@@ -480,15 +748,33 @@ impl PLMDelta {
 
 pub mod delta_computer {
     use super::*;
-    use crate::compiler::semantic::SemanticModel;
+    use crate::compiler::ast::{self, Model, PhysicalNode};
     
+    /// Physical nodes carrying a `part_number` attribute are PLM parts; a
+    /// node's `assembly` attribute names the part number it is built into.
     pub struct DeltaComputer {
-        current_model: SemanticModel,
+        current_model: Model,
         baseline: Option<PLMBaseline>,
     }
     
+    fn string_attr<'a>(node: &'a PhysicalNode, key: &str) -> Option<&'a str> {
+        node.attributes.get(key).and_then(|v| v.as_string())
+    }
+    
+    fn number_attr(node: &PhysicalNode, key: &str) -> Option<f64> {
+        match node.attributes.get(key)? {
+            ast::AttributeValue::Number(n) => Some(*n),
+            ast::AttributeValue::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+    
+    fn part_number(node: &PhysicalNode) -> Option<&str> {
+        string_attr(node, "part_number")
+    }
+    
     impl DeltaComputer {
-        pub fn new(model: SemanticModel, baseline: Option<PLMBaseline>) -> Self {
+        pub fn new(model: Model, baseline: Option<PLMBaseline>) -> Self {
             Self {
                 current_model: model,
                 baseline,
@@ -509,6 +795,7 @@ pub mod delta_computer {
                     cost_impact: None,
                     schedule_impact_days: None,
                     safety_impact: false,
+                    released_parts: Vec::new(),
                 },
             };
             
@@ -528,73 +815,111 @@ pub mod delta_computer {
             Ok(delta)
         }
         
-        fn detect_added_parts(&self, delta: &mut PLMDelta, baseline: &PLMBaseline) {
-            for node in self.current_model.physical_nodes() {
-                if let Some(plm_info) = &node.plm {
-                    if !baseline.parts.contains_key(&plm_info.part_number) {
-                        delta.added_parts.push(self.convert_to_plm_part(node));
+        fn physical_nodes(&self) -> impl Iterator<Item = &PhysicalNode> {
+            self.current_model
+                .physical_architecture
+                .iter()
+                .flat_map(|pa| &pa.nodes)
+        }
+        
+        fn parts(&self) -> impl Iterator<Item = (&str, &PhysicalNode)> {
+            self.physical_nodes().filter_map(|node| part_number(node).map(|pn| (pn, node)))
+        }
+        
+        fn boms(&self) -> Vec<BOM> {
+            let mut boms: Vec<BOM> = Vec::new();
+            for (number, node) in self.parts() {
+                let Some(parent) = string_attr(node, "assembly") else { continue };
+                let index = match boms.iter().position(|b| b.parent_part == parent) {
+                    Some(index) => index,
+                    None => {
+                        boms.push(BOM {
+                            parent_part: parent.to_string(),
+                            structure_type: "Engineering".to_string(),
+                            items: Vec::new(),
+                            effectivity: None,
+                        });
+                        boms.len() - 1
                     }
+                };
+                let items = &mut boms[index].items;
+                items.push(BOMItem {
+                    level: 1,
+                    item_number: (items.len() as u32 + 1) * 10,
+                    part_number: number.to_string(),
+                    quantity: number_attr(node, "quantity").unwrap_or(1.0),
+                    unit: "EA".to_string(),
+                    reference_designator: None,
+                    find_number: None,
+                    notes: None,
+                    effectivity: None,
+                });
+            }
+            boms
+        }
+        
+        fn detect_added_parts(&self, delta: &mut PLMDelta, baseline: &PLMBaseline) {
+            for (number, node) in self.parts() {
+                if !baseline.parts.contains_key(number) {
+                    delta.added_parts.push(self.convert_to_plm_part(node));
                 }
             }
         }
         
         fn detect_modified_parts(&self, delta: &mut PLMDelta, baseline: &PLMBaseline) {
-            for node in self.current_model.physical_nodes() {
-                if let Some(plm_info) = &node.plm {
-                    if let Some(baseline_part) = baseline.parts.get(&plm_info.part_number) {
-                        if let Some(diff) = self.compute_part_diff(node, baseline_part) {
-                            delta.modified_parts.push(diff);
-                        }
+            for (number, node) in self.parts() {
+                if let Some(baseline_part) = baseline.parts.get(number) {
+                    if let Some(diff) = self.compute_part_diff(node, baseline_part) {
+                        delta.modified_parts.push(diff);
                     }
                 }
             }
         }
         
         fn detect_deleted_parts(&self, delta: &mut PLMDelta, baseline: &PLMBaseline) {
-            for (part_number, _) in &baseline.parts {
-                if !self.current_model.has_part(part_number) {
+            for part_number in baseline.parts.keys() {
+                if !self.parts().any(|(number, _)| number == part_number) {
                     delta.deleted_parts.push(part_number.clone());
                 }
             }
         }
         
         fn detect_bom_changes(&self, delta: &mut PLMDelta, baseline: &PLMBaseline) {
-            for bom in self.current_model.boms() {
+            for bom in self.boms() {
                 if let Some(baseline_bom) = baseline.boms.get(&bom.parent_part) {
-                    self.compute_bom_diff(bom, baseline_bom, delta);
+                    self.compute_bom_diff(&bom, baseline_bom, delta);
                 }
             }
         }
         
-        fn compute_part_diff(&self, node: &NodeDecl, baseline: &PLMPart) -> Option<PartDiff> {
+        fn compute_part_diff(&self, node: &PhysicalNode, baseline: &PLMPart) -> Option<PartDiff> {
             let mut changes = Vec::new();
             
-            if let Some(plm_info) = &node.plm {
-                if let Some(new_cost) = plm_info.cost {
-                    if let Some(old_cost) = baseline.unit_cost {
-                        if (new_cost - old_cost).abs() > 0.01 {
-                            changes.push(AttributeChange {
-                                attribute: "unit_cost".to_string(),
-                                old_value: Some(AttributeValue::Number(old_cost)),
-                                new_value: Some(AttributeValue::Number(new_cost)),
-                                change_type: ChangeType::Modified,
-                            });
-                        }
+            if let Some(new_cost) = number_attr(node, "unit_cost") {
+                if let Some(old_cost) = baseline.unit_cost {
+                    if (new_cost - old_cost).abs() > 0.01 {
+                        changes.push(AttributeChange {
+                            attribute: "unit_cost".to_string(),
+                            old_value: Some(AttributeValue::Number(old_cost)),
+                            new_value: Some(AttributeValue::Number(new_cost)),
+                            change_type: ChangeType::Modified,
+                        });
                     }
                 }
-                
-                if plm_info.manufacturer != baseline.manufacturer {
-                    changes.push(AttributeChange {
-                        attribute: "manufacturer".to_string(),
-                        old_value: baseline.manufacturer.as_ref().map(|m| AttributeValue::String(m.clone())),
-                        new_value: plm_info.manufacturer.as_ref().map(|m| AttributeValue::String(m.clone())),
-                        change_type: ChangeType::Modified,
-                    });
-                }
             }
             
-            if let Some(safety) = &node.safety {
-                let new_safety = format!("{:?}", safety.asil);
+            let manufacturer = string_attr(node, "manufacturer");
+            if manufacturer != baseline.manufacturer.as_deref() {
+                changes.push(AttributeChange {
+                    attribute: "manufacturer".to_string(),
+                    old_value: baseline.manufacturer.as_ref().map(|m| AttributeValue::String(m.clone())),
+                    new_value: manufacturer.map(|m| AttributeValue::String(m.to_string())),
+                    change_type: ChangeType::Modified,
+                });
+            }
+            
+            if let Some(safety) = string_attr(node, "safety_level") {
+                let new_safety = safety.to_string();
                 if Some(&new_safety) != baseline.safety_level.as_ref() {
                     changes.push(AttributeChange {
                         attribute: "safety_level".to_string(),
@@ -652,16 +977,27 @@ pub mod delta_computer {
         }
         
         fn create_initial_sync(&self, delta: &mut PLMDelta) {
-            for node in self.current_model.physical_nodes() {
-                if node.plm.is_some() {
-                    delta.added_parts.push(self.convert_to_plm_part(node));
-                }
+            for (_, node) in self.parts() {
+                delta.added_parts.push(self.convert_to_plm_part(node));
             }
         }
         
         fn analyze_impact(&self, delta: &mut PLMDelta) {
             delta.impact_analysis.affected_parts = delta.affected_part_numbers();
             
+            if let Some(baseline) = &self.baseline {
+                let touched = delta.modified_parts.iter().map(|p| &p.part_number)
+                    .chain(&delta.deleted_parts)
+                    .chain(delta.bom_changes.iter().map(|c| &c.parent_part));
+                let mut released: Vec<String> = touched
+                    .filter(|number| baseline.parts.get(*number).is_some_and(|p| p.lifecycle_state.is_released()))
+                    .cloned()
+                    .collect();
+                released.sort();
+                released.dedup();
+                delta.impact_analysis.released_parts = released;
+            }
+            
             delta.impact_analysis.safety_impact = delta.modified_parts.iter().any(|p| {
                 p.changes.iter().any(|c| c.attribute == "safety_level")
             }) || delta.deleted_parts.iter().any(|part_num| {
@@ -718,24 +1054,22 @@ pub mod delta_computer {
             summary
         }
         
-        fn convert_to_plm_part(&self, node: &NodeDecl) -> PLMPart {
-            let plm_info = node.plm.as_ref().unwrap();
-            
+        fn convert_to_plm_part(&self, node: &PhysicalNode) -> PLMPart {
             PLMPart {
                 id: String::new(),
-                part_number: plm_info.part_number.clone(),
-                revision: plm_info.revision.clone().unwrap_or_else(|| "A".to_string()),
+                part_number: part_number(node).unwrap_or_default().to_string(),
+                revision: string_attr(node, "revision").unwrap_or("A").to_string(),
                 name: node.name.clone(),
-                description: node.description.clone(),
-                part_type: "Electronic Assembly".to_string(),
+                description: string_attr(node, "description").map(str::to_string),
+                part_type: string_attr(node, "part_type").unwrap_or("Electronic Assembly").to_string(),
                 lifecycle_state: LifecycleState::InWork,
-                manufacturer: plm_info.manufacturer.clone(),
+                manufacturer: string_attr(node, "manufacturer").map(str::to_string),
                 supplier: None,
-                unit_cost: plm_info.cost,
-                lead_time_weeks: plm_info.lead_time,
-                weight_kg: None,
-                material: None,
-                safety_level: node.safety.as_ref().map(|s| format!("{:?}", s.asil)),
+                unit_cost: number_attr(node, "unit_cost"),
+                lead_time_weeks: number_attr(node, "lead_time_weeks").map(|w| w as u32),
+                weight_kg: number_attr(node, "weight_kg"),
+                material: string_attr(node, "material").map(str::to_string),
+                safety_level: string_attr(node, "safety_level").map(str::to_string),
                 custom_attributes: HashMap::new(),
                 created_at: Utc::now(),
                 modified_at: Utc::now(),
@@ -1,12 +1,16 @@
 use async_trait::async_trait;
-use reqwest::{Client, header};
+use futures::StreamExt;
+use reqwest::header;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use super::requirements_management::*;
+use super::transport::{ConnectionOptions, HttpRequest, HttpTransport, ReqwestTransport};
 
 pub struct PolarionConnector {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: PolarionConfig,
     session_id: Option<String>,
 }
@@ -16,8 +20,28 @@ pub struct PolarionConfig {
     pub server_url: String,
     pub project_id: String,
     pub auth: RMAuthentication,
+    /// Work items requested per `page[size]`; results are followed through `links.next`.
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+    /// Link requests in flight at once during `fetch_baseline`.
+    #[serde(default = "default_link_fetch_concurrency")]
+    pub link_fetch_concurrency: usize,
+    /// Timeout, proxy and extra CA certificate for the HTTP client.
+    #[serde(default)]
+    pub connection: ConnectionOptions,
 }
 
+fn default_page_size() -> usize {
+    100
+}
+
+fn default_link_fetch_concurrency() -> usize {
+    8
+}
+
+/// Times a request is retried after Polarion answers 429 Too Many Requests.
+const RATE_LIMIT_RETRIES: u32 = 5;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct PolarionWorkItem {
     id: String,
@@ -82,28 +106,26 @@ struct PolarionLinkWorkItems {
 struct PolarionWorkItemsResponse {
     #[serde(rename = "workItems")]
     work_items: Vec<PolarionWorkItem>,
+    #[serde(default)]
+    links: PolarionLinks,
+}
+
+/// JSON:API pagination links of a list response.
+#[derive(Debug, Default, Deserialize)]
+struct PolarionLinks {
+    next: Option<String>,
 }
 
 impl PolarionConnector {
     pub fn new(config: PolarionConfig) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json"),
-        );
-        
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .expect("Failed to create HTTP client");
-        
+        let transport = ReqwestTransport::with_options(Duration::from_secs(60), &config.connection);
+        Self::with_transport(config, Arc::new(transport))
+    }
+    
+    /// Connector that sends its requests through `transport`.
+    pub fn with_transport(config: PolarionConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self {
-            client,
+            transport,
             config,
             session_id: None,
         }
@@ -130,10 +152,10 @@ impl PolarionConnector {
             }
         };
         
-        let response = self.client
-            .post(&login_url)
-            .json(&credentials)
-            .send()
+        let req = HttpRequest::post(&login_url)
+            .json(&credentials);
+        let response = self.transport
+            .send(req)
             .await
             .map_err(|e| RMError::AuthenticationError(e.to_string()))?;
         
@@ -161,15 +183,17 @@ impl PolarionConnector {
     }
     
     async fn get_with_auth(&self, path: &str) -> Result<reqwest::Response, RMError> {
-        let url = self.build_url(path);
-        
-        let mut req = self.client.get(&url);
+        self.get_url_with_auth(&self.build_url(path)).await
+    }
+    
+    async fn get_url_with_auth(&self, url: &str) -> Result<reqwest::Response, RMError> {
+        let mut req = HttpRequest::get(url);
         
         if let Some(token) = &self.session_id {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -177,13 +201,13 @@ impl PolarionConnector {
     async fn post_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.post(&url).json(body);
+        let mut req = HttpRequest::post(&url).json(body);
         
         if let Some(token) = &self.session_id {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -191,13 +215,13 @@ impl PolarionConnector {
     async fn patch_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.patch(&url).json(body);
+        let mut req = HttpRequest::patch(&url).json(body);
         
         if let Some(token) = &self.session_id {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
@@ -205,17 +229,100 @@ impl PolarionConnector {
     async fn delete_with_auth(&self, path: &str) -> Result<reqwest::Response, RMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.delete(&url);
+        let mut req = HttpRequest::delete(&url);
         
         if let Some(token) = &self.session_id {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| RMError::NetworkError(e.to_string()))
     }
     
+    /// All work items matching `query`, following `links.next` page by page.
+    /// Links of one work item; empty when they can't be read.
+    async fn fetch_linked_work_items(&self, req_id: &str) -> Vec<TraceLink> {
+        #[derive(Deserialize)]
+        struct LinkedItemsResponse {
+            #[serde(rename = "linkedWorkItems")]
+            linked_work_items: Vec<PolarionLinkedWorkItem>,
+        }
+        
+        let links_path = format!(
+            "/projects/{}/workitems/{}/linkedWorkItems",
+            self.config.project_id,
+            req_id
+        );
+        let response = super::retry_rate_limited(RATE_LIMIT_RETRIES, || self.get_with_auth(&links_path)).await;
+        let Ok(links_response) = response else { return Vec::new() };
+        if !links_response.status().is_success() {
+            return Vec::new();
+        }
+        let Ok(links_data) = links_response.json::<LinkedItemsResponse>().await else {
+            return Vec::new();
+        };
+        
+        links_data.linked_work_items.into_iter()
+            .map(|link| TraceLink {
+                id: format!("{}-{}", req_id, link.work_item_id),
+                source_id: req_id.to_string(),
+                target_id: link.work_item_id,
+                link_type: self.map_from_link_role(&link.role),
+                rationale: None,
+                created_at: chrono::Utc::now(),
+                created_by: "polarion".to_string(),
+            })
+            .collect()
+    }
+    
+    async fn fetch_work_items(&self, query: &str) -> Result<Vec<PolarionWorkItem>, RMError> {
+        let mut url = self.build_url(&format!(
+            "/projects/{}/workitems?query={}&page[size]={}&page[number]=1",
+            self.config.project_id,
+            super::url_encode(query),
+            self.config.page_size.max(1)
+        ));
+        let mut work_items = Vec::new();
+        
+        loop {
+            let response = self.get_url_with_auth(&url).await?;
+            
+            if !response.status().is_success() {
+                return Err(RMError::APIError(
+                    format!("Failed to fetch work items: {}", response.status())
+                ));
+            }
+            
+            let page: PolarionWorkItemsResponse = response.json().await
+                .map_err(|e| RMError::SerializationError(e.to_string()))?;
+            let exhausted = page.work_items.is_empty();
+            work_items.extend(page.work_items);
+            
+            let next = match page.links.next {
+                Some(next) if !exhausted => self.resolve_link(&next),
+                _ => break,
+            };
+            if next == url {
+                break;
+            }
+            url = next;
+        }
+        
+        Ok(work_items)
+    }
+    
+    /// `links.next` may be absolute or relative to the server.
+    fn resolve_link(&self, link: &str) -> String {
+        if link.starts_with("http://") || link.starts_with("https://") {
+            link.to_string()
+        } else if link.starts_with("/polarion/") {
+            format!("{}{}", self.config.server_url, link)
+        } else {
+            self.build_url(link)
+        }
+    }
+    
     fn convert_to_requirement(&self, work_item: PolarionWorkItem) -> Requirement {
         let text = work_item.description
             .as_ref()
@@ -386,23 +493,9 @@ impl RequirementsConnector for PolarionConnector {
     }
     
     async fn fetch_baseline(&self) -> Result<RMBaseline, RMError> {
-        let path = format!(
-            "/projects/{}/workitems?query=type:requirement",
-            self.config.project_id
-        );
+        let work_items = self.fetch_work_items("type:requirement").await?;
         
-        let response = self.get_with_auth(&path).await?;
-        
-        if !response.status().is_success() {
-            return Err(RMError::APIError(
-                format!("Failed to fetch baseline: {}", response.status())
-            ));
-        }
-        
-        let work_items_response: PolarionWorkItemsResponse = response.json().await
-            .map_err(|e| RMError::SerializationError(e.to_string()))?;
-        
-        let requirements: HashMap<String, Requirement> = work_items_response.work_items
+        let requirements: HashMap<String, Requirement> = work_items
             .into_iter()
             .map(|wi| {
                 let req = self.convert_to_requirement(wi);
@@ -410,38 +503,13 @@ impl RequirementsConnector for PolarionConnector {
             })
             .collect();
         
-        let mut trace_links = Vec::new();
-        for (req_id, _) in &requirements {
-            let links_path = format!(
-                "/projects/{}/workitems/{}/linkedWorkItems",
-                self.config.project_id,
-                req_id
-            );
-            
-            if let Ok(links_response) = self.get_with_auth(&links_path).await {
-                if links_response.status().is_success() {
-                    #[derive(Deserialize)]
-                    struct LinkedItemsResponse {
-                        #[serde(rename = "linkedWorkItems")]
-                        linked_work_items: Vec<PolarionLinkedWorkItem>,
-                    }
-                    
-                    if let Ok(links_data) = links_response.json::<LinkedItemsResponse>().await {
-                        for link in links_data.linked_work_items {
-                            trace_links.push(TraceLink {
-                                id: format!("{}-{}", req_id, link.work_item_id),
-                                source_id: req_id.clone(),
-                                target_id: link.work_item_id,
-                                link_type: self.map_from_link_role(&link.role),
-                                rationale: None,
-                                created_at: chrono::Utc::now(),
-                                created_by: "polarion".to_string(),
-                            });
-                        }
-                    }
-                }
-            }
-        }
+        let req_ids: Vec<String> = requirements.keys().cloned().collect();
+        let mut trace_links: Vec<TraceLink> = futures::stream::iter(req_ids)
+            .map(|req_id| async move { self.fetch_linked_work_items(&req_id).await })
+            .buffer_unordered(self.config.link_fetch_concurrency.max(1))
+            .concat()
+            .await;
+        trace_links.sort_by(|a, b| (&a.source_id, &a.target_id, &a.id).cmp(&(&b.source_id, &b.target_id, &b.id)));
         
         Ok(RMBaseline {
             timestamp: chrono::Utc::now(),
@@ -679,24 +747,7 @@ impl RequirementsConnector for PolarionConnector {
         
         let query = query_parts.join(" AND ");
         
-        let path = format!(
-            "/projects/{}/workitems?query={}",
-            self.config.project_id,
-            urlencoding::encode(&query)
-        );
-        
-        let response = self.get_with_auth(&path).await?;
-        
-        if !response.status().is_success() {
-            return Err(RMError::APIError(
-                format!("Failed to query requirements: {}", response.status())
-            ));
-        }
-        
-        let work_items_response: PolarionWorkItemsResponse = response.json().await
-            .map_err(|e| RMError::SerializationError(e.to_string()))?;
-        
-        Ok(work_items_response.work_items.into_iter()
+        Ok(self.fetch_work_items(&query).await?.into_iter()
             .map(|wi| self.convert_to_requirement(wi))
             .collect())
     }
@@ -705,8 +756,8 @@ impl RequirementsConnector for PolarionConnector {
         let path = format!(
             "/projects/{}/traceability?from={}&to={}",
             self.config.project_id,
-            urlencoding::encode(from),
-            urlencoding::encode(to)
+            super::url_encode(from),
+            super::url_encode(to)
         );
         
         let response = self.get_with_auth(&path).await?;
@@ -747,6 +798,7 @@ impl RequirementsConnector for PolarionConnector {
                 column_id: col_id,
                 has_link: true,
                 link_type: Some(TraceLinkType::Traces),
+                rationale: None,
             })
             .collect();
         
@@ -854,7 +906,9 @@ impl RequirementsConnector for PolarionConnector {
             gaps,
         })
     }
-    
+}
+
+impl PolarionConnector {
     fn attribute_value_to_json(&self, value: &AttributeValue) -> serde_json::Value {
         match value {
             AttributeValue::String(s) => serde_json::json!(s),
@@ -879,9 +933,191 @@ mod tests {
                 username: "test".to_string(),
                 password: "test".to_string(),
             },
+            page_size: 100,
+            link_fetch_concurrency: default_link_fetch_concurrency(),
+            connection: ConnectionOptions::default(),
         };
         
         let connector = PolarionConnector::new(config);
         assert_eq!(connector.name(), "Polarion ALM");
     }
+    
+    fn work_item(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": "requirement",
+            "title": format!("Title {id}"),
+            "description": { "type": "text/plain", "content": "Shall work" },
+            "status": "approved",
+            "priority": "high",
+            "customFields": {},
+            "author": "tester",
+            "created": "2024-01-01T00:00:00Z",
+            "updated": "2024-01-01T00:00:00Z"
+        })
+    }
+    
+    #[tokio::test]
+    async fn fetch_baseline_follows_next_links_across_pages() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("GET", "/polarion/rest/v1/projects/afcs/workitems")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("query".into(), "type:requirement".into()),
+                mockito::Matcher::UrlEncoded("page[size]".into(), "2".into()),
+                mockito::Matcher::UrlEncoded("page[number]".into(), "1".into()),
+            ]))
+            .with_body(
+                serde_json::json!({
+                    "workItems": [work_item("REQ-1"), work_item("REQ-2")],
+                    "links": { "next": "/polarion/rest/v1/projects/afcs/workitems?query=type%3Arequirement&page%5Bsize%5D=2&page%5Bnumber%5D=2" }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/polarion/rest/v1/projects/afcs/workitems")
+            .match_query(mockito::Matcher::UrlEncoded("page[number]".into(), "2".into()))
+            .with_body(serde_json::json!({ "workItems": [work_item("REQ-3")], "links": {} }).to_string())
+            .create_async()
+            .await;
+        let links = server
+            .mock("GET", mockito::Matcher::Regex("/linkedWorkItems$".into()))
+            .with_body(r#"{"linkedWorkItems": []}"#)
+            .expect(3)
+            .create_async()
+            .await;
+        
+        let connector = PolarionConnector::new(PolarionConfig {
+            server_url: server.url(),
+            project_id: "afcs".to_string(),
+            auth: RMAuthentication::PAT { personal_access_token: "token".to_string() },
+            page_size: 2,
+            link_fetch_concurrency: default_link_fetch_concurrency(),
+            connection: ConnectionOptions::default(),
+        });
+        let baseline = connector.fetch_baseline().await.unwrap();
+        
+        first.assert_async().await;
+        second.assert_async().await;
+        links.assert_async().await;
+        let mut ids: Vec<_> = baseline.requirements.keys().cloned().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["REQ-1", "REQ-2", "REQ-3"]);
+    }
+    
+    #[tokio::test]
+    async fn fetch_baseline_converts_recorded_work_items() {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let api = "https://polarion.example/polarion/rest/v1/projects/afcs/workitems";
+        let transport = Arc::new(
+            MockTransport::new()
+                .on(
+                    reqwest::Method::GET,
+                    &format!("{api}?"),
+                    200,
+                    include_str!("../../../tests/fixtures/connectors/polarion_workitems.json"),
+                )
+                .on(
+                    reqwest::Method::GET,
+                    &format!("{api}/AFCS-101/linkedWorkItems"),
+                    200,
+                    include_str!("../../../tests/fixtures/connectors/polarion_linked_afcs_101.json"),
+                )
+                .on(reqwest::Method::GET, &format!("{api}/AFCS-102/linkedWorkItems"), 200, r#"{"linkedWorkItems": []}"#),
+        );
+        let connector = PolarionConnector::with_transport(
+            PolarionConfig {
+                server_url: "https://polarion.example".to_string(),
+                project_id: "afcs".to_string(),
+                auth: RMAuthentication::PAT { personal_access_token: "token".to_string() },
+                page_size: 100,
+                link_fetch_concurrency: default_link_fetch_concurrency(),
+                connection: ConnectionOptions::default(),
+            },
+            transport.clone(),
+        );
+        let baseline = connector.fetch_baseline().await.unwrap();
+        
+        assert_eq!(transport.requests().len(), 3);
+        
+        let req = &baseline.requirements["AFCS-101"];
+        assert_eq!(req.title, "Autopilot disconnect");
+        assert_eq!(req.text, "The autopilot shall disconnect on stick force above 25 lbf.");
+        assert_eq!(req.requirement_type, RequirementType::Safety);
+        assert_eq!(req.status, RequirementStatus::Approved);
+        assert_eq!(req.priority, RequirementPriority::Critical);
+        assert_eq!(req.created_at.to_rfc3339(), "2024-01-08T08:15:00+00:00");
+        assert_eq!(req.created_by, "jdoe");
+        assert!(matches!(&req.custom_attributes["dal"], AttributeValue::String(dal) if dal == "A"));
+        assert!(matches!(
+            &req.custom_attributes["hazardRef"],
+            AttributeValue::List(refs) if refs == &["HAZ-3"]
+        ));
+        
+        let other = &baseline.requirements["AFCS-102"];
+        assert_eq!(other.requirement_type, RequirementType::Functional);
+        assert_eq!(other.status, RequirementStatus::UnderReview);
+        assert_eq!(other.text, "");
+        
+        let mut links: Vec<_> = baseline.trace_links.iter()
+            .map(|l| (l.source_id.as_str(), l.target_id.as_str(), l.link_type.clone()))
+            .collect();
+        links.sort_by_key(|l| l.1);
+        assert_eq!(
+            links,
+            vec![
+                ("AFCS-101", "AFCS-102", TraceLinkType::Refines),
+                ("AFCS-101", "TC-7", TraceLinkType::VerifiedBy),
+            ]
+        );
+    }
+    
+    #[tokio::test]
+    async fn fetch_baseline_fans_out_link_requests_and_sorts_the_links() {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let api = "https://polarion.example/polarion/rest/v1/projects/afcs/workitems";
+        let linked = |targets: &[&str]| {
+            let items: Vec<_> = targets.iter()
+                .map(|id| serde_json::json!({ "workItemId": id, "role": "refines" }))
+                .collect();
+            serde_json::json!({ "linkedWorkItems": items }).to_string()
+        };
+        let ids = ["REQ-1", "REQ-2", "REQ-3", "REQ-4"];
+        let items: Vec<_> = ids.iter().map(|id| work_item(id)).collect();
+        let transport = MockTransport::new()
+            .on(reqwest::Method::GET, &format!("{api}?"), 200, serde_json::json!({ "workItems": items }).to_string())
+            .on(reqwest::Method::GET, &format!("{api}/REQ-1/linkedWorkItems"), 200, linked(&["REQ-4", "REQ-2"]))
+            // Rate-limited once, then answered.
+            .on(reqwest::Method::GET, &format!("{api}/REQ-2/linkedWorkItems"), 429, "")
+            .on(reqwest::Method::GET, &format!("{api}/REQ-2/linkedWorkItems"), 200, linked(&["REQ-3"]))
+            .on(reqwest::Method::GET, &format!("{api}/REQ-3/linkedWorkItems"), 200, linked(&[]))
+            .on(reqwest::Method::GET, &format!("{api}/REQ-4/linkedWorkItems"), 200, linked(&["REQ-1"]));
+        let transport = Arc::new(transport);
+        let connector = PolarionConnector::with_transport(
+            PolarionConfig {
+                server_url: "https://polarion.example".to_string(),
+                project_id: "afcs".to_string(),
+                auth: RMAuthentication::PAT { personal_access_token: "token".to_string() },
+                page_size: 100,
+                link_fetch_concurrency: 2,
+                connection: ConnectionOptions::default(),
+            },
+            transport.clone(),
+        );
+        
+        let baseline = connector.fetch_baseline().await.unwrap();
+        
+        let links: Vec<_> = baseline.trace_links.iter()
+            .map(|l| (l.source_id.as_str(), l.target_id.as_str()))
+            .collect();
+        assert_eq!(
+            links,
+            vec![("REQ-1", "REQ-2"), ("REQ-1", "REQ-4"), ("REQ-2", "REQ-3"), ("REQ-4", "REQ-1")]
+        );
+        assert_eq!(transport.requests().len(), 1 + ids.len() + 1);
+    }
 }
@@ -2,6 +2,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
+use crate::compiler::semantic::CustomValue;
+use super::transport::REDACTED;
+
+pub use crate::compiler::semantic::{CoverageGap, GapSeverity, GapType};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RMConfig {
@@ -11,7 +15,7 @@ pub struct RMConfig {
     pub mapping: RMMapping,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RMSystem {
     DOORS,
     DOORSNext,
@@ -30,7 +34,7 @@ pub struct RMConnectionConfig {
     pub retry_count: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum RMAuthentication {
     OAuth2 {
         client_id: String,
@@ -49,6 +53,25 @@ pub enum RMAuthentication {
     },
 }
 
+/// Secrets masked, so configs can be logged.
+impl std::fmt::Debug for RMAuthentication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OAuth2 { client_id, token_url, .. } => f.debug_struct("OAuth2")
+                .field("client_id", client_id)
+                .field("client_secret", &REDACTED)
+                .field("token_url", token_url)
+                .finish(),
+            Self::BasicAuth { username, .. } => f.debug_struct("BasicAuth")
+                .field("username", username)
+                .field("password", &REDACTED)
+                .finish(),
+            Self::APIToken { .. } => f.debug_struct("APIToken").field("token", &REDACTED).finish(),
+            Self::PAT { .. } => f.debug_struct("PAT").field("personal_access_token", &REDACTED).finish(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RMSyncPolicy {
     pub mode: RMSyncMode,
@@ -104,6 +127,18 @@ pub trait RequirementsConnector: Send + Sync {
     
     async fn create_requirement(&self, req: &Requirement) -> Result<String, RMError>;
     
+    /// Create `reqs`, returning the remote IDs in input order. Connectors
+    /// with a faster path override this; the default creates one at a time.
+    /// If any create fails the error is [`RMError::PartialFailure`], which
+    /// still carries the IDs of the requirements that were created.
+    async fn create_requirements_bulk(&self, reqs: &[Requirement]) -> Result<Vec<String>, RMError> {
+        let mut results = Vec::with_capacity(reqs.len());
+        for req in reqs {
+            results.push(self.create_requirement(req).await);
+        }
+        collect_bulk_results(reqs, results)
+    }
+    
     async fn update_requirement(&self, req_id: &str, changes: &RequirementChanges) -> Result<(), RMError>;
     
     async fn delete_requirement(&self, req_id: &str) -> Result<(), RMError>;
@@ -172,7 +207,7 @@ pub struct Requirement {
     pub modified_by: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum RequirementType {
     Stakeholder,
     System,
@@ -239,17 +274,7 @@ pub struct TraceLink {
     pub created_by: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum TraceLinkType {
-    Satisfies,
-    DerivedFrom,
-    Refines,
-    AllocatedTo,
-    VerifiedBy,
-    Traces,
-    Implements,
-    DependsOn,
-}
+pub use crate::compiler::semantic::TraceLinkType;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -261,6 +286,31 @@ pub enum AttributeValue {
     List(Vec<String>),
 }
 
+impl AttributeValue {
+    /// An ArcLang custom attribute (`attr key: value`); list items become
+    /// strings.
+    pub fn from_custom(value: &CustomValue) -> Self {
+        match value {
+            CustomValue::Boolean(b) => Self::Boolean(*b),
+            CustomValue::Number(n) => Self::Number(*n),
+            CustomValue::Date(date) => Self::Date(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+            CustomValue::String(s) => Self::String(s.clone()),
+            CustomValue::List(items) => Self::List(
+                items
+                    .iter()
+                    .map(|item| match item {
+                        CustomValue::String(s) => s.clone(),
+                        CustomValue::Date(date) => date.format("%Y-%m-%d").to_string(),
+                        CustomValue::Number(n) => n.to_string(),
+                        CustomValue::Boolean(b) => b.to_string(),
+                        CustomValue::List(_) => serde_json::to_string(item).unwrap_or_default(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequirementChanges {
     pub title: Option<String>,
@@ -272,7 +322,7 @@ pub struct RequirementChanges {
     pub custom_attributes: HashMap<String, AttributeValue>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RequirementFilter {
     pub requirement_type: Option<RequirementType>,
     pub status: Option<RequirementStatus>,
@@ -293,6 +343,162 @@ pub struct TraceabilityMatrix {
     pub coverage_percentage: f64,
 }
 
+impl TraceabilityMatrix {
+    /// The matrix of a local model: requirements as rows; components, then
+    /// test cases, as columns; a cell wherever a trace or `test_case` links
+    /// the two.
+    pub fn from_model(model: &crate::compiler::semantic::SemanticModel) -> Self {
+        let rows: Vec<MatrixRow> = model.requirements
+            .iter()
+            .map(|req| MatrixRow { id: req.id.clone(), label: req.title.clone().unwrap_or_else(|| req.description.clone()) })
+            .collect();
+        let mut columns: Vec<MatrixColumn> = model.components
+            .iter()
+            .map(|comp| MatrixColumn { id: comp.id.clone(), label: comp.name.clone() })
+            .collect();
+        let test_cases = model.verifications.iter().map(|v| &v.test_case).chain(
+            model.traces.iter().filter(|t| t.link_type == TraceLinkType::VerifiedBy).map(|t| &t.from),
+        );
+        for test_case in test_cases {
+            if !columns.iter().any(|c| &c.id == test_case) {
+                columns.push(MatrixColumn { id: test_case.clone(), label: test_case.clone() });
+            }
+        }
+
+        let mut cells: Vec<MatrixCell> = Vec::new();
+        let mut link = |row: &str, column: &str, link_type: TraceLinkType, rationale: Option<&String>| {
+            let known = cells.iter().any(|cell| cell.row_id == row && cell.column_id == column);
+            if !known && rows.iter().any(|r| r.id == row) && columns.iter().any(|c| c.id == column) {
+                cells.push(MatrixCell {
+                    row_id: row.to_string(),
+                    column_id: column.to_string(),
+                    has_link: true,
+                    link_type: Some(link_type),
+                    rationale: rationale.cloned(),
+                });
+            }
+        };
+        for verification in &model.verifications {
+            link(&verification.requirement, &verification.test_case, TraceLinkType::VerifiedBy, None);
+        }
+        for trace in &model.traces {
+            // Either end may be the requirement (`allocated_to` points away
+            // from it, `satisfies` towards it).
+            link(&trace.from, &trace.to, trace.link_type.clone(), trace.rationale.as_ref());
+            link(&trace.to, &trace.from, trace.link_type.clone(), trace.rationale.as_ref());
+        }
+
+        let covered_rows = rows.iter()
+            .filter(|row| cells.iter().any(|cell| cell.row_id == row.id))
+            .count();
+        let coverage_percentage = if rows.is_empty() {
+            0.0
+        } else {
+            (covered_rows as f64 / rows.len() as f64) * 100.0
+        };
+
+        Self {
+            source_type: "Requirement".to_string(),
+            target_type: "Component / Test case".to_string(),
+            rows,
+            columns,
+            cells,
+            coverage_percentage,
+        }
+    }
+
+    /// The matrix of the traces between two element kinds (see
+    /// [`SemanticModel::traces_between`]): every element of kind `from` as a
+    /// row, every element of kind `to` as a column. A side left `None` holds
+    /// only the elements some matching trace reaches.
+    ///
+    /// [`SemanticModel::traces_between`]: crate::compiler::semantic::SemanticModel::traces_between
+    pub fn between(model: &crate::compiler::semantic::SemanticModel, from: Option<&str>, to: Option<&str>) -> Self {
+        let of_kind = |kind: &str| -> Vec<String> {
+            let mut ids: Vec<String> = model.all_elements.keys()
+                .chain(model.verifications.iter().map(|v| &v.test_case))
+                .filter(|id| model.element_kind(id) == Some(kind))
+                .cloned()
+                .collect();
+            ids.sort();
+            ids.dedup();
+            ids
+        };
+        let mut row_ids = from.map(of_kind).unwrap_or_default();
+        let mut column_ids = to.map(of_kind).unwrap_or_default();
+
+        // Each link as (row, column, type, rationale), in both orientations
+        // that fit the kinds.
+        let is = |id: &str, kind: Option<&str>| kind.is_none() || model.element_kind(id) == kind;
+        let mut links: Vec<(&str, &str, TraceLinkType, Option<&String>)> = Vec::new();
+        for trace in model.traces_between(from, to) {
+            for (a, b) in [(&trace.from, &trace.to), (&trace.to, &trace.from)] {
+                if is(a, from) && is(b, to) {
+                    links.push((a, b, trace.link_type.clone(), trace.rationale.as_ref()));
+                }
+            }
+        }
+        if from.is_none_or(|kind| kind == "requirement") && to.is_none_or(|kind| kind == "test_case") {
+            for verification in &model.verifications {
+                links.push((&verification.requirement, &verification.test_case, TraceLinkType::VerifiedBy, None));
+            }
+        }
+        for (row, column, ..) in &links {
+            if from.is_none() && !row_ids.iter().any(|id| id == row) {
+                row_ids.push(row.to_string());
+            }
+            if to.is_none() && !column_ids.iter().any(|id| id == column) {
+                column_ids.push(column.to_string());
+            }
+        }
+
+        let mut cells: Vec<MatrixCell> = Vec::new();
+        for (row, column, link_type, rationale) in links {
+            if !cells.iter().any(|cell| cell.row_id == row && cell.column_id == column) {
+                cells.push(MatrixCell {
+                    row_id: row.to_string(),
+                    column_id: column.to_string(),
+                    has_link: true,
+                    link_type: Some(link_type),
+                    rationale: rationale.cloned(),
+                });
+            }
+        }
+
+        let label = |id: &String| -> String {
+            if let Some(req) = model.get_requirement(id) {
+                return req.title.clone().unwrap_or_else(|| req.description.clone());
+            }
+            model.all_elements.get(id).map_or_else(|| id.clone(), |e| e.name.clone())
+        };
+        let rows: Vec<MatrixRow> = row_ids.iter().map(|id| MatrixRow { id: id.clone(), label: label(id) }).collect();
+        let columns: Vec<MatrixColumn> = column_ids.iter().map(|id| MatrixColumn { id: id.clone(), label: label(id) }).collect();
+
+        let covered_rows = rows.iter()
+            .filter(|row| cells.iter().any(|cell| cell.row_id == row.id))
+            .count();
+        let coverage_percentage = if rows.is_empty() {
+            0.0
+        } else {
+            (covered_rows as f64 / rows.len() as f64) * 100.0
+        };
+
+        Self {
+            source_type: from.unwrap_or("any").to_string(),
+            target_type: to.unwrap_or("any").to_string(),
+            rows,
+            columns,
+            cells,
+            coverage_percentage,
+        }
+    }
+
+    /// The cell at `row`, `column`, when they are linked.
+    pub fn cell(&self, row: &str, column: &str) -> Option<&MatrixCell> {
+        self.cells.iter().find(|cell| cell.has_link && cell.row_id == row && cell.column_id == column)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatrixRow {
     pub id: String,
@@ -311,6 +517,9 @@ pub struct MatrixCell {
     pub column_id: String,
     pub has_link: bool,
     pub link_type: Option<TraceLinkType>,
+    /// Why the link exists, when the trace says.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -331,30 +540,6 @@ pub struct CoverageStats {
     pub coverage_percentage: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CoverageGap {
-    pub requirement_id: String,
-    pub gap_type: GapType,
-    pub severity: GapSeverity,
-    pub description: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum GapType {
-    NoTraceToDesign,
-    NoVerification,
-    NoImplementation,
-    OrphanRequirement,
-    BrokenTraceLink,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum GapSeverity {
-    Critical,
-    High,
-    Medium,
-    Low,
-}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RMDelta {
@@ -421,6 +606,35 @@ pub enum RMError {
     
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    
+    #[error("{} of {} creates failed", failed.len(), created.len() + failed.len())]
+    PartialFailure {
+        /// `(local id, remote id)` of every requirement that was created.
+        created: Vec<(String, String)>,
+        /// `(local id, error)` of every requirement that was not.
+        failed: Vec<(String, String)>,
+    },
+}
+
+/// Fold per-requirement create results (in `reqs` order) into the
+/// `create_requirements_bulk` return value.
+pub fn collect_bulk_results(
+    reqs: &[Requirement],
+    results: Vec<Result<String, RMError>>,
+) -> Result<Vec<String>, RMError> {
+    let mut created = Vec::new();
+    let mut failed = Vec::new();
+    for (req, result) in reqs.iter().zip(results) {
+        match result {
+            Ok(id) => created.push((req.id.clone(), id)),
+            Err(e) => failed.push((req.id.clone(), e.to_string())),
+        }
+    }
+    if failed.is_empty() {
+        Ok(created.into_iter().map(|(_, id)| id).collect())
+    } else {
+        Err(RMError::PartialFailure { created, failed })
+    }
 }
 
 pub struct RMIntegrationManager {
@@ -455,11 +669,18 @@ impl RMIntegrationManager {
             sync_timestamp: Utc::now(),
         };
         
-        for req in &delta.added_requirements {
-            match connector.create_requirement(req).await {
-                Ok(id) => result.requirements_created.push(id),
+        if !delta.added_requirements.is_empty() {
+            match connector.create_requirements_bulk(&delta.added_requirements).await {
+                Ok(ids) => result.requirements_created.extend(ids),
+                Err(RMError::PartialFailure { created, failed }) => {
+                    result.requirements_created.extend(created.into_iter().map(|(_, id)| id));
+                    result.requirements_failed.extend(failed);
+                    result.success = false;
+                }
                 Err(e) => {
-                    result.requirements_failed.push((req.id.clone(), e.to_string()));
+                    result.requirements_failed.extend(
+                        delta.added_requirements.iter().map(|req| (req.id.clone(), e.to_string()))
+                    );
                     result.success = false;
                 }
             }
@@ -482,6 +703,7 @@ impl RMIntegrationManager {
                     Ok(id) => result.trace_links_created.push(id),
                     Err(e) => {
                         result.trace_links_failed.push((link.id.clone(), e.to_string()));
+                        result.success = false;
                     }
                 }
             }
@@ -574,7 +796,7 @@ impl RMDelta {
 
 pub mod delta_computer {
     use super::*;
-    use crate::compiler::semantic::SemanticModel;
+    use crate::compiler::semantic::{RequirementInfo, SemanticModel};
     
     pub struct RMDeltaComputer {
         current_model: SemanticModel,
@@ -614,7 +836,7 @@ pub mod delta_computer {
         }
         
         fn detect_added_requirements(&self, delta: &mut RMDelta, baseline: &RMBaseline) {
-            for req in self.current_model.requirements() {
+            for req in &self.current_model.requirements {
                 if !baseline.requirements.contains_key(&req.id) {
                     delta.added_requirements.push(self.convert_to_rm_requirement(req));
                 }
@@ -622,7 +844,7 @@ pub mod delta_computer {
         }
         
         fn detect_modified_requirements(&self, delta: &mut RMDelta, baseline: &RMBaseline) {
-            for req in self.current_model.requirements() {
+            for req in &self.current_model.requirements {
                 if let Some(baseline_req) = baseline.requirements.get(&req.id) {
                     if let Some(diff) = self.compute_requirement_diff(req, baseline_req) {
                         delta.modified_requirements.push(diff);
@@ -632,8 +854,8 @@ pub mod delta_computer {
         }
         
         fn detect_deleted_requirements(&self, delta: &mut RMDelta, baseline: &RMBaseline) {
-            for (req_id, _) in &baseline.requirements {
-                if !self.current_model.has_requirement(req_id) {
+            for req_id in baseline.requirements.keys() {
+                if !self.current_model.requirements.iter().any(|r| &r.id == req_id) {
                     delta.deleted_requirements.push(req_id.clone());
                 }
             }
@@ -642,57 +864,53 @@ pub mod delta_computer {
         fn detect_trace_link_changes(&self, delta: &mut RMDelta, baseline: &RMBaseline) {
             let current_links = self.extract_trace_links();
             
+            // Remote link ids are assigned by the RM tool: match on endpoints and type.
+            let same = |a: &TraceLink, b: &TraceLink| {
+                a.source_id == b.source_id && a.target_id == b.target_id && a.link_type == b.link_type
+            };
+            
             for link in &current_links {
-                if !baseline.trace_links.iter().any(|bl| bl.id == link.id) {
+                if !baseline.trace_links.iter().any(|bl| same(bl, link)) {
                     delta.added_trace_links.push(link.clone());
                 }
             }
             
             for baseline_link in &baseline.trace_links {
-                if !current_links.iter().any(|cl| cl.id == baseline_link.id) {
+                if !current_links.iter().any(|cl| same(cl, baseline_link)) {
                     delta.deleted_trace_links.push(baseline_link.id.clone());
                 }
             }
         }
         
         fn create_initial_sync(&self, delta: &mut RMDelta) {
-            for req in self.current_model.requirements() {
+            for req in &self.current_model.requirements {
                 delta.added_requirements.push(self.convert_to_rm_requirement(req));
             }
             
             delta.added_trace_links = self.extract_trace_links();
         }
         
-        fn compute_requirement_diff(&self, current: &RequirementDecl, baseline: &Requirement) 
+        fn compute_requirement_diff(&self, current: &RequirementInfo, baseline: &Requirement) 
             -> Option<RequirementDiff> {
             let mut changes = Vec::new();
             
-            if current.title != baseline.title {
+            let title = current.title.as_deref().unwrap_or(&current.id);
+            if title != baseline.title {
                 changes.push(AttributeChange {
                     attribute: "title".to_string(),
                     old_value: Some(AttributeValue::String(baseline.title.clone())),
-                    new_value: Some(AttributeValue::String(current.title.clone())),
+                    new_value: Some(AttributeValue::String(title.to_string())),
                 });
             }
             
-            if current.text != baseline.text {
+            if current.description != baseline.text {
                 changes.push(AttributeChange {
                     attribute: "text".to_string(),
                     old_value: Some(AttributeValue::String(baseline.text.clone())),
-                    new_value: Some(AttributeValue::String(current.text.clone())),
+                    new_value: Some(AttributeValue::String(current.description.clone())),
                 });
             }
             
-            if let Some(rationale) = &current.rationale {
-                if Some(rationale) != baseline.rationale.as_ref() {
-                    changes.push(AttributeChange {
-                        attribute: "rationale".to_string(),
-                        old_value: baseline.rationale.as_ref().map(|r| AttributeValue::String(r.clone())),
-                        new_value: Some(AttributeValue::String(rationale.clone())),
-                    });
-                }
-            }
-            
             if changes.is_empty() {
                 None
             } else {
@@ -704,58 +922,56 @@ pub mod delta_computer {
         }
         
         fn extract_trace_links(&self) -> Vec<TraceLink> {
-            let mut links = Vec::new();
-            
-            for req in self.current_model.requirements() {
-                for trace in &req.traces.satisfies {
-                    links.push(TraceLink {
-                        id: format!("{}-satisfies-{}", req.id, trace),
-                        source_id: req.id.clone(),
-                        target_id: trace.clone(),
-                        link_type: TraceLinkType::Satisfies,
-                        rationale: None,
-                        created_at: Utc::now(),
-                        created_by: "arclang".to_string(),
-                    });
-                }
-                
-                for trace in &req.traces.verified_by {
-                    links.push(TraceLink {
-                        id: format!("{}-verifiedby-{}", req.id, trace),
-                        source_id: req.id.clone(),
-                        target_id: trace.clone(),
-                        link_type: TraceLinkType::VerifiedBy,
-                        rationale: None,
-                        created_at: Utc::now(),
-                        created_by: "arclang".to_string(),
-                    });
-                }
-            }
+            let is_requirement = |id: &str| self.current_model.requirements.iter().any(|r| r.id == id);
             
-            links
+            self.current_model
+                .traces
+                .iter()
+                .filter(|t| is_requirement(&t.from) || is_requirement(&t.to))
+                .map(|t| TraceLink {
+                    id: format!("{}-{}-{}", t.from, t.trace_type, t.to),
+                    source_id: t.from.clone(),
+                    target_id: t.to.clone(),
+                    link_type: self.map_link_type(&t.trace_type),
+                    rationale: t.rationale.clone(),
+                    created_at: Utc::now(),
+                    created_by: "arclang".to_string(),
+                })
+                .collect()
         }
         
-        fn convert_to_rm_requirement(&self, req: &RequirementDecl) -> Requirement {
+        fn convert_to_rm_requirement(&self, req: &RequirementInfo) -> Requirement {
+            let mut custom_attributes: HashMap<String, AttributeValue> = self
+                .current_model
+                .all_elements
+                .get(&req.id)
+                .map(|element| {
+                    element
+                        .custom_attributes
+                        .iter()
+                        .map(|(key, value)| (key.clone(), AttributeValue::from_custom(value)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let Some(level) = &req.safety_level {
+                custom_attributes.insert("safety_level".to_string(), AttributeValue::String(level.clone()));
+            }
             Requirement {
                 id: req.id.clone(),
-                external_id: req.plm_metadata.as_ref().and_then(|p| p.external_id.clone()),
-                title: req.title.clone(),
-                text: req.text.clone(),
-                requirement_type: self.map_requirement_type(&req.req_type),
-                status: self.map_requirement_status(&req.status),
+                external_id: None,
+                title: req.title.clone().unwrap_or_else(|| req.id.clone()),
+                text: req.description.clone(),
+                requirement_type: self.map_requirement_type(req.category.as_deref().unwrap_or("")),
+                status: RequirementStatus::Draft,
                 priority: self.map_priority(&req.priority),
-                rationale: req.rationale.clone(),
+                rationale: None,
                 acceptance_criteria: None,
-                verification_method: req.verification.as_ref().map(|v| self.map_verification_method(&v.method)),
+                verification_method: None,
                 verification_status: None,
-                compliance: req.compliance.iter().map(|c| ComplianceTag {
-                    standard: c.standard.clone(),
-                    section: c.section.clone(),
-                    level: c.level.clone(),
-                }).collect(),
-                custom_attributes: HashMap::new(),
-                parent_id: None,
-                children_ids: Vec::new(),
+                compliance: Vec::new(),
+                custom_attributes,
+                parent_id: req.parent_id.clone(),
+                children_ids: req.children_ids.clone(),
                 created_at: Utc::now(),
                 modified_at: Utc::now(),
                 created_by: "arclang".to_string(),
@@ -763,6 +979,19 @@ pub mod delta_computer {
             }
         }
         
+        fn map_link_type(&self, trace_type: &str) -> TraceLinkType {
+            match trace_type.to_lowercase().as_str() {
+                "satisfies" => TraceLinkType::Satisfies,
+                "derives" | "derived_from" => TraceLinkType::DerivedFrom,
+                "refines" => TraceLinkType::Refines,
+                "allocated_to" | "allocates" => TraceLinkType::AllocatedTo,
+                "verifies" | "validates" | "verified_by" => TraceLinkType::VerifiedBy,
+                "implements" | "realizes" => TraceLinkType::Implements,
+                "depends_on" => TraceLinkType::DependsOn,
+                _ => TraceLinkType::Traces,
+            }
+        }
+        
         fn map_requirement_type(&self, req_type: &str) -> RequirementType {
             match req_type.to_lowercase().as_str() {
                 "functional" => RequirementType::Functional,
@@ -774,16 +1003,6 @@ pub mod delta_computer {
             }
         }
         
-        fn map_requirement_status(&self, status: &str) -> RequirementStatus {
-            match status.to_lowercase().as_str() {
-                "draft" => RequirementStatus::Draft,
-                "approved" => RequirementStatus::Approved,
-                "rejected" => RequirementStatus::Rejected,
-                "obsolete" => RequirementStatus::Obsolete,
-                _ => RequirementStatus::UnderReview,
-            }
-        }
-        
         fn map_priority(&self, priority: &str) -> RequirementPriority {
             match priority.to_lowercase().as_str() {
                 "critical" => RequirementPriority::Critical,
@@ -793,15 +1012,6 @@ pub mod delta_computer {
             }
         }
         
-        fn map_verification_method(&self, method: &str) -> VerificationMethod {
-            match method.to_lowercase().as_str() {
-                "test" => VerificationMethod::Test,
-                "inspection" => VerificationMethod::Inspection,
-                "analysis" => VerificationMethod::Analysis,
-                _ => VerificationMethod::Demonstration,
-            }
-        }
-        
         fn generate_summary(&self, delta: &RMDelta) -> String {
             let mut summary = String::new();
             
@@ -0,0 +1,817 @@
+//! Glue between `.arclang/sync.toml` connectors and the model files:
+//! connector instantiation, credentials loading, merging a fetched
+//! requirements baseline into the sync-managed `.arc` file, and describing
+//! the API calls a push would make.
+//!
+//! The pull target is owned by `arclang sync pull` (it starts with
+//! [`PULL_HEADER`]); it is regenerated on every pull. Local requirements the
+//! remote no longer has are kept and reported as orphaned, never deleted.
+
+use super::credentials::Credentials;
+use super::doors::{DOORSConfig, DOORSConnector};
+use super::jama::{JamaConfig, JamaConnector};
+use super::jira::{JiraConfig, JiraConnector};
+use super::plm_integration::{
+    AuthenticationMethod, BOMChangeType, BOMStructureMapping, ConflictResolution, ConnectionConfig,
+    MappingConfig, PLMConfig, PLMConnector, PLMDelta, PLMError, PLMSystem, PlannedOperation, SyncFrequency,
+    SyncMode, SyncPolicy,
+};
+use super::polarion::{PolarionConfig, PolarionConnector};
+use super::teamcenter::{TeamcenterConfig, TeamcenterConnector, TeamcenterProtocol};
+use super::transport::ConnectionOptions;
+use super::three_dexperience::{SecurityContext, ThreeDExperienceConfig, ThreeDExperienceConnector};
+use super::windchill::{WindchillConfig, WindchillConnector};
+use super::requirements_management::{
+    self as rm, RMAuthentication, RMBaseline, RMConfig, RMConnectionConfig, RMConflictResolution,
+    RMDelta, RMError, RMMapping, RMSyncFrequency, RMSyncMode, RMSyncPolicy, RMSystem,
+    RequirementsConnector,
+};
+use crate::compiler::ast::{AttributeValue, Requirement, CUSTOM_ATTRIBUTE_PREFIX};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// First line of every file written by `sync pull`.
+pub const PULL_HEADER: &str = "// Synchronized by `arclang sync pull`";
+
+/// Read a credentials file (JSON, see [`Credentials`]) into the connector
+/// authentication, resolving its secret.
+pub fn load_credentials(path: &Path) -> Result<RMAuthentication, RMError> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        RMError::AuthenticationError(format!("cannot read credentials {}: {e}", path.display()))
+    })?;
+    let file: Credentials = serde_json::from_str(&text).map_err(|e| {
+        RMError::AuthenticationError(format!("invalid credentials {}: {e}", path.display()))
+    })?;
+    file.resolve()
+}
+
+/// Everything needed to reach one requirements-management project.
+pub struct RemoteProject<'a> {
+    /// Connector type from `sync.toml` (`jira`, `polarion`, `jama`, `doors`).
+    pub kind: &'a str,
+    pub url: &'a str,
+    /// Project key / id on the remote side.
+    pub project: &'a str,
+    /// Connector-specific settings (`issue_type`, `verification_field`,
+    /// `item_type`, `relationship_<link type>`, `database`, `module`,
+    /// `status_attribute`, `priority_attribute`, `link_fetch_concurrency`,
+    /// and for every connector `timeout_secs`, `proxy`, `ca_cert`).
+    pub options: &'a BTreeMap<String, String>,
+    pub auth: RMAuthentication,
+}
+
+impl RemoteProject<'_> {
+    fn option(&self, key: &str) -> Result<String, RMError> {
+        self.options
+            .get(key)
+            .cloned()
+            .ok_or_else(|| RMError::ValidationError(format!("{} connector needs option '{key}'", self.kind)))
+    }
+
+    /// `link_fetch_concurrency`: requests in flight while fetching links.
+    fn concurrency(&self) -> Result<usize, RMError> {
+        self.option_or("link_fetch_concurrency", "8")
+            .parse()
+            .map_err(|_| RMError::ValidationError(format!("{} option 'link_fetch_concurrency' must be a number", self.kind)))
+    }
+
+    /// `timeout_secs`, `proxy` and `ca_cert`: how to reach the server.
+    fn connection(&self) -> Result<ConnectionOptions, String> {
+        Ok(ConnectionOptions {
+            timeout_secs: self.options
+                .get("timeout_secs")
+                .map(|v| v.parse().map_err(|_| format!("{} option 'timeout_secs' must be a number", self.kind)))
+                .transpose()?,
+            proxy: self.options.get("proxy").cloned(),
+            ca_cert: self.options.get("ca_cert").map(PathBuf::from),
+        })
+    }
+
+    fn option_or(&self, key: &str, default: &str) -> String {
+        self.options.get(key).cloned().unwrap_or_else(|| default.to_string())
+    }
+
+    fn system(&self) -> Result<RMSystem, RMError> {
+        match self.kind {
+            "jira" => Ok(RMSystem::JIRA),
+            "polarion" => Ok(RMSystem::Polarion),
+            "jama" => Ok(RMSystem::Jama),
+            "doors" => Ok(RMSystem::DOORSNext),
+            other => Err(RMError::ValidationError(format!("'{other}' is not a requirements connector"))),
+        }
+    }
+
+    /// The `RMConfig` handed to `RequirementsConnector::connect`.
+    pub fn rm_config(&self) -> Result<RMConfig, RMError> {
+        Ok(RMConfig {
+            system: self.system()?,
+            connection: RMConnectionConfig {
+                server_url: self.url.to_string(),
+                authentication: self.auth.clone(),
+                timeout_seconds: 30,
+                retry_count: 0,
+            },
+            sync_policy: RMSyncPolicy {
+                mode: RMSyncMode::Bidirectional,
+                frequency: RMSyncFrequency::Manual,
+                conflict_resolution: RMConflictResolution::Manual,
+                auto_create_links: true,
+            },
+            mapping: RMMapping {
+                requirement_type_mappings: HashMap::new(),
+                attribute_mappings: HashMap::new(),
+                status_mappings: HashMap::new(),
+                priority_mappings: HashMap::new(),
+            },
+        })
+    }
+
+    /// Instantiate the connector for this project.
+    pub fn connector(&self) -> Result<Box<dyn RequirementsConnector>, RMError> {
+        let url = self.url.trim_end_matches('/').to_string();
+        let auth = self.auth.clone();
+        let connection = self.connection().map_err(RMError::ValidationError)?;
+        Ok(match self.system()? {
+            RMSystem::JIRA => Box::new(JiraConnector::new(JiraConfig {
+                base_url: url,
+                project_key: self.project.to_string(),
+                auth,
+                issue_type: self.option("issue_type").unwrap_or_else(|_| "Requirement".to_string()),
+                verification_field: self.options.get("verification_field").cloned(),
+                connection,
+            })),
+            RMSystem::Polarion => Box::new(PolarionConnector::new(PolarionConfig {
+                server_url: url,
+                project_id: self.project.to_string(),
+                auth,
+                page_size: self.option_or("page_size", "100")
+                    .parse()
+                    .map_err(|_| RMError::ValidationError("polarion option 'page_size' must be a number".to_string()))?,
+                link_fetch_concurrency: self.concurrency()?,
+                connection,
+            })),
+            RMSystem::Jama => {
+                let numeric = |key: &str, value: &str| {
+                    value.parse::<i64>()
+                        .map_err(|_| RMError::ValidationError(format!("jama option '{key}' must be a number")))
+                };
+                // `relationship_verified_by = "7"` overrides one link type.
+                let mut relationship_type_map = JamaConfig::default_relationship_type_map();
+                for (link_type, id) in relationship_type_map.iter_mut() {
+                    let key = format!("relationship_{}", snake(&format!("{link_type:?}")));
+                    if let Some(value) = self.options.get(&key) {
+                        *id = numeric(&key, value)?;
+                    }
+                }
+                Box::new(JamaConnector::new(JamaConfig {
+                    instance_url: url,
+                    project_id: self.project.parse().map_err(|_| {
+                        RMError::ValidationError(format!("Jama project id must be numeric, got '{}'", self.project))
+                    })?,
+                    auth,
+                    requirement_item_type: numeric(
+                        "item_type",
+                        &self.option_or("item_type", &JamaConfig::default_requirement_item_type().to_string()),
+                    )?,
+                    relationship_type_map,
+                    link_fetch_concurrency: self.concurrency()?,
+                    connection,
+                }))
+            }
+            _ => Box::new(DOORSConnector::new(DOORSConfig {
+                server_url: url,
+                database: self.option("database")?,
+                project: self.project.to_string(),
+                module: self.option("module")?,
+                auth,
+                status_attribute: self.option_or("status_attribute", "Status"),
+                priority_attribute: self.option_or("priority_attribute", "Priority"),
+                connection,
+            })),
+        })
+    }
+}
+
+impl RemoteProject<'_> {
+    fn plm_system(&self) -> Result<PLMSystem, PLMError> {
+        match self.kind {
+            "teamcenter" => Ok(PLMSystem::Teamcenter),
+            "windchill" => Ok(PLMSystem::Windchill),
+            "3dexperience" => Ok(PLMSystem::ThreeDExperience),
+            other => Err(PLMError::ValidationError(format!("'{other}' is not a PLM connector"))),
+        }
+    }
+
+    fn plm_auth(&self) -> AuthenticationMethod {
+        match self.auth.clone() {
+            RMAuthentication::BasicAuth { username, password } => AuthenticationMethod::BasicAuth { username, password },
+            RMAuthentication::OAuth2 { client_id, client_secret, token_url } => {
+                AuthenticationMethod::OAuth2 { client_id, client_secret, token_url }
+            }
+            RMAuthentication::APIToken { token: key } | RMAuthentication::PAT { personal_access_token: key } => {
+                AuthenticationMethod::APIKey { key, header: self.option_or("api_key_header", "Authorization") }
+            }
+        }
+    }
+
+    /// The `PLMConfig` handed to `PLMConnector::connect`.
+    pub fn plm_config(&self) -> Result<PLMConfig, PLMError> {
+        Ok(PLMConfig {
+            system: self.plm_system()?,
+            connection: ConnectionConfig {
+                url: self.url.to_string(),
+                authentication: self.plm_auth(),
+                timeout_seconds: 60,
+                retry_count: 0,
+            },
+            sync_policy: SyncPolicy {
+                mode: SyncMode::ArcLangToPLM,
+                frequency: SyncFrequency::Manual,
+                conflict_resolution: ConflictResolution::Manual,
+                // `push_changes` raises the change order itself
+                auto_create_eco: false,
+            },
+            mapping: MappingConfig {
+                part_mappings: Vec::new(),
+                bom_structure: BOMStructureMapping {
+                    structure_type: "Engineering".to_string(),
+                    quantity_field: "quantity".to_string(),
+                    reference_designator_field: "reference_designator".to_string(),
+                },
+                attribute_mappings: HashMap::new(),
+            },
+        })
+    }
+
+    /// Instantiate the PLM connector for this project.
+    pub fn plm_connector(&self) -> Result<Box<dyn PLMConnector>, PLMError> {
+        let url = self.url.trim_end_matches('/').to_string();
+        let auth = self.plm_auth();
+        let connection = self.connection().map_err(PLMError::ValidationError)?;
+        Ok(match self.plm_system()? {
+            PLMSystem::Teamcenter => Box::new(TeamcenterConnector::new(TeamcenterConfig {
+                base_url: url,
+                protocol: TeamcenterProtocol::REST,
+                pool_manager: self.option_or("pool_manager", ""),
+                auth,
+                item_type: self.option_or("item_type", "Item"),
+                revision_rule: self.option_or("revision_rule", "Latest Working"),
+                lifecycle_property: self.option_or("lifecycle_property", "release_status_list"),
+                connection,
+            })),
+            PLMSystem::Windchill => Box::new(WindchillConnector::new(WindchillConfig {
+                base_url: url,
+                context: self.option_or("context", "default"),
+                product: self.project.to_string(),
+                organization: self.option_or("organization", ""),
+                library: self.option_or("library", ""),
+                auth,
+                connection,
+            })),
+            _ => Box::new(ThreeDExperienceConnector::new(ThreeDExperienceConfig {
+                platform_url: url,
+                tenant: self.option_or("tenant", ""),
+                auth,
+                collaborative_space: self.project.to_string(),
+                security_context: SecurityContext {
+                    role: self.option_or("role", "VPLMCreator"),
+                    organization: self.option_or("organization", ""),
+                    project: self.project.to_string(),
+                },
+                connection,
+            })),
+        })
+    }
+}
+
+fn show<T: serde::Serialize>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .and_then(|v| serde_json::to_string(v).ok())
+        .unwrap_or_else(|| "∅".to_string())
+}
+
+/// The connector calls `sync push` makes for a requirements delta, one per
+/// line, with the field-level diff of every update.
+pub fn describe_rm_delta(delta: &RMDelta) -> Vec<String> {
+    let mut calls = Vec::new();
+    for req in &delta.added_requirements {
+        calls.push(format!("create_requirement {} {:?}: {:?}", req.id, req.title, req.text));
+    }
+    for diff in &delta.modified_requirements {
+        calls.push(format!("update_requirement {}", diff.requirement_id));
+        for change in &diff.changes {
+            calls.push(format!("    {}: {} → {}", change.attribute, show(&change.old_value), show(&change.new_value)));
+        }
+    }
+    for link in &delta.added_trace_links {
+        calls.push(format!("create_trace_link {} -[{:?}]-> {}", link.source_id, link.link_type, link.target_id));
+    }
+    calls
+}
+
+/// The connector calls of a PLM push plan, one per line, with the
+/// field-level diff of every update. BOM changes in `delta` are listed
+/// after them: `push_changes` does not restructure BOMs.
+pub fn describe_plm_plan(plan: &[PlannedOperation], delta: &PLMDelta) -> Vec<String> {
+    let mut calls = Vec::new();
+    for operation in plan {
+        match operation {
+            PlannedOperation::CreatePart(part) => {
+                calls.push(format!("create_part {} rev {} {:?}", part.part_number, part.revision, part.name));
+            }
+            PlannedOperation::UpdatePart(diff) => {
+                calls.push(format!("update_part {}", diff.part_number));
+                for change in &diff.changes {
+                    calls.push(format!("    {}: {} → {}", change.attribute, show(&change.old_value), show(&change.new_value)));
+                }
+            }
+            PlannedOperation::CreateEco { request, released_parts } => {
+                let reason = if released_parts.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (released: {})", released_parts.join(", "))
+                };
+                calls.push(format!("create_eco for {}{}", request.affected_items.join(", "), reason));
+            }
+        }
+    }
+    for change in &delta.bom_changes {
+        let what = match change.change_type {
+            BOMChangeType::ItemAdded => "add",
+            BOMChangeType::ItemRemoved => "remove",
+            BOMChangeType::QuantityChanged => "set quantity of",
+        };
+        calls.push(format!(
+            "BOM {}: {} {} (qty {}) — not pushed, edit the BOM in PLM",
+            change.parent_part, what, change.item.part_number, change.item.quantity
+        ));
+    }
+    calls
+}
+
+/// Outcome of merging a remote baseline into the local requirements.
+#[derive(Debug, Default)]
+pub struct PullPlan {
+    /// Merged requirements, local order first, then new remote ones.
+    pub requirements: Vec<Requirement>,
+    /// Requirement-to-requirement traces `(from, trace_type, to)`.
+    pub traces: Vec<(String, String, String)>,
+    pub added: Vec<String>,
+    /// Changed requirement ids with the attributes that changed.
+    pub changed: Vec<(String, Vec<String>)>,
+    pub unchanged: usize,
+    /// Local requirements absent from the remote baseline.
+    pub orphaned: Vec<String>,
+}
+
+fn text(value: impl Into<String>) -> AttributeValue {
+    AttributeValue::String(value.into())
+}
+
+/// `DerivedFrom` → `derived_from`, `Story Points` → `story_points`
+fn snake(name: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+fn convert_value(value: &rm::AttributeValue) -> AttributeValue {
+    match value {
+        rm::AttributeValue::String(s) => text(s.clone()),
+        rm::AttributeValue::Number(n) => AttributeValue::Number(*n),
+        rm::AttributeValue::Boolean(b) => AttributeValue::Boolean(*b),
+        rm::AttributeValue::Date(d) => text(d.format("%Y-%m-%d").to_string()),
+        rm::AttributeValue::List(items) => AttributeValue::List(items.iter().cloned().map(text).collect()),
+    }
+}
+
+/// ArcLang attributes carried by a remote requirement.
+fn remote_attributes(req: &rm::Requirement) -> Vec<(String, AttributeValue)> {
+    let mut attrs = vec![
+        ("title".to_string(), text(req.title.clone())),
+        ("description".to_string(), text(req.text.clone())),
+        ("priority".to_string(), text(format!("{:?}", req.priority))),
+        ("status".to_string(), text(snake(&format!("{:?}", req.status)))),
+        ("category".to_string(), text(snake(&format!("{:?}", req.requirement_type)))),
+    ];
+    let optional = [
+        ("external_id", req.external_id.clone()),
+        ("rationale", req.rationale.clone()),
+        ("acceptance_criteria", req.acceptance_criteria.clone()),
+        ("verification_method", req.verification_method.as_ref().map(|m| snake(&format!("{m:?}")))),
+        ("verification_status", req.verification_status.as_ref().map(|s| snake(&format!("{s:?}")))),
+        ("parent", req.parent_id.clone()),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            attrs.push((key.to_string(), text(value)));
+        }
+    }
+    let mut custom: Vec<_> = req.custom_attributes.iter().collect();
+    custom.sort_by(|a, b| a.0.cmp(b.0));
+    // Remote fields come back as custom attributes (`attr key: value`),
+    // except the safety level ArcLang models itself.
+    for (key, value) in custom {
+        let key = snake(key);
+        let key = if key == "safety_level" { key } else { format!("{CUSTOM_ATTRIBUTE_PREFIX}{key}") };
+        attrs.push((key, convert_value(value)));
+    }
+    attrs
+}
+
+/// Merge `baseline` over the `local` requirements (remote wins field by
+/// field; local-only attributes are kept).
+pub fn plan_pull(local: &[Requirement], local_traces: &[(String, String, String)], baseline: &RMBaseline) -> PullPlan {
+    let mut plan = PullPlan::default();
+
+    let mut remote: Vec<&rm::Requirement> = baseline.requirements.values().collect();
+    remote.sort_by(|a, b| a.id.cmp(&b.id));
+    let remote_by_id: HashMap<&str, &rm::Requirement> = remote.iter().map(|r| (r.id.as_str(), *r)).collect();
+    let external = |req: &Requirement| req.attributes.get("external_id").and_then(|v| v.as_string()).map(str::to_string);
+    let mut matched: HashSet<&str> = HashSet::new();
+
+    for req in local {
+        let found = remote_by_id.get(req.id.as_str()).copied().or_else(|| {
+            let ext = external(req)?;
+            remote.iter().copied().find(|r| r.external_id.as_deref() == Some(ext.as_str()))
+        });
+        let Some(remote_req) = found else {
+            plan.orphaned.push(req.id.clone());
+            plan.requirements.push(req.clone());
+            continue;
+        };
+        matched.insert(remote_req.id.as_str());
+        let mut merged = req.clone();
+        let mut changed = Vec::new();
+        for (key, value) in remote_attributes(remote_req) {
+            let differs = merged.attributes.get(&key).map(render_value) != Some(render_value(&value));
+            if differs {
+                changed.push(key.clone());
+                merged.attributes.insert(key, value);
+            }
+        }
+        if changed.is_empty() {
+            plan.unchanged += 1;
+        } else {
+            plan.changed.push((merged.id.clone(), changed));
+        }
+        plan.requirements.push(merged);
+    }
+
+    for remote_req in remote {
+        if matched.contains(remote_req.id.as_str()) {
+            continue;
+        }
+        plan.added.push(remote_req.id.clone());
+        plan.requirements.push(Requirement {
+            id: remote_req.id.clone(),
+            attributes: remote_attributes(remote_req).into_iter().collect(),
+        });
+    }
+
+    let known: HashSet<&str> = plan.requirements.iter().map(|r| r.id.as_str()).collect();
+    let mut seen = HashSet::new();
+    let remote_traces = baseline
+        .trace_links
+        .iter()
+        .map(|l| (l.source_id.clone(), snake(&format!("{:?}", l.link_type)), l.target_id.clone()));
+    for trace in local_traces.iter().cloned().chain(remote_traces) {
+        if known.contains(trace.0.as_str()) && known.contains(trace.2.as_str()) && seen.insert(trace.clone()) {
+            plan.traces.push(trace);
+        }
+    }
+    plan
+}
+
+fn quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"").replace('\r', "").replace('\n', "\\n")
+    )
+}
+
+fn render_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::String(s) => quote(s),
+        AttributeValue::Number(n) => n.to_string(),
+        AttributeValue::Boolean(b) => b.to_string(),
+        AttributeValue::List(items) => {
+            format!("[{}]", items.iter().map(render_value).collect::<Vec<_>>().join(", "))
+        }
+        AttributeValue::Map(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let body: Vec<String> = entries.iter().map(|(k, v)| format!("{k}: {}", render_value(v))).collect();
+            format!("{{ {} }}", body.join(" "))
+        }
+    }
+}
+
+/// Render the merged requirements as the sync-managed `.arc` file.
+pub fn render_pull(connector: &str, plan: &PullPlan) -> String {
+    let mut out = format!("{PULL_HEADER} from '{connector}' — edits are merged, remote wins\n\n");
+    out.push_str("requirements {\n");
+    let known = ["title", "description", "priority", "status", "category", "safety_level"];
+    for req in &plan.requirements {
+        out.push_str(&format!("  req {} {{\n", quote(&req.id)));
+        let mut others: Vec<&String> = req.attributes.keys().filter(|k| !known.contains(&k.as_str())).collect();
+        others.sort();
+        for key in known.iter().copied().chain(others.into_iter().map(String::as_str)) {
+            if let Some(value) = req.attributes.get(key) {
+                match key.strip_prefix(CUSTOM_ATTRIBUTE_PREFIX) {
+                    Some(custom) => out.push_str(&format!("    attr {custom}: {}\n", render_value(value))),
+                    None => out.push_str(&format!("    {key}: {}\n", render_value(value))),
+                }
+            }
+        }
+        out.push_str("  }\n");
+    }
+    out.push_str("}\n");
+    if !plan.traces.is_empty() {
+        out.push('\n');
+    }
+    for (from, kind, to) in &plan.traces {
+        out.push_str(&format!("trace {} -> {} {{ trace_type: {} }}\n", quote(from), quote(to), quote(kind)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+    use chrono::Utc;
+
+    fn remote(id: &str, title: &str, text: &str) -> rm::Requirement {
+        rm::Requirement {
+            id: id.to_string(),
+            external_id: None,
+            title: title.to_string(),
+            text: text.to_string(),
+            requirement_type: rm::RequirementType::Functional,
+            status: rm::RequirementStatus::Approved,
+            priority: rm::RequirementPriority::High,
+            rationale: None,
+            acceptance_criteria: None,
+            verification_method: None,
+            verification_status: None,
+            compliance: Vec::new(),
+            custom_attributes: HashMap::new(),
+            parent_id: None,
+            children_ids: Vec::new(),
+            created_at: Utc::now(),
+            modified_at: Utc::now(),
+            created_by: "tester".to_string(),
+            modified_by: "tester".to_string(),
+        }
+    }
+
+    fn baseline(reqs: Vec<rm::Requirement>, links: Vec<(&str, &str)>) -> RMBaseline {
+        RMBaseline {
+            timestamp: Utc::now(),
+            system: "Jira".to_string(),
+            project: "BRK".to_string(),
+            modules: Vec::new(),
+            requirements: reqs.into_iter().map(|r| (r.id.clone(), r)).collect(),
+            trace_links: links
+                .into_iter()
+                .map(|(from, to)| rm::TraceLink {
+                    id: format!("{from}-{to}"),
+                    source_id: from.to_string(),
+                    target_id: to.to_string(),
+                    link_type: rm::TraceLinkType::DerivedFrom,
+                    rationale: None,
+                    created_at: Utc::now(),
+                    created_by: "tester".to_string(),
+                })
+                .collect(),
+            metadata: rm::RMMetadata {
+                system_version: "1".to_string(),
+                baseline_name: "test".to_string(),
+                created_by: "tester".to_string(),
+                description: None,
+            },
+        }
+    }
+
+    #[test]
+    fn pull_classifies_new_changed_unchanged_and_orphaned() {
+        let first = plan_pull(
+            &[],
+            &[],
+            &baseline(
+                vec![remote("BRK-1", "Brake", "Shall brake"), remote("BRK-2", "Hold", "Shall hold")],
+                vec![("BRK-2", "BRK-1")],
+            ),
+        );
+        assert_eq!(first.added, vec!["BRK-1", "BRK-2"]);
+        let source = render_pull("jira", &first);
+        assert!(source.starts_with(PULL_HEADER));
+
+        let compiled = Compiler::new(CompilerConfig::default())
+            .compile_string(&source)
+            .expect("pulled file compiles");
+        assert!(compiled
+            .semantic_model
+            .traces
+            .iter()
+            .any(|t| t.from == "BRK-2" && t.to == "BRK-1" && t.trace_type == "derived_from"));
+
+        let mut local: Vec<Requirement> = compiled
+            .ast
+            .system_analysis
+            .iter()
+            .flat_map(|sa| sa.requirements.clone())
+            .collect();
+        local.push(Requirement {
+            id: "LOCAL-1".to_string(),
+            attributes: HashMap::from([("description".to_string(), text("Local only"))]),
+        });
+        let second = plan_pull(
+            &local,
+            &[],
+            &baseline(
+                vec![remote("BRK-1", "Brake", "Shall brake harder"), remote("BRK-2", "Hold", "Shall hold")],
+                vec![],
+            ),
+        );
+        assert!(second.added.is_empty());
+        assert_eq!(second.changed, vec![("BRK-1".to_string(), vec!["description".to_string()])]);
+        assert_eq!(second.unchanged, 1);
+        assert_eq!(second.orphaned, vec!["LOCAL-1"]);
+        assert_eq!(second.requirements.len(), 3);
+    }
+
+    #[test]
+    fn push_dry_run_lists_field_level_calls() {
+        use crate::integrations::requirements_management::delta_computer::RMDeltaComputer;
+
+        let remote_baseline = || {
+            baseline(
+                vec![remote("BRK-1", "Brake", "Shall brake"), remote("BRK-2", "Hold", "Shall hold")],
+                vec![("BRK-2", "BRK-1")],
+            )
+        };
+        let pulled = render_pull("jira", &plan_pull(&[], &[], &remote_baseline()));
+        let source = format!(
+            "{}\nrequirements {{\n    requirement \"BRK-3\" {{\n        title: \"Release\"\n        description: \"Shall release\"\n    }}\n}}\n\ntrace \"BRK-3\" -> \"BRK-1\" {{\n    trace_type: \"refines\"\n}}\n",
+            pulled.replace("Shall brake", "Shall brake harder")
+        );
+        let compiled = Compiler::new(CompilerConfig::default())
+            .compile_string(&source)
+            .expect("local model compiles");
+
+        let delta = RMDeltaComputer::new(compiled.semantic_model, Some(remote_baseline()))
+            .compute_delta()
+            .unwrap();
+        assert!(delta.deleted_trace_links.is_empty());
+        let calls = describe_rm_delta(&delta);
+        assert_eq!(
+            calls,
+            vec![
+                "create_requirement BRK-3 \"Release\": \"Shall release\"".to_string(),
+                "update_requirement BRK-1".to_string(),
+                "    text: \"Shall brake\" → \"Shall brake harder\"".to_string(),
+                "create_trace_link BRK-3 -[Refines]-> BRK-1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_attributes_are_pushed_and_pulled_back() {
+        use crate::compiler::semantic::CustomValue;
+        use crate::integrations::requirements_management::delta_computer::RMDeltaComputer;
+
+        let source = "requirements {\n  req \"BRK-1\" {\n    description: \"Shall brake\"\n    safety_level: \"ASIL_B\"\n    attr safety_classification: \"SC-2\"\n    attr reviewed: true\n    attr review_date: \"2026-05-01\"\n  }\n}\n";
+        let model = Compiler::new(CompilerConfig::default()).compile_string(source).unwrap().semantic_model;
+        let expected = model.all_elements["BRK-1"].custom_attributes.clone();
+
+        let delta = RMDeltaComputer::new(model, None).compute_delta().unwrap();
+        let pushed = delta.added_requirements[0].clone();
+        assert!(matches!(&pushed.custom_attributes["safety_classification"], rm::AttributeValue::String(s) if s == "SC-2"));
+        assert!(matches!(pushed.custom_attributes["reviewed"], rm::AttributeValue::Boolean(true)));
+        assert!(matches!(pushed.custom_attributes["review_date"], rm::AttributeValue::Date(_)));
+
+        let pulled = render_pull("jira", &plan_pull(&[], &[], &baseline(vec![pushed], vec![])));
+        assert!(pulled.contains("    attr safety_classification: \"SC-2\"\n"), "{pulled}");
+        assert!(pulled.contains("    safety_level: \"ASIL_B\"\n"), "{pulled}");
+        let round_trip = Compiler::new(CompilerConfig::default()).compile_string(&pulled).unwrap().semantic_model;
+        assert_eq!(round_trip.all_elements["BRK-1"].custom_attributes, expected);
+        assert_eq!(expected["reviewed"], CustomValue::Boolean(true));
+    }
+
+    #[test]
+    fn connection_options_come_from_the_connector_options() {
+        let mut options = BTreeMap::from([
+            ("timeout_secs".to_string(), "120".to_string()),
+            ("proxy".to_string(), "http://proxy.corp:3128".to_string()),
+            ("ca_cert".to_string(), "/etc/pki/corp-root.pem".to_string()),
+        ]);
+        fn project(options: &BTreeMap<String, String>) -> RemoteProject<'_> {
+            RemoteProject {
+                kind: "teamcenter",
+                url: "https://tc.example",
+                project: "BRK",
+                options,
+                auth: RMAuthentication::APIToken { token: "t".to_string() },
+            }
+        }
+
+        assert_eq!(
+            project(&options).connection().unwrap(),
+            ConnectionOptions {
+                timeout_secs: Some(120),
+                proxy: Some("http://proxy.corp:3128".to_string()),
+                ca_cert: Some(PathBuf::from("/etc/pki/corp-root.pem")),
+            }
+        );
+        assert_eq!(project(&BTreeMap::new()).connection().unwrap(), ConnectionOptions::default());
+        options.insert("timeout_secs".to_string(), "soon".to_string());
+        assert!(project(&options).plm_connector().is_err());
+    }
+
+    #[tokio::test]
+    async fn plm_dry_run_plan_is_what_push_executes() {
+        use crate::integrations::plm_integration::{delta_computer::DeltaComputer, LifecycleState, PLMBaseline, BaselineMetadata};
+        use crate::integrations::transport::mock::MockTransport;
+        use std::sync::Arc;
+
+        let source = "physical_architecture \"PA\" {\n  node \"ECU\" { part_number: \"PN-1\" }\n  node \"Sensor\" { part_number: \"PN-2\" manufacturer: \"Bosch\" }\n}\n";
+        let ast = Compiler::new(CompilerConfig::default()).compile_string(source).unwrap().ast;
+        let initial = DeltaComputer::new(ast.clone(), None).compute_delta().unwrap();
+        let mut released = initial.added_parts.into_iter().find(|p| p.part_number == "PN-2").unwrap();
+        released.id = "wc-2".to_string();
+        released.manufacturer = Some("Conti".to_string());
+        released.lifecycle_state = LifecycleState::Released;
+        let remote = PLMBaseline {
+            timestamp: Utc::now(),
+            model_hash: String::new(),
+            parts: HashMap::from([("PN-2".to_string(), released)]),
+            boms: HashMap::new(),
+            metadata: BaselineMetadata {
+                source_system: "Windchill".to_string(),
+                version: "12.0".to_string(),
+                created_by: "tester".to_string(),
+                project: "TEST".to_string(),
+            },
+        };
+        let delta = DeltaComputer::new(ast, Some(remote)).compute_delta().unwrap();
+
+        let api = "https://wc.example/Windchill";
+        let transport = Arc::new(
+            MockTransport::new()
+                .on(reqwest::Method::POST, &format!("{api}/ProdMgmt/parts"), 201, r#"{"id": "wc-1"}"#)
+                .on(reqwest::Method::PUT, &format!("{api}/ProdMgmt/parts/wc-2"), 200, "{}")
+                .on(reqwest::Method::POST, &format!("{api}/ChangeMgmt/changes"), 201, r#"{"id": "c-1", "number": "ECO-7"}"#),
+        );
+        let connector = WindchillConnector::with_transport(
+            WindchillConfig {
+                base_url: "https://wc.example".to_string(),
+                context: "/Windchill".to_string(),
+                product: "TEST".to_string(),
+                organization: "Test Org".to_string(),
+                library: "Engineering".to_string(),
+                auth: AuthenticationMethod::BasicAuth { username: "u".to_string(), password: "p".to_string() },
+                connection: ConnectionOptions::default(),
+            },
+            transport.clone(),
+        );
+
+        let plan = connector.plan_changes(&delta);
+        let subjects: Vec<_> = plan.iter().map(|op| op.subject()).collect();
+        assert_eq!(subjects, vec!["PN-1", "PN-2", "ECO"]);
+        let calls = describe_plm_plan(&plan, &delta);
+        assert_eq!(calls.first().map(String::as_str), Some("create_part PN-1 rev A \"ECU\""));
+        assert_eq!(calls.last().map(String::as_str), Some("create_eco for PN-1, PN-2 (released: PN-2)"));
+        assert!(transport.requests().is_empty(), "planning must not call the server");
+
+        let result = connector.push_changes(&delta).await.unwrap();
+        let sent: Vec<_> = transport.requests().iter().map(|r| (r.method.to_string(), r.url.clone())).collect();
+        assert_eq!(
+            sent,
+            vec![
+                ("POST".to_string(), format!("{api}/ProdMgmt/parts")),
+                ("PUT".to_string(), format!("{api}/ProdMgmt/parts/wc-2")),
+                ("POST".to_string(), format!("{api}/ChangeMgmt/changes")),
+            ]
+        );
+        assert!(result.success);
+        assert_eq!((result.parts_created, result.parts_updated), (vec!["wc-1".to_string()], vec!["PN-2".to_string()]));
+        assert_eq!(result.eco_id.as_deref(), Some("ECO-7"));
+    }
+}
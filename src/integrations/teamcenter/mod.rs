@@ -1,14 +1,21 @@
 use async_trait::async_trait;
-use reqwest::{Client, header};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use super::plm_integration::*;
+use super::transport::{ConnectionOptions, HttpRequest, HttpTransport, ReqwestTransport};
+
+/// Exception Teamcenter raises when a request carries an expired or
+/// otherwise unknown session.
+const SESSION_EXPIRED_FAULT: &str = "InvalidUserException";
 
 pub struct TeamcenterConnector {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: TeamcenterConfig,
-    session_id: Option<String>,
+    /// Replaced from `soa_request` (behind `&self`) when the session expires.
+    session_id: RwLock<Option<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +26,16 @@ pub struct TeamcenterConfig {
     pub auth: AuthenticationMethod,
     pub item_type: String,
     pub revision_rule: String,
+    /// Item property holding the release status; sites commonly rename it.
+    #[serde(default = "default_lifecycle_property")]
+    pub lifecycle_property: String,
+    /// Timeout, proxy and extra CA certificate for the HTTP client.
+    #[serde(default)]
+    pub connection: ConnectionOptions,
+}
+
+fn default_lifecycle_property() -> String {
+    "release_status_list".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,13 +45,6 @@ pub enum TeamcenterProtocol {
     REST,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct TCSession {
-    session_id: String,
-    user_id: String,
-    discriminator: String,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct TCItem {
     uid: String,
@@ -120,8 +130,6 @@ struct CreateItemsResponse {
 #[derive(Debug, Deserialize)]
 struct CreateItemOutput {
     uid: String,
-    #[serde(rename = "itemId")]
-    item_id: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -135,20 +143,6 @@ struct UpdateObjectInput {
     properties: HashMap<String, String>,
 }
 
-#[derive(Debug, Serialize)]
-struct CreateBOMRequest {
-    parent_uid: String,
-    children: Vec<BOMChildInput>,
-}
-
-#[derive(Debug, Serialize)]
-struct BOMChildInput {
-    item_uid: String,
-    quantity: f64,
-    #[serde(rename = "findNumber")]
-    find_number: Option<String>,
-}
-
 #[derive(Debug, Serialize)]
 struct CreateChangeRequest {
     #[serde(rename = "changeType")]
@@ -161,30 +155,20 @@ struct CreateChangeRequest {
 
 impl TeamcenterConnector {
     pub fn new(config: TeamcenterConfig) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json"),
-        );
-        
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .expect("Failed to create HTTP client");
-        
+        let transport = ReqwestTransport::with_options(Duration::from_secs(60), &config.connection);
+        Self::with_transport(config, Arc::new(transport))
+    }
+    
+    /// Connector that sends its requests through `transport`.
+    pub fn with_transport(config: TeamcenterConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self {
-            client,
+            transport,
             config,
-            session_id: None,
+            session_id: RwLock::new(None),
         }
     }
     
-    async fn authenticate(&mut self) -> Result<(), PLMError> {
+    async fn authenticate(&self) -> Result<(), PLMError> {
         let login_url = format!("{}/tc/JsonRestServices/Core-2011-06-Session/login", self.config.base_url);
         
         let (username, password) = match &self.config.auth {
@@ -201,10 +185,10 @@ impl TeamcenterConnector {
             }
         });
         
-        let response = self.client
-            .post(&login_url)
-            .json(&login_request)
-            .send()
+        let req = HttpRequest::post(&login_url)
+            .json(&login_request);
+        let response = self.transport
+            .send(req)
             .await
             .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
         
@@ -223,7 +207,7 @@ impl TeamcenterConnector {
         let login_response: LoginResponse = response.json().await
             .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
         
-        self.session_id = Some(login_response.session_id);
+        *self.session_id.write().unwrap() = Some(login_response.session_id);
         
         Ok(())
     }
@@ -245,36 +229,59 @@ impl TeamcenterConnector {
         R: for<'de> Deserialize<'de>,
     {
         let url = self.build_url(service, operation);
-        
-        let session_id = self.session_id.as_ref()
-            .ok_or_else(|| PLMError::AuthenticationError("Not authenticated".to_string()))?;
-        
-        let request = TCSOARequest {
+        let mut request = TCSOARequest {
             header: TCSOAHeader {
-                session_id: session_id.clone(),
+                session_id: String::new(),
                 version: "2011-06".to_string(),
             },
             body,
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| PLMError::NetworkError(e.to_string()))?;
-        
-        if !response.status().is_success() {
-            return Err(PLMError::APIError(
-                format!("SOA request failed: {}", response.status())
-            ));
+        let mut reauthenticated = false;
+        loop {
+            request.header.session_id = self.session_id.read().unwrap()
+                .clone()
+                .ok_or_else(|| PLMError::AuthenticationError("Not authenticated".to_string()))?;
+            
+            let req = HttpRequest::post(&url)
+                .json(&request);
+            let response = self.transport
+                .send(req)
+                .await
+                .map_err(|e| PLMError::NetworkError(e.to_string()))?;
+            
+            let status = response.status();
+            let text = response.text().await
+                .map_err(|e| PLMError::NetworkError(e.to_string()))?;
+            
+            if is_session_expired(status, &text) {
+                if reauthenticated {
+                    return Err(PLMError::AuthenticationError(
+                        "Teamcenter session expired again after re-authentication".to_string()
+                    ));
+                }
+                self.authenticate().await?;
+                reauthenticated = true;
+                continue;
+            }
+            
+            if !status.is_success() {
+                return Err(PLMError::APIError(
+                    format!("SOA request failed: {}", status)
+                ));
+            }
+            
+            return serde_json::from_str(&text)
+                .map_err(|e| PLMError::SerializationError(e.to_string()));
         }
-        
-        response.json().await
-            .map_err(|e| PLMError::SerializationError(e.to_string()))
     }
     
     fn convert_to_plm_part(&self, tc_item: TCItem, revision: TCItemRevision) -> PLMPart {
+        let lifecycle_state = tc_item.properties
+            .get(&self.config.lifecycle_property)
+            .map(|prop| self.map_lifecycle_state(&prop.display_value))
+            .unwrap_or(LifecycleState::InWork);
+        
         let mut custom_attrs = HashMap::new();
         
         for (key, prop) in tc_item.properties {
@@ -288,7 +295,7 @@ impl TeamcenterConnector {
             name: tc_item.object_name,
             description: tc_item.object_desc,
             part_type: tc_item.item_type,
-            lifecycle_state: LifecycleState::InWork,
+            lifecycle_state,
             manufacturer: None,
             supplier: None,
             unit_cost: None,
@@ -299,11 +306,25 @@ impl TeamcenterConnector {
             custom_attributes: custom_attrs,
             created_at: chrono::Utc::now(),
             modified_at: chrono::Utc::now(),
-            created_by: tc_item.owning_user,
+            created_by: tc_item.owning_user.clone(),
             modified_by: tc_item.owning_user,
         }
     }
     
+    /// A release status list displays as `"TCM Released, Approved"`, most
+    /// recent last; an empty list means the item is still in work.
+    fn map_lifecycle_state(&self, tc_status: &str) -> LifecycleState {
+        let latest = tc_status.rsplit(',').next().unwrap_or_default().trim().to_lowercase();
+        match latest.as_str() {
+            "" | "working" | "in work" => LifecycleState::InWork,
+            s if s.contains("review") || s.contains("pending") => LifecycleState::UnderReview,
+            s if s.contains("released") || s.contains("approved") || s.contains("production") => LifecycleState::Released,
+            s if s.contains("obsolete") || s.contains("superseded") => LifecycleState::Obsolete,
+            s if s.contains("frozen") || s.contains("baselined") => LifecycleState::Frozen,
+            _ => LifecycleState::InWork,
+        }
+    }
+    
     fn convert_tc_property(&self, prop: TCProperty) -> AttributeValue {
         match prop.db_value {
             serde_json::Value::String(s) => AttributeValue::String(s),
@@ -400,6 +421,18 @@ impl TeamcenterConnector {
     }
 }
 
+/// Teamcenter reports an expired session as an `InvalidUserException` service
+/// fault, either with HTTP 401 or inside an otherwise successful response.
+fn is_session_expired(status: reqwest::StatusCode, body: &str) -> bool {
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return true;
+    }
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|fault| fault.get(".QName")?.as_str().map(|qname| qname.ends_with(SESSION_EXPIRED_FAULT)))
+        .unwrap_or(false)
+}
+
 #[async_trait]
 impl PLMConnector for TeamcenterConnector {
     fn name(&self) -> &str {
@@ -411,9 +444,9 @@ impl PLMConnector for TeamcenterConnector {
         
         let ping_url = format!("{}/tc/JsonRestServices/Core-2011-06-Session/ping", self.config.base_url);
         
-        let response = self.client
-            .get(&ping_url)
-            .send()
+        let req = HttpRequest::get(&ping_url);
+        let response = self.transport
+            .send(req)
             .await
             .map_err(|e| PLMError::ConnectionError(e.to_string()))?;
         
@@ -427,15 +460,15 @@ impl PLMConnector for TeamcenterConnector {
     }
     
     async fn disconnect(&mut self) -> Result<(), PLMError> {
-        if self.session_id.is_some() {
+        if self.session_id.read().unwrap().is_some() {
             let logout_url = format!("{}/tc/JsonRestServices/Core-2011-06-Session/logout", self.config.base_url);
             
-            let _ = self.client
-                .post(&logout_url)
-                .send()
+            let req = HttpRequest::post(&logout_url);
+            let _ = self.transport
+                .send(req)
                 .await;
             
-            self.session_id = None;
+            *self.session_id.write().unwrap() = None;
         }
         
         Ok(())
@@ -469,7 +502,7 @@ impl PLMConnector for TeamcenterConnector {
         let mut parts = HashMap::new();
         
         for (item, revision) in response.items.into_iter()
-            .zip(response.revisions.into_iter()) {
+            .zip(response.revisions) {
             let part = self.convert_to_plm_part(item, revision);
             parts.insert(part.part_number.clone(), part);
         }
@@ -500,6 +533,7 @@ impl PLMConnector for TeamcenterConnector {
         let items = bom_lines.into_iter()
             .enumerate()
             .map(|(idx, line)| BOMItem {
+                level: 1,
                 item_number: (idx + 1) as u32,
                 part_number: line.item_id,
                 quantity: line.quantity,
@@ -507,6 +541,7 @@ impl PLMConnector for TeamcenterConnector {
                 reference_designator: line.reference_designator,
                 find_number: line.find_number,
                 notes: line.occurrence_note,
+                effectivity: None,
             })
             .collect();
         
@@ -518,66 +553,6 @@ impl PLMConnector for TeamcenterConnector {
         })
     }
     
-    async fn push_changes(&self, delta: &PLMDelta) -> Result<PLMSyncResult, PLMError> {
-        let mut result = PLMSyncResult {
-            success: true,
-            parts_created: Vec::new(),
-            parts_updated: Vec::new(),
-            parts_failed: Vec::new(),
-            eco_id: None,
-            sync_timestamp: chrono::Utc::now(),
-        };
-        
-        for part in &delta.added_parts {
-            match self.create_part(part).await {
-                Ok(id) => result.parts_created.push(id),
-                Err(e) => {
-                    result.parts_failed.push((part.part_number.clone(), e.to_string()));
-                    result.success = false;
-                }
-            }
-        }
-        
-        for part_diff in &delta.modified_parts {
-            let changes = PartChanges {
-                description: None,
-                lifecycle_state: None,
-                supplier: None,
-                unit_cost: None,
-                custom_attributes: HashMap::new(),
-            };
-            
-            match self.update_part(&part_diff.part_id, &changes).await {
-                Ok(_) => result.parts_updated.push(part_diff.part_number.clone()),
-                Err(e) => {
-                    result.parts_failed.push((part_diff.part_number.clone(), e.to_string()));
-                    result.success = false;
-                }
-            }
-        }
-        
-        if delta.eco_required {
-            let change_request = ChangeRequest {
-                title: delta.change_summary.clone(),
-                description: "Automated sync from ArcLang".to_string(),
-                reason: "Model synchronization".to_string(),
-                affected_items: delta.affected_part_numbers(),
-                requester: "arclang".to_string(),
-                priority: Priority::Medium,
-                change_type: ECOChangeType::Engineering,
-            };
-            
-            match self.create_eco(&change_request).await {
-                Ok(eco_id) => result.eco_id = Some(eco_id),
-                Err(e) => {
-                    result.parts_failed.push(("ECO".to_string(), e.to_string()));
-                }
-            }
-        }
-        
-        Ok(result)
-    }
-    
     async fn create_part(&self, part: &PLMPart) -> Result<String, PLMError> {
         let mut properties = HashMap::new();
         properties.insert("object_name".to_string(), part.name.clone());
@@ -646,7 +621,6 @@ impl PLMConnector for TeamcenterConnector {
         
         #[derive(Deserialize)]
         struct CreateChangeResponse {
-            change_uid: String,
             change_number: String,
         }
         
@@ -688,7 +662,7 @@ impl PLMConnector for TeamcenterConnector {
         ).await?;
         
         Ok(response.items.into_iter()
-            .zip(response.revisions.into_iter())
+            .zip(response.revisions)
             .map(|(item, revision)| self.convert_to_plm_part(item, revision))
             .collect())
     }
@@ -755,9 +729,184 @@ mod tests {
             },
             item_type: "Item".to_string(),
             revision_rule: "Latest Working".to_string(),
+            lifecycle_property: default_lifecycle_property(),
+            connection: ConnectionOptions::default(),
         };
         
         let connector = TeamcenterConnector::new(config);
         assert_eq!(connector.name(), "Teamcenter");
     }
+    
+    #[tokio::test]
+    async fn soa_request_reauthenticates_once_on_expired_session() {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let base = "https://tc.example/tc/JsonRestServices";
+        let expired = r#"{
+            ".QName": "http://teamcenter.com/Schemas/Soa/2006-03/Exceptions.InvalidUserException",
+            "code": 515022,
+            "level": 3,
+            "message": "The login session has expired."
+        }"#;
+        let item = r#"{
+            "items": [{"uid": "QxB1", "itemId": "P-100", "itemType": "Item", "objectName": "Actuator",
+                       "objectDesc": null, "owningUser": "infodba", "properties": {}}],
+            "revisions": [{"uid": "QxB1r", "itemRevisionId": "B", "revisionRule": "Latest Working"}]
+        }"#;
+        let transport = Arc::new(
+            MockTransport::new()
+                .on(reqwest::Method::POST, &format!("{base}/Core-2011-06-Session/login"), 200, r#"{"sessionId": "s1"}"#)
+                .on(reqwest::Method::POST, &format!("{base}/Core-2011-06-Session/login"), 200, r#"{"sessionId": "s2"}"#)
+                .on(reqwest::Method::POST, &format!("{base}/Core-2008-06-DataManagement/loadObjects"), 200, expired)
+                .on(reqwest::Method::POST, &format!("{base}/Core-2008-06-DataManagement/loadObjects"), 200, item),
+        );
+        let connector = TeamcenterConnector::with_transport(
+            TeamcenterConfig {
+                base_url: "https://tc.example".to_string(),
+                protocol: TeamcenterProtocol::SOAHTTP,
+                pool_manager: String::new(),
+                auth: AuthenticationMethod::BasicAuth {
+                    username: "infodba".to_string(),
+                    password: "infodba".to_string(),
+                },
+                item_type: "Item".to_string(),
+                revision_rule: "Latest Working".to_string(),
+                lifecycle_property: default_lifecycle_property(),
+                connection: ConnectionOptions::default(),
+            },
+            transport.clone(),
+        );
+        connector.authenticate().await.unwrap();
+        
+        let part = connector.fetch_part("P-100").await.unwrap();
+        assert_eq!(part.part_number, "P-100");
+        assert_eq!(part.revision, "B");
+        
+        let sessions: Vec<_> = transport.requests().iter()
+            .filter(|r| r.url.ends_with("/loadObjects"))
+            .map(|r| match &r.body {
+                Some(crate::integrations::transport::HttpBody::Json(body)) => body["header"]["session_id"].clone(),
+                _ => serde_json::Value::Null,
+            })
+            .collect();
+        assert_eq!(sessions, vec![serde_json::json!("s1"), serde_json::json!("s2")]);
+    }
+    
+    #[tokio::test]
+    async fn lifecycle_state_read_from_configured_property() {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let base = "https://tc.example/tc/JsonRestServices";
+        let item = |status: &str| serde_json::json!({
+            "items": [{"uid": "QxB1", "itemId": "P-100", "itemType": "Item", "objectName": "Actuator",
+                       "objectDesc": null, "owningUser": "infodba", "properties": {
+                           "site_status": {"displayValue": status, "dbValue": ["AbC1"]}
+                       }}],
+            "revisions": [{"uid": "QxB1r", "itemRevisionId": "B", "revisionRule": "Latest Working"}]
+        }).to_string();
+        let transport = Arc::new(
+            MockTransport::new()
+                .on(reqwest::Method::POST, &format!("{base}/Core-2011-06-Session/login"), 200, r#"{"sessionId": "s1"}"#)
+                .on(reqwest::Method::POST, &format!("{base}/Core-2008-06-DataManagement/loadObjects"), 200, item("TCM Released"))
+                .on(reqwest::Method::POST, &format!("{base}/Core-2008-06-DataManagement/loadObjects"), 200, item("Approved, Obsolete"))
+                .on(reqwest::Method::POST, &format!("{base}/Core-2008-06-DataManagement/loadObjects"), 200, item("")),
+        );
+        let connector = TeamcenterConnector::with_transport(
+            TeamcenterConfig {
+                base_url: "https://tc.example".to_string(),
+                protocol: TeamcenterProtocol::SOAHTTP,
+                pool_manager: String::new(),
+                auth: AuthenticationMethod::BasicAuth {
+                    username: "infodba".to_string(),
+                    password: "infodba".to_string(),
+                },
+                item_type: "Item".to_string(),
+                revision_rule: "Latest Working".to_string(),
+                lifecycle_property: "site_status".to_string(),
+                connection: ConnectionOptions::default(),
+            },
+            transport,
+        );
+        connector.authenticate().await.unwrap();
+        
+        assert_eq!(connector.get_lifecycle_state("P-100").await.unwrap(), LifecycleState::Released);
+        assert_eq!(connector.get_lifecycle_state("P-100").await.unwrap(), LifecycleState::Obsolete);
+        assert_eq!(connector.get_lifecycle_state("P-100").await.unwrap(), LifecycleState::InWork);
+    }
+    
+    fn bom_connector(expansions: &[&[&str]]) -> TeamcenterConnector {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let base = "https://tc.example/tc/JsonRestServices";
+        let item = r#"{
+            "items": [{"uid": "QxB1", "itemId": "P", "itemType": "Item", "objectName": "Part",
+                       "objectDesc": null, "owningUser": "infodba", "properties": {}}],
+            "revisions": []
+        }"#;
+        let mut transport = MockTransport::new()
+            .on(reqwest::Method::POST, &format!("{base}/Core-2011-06-Session/login"), 200, r#"{"sessionId": "s1"}"#)
+            .on(reqwest::Method::POST, &format!("{base}/Core-2008-06-DataManagement/loadObjects"), 200, item);
+        for children in expansions {
+            let lines: Vec<_> = children.iter()
+                .map(|id| serde_json::json!({"uid": format!("L-{id}"), "itemId": id, "quantity": 2.0}))
+                .collect();
+            transport = transport.on(
+                reqwest::Method::POST,
+                &format!("{base}/Cad-2007-01-StructureManagement/expandPSOneLevel"),
+                200,
+                serde_json::json!({ "bomLines": lines }).to_string(),
+            );
+        }
+        
+        TeamcenterConnector::with_transport(
+            TeamcenterConfig {
+                base_url: "https://tc.example".to_string(),
+                protocol: TeamcenterProtocol::SOAHTTP,
+                pool_manager: String::new(),
+                auth: AuthenticationMethod::BasicAuth {
+                    username: "infodba".to_string(),
+                    password: "infodba".to_string(),
+                },
+                item_type: "Item".to_string(),
+                revision_rule: "Latest Working".to_string(),
+                lifecycle_property: default_lifecycle_property(),
+                connection: ConnectionOptions::default(),
+            },
+            Arc::new(transport),
+        )
+    }
+    
+    #[tokio::test]
+    async fn fetch_bom_recursive_flattens_levels_depth_first() {
+        // ASSY → [SUB, BOLT], SUB → [PIN], PIN → [], BOLT → []
+        let connector = bom_connector(&[&["SUB", "BOLT"], &["PIN"], &[], &[]]);
+        connector.authenticate().await.unwrap();
+        
+        let bom = connector.fetch_bom_recursive("ASSY", 5).await.unwrap();
+        let rows: Vec<_> = bom.items.iter().map(|i| (i.level, i.part_number.as_str())).collect();
+        assert_eq!(rows, vec![(1, "SUB"), (2, "PIN"), (1, "BOLT")]);
+        assert_eq!(bom.parent_part, "ASSY");
+    }
+    
+    #[tokio::test]
+    async fn fetch_bom_recursive_stops_at_max_depth() {
+        let connector = bom_connector(&[&["SUB"], &["PIN"], &["NEVER"]]);
+        connector.authenticate().await.unwrap();
+        
+        let bom = connector.fetch_bom_recursive("ASSY", 2).await.unwrap();
+        let rows: Vec<_> = bom.items.iter().map(|i| (i.level, i.part_number.as_str())).collect();
+        assert_eq!(rows, vec![(1, "SUB"), (2, "PIN")]);
+    }
+    
+    #[tokio::test]
+    async fn fetch_bom_recursive_rejects_cycles() {
+        // ASSY → [SUB], SUB → [ASSY]
+        let connector = bom_connector(&[&["SUB"], &["ASSY"]]);
+        connector.authenticate().await.unwrap();
+        
+        match connector.fetch_bom_recursive("ASSY", 10).await {
+            Err(PLMError::BOMCycle(path)) => assert_eq!(path, "ASSY → SUB → ASSY"),
+            other => panic!("expected a BOM cycle, got {:?}", other.map(|b| b.items.len())),
+        }
+    }
 }
@@ -1,14 +1,47 @@
 use async_trait::async_trait;
-use reqwest::{Client, header};
+use reqwest::header;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use super::plm_integration::*;
+use super::transport::{ConnectionOptions, HttpRequest, HttpTransport, ReqwestTransport, REDACTED};
 
 pub struct ThreeDExperienceConnector {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: ThreeDExperienceConfig,
-    access_token: Option<String>,
+    /// Renewed from the `*_with_auth` helpers (behind `&self`) when it
+    /// nears expiry.
+    token: RwLock<Option<AccessToken>>,
+}
+
+/// Renew OAuth2 tokens this long before they expire.
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 60;
+
+#[derive(Clone)]
+struct AccessToken {
+    value: String,
+    /// `None` for 3DPassport sessions and grants without `expires_in`.
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    refresh_token: Option<String>,
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("value", &REDACTED)
+            .field("expires_at", &self.expires_at)
+            .field("refresh_token", &self.refresh_token.as_ref().map(|_| REDACTED))
+            .finish()
+    }
+}
+
+impl AccessToken {
+    fn needs_refresh(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|at| at - chrono::Duration::seconds(TOKEN_REFRESH_MARGIN_SECS) <= now)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +51,9 @@ pub struct ThreeDExperienceConfig {
     pub auth: AuthenticationMethod,
     pub collaborative_space: String,
     pub security_context: SecurityContext,
+    /// Timeout, proxy and extra CA certificate for the HTTP client.
+    #[serde(default)]
+    pub connection: ConnectionOptions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,26 +79,6 @@ struct ThreeDXProduct {
     attributes: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ThreeDXRequirement {
-    id: String,
-    title: String,
-    #[serde(rename = "Chapter")]
-    chapter: String,
-    #[serde(rename = "Criticality")]
-    criticality: String,
-    content: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ThreeDXLogicalComponent {
-    id: String,
-    #[serde(rename = "type")]
-    component_type: String,
-    name: String,
-    representation: String,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct ThreeDXStructure {
     parent: String,
@@ -104,50 +120,41 @@ struct ThreeDXChangeAction {
 
 #[derive(Debug, Deserialize)]
 struct ThreeDXSearchResult {
-    #[serde(rename = "totalItems")]
-    total_items: usize,
     items: Vec<ThreeDXProduct>,
 }
 
 #[derive(Debug, Deserialize)]
 struct ThreeDXCreateResponse {
     id: String,
-    #[serde(rename = "physicalid")]
-    physical_id: String,
 }
 
 impl ThreeDExperienceConnector {
     pub fn new(config: ThreeDExperienceConfig) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_static("application/json"),
-        );
-        
-        let client = Client::builder()
-            .default_headers(headers)
-            .cookie_store(true)
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .expect("Failed to create HTTP client");
-        
+        let transport = ReqwestTransport::with_options(Duration::from_secs(60), &config.connection);
+        Self::with_transport(config, Arc::new(transport))
+    }
+    
+    /// Connector that sends its requests through `transport`.
+    pub fn with_transport(config: ThreeDExperienceConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self {
-            client,
+            transport,
             config,
-            access_token: None,
+            token: RwLock::new(None),
         }
     }
     
-    async fn authenticate(&mut self) -> Result<(), PLMError> {
+    async fn authenticate(&self) -> Result<(), PLMError> {
         let auth_url = format!("{}/3DPassport/login", self.config.platform_url);
         
-        let credentials = match &self.config.auth {
+        let auth = self.config.auth.clone();
+        let credentials = match &auth {
             AuthenticationMethod::OAuth2 { client_id, client_secret, token_url } => {
-                return self.authenticate_oauth2(token_url, client_id, client_secret).await;
+                return self.request_token(token_url, &[
+                    ("grant_type", "client_credentials"),
+                    ("client_id", client_id),
+                    ("client_secret", client_secret),
+                    ("scope", "3DSpace"),
+                ]).await;
             }
             AuthenticationMethod::BasicAuth { username, password } => {
                 serde_json::json!({
@@ -163,10 +170,10 @@ impl ThreeDExperienceConnector {
             }
         };
         
-        let response = self.client
-            .post(&auth_url)
-            .json(&credentials)
-            .send()
+        let req = HttpRequest::post(&auth_url)
+            .json(&credentials);
+        let response = self.transport
+            .send(req)
             .await
             .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
         
@@ -185,24 +192,22 @@ impl ThreeDExperienceConnector {
         let login_response: LoginResponse = response.json().await
             .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
         
-        self.access_token = Some(login_response.access_token);
+        *self.token.write().unwrap() = Some(AccessToken {
+            value: login_response.access_token,
+            expires_at: None,
+            refresh_token: None,
+        });
         
         Ok(())
     }
     
-    async fn authenticate_oauth2(&mut self, token_url: &str, client_id: &str, client_secret: &str) 
-        -> Result<(), PLMError> {
-        let params = [
-            ("grant_type", "client_credentials"),
-            ("client_id", client_id),
-            ("client_secret", client_secret),
-            ("scope", "3DSpace"),
-        ];
-        
-        let response = self.client
-            .post(token_url)
-            .form(&params)
-            .send()
+    /// Run an OAuth2 grant against `token_url` and store the token it
+    /// returns.
+    async fn request_token(&self, token_url: &str, params: &[(&str, &str)]) -> Result<(), PLMError> {
+        let req = HttpRequest::post(token_url)
+            .form(params);
+        let response = self.transport
+            .send(req)
             .await
             .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
         
@@ -215,64 +220,117 @@ impl ThreeDExperienceConnector {
         #[derive(Deserialize)]
         struct TokenResponse {
             access_token: String,
+            expires_in: Option<i64>,
+            refresh_token: Option<String>,
         }
         
         let token: TokenResponse = response.json().await
             .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
         
-        self.access_token = Some(token.access_token);
+        *self.token.write().unwrap() = Some(AccessToken {
+            value: token.access_token,
+            expires_at: token.expires_in
+                .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs)),
+            refresh_token: token.refresh_token,
+        });
         
         Ok(())
     }
     
+    /// Renew an OAuth2 token about to expire: with its refresh token when
+    /// the grant returned one, else (or if that is refused) by re-running
+    /// the client-credentials grant.
+    async fn refresh_token_if_expiring(&self) -> Result<(), PLMError> {
+        let AuthenticationMethod::OAuth2 { client_id, client_secret, token_url } = &self.config.auth else {
+            return Ok(());
+        };
+        let expiring = self.token.read().unwrap()
+            .clone()
+            .filter(|token| token.needs_refresh(chrono::Utc::now()));
+        let Some(expiring) = expiring else {
+            return Ok(());
+        };
+        
+        if let Some(refresh_token) = &expiring.refresh_token {
+            let refreshed = self.request_token(token_url, &[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+            ]).await;
+            if refreshed.is_ok() {
+                return Ok(());
+            }
+        }
+        
+        self.request_token(token_url, &[
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("scope", "3DSpace"),
+        ]).await
+    }
+    
+    fn bearer(&self) -> Option<String> {
+        self.token.read().unwrap()
+            .as_ref()
+            .map(|token| format!("Bearer {}", token.value))
+    }
+    
     fn build_url(&self, api_path: &str) -> String {
         format!("{}{}", self.config.platform_url, api_path)
     }
     
     async fn get_with_auth(&self, path: &str) -> Result<reqwest::Response, PLMError> {
+        self.refresh_token_if_expiring().await?;
+        
         let url = self.build_url(path);
         
-        let mut req = self.client.get(&url);
+        let mut req = HttpRequest::get(&url);
         
-        if let Some(token) = &self.access_token {
-            req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        if let Some(bearer) = self.bearer() {
+            req = req.header(header::AUTHORIZATION, bearer);
         }
         
         req = req.header("SecurityContext", self.format_security_context());
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| PLMError::NetworkError(e.to_string()))
     }
     
     async fn post_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, PLMError> {
+        self.refresh_token_if_expiring().await?;
+        
         let url = self.build_url(path);
         
-        let mut req = self.client.post(&url).json(body);
+        let mut req = HttpRequest::post(&url).json(body);
         
-        if let Some(token) = &self.access_token {
-            req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        if let Some(bearer) = self.bearer() {
+            req = req.header(header::AUTHORIZATION, bearer);
         }
         
         req = req.header("SecurityContext", self.format_security_context());
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| PLMError::NetworkError(e.to_string()))
     }
     
     async fn put_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, PLMError> {
+        self.refresh_token_if_expiring().await?;
+        
         let url = self.build_url(path);
         
-        let mut req = self.client.put(&url).json(body);
+        let mut req = HttpRequest::put(&url).json(body);
         
-        if let Some(token) = &self.access_token {
-            req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        if let Some(bearer) = self.bearer() {
+            req = req.header(header::AUTHORIZATION, bearer);
         }
         
         req = req.header("SecurityContext", self.format_security_context());
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| PLMError::NetworkError(e.to_string()))
     }
@@ -384,10 +442,10 @@ impl PLMConnector for ThreeDExperienceConnector {
     }
     
     async fn disconnect(&mut self) -> Result<(), PLMError> {
-        if self.access_token.is_some() {
+        if self.token.read().unwrap().is_some() {
             let logout_url = "/3DPassport/logout";
             let _ = self.post_with_auth(logout_url, &serde_json::json!({})).await;
-            self.access_token = None;
+            *self.token.write().unwrap() = None;
         }
         
         Ok(())
@@ -478,6 +536,7 @@ impl PLMConnector for ThreeDExperienceConnector {
         let items = structure.children.into_iter()
             .enumerate()
             .map(|(idx, member)| BOMItem {
+                level: 1,
                 item_number: (idx + 1) as u32,
                 part_number: member.id,
                 quantity: member.quantity,
@@ -485,6 +544,7 @@ impl PLMConnector for ThreeDExperienceConnector {
                 reference_designator: member.reference_designator,
                 find_number: Some(member.rel_id),
                 notes: None,
+                effectivity: None,
             })
             .collect();
         
@@ -496,66 +556,6 @@ impl PLMConnector for ThreeDExperienceConnector {
         })
     }
     
-    async fn push_changes(&self, delta: &PLMDelta) -> Result<PLMSyncResult, PLMError> {
-        let mut result = PLMSyncResult {
-            success: true,
-            parts_created: Vec::new(),
-            parts_updated: Vec::new(),
-            parts_failed: Vec::new(),
-            eco_id: None,
-            sync_timestamp: chrono::Utc::now(),
-        };
-        
-        for part in &delta.added_parts {
-            match self.create_part(part).await {
-                Ok(id) => result.parts_created.push(id),
-                Err(e) => {
-                    result.parts_failed.push((part.part_number.clone(), e.to_string()));
-                    result.success = false;
-                }
-            }
-        }
-        
-        for part_diff in &delta.modified_parts {
-            let changes = PartChanges {
-                description: None,
-                lifecycle_state: None,
-                supplier: None,
-                unit_cost: None,
-                custom_attributes: HashMap::new(),
-            };
-            
-            match self.update_part(&part_diff.part_id, &changes).await {
-                Ok(_) => result.parts_updated.push(part_diff.part_number.clone()),
-                Err(e) => {
-                    result.parts_failed.push((part_diff.part_number.clone(), e.to_string()));
-                    result.success = false;
-                }
-            }
-        }
-        
-        if delta.eco_required {
-            let change_request = ChangeRequest {
-                title: delta.change_summary.clone(),
-                description: "Automated sync from ArcLang".to_string(),
-                reason: "Model synchronization".to_string(),
-                affected_items: delta.affected_part_numbers(),
-                requester: "arclang".to_string(),
-                priority: Priority::Medium,
-                change_type: ECOChangeType::Engineering,
-            };
-            
-            match self.create_eco(&change_request).await {
-                Ok(eco_id) => result.eco_id = Some(eco_id),
-                Err(e) => {
-                    result.parts_failed.push(("ChangeAction".to_string(), e.to_string()));
-                }
-            }
-        }
-        
-        Ok(result)
-    }
-    
     async fn create_part(&self, part: &PLMPart) -> Result<String, PLMError> {
         let mut attributes = HashMap::new();
         attributes.insert("V_Name".to_string(), serde_json::json!(part.name));
@@ -652,7 +652,7 @@ impl PLMConnector for ThreeDExperienceConnector {
         
         let path = format!(
             "/3DSpace/resources/v1/modeler/dseng:EngItem/search?$searchStr={}",
-            urlencoding::encode(&search_str)
+            super::url_encode(&search_str)
         );
         
         let response = self.get_with_auth(&path).await?;
@@ -734,9 +734,61 @@ mod tests {
                 organization: "MyCompany".to_string(),
                 project: "AFCS".to_string(),
             },
+            connection: ConnectionOptions::default(),
         };
         
         let connector = ThreeDExperienceConnector::new(config);
         assert_eq!(connector.name(), "3DEXPERIENCE");
     }
+    
+    #[tokio::test]
+    async fn oauth2_token_is_refreshed_before_it_expires() {
+        use crate::integrations::transport::{mock::MockTransport, HttpBody};
+        
+        let token_url = "https://3dx.example/oauth/token";
+        let transport = Arc::new(
+            MockTransport::new()
+                .on(reqwest::Method::POST, token_url, 200,
+                    r#"{"access_token": "t1", "expires_in": 30, "refresh_token": "r1"}"#)
+                .on(reqwest::Method::POST, token_url, 200,
+                    r#"{"access_token": "t2", "expires_in": 3600}"#)
+                .on(reqwest::Method::GET, "https://3dx.example/3DSpace/", 200, "{}"),
+        );
+        let connector = ThreeDExperienceConnector::with_transport(
+            ThreeDExperienceConfig {
+                platform_url: "https://3dx.example".to_string(),
+                tenant: "TestTenant".to_string(),
+                auth: AuthenticationMethod::OAuth2 {
+                    client_id: "arclang".to_string(),
+                    client_secret: "secret".to_string(),
+                    token_url: token_url.to_string(),
+                },
+                collaborative_space: "Engineering".to_string(),
+                security_context: SecurityContext {
+                    role: "VPLMProjectLeader".to_string(),
+                    organization: "MyCompany".to_string(),
+                    project: "AFCS".to_string(),
+                },
+                connection: ConnectionOptions::default(),
+            },
+            transport.clone(),
+        );
+        
+        connector.authenticate().await.unwrap();
+        connector.get_with_auth("/3DSpace/resources/v1/modeler/ping").await.unwrap();
+        connector.get_with_auth("/3DSpace/resources/v1/modeler/ping").await.unwrap();
+        
+        let requests = transport.requests();
+        let grant = |request: &HttpRequest| match &request.body {
+            Some(HttpBody::Form(params)) => params.iter()
+                .find(|(key, _)| key == "grant_type")
+                .map(|(_, value)| value.clone()),
+            _ => None,
+        };
+        assert_eq!(requests.len(), 4);
+        assert_eq!(grant(&requests[0]).as_deref(), Some("client_credentials"));
+        assert_eq!(grant(&requests[1]).as_deref(), Some("refresh_token"));
+        assert_eq!(requests[2].header_value("Authorization"), Some("Bearer t2"));
+        assert_eq!(requests[3].header_value("Authorization"), Some("Bearer t2"));
+    }
 }
@@ -0,0 +1,203 @@
+//! HTML traceability heatmap (`arclang trace --matrix -o matrix.html`), the
+//! page reviewers read instead of the printed link list.
+//!
+//! One row per requirement, one column per target of the
+//! [`TraceabilityMatrix`]; linked cells are colored by link type and carry
+//! the trace rationale as a tooltip. Each row ends with the share of
+//! columns it links to, each column with the share of rows linking to it,
+//! and the heading badge shows the matrix coverage (rows with any link).
+//! Styled like the HTML report and fully self-contained.
+
+use super::requirements_management::{TraceLinkType, TraceabilityMatrix};
+use crate::compiler::html_report::{coverage_class, html_escape, MERMAID_PAGE_CSS, REPORT_CSS};
+
+/// Heatmap-only additions on top of the report styling.
+const HEATMAP_CSS: &str = r#"
+        .heatmap { width: auto; }
+        .heatmap th.target { writing-mode: vertical-rl; transform: rotate(180deg); white-space: nowrap; }
+        .heatmap td.cell { width: 22px; min-width: 22px; padding: 0; text-align: center; }
+        .heatmap td.pct, .heatmap th.pct { text-align: right; white-space: nowrap; }
+        .heatmap td.empty { background: #fafafa; }
+        .legend span { display: inline-block; margin-right: 12px; font-size: 0.9em; }
+        .legend .swatch { width: 12px; height: 12px; margin-right: 4px; vertical-align: middle; border: 1px solid #ccc; }
+        .satisfies { background: #2e7d32; }
+        .derived-from { background: #6a1b9a; }
+        .refines { background: #8e24aa; }
+        .allocated-to { background: #1565c0; }
+        .verified-by { background: #00838f; }
+        .traces { background: #9e9e9e; }
+        .implements { background: #ef6c00; }
+        .depends-on { background: #6d4c41; }
+"#;
+
+/// Every link type, in legend order.
+const LINK_TYPES: [TraceLinkType; 8] = [
+    TraceLinkType::Satisfies,
+    TraceLinkType::DerivedFrom,
+    TraceLinkType::Refines,
+    TraceLinkType::AllocatedTo,
+    TraceLinkType::VerifiedBy,
+    TraceLinkType::Traces,
+    TraceLinkType::Implements,
+    TraceLinkType::DependsOn,
+];
+
+/// CSS class coloring cells of `link_type`.
+fn link_class(link_type: &TraceLinkType) -> &'static str {
+    match link_type {
+        TraceLinkType::Satisfies => "satisfies",
+        TraceLinkType::DerivedFrom => "derived-from",
+        TraceLinkType::Refines => "refines",
+        TraceLinkType::AllocatedTo => "allocated-to",
+        TraceLinkType::VerifiedBy => "verified-by",
+        TraceLinkType::Traces => "traces",
+        TraceLinkType::Implements => "implements",
+        TraceLinkType::DependsOn => "depends-on",
+    }
+}
+
+fn percentage(linked: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        linked as f64 * 100.0 / total as f64
+    }
+}
+
+/// The heatmap page for `matrix`.
+pub fn generate_traceability_heatmap(matrix: &TraceabilityMatrix, title: &str) -> String {
+    let mut body = String::new();
+    body.push_str(&format!(
+        "        <h1>{} <span class=\"badge {}\" title=\"Requirements with at least one link\">{:.1}% covered</span></h1>\n",
+        html_escape(title),
+        coverage_class(matrix.coverage_percentage),
+        matrix.coverage_percentage
+    ));
+    body.push_str(&format!(
+        "        <p class=\"muted\">{} ({}) × {} ({}) · {} links</p>\n",
+        html_escape(&matrix.source_type),
+        matrix.rows.len(),
+        html_escape(&matrix.target_type),
+        matrix.columns.len(),
+        matrix.cells.iter().filter(|cell| cell.has_link).count()
+    ));
+
+    // Only the link types present get a legend entry.
+    body.push_str("        <p class=\"legend\">");
+    for link_type in LINK_TYPES.iter().filter(|t| matrix.cells.iter().any(|c| c.has_link && c.link_type.as_ref() == Some(*t))) {
+        body.push_str(&format!(
+            "<span><span class=\"swatch {}\"></span>{:?}</span>",
+            link_class(link_type),
+            link_type
+        ));
+    }
+    body.push_str("</p>\n");
+
+    body.push_str("        <table class=\"heatmap\">\n            <tr><th>Requirement</th>");
+    for column in &matrix.columns {
+        body.push_str(&format!(
+            "<th class=\"target\" title=\"{}\">{}</th>",
+            html_escape(&column.id),
+            html_escape(&column.label)
+        ));
+    }
+    body.push_str("<th class=\"pct\">Coverage</th></tr>\n");
+
+    for row in &matrix.rows {
+        body.push_str(&format!(
+            "            <tr><th title=\"{}\">{}</th>",
+            html_escape(&row.label),
+            html_escape(&row.id)
+        ));
+        let mut linked = 0;
+        for column in &matrix.columns {
+            match matrix.cell(&row.id, &column.id) {
+                Some(cell) => {
+                    linked += 1;
+                    let link_type = cell.link_type.clone().unwrap_or_default();
+                    let mut tooltip = format!("{} → {}: {:?}", row.id, column.id, link_type);
+                    if let Some(rationale) = &cell.rationale {
+                        tooltip.push_str(&format!(" — {rationale}"));
+                    }
+                    body.push_str(&format!(
+                        "<td class=\"cell {}\" title=\"{}\"></td>",
+                        link_class(&link_type),
+                        html_escape(&tooltip)
+                    ));
+                }
+                None => body.push_str("<td class=\"cell empty\"></td>"),
+            }
+        }
+        body.push_str(&format!(
+            "<td class=\"pct\">{:.1}%</td></tr>\n",
+            percentage(linked, matrix.columns.len())
+        ));
+    }
+
+    body.push_str("            <tr><th class=\"pct\">Coverage</th>");
+    for column in &matrix.columns {
+        let linked = matrix.rows.iter().filter(|row| matrix.cell(&row.id, &column.id).is_some()).count();
+        body.push_str(&format!("<td class=\"pct\">{:.0}%</td>", percentage(linked, matrix.rows.len())));
+    }
+    body.push_str(&format!("<td class=\"pct\">{:.1}%</td></tr>\n", matrix.coverage_percentage));
+    body.push_str("        </table>\n");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <style>{MERMAID_PAGE_CSS}{REPORT_CSS}{HEATMAP_CSS}    </style>
+</head>
+<body>
+    <div class="container">
+{body}    </div>
+</body>
+</html>
+"#,
+        title = html_escape(title),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const MODEL: &str = r#"model "Brake" {
+}
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "The system shall brake"
+    }
+    requirement "REQ-002" {
+        description: "The system shall log"
+    }
+}
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+    component "Logger" { id: "LC-002" }
+}
+trace "LC-001" satisfies "REQ-001" { rationale: "brakes <hard>" }
+"#;
+
+    #[test]
+    fn cells_are_colored_by_link_type_with_the_rationale_as_tooltip() {
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(MODEL)
+            .expect("compiles")
+            .semantic_model;
+        let html = generate_traceability_heatmap(&TraceabilityMatrix::from_model(&model), "Brake");
+
+        assert!(html.contains(
+            "<td class=\"cell satisfies\" title=\"REQ-001 → LC-001: Satisfies — brakes &lt;hard&gt;\"></td>"
+        ));
+        assert_eq!(html.matches("<td class=\"cell empty\">").count(), 3);
+        // REQ-001 links one of two columns; LC-001 is linked by one of two rows.
+        assert!(html.contains("<td class=\"pct\">50.0%</td></tr>"));
+        assert!(html.contains("<td class=\"pct\">50%</td><td class=\"pct\">0%</td>"));
+        assert!(html.contains("class=\"badge fair\""));
+        assert!(!html.contains("src=\"http"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Excel traceability workbook (`arclang export -f xlsx`), the form auditors
+//! usually ask for. Three sheets:
+//!
+//! - **Matrix**: one row per requirement, one column per component and test
+//!   case ([`TraceabilityMatrix::from_model`]), the link type in linked
+//!   cells. The "Satisfied by" / "Verified by" summary columns are red when
+//!   empty; the header row and requirement column stay frozen.
+//! - **Requirements**: id, title, text, priority, category, safety level and
+//!   verification method.
+//! - **Gaps**: the model's coverage gaps, one row each.
+
+use super::requirements_management::{TraceLinkType, TraceabilityMatrix};
+use crate::compiler::semantic::SemanticModel;
+use rust_xlsxwriter::{Color, ConditionalFormatBlank, Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+
+/// Columns of the Matrix sheet before the per-element ones.
+const MATRIX_LEADING: [&str; 4] = ["Requirement", "Title", "Satisfied by", "Verified by"];
+
+/// The workbook for `model`, as `.xlsx` bytes.
+pub fn generate_traceability_xlsx(model: &SemanticModel) -> Result<Vec<u8>, XlsxError> {
+    let matrix = TraceabilityMatrix::from_model(model);
+    let header = Format::new().set_bold().set_background_color(Color::RGB(0xD9E1F2)).set_border(FormatBorder::Thin);
+    let mut workbook = Workbook::new();
+
+    write_matrix(workbook.add_worksheet().set_name("Matrix")?, &matrix, &header)?;
+    write_requirements(workbook.add_worksheet().set_name("Requirements")?, model, &header)?;
+    write_gaps(workbook.add_worksheet().set_name("Gaps")?, model, &header)?;
+
+    workbook.save_to_buffer()
+}
+
+fn write_header(sheet: &mut Worksheet, titles: &[&str], format: &Format) -> Result<(), XlsxError> {
+    for (col, title) in titles.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, format)?;
+    }
+    Ok(())
+}
+
+fn write_matrix(sheet: &mut Worksheet, matrix: &TraceabilityMatrix, header: &Format) -> Result<(), XlsxError> {
+    let first = MATRIX_LEADING.len() as u16;
+    write_header(sheet, &MATRIX_LEADING, header)?;
+    let rotated = header.clone().set_rotation(90);
+    for (i, column) in matrix.columns.iter().enumerate() {
+        sheet.write_string_with_format(0, first + i as u16, &column.label, &rotated)?;
+    }
+
+    let linked = Format::new().set_align(FormatAlign::Center);
+    for (r, row) in matrix.rows.iter().enumerate() {
+        let r = r as u32 + 1;
+        sheet.write_string(r, 0, &row.id)?;
+        sheet.write_string(r, 1, &row.label)?;
+        let mut satisfied = Vec::new();
+        let mut verified = Vec::new();
+        for (c, column) in matrix.columns.iter().enumerate() {
+            let Some(cell) = matrix.cell(&row.id, &column.id) else { continue };
+            let link_type = cell.link_type.clone().unwrap_or_default();
+            if link_type == TraceLinkType::VerifiedBy {
+                verified.push(column.label.as_str());
+            } else {
+                satisfied.push(column.label.as_str());
+            }
+            sheet.write_string_with_format(r, first + c as u16, format!("{link_type:?}"), &linked)?;
+        }
+        if !satisfied.is_empty() {
+            sheet.write_string(r, 2, satisfied.join(", "))?;
+        }
+        if !verified.is_empty() {
+            sheet.write_string(r, 3, verified.join(", "))?;
+        }
+    }
+
+    if !matrix.rows.is_empty() {
+        let missing = Format::new().set_background_color(Color::RGB(0xFFC7CE)).set_font_color(Color::RGB(0x9C0006));
+        let blank = ConditionalFormatBlank::new().set_format(missing);
+        sheet.add_conditional_format(1, 2, matrix.rows.len() as u32, 3, &blank)?;
+    }
+    sheet.set_freeze_panes(1, 1)?;
+    sheet.set_column_width(1, 40)?;
+    sheet.set_column_width(2, 24)?;
+    sheet.set_column_width(3, 24)?;
+    Ok(())
+}
+
+fn write_requirements(sheet: &mut Worksheet, model: &SemanticModel, header: &Format) -> Result<(), XlsxError> {
+    write_header(
+        sheet,
+        &["Requirement", "Title", "Text", "Priority", "Category", "Safety level", "Verification method"],
+        header,
+    )?;
+    let wrap = Format::new().set_text_wrap();
+    for (r, req) in model.requirements.iter().enumerate() {
+        let r = r as u32 + 1;
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        sheet.write_string(r, 0, &req.id)?;
+        sheet.write_string(r, 1, optional(&req.title))?;
+        sheet.write_string_with_format(r, 2, &req.description, &wrap)?;
+        sheet.write_string(r, 3, &req.priority)?;
+        sheet.write_string(r, 4, optional(&req.category))?;
+        sheet.write_string(r, 5, optional(&req.safety_level))?;
+        sheet.write_string(r, 6, req.verification_method.map(|m| m.as_str()).unwrap_or_default())?;
+    }
+    sheet.set_freeze_panes(1, 1)?;
+    sheet.set_column_width(1, 30)?;
+    sheet.set_column_width(2, 60)?;
+    Ok(())
+}
+
+fn write_gaps(sheet: &mut Worksheet, model: &SemanticModel, header: &Format) -> Result<(), XlsxError> {
+    write_header(sheet, &["Requirement", "Gap", "Severity", "Description"], header)?;
+    for (r, gap) in model.coverage_gaps().iter().enumerate() {
+        let r = r as u32 + 1;
+        sheet.write_string(r, 0, &gap.requirement_id)?;
+        sheet.write_string(r, 1, format!("{:?}", gap.gap_type))?;
+        sheet.write_string(r, 2, format!("{:?}", gap.severity))?;
+        sheet.write_string(r, 3, &gap.description)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.set_column_width(3, 60)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const MODEL: &str = r#"model "Brake" {
+}
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "The system shall brake"
+        safety_level: "ASIL-B"
+    }
+    requirement "REQ-002" {
+        description: "The system shall log"
+    }
+}
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+    component "Logger" { id: "LC-002" }
+}
+test_case "TC-001" {
+    verifies: "REQ-001"
+}
+trace "LC-001" satisfies "REQ-001" { rationale: "brakes" }
+"#;
+
+    fn model() -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(MODEL)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    #[test]
+    fn the_matrix_links_requirements_to_components_and_test_cases() {
+        let matrix = TraceabilityMatrix::from_model(&model());
+
+        let columns: Vec<&str> = matrix.columns.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(columns, vec!["LC-001", "LC-002", "TC-001"]);
+        assert_eq!(matrix.cell("REQ-001", "LC-001").and_then(|c| c.link_type.clone()), Some(TraceLinkType::Satisfies));
+        assert_eq!(matrix.cell("REQ-001", "TC-001").and_then(|c| c.link_type.clone()), Some(TraceLinkType::VerifiedBy));
+        assert!(matrix.cell("REQ-002", "LC-002").is_none());
+        assert_eq!(matrix.coverage_percentage, 50.0);
+    }
+
+    #[test]
+    fn the_workbook_is_an_xlsx_package() {
+        let bytes = generate_traceability_xlsx(&model()).unwrap();
+        assert!(bytes.starts_with(b"PK"), "xlsx files are zip archives");
+    }
+}
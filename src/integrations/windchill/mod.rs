@@ -1,12 +1,15 @@
 use async_trait::async_trait;
-use reqwest::{Client, header};
+use reqwest::header;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use super::plm_integration::*;
+use super::transport::{ConnectionOptions, HttpRequest, HttpTransport, ReqwestTransport};
 
 pub struct WindchillConnector {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     config: WindchillConfig,
     session_token: Option<String>,
 }
@@ -19,6 +22,9 @@ pub struct WindchillConfig {
     pub organization: String,
     pub library: String,
     pub auth: AuthenticationMethod,
+    /// Timeout, proxy and extra CA certificate for the HTTP client.
+    #[serde(default)]
+    pub connection: ConnectionOptions,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -44,6 +50,8 @@ struct WindchillBOM {
     parent: String,
     structure_type: String,
     members: Vec<WindchillBOMMember>,
+    #[serde(default)]
+    effectivity: Option<WindchillEffectivity>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +63,17 @@ struct WindchillBOMMember {
     unit: String,
     find_number: Option<String>,
     reference_designator: Option<String>,
+    #[serde(default)]
+    effectivity: Option<WindchillEffectivity>,
+}
+
+/// A date, serial number or lot/unit range on a part usage.
+#[derive(Debug, Serialize, Deserialize)]
+struct WindchillEffectivity {
+    #[serde(rename = "type")]
+    effectivity_type: String,
+    start: String,
+    end: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -68,20 +87,14 @@ struct WindchillChangeRequest {
 
 impl WindchillConnector {
     pub fn new(config: WindchillConfig) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::CONTENT_TYPE,
-            header::HeaderValue::from_static("application/json"),
-        );
-        
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-        
+        let transport = ReqwestTransport::with_options(Duration::from_secs(30), &config.connection);
+        Self::with_transport(config, Arc::new(transport))
+    }
+    
+    /// Connector that sends its requests through `transport`.
+    pub fn with_transport(config: WindchillConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self {
-            client,
+            transport,
             config,
             session_token: None,
         }
@@ -96,10 +109,10 @@ impl WindchillConnector {
                     ("client_secret", client_secret),
                 ];
                 
-                let response = self.client
-                    .post(token_url)
-                    .form(&params)
-                    .send()
+                let req = HttpRequest::post(token_url)
+                    .form(&params);
+                let response = self.transport
+                    .send(req)
                     .await
                     .map_err(|e| PLMError::AuthenticationError(e.to_string()))?;
                 
@@ -123,7 +136,7 @@ impl WindchillConnector {
             AuthenticationMethod::BasicAuth { username, password } => {
                 let auth_header = format!(
                     "Basic {}",
-                    base64::encode(format!("{}:{}", username, password))
+                    super::basic_credentials(username, password)
                 );
                 self.session_token = Some(auth_header);
             }
@@ -149,13 +162,13 @@ impl WindchillConnector {
     async fn get_with_auth(&self, path: &str) -> Result<reqwest::Response, PLMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.get(&url);
+        let mut req = HttpRequest::get(&url);
         
         if let Some(token) = &self.session_token {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| PLMError::NetworkError(e.to_string()))
     }
@@ -163,13 +176,13 @@ impl WindchillConnector {
     async fn post_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, PLMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.post(&url).json(body);
+        let mut req = HttpRequest::post(&url).json(body);
         
         if let Some(token) = &self.session_token {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| PLMError::NetworkError(e.to_string()))
     }
@@ -177,17 +190,42 @@ impl WindchillConnector {
     async fn put_with_auth(&self, path: &str, body: &impl Serialize) -> Result<reqwest::Response, PLMError> {
         let url = self.build_url(path);
         
-        let mut req = self.client.put(&url).json(body);
+        let mut req = HttpRequest::put(&url).json(body);
         
         if let Some(token) = &self.session_token {
             req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
         }
         
-        req.send()
+        self.transport.send(req)
             .await
             .map_err(|e| PLMError::NetworkError(e.to_string()))
     }
     
+    /// Multi-level BOM of `parent_part` as built at `point`: usages not
+    /// effective there are left out with their whole subtree.
+    pub async fn fetch_bom_as_built(
+        &self,
+        parent_part: &str,
+        point: &EffectivityPoint,
+        max_depth: u32,
+    ) -> Result<BOM, PLMError> {
+        Ok(self.fetch_bom_recursive(parent_part, max_depth).await?.effective_at(point))
+    }
+    
+    fn convert_effectivity(&self, wc_effectivity: WindchillEffectivity) -> Option<Effectivity> {
+        let effectivity_type = match wc_effectivity.effectivity_type.to_lowercase().as_str() {
+            "date" => EffectivityType::Date,
+            "serial" | "serialnumber" | "serial_number" => EffectivityType::SerialNumber,
+            "unit" | "lot" | "block" => EffectivityType::Unit,
+            _ => return None,
+        };
+        Some(Effectivity {
+            effectivity_type,
+            start: wc_effectivity.start,
+            end: wc_effectivity.end,
+        })
+    }
+    
     fn convert_to_plm_part(&self, wc_part: WindchillPart) -> PLMPart {
         PLMPart {
             id: wc_part.id,
@@ -364,6 +402,7 @@ impl PLMConnector for WindchillConnector {
         let items = wc_bom.members.into_iter()
             .enumerate()
             .map(|(idx, member)| BOMItem {
+                level: 1,
                 item_number: (idx + 1) as u32,
                 part_number: member.part_number,
                 quantity: member.quantity,
@@ -371,6 +410,7 @@ impl PLMConnector for WindchillConnector {
                 reference_designator: member.reference_designator,
                 find_number: member.find_number,
                 notes: None,
+                effectivity: member.effectivity.and_then(|e| self.convert_effectivity(e)),
             })
             .collect();
         
@@ -378,70 +418,10 @@ impl PLMConnector for WindchillConnector {
             parent_part: parent_part.to_string(),
             structure_type: wc_bom.structure_type,
             items,
-            effectivity: None,
+            effectivity: wc_bom.effectivity.and_then(|e| self.convert_effectivity(e)),
         })
     }
     
-    async fn push_changes(&self, delta: &PLMDelta) -> Result<PLMSyncResult, PLMError> {
-        let mut result = PLMSyncResult {
-            success: true,
-            parts_created: Vec::new(),
-            parts_updated: Vec::new(),
-            parts_failed: Vec::new(),
-            eco_id: None,
-            sync_timestamp: chrono::Utc::now(),
-        };
-        
-        for part in &delta.added_parts {
-            match self.create_part(part).await {
-                Ok(id) => result.parts_created.push(id),
-                Err(e) => {
-                    result.parts_failed.push((part.part_number.clone(), e.to_string()));
-                    result.success = false;
-                }
-            }
-        }
-        
-        for part_diff in &delta.modified_parts {
-            let changes = PartChanges {
-                description: None,
-                lifecycle_state: None,
-                supplier: None,
-                unit_cost: None,
-                custom_attributes: HashMap::new(),
-            };
-            
-            match self.update_part(&part_diff.part_id, &changes).await {
-                Ok(_) => result.parts_updated.push(part_diff.part_number.clone()),
-                Err(e) => {
-                    result.parts_failed.push((part_diff.part_number.clone(), e.to_string()));
-                    result.success = false;
-                }
-            }
-        }
-        
-        if delta.eco_required {
-            let change_request = ChangeRequest {
-                title: delta.change_summary.clone(),
-                description: "Automated sync from ArcLang".to_string(),
-                reason: "Model update".to_string(),
-                affected_items: delta.affected_part_numbers(),
-                requester: "arclang".to_string(),
-                priority: Priority::Medium,
-                change_type: ECOChangeType::Engineering,
-            };
-            
-            match self.create_eco(&change_request).await {
-                Ok(eco_id) => result.eco_id = Some(eco_id),
-                Err(e) => {
-                    result.parts_failed.push(("ECO".to_string(), e.to_string()));
-                }
-            }
-        }
-        
-        Ok(result)
-    }
-    
     async fn create_part(&self, part: &PLMPart) -> Result<String, PLMError> {
         let wc_part = WindchillPart {
             id: String::new(),
@@ -508,7 +488,6 @@ impl PLMConnector for WindchillConnector {
         
         #[derive(Deserialize)]
         struct ECOResponse {
-            id: String,
             number: String,
         }
         
@@ -607,9 +586,106 @@ mod tests {
                 username: "test".to_string(),
                 password: "test".to_string(),
             },
+            connection: ConnectionOptions::default(),
         };
         
         let connector = WindchillConnector::new(config);
         assert_eq!(connector.name(), "Windchill");
     }
+    
+    fn bom_body(parent: &str, members: serde_json::Value) -> String {
+        serde_json::json!({
+            "@id": format!("bom-{parent}"),
+            "parent": parent,
+            "structure_type": "Design",
+            "members": members
+        })
+        .to_string()
+    }
+    
+    fn member(part_number: &str, effectivity: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "@id": format!("usage-{part_number}"),
+            "part_number": part_number,
+            "quantity": 1.0,
+            "unit": "ea",
+            "find_number": null,
+            "reference_designator": null,
+            "effectivity": effectivity
+        })
+    }
+    
+    /// ASSY → [SUB-A (2023 – mid 2024), SUB-B (from mid 2024), BOLT (serials 100–199)],
+    /// SUB-A → [PIN], SUB-B → [], PIN → [], BOLT → []
+    fn bom_connector() -> WindchillConnector {
+        use crate::integrations::transport::mock::MockTransport;
+        
+        let api = "https://wc.example/Windchill/ProdMgmt/parts";
+        let date = |start: &str, end: Option<&str>| serde_json::json!({ "type": "Date", "start": start, "end": end });
+        let transport = MockTransport::new()
+            .on(reqwest::Method::GET, &format!("{api}/ASSY/bom"), 200, bom_body("ASSY", serde_json::json!([
+                member("SUB-A", date("2023-01-01", Some("2024-06-30"))),
+                member("SUB-B", date("2024-07-01", None)),
+                member("BOLT", serde_json::json!({ "type": "Serial", "start": "100", "end": "199" })),
+            ])))
+            .on(reqwest::Method::GET, &format!("{api}/SUB-A/bom"), 200,
+                bom_body("SUB-A", serde_json::json!([member("PIN", serde_json::Value::Null)])))
+            .on(reqwest::Method::GET, &format!("{api}/SUB-B/bom"), 200, bom_body("SUB-B", serde_json::json!([])))
+            .on(reqwest::Method::GET, &format!("{api}/PIN/bom"), 200, bom_body("PIN", serde_json::json!([])))
+            .on(reqwest::Method::GET, &format!("{api}/BOLT/bom"), 200, bom_body("BOLT", serde_json::json!([])));
+        
+        WindchillConnector::with_transport(
+            WindchillConfig {
+                base_url: "https://wc.example".to_string(),
+                context: "/Windchill".to_string(),
+                product: "TEST-PRODUCT".to_string(),
+                organization: "Test Org".to_string(),
+                library: "Engineering".to_string(),
+                auth: AuthenticationMethod::BasicAuth {
+                    username: "test".to_string(),
+                    password: "test".to_string(),
+                },
+                connection: ConnectionOptions::default(),
+            },
+            Arc::new(transport),
+        )
+    }
+    
+    fn rows(bom: &BOM) -> Vec<(u32, &str)> {
+        bom.items.iter().map(|i| (i.level, i.part_number.as_str())).collect()
+    }
+    
+    #[tokio::test]
+    async fn fetch_bom_parses_usage_effectivity() {
+        let bom = bom_connector().fetch_bom("ASSY").await.unwrap();
+        
+        let sub_a = bom.items[0].effectivity.as_ref().unwrap();
+        assert!(matches!(sub_a.effectivity_type, EffectivityType::Date));
+        assert_eq!((sub_a.start.as_str(), sub_a.end.as_deref()), ("2023-01-01", Some("2024-06-30")));
+        let bolt = bom.items[2].effectivity.as_ref().unwrap();
+        assert!(matches!(bolt.effectivity_type, EffectivityType::SerialNumber));
+    }
+    
+    #[tokio::test]
+    async fn fetch_bom_recursive_expands_every_level() {
+        let bom = bom_connector().fetch_bom_recursive("ASSY", 5).await.unwrap();
+        
+        assert_eq!(rows(&bom), vec![(1, "SUB-A"), (2, "PIN"), (1, "SUB-B"), (1, "BOLT")]);
+    }
+    
+    #[tokio::test]
+    async fn fetch_bom_as_built_keeps_the_structure_effective_at_the_point() {
+        let connector = bom_connector();
+        let on = |date: &str| EffectivityPoint::Date(date.parse().unwrap());
+        
+        let early = connector.fetch_bom_as_built("ASSY", &on("2024-03-01"), 5).await.unwrap();
+        assert_eq!(rows(&early), vec![(1, "SUB-A"), (2, "PIN"), (1, "BOLT")]);
+        
+        let late = connector.fetch_bom_as_built("ASSY", &on("2024-07-01"), 5).await.unwrap();
+        assert_eq!(rows(&late), vec![(1, "SUB-B"), (1, "BOLT")]);
+        
+        let serial = EffectivityPoint::SerialNumber("250".to_string());
+        let unit = connector.fetch_bom_as_built("ASSY", &serial, 5).await.unwrap();
+        assert_eq!(rows(&unit), vec![(1, "SUB-A"), (2, "PIN"), (1, "SUB-B")]);
+    }
 }
@@ -9,7 +9,11 @@ pub mod safety;
 pub mod collaboration;
 pub mod plugins;
 #[cfg(feature = "native")]
+pub mod integrations;
+#[cfg(feature = "native")]
 pub mod web_server;
+#[cfg(feature = "wasm32")]
+pub mod wasm;
 
 // Re-export for convenience
 pub use compiler::{Compiler, CompilerConfig, CompilerError, CompilationResult};
@@ -41,6 +45,7 @@ mod tests {
         let mut compiler = Compiler::new(CompilerConfig {
             optimization_level: 0,
             target: "json".to_string(),
+            ..CompilerConfig::default()
         });
         
         let result = compiler.compile_string(source);
@@ -1,8 +1,171 @@
-// Plugin system stub
-pub struct PluginManager;
+//! Export plugins: site-specific output formats for `arclang export` without
+//! forking the compiler. A plugin turns the semantic model into text; the
+//! `export` command looks `-f custom:<format>` (or a plain `-f <format>`)
+//! up in its [`ExportRegistry`] before falling back to the built-in formats.
+//!
+//! ```
+//! use arclang::compiler::semantic::SemanticModel;
+//! use arclang::plugins::{ExportPlugin, ExportRegistry, PluginError};
+//!
+//! struct RequirementIds;
+//!
+//! impl ExportPlugin for RequirementIds {
+//!     fn format_name(&self) -> &str {
+//!         "req-ids"
+//!     }
+//!
+//!     fn export(&self, model: &SemanticModel) -> Result<String, PluginError> {
+//!         Ok(model.requirements.iter().map(|r| format!("{}\n", r.id)).collect())
+//!     }
+//! }
+//!
+//! let mut registry = ExportRegistry::with_builtin_plugins();
+//! registry.register(Box::new(RequirementIds));
+//! assert!(registry.formats().contains(&"req-ids"));
+//! ```
+//!
+//! A binary embedding the CLI registers its plugins with
+//! `CliRunner::register_export_plugin` before calling `run`.
 
-impl PluginManager {
+use crate::compiler::semantic::SemanticModel;
+
+pub trait ExportPlugin: Send + Sync {
+    /// The name selecting this plugin, as in `-f custom:<name>`.
+    fn format_name(&self) -> &str;
+
+    fn export(&self, model: &SemanticModel) -> Result<String, PluginError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("no export plugin registered for format '{0}'")]
+    UnknownFormat(String),
+
+    #[error("{plugin}: {message}")]
+    Export { plugin: String, message: String },
+}
+
+#[derive(Default)]
+pub struct ExportRegistry {
+    plugins: Vec<Box<dyn ExportPlugin>>,
+}
+
+impl ExportRegistry {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// A registry holding the plugins shipped with ArcLang.
+    pub fn with_builtin_plugins() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(RequirementsCsv));
+        registry
+    }
+
+    /// Add a plugin; one registered earlier under the same format name is
+    /// replaced.
+    pub fn register(&mut self, plugin: Box<dyn ExportPlugin>) {
+        self.plugins.retain(|p| p.format_name() != plugin.format_name());
+        self.plugins.push(plugin);
+    }
+
+    pub fn get(&self, format: &str) -> Option<&dyn ExportPlugin> {
+        self.plugins
+            .iter()
+            .find(|p| p.format_name() == format)
+            .map(|p| p.as_ref())
+    }
+
+    /// Registered format names, in registration order.
+    pub fn formats(&self) -> Vec<&str> {
+        self.plugins.iter().map(|p| p.format_name()).collect()
+    }
+
+    pub fn export(&self, format: &str, model: &SemanticModel) -> Result<String, PluginError> {
+        self.get(format)
+            .ok_or_else(|| PluginError::UnknownFormat(format.to_string()))?
+            .export(model)
+    }
+}
+
+/// `requirements-csv`: one row per requirement, for spreadsheets and
+/// review checklists.
+pub struct RequirementsCsv;
+
+impl ExportPlugin for RequirementsCsv {
+    fn format_name(&self) -> &str {
+        "requirements-csv"
+    }
+
+    fn export(&self, model: &SemanticModel) -> Result<String, PluginError> {
+        let mut csv = String::from("id,title,description,priority,category,safety_level\n");
+        for req in &model.requirements {
+            let fields = [
+                req.id.as_str(),
+                req.title.as_deref().unwrap_or(""),
+                req.description.as_str(),
+                req.priority.as_str(),
+                req.category.as_deref().unwrap_or(""),
+                req.safety_level.as_deref().unwrap_or(""),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        Ok(csv)
+    }
+}
+
+/// Quote a field holding a separator, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn requirements_csv_quotes_fields_that_need_it() {
+        let source = "requirements system {\n  req \"REQ-001\" \"Brake\" {\n    description: \"Stop the car, \\\"fast\\\"\"\n    priority: \"High\"\n  }\n}\n";
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .unwrap()
+            .semantic_model;
+
+        let csv = ExportRegistry::with_builtin_plugins()
+            .export("requirements-csv", &model)
+            .unwrap();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "id,title,description,priority,category,safety_level");
+        assert!(lines[1].starts_with("REQ-001,"), "{}", lines[1]);
+        assert!(lines[1].contains("\"Stop the car, \"\"fast\"\"\""), "{}", lines[1]);
+        assert!(lines[1].contains(",High,"), "{}", lines[1]);
+    }
+
+    #[test]
+    fn later_registration_replaces_and_unknown_formats_fail() {
+        struct Empty;
+        impl ExportPlugin for Empty {
+            fn format_name(&self) -> &str {
+                "requirements-csv"
+            }
+            fn export(&self, _: &SemanticModel) -> Result<String, PluginError> {
+                Ok(String::new())
+            }
+        }
+        let mut registry = ExportRegistry::with_builtin_plugins();
+        registry.register(Box::new(Empty));
+        let model = SemanticModel::default();
+
+        assert_eq!(registry.formats(), vec!["requirements-csv"]);
+        assert_eq!(registry.export("requirements-csv", &model).unwrap(), "");
+        assert!(matches!(registry.export("nope", &model), Err(PluginError::UnknownFormat(_))));
     }
 }
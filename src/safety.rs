@@ -1,8 +0,0 @@
-// Safety analysis stub
-pub struct SafetyAnalyzer;
-
-impl SafetyAnalyzer {
-    pub fn new() -> Self {
-        Self
-    }
-}
@@ -0,0 +1,440 @@
+use super::*;
+use super::fmea::md_cell;
+use crate::compiler::semantic::{CoverageGap, GapType, SemanticModel, TraceLinkType};
+
+pub fn check_compliance(model: &SemanticModel, config: &SafetyConfig) -> ComplianceStatus {
+    let dal = extract_dal_from_config(config);
+    let checklist = generate_objective_checklist(model, dal.clone());
+
+    let non_compliances: Vec<NonCompliance> = checklist.iter()
+        .filter(|status| status.state != ObjectiveState::Satisfied)
+        .map(|status| NonCompliance {
+            clause: format!("DO-178C Table {}", status.objective.id),
+            description: format!("{} ({})", status.objective.description, status.evidence),
+            severity: match (status.state, status.independence) {
+                (ObjectiveState::Missing, true) => ComplianceSeverity::Critical,
+                (ObjectiveState::Missing, false) => ComplianceSeverity::Major,
+                _ => ComplianceSeverity::Minor,
+            },
+            affected_elements: vec![format!("Table {}", status.objective.table())],
+        })
+        .collect();
+
+    let mut recommendations = Vec::new();
+    if !non_compliances.is_empty() {
+        recommendations.push(format!("Complete all DO-178C objectives for DAL {:?}", dal));
+        recommendations.push("Consider using qualified tools to reduce verification burden".to_string());
+        if matches!(dal, DO178Level::LevelA | DO178Level::LevelB) {
+            recommendations.push("Implement formal methods (DO-333) for critical components".to_string());
+        }
+    }
+
+    let compliance_percentage = if checklist.is_empty() {
+        100.0
+    } else {
+        (checklist.len() - non_compliances.len()) as f64 / checklist.len() as f64 * 100.0
+    };
+
+    ComplianceStatus {
+        compliant: non_compliances.is_empty(),
+        compliance_percentage,
+        non_compliances,
+        recommendations,
+    }
+}
+
+fn extract_dal_from_config(config: &SafetyConfig) -> DO178Level {
+    software_level(&config.target_integrity_level).unwrap_or(DO178Level::LevelC)
+}
+
+fn software_level(level: &IntegrityLevel) -> Option<DO178Level> {
+    match level {
+        IntegrityLevel::DAL_A => Some(DO178Level::LevelA),
+        IntegrityLevel::DAL_B => Some(DO178Level::LevelB),
+        IntegrityLevel::DAL_C => Some(DO178Level::LevelC),
+        IntegrityLevel::DAL_D => Some(DO178Level::LevelD),
+        IntegrityLevel::DAL_E => Some(DO178Level::LevelE),
+        _ => None,
+    }
+}
+
+pub fn level_letter(level: &DO178Level) -> char {
+    match level {
+        DO178Level::LevelA => 'A',
+        DO178Level::LevelB => 'B',
+        DO178Level::LevelC => 'C',
+        DO178Level::LevelD => 'D',
+        DO178Level::LevelE => 'E',
+    }
+}
+
+/// The most demanding software level declared on a requirement or
+/// component (`safety_level: "DAL_B"`), if any.
+pub fn declared_level(model: &SemanticModel) -> Option<DO178Level> {
+    model.requirements.iter()
+        .filter_map(|req| req.safety_level.as_deref())
+        .chain(model.components.iter().filter_map(|comp| comp.safety_level.as_deref()))
+        .filter_map(IntegrityLevel::parse)
+        .filter_map(|level| software_level(&level))
+        .min_by_key(level_letter)
+}
+
+/// An Annex A objective. `levels` holds the software levels it applies to
+/// and `independent` those at which it is satisfied with independence.
+#[derive(Debug)]
+pub struct Objective {
+    /// Table and objective number, e.g. `A-3.1`.
+    pub id: &'static str,
+    pub description: &'static str,
+    pub levels: &'static str,
+    pub independent: &'static str,
+    evidence: Evidence,
+}
+
+impl Objective {
+    pub fn table(&self) -> &'static str {
+        self.id.split('.').next().unwrap_or(self.id)
+    }
+
+    pub fn applies_to(&self, level: &DO178Level) -> bool {
+        self.levels.contains(level_letter(level))
+    }
+
+    pub fn needs_independence(&self, level: &DO178Level) -> bool {
+        self.independent.contains(level_letter(level))
+    }
+}
+
+/// What in the model evidences an objective.
+#[derive(Debug)]
+enum Evidence {
+    Requirements,
+    /// Requirements deriving from or refining other requirements.
+    Derived,
+    Architecture,
+    /// Requirements satisfied by a component.
+    Traced,
+    /// Components satisfying a requirement.
+    Allocated,
+    /// Requirements verified by one of these methods (any when empty).
+    Verified(&'static [&'static str]),
+    /// A process work product, declared as requirements of this category.
+    Process(&'static str),
+}
+
+const REVIEWED: Evidence = Evidence::Verified(&["analysis", "inspection", "review"]);
+const TESTED: Evidence = Evidence::Verified(&["test"]);
+const VERIFIED: Evidence = Evidence::Verified(&[]);
+
+macro_rules! objective {
+    ($id:literal, $levels:literal, $independent:literal, $evidence:expr, $description:literal) => {
+        Objective { id: $id, description: $description, levels: $levels, independent: $independent, evidence: $evidence }
+    };
+}
+
+/// DO-178C Annex A, Tables A-1 to A-10.
+pub const ANNEX_A: &[Objective] = &[
+    objective!("A-1.1", "ABCD", "", Evidence::Process("planning"), "Software development and integral processes activities are defined"),
+    objective!("A-1.2", "ABC", "", Evidence::Process("planning"), "Transition criteria, inter-relationships and sequencing among processes are defined"),
+    objective!("A-1.3", "ABC", "", Evidence::Process("planning"), "Software life cycle environment is defined"),
+    objective!("A-1.4", "ABCD", "", Evidence::Process("planning"), "Additional considerations are addressed"),
+    objective!("A-1.5", "ABC", "", Evidence::Process("standards"), "Software development standards are defined"),
+    objective!("A-1.6", "ABC", "", Evidence::Process("planning"), "Software plans comply with this document"),
+    objective!("A-1.7", "ABC", "", Evidence::Process("planning"), "Development and revision of software plans are coordinated"),
+    objective!("A-2.1", "ABCD", "", Evidence::Requirements, "High-level requirements are developed"),
+    objective!("A-2.2", "ABCD", "", Evidence::Derived, "Derived high-level requirements are defined and provided to the system processes"),
+    objective!("A-2.3", "ABCD", "", Evidence::Architecture, "Software architecture is developed"),
+    objective!("A-2.4", "ABC", "", Evidence::Derived, "Low-level requirements are developed"),
+    objective!("A-2.5", "ABC", "", Evidence::Derived, "Derived low-level requirements are defined and provided to the system processes"),
+    objective!("A-2.6", "ABC", "", Evidence::Process("source_code"), "Source Code is developed"),
+    objective!("A-2.7", "ABCD", "", Evidence::Process("integration"), "Executable Object Code and Parameter Data Item Files are produced and loaded in the target computer"),
+    objective!("A-3.1", "ABCD", "AB", REVIEWED, "High-level requirements comply with system requirements"),
+    objective!("A-3.2", "ABCD", "AB", REVIEWED, "High-level requirements are accurate and consistent"),
+    objective!("A-3.3", "AB", "", Evidence::Process("target_computer"), "High-level requirements are compatible with target computer"),
+    objective!("A-3.4", "ABC", "", VERIFIED, "High-level requirements are verifiable"),
+    objective!("A-3.5", "ABC", "", Evidence::Process("standards"), "High-level requirements conform to standards"),
+    objective!("A-3.6", "ABCD", "", Evidence::Traced, "High-level requirements are traceable to system requirements"),
+    objective!("A-3.7", "ABC", "AB", REVIEWED, "Algorithms are accurate"),
+    objective!("A-4.1", "ABC", "AB", Evidence::Derived, "Low-level requirements comply with high-level requirements"),
+    objective!("A-4.2", "ABC", "AB", REVIEWED, "Low-level requirements are accurate and consistent"),
+    objective!("A-4.3", "AB", "", Evidence::Process("target_computer"), "Low-level requirements are compatible with target computer"),
+    objective!("A-4.4", "AB", "", VERIFIED, "Low-level requirements are verifiable"),
+    objective!("A-4.5", "ABC", "", Evidence::Process("standards"), "Low-level requirements conform to standards"),
+    objective!("A-4.6", "ABC", "", Evidence::Derived, "Low-level requirements are traceable to high-level requirements"),
+    objective!("A-4.7", "ABC", "AB", REVIEWED, "Algorithms are accurate"),
+    objective!("A-4.8", "ABC", "A", Evidence::Traced, "Software architecture is compatible with high-level requirements"),
+    objective!("A-4.9", "ABC", "A", Evidence::Allocated, "Software architecture is consistent"),
+    objective!("A-4.10", "AB", "", Evidence::Process("target_computer"), "Software architecture is compatible with target computer"),
+    objective!("A-4.11", "AB", "", Evidence::Allocated, "Software architecture is verifiable"),
+    objective!("A-4.12", "ABC", "", Evidence::Process("standards"), "Software architecture conforms to standards"),
+    objective!("A-4.13", "ABCD", "A", Evidence::Process("partitioning"), "Software partitioning integrity is confirmed"),
+    objective!("A-5.1", "ABC", "AB", Evidence::Process("source_code"), "Source Code complies with low-level requirements"),
+    objective!("A-5.2", "ABC", "A", Evidence::Process("source_code"), "Source Code complies with software architecture"),
+    objective!("A-5.3", "AB", "", Evidence::Process("source_code"), "Source Code is verifiable"),
+    objective!("A-5.4", "ABC", "", Evidence::Process("standards"), "Source Code conforms to standards"),
+    objective!("A-5.5", "ABC", "", Evidence::Process("source_code"), "Source Code is traceable to low-level requirements"),
+    objective!("A-5.6", "ABC", "A", Evidence::Process("source_code"), "Source Code is accurate and consistent"),
+    objective!("A-5.7", "ABC", "", Evidence::Process("integration"), "Output of software integration process is complete and correct"),
+    objective!("A-5.8", "ABCD", "A", Evidence::Process("parameter_data"), "Parameter Data Item File is correct and complete"),
+    objective!("A-5.9", "ABCD", "", Evidence::Process("parameter_data"), "Verification of Parameter Data Item File is achieved"),
+    objective!("A-6.1", "ABCD", "", TESTED, "Executable Object Code complies with high-level requirements"),
+    objective!("A-6.2", "ABCD", "", TESTED, "Executable Object Code is robust with high-level requirements"),
+    objective!("A-6.3", "ABC", "AB", TESTED, "Executable Object Code complies with low-level requirements"),
+    objective!("A-6.4", "ABC", "A", TESTED, "Executable Object Code is robust with low-level requirements"),
+    objective!("A-6.5", "ABCD", "", Evidence::Process("target_computer"), "Executable Object Code is compatible with target computer"),
+    objective!("A-7.1", "ABC", "A", TESTED, "Test procedures are correct"),
+    objective!("A-7.2", "ABC", "A", TESTED, "Test results are correct and discrepancies explained"),
+    objective!("A-7.3", "ABCD", "A", VERIFIED, "Test coverage of high-level requirements is achieved"),
+    objective!("A-7.4", "ABC", "A", VERIFIED, "Test coverage of low-level requirements is achieved"),
+    objective!("A-7.5", "A", "A", Evidence::Process("structural_coverage"), "Test coverage of software structure (modified condition/decision coverage) is achieved"),
+    objective!("A-7.6", "AB", "AB", Evidence::Process("structural_coverage"), "Test coverage of software structure (decision coverage) is achieved"),
+    objective!("A-7.7", "ABC", "AB", Evidence::Process("structural_coverage"), "Test coverage of software structure (statement coverage) is achieved"),
+    objective!("A-7.8", "ABC", "AB", Evidence::Process("structural_coverage"), "Test coverage of software structure (data coupling and control coupling) is achieved"),
+    objective!("A-7.9", "A", "A", Evidence::Process("structural_coverage"), "Verification of additional code that cannot be traced to Source Code is achieved"),
+    objective!("A-8.1", "ABCD", "", Evidence::Process("configuration_management"), "Configuration items are identified"),
+    objective!("A-8.2", "ABC", "", Evidence::Traced, "Baselines and traceability are established"),
+    objective!("A-8.3", "ABCD", "", Evidence::Process("configuration_management"), "Problem reporting, change control, change review and configuration status accounting are established"),
+    objective!("A-8.4", "ABCD", "", Evidence::Process("configuration_management"), "Archive, retrieval and release are established"),
+    objective!("A-8.5", "ABCD", "", Evidence::Process("configuration_management"), "Software load control is established"),
+    objective!("A-8.6", "ABCD", "", Evidence::Process("configuration_management"), "Software life cycle environment control is established"),
+    objective!("A-9.1", "ABC", "ABC", Evidence::Process("quality_assurance"), "Assurance is obtained that software plans and standards are developed and reviewed for compliance and consistency"),
+    objective!("A-9.2", "ABCD", "ABCD", Evidence::Process("quality_assurance"), "Assurance is obtained that software life cycle processes comply with approved software plans"),
+    objective!("A-9.3", "ABC", "ABC", Evidence::Process("quality_assurance"), "Assurance is obtained that software life cycle processes comply with approved software standards"),
+    objective!("A-9.4", "ABC", "ABC", Evidence::Process("quality_assurance"), "Assurance is obtained that transition criteria for the software life cycle processes are satisfied"),
+    objective!("A-9.5", "ABCD", "ABCD", Evidence::Process("quality_assurance"), "Assurance is obtained that software conformity review is conducted"),
+    objective!("A-10.1", "ABCD", "", Evidence::Process("certification"), "Communication and understanding between the applicant and the certification authority is established"),
+    objective!("A-10.2", "ABCD", "", Evidence::Process("certification"), "The means of compliance is proposed and agreement with the Plan for Software Aspects of Certification is obtained"),
+    objective!("A-10.3", "ABCD", "", Evidence::Process("certification"), "Compliance substantiation is provided"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveState {
+    Satisfied,
+    Partial,
+    Missing,
+}
+
+impl std::fmt::Display for ObjectiveState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ObjectiveState::Satisfied => "Satisfied",
+            ObjectiveState::Partial => "Partial",
+            ObjectiveState::Missing => "Missing",
+        };
+        f.write_str(text)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjectiveStatus {
+    pub objective: &'static Objective,
+    /// The objective is satisfied with independence at the checked level.
+    pub independence: bool,
+    pub state: ObjectiveState,
+    /// What the state is based on, e.g. `3 of 4 requirements verified`.
+    pub evidence: String,
+}
+
+/// The Annex A objectives applying to `level`, each marked satisfied,
+/// partial or missing from what the model declares: requirements and their
+/// refinements, the logical architecture, trace links, test case methods,
+/// and process work products as requirements of categories such as
+/// `planning` or `configuration_management`.
+pub fn generate_objective_checklist(model: &SemanticModel, level: DO178Level) -> Vec<ObjectiveStatus> {
+    let gaps = model.coverage_gaps();
+    ANNEX_A.iter()
+        .filter(|objective| objective.applies_to(&level))
+        .map(|objective| {
+            let (state, evidence) = evaluate(model, &gaps, &objective.evidence);
+            ObjectiveStatus {
+                objective,
+                independence: objective.needs_independence(&level),
+                state,
+                evidence,
+            }
+        })
+        .collect()
+}
+
+fn evaluate(model: &SemanticModel, gaps: &[CoverageGap], evidence: &Evidence) -> (ObjectiveState, String) {
+    let gap_count = |gap_type: GapType| gaps.iter().filter(|g| g.gap_type == gap_type).count();
+    match evidence {
+        Evidence::Requirements => {
+            let count = model.requirements.len();
+            proportion(count, count, "requirements declared")
+        }
+        Evidence::Derived => {
+            let is_requirement = |id: &str| model.requirements.iter().any(|r| r.id == id);
+            let derived = model.requirements.iter()
+                .filter(|req| model.traces.iter().any(|t| {
+                    matches!(t.link_type, TraceLinkType::DerivedFrom | TraceLinkType::Refines)
+                        && t.from == req.id
+                        && is_requirement(&t.to)
+                }))
+                .count();
+            proportion(derived, derived, "requirements derived from or refining another")
+        }
+        Evidence::Architecture => {
+            let count = model.components.iter().filter(|c| c.level == "Logical").count();
+            proportion(count, count, "logical components declared")
+        }
+        Evidence::Traced => {
+            let total = model.requirements.len();
+            proportion(total - gap_count(GapType::NoTraceToDesign), total, "requirements satisfied by a component")
+        }
+        Evidence::Allocated => {
+            let total = model.components.iter().filter(|c| c.level == "Logical" || c.level == "Physical").count();
+            proportion(total - gap_count(GapType::NoRequirement), total, "components satisfying a requirement")
+        }
+        Evidence::Verified(methods) => {
+            let verified = model.requirements.iter()
+                .filter(|req| {
+                    let declared = req.is_declared_verified()
+                        && (methods.is_empty()
+                            || methods.iter().any(|m| req.verification_method.is_some_and(|method| method.as_str().eq_ignore_ascii_case(m))));
+                    declared || model.verifications.iter().any(|v| {
+                        v.requirement == req.id
+                            && (methods.is_empty() || methods.iter().any(|m| v.method.eq_ignore_ascii_case(m)))
+                    }) || (methods.is_empty()
+                        && model.traces.iter().any(|t| t.link_type == TraceLinkType::VerifiedBy && t.to == req.id))
+                })
+                .count();
+            let what = if methods.is_empty() {
+                "requirements verified".to_string()
+            } else {
+                format!("requirements verified by {}", methods.join(" or "))
+            };
+            proportion(verified, model.requirements.len(), &what)
+        }
+        Evidence::Process(category) => {
+            if has_process_evidence(model, category) {
+                (ObjectiveState::Satisfied, format!("{} requirements declared", category))
+            } else {
+                (ObjectiveState::Missing, format!("no {} requirement declared", category))
+            }
+        }
+    }
+}
+
+fn proportion(done: usize, total: usize, what: &str) -> (ObjectiveState, String) {
+    let state = if done == 0 {
+        ObjectiveState::Missing
+    } else if done == total {
+        ObjectiveState::Satisfied
+    } else {
+        ObjectiveState::Partial
+    };
+    let evidence = if done == total { format!("{} {}", done, what) } else { format!("{} of {} {}", done, total, what) };
+    (state, evidence)
+}
+
+/// The checklist as a Markdown table: one row per objective, a column per
+/// software level (● with independence, ○ without) and the status at `level`.
+pub fn export_checklist_to_markdown(title: &str, level: &DO178Level, checklist: &[ObjectiveStatus]) -> String {
+    let mut md = String::new();
+    let count = |state: ObjectiveState| checklist.iter().filter(|s| s.state == state).count();
+
+    md.push_str(&format!("# DO-178C Objectives: {}\n\n", title));
+    md.push_str(&format!(
+        "Software level {}: {} objectives, {} satisfied, {} partial, {} missing.\n\n",
+        level_letter(level),
+        checklist.len(),
+        count(ObjectiveState::Satisfied),
+        count(ObjectiveState::Partial),
+        count(ObjectiveState::Missing)
+    ));
+    md.push_str("● applies with independence, ○ applies.\n\n");
+    md.push_str("| Objective | Description | A | B | C | D | Status | Evidence |\n");
+    md.push_str("|-----------|-------------|---|---|---|---|--------|----------|\n");
+
+    for status in checklist {
+        let objective = status.objective;
+        let columns: Vec<&str> = [DO178Level::LevelA, DO178Level::LevelB, DO178Level::LevelC, DO178Level::LevelD]
+            .iter()
+            .map(|l| match (objective.applies_to(l), objective.needs_independence(l)) {
+                (true, true) => "●",
+                (true, false) => "○",
+                _ => "",
+            })
+            .collect();
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            objective.id,
+            md_cell(objective.description),
+            columns.join(" | "),
+            status.state,
+            md_cell(&status.evidence)
+        ));
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn model(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    const MODEL: &str = r#"
+model Demo {
+}
+
+requirements system {
+    req "HLR-001" "Climb" { description: "Command climb" safety_level: "DAL_B" }
+    req "HLR-002" "Descend" { description: "Command descent" }
+    req "PLAN-001" "PSAC" { description: "Plan for Software Aspects of Certification" category: "planning" }
+}
+
+architecture logical {
+    component "Autopilot" { id: "LC-001" }
+}
+
+trace "HLR-001" allocated_to "LC-001" {}
+test_case "TC-001" { verifies: ["HLR-001"] method: "test" }
+"#;
+
+    #[test]
+    fn objectives_follow_the_software_level() {
+        let model = model(MODEL);
+
+        assert_eq!(declared_level(&model), Some(DO178Level::LevelB));
+        let level_a = generate_objective_checklist(&model, DO178Level::LevelA);
+        let level_d = generate_objective_checklist(&model, DO178Level::LevelD);
+        assert_eq!(level_a.len(), ANNEX_A.len());
+        assert!(level_d.len() < level_a.len());
+        assert!(generate_objective_checklist(&model, DO178Level::LevelE).is_empty());
+
+        let a31 = |checklist: &[ObjectiveStatus]| checklist.iter().find(|s| s.objective.id == "A-3.1").unwrap().independence;
+        assert!(a31(&level_a));
+        assert!(!a31(&level_d));
+    }
+
+    #[test]
+    fn states_reflect_requirements_traces_and_methods() {
+        let model = model(MODEL);
+        let checklist = generate_objective_checklist(&model, DO178Level::LevelB);
+        let state = |id: &str| {
+            let status = checklist.iter().find(|s| s.objective.id == id).unwrap();
+            (status.state, status.evidence.as_str())
+        };
+
+        assert_eq!(state("A-1.1"), (ObjectiveState::Satisfied, "planning requirements declared"));
+        assert_eq!(state("A-2.1").0, ObjectiveState::Satisfied);
+        assert_eq!(state("A-3.6"), (ObjectiveState::Partial, "1 of 3 requirements satisfied by a component"));
+        assert_eq!(state("A-6.1"), (ObjectiveState::Partial, "1 of 3 requirements verified by test"));
+        assert_eq!(state("A-3.1").0, ObjectiveState::Missing);
+        assert_eq!(state("A-8.1").0, ObjectiveState::Missing);
+
+        let md = export_checklist_to_markdown("Demo", &DO178Level::LevelB, &checklist);
+        assert!(md.contains("Software level B:"), "{}", md);
+        assert!(md.contains("| A-3.1 | High-level requirements comply with system requirements | ● | ● | ○ | ○ | Missing |"), "{}", md);
+        assert!(!md.contains("| A-7.5 |"));
+    }
+}
@@ -0,0 +1,710 @@
+use super::*;
+use crate::compiler::semantic::{ComponentInfo, FmeaActionInfo, SemanticModel};
+use chrono::NaiveDate;
+
+pub struct FMEAGenerator {
+    config: FMEAConfig,
+}
+
+/// Walk every component and its allocated functions and build the FMEA
+/// worksheet, highest RPN first.
+pub fn generate_fmea(model: &SemanticModel, config: &FMEAConfig) -> Vec<FMEAEntry> {
+    FMEAGenerator::new(config.clone()).generate(model)
+}
+
+impl FMEAGenerator {
+    pub fn new(config: FMEAConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn generate(&self, model: &SemanticModel) -> Vec<FMEAEntry> {
+        let mut fmea_entries = Vec::new();
+
+        for component in &model.components {
+            let integrity_level = component
+                .asil
+                .as_deref()
+                .or(component.safety_level.as_deref())
+                .and_then(IntegrityLevel::parse);
+
+            if component.functions.is_empty() {
+                let failure_mode = FailureMode {
+                    mode: "No function allocated".to_string(),
+                    cause: "Component has no allocated function; its failure behaviour is unanalysed".to_string(),
+                };
+                let function = FunctionRef { name: "(none)".to_string(), has_inputs: false };
+                fmea_entries.push(self.create_fmea_entry(component, integrity_level.as_ref(), &function, failure_mode));
+            }
+
+            for name in &component.functions {
+                let function = model
+                    .functions
+                    .iter()
+                    .find(|f| &f.name == name || &f.id == name)
+                    .map(|f| FunctionRef { name: f.name.clone(), has_inputs: !f.inputs.is_empty() })
+                    .unwrap_or_else(|| FunctionRef { name: name.clone(), has_inputs: false });
+
+                for failure_mode in self.identify_failure_modes(&function) {
+                    fmea_entries.push(self.create_fmea_entry(component, integrity_level.as_ref(), &function, failure_mode));
+                }
+            }
+
+            for interface in self.component_interfaces(model, component) {
+                let function = FunctionRef { name: format!("interface {}", interface), has_inputs: true };
+                let failure_mode = FailureMode {
+                    mode: "Communication failure".to_string(),
+                    cause: "Network error, protocol mismatch".to_string(),
+                };
+                fmea_entries.push(self.create_fmea_entry(component, integrity_level.as_ref(), &function, failure_mode));
+            }
+        }
+
+        for (index, entry) in fmea_entries.iter_mut().enumerate() {
+            entry.id = format!("FMEA-{:03}", index + 1);
+        }
+        attach_actions(&mut fmea_entries, &model.fmea_actions);
+        fmea_entries.sort_by(|a, b| b.rpn.cmp(&a.rpn).then(b.severity.cmp(&a.severity)));
+
+        fmea_entries
+    }
+
+    /// Interface names declared on the component's ports or connecting it
+    /// to another element, without duplicates.
+    fn component_interfaces(&self, model: &SemanticModel, component: &ComponentInfo) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let ports = component.interfaces_in.iter().chain(&component.interfaces_out).map(|port| &port.name);
+        let connections = model
+            .interfaces
+            .iter()
+            .filter(|i| [&i.from, &i.to].iter().any(|end| **end == component.id || **end == component.name))
+            .map(|i| &i.name);
+        for name in ports.chain(connections) {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+
+    fn identify_failure_modes(&self, function: &FunctionRef) -> Vec<FailureMode> {
+        let mut failure_modes = vec![
+            FailureMode {
+                mode: "Loss of function".to_string(),
+                cause: "Software defect, incorrect logic".to_string(),
+            },
+            FailureMode {
+                mode: "Incorrect output".to_string(),
+                cause: "Calculation error, incorrect algorithm".to_string(),
+            },
+            FailureMode {
+                mode: "Delayed response".to_string(),
+                cause: "Performance issue, blocking operation".to_string(),
+            },
+            FailureMode {
+                mode: "Intermittent failure".to_string(),
+                cause: "Race condition, timing issue".to_string(),
+            },
+        ];
+
+        if function.has_inputs {
+            failure_modes.push(FailureMode {
+                mode: "Invalid input accepted".to_string(),
+                cause: "Out-of-range or stale input data".to_string(),
+            });
+        }
+
+        failure_modes
+    }
+
+    fn create_fmea_entry(
+        &self,
+        component: &ComponentInfo,
+        integrity_level: Option<&IntegrityLevel>,
+        function: &FunctionRef,
+        failure_mode: FailureMode,
+    ) -> FMEAEntry {
+        let severity = clamp_to_scale(
+            self.assess_severity(integrity_level, &failure_mode),
+            self.config.severity_scale.iter().map(|l| l.level),
+        );
+        let occurrence = clamp_to_scale(
+            self.assess_occurrence(&failure_mode),
+            self.config.occurrence_scale.iter().map(|l| l.level),
+        );
+        let detection = clamp_to_scale(
+            self.assess_detection(component, &failure_mode),
+            self.config.detection_scale.iter().map(|l| l.level),
+        );
+        let rpn = severity * occurrence * detection;
+
+        let current_controls = self.identify_current_controls(component, function);
+        let recommended_actions = if rpn > self.config.rpn_threshold {
+            self.generate_recommended_actions(severity, occurrence, detection, &failure_mode)
+        } else {
+            Vec::new()
+        };
+
+        FMEAEntry {
+            id: String::new(),
+            component: component.name.clone(),
+            function: function.name.clone(),
+            failure_mode: failure_mode.mode.clone(),
+            failure_cause: failure_mode.cause.clone(),
+            failure_effect_local: self.describe_local_effect(function, &failure_mode),
+            failure_effect_system: self.describe_system_effect(component, integrity_level),
+            severity,
+            occurrence,
+            detection,
+            rpn,
+            current_controls,
+            recommended_actions,
+            responsibility: "System Safety Engineer".to_string(),
+            target_completion: None,
+            revised_rpn: None,
+        }
+    }
+
+    fn assess_severity(&self, integrity_level: Option<&IntegrityLevel>, failure_mode: &FailureMode) -> u32 {
+        if integrity_level.is_some_and(is_highest_integrity) {
+            if failure_mode.mode.contains("Loss") || failure_mode.mode.contains("No function") {
+                return 10;
+            } else if failure_mode.mode.contains("Incorrect") {
+                return 9;
+            }
+            return 8;
+        }
+
+        if integrity_level.is_some_and(is_high_integrity) {
+            if failure_mode.mode.contains("Loss") || failure_mode.mode.contains("No function") {
+                return 8;
+            }
+            return 7;
+        }
+
+        if failure_mode.mode.contains("Loss") || failure_mode.mode.contains("Incorrect") {
+            return 6;
+        }
+
+        4
+    }
+
+    fn assess_occurrence(&self, failure_mode: &FailureMode) -> u32 {
+        if failure_mode.cause.contains("Race condition") || failure_mode.cause.contains("timing") {
+            return 6;
+        }
+
+        if failure_mode.cause.contains("defect") || failure_mode.cause.contains("error") {
+            return 5;
+        }
+
+        if failure_mode.cause.contains("Network") || failure_mode.cause.contains("input") {
+            return 4;
+        }
+
+        3
+    }
+
+    fn assess_detection(&self, component: &ComponentInfo, failure_mode: &FailureMode) -> u32 {
+        let mut detection_score: u32 = 8;
+
+        if component.functions.iter().any(|f| is_diagnostic_function(f)) {
+            detection_score -= 2;
+        }
+
+        if failure_mode.mode.contains("Intermittent") || failure_mode.mode.contains("No function") {
+            detection_score += 2;
+        }
+
+        detection_score.max(1)
+    }
+
+    fn identify_current_controls(&self, component: &ComponentInfo, function: &FunctionRef) -> Vec<String> {
+        let mut controls = Vec::new();
+
+        if function.has_inputs {
+            controls.push("Input range checking".to_string());
+        }
+
+        for name in component.functions.iter().filter(|f| is_diagnostic_function(f)) {
+            controls.push(format!("Diagnostic function '{}'", name));
+        }
+
+        controls
+    }
+
+    fn generate_recommended_actions(&self, severity: u32, occurrence: u32, detection: u32, failure_mode: &FailureMode) -> Vec<String> {
+        let mut actions = Vec::new();
+
+        if failure_mode.mode.contains("No function") {
+            actions.push("Allocate functions to the component or justify its absence".to_string());
+        }
+
+        if severity >= 8 {
+            actions.push("Implement redundant safety mechanism".to_string());
+            actions.push("Add fail-safe default behavior".to_string());
+        }
+
+        if occurrence >= 6 {
+            actions.push("Perform detailed code review".to_string());
+            actions.push("Add additional test cases for edge conditions".to_string());
+        }
+
+        if detection >= 7 {
+            actions.push("Implement runtime monitoring and diagnostics".to_string());
+            actions.push("Add built-in self-test (BIST)".to_string());
+        }
+
+        if failure_mode.mode.contains("Communication") {
+            actions.push("Implement CRC or checksum verification".to_string());
+            actions.push("Add timeout and retry mechanisms".to_string());
+        }
+
+        if failure_mode.mode.contains("input") {
+            actions.push("Add plausibility and range checks on inputs".to_string());
+        }
+
+        actions
+    }
+
+    fn describe_local_effect(&self, function: &FunctionRef, failure_mode: &FailureMode) -> String {
+        format!("Function '{}' experiences {}", function.name, failure_mode.mode.to_lowercase())
+    }
+
+    fn describe_system_effect(&self, component: &ComponentInfo, integrity_level: Option<&IntegrityLevel>) -> String {
+        if integrity_level.is_some_and(|level| is_highest_integrity(level) || is_high_integrity(level)) {
+            format!("System-level safety function compromised in component '{}'", component.name)
+        } else {
+            format!("Degraded functionality in component '{}'", component.name)
+        }
+    }
+}
+
+struct FailureMode {
+    mode: String,
+    cause: String,
+}
+
+/// A function as seen from the FMEA: the allocated name, and whether it
+/// consumes declared inputs.
+struct FunctionRef {
+    name: String,
+    has_inputs: bool,
+}
+
+fn is_highest_integrity(level: &IntegrityLevel) -> bool {
+    matches!(level, IntegrityLevel::ASIL_D | IntegrityLevel::DAL_A | IntegrityLevel::SIL_4)
+}
+
+fn is_high_integrity(level: &IntegrityLevel) -> bool {
+    matches!(level, IntegrityLevel::ASIL_C | IntegrityLevel::DAL_B | IntegrityLevel::SIL_3)
+}
+
+/// Monitoring/watchdog functions count as a detection control for their component.
+fn is_diagnostic_function(name: &str) -> bool {
+    let name = name.to_lowercase();
+    ["monitor", "diagnos", "watchdog", "self_test", "self-test"].iter().any(|k| name.contains(k))
+}
+
+/// Clamp a score into the range spanned by a configured scale (1..=10 when empty).
+fn clamp_to_scale(score: u32, levels: impl Iterator<Item = u32>) -> u32 {
+    let (min, max) = levels.fold((u32::MAX, 0), |(min, max), level| (min.min(level), max.max(level)));
+    if min > max {
+        score.clamp(1, 10)
+    } else {
+        score.clamp(min, max)
+    }
+}
+
+/// Attach each entry's `fmea_action`s: their owners become the
+/// responsibility, the earliest due date of an open action the target
+/// completion, and the best occurrence and detection they declare give the
+/// revised RPN.
+fn attach_actions(entries: &mut [FMEAEntry], actions: &[FmeaActionInfo]) {
+    for entry in entries.iter_mut() {
+        let recorded: Vec<&FmeaActionInfo> = actions.iter().filter(|a| a.entry == entry.id).collect();
+        if recorded.is_empty() {
+            continue;
+        }
+
+        let mut owners: Vec<&str> = Vec::new();
+        for owner in recorded.iter().filter_map(|a| a.owner.as_deref()) {
+            if !owners.contains(&owner) {
+                owners.push(owner);
+            }
+        }
+        if !owners.is_empty() {
+            entry.responsibility = owners.join(", ");
+        }
+        entry.target_completion = recorded
+            .iter()
+            .filter(|a| !a.closed)
+            .filter_map(|a| a.due)
+            .min()
+            .and_then(|due| due.and_hms_opt(0, 0, 0))
+            .map(|due| due.and_utc());
+        for action in recorded.iter().filter(|a| !a.action.is_empty()) {
+            entry.recommended_actions.push(action.action.clone());
+        }
+
+        let occurrence = recorded.iter().filter_map(|a| a.occurrence).min();
+        let detection = recorded.iter().filter_map(|a| a.detection).min();
+        if occurrence.is_some() || detection.is_some() {
+            entry.revised_rpn = Some(
+                entry.severity * occurrence.unwrap_or(entry.occurrence) * detection.unwrap_or(entry.detection),
+            );
+        }
+    }
+}
+
+/// Actions still open after their due date, as of `today`.
+pub fn overdue_actions(model: &SemanticModel, today: NaiveDate) -> Vec<&FmeaActionInfo> {
+    model
+        .fmea_actions
+        .iter()
+        .filter(|a| !a.closed && a.due.is_some_and(|due| due < today))
+        .collect()
+}
+
+fn csv_field(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+pub(crate) fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+pub fn export_fmea_to_csv(entries: &[FMEAEntry]) -> String {
+    let mut csv = String::new();
+
+    csv.push_str("ID,Component,Function,Failure Mode,Failure Cause,Local Effect,System Effect,Severity,Occurrence,Detection,RPN,Current Controls,Recommended Actions,Responsibility,Target Completion,Revised RPN\n");
+
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&entry.id),
+            csv_field(&entry.component),
+            csv_field(&entry.function),
+            csv_field(&entry.failure_mode),
+            csv_field(&entry.failure_cause),
+            csv_field(&entry.failure_effect_local),
+            csv_field(&entry.failure_effect_system),
+            entry.severity,
+            entry.occurrence,
+            entry.detection,
+            entry.rpn,
+            csv_field(&entry.current_controls.join("; ")),
+            csv_field(&entry.recommended_actions.join("; ")),
+            csv_field(&entry.responsibility),
+            entry.target_completion.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            entry.revised_rpn.map(|rpn| rpn.to_string()).unwrap_or_default()
+        ));
+    }
+
+    csv
+}
+
+pub fn export_fmea_to_markdown(title: &str, entries: &[FMEAEntry], rpn_threshold: u32) -> String {
+    let mut md = String::new();
+
+    md.push_str(&format!("# FMEA Worksheet: {}\n\n", title));
+    md.push_str(&format!(
+        "{} failure modes, {} above the RPN threshold of {}.\n\n",
+        entries.len(),
+        entries.iter().filter(|e| e.rpn > rpn_threshold).count(),
+        rpn_threshold
+    ));
+    md.push_str("| ID | Component | Function | Failure Mode | Cause | System Effect | S | O | D | RPN | Recommended Actions | Owner / Due | Revised RPN |\n");
+    md.push_str("|----|-----------|----------|--------------|-------|---------------|---|---|---|-----|---------------------|-------------|-------------|\n");
+
+    for entry in entries {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            md_cell(&entry.id),
+            md_cell(&entry.component),
+            md_cell(&entry.function),
+            md_cell(&entry.failure_mode),
+            md_cell(&entry.failure_cause),
+            md_cell(&entry.failure_effect_system),
+            entry.severity,
+            entry.occurrence,
+            entry.detection,
+            entry.rpn,
+            if entry.recommended_actions.is_empty() { "-".to_string() } else { md_cell(&entry.recommended_actions.join("; ")) },
+            match entry.target_completion {
+                Some(due) => format!("{} / {}", md_cell(&entry.responsibility), due.format("%Y-%m-%d")),
+                None => "-".to_string(),
+            },
+            entry.revised_rpn.map_or("-".to_string(), |rpn| rpn.to_string())
+        ));
+    }
+
+    md
+}
+
+/// Share of the total RPN the "vital few" failure modes account for.
+pub const PARETO_CUTOFF: f64 = 80.0;
+
+/// FMEA entries ranked by risk for prioritization (see [`pareto`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParetoReport {
+    pub total_rpn: u32,
+    /// Highest RPN first, ties broken by severity.
+    pub items: Vec<ParetoItem>,
+    /// How many of the leading `items` reach [`PARETO_CUTOFF`] percent of
+    /// the total RPN.
+    pub vital_few: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParetoItem {
+    pub id: String,
+    pub component: String,
+    pub failure_mode: String,
+    pub severity: u32,
+    pub rpn: u32,
+    /// Percent of the total RPN.
+    pub share: f64,
+    /// Percent of the total RPN covered by this item and those above it.
+    pub cumulative: f64,
+}
+
+impl ParetoReport {
+    /// The failure modes to act on first.
+    pub fn vital(&self) -> &[ParetoItem] {
+        &self.items[..self.vital_few]
+    }
+}
+
+/// Rank `entries` by RPN, highest first; equal RPNs rank the more severe
+/// failure mode first, as the worksheet review would. The vital few are the
+/// fewest leading entries making up 80% of the total RPN.
+pub fn pareto(entries: &[FMEAEntry]) -> ParetoReport {
+    let mut ranked: Vec<&FMEAEntry> = entries.iter().collect();
+    ranked.sort_by(|a, b| b.rpn.cmp(&a.rpn).then(b.severity.cmp(&a.severity)).then(a.id.cmp(&b.id)));
+
+    let total_rpn: u32 = ranked.iter().map(|e| e.rpn).sum();
+    let percent = |rpn: u32| if total_rpn == 0 { 0.0 } else { rpn as f64 * 100.0 / total_rpn as f64 };
+
+    let mut running = 0;
+    let items: Vec<ParetoItem> = ranked
+        .into_iter()
+        .map(|entry| {
+            running += entry.rpn;
+            ParetoItem {
+                id: entry.id.clone(),
+                component: entry.component.clone(),
+                failure_mode: entry.failure_mode.clone(),
+                severity: entry.severity,
+                rpn: entry.rpn,
+                share: percent(entry.rpn),
+                cumulative: percent(running),
+            }
+        })
+        .collect();
+    let vital_few = if total_rpn == 0 {
+        0
+    } else {
+        items.iter().position(|i| i.cumulative >= PARETO_CUTOFF).map_or(items.len(), |i| i + 1)
+    };
+
+    ParetoReport { total_rpn, items, vital_few }
+}
+
+pub fn export_pareto_to_markdown(report: &ParetoReport) -> String {
+    let mut md = String::new();
+
+    md.push_str("## Pareto Analysis\n\n");
+    md.push_str(&format!(
+        "{} of {} failure modes account for {:.0}% of the total RPN of {}.\n\n",
+        report.vital_few,
+        report.items.len(),
+        report.vital().last().map_or(0.0, |i| i.cumulative),
+        report.total_rpn
+    ));
+    md.push_str("| Rank | ID | Component | Failure Mode | S | RPN | Share | Cumulative |\n");
+    md.push_str("|------|----|-----------|--------------|---|-----|-------|------------|\n");
+
+    for (i, item) in report.items.iter().enumerate() {
+        md.push_str(&format!(
+            "| {}{} | {} | {} | {} | {} | {} | {:.1}% | {:.1}% |\n",
+            i + 1,
+            if i < report.vital_few { " ★" } else { "" },
+            md_cell(&item.id),
+            md_cell(&item.component),
+            md_cell(&item.failure_mode),
+            item.severity,
+            item.rpn,
+            item.share,
+            item.cumulative
+        ));
+    }
+
+    md
+}
+
+pub fn generate_fmea_summary(entries: &[FMEAEntry]) -> String {
+    let mut summary = String::new();
+    
+    summary.push_str("FMEA Summary Report\n");
+    summary.push_str("===================\n\n");
+    
+    summary.push_str(&format!("Total Failure Modes Analyzed: {}\n", entries.len()));
+    
+    let high_risk = entries.iter().filter(|e| e.rpn >= 200).count();
+    let medium_risk = entries.iter().filter(|e| e.rpn >= 100 && e.rpn < 200).count();
+    let low_risk = entries.iter().filter(|e| e.rpn < 100).count();
+    
+    summary.push_str(&format!("High Risk (RPN >= 200): {}\n", high_risk));
+    summary.push_str(&format!("Medium Risk (RPN 100-199): {}\n", medium_risk));
+    summary.push_str(&format!("Low Risk (RPN < 100): {}\n\n", low_risk));
+    
+    if high_risk > 0 {
+        summary.push_str("Top 5 High-Risk Failure Modes:\n");
+        summary.push_str("-------------------------------\n");
+        for (i, entry) in entries.iter().take(5).enumerate() {
+            summary.push_str(&format!("{}. {} - {} (RPN: {})\n", 
+                i + 1, 
+                entry.component, 
+                entry.failure_mode, 
+                entry.rpn
+            ));
+        }
+    }
+    
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn model(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    const MODEL: &str = r#"
+model Demo {
+}
+
+architecture logical {
+  component "Controller" { id: "LC-001" asil: "ASIL_D" function "compute" }
+  component "Logger" { id: "LC-002" function "record" }
+  component "Spare" { id: "LC-003" }
+}
+"#;
+
+    #[test]
+    fn rpn_is_product_and_threshold_flags_actions() {
+        let config = FMEAConfig::default();
+        let entries = generate_fmea(&model(MODEL), &config);
+
+        assert!(entries.windows(2).all(|w| w[0].rpn >= w[1].rpn));
+        for entry in &entries {
+            assert_eq!(entry.rpn, entry.severity * entry.occurrence * entry.detection);
+            assert_eq!(entry.rpn > config.rpn_threshold, !entry.recommended_actions.is_empty(), "{}", entry.id);
+        }
+
+        let loss = entries
+            .iter()
+            .find(|e| e.component == "Controller" && e.failure_mode == "Loss of function")
+            .unwrap();
+        assert_eq!(loss.severity, 10);
+    }
+
+    #[test]
+    fn component_without_functions_emits_gap_entry() {
+        let entries = generate_fmea(&model(MODEL), &FMEAConfig::default());
+        let gap: Vec<_> = entries.iter().filter(|e| e.component == "Spare").collect();
+
+        assert_eq!(gap.len(), 1);
+        assert_eq!(gap[0].failure_mode, "No function allocated");
+    }
+
+    #[test]
+    fn scores_are_clamped_to_config_scales() {
+        let mut config = FMEAConfig::default();
+        config.severity_scale.truncate(5);
+        config.rpn_threshold = 1000;
+        let entries = generate_fmea(&model(MODEL), &config);
+
+        assert!(entries.iter().all(|e| e.severity <= 5));
+        assert!(entries.iter().all(|e| e.recommended_actions.is_empty()));
+    }
+
+    #[test]
+    fn pareto_ranks_by_rpn_then_severity_and_finds_the_vital_few() {
+        let entry = |id: &str, severity: u32, rpn: u32| FMEAEntry {
+            id: id.to_string(),
+            component: "C".to_string(),
+            function: "f".to_string(),
+            failure_mode: "Loss of function".to_string(),
+            failure_cause: String::new(),
+            failure_effect_local: String::new(),
+            failure_effect_system: String::new(),
+            severity,
+            occurrence: 1,
+            detection: 1,
+            rpn,
+            current_controls: Vec::new(),
+            recommended_actions: Vec::new(),
+            responsibility: String::new(),
+            target_completion: None,
+            revised_rpn: None,
+        };
+        let entries = vec![
+            entry("F-1", 2, 10),
+            entry("F-2", 5, 400),
+            entry("F-3", 9, 400),
+            entry("F-4", 3, 100),
+            entry("F-5", 4, 90),
+        ];
+
+        let report = pareto(&entries);
+
+        let order: Vec<&str> = report.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(order, vec!["F-3", "F-2", "F-4", "F-5", "F-1"]);
+        assert_eq!(report.total_rpn, 1000);
+        assert_eq!(report.items[1].cumulative, 80.0);
+        assert_eq!(report.vital_few, 2);
+        assert_eq!(report.items.last().unwrap().cumulative, 100.0);
+        assert!(export_pareto_to_markdown(&report).contains("| 2 ★ | F-2 | C | Loss of function | 5 | 400 | 40.0% | 80.0% |"));
+        assert_eq!(pareto(&[]).vital_few, 0);
+    }
+
+    #[test]
+    fn recorded_actions_revise_the_rpn_and_overdue_ones_are_gaps() {
+        let source = format!(
+            "{}\nsafety_analysis {{\n  {}\n  {}\n}}\n",
+            MODEL,
+            r#"fmea_action { for: "FMEA-001" owner: "J. Doe" due: "2020-01-01" action: "Add a watchdog" detection: 2 }"#,
+            r#"fmea_action { for: "FMEA-002" owner: "A. Lee" due: "2020-01-01" action: "Review" status: "closed" }"#
+        );
+        let model = model(&source);
+        let entries = generate_fmea(&model, &FMEAConfig::default());
+
+        let entry = entries.iter().find(|e| e.id == "FMEA-001").unwrap();
+        assert_eq!(entry.revised_rpn, Some(entry.severity * entry.occurrence * 2));
+        assert_eq!(entry.responsibility, "J. Doe");
+        assert_eq!(entry.target_completion.unwrap().format("%Y-%m-%d").to_string(), "2020-01-01");
+        assert!(entry.recommended_actions.contains(&"Add a watchdog".to_string()));
+        let closed = entries.iter().find(|e| e.id == "FMEA-002").unwrap();
+        assert_eq!((closed.revised_rpn, closed.target_completion), (None, None));
+
+        let overdue = overdue_actions(&model, NaiveDate::from_ymd_opt(2020, 1, 2).unwrap());
+        assert_eq!(overdue.iter().map(|a| a.entry.as_str()).collect::<Vec<_>>(), vec!["FMEA-001"]);
+        assert!(overdue_actions(&model, NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).is_empty());
+
+        let config = SafetyConfig::new(SafetyStandard::ISO26262 { edition: "2018".to_string() }, IntegrityLevel::ASIL_D);
+        let result = SafetyAnalyzer::new(config).analyze(&model).unwrap();
+        let gaps: Vec<_> = result.gaps.iter().filter(|g| matches!(g.gap_type, SafetyGapType::OverdueAction)).collect();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].affected_elements, vec!["FMEA-001".to_string()]);
+
+        let bad = MODEL.to_string() + "\nsafety_analysis { fmea_action { for: \"FMEA-001\" due: \"June\" } }\n";
+        assert!(Compiler::new(CompilerConfig::default()).compile_string(&bad).is_err());
+    }
+}
@@ -0,0 +1,454 @@
+use super::*;
+use crate::compiler::ast::{self, AttributeValue};
+use std::collections::{BTreeSet, HashMap};
+
+pub struct FTAGenerator {
+    config: FTAConfig,
+}
+
+impl FTAGenerator {
+    pub fn new(config: FTAConfig) -> Self {
+        Self { config }
+    }
+
+    /// Build the fault tree of a declared hazard: the hazard is the top
+    /// event and each of its `causes` a basic event, combined through the
+    /// hazard's `gate` (`OR` by default, `AND`, or `k-of-n` as e.g. `2oo3`).
+    pub fn generate(&self, hazard: &ast::Hazard) -> FaultTree {
+        let hazard_id = hazard
+            .attributes
+            .get("id")
+            .and_then(AttributeValue::as_string)
+            .unwrap_or(&hazard.name)
+            .to_string();
+        let description = hazard
+            .attributes
+            .get("description")
+            .and_then(AttributeValue::as_string)
+            .unwrap_or(&hazard.name)
+            .to_string();
+
+        let top_event = FaultEvent {
+            id: format!("TE-{}", hazard_id),
+            description,
+            event_type: FaultEventType::TopEvent,
+            probability: None,
+        };
+
+        let integrity_level = ["integrity_level", "asil_level", "asil", "dal", "sil", "safety_level"]
+            .iter()
+            .filter_map(|key| hazard.attributes.get(*key).and_then(AttributeValue::as_string))
+            .find_map(IntegrityLevel::parse)
+            .unwrap_or(IntegrityLevel::ASIL_QM);
+        let has_diagnostics = ["mitigations", "mitigation"]
+            .iter()
+            .any(|key| hazard.attributes.get(*key).is_some_and(|m| !matches!(m, AttributeValue::List(l) if l.is_empty())));
+        let exposure_time = hazard
+            .attributes
+            .get("exposure_time")
+            .and_then(AttributeValue::as_number)
+            .unwrap_or(1.0);
+
+        let mut causes: Vec<String> = match hazard.attributes.get("causes") {
+            Some(AttributeValue::List(items)) => items.iter().filter_map(|c| c.as_string().map(str::to_string)).collect(),
+            Some(AttributeValue::String(cause)) => vec![cause.clone()],
+            _ => Vec::new(),
+        };
+        if causes.is_empty() {
+            causes.push(format!("Undeveloped cause of {}", hazard.name));
+        }
+
+        let mut basic_events: Vec<BasicEvent> = causes
+            .iter()
+            .enumerate()
+            .map(|(i, cause)| BasicEvent {
+                id: format!("BE-{}-{}", hazard_id, i + 1),
+                description: cause.clone(),
+                failure_rate: self.estimate_failure_rate(&integrity_level),
+                exposure_time,
+                probability: 0.0,
+                diagnostic_coverage: self.estimate_diagnostic_coverage(&integrity_level, has_diagnostics),
+            })
+            .collect();
+        self.calculate_probabilities(&mut basic_events);
+
+        let gate_type = hazard
+            .attributes
+            .get("gate")
+            .and_then(AttributeValue::as_string)
+            .and_then(|g| parse_gate_type(g, basic_events.len() as u32))
+            .unwrap_or(GateType::OR);
+        let gates = vec![FaultGate {
+            id: format!("G-{}", hazard_id),
+            gate_type,
+            input_events: basic_events.iter().map(|be| be.id.clone()).collect(),
+            output_event: top_event.id.clone(),
+        }];
+
+        let mut tree = FaultTree {
+            id: format!("FTA-{}", hazard_id),
+            top_event,
+            gates,
+            basic_events,
+            minimal_cut_sets: Vec::new(),
+            top_event_probability: 0.0,
+        };
+
+        let order_limit = self.config.cut_set_order.max(1);
+        tree.minimal_cut_sets = compute_minimal_cut_sets(&tree)
+            .into_iter()
+            .filter(|cs| cs.order <= order_limit && cs.probability >= self.config.probability_threshold)
+            .collect();
+        tree.top_event_probability = top_event_probability(&tree.minimal_cut_sets);
+
+        tree
+    }
+
+    fn estimate_failure_rate(&self, integrity_level: &IntegrityLevel) -> f64 {
+        match integrity_level {
+            IntegrityLevel::ASIL_D | IntegrityLevel::DAL_A | IntegrityLevel::SIL_4 => 1e-9,
+            IntegrityLevel::ASIL_C | IntegrityLevel::DAL_B | IntegrityLevel::SIL_3 => 1e-8,
+            IntegrityLevel::ASIL_B | IntegrityLevel::DAL_C | IntegrityLevel::SIL_2 => 1e-7,
+            IntegrityLevel::ASIL_A | IntegrityLevel::DAL_D | IntegrityLevel::SIL_1 => 1e-6,
+            _ => 1e-5,
+        }
+    }
+
+    fn estimate_diagnostic_coverage(&self, integrity_level: &IntegrityLevel, has_diagnostics: bool) -> f64 {
+        if has_diagnostics {
+            match integrity_level {
+                IntegrityLevel::ASIL_D => 0.99,
+                IntegrityLevel::ASIL_C => 0.97,
+                IntegrityLevel::ASIL_B => 0.90,
+                _ => 0.60,
+            }
+        } else {
+            0.0
+        }
+    }
+
+    fn calculate_probabilities(&self, basic_events: &mut [BasicEvent]) {
+        for be in basic_events.iter_mut() {
+            let lambda = be.failure_rate;
+            let t = be.exposure_time;
+            let dc = be.diagnostic_coverage;
+
+            let residual_failure_rate = lambda * (1.0 - dc);
+
+            be.probability = 1.0 - (-residual_failure_rate * t).exp();
+        }
+    }
+}
+
+/// `OR`, `AND`, or a `k-of-n` vote written as `2oo3` (n defaults to the
+/// number of inputs when written as just `2oo`).
+fn parse_gate_type(text: &str, inputs: u32) -> Option<GateType> {
+    let text = text.trim().to_uppercase();
+    match text.as_str() {
+        "OR" => Some(GateType::OR),
+        "AND" => Some(GateType::AND),
+        _ => {
+            let (k, n) = text.split_once("OO")?;
+            let k = k.parse().ok()?;
+            let n = if n.is_empty() { inputs } else { n.parse().ok()? };
+            Some(GateType::VOTE { k, n })
+        }
+    }
+}
+
+/// Minimal cut sets of a fault tree by MOCUS top-down expansion.
+///
+/// Starting from the top event, every gate output in a row is replaced by
+/// its inputs: an OR gate splits the row into one row per input, an AND
+/// gate puts all inputs into the same row, and a `VOTE { k, n }` gate
+/// becomes one row per k-combination of its inputs. Non-coherent gates are
+/// approximated (XOR/POR as OR, PAND as AND) and NOT inputs are dropped.
+/// Supersets are then removed, leaving the minimal cut sets sorted by
+/// probability, highest first.
+pub fn compute_minimal_cut_sets(tree: &FaultTree) -> Vec<CutSet> {
+    let gates: HashMap<&str, &FaultGate> = tree.gates.iter().map(|g| (g.output_event.as_str(), g)).collect();
+    let probabilities: HashMap<&str, f64> = tree
+        .basic_events
+        .iter()
+        .map(|be| (be.id.as_str(), be.probability))
+        .collect();
+
+    let mut pending: Vec<BTreeSet<String>> = vec![BTreeSet::from([tree.top_event.id.clone()])];
+    let mut expanded: Vec<BTreeSet<String>> = Vec::new();
+
+    while let Some(row) = pending.pop() {
+        let Some((event, gate)) = row.iter().find_map(|e| gates.get(e.as_str()).map(|g| (e.clone(), *g))) else {
+            expanded.push(row);
+            continue;
+        };
+        let mut rest = row;
+        rest.remove(&event);
+
+        let inputs = &gate.input_events;
+        let alternatives: Vec<Vec<&String>> = match gate.gate_type {
+            GateType::OR | GateType::XOR | GateType::POR => inputs.iter().map(|i| vec![i]).collect(),
+            GateType::AND | GateType::PAND => vec![inputs.iter().collect()],
+            GateType::VOTE { k, .. } => combinations(inputs, k as usize),
+            GateType::NOT => vec![Vec::new()],
+        };
+        for alternative in alternatives {
+            let mut next = rest.clone();
+            next.extend(alternative.into_iter().cloned());
+            pending.push(next);
+        }
+    }
+
+    expanded.sort_by_key(|row| row.len());
+    let mut minimal: Vec<BTreeSet<String>> = Vec::new();
+    for row in expanded {
+        if !row.is_empty() && !minimal.iter().any(|m| m.is_subset(&row)) {
+            minimal.push(row);
+        }
+    }
+
+    let mut cut_sets: Vec<CutSet> = minimal
+        .into_iter()
+        .map(|events| CutSet {
+            probability: events.iter().map(|e| probabilities.get(e.as_str()).copied().unwrap_or(0.0)).product(),
+            order: events.len() as u32,
+            events: events.into_iter().collect(),
+        })
+        .collect();
+    cut_sets.sort_by(|a, b| {
+        b.probability
+            .total_cmp(&a.probability)
+            .then(a.order.cmp(&b.order))
+            .then_with(|| a.events.cmp(&b.events))
+    });
+
+    cut_sets
+}
+
+/// Top-event probability by the rare-event approximation: the sum of the
+/// minimal cut set probabilities, capped at 1.
+pub fn top_event_probability(cut_sets: &[CutSet]) -> f64 {
+    cut_sets.iter().map(|cs| cs.probability).sum::<f64>().min(1.0)
+}
+
+fn combinations(items: &[String], k: usize) -> Vec<Vec<&String>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    if k > items.len() {
+        return Vec::new();
+    }
+    let mut result = Vec::new();
+    for (i, first) in items.iter().enumerate() {
+        for mut tail in combinations(&items[i + 1..], k - 1) {
+            tail.insert(0, first);
+            result.push(tail);
+        }
+    }
+    result
+}
+
+pub fn export_fta_to_graphviz(fault_tree: &FaultTree) -> String {
+    let mut dot = String::new();
+    
+    dot.push_str("digraph FaultTree {\n");
+    dot.push_str("    rankdir=TB;\n");
+    dot.push_str("    node [shape=box];\n\n");
+    
+    dot.push_str(&format!("    \"{}\" [label=\"{}\\nP={:.2e}\", shape=ellipse, style=filled, fillcolor=red];\n",
+        fault_tree.top_event.id,
+        fault_tree.top_event.description,
+        fault_tree.top_event_probability
+    ));
+    
+    for gate in &fault_tree.gates {
+        let gate_shape = match gate.gate_type {
+            GateType::AND => "invtrapezium",
+            GateType::OR => "trapezium",
+            _ => "diamond",
+        };
+        
+        dot.push_str(&format!("    \"{}\" [label=\"{:?}\", shape={}];\n",
+            gate.id,
+            gate.gate_type,
+            gate_shape
+        ));
+        
+        dot.push_str(&format!("    \"{}\" -> \"{}\";\n", gate.id, gate.output_event));
+        
+        for input in &gate.input_events {
+            dot.push_str(&format!("    \"{}\" -> \"{}\";\n", input, gate.id));
+        }
+    }
+    
+    for be in &fault_tree.basic_events {
+        dot.push_str(&format!("    \"{}\" [label=\"{}\\nλ={:.2e}\\nP={:.2e}\", shape=circle, style=filled, fillcolor=lightblue];\n",
+            be.id,
+            be.description,
+            be.failure_rate,
+            be.probability
+        ));
+    }
+    
+    dot.push_str("}\n");
+    
+    dot
+}
+
+pub fn generate_fta_report(fault_tree: &FaultTree) -> String {
+    let mut report = String::new();
+    
+    report.push_str("Fault Tree Analysis Report\n");
+    report.push_str("==========================\n\n");
+    
+    report.push_str(&format!("Top Event: {}\n", fault_tree.top_event.description));
+    report.push_str(&format!("Top Event Probability: {:.2e}\n\n", fault_tree.top_event_probability));
+    
+    report.push_str("Minimal Cut Sets:\n");
+    report.push_str("-----------------\n");
+    
+    for (i, cut_set) in fault_tree.minimal_cut_sets.iter().take(10).enumerate() {
+        report.push_str(&format!("{}. Order {}: {} (P={:.2e})\n",
+            i + 1,
+            cut_set.order,
+            cut_set.events.join(" AND "),
+            cut_set.probability
+        ));
+    }
+    
+    report.push('\n');
+    
+    report.push_str("Basic Events:\n");
+    report.push_str("-------------\n");
+    
+    for be in &fault_tree.basic_events {
+        report.push_str(&format!("{}: {} (λ={:.2e}, DC={:.1}%, P={:.2e})\n",
+            be.id,
+            be.description,
+            be.failure_rate,
+            be.diagnostic_coverage * 100.0,
+            be.probability
+        ));
+    }
+    
+    report
+}
+
+pub fn compute_importance_measures(fault_tree: &FaultTree) -> HashMap<String, ImportanceMeasures> {
+    let mut importance = HashMap::new();
+    
+    for be in &fault_tree.basic_events {
+        let q_top = fault_tree.top_event_probability;
+        
+        let fussell_vesely = be.probability / q_top;
+        
+        let birnbaum = fault_tree.minimal_cut_sets.iter()
+            .filter(|cs| cs.events.contains(&be.id))
+            .map(|cs| cs.probability / be.probability)
+            .sum::<f64>();
+        
+        let risk_achievement_worth = q_top / (q_top - fussell_vesely * q_top);
+        
+        let risk_reduction_worth = q_top / (q_top - be.probability);
+        
+        importance.insert(be.id.clone(), ImportanceMeasures {
+            fussell_vesely,
+            birnbaum,
+            risk_achievement_worth,
+            risk_reduction_worth,
+        });
+    }
+    
+    importance
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportanceMeasures {
+    pub fussell_vesely: f64,
+    pub birnbaum: f64,
+    pub risk_achievement_worth: f64,
+    pub risk_reduction_worth: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basic(id: &str, probability: f64) -> BasicEvent {
+        BasicEvent {
+            id: id.to_string(),
+            description: id.to_string(),
+            failure_rate: 0.0,
+            exposure_time: 1.0,
+            probability,
+            diagnostic_coverage: 0.0,
+        }
+    }
+
+    fn gate(output: &str, gate_type: GateType, inputs: &[&str]) -> FaultGate {
+        FaultGate {
+            id: format!("G-{}", output),
+            gate_type,
+            input_events: inputs.iter().map(|i| i.to_string()).collect(),
+            output_event: output.to_string(),
+        }
+    }
+
+    fn tree(gates: Vec<FaultGate>, basic_events: Vec<BasicEvent>) -> FaultTree {
+        FaultTree {
+            id: "FTA-T".to_string(),
+            top_event: FaultEvent {
+                id: "TOP".to_string(),
+                description: "top".to_string(),
+                event_type: FaultEventType::TopEvent,
+                probability: None,
+            },
+            gates,
+            basic_events,
+            minimal_cut_sets: Vec::new(),
+            top_event_probability: 0.0,
+        }
+    }
+
+    fn events(cut_sets: &[CutSet]) -> Vec<Vec<&str>> {
+        cut_sets.iter().map(|cs| cs.events.iter().map(String::as_str).collect()).collect()
+    }
+
+    #[test]
+    fn mocus_expands_and_or_and_drops_supersets() {
+        // TOP = A OR (A AND B) OR (B AND C)
+        let tree = tree(
+            vec![
+                gate("TOP", GateType::OR, &["A", "IE1", "IE2"]),
+                gate("IE1", GateType::AND, &["A", "B"]),
+                gate("IE2", GateType::AND, &["B", "C"]),
+            ],
+            vec![basic("A", 1e-3), basic("B", 1e-2), basic("C", 1e-2)],
+        );
+        let cut_sets = compute_minimal_cut_sets(&tree);
+
+        assert_eq!(events(&cut_sets), vec![vec!["A"], vec!["B", "C"]]);
+        assert_eq!(cut_sets[1].order, 2);
+        assert!((cut_sets[1].probability - 1e-4).abs() < 1e-12);
+        assert!((top_event_probability(&cut_sets) - 1.1e-3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn vote_gate_expands_to_k_combinations() {
+        let tree = tree(
+            vec![gate("TOP", GateType::VOTE { k: 2, n: 3 }, &["A", "B", "C"])],
+            vec![basic("A", 0.1), basic("B", 0.2), basic("C", 0.3)],
+        );
+        let cut_sets = compute_minimal_cut_sets(&tree);
+
+        assert_eq!(events(&cut_sets), vec![vec!["B", "C"], vec!["A", "C"], vec!["A", "B"]]);
+        assert!(cut_sets.windows(2).all(|w| w[0].probability >= w[1].probability));
+    }
+
+    #[test]
+    fn gate_attribute_parses_votes() {
+        assert!(matches!(parse_gate_type("2oo3", 4), Some(GateType::VOTE { k: 2, n: 3 })));
+        assert!(matches!(parse_gate_type("2oo", 4), Some(GateType::VOTE { k: 2, n: 4 })));
+        assert!(matches!(parse_gate_type("and", 4), Some(GateType::AND)));
+        assert!(parse_gate_type("maybe", 4).is_none());
+    }
+}
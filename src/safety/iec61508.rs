@@ -1,4 +1,5 @@
 use super::*;
+use crate::compiler::ast::Hazard;
 use crate::compiler::semantic::SemanticModel;
 
 pub fn check_compliance(model: &SemanticModel, config: &SafetyConfig) -> ComplianceStatus {
@@ -14,6 +15,7 @@ pub fn check_compliance(model: &SemanticModel, config: &SafetyConfig) -> Complia
     check_part3_software_requirements(model, sil, &mut non_compliances, &mut total_checks, &mut passed_checks);
     check_part4_definitions(model, sil, &mut non_compliances, &mut total_checks, &mut passed_checks);
     check_part7_techniques(model, sil, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    check_safety_goals(model, config, &mut non_compliances, &mut total_checks, &mut passed_checks);
     
     if !non_compliances.is_empty() {
         recommendations.push(format!("Complete all IEC 61508 requirements for SIL {:?}", sil));
@@ -38,7 +40,7 @@ pub fn check_compliance(model: &SemanticModel, config: &SafetyConfig) -> Complia
 }
 
 #[derive(Debug, Clone, Copy)]
-enum SIL {
+pub enum SIL {
     SIL1,
     SIL2,
     SIL3,
@@ -55,9 +57,184 @@ fn extract_sil_from_config(config: &SafetyConfig) -> SIL {
     }
 }
 
-fn check_part1_general_requirements(model: &SemanticModel, sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+/// Consequence of the hazardous event (IEC 61508-5 Annex E): minor injury,
+/// serious permanent injury or one death, several deaths, very many deaths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Consequence {
+    C1,
+    C2,
+    C3,
+    C4,
+}
+
+/// Frequency of, and exposure time in, the hazardous zone: rare to more
+/// often, frequent to permanent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    F1,
+    F2,
+}
+
+/// Possibility of avoiding the hazardous event: possible under certain
+/// conditions, almost impossible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Avoidance {
+    P1,
+    P2,
+}
+
+/// Probability of the unwanted occurrence without the safety function:
+/// very slight, slight, relatively high.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DemandRate {
+    W1,
+    W2,
+    W3,
+}
+
+/// The SIL required by the risk graph of IEC 61508-5 Figure E.2, or `None`
+/// when the graph asks for no special safety requirements. Outcome "b" (a
+/// single E/E/PE system is not sufficient) is reported as SIL 4.
+pub fn determine_sil(
+    frequency: Frequency,
+    consequence: Consequence,
+    avoidance: Avoidance,
+    demand_rate: DemandRate,
+) -> Option<IntegrityLevel> {
+    // Row X1..X6 of the graph; F and P only refine C2, F refines C3.
+    let row = match (consequence, frequency, avoidance) {
+        (Consequence::C1, _, _) => 1,
+        (Consequence::C2, Frequency::F1, Avoidance::P1) => 2,
+        (Consequence::C2, Frequency::F1, Avoidance::P2) | (Consequence::C2, Frequency::F2, Avoidance::P1) => 3,
+        (Consequence::C2, Frequency::F2, Avoidance::P2) | (Consequence::C3, Frequency::F1, _) => 4,
+        (Consequence::C3, Frequency::F2, _) => 5,
+        (Consequence::C4, _, _) => 6,
+    };
+    // W3 needs one SIL more than W2, and W2 one more than W1.
+    let column = match demand_rate {
+        DemandRate::W3 => 0,
+        DemandRate::W2 => 1,
+        DemandRate::W1 => 2,
+    };
+    match row - 1 - column {
+        1 => Some(IntegrityLevel::SIL_1),
+        2 => Some(IntegrityLevel::SIL_2),
+        3 => Some(IntegrityLevel::SIL_3),
+        4 | 5 => Some(IntegrityLevel::SIL_4),
+        _ => None,
+    }
+}
+
+/// 1..4 for `SIL_1`..`SIL_4`, 0 for levels of other standards.
+fn sil_rank(level: &IntegrityLevel) -> u8 {
+    match level {
+        IntegrityLevel::SIL_1 => 1,
+        IntegrityLevel::SIL_2 => 2,
+        IntegrityLevel::SIL_3 => 3,
+        IntegrityLevel::SIL_4 => 4,
+        _ => 0,
+    }
+}
+
+/// A SIL as written in the model (`SIL_2`, `SIL-2`, ...).
+fn parse_sil(text: Option<&str>) -> Option<IntegrityLevel> {
+    text.and_then(IntegrityLevel::parse).filter(|level| sil_rank(level) > 0)
+}
+
+/// A hazard's declared SIL against the one its risk parameters call for.
+#[derive(Debug, Clone)]
+pub struct SilAssessment {
+    pub hazard: String,
+    pub declared: Option<IntegrityLevel>,
+    pub derived: Option<IntegrityLevel>,
+}
+
+impl SilAssessment {
+    /// The declared level is lower than the derived one.
+    pub fn is_understated(&self) -> bool {
+        self.declared.as_ref().map_or(0, sil_rank) < self.derived.as_ref().map_or(0, sil_rank)
+    }
+}
+
+/// Derive the SIL of a hazard from its `consequence`, `frequency`,
+/// `avoidance` and `demand_rate` attributes (`"C3"`, `"F2"`, ...). `None`
+/// when it gives none of them; an error when some are missing or invalid.
+pub fn assess_hazard(hazard: &Hazard) -> Result<Option<SilAssessment>, String> {
+    let text = |key: &str| hazard.attributes.get(key).and_then(|v| v.as_string()).map(|s| s.trim().to_uppercase());
+    let id = text("id").unwrap_or_else(|| hazard.name.clone());
+    let keys = ["consequence", "frequency", "avoidance", "demand_rate"];
+    if keys.iter().all(|key| text(key).is_none()) {
+        return Ok(None);
+    }
+
+    let parameter = |key: &str, values: &[&str]| -> Result<usize, String> {
+        let value = text(key).ok_or_else(|| format!("hazard {} has no '{}' risk parameter", id, key))?;
+        values.iter().position(|v| *v == value).ok_or_else(|| {
+            format!("hazard {}: '{}' is not a valid {} (expected {})", id, value, key, values.join(", "))
+        })
+    };
+    let consequence = [Consequence::C1, Consequence::C2, Consequence::C3, Consequence::C4]
+        [parameter("consequence", &["C1", "C2", "C3", "C4"])?];
+    let frequency = [Frequency::F1, Frequency::F2][parameter("frequency", &["F1", "F2"])?];
+    let avoidance = [Avoidance::P1, Avoidance::P2][parameter("avoidance", &["P1", "P2"])?];
+    let demand_rate = [DemandRate::W1, DemandRate::W2, DemandRate::W3][parameter("demand_rate", &["W1", "W2", "W3"])?];
+
+    let declared = parse_sil(["sil", "integrity_level", "safety_level"].iter().find_map(|key| text(key)).as_deref());
+
+    Ok(Some(SilAssessment {
+        hazard: id,
+        declared,
+        derived: determine_sil(frequency, consequence, avoidance, demand_rate),
+    }))
+}
+
+/// The model's SIL-rated requirements as safety goals, each against the
+/// hazard named by its `hazard` attribute.
+pub fn model_safety_goals(model: &SemanticModel) -> Vec<SafetyGoal> {
+    model.requirements.iter()
+        .filter_map(|req| {
+            Some(SafetyGoal {
+                id: req.id.clone(),
+                description: req.description.clone(),
+                hazard_id: req.hazard.clone().unwrap_or_default(),
+                integrity_level: parse_sil(req.safety_level.as_deref())?,
+                functional_safety_concept: String::new(),
+                verification_measures: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// SIL-rated safety goals whose hazard is not in the model's hazard analysis.
+pub fn unsupported_safety_goals<'a>(model: &SemanticModel, goals: &'a [SafetyGoal]) -> Vec<&'a SafetyGoal> {
+    goals.iter()
+        .filter(|goal| sil_rank(&goal.integrity_level) > 0)
+        .filter(|goal| !model.hazards.iter().any(|h| h.id == goal.hazard_id || h.name == goal.hazard_id))
+        .collect()
+}
+
+fn check_safety_goals(model: &SemanticModel, config: &SafetyConfig, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    let mut goals = config.project_safety_goals.clone();
+    goals.extend(model_safety_goals(model));
+
+    *total_checks += 1;
+    let unsupported = unsupported_safety_goals(model, &goals);
+    if unsupported.is_empty() {
+        *passed_checks += 1;
+    }
+    for goal in unsupported {
+        non_compliances.push(NonCompliance {
+            clause: "IEC 61508-1:2010 Clause 7.5".to_string(),
+            description: format!("Safety goal {} ({:?}) is not supported by a hazard analysis entry", goal.id, goal.integrity_level),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec![goal.id.clone()],
+        });
+    }
+}
+
+fn check_part1_general_requirements(model: &SemanticModel, _sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
     *total_checks += 1;
-    if model.has_safety_lifecycle() {
+    if has_process_evidence(model, "safety_lifecycle") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -69,7 +246,7 @@ fn check_part1_general_requirements(model: &SemanticModel, sil: SIL, non_complia
     }
     
     *total_checks += 1;
-    if model.has_safety_plan() {
+    if has_process_evidence(model, "safety_plan") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -81,7 +258,7 @@ fn check_part1_general_requirements(model: &SemanticModel, sil: SIL, non_complia
     }
     
     *total_checks += 1;
-    if !model.hazards().is_empty() {
+    if !model.hazards.is_empty() {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -93,9 +270,9 @@ fn check_part1_general_requirements(model: &SemanticModel, sil: SIL, non_complia
     }
 }
 
-fn check_part2_euc_requirements(model: &SemanticModel, sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+fn check_part2_euc_requirements(model: &SemanticModel, _sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
     *total_checks += 1;
-    if model.has_euc_description() {
+    if has_layer(model, "System") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -107,7 +284,7 @@ fn check_part2_euc_requirements(model: &SemanticModel, sil: SIL, non_compliances
     }
     
     *total_checks += 1;
-    if model.has_safety_functions() {
+    if model.requirements.iter().any(|req| parse_sil(req.safety_level.as_deref()).is_some()) {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -121,7 +298,7 @@ fn check_part2_euc_requirements(model: &SemanticModel, sil: SIL, non_compliances
 
 fn check_part3_software_requirements(model: &SemanticModel, sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
     *total_checks += 1;
-    if model.has_software_safety_lifecycle() {
+    if has_process_evidence(model, "software_safety_lifecycle") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -133,8 +310,8 @@ fn check_part3_software_requirements(model: &SemanticModel, sil: SIL, non_compli
     }
     
     *total_checks += 1;
-    let has_sw_safety_requirements = model.requirements().iter()
-        .any(|req| req.is_safety_requirement && req.level == "software");
+    let has_sw_safety_requirements = model.requirements.iter()
+        .any(|req| req.is_safety_requirement() && has_category(req, "software"));
     
     if has_sw_safety_requirements {
         *passed_checks += 1;
@@ -148,7 +325,7 @@ fn check_part3_software_requirements(model: &SemanticModel, sil: SIL, non_compli
     }
     
     *total_checks += 1;
-    if model.has_software_architecture() {
+    if has_layer(model, "Logical") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -163,19 +340,17 @@ fn check_part3_software_requirements(model: &SemanticModel, sil: SIL, non_compli
     check_software_validation(model, sil, non_compliances, total_checks, passed_checks);
 }
 
-fn check_part4_definitions(model: &SemanticModel, _sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+fn check_part4_definitions(_model: &SemanticModel, _sil: SIL, _non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
     *total_checks += 1;
     *passed_checks += 1;
 }
 
 fn check_part7_techniques(model: &SemanticModel, sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
-    let recommended_techniques = get_recommended_techniques_for_sil(sil);
-    
     *total_checks += 1;
     
-    let has_modular_approach = model.has_modular_design();
-    let has_defensive_programming = model.has_defensive_programming_patterns();
-    let has_coding_standards = model.has_coding_standards();
+    let has_modular_approach = has_process_evidence(model, "modular_design");
+    let has_defensive_programming = has_process_evidence(model, "defensive_programming");
+    let has_coding_standards = has_process_evidence(model, "coding_standards");
     
     let technique_compliance = has_modular_approach && has_coding_standards;
     
@@ -202,7 +377,7 @@ fn check_part7_techniques(model: &SemanticModel, sil: SIL, non_compliances: &mut
 
 fn check_software_verification(model: &SemanticModel, sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
     *total_checks += 1;
-    if model.has_verification_plan() {
+    if has_process_evidence(model, "verification_plan") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -214,8 +389,8 @@ fn check_software_verification(model: &SemanticModel, sil: SIL, non_compliances:
     }
     
     *total_checks += 1;
-    let has_unit_testing = model.test_specifications().iter()
-        .any(|t| t.test_level == "unit");
+    let has_unit_testing = model.verifications.iter()
+        .any(|v| v.method.eq_ignore_ascii_case("test"));
     
     if has_unit_testing {
         *passed_checks += 1;
@@ -229,8 +404,7 @@ fn check_software_verification(model: &SemanticModel, sil: SIL, non_compliances:
     }
     
     *total_checks += 1;
-    let has_integration_testing = model.test_specifications().iter()
-        .any(|t| t.test_level == "integration");
+    let has_integration_testing = has_process_evidence(model, "integration_testing");
     
     if has_integration_testing {
         *passed_checks += 1;
@@ -245,7 +419,7 @@ fn check_software_verification(model: &SemanticModel, sil: SIL, non_compliances:
     
     if matches!(sil, SIL::SIL3 | SIL::SIL4) {
         *total_checks += 1;
-        if model.has_static_analysis() {
+        if model.verifications.iter().any(|v| v.method.eq_ignore_ascii_case("analysis")) {
             *passed_checks += 1;
         } else {
             non_compliances.push(NonCompliance {
@@ -260,7 +434,7 @@ fn check_software_verification(model: &SemanticModel, sil: SIL, non_compliances:
 
 fn check_software_validation(model: &SemanticModel, _sil: SIL, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
     *total_checks += 1;
-    if model.has_validation_plan() {
+    if has_process_evidence(model, "validation_plan") {
         *passed_checks += 1;
     } else {
         non_compliances.push(NonCompliance {
@@ -272,8 +446,8 @@ fn check_software_validation(model: &SemanticModel, _sil: SIL, non_compliances:
     }
     
     *total_checks += 1;
-    let has_functional_tests = model.test_specifications().iter()
-        .any(|t| t.test_type == "functional");
+    let has_functional_tests = model.verifications.iter()
+        .any(|v| v.method.eq_ignore_ascii_case("test") || v.method.eq_ignore_ascii_case("demonstration"));
     
     if has_functional_tests {
         *passed_checks += 1;
@@ -296,7 +470,6 @@ struct RecommendedTechnique {
 enum TechniqueRecommendation {
     HighlyRecommended,
     Recommended,
-    NeutralOrNotRecommended,
 }
 
 fn get_recommended_techniques_for_sil(sil: SIL) -> Vec<RecommendedTechnique> {
@@ -375,7 +548,7 @@ pub fn generate_iec61508_report(analysis: &SafetyAnalysisResult, sil: SIL) -> St
                     nc.description
                 ));
             }
-            report.push_str("\n");
+            report.push('\n');
         }
         
         report.push_str("Recommended Techniques:\n");
@@ -391,3 +564,81 @@ pub fn generate_iec61508_report(analysis: &SafetyAnalysisResult, sil: SIL) -> St
     
     report
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    #[test]
+    fn risk_graph_follows_figure_e2() {
+        use Avoidance::*;
+        use Consequence::*;
+        use DemandRate::*;
+        use Frequency::*;
+
+        assert_eq!(determine_sil(F2, C1, P2, W3), None);
+        assert_eq!(determine_sil(F1, C2, P1, W3), Some(IntegrityLevel::SIL_1));
+        assert_eq!(determine_sil(F1, C2, P1, W2), None);
+        assert_eq!(determine_sil(F2, C2, P1, W3), Some(IntegrityLevel::SIL_2));
+        assert_eq!(determine_sil(F2, C2, P2, W2), Some(IntegrityLevel::SIL_2));
+        assert_eq!(determine_sil(F1, C3, P2, W1), Some(IntegrityLevel::SIL_1));
+        assert_eq!(determine_sil(F2, C3, P1, W3), Some(IntegrityLevel::SIL_4));
+        assert_eq!(determine_sil(F1, C4, P1, W1), Some(IntegrityLevel::SIL_3));
+        assert_eq!(determine_sil(F1, C4, P1, W3), Some(IntegrityLevel::SIL_4));
+    }
+
+    const MODEL: &str = r#"
+model Press {
+}
+
+requirements system {
+    req "SG-001" "Guard" { description: "Stop the press when the guard opens" safety_level: "SIL_3" hazard: "HAZ-001" }
+    req "SG-002" "Overload" { description: "Stop on overload" safety_level: "SIL_2" hazard: "HAZ-404" }
+}
+
+safety_analysis {
+    hazard "Crushing" {
+        id: "HAZ-001"
+        sil: "SIL_2"
+        consequence: "C3"
+        frequency: "F2"
+        avoidance: "P2"
+        demand_rate: "W2"
+    }
+    hazard "Noise" {
+        id: "HAZ-002"
+    }
+}
+"#;
+
+    #[test]
+    fn understated_hazards_and_unsupported_goals_are_found() {
+        let result = Compiler::new(CompilerConfig::default()).compile_string(MODEL).expect("compiles");
+        let hazards: Vec<&Hazard> = result.ast.safety_analysis.iter().flat_map(|s| &s.hazards).collect();
+
+        let crushing = assess_hazard(hazards[0]).unwrap().unwrap();
+        assert_eq!(crushing.hazard, "HAZ-001");
+        assert_eq!(crushing.declared, Some(IntegrityLevel::SIL_2));
+        assert_eq!(crushing.derived, Some(IntegrityLevel::SIL_3));
+        assert!(crushing.is_understated());
+        assert!(assess_hazard(hazards[1]).unwrap().is_none());
+
+        let goals = model_safety_goals(&result.semantic_model);
+        assert_eq!(goals.len(), 2);
+        let unsupported: Vec<&str> = unsupported_safety_goals(&result.semantic_model, &goals)
+            .iter()
+            .map(|g| g.id.as_str())
+            .collect();
+        assert_eq!(unsupported, vec!["SG-002"]);
+    }
+
+    #[test]
+    fn invalid_risk_parameters_are_rejected() {
+        let mut hazard = Hazard { name: "Crushing".to_string(), attributes: HashMap::new() };
+        hazard.attributes.insert("consequence".to_string(), crate::compiler::ast::AttributeValue::String("C5".to_string()));
+
+        let err = assess_hazard(&hazard).unwrap_err();
+        assert!(err.contains("'C5' is not a valid consequence"), "{}", err);
+    }
+}
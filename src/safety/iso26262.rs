@@ -0,0 +1,830 @@
+use super::*;
+use crate::compiler::semantic::{SemanticModel, TraceLinkType};
+use std::collections::BTreeMap;
+
+pub fn check_compliance(model: &SemanticModel, config: &SafetyConfig) -> ComplianceStatus {
+    let mut non_compliances = Vec::new();
+    let mut recommendations = Vec::new();
+    let mut total_checks = 0;
+    let mut passed_checks = 0;
+    
+    check_part3_system_design(model, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    check_part4_sw_level(model, config, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    check_part5_hw_level(model, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    check_part6_sw_product(model, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    check_part8_supporting_processes(model, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    check_part9_asil_oriented(model, config, &mut non_compliances, &mut total_checks, &mut passed_checks);
+    
+    if !non_compliances.is_empty() {
+        recommendations.push("Review all non-compliances and create corrective action plan".to_string());
+        recommendations.push("Consider tool qualification for ASIL-C and above (Part 8)".to_string());
+    }
+    
+    let compliance_percentage = if total_checks > 0 {
+        (passed_checks as f64 / total_checks as f64) * 100.0
+    } else {
+        0.0
+    };
+    
+    ComplianceStatus {
+        compliant: non_compliances.is_empty(),
+        compliance_percentage,
+        non_compliances,
+        recommendations,
+    }
+}
+
+fn check_part3_system_design(model: &SemanticModel, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+    if has_layer(model, "Operational") || !model.missions.is_empty() {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-3:2018 Clause 5".to_string(),
+            description: "Missing item definition and operational context (Operational Analysis)".to_string(),
+            severity: ComplianceSeverity::Critical,
+            affected_elements: vec!["Operational Level".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    if !model.hazards.is_empty() {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-3:2018 Clause 6".to_string(),
+            description: "Hazard analysis and risk assessment not performed".to_string(),
+            severity: ComplianceSeverity::Critical,
+            affected_elements: vec!["System Level".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    let has_functional_safety_concept = model.requirements.iter()
+        .any(|req| req.safety_level.is_some() && !has_category(req, "software") && !has_category(req, "hardware"));
+    
+    if has_functional_safety_concept {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-3:2018 Clause 7".to_string(),
+            description: "Functional safety concept not defined".to_string(),
+            severity: ComplianceSeverity::Critical,
+            affected_elements: vec!["System Level".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    if has_layer(model, "System") {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-3:2018 Clause 8".to_string(),
+            description: "Technical safety concept and system architecture missing".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["System Level".to_string()],
+        });
+    }
+}
+
+fn check_part4_sw_level(model: &SemanticModel, config: &SafetyConfig, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+    let has_sw_safety_requirements = model.requirements.iter()
+        .any(|req| req.safety_level.is_some() && has_category(req, "software"));
+    
+    if has_sw_safety_requirements {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-6:2018 Clause 5".to_string(),
+            description: "Software safety requirements specification missing".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Software Level".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    if has_layer(model, "Logical") {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-6:2018 Clause 6".to_string(),
+            description: "Software architectural design missing".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Software Level".to_string()],
+        });
+    }
+    
+    check_asil_decomposition(model, config, non_compliances, total_checks, passed_checks);
+}
+
+fn check_part5_hw_level(model: &SemanticModel, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+    let has_hw_safety_requirements = model.requirements.iter()
+        .any(|req| req.safety_level.is_some() && has_category(req, "hardware"));
+    
+    if has_hw_safety_requirements {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-5:2018 Clause 6".to_string(),
+            description: "Hardware safety requirements specification missing".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Hardware Level".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    if has_layer(model, "Physical") {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-5:2018 Clause 7".to_string(),
+            description: "Hardware architectural design missing".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Physical Level".to_string()],
+        });
+    }
+}
+
+fn check_part6_sw_product(model: &SemanticModel, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+    let has_unit_design = model.components.iter()
+        .any(|c| c.component_type.eq_ignore_ascii_case("software_unit") || c.component_type.eq_ignore_ascii_case("software"));
+    
+    if has_unit_design {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-6:2018 Clause 7".to_string(),
+            description: "Software unit design and implementation not documented".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Software Level".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    let has_test_spec = model.traces.iter()
+        .any(|t| t.trace_type == "verifies");
+    
+    if has_test_spec {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-6:2018 Clause 9".to_string(),
+            description: "Software unit testing specification missing".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Software Level".to_string()],
+        });
+    }
+}
+
+fn check_part8_supporting_processes(model: &SemanticModel, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+    if has_process_evidence(model, "configuration_management") {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-8:2018 Clause 5".to_string(),
+            description: "Configuration management strategy not defined".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Process".to_string()],
+        });
+    }
+    
+    *total_checks += 1;
+    if has_process_evidence(model, "change_management") {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-8:2018 Clause 6".to_string(),
+            description: "Change management process not defined".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["Process".to_string()],
+        });
+    }
+}
+
+fn check_part9_asil_oriented(model: &SemanticModel, config: &SafetyConfig, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+    
+    let requires_analysis = matches!(
+        config.target_integrity_level,
+        IntegrityLevel::ASIL_C | IntegrityLevel::ASIL_D
+    );
+    
+    if requires_analysis {
+        let has_dependent_failure_analysis = has_process_evidence(model, "dependent_failure_analysis");
+        
+        if has_dependent_failure_analysis {
+            *passed_checks += 1;
+        } else {
+            non_compliances.push(NonCompliance {
+                clause: "ISO 26262-9:2018 Clause 7".to_string(),
+                description: format!("Analysis of dependent failures required for {:?}", config.target_integrity_level),
+                severity: ComplianceSeverity::Critical,
+                affected_elements: vec!["System Level".to_string()],
+            });
+        }
+    } else {
+        *passed_checks += 1;
+    }
+    
+    *total_checks += 1;
+    let has_safety_case = has_process_evidence(model, "safety_case");
+    if has_safety_case {
+        *passed_checks += 1;
+    } else {
+        non_compliances.push(NonCompliance {
+            clause: "ISO 26262-9:2018 Clause 8".to_string(),
+            description: "Safety validation and confirmation measures not defined".to_string(),
+            severity: ComplianceSeverity::Major,
+            affected_elements: vec!["System Level".to_string()],
+        });
+    }
+
+    *total_checks += 1;
+    let interference = check_interference(model);
+    if interference.is_empty() {
+        *passed_checks += 1;
+    }
+    non_compliances.extend(interference);
+}
+
+fn check_asil_decomposition(model: &SemanticModel, _config: &SafetyConfig, non_compliances: &mut Vec<NonCompliance>, total_checks: &mut usize, passed_checks: &mut usize) {
+    *total_checks += 1;
+
+    let mut valid = true;
+    for decomposition in declared_decompositions(model) {
+        if let Err(error) = validate_decomposition(model, &decomposition.original, &decomposition.parts) {
+            valid = false;
+            non_compliances.push(NonCompliance {
+                clause: "ISO 26262-9:2018 Clause 5".to_string(),
+                description: format!("Invalid ASIL decomposition for requirement {}: {}", decomposition.requirement, error),
+                severity: ComplianceSeverity::Critical,
+                affected_elements: std::iter::once(decomposition.requirement.clone())
+                    .chain(decomposition.parts.iter().map(|(element, _)| element.clone()))
+                    .collect(),
+            });
+        }
+    }
+
+    if valid {
+        *passed_checks += 1;
+    }
+}
+
+/// Components hosting requirements of different ASILs (mixed criticality)
+/// with no `isolation:` mechanism declared: without freedom from
+/// interference, ISO 26262-9 Clause 6 requires the whole component to be
+/// developed to the highest of them, so a component declaring that level
+/// itself is not reported. A requirement is hosted by the components it is
+/// allocated to or satisfied by, directly or through their functions.
+pub fn check_interference(model: &SemanticModel) -> Vec<NonCompliance> {
+    let mut hosted: BTreeMap<String, Vec<(&str, IntegrityLevel)>> = BTreeMap::new();
+    for trace in &model.traces {
+        let (requirement, element) = match trace.link_type {
+            TraceLinkType::AllocatedTo => (&trace.from, &trace.to),
+            TraceLinkType::Satisfies | TraceLinkType::Implements => (&trace.to, &trace.from),
+            _ => continue,
+        };
+        let Some(level) = model
+            .requirements
+            .iter()
+            .find(|r| r.id == *requirement)
+            .and_then(|r| r.safety_level.as_deref())
+            .and_then(IntegrityLevel::parse)
+            .filter(|level| asil_rank(level).is_some())
+        else {
+            continue;
+        };
+        for component in owning_components(model, element) {
+            let entry = hosted.entry(component).or_default();
+            if !entry.iter().any(|(r, _)| r == requirement) {
+                entry.push((requirement.as_str(), level.clone()));
+            }
+        }
+    }
+
+    hosted
+        .into_iter()
+        .filter_map(|(component, requirements)| {
+            let info = model.components.iter().find(|c| c.id == component)?;
+            if info.isolation.is_some() {
+                return None;
+            }
+            let (highest, high) = requirements.iter().max_by_key(|(_, l)| asil_rank(l))?;
+            let (lowest, low) = requirements.iter().min_by_key(|(_, l)| asil_rank(l))?;
+            let developed_to = info
+                .safety_level
+                .as_deref()
+                .or(info.asil.as_deref())
+                .and_then(IntegrityLevel::parse)
+                .and_then(|level| asil_rank(&level));
+            if asil_rank(high) == asil_rank(low) || developed_to >= asil_rank(high) {
+                return None;
+            }
+            let label = |level: &IntegrityLevel| match level {
+                IntegrityLevel::ASIL_QM => "QM".to_string(),
+                _ => format!("ASIL {}", asil_letter(level)),
+            };
+            Some(NonCompliance {
+                clause: "ISO 26262-9:2018 Clause 6".to_string(),
+                description: format!(
+                    "Component {} hosts {} ({}) and {} ({}) requirements without a declared isolation mechanism; \
+                     develop it to {} or declare `isolation:` for freedom from interference",
+                    component,
+                    label(high),
+                    highest,
+                    label(low),
+                    lowest,
+                    label(high)
+                ),
+                severity: if matches!(high, IntegrityLevel::ASIL_C | IntegrityLevel::ASIL_D) { ComplianceSeverity::Critical } else { ComplianceSeverity::Major },
+                affected_elements: std::iter::once(component.clone())
+                    .chain(requirements.iter().map(|(r, _)| r.to_string()))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// ISO 26262-3:2018 Table 4, indexed by severity (S1–S3), probability of
+/// exposure (E1–E4) and controllability (C1–C3).
+const ASIL_TABLE: [[[IntegrityLevel; 3]; 4]; 3] = {
+    use IntegrityLevel::{ASIL_A as A, ASIL_B as B, ASIL_C as C, ASIL_D as D, ASIL_QM as QM};
+    [
+        // S1
+        [[QM, QM, QM], [QM, QM, QM], [QM, QM, A], [QM, A, B]],
+        // S2
+        [[QM, QM, QM], [QM, QM, A], [QM, A, B], [A, B, C]],
+        // S3
+        [[QM, QM, A], [QM, A, B], [A, B, C], [B, C, D]],
+    ]
+};
+
+/// The ASIL of a hazardous event (ISO 26262-3 Clause 6.4.3). S0, E0 or C0
+/// put the event outside the table: QM.
+pub fn determine_asil(
+    severity: &HazardSeverity,
+    exposure: &ExposureLevel,
+    controllability: &ControllabilityLevel,
+) -> IntegrityLevel {
+    let s = match severity {
+        HazardSeverity::S0 => return IntegrityLevel::ASIL_QM,
+        HazardSeverity::S1 => 0,
+        HazardSeverity::S2 => 1,
+        HazardSeverity::S3 => 2,
+    };
+    let e = match exposure {
+        ExposureLevel::E0 => return IntegrityLevel::ASIL_QM,
+        ExposureLevel::E1 => 0,
+        ExposureLevel::E2 => 1,
+        ExposureLevel::E3 => 2,
+        ExposureLevel::E4 => 3,
+    };
+    let c = match controllability {
+        ControllabilityLevel::C0 => return IntegrityLevel::ASIL_QM,
+        ControllabilityLevel::C1 => 0,
+        ControllabilityLevel::C2 => 1,
+        ControllabilityLevel::C3 => 2,
+    };
+    ASIL_TABLE[s][e][c].clone()
+}
+
+/// An ASIL decomposition declared in the model: a safety requirement and
+/// the elements satisfying it with decomposed levels such as `ASIL_B(D)`.
+#[derive(Debug, Clone)]
+pub struct ASILDecomposition {
+    pub requirement: String,
+    pub original: IntegrityLevel,
+    pub parts: Vec<(String, IntegrityLevel)>,
+}
+
+/// Position of an ASIL on the QM < A < B < C < D scale; `None` for DAL/SIL.
+fn asil_rank(level: &IntegrityLevel) -> Option<u8> {
+    match level {
+        IntegrityLevel::ASIL_QM => Some(0),
+        IntegrityLevel::ASIL_A => Some(1),
+        IntegrityLevel::ASIL_B => Some(2),
+        IntegrityLevel::ASIL_C => Some(3),
+        IntegrityLevel::ASIL_D => Some(4),
+        _ => None,
+    }
+}
+
+fn asil_letter(level: &IntegrityLevel) -> &'static str {
+    ["QM", "A", "B", "C", "D"][asil_rank(level).unwrap_or(0) as usize]
+}
+
+/// ISO 26262-9 notation for a decomposed level, e.g. `ASIL B(D)` or `QM(A)`.
+pub fn decomposed_label(level: &IntegrityLevel, original: &IntegrityLevel) -> String {
+    match level {
+        IntegrityLevel::ASIL_QM => format!("QM({})", asil_letter(original)),
+        _ => format!("ASIL {}({})", asil_letter(level), asil_letter(original)),
+    }
+}
+
+fn asil_from_rank(rank: u8) -> IntegrityLevel {
+    match rank {
+        0 => IntegrityLevel::ASIL_QM,
+        1 => IntegrityLevel::ASIL_A,
+        2 => IntegrityLevel::ASIL_B,
+        3 => IntegrityLevel::ASIL_C,
+        _ => IntegrityLevel::ASIL_D,
+    }
+}
+
+/// Parse a decomposed level as written in ISO 26262-9 notation, e.g.
+/// `ASIL_B(D)` or `QM(B)`, into `(level, original level)`.
+pub fn parse_decomposed_level(text: &str) -> Option<(IntegrityLevel, IntegrityLevel)> {
+    let (level, original) = text.trim().strip_suffix(')')?.split_once('(')?;
+    let original = if original.len() <= 2 && !original.eq_ignore_ascii_case("QM") {
+        format!("ASIL_{}", original)
+    } else {
+        original.to_string()
+    };
+    Some((IntegrityLevel::parse(level)?, IntegrityLevel::parse(&original)?))
+}
+
+/// Split a safety goal's ASIL across the elements it is allocated to,
+/// following the ISO 26262-9 clause 5 schemes (D → B(D) + B(D),
+/// C → B(C) + A(C), B → A(B) + A(B), A → A(A) + QM(A)). Each split halves
+/// the remaining level, so more than two elements are handled by
+/// decomposing again. A single allocation keeps the full level, as do
+/// non-ASIL goals.
+pub fn decompose_asil(goal: &SafetyGoal, allocations: &[String]) -> Vec<(String, IntegrityLevel)> {
+    let Some(rank) = asil_rank(&goal.integrity_level) else {
+        return allocations.iter().map(|a| (a.clone(), goal.integrity_level.clone())).collect();
+    };
+
+    fn split(rank: u8, allocations: &[String], out: &mut Vec<(String, IntegrityLevel)>) {
+        if allocations.len() == 1 {
+            out.push((allocations[0].clone(), asil_from_rank(rank)));
+            return;
+        }
+        let (left, right) = allocations.split_at(allocations.len().div_ceil(2));
+        split(rank.div_ceil(2), left, out);
+        split(rank / 2, right, out);
+    }
+
+    let mut parts = Vec::new();
+    if !allocations.is_empty() {
+        split(rank, allocations, &mut parts);
+    }
+    parts
+}
+
+/// Check a decomposition: at least two parts, decomposed levels that
+/// recombine to the original ASIL, and pairwise independent elements (no
+/// element, and no owning component, shared between parts).
+pub fn validate_decomposition(
+    model: &SemanticModel,
+    original: &IntegrityLevel,
+    parts: &[(String, IntegrityLevel)],
+) -> Result<(), SafetyError> {
+    let illegal = |reason: String| SafetyError::NonCompliant {
+        standard: "ISO 26262-9".to_string(),
+        reason,
+    };
+
+    let original_rank = asil_rank(original)
+        .ok_or_else(|| SafetyError::InvalidIntegrityLevel(format!("{:?} cannot be ASIL-decomposed", original)))?;
+    if parts.len() < 2 {
+        return Err(illegal(format!("{:?} must be decomposed into at least two elements", original)));
+    }
+
+    let mut total = 0;
+    for (element, level) in parts {
+        let rank = asil_rank(level)
+            .ok_or_else(|| SafetyError::InvalidIntegrityLevel(format!("{} is assigned {:?}", element, level)))?;
+        total += rank;
+    }
+    if total != original_rank {
+        let levels: Vec<String> = parts.iter().map(|(_, level)| decomposed_label(level, original)).collect();
+        return Err(illegal(format!(
+            "{} does not recombine to ASIL {}",
+            levels.join(" + "),
+            asil_letter(original)
+        )));
+    }
+
+    let mut owners: Vec<(&str, String)> = Vec::new();
+    for (element, _) in parts {
+        for owner in owning_components(model, element) {
+            if let Some((other, _)) = owners.iter().find(|(_, o)| *o == owner) {
+                return Err(illegal(format!(
+                    "{} and {} are not independent: both are realized by {}",
+                    other, element, owner
+                )));
+            }
+            owners.push((element, owner));
+        }
+    }
+
+    Ok(())
+}
+
+/// Components an element is realized by: the component itself, or the
+/// components a function is allocated to.
+fn owning_components(model: &SemanticModel, element: &str) -> Vec<String> {
+    if model.components.iter().any(|c| c.id == element) {
+        return vec![element.to_string()];
+    }
+    let function_name = model
+        .functions
+        .iter()
+        .find(|f| f.id == element)
+        .map_or(element, |f| f.name.as_str());
+    let owners: Vec<String> = model
+        .components
+        .iter()
+        .filter(|c| c.functions.iter().any(|f| f == function_name || f == element))
+        .map(|c| c.id.clone())
+        .collect();
+    if owners.is_empty() {
+        vec![element.to_string()]
+    } else {
+        owners
+    }
+}
+
+/// Decompositions declared in the model: for each safety requirement, the
+/// elements tracing to it whose `asil`/`safety_level` is written in
+/// decomposed notation for that requirement's level.
+pub fn declared_decompositions(model: &SemanticModel) -> Vec<ASILDecomposition> {
+    let declared_level = |id: &str| -> Option<&str> {
+        model
+            .components
+            .iter()
+            .find(|c| c.id == id)
+            .and_then(|c| c.asil.as_deref().or(c.safety_level.as_deref()))
+    };
+
+    let mut decompositions = Vec::new();
+    for req in &model.requirements {
+        let Some(original) = req.safety_level.as_deref().and_then(IntegrityLevel::parse) else { continue };
+
+        let mut parts: Vec<(String, IntegrityLevel)> = Vec::new();
+        for trace in model.get_traces_to(&req.id) {
+            let Some((level, of)) = declared_level(&trace.from).and_then(parse_decomposed_level) else { continue };
+            if of == original && !parts.iter().any(|(element, _)| *element == trace.from) {
+                parts.push((trace.from.clone(), level));
+            }
+        }
+
+        if !parts.is_empty() {
+            decompositions.push(ASILDecomposition {
+                requirement: req.id.clone(),
+                original,
+                parts,
+            });
+        }
+    }
+
+    decompositions
+}
+
+/// Decompositions to consider for safety requirements satisfied by several
+/// components that do not declare decomposed levels yet.
+pub fn suggested_decompositions(model: &SemanticModel) -> Vec<ASILDecomposition> {
+    let declared: Vec<String> = declared_decompositions(model).into_iter().map(|d| d.requirement).collect();
+
+    let mut suggestions = Vec::new();
+    for req in &model.requirements {
+        let Some(level) = req.safety_level.as_deref().and_then(IntegrityLevel::parse) else { continue };
+        if declared.contains(&req.id) || asil_rank(&level).unwrap_or(0) < 2 {
+            continue;
+        }
+
+        let mut allocations: Vec<String> = Vec::new();
+        for trace in model.get_traces_to(&req.id) {
+            if model.components.iter().any(|c| c.id == trace.from) && !allocations.contains(&trace.from) {
+                allocations.push(trace.from.clone());
+            }
+        }
+        if allocations.len() < 2 {
+            continue;
+        }
+
+        let goal = SafetyGoal {
+            id: req.id.clone(),
+            description: req.description.clone(),
+            hazard_id: String::new(),
+            integrity_level: level.clone(),
+            functional_safety_concept: String::new(),
+            verification_measures: Vec::new(),
+        };
+        suggestions.push(ASILDecomposition {
+            requirement: req.id.clone(),
+            original: level,
+            parts: decompose_asil(&goal, &allocations),
+        });
+    }
+
+    suggestions
+}
+
+pub fn generate_iso26262_report(analysis: &SafetyAnalysisResult) -> String {
+    let mut report = String::new();
+    
+    report.push_str("ISO 26262:2018 Compliance Report\n");
+    report.push_str("=================================\n\n");
+    
+    if let Some(compliance) = analysis.standards_compliance.iter()
+        .find(|(std, _)| matches!(std, SafetyStandard::ISO26262 { .. }))
+    {
+        report.push_str(&format!("Overall Compliance: {:.1}%\n", compliance.1.compliance_percentage));
+        report.push_str(&format!("Status: {}\n\n", if compliance.1.compliant { "COMPLIANT" } else { "NON-COMPLIANT" }));
+        
+        if !compliance.1.non_compliances.is_empty() {
+            report.push_str("Non-Compliances:\n");
+            report.push_str("----------------\n");
+            for nc in &compliance.1.non_compliances {
+                report.push_str(&format!("- [{}] {}: {}\n", 
+                    match nc.severity {
+                        ComplianceSeverity::Critical => "CRITICAL",
+                        ComplianceSeverity::Major => "MAJOR",
+                        ComplianceSeverity::Minor => "MINOR",
+                        ComplianceSeverity::Observation => "OBSERVATION",
+                    },
+                    nc.clause,
+                    nc.description
+                ));
+            }
+            report.push('\n');
+        }
+        
+        if !compliance.1.recommendations.is_empty() {
+            report.push_str("Recommendations:\n");
+            report.push_str("----------------\n");
+            for rec in &compliance.1.recommendations {
+                report.push_str(&format!("- {}\n", rec));
+            }
+        }
+    }
+    
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    fn model(source: &str) -> SemanticModel {
+        Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model
+    }
+
+    fn goal(level: IntegrityLevel) -> SafetyGoal {
+        SafetyGoal {
+            id: "SG-1".to_string(),
+            description: String::new(),
+            hazard_id: String::new(),
+            integrity_level: level,
+            functional_safety_concept: String::new(),
+            verification_measures: Vec::new(),
+        }
+    }
+
+    fn elements(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn asil_determination_matches_part3_table_4() {
+        // ISO 26262-3:2018 Table 4, one row per severity and exposure:
+        // the ASIL for C1, C2 and C3.
+        const TABLE: &str = "
+            S1 E1 QM QM QM
+            S1 E2 QM QM QM
+            S1 E3 QM QM A
+            S1 E4 QM A  B
+            S2 E1 QM QM QM
+            S2 E2 QM QM A
+            S2 E3 QM A  B
+            S2 E4 A  B  C
+            S3 E1 QM QM A
+            S3 E2 QM A  B
+            S3 E3 A  B  C
+            S3 E4 B  C  D";
+        let severities = [HazardSeverity::S0, HazardSeverity::S1, HazardSeverity::S2, HazardSeverity::S3];
+        let exposures = [ExposureLevel::E0, ExposureLevel::E1, ExposureLevel::E2, ExposureLevel::E3, ExposureLevel::E4];
+        let controllabilities =
+            [ControllabilityLevel::C0, ControllabilityLevel::C1, ControllabilityLevel::C2, ControllabilityLevel::C3];
+
+        let mut cells = 0;
+        for row in TABLE.lines().map(str::split_whitespace).map(Iterator::collect::<Vec<_>>).filter(|r| !r.is_empty()) {
+            let index = |text: &str| text[1..].parse::<usize>().unwrap();
+            for (c, expected) in row[2..].iter().enumerate() {
+                let asil = determine_asil(&severities[index(row[0])], &exposures[index(row[1])], &controllabilities[c + 1]);
+                assert_eq!(asil_letter(&asil), *expected, "{} {} C{}", row[0], row[1], c + 1);
+                cells += 1;
+            }
+        }
+        assert_eq!(cells, 36);
+
+        // S0, E0 and C0 fall outside the table.
+        for s in &severities {
+            for e in &exposures {
+                for c in &controllabilities {
+                    if matches!(s, HazardSeverity::S0) || matches!(e, ExposureLevel::E0) || matches!(c, ControllabilityLevel::C0) {
+                        assert_eq!(determine_asil(s, e, c), IntegrityLevel::ASIL_QM, "{:?} {:?} {:?}", s, e, c);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decomposition_follows_part9_schemes() {
+        let levels = |level| -> Vec<IntegrityLevel> {
+            decompose_asil(&goal(level), &elements(&["X", "Y"])).into_iter().map(|(_, l)| l).collect()
+        };
+        assert_eq!(levels(IntegrityLevel::ASIL_D), vec![IntegrityLevel::ASIL_B, IntegrityLevel::ASIL_B]);
+        assert_eq!(levels(IntegrityLevel::ASIL_C), vec![IntegrityLevel::ASIL_B, IntegrityLevel::ASIL_A]);
+        assert_eq!(levels(IntegrityLevel::ASIL_B), vec![IntegrityLevel::ASIL_A, IntegrityLevel::ASIL_A]);
+        assert_eq!(levels(IntegrityLevel::ASIL_A), vec![IntegrityLevel::ASIL_A, IntegrityLevel::ASIL_QM]);
+
+        let three = decompose_asil(&goal(IntegrityLevel::ASIL_D), &elements(&["X", "Y", "Z"]));
+        let ranks: u8 = three.iter().filter_map(|(_, l)| asil_rank(l)).sum();
+        assert_eq!(ranks, 4);
+    }
+
+    const MODEL: &str = r#"
+model Demo {
+}
+
+architecture logical {
+  component "Primary" { id: "LC-001" function "brake" function "monitor" }
+  component "Backup" { id: "LC-002" function "brake_backup" }
+}
+"#;
+
+    #[test]
+    fn illegal_splits_are_rejected() {
+        let model = model(MODEL);
+        let parts = |a: IntegrityLevel, b: IntegrityLevel| vec![("LC-001".to_string(), a), ("LC-002".to_string(), b)];
+
+        assert!(validate_decomposition(&model, &IntegrityLevel::ASIL_D, &parts(IntegrityLevel::ASIL_B, IntegrityLevel::ASIL_B)).is_ok());
+        assert!(validate_decomposition(&model, &IntegrityLevel::ASIL_D, &parts(IntegrityLevel::ASIL_C, IntegrityLevel::ASIL_A)).is_ok());
+        assert!(validate_decomposition(&model, &IntegrityLevel::ASIL_A, &parts(IntegrityLevel::ASIL_QM, IntegrityLevel::ASIL_QM)).is_err());
+        assert!(validate_decomposition(&model, &IntegrityLevel::ASIL_D, &parts(IntegrityLevel::ASIL_B, IntegrityLevel::ASIL_A)).is_err());
+    }
+
+    #[test]
+    fn parts_sharing_a_component_are_not_independent() {
+        let model = model(MODEL);
+        let parts = vec![
+            ("brake".to_string(), IntegrityLevel::ASIL_B),
+            ("monitor".to_string(), IntegrityLevel::ASIL_B),
+        ];
+        let error = validate_decomposition(&model, &IntegrityLevel::ASIL_D, &parts).unwrap_err();
+        assert!(error.to_string().contains("not independent"));
+    }
+
+    #[test]
+    fn decomposed_notation_parses() {
+        assert_eq!(parse_decomposed_level("ASIL_B(D)"), Some((IntegrityLevel::ASIL_B, IntegrityLevel::ASIL_D)));
+        assert_eq!(parse_decomposed_level("QM(A)"), Some((IntegrityLevel::ASIL_QM, IntegrityLevel::ASIL_A)));
+        assert_eq!(parse_decomposed_level("ASIL-C(ASIL-D)"), Some((IntegrityLevel::ASIL_C, IntegrityLevel::ASIL_D)));
+        assert_eq!(parse_decomposed_level("ASIL_B"), None);
+    }
+
+    #[test]
+    fn mixed_criticality_needs_isolation_or_the_highest_level() {
+        let source = |attributes: &str| {
+            format!(
+                r#"
+model Demo {{
+}}
+
+requirements {{
+  req "SR-001" "Brake" {{ description: "Brake on demand" priority: "High" safety_level: "ASIL_D" }}
+  req "REQ-002" "Log" {{ description: "Log trips" priority: "Low" safety_level: "QM" }}
+}}
+
+architecture logical {{
+  component "Controller" {{ id: "LC-001" {} function "brake" function "log" }}
+}}
+
+trace "SR-001" allocated_to "LC-001" {{ rationale: "x" }}
+trace "brake" satisfies "REQ-002" {{ rationale: "x" }}
+"#,
+                attributes
+            )
+        };
+
+        let findings = check_interference(&model(&source("")));
+        assert_eq!(findings.len(), 1);
+        assert!(matches!(findings[0].severity, ComplianceSeverity::Critical));
+        assert_eq!(findings[0].affected_elements, elements(&["LC-001", "SR-001", "REQ-002"]));
+        assert!(findings[0].description.contains("ASIL D (SR-001) and QM (REQ-002)"), "{}", findings[0].description);
+
+        assert!(check_interference(&model(&source("isolation: \"MPU partitioning\""))).is_empty());
+        assert!(check_interference(&model(&source("safety_level: \"ASIL_D\""))).is_empty());
+    }
+}
@@ -0,0 +1,899 @@
+pub mod iso26262;
+pub mod do178c;
+pub mod iec61508;
+pub mod fmea;
+pub mod fta;
+pub mod safety_case;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyConfig {
+    pub standards: Vec<SafetyStandard>,
+    pub target_integrity_level: IntegrityLevel,
+    pub certification_authority: Option<String>,
+    pub project_safety_goals: Vec<SafetyGoal>,
+    pub fmea_config: FMEAConfig,
+    pub fta_config: FTAConfig,
+}
+
+impl SafetyConfig {
+    /// One standard at `target_integrity_level`, with no project safety
+    /// goals and the default FMEA and FTA settings.
+    pub fn new(standard: SafetyStandard, target_integrity_level: IntegrityLevel) -> Self {
+        Self {
+            standards: vec![standard],
+            target_integrity_level,
+            certification_authority: None,
+            project_safety_goals: Vec::new(),
+            fmea_config: FMEAConfig::default(),
+            fta_config: FTAConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SafetyStandard {
+    ISO26262 { edition: String },
+    DO178C { level: DO178Level },
+    IEC61508 { edition: String },
+    IEC62304,
+    MISRAC,
+    ARINC653,
+}
+
+/// Variants keep the standards' own spelling (`ASIL_D`, `DAL_A`, `SIL_3`),
+/// which is also how they serialize.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum IntegrityLevel {
+    ASIL_QM,
+    ASIL_A,
+    ASIL_B,
+    ASIL_C,
+    ASIL_D,
+    DAL_E,
+    DAL_D,
+    DAL_C,
+    DAL_B,
+    DAL_A,
+    SIL_1,
+    SIL_2,
+    SIL_3,
+    SIL_4,
+}
+
+impl IntegrityLevel {
+    /// Parse a level as written in ArcLang sources: `ASIL_D`, `ASIL-D`,
+    /// `QM`, `DAL_A`, `SIL_3`, ...
+    pub fn parse(text: &str) -> Option<Self> {
+        let normalized = text.trim().to_uppercase().replace(['-', ' '], "_");
+        let level = match normalized.as_str() {
+            "QM" | "ASIL_QM" => IntegrityLevel::ASIL_QM,
+            "ASIL_A" => IntegrityLevel::ASIL_A,
+            "ASIL_B" => IntegrityLevel::ASIL_B,
+            "ASIL_C" => IntegrityLevel::ASIL_C,
+            "ASIL_D" => IntegrityLevel::ASIL_D,
+            "DAL_E" => IntegrityLevel::DAL_E,
+            "DAL_D" => IntegrityLevel::DAL_D,
+            "DAL_C" => IntegrityLevel::DAL_C,
+            "DAL_B" => IntegrityLevel::DAL_B,
+            "DAL_A" => IntegrityLevel::DAL_A,
+            "SIL_1" => IntegrityLevel::SIL_1,
+            "SIL_2" => IntegrityLevel::SIL_2,
+            "SIL_3" => IntegrityLevel::SIL_3,
+            "SIL_4" => IntegrityLevel::SIL_4,
+            _ => return None,
+        };
+        Some(level)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DO178Level {
+    LevelA,
+    LevelB,
+    LevelC,
+    LevelD,
+    LevelE,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyGoal {
+    pub id: String,
+    pub description: String,
+    pub hazard_id: String,
+    pub integrity_level: IntegrityLevel,
+    pub functional_safety_concept: String,
+    pub verification_measures: Vec<VerificationMeasure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationMeasure {
+    pub method: VerificationMethod,
+    pub coverage_target: f64,
+    pub evidence: Vec<String>,
+}
+
+pub use crate::compiler::semantic::VerificationMethod;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FMEAConfig {
+    pub severity_scale: Vec<SeverityLevel>,
+    pub occurrence_scale: Vec<OccurrenceLevel>,
+    pub detection_scale: Vec<DetectionLevel>,
+    pub rpn_threshold: u32,
+}
+
+impl Default for FMEAConfig {
+    /// The usual 1-10 severity/occurrence/detection scales with an action
+    /// threshold of RPN 100.
+    fn default() -> Self {
+        let severity = [
+            "No effect", "Very minor", "Minor", "Very low", "Low",
+            "Moderate", "High", "Very high", "Hazardous with warning", "Hazardous without warning",
+        ];
+        let occurrence = [
+            "Remote", "Very low", "Low", "Moderately low", "Moderate",
+            "Moderately high", "High", "Very high", "Extremely high", "Almost certain",
+        ];
+        let detection = [
+            "Almost certain", "Very high", "High", "Moderately high", "Moderate",
+            "Low", "Very low", "Remote", "Very remote", "Absolute uncertainty",
+        ];
+
+        Self {
+            severity_scale: (1..).zip(severity).map(|(level, description)| SeverityLevel {
+                level,
+                description: description.to_string(),
+                criteria: String::new(),
+            }).collect(),
+            occurrence_scale: (1..).zip(occurrence).map(|(level, description)| OccurrenceLevel {
+                level,
+                description: description.to_string(),
+                probability_range: String::new(),
+            }).collect(),
+            detection_scale: (1..).zip(detection).map(|(level, description)| DetectionLevel {
+                level,
+                description: description.to_string(),
+                detection_capability: String::new(),
+            }).collect(),
+            rpn_threshold: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityLevel {
+    pub level: u32,
+    pub description: String,
+    pub criteria: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OccurrenceLevel {
+    pub level: u32,
+    pub description: String,
+    pub probability_range: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionLevel {
+    pub level: u32,
+    pub description: String,
+    pub detection_capability: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FTAConfig {
+    pub top_event: String,
+    pub cut_set_order: u32,
+    pub probability_threshold: f64,
+    pub include_common_cause: bool,
+}
+
+impl Default for FTAConfig {
+    fn default() -> Self {
+        Self {
+            top_event: String::new(),
+            cut_set_order: 4,
+            probability_threshold: 0.0,
+            include_common_cause: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyAnalysisResult {
+    pub project: String,
+    pub timestamp: DateTime<Utc>,
+    pub standards_compliance: HashMap<SafetyStandard, ComplianceStatus>,
+    pub hazards: Vec<Hazard>,
+    pub fmea_results: Vec<FMEAEntry>,
+    pub fta_results: Vec<FaultTree>,
+    pub safety_requirements: Vec<SafetyRequirement>,
+    pub verification_summary: VerificationSummary,
+    pub gaps: Vec<SafetyGap>,
+    /// The configured safety goals and the model's top-level safety
+    /// requirements.
+    #[serde(default)]
+    pub safety_goals: Vec<SafetyGoal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceStatus {
+    pub compliant: bool,
+    pub compliance_percentage: f64,
+    pub non_compliances: Vec<NonCompliance>,
+    pub recommendations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonCompliance {
+    pub clause: String,
+    pub description: String,
+    pub severity: ComplianceSeverity,
+    pub affected_elements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ComplianceSeverity {
+    Critical,
+    Major,
+    Minor,
+    Observation,
+}
+
+impl ComplianceSeverity {
+    pub const ALL: [Self; 4] = [Self::Critical, Self::Major, Self::Minor, Self::Observation];
+
+    /// Parse a severity name, ignoring case.
+    pub fn parse(text: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| format!("{:?}", s).eq_ignore_ascii_case(text.trim()))
+    }
+}
+
+/// When a compliance check fails a build (`arclang safety --gate`): any
+/// non-compliance of a `blocking` severity, or a compliance percentage
+/// under `min_percentage`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceGate {
+    pub min_percentage: f64,
+    pub blocking: Vec<ComplianceSeverity>,
+}
+
+impl Default for ComplianceGate {
+    /// Every non-compliance blocks, whatever the percentage.
+    fn default() -> Self {
+        Self { min_percentage: 0.0, blocking: ComplianceSeverity::ALL.to_vec() }
+    }
+}
+
+impl ComplianceGate {
+    /// The non-compliances of `status` that block the gate.
+    pub fn blockers<'a>(&self, status: &'a ComplianceStatus) -> Vec<&'a NonCompliance> {
+        status.non_compliances.iter().filter(|nc| self.blocking.contains(&nc.severity)).collect()
+    }
+
+    pub fn passes(&self, status: &ComplianceStatus) -> bool {
+        status.compliance_percentage >= self.min_percentage && self.blockers(status).is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hazard {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub hazard_type: HazardType,
+    pub severity: HazardSeverity,
+    pub exposure: ExposureLevel,
+    pub controllability: ControllabilityLevel,
+    pub integrity_level: IntegrityLevel,
+    pub safety_goals: Vec<String>,
+    pub mitigation_measures: Vec<MitigationMeasure>,
+    pub residual_risk: RiskLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HazardType {
+    Functional,
+    Environmental,
+    Systematic,
+    Random,
+    CommonCause,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HazardSeverity {
+    S0,
+    S1,
+    S2,
+    S3,
+}
+
+impl HazardSeverity {
+    /// `S0` to `S3`, ignoring case.
+    pub fn parse(text: &str) -> Option<Self> {
+        let severity = match text.trim().to_uppercase().as_str() {
+            "S0" => Self::S0,
+            "S1" => Self::S1,
+            "S2" => Self::S2,
+            "S3" => Self::S3,
+            _ => return None,
+        };
+        Some(severity)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ExposureLevel {
+    E0,
+    E1,
+    E2,
+    E3,
+    E4,
+}
+
+impl ExposureLevel {
+    /// `E0` to `E4`, ignoring case.
+    pub fn parse(text: &str) -> Option<Self> {
+        let exposure = match text.trim().to_uppercase().as_str() {
+            "E0" => Self::E0,
+            "E1" => Self::E1,
+            "E2" => Self::E2,
+            "E3" => Self::E3,
+            "E4" => Self::E4,
+            _ => return None,
+        };
+        Some(exposure)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ControllabilityLevel {
+    C0,
+    C1,
+    C2,
+    C3,
+}
+
+impl ControllabilityLevel {
+    /// `C0` to `C3`, ignoring case.
+    pub fn parse(text: &str) -> Option<Self> {
+        let controllability = match text.trim().to_uppercase().as_str() {
+            "C0" => Self::C0,
+            "C1" => Self::C1,
+            "C2" => Self::C2,
+            "C3" => Self::C3,
+            _ => return None,
+        };
+        Some(controllability)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MitigationMeasure {
+    pub id: String,
+    pub description: String,
+    pub measure_type: MitigationType,
+    pub effectiveness: f64,
+    pub implemented_in: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MitigationType {
+    Prevention,
+    Detection,
+    Control,
+    Redundancy,
+    Isolation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RiskLevel {
+    Acceptable,
+    Tolerable,
+    Unacceptable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FMEAEntry {
+    pub id: String,
+    pub component: String,
+    pub function: String,
+    pub failure_mode: String,
+    pub failure_cause: String,
+    pub failure_effect_local: String,
+    pub failure_effect_system: String,
+    pub severity: u32,
+    pub occurrence: u32,
+    pub detection: u32,
+    pub rpn: u32,
+    pub current_controls: Vec<String>,
+    pub recommended_actions: Vec<String>,
+    pub responsibility: String,
+    pub target_completion: Option<DateTime<Utc>>,
+    /// RPN once the occurrence/detection improvements of its recorded
+    /// actions take effect.
+    #[serde(default)]
+    pub revised_rpn: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultTree {
+    pub id: String,
+    pub top_event: FaultEvent,
+    pub gates: Vec<FaultGate>,
+    pub basic_events: Vec<BasicEvent>,
+    pub minimal_cut_sets: Vec<CutSet>,
+    pub top_event_probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultEvent {
+    pub id: String,
+    pub description: String,
+    pub event_type: FaultEventType,
+    pub probability: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FaultEventType {
+    TopEvent,
+    IntermediateEvent,
+    BasicEvent,
+    UndevelopedEvent,
+    ConditionalEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultGate {
+    pub id: String,
+    pub gate_type: GateType,
+    pub input_events: Vec<String>,
+    pub output_event: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GateType {
+    AND,
+    OR,
+    XOR,
+    NOT,
+    VOTE { k: u32, n: u32 },
+    PAND,
+    POR,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicEvent {
+    pub id: String,
+    pub description: String,
+    pub failure_rate: f64,
+    pub exposure_time: f64,
+    pub probability: f64,
+    pub diagnostic_coverage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CutSet {
+    pub events: Vec<String>,
+    pub probability: f64,
+    pub order: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyRequirement {
+    pub id: String,
+    pub derived_from: Vec<String>,
+    pub requirement_text: String,
+    pub integrity_level: IntegrityLevel,
+    pub verification_method: VerificationMethod,
+    pub verification_status: SafetyVerificationStatus,
+    pub allocated_to: Vec<String>,
+}
+
+pub use crate::compiler::semantic::VerificationStatus as SafetyVerificationStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationSummary {
+    pub total_safety_requirements: usize,
+    pub verified_requirements: usize,
+    pub coverage_by_method: HashMap<VerificationMethod, VerificationCoverage>,
+    pub coverage_by_integrity_level: HashMap<IntegrityLevel, VerificationCoverage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerificationCoverage {
+    pub total: usize,
+    pub verified: usize,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyGap {
+    pub gap_type: SafetyGapType,
+    pub severity: ComplianceSeverity,
+    pub description: String,
+    pub affected_elements: Vec<String>,
+    pub recommendation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SafetyGapType {
+    MissingHazardAnalysis,
+    MissingSafetyRequirement,
+    MissingVerification,
+    InsufficientIntegrityLevel,
+    MissingFMEA,
+    MissingFTA,
+    IncompleteMitigation,
+    NonCompliantDesign,
+    OverdueAction,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SafetyError {
+    #[error("Invalid integrity level: {0}")]
+    InvalidIntegrityLevel(String),
+    
+    #[error("Hazard not found: {0}")]
+    HazardNotFound(String),
+    
+    #[error("Safety requirement not satisfied: {0}")]
+    RequirementNotSatisfied(String),
+    
+    #[error("Insufficient verification coverage: expected {expected}%, got {actual}%")]
+    InsufficientCoverage { expected: f64, actual: f64 },
+    
+    #[error("Non-compliant with {standard}: {reason}")]
+    NonCompliant { standard: String, reason: String },
+    
+    #[error("Analysis error: {0}")]
+    AnalysisError(String),
+}
+
+pub struct SafetyAnalyzer {
+    config: SafetyConfig,
+}
+
+impl SafetyAnalyzer {
+    pub fn new(config: SafetyConfig) -> Self {
+        Self { config }
+    }
+    
+    pub fn analyze(&self, model: &crate::compiler::semantic::SemanticModel) -> Result<SafetyAnalysisResult, SafetyError> {
+        let mut result = SafetyAnalysisResult {
+            project: model.name.clone().unwrap_or_default(),
+            timestamp: Utc::now(),
+            standards_compliance: HashMap::new(),
+            hazards: Vec::new(),
+            fmea_results: Vec::new(),
+            fta_results: Vec::new(),
+            safety_requirements: Vec::new(),
+            verification_summary: VerificationSummary {
+                total_safety_requirements: 0,
+                verified_requirements: 0,
+                coverage_by_method: HashMap::new(),
+                coverage_by_integrity_level: HashMap::new(),
+            },
+            gaps: Vec::new(),
+            safety_goals: self.config.project_safety_goals.clone(),
+        };
+        
+        self.extract_hazards(model, &mut result);
+        self.extract_safety_requirements(model, &mut result)?;
+        self.extract_safety_goals(&mut result);
+        self.check_standards_compliance(model, &mut result)?;
+        self.compute_verification_summary(&mut result);
+        result.fmea_results = fmea::generate_fmea(model, &self.config.fmea_config);
+        self.identify_gaps(model, &mut result);
+        
+        Ok(result)
+    }
+    
+    /// The model's hazards, each with the requirements naming it (`hazard:`)
+    /// as safety goals and its `mitigations`. A hazard classified by
+    /// severity, exposure and controllability gets the ASIL of ISO 26262-3
+    /// Table 4; a declared level that differs from it is a gap.
+    fn extract_hazards(&self, model: &crate::compiler::semantic::SemanticModel, result: &mut SafetyAnalysisResult) {
+        for hazard in &model.hazards {
+            let declared = hazard.integrity_level.as_deref().and_then(IntegrityLevel::parse);
+            let classification = (
+                hazard.severity.as_deref().and_then(HazardSeverity::parse),
+                hazard.exposure.as_deref().and_then(ExposureLevel::parse),
+                hazard.controllability.as_deref().and_then(ControllabilityLevel::parse),
+            );
+            let (severity, exposure, controllability, integrity_level) = match classification {
+                (Some(severity), Some(exposure), Some(controllability)) => {
+                    let computed = iso26262::determine_asil(&severity, &exposure, &controllability);
+                    if let Some(declared) = declared.filter(|level| *level != computed) {
+                        result.gaps.push(SafetyGap {
+                            gap_type: SafetyGapType::InsufficientIntegrityLevel,
+                            severity: ComplianceSeverity::Major,
+                            description: format!(
+                                "Hazard {} declares {:?} but {:?}/{:?}/{:?} gives {:?} (ISO 26262-3 Table 4)",
+                                hazard.id, declared, severity, exposure, controllability, computed
+                            ),
+                            affected_elements: vec![hazard.id.clone()],
+                            recommendation: "Correct the declared ASIL or revisit the severity, exposure and controllability".to_string(),
+                        });
+                    }
+                    (severity, exposure, controllability, computed)
+                }
+                _ => (
+                    HazardSeverity::S0,
+                    ExposureLevel::E0,
+                    ControllabilityLevel::C0,
+                    declared.unwrap_or(IntegrityLevel::ASIL_QM),
+                ),
+            };
+            result.hazards.push(Hazard {
+                id: hazard.id.clone(),
+                title: hazard.name.clone(),
+                description: hazard.description.clone(),
+                hazard_type: HazardType::Functional,
+                severity,
+                exposure,
+                controllability,
+                integrity_level,
+                safety_goals: model.requirements.iter()
+                    .filter(|req| req.hazard.as_deref().is_some_and(|h| h == hazard.id || h == hazard.name))
+                    .map(|req| req.id.clone())
+                    .collect(),
+                mitigation_measures: hazard.mitigations.iter().enumerate()
+                    .map(|(i, mitigation)| MitigationMeasure {
+                        id: format!("{}-M{}", hazard.id, i + 1),
+                        description: mitigation.clone(),
+                        measure_type: MitigationType::Control,
+                        effectiveness: 0.0,
+                        implemented_in: Vec::new(),
+                    })
+                    .collect(),
+                residual_risk: RiskLevel::Tolerable,
+            });
+        }
+    }
+
+    fn extract_safety_requirements(&self, model: &crate::compiler::semantic::SemanticModel, result: &mut SafetyAnalysisResult) -> Result<(), SafetyError> {
+        use crate::compiler::semantic::TraceLinkType;
+
+        for req in &model.requirements {
+            let Some(level) = req.safety_level.as_deref() else { continue };
+
+            let verification = model.verifications.iter().find(|v| v.requirement == req.id);
+            let verified = verification.is_some()
+                || model.get_traces_to(&req.id).iter().any(|t| t.link_type == TraceLinkType::VerifiedBy);
+            let safety_req = SafetyRequirement {
+                id: req.id.clone(),
+                derived_from: model.get_traces_from(&req.id).iter()
+                    .filter(|t| matches!(t.link_type, TraceLinkType::DerivedFrom | TraceLinkType::Refines))
+                    .map(|t| t.to.clone())
+                    .collect(),
+                requirement_text: req.description.clone(),
+                integrity_level: IntegrityLevel::parse(level).unwrap_or(IntegrityLevel::ASIL_QM),
+                // The declared method and status win over what the test
+                // cases suggest.
+                verification_method: req.verification_method
+                    .or_else(|| verification.and_then(|v| VerificationMethod::parse(&v.method)))
+                    .unwrap_or(VerificationMethod::Test),
+                verification_status: req.verification_status.unwrap_or(if verified {
+                    SafetyVerificationStatus::Verified
+                } else {
+                    SafetyVerificationStatus::NotStarted
+                }),
+                allocated_to: model.get_traces_to(&req.id).iter()
+                    .filter(|t| t.trace_type == "satisfies" || t.trace_type == "implements")
+                    .map(|t| t.from.clone())
+                    .collect(),
+            };
+
+            result.safety_requirements.push(safety_req);
+        }
+
+        Ok(())
+    }
+
+    /// Safety requirements that refine no other safety requirement argue
+    /// as safety goals of their own, against the hazard naming them.
+    fn extract_safety_goals(&self, result: &mut SafetyAnalysisResult) {
+        let is_safety_requirement = |id: &str| result.safety_requirements.iter().any(|r| r.id == id);
+        let goals: Vec<SafetyGoal> = result.safety_requirements.iter()
+            .filter(|req| req.integrity_level != IntegrityLevel::ASIL_QM)
+            .filter(|req| !req.derived_from.iter().any(|parent| is_safety_requirement(parent)))
+            .filter(|req| !result.safety_goals.iter().any(|goal| goal.id == req.id))
+            .map(|req| SafetyGoal {
+                id: req.id.clone(),
+                description: req.requirement_text.clone(),
+                hazard_id: result.hazards.iter()
+                    .find(|h| h.safety_goals.contains(&req.id))
+                    .map(|h| h.id.clone())
+                    .unwrap_or_default(),
+                integrity_level: req.integrity_level.clone(),
+                functional_safety_concept: String::new(),
+                verification_measures: Vec::new(),
+            })
+            .collect();
+        result.safety_goals.extend(goals);
+    }
+
+    fn check_standards_compliance(&self, model: &crate::compiler::semantic::SemanticModel, result: &mut SafetyAnalysisResult) -> Result<(), SafetyError> {
+        for standard in &self.config.standards {
+            let compliance = match standard {
+                SafetyStandard::ISO26262 { .. } => iso26262::check_compliance(model, &self.config),
+                SafetyStandard::DO178C { .. } => do178c::check_compliance(model, &self.config),
+                SafetyStandard::IEC61508 { .. } => iec61508::check_compliance(model, &self.config),
+                _ => ComplianceStatus {
+                    compliant: false,
+                    compliance_percentage: 0.0,
+                    non_compliances: Vec::new(),
+                    recommendations: vec!["Standard not yet implemented".to_string()],
+                },
+            };
+            
+            result.standards_compliance.insert(standard.clone(), compliance);
+        }
+        
+        Ok(())
+    }
+    
+    fn compute_verification_summary(&self, result: &mut SafetyAnalysisResult) {
+        result.verification_summary.total_safety_requirements = result.safety_requirements.len();
+        result.verification_summary.verified_requirements = result.safety_requirements.iter()
+            .filter(|req| req.verification_status == SafetyVerificationStatus::Verified)
+            .count();
+
+        let summary = &mut result.verification_summary;
+        for req in &result.safety_requirements {
+            let verified = usize::from(req.verification_status == SafetyVerificationStatus::Verified);
+            for coverage in [
+                summary.coverage_by_method.entry(req.verification_method).or_default(),
+                summary.coverage_by_integrity_level.entry(req.integrity_level.clone()).or_default(),
+            ] {
+                coverage.total += 1;
+                coverage.verified += verified;
+                coverage.percentage = coverage.verified as f64 / coverage.total as f64 * 100.0;
+            }
+        }
+    }
+    
+    fn identify_gaps(&self, model: &crate::compiler::semantic::SemanticModel, result: &mut SafetyAnalysisResult) {
+        for hazard in &result.hazards {
+            if hazard.mitigation_measures.is_empty() {
+                result.gaps.push(SafetyGap {
+                    gap_type: SafetyGapType::IncompleteMitigation,
+                    severity: ComplianceSeverity::Major,
+                    description: format!("Hazard {} has no mitigation measures", hazard.id),
+                    affected_elements: vec![hazard.id.clone()],
+                    recommendation: "Define and implement mitigation measures".to_string(),
+                });
+            }
+        }
+        
+        for req in &result.safety_requirements {
+            if req.verification_status == SafetyVerificationStatus::NotStarted {
+                result.gaps.push(SafetyGap {
+                    gap_type: SafetyGapType::MissingVerification,
+                    severity: ComplianceSeverity::Major,
+                    description: format!("Safety requirement {} not verified", req.id),
+                    affected_elements: vec![req.id.clone()],
+                    recommendation: "Create and execute verification test cases".to_string(),
+                });
+            }
+        }
+
+        for action in fmea::overdue_actions(model, Utc::now().date_naive()) {
+            result.gaps.push(SafetyGap {
+                gap_type: SafetyGapType::OverdueAction,
+                severity: ComplianceSeverity::Major,
+                description: format!(
+                    "FMEA action on {} ({}) was due {} and is not closed",
+                    action.entry,
+                    action.owner.as_deref().unwrap_or("no owner"),
+                    action.due.map(|d| d.to_string()).unwrap_or_default()
+                ),
+                affected_elements: vec![action.entry.clone()],
+                recommendation: "Complete the action and mark it status: \"closed\", or re-plan its due date".to_string(),
+            });
+        }
+    }
+}
+
+pub(crate) fn has_layer(model: &crate::compiler::semantic::SemanticModel, level: &str) -> bool {
+    model.components.iter().any(|c| c.level == level)
+}
+
+pub(crate) fn has_category(req: &crate::compiler::semantic::RequirementInfo, category: &str) -> bool {
+    req.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(category))
+}
+
+/// Process work products (configuration management, safety case, ...) are
+/// evidenced in the model by requirements of that category.
+pub(crate) fn has_process_evidence(model: &crate::compiler::semantic::SemanticModel, category: &str) -> bool {
+    model.requirements.iter().any(|req| has_category(req, category))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(percentage: f64, severities: &[ComplianceSeverity]) -> ComplianceStatus {
+        ComplianceStatus {
+            compliant: severities.is_empty(),
+            compliance_percentage: percentage,
+            non_compliances: severities
+                .iter()
+                .map(|severity| NonCompliance {
+                    clause: format!("Clause {:?}", severity),
+                    description: String::new(),
+                    severity: *severity,
+                    affected_elements: Vec::new(),
+                })
+                .collect(),
+            recommendations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn the_default_gate_fails_on_any_non_compliance() {
+        let gate = ComplianceGate::default();
+        assert!(gate.passes(&status(100.0, &[])));
+        assert!(!gate.passes(&status(95.0, &[ComplianceSeverity::Observation])));
+    }
+
+    #[test]
+    fn the_gate_blocks_on_chosen_severities_and_the_threshold() {
+        let gate = ComplianceGate {
+            min_percentage: 80.0,
+            blocking: vec![ComplianceSeverity::Critical, ComplianceSeverity::Major],
+        };
+        let minor = status(90.0, &[ComplianceSeverity::Minor]);
+        assert!(gate.blockers(&minor).is_empty());
+        assert!(gate.passes(&minor));
+
+        let major = status(90.0, &[ComplianceSeverity::Minor, ComplianceSeverity::Major]);
+        let clauses: Vec<&str> = gate.blockers(&major).iter().map(|nc| nc.clause.as_str()).collect();
+        assert_eq!(clauses, vec!["Clause Major"]);
+        assert!(!gate.passes(&major));
+
+        assert!(!gate.passes(&status(75.0, &[ComplianceSeverity::Minor])));
+        assert_eq!(ComplianceSeverity::parse(" MAJOR "), Some(ComplianceSeverity::Major));
+        assert_eq!(ComplianceSeverity::parse("blocker"), None);
+    }
+
+    #[test]
+    fn classified_hazards_get_the_table_asil_and_mismatches_are_gaps() {
+        let source = r#"model Brake {
+}
+safety_analysis {
+  hazard "Unintended braking" { id: "HAZ-001" severity: "S3" exposure: "E4" controllability: "C2" }
+  hazard "Late warning" { id: "HAZ-002" severity: S2 exposure: E3 controllability: C2 asil: "ASIL-D" }
+  hazard "Glare" { id: "HAZ-003" asil: "ASIL-A" }
+}
+"#;
+        let model = crate::compiler::Compiler::new(crate::compiler::CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model;
+        let config = SafetyConfig::new(SafetyStandard::ISO26262 { edition: "2018".to_string() }, IntegrityLevel::ASIL_D);
+        let result = SafetyAnalyzer::new(config).analyze(&model).unwrap();
+
+        let level = |id: &str| result.hazards.iter().find(|h| h.id == id).unwrap().integrity_level.clone();
+        assert_eq!(level("HAZ-001"), IntegrityLevel::ASIL_C);
+        assert_eq!(level("HAZ-002"), IntegrityLevel::ASIL_A);
+        // Unclassified: the declared level stands.
+        assert_eq!(level("HAZ-003"), IntegrityLevel::ASIL_A);
+
+        let mismatches: Vec<&SafetyGap> = result
+            .gaps
+            .iter()
+            .filter(|g| matches!(g.gap_type, SafetyGapType::InsufficientIntegrityLevel))
+            .collect();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].affected_elements, vec!["HAZ-002".to_string()]);
+        assert!(mismatches[0].description.contains("declares ASIL_D but S2/E3/C2 gives ASIL_A"), "{}", mismatches[0].description);
+    }
+}
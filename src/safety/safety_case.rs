@@ -1,6 +1,5 @@
 use super::*;
 use crate::compiler::semantic::SemanticModel;
-use std::collections::HashMap;
 
 pub struct SafetyCaseBuilder {
     config: SafetyConfig,
@@ -12,9 +11,10 @@ impl SafetyCaseBuilder {
     }
     
     pub fn build(&self, model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Result<SafetyCase, SafetyError> {
+        let system_name = model.name.clone().unwrap_or_else(|| "System".to_string());
         let top_claim = SafetyClaim {
             id: "SC-TOP".to_string(),
-            claim_text: format!("{} is acceptably safe to operate", model.name()),
+            claim_text: format!("{} is acceptably safe to operate", system_name),
             claim_type: ClaimType::TopLevel,
             status: ClaimStatus::Asserted,
             evidence: Vec::new(),
@@ -22,8 +22,8 @@ impl SafetyCaseBuilder {
         };
         
         let mut safety_case = SafetyCase {
-            id: format!("SC-{}", model.name()),
-            system_name: model.name().to_string(),
+            id: format!("SC-{}", system_name),
+            system_name,
             version: "1.0".to_string(),
             created_at: Utc::now(),
             top_claim,
@@ -39,12 +39,16 @@ impl SafetyCaseBuilder {
         Ok(safety_case)
     }
     
-    fn build_context(&self, model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Vec<ContextElement> {
+    fn build_context(&self, model: &SemanticModel, _analysis: &SafetyAnalysisResult) -> Vec<ContextElement> {
         let mut context = Vec::new();
         
         context.push(ContextElement {
             id: "CTX-1".to_string(),
-            description: format!("System operates in {} domain", model.domain()),
+            description: format!(
+                "System operates as described by its operational analysis ({} missions, {} capabilities)",
+                model.missions.len(),
+                model.capabilities.len()
+            ),
             element_type: ContextType::Operational,
         });
         
@@ -65,34 +69,30 @@ impl SafetyCaseBuilder {
         context
     }
     
-    fn build_assumptions(&self, model: &SemanticModel) -> Vec<Assumption> {
-        let mut assumptions = Vec::new();
-        
-        assumptions.push(Assumption {
-            id: "ASM-1".to_string(),
-            description: "Hardware platform functions according to specification".to_string(),
-            rationale: "Hardware is certified and tested independently".to_string(),
-            validity: AssumptionValidity::Valid,
-        });
-        
-        assumptions.push(Assumption {
-            id: "ASM-2".to_string(),
-            description: "Operators are trained and follow procedures".to_string(),
-            rationale: "Training program in place and validated".to_string(),
-            validity: AssumptionValidity::Valid,
-        });
-        
-        assumptions.push(Assumption {
-            id: "ASM-3".to_string(),
-            description: "System operates within specified environmental conditions".to_string(),
-            rationale: "Environmental limits defined in operational requirements".to_string(),
-            validity: AssumptionValidity::Valid,
-        });
-        
-        assumptions
+    fn build_assumptions(&self, _model: &SemanticModel) -> Vec<Assumption> {
+        vec![
+            Assumption {
+                id: "ASM-1".to_string(),
+                description: "Hardware platform functions according to specification".to_string(),
+                rationale: "Hardware is certified and tested independently".to_string(),
+                validity: AssumptionValidity::Valid,
+            },
+            Assumption {
+                id: "ASM-2".to_string(),
+                description: "Operators are trained and follow procedures".to_string(),
+                rationale: "Training program in place and validated".to_string(),
+                validity: AssumptionValidity::Valid,
+            },
+            Assumption {
+                id: "ASM-3".to_string(),
+                description: "System operates within specified environmental conditions".to_string(),
+                rationale: "Environmental limits defined in operational requirements".to_string(),
+                validity: AssumptionValidity::Valid,
+            },
+        ]
     }
     
-    fn build_justifications(&self, model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Vec<Justification> {
+    fn build_justifications(&self, _model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Vec<Justification> {
         let mut justifications = Vec::new();
         
         justifications.push(Justification {
@@ -118,7 +118,7 @@ impl SafetyCaseBuilder {
         justifications
     }
     
-    fn collect_evidence(&self, model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Vec<Evidence> {
+    fn collect_evidence(&self, _model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Vec<Evidence> {
         let mut evidence = Vec::new();
         
         evidence.push(Evidence {
@@ -171,7 +171,7 @@ impl SafetyCaseBuilder {
         evidence
     }
     
-    fn develop_argument_structure(&self, safety_case: &mut SafetyCase, model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Result<(), SafetyError> {
+    fn develop_argument_structure(&self, safety_case: &mut SafetyCase, _model: &SemanticModel, analysis: &SafetyAnalysisResult) -> Result<(), SafetyError> {
         let mut sub_claims = Vec::new();
         
         let hazards_mitigated_claim = SafetyClaim {
@@ -367,19 +367,19 @@ pub fn export_to_gsn(safety_case: &SafetyCase) -> String {
     for ctx in &safety_case.context {
         gsn.push_str(&format!("[C-{}] {}\n", ctx.id, ctx.description));
     }
-    gsn.push_str("\n");
+    gsn.push('\n');
     
     gsn.push_str("Assumptions:\n");
     for asm in &safety_case.assumptions {
         gsn.push_str(&format!("[A-{}] {}\n", asm.id, asm.description));
     }
-    gsn.push_str("\n");
+    gsn.push('\n');
     
     gsn.push_str("Argument Structure:\n");
     for arg in &safety_case.arguments {
         gsn.push_str(&format!("[S-{}] {:?}: {}\n", arg.id, arg.argument_type, arg.description));
     }
-    gsn.push_str("\n");
+    gsn.push('\n');
     
     gsn.push_str("Sub-Goals:\n");
     for sub_claim in &safety_case.top_claim.sub_claims {
@@ -393,7 +393,7 @@ pub fn export_to_gsn(safety_case: &SafetyCase) -> String {
                 }
             }
         }
-        gsn.push_str("\n");
+        gsn.push('\n');
     }
     
     gsn
@@ -417,14 +417,14 @@ pub fn generate_safety_case_report(safety_case: &SafetyCase) -> String {
     for ctx in &safety_case.context {
         report.push_str(&format!("- {}\n", ctx.description));
     }
-    report.push_str("\n");
+    report.push('\n');
     
     report.push_str("3. Assumptions\n");
     report.push_str("--------------\n");
     for asm in &safety_case.assumptions {
         report.push_str(&format!("- {} ({})\n", asm.description, asm.rationale));
     }
-    report.push_str("\n");
+    report.push('\n');
     
     report.push_str("4. Argument Structure\n");
     report.push_str("---------------------\n");
@@ -444,3 +444,257 @@ pub fn generate_safety_case_report(safety_case: &SafetyCase) -> String {
     
     report
 }
+
+/// A Goal Structuring Notation argument built from an analysis result.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GsnGraph {
+    pub nodes: Vec<GsnNode>,
+    pub edges: Vec<GsnEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GsnNode {
+    /// Identifier safe to use as a Mermaid / DOT node id.
+    pub id: String,
+    pub kind: GsnNodeKind,
+    pub text: String,
+    /// Left for further development (a safety gap).
+    pub undeveloped: bool,
+    /// A goal with no solution anywhere beneath it.
+    pub unsupported: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GsnNodeKind {
+    Goal,
+    Strategy,
+    Solution,
+    Context,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GsnEdge {
+    pub from: String,
+    pub to: String,
+    pub relation: GsnRelation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GsnRelation {
+    SupportedBy,
+    InContextOf,
+}
+
+impl GsnGraph {
+    pub fn node(&self, id: &str) -> Option<&GsnNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Nodes `id` is supported by.
+    pub fn children(&self, id: &str) -> Vec<&GsnNode> {
+        self.edges
+            .iter()
+            .filter(|e| e.from == id && e.relation == GsnRelation::SupportedBy)
+            .filter_map(|e| self.node(&e.to))
+            .collect()
+    }
+
+    fn has_solution_below(&self, id: &str) -> bool {
+        self.children(id)
+            .iter()
+            .any(|n| n.kind == GsnNodeKind::Solution || self.has_solution_below(&n.id))
+    }
+
+    fn add(&mut self, id: String, kind: GsnNodeKind, text: String) -> String {
+        if self.node(&id).is_none() {
+            self.nodes.push(GsnNode { id: id.clone(), kind, text, undeveloped: false, unsupported: false });
+        }
+        id
+    }
+
+    fn link(&mut self, from: &str, to: &str, relation: GsnRelation) {
+        let edge = GsnEdge { from: from.to_string(), to: to.to_string(), relation };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+}
+
+fn gsn_id(prefix: &str, raw: &str) -> String {
+    let slug: String = raw.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("{}_{}", prefix, slug)
+}
+
+/// Build the GSN argument of `result`: every safety goal is a top goal in
+/// the context of its hazard, argued over the hazard's mitigations
+/// (strategies) and supported by the verified safety requirements refining
+/// it (solutions). Safety gaps become undeveloped goals, under the goal or
+/// hazard they affect when there is one.
+pub fn build_gsn(result: &SafetyAnalysisResult) -> GsnGraph {
+    let mut graph = GsnGraph::default();
+    let mut placed_gaps = vec![false; result.gaps.len()];
+
+    for goal in &result.safety_goals {
+        let goal_node = graph.add(
+            gsn_id("G", &goal.id),
+            GsnNodeKind::Goal,
+            format!("{}: {} ({:?})", goal.id, goal.description, goal.integrity_level),
+        );
+
+        let hazard = result.hazards.iter().find(|h| h.id == goal.hazard_id);
+        if let Some(hazard) = hazard {
+            let context = graph.add(
+                gsn_id("C", &hazard.id),
+                GsnNodeKind::Context,
+                format!("Hazard {}: {}", hazard.id, hazard.title),
+            );
+            graph.link(&goal_node, &context, GsnRelation::InContextOf);
+        }
+
+        let mitigations = hazard.map(|h| h.mitigation_measures.as_slice()).unwrap_or_default();
+        let strategies: Vec<(String, &MitigationMeasure)> = mitigations
+            .iter()
+            .map(|m| {
+                let node = graph.add(
+                    gsn_id("S", &m.id),
+                    GsnNodeKind::Strategy,
+                    format!("Argument over mitigation: {}", m.description),
+                );
+                graph.link(&goal_node, &node, GsnRelation::SupportedBy);
+                (node, m)
+            })
+            .collect();
+
+        let evidence = result.safety_requirements.iter().filter(|r| {
+            (r.id == goal.id || r.derived_from.contains(&goal.id))
+                && r.verification_status == SafetyVerificationStatus::Verified
+        });
+        for req in evidence {
+            let solution = graph.add(
+                gsn_id("Sn", &req.id),
+                GsnNodeKind::Solution,
+                format!("{:?} of {}", req.verification_method, req.id),
+            );
+            let parents: Vec<&String> = strategies
+                .iter()
+                .filter(|(_, m)| m.implemented_in.iter().any(|c| req.allocated_to.contains(c)))
+                .map(|(node, _)| node)
+                .collect();
+            if parents.is_empty() {
+                graph.link(&goal_node, &solution, GsnRelation::SupportedBy);
+            }
+            for parent in parents {
+                graph.link(parent, &solution, GsnRelation::SupportedBy);
+            }
+        }
+
+        for (i, gap) in result.gaps.iter().enumerate() {
+            if gap.affected_elements.iter().any(|e| *e == goal.id || *e == goal.hazard_id) {
+                let node = add_gap(&mut graph, i, gap);
+                graph.link(&goal_node, &node, GsnRelation::SupportedBy);
+                placed_gaps[i] = true;
+            }
+        }
+
+        for (strategy, _) in &strategies {
+            if graph.children(strategy).is_empty() {
+                mark(&mut graph, strategy, |n| n.undeveloped = true);
+            }
+        }
+        if !graph.has_solution_below(&goal_node) {
+            mark(&mut graph, &goal_node, |n| n.unsupported = true);
+        }
+    }
+
+    for (i, gap) in result.gaps.iter().enumerate() {
+        if !placed_gaps[i] {
+            add_gap(&mut graph, i, gap);
+        }
+    }
+
+    graph
+}
+
+fn add_gap(graph: &mut GsnGraph, index: usize, gap: &SafetyGap) -> String {
+    let node = graph.add(format!("U_{}", index + 1), GsnNodeKind::Goal, gap.description.clone());
+    mark(graph, &node, |n| n.undeveloped = true);
+    node
+}
+
+fn mark(graph: &mut GsnGraph, id: &str, apply: impl FnOnce(&mut GsnNode)) {
+    if let Some(node) = graph.nodes.iter_mut().find(|n| n.id == id) {
+        apply(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerConfig};
+
+    const BRAKES: &str = r#"
+model "Brakes" {
+}
+
+requirements {
+  req "SG-001" "No unintended braking" { description: "Avoid unintended braking" priority: "High" safety_level: "ASIL-B" hazard: "HAZ-001" }
+  req "SR-001" "Monitor" { description: "Monitor the pedal" priority: "High" safety_level: "ASIL-B" }
+  req "SG-002" "Brake on demand" { description: "Brake when asked" priority: "High" safety_level: "ASIL-C" hazard: "HAZ-002" }
+}
+
+trace "SR-001" derives_from "SG-001" { rationale: "Detects a stuck pedal" }
+
+safety_analysis {
+  hazard "Unintended braking" { id: "HAZ-001" description: "d" severity: "S3" asil: "ASIL_B" mitigations: ["Pedal plausibility check"] }
+  hazard "Loss of braking" { id: "HAZ-002" description: "d" severity: "S3" asil: "ASIL_C" }
+}
+
+test_case "Pedal HIL" {
+  id: "TC-001"
+  verifies: "SR-001"
+  method: "Test"
+}
+"#;
+
+    fn analysis(source: &str) -> SafetyAnalysisResult {
+        let model = Compiler::new(CompilerConfig::default())
+            .compile_string(source)
+            .expect("compiles")
+            .semantic_model;
+        let config = SafetyConfig::new(
+            SafetyStandard::ISO26262 { edition: "2018".to_string() },
+            IntegrityLevel::ASIL_C,
+        );
+        SafetyAnalyzer::new(config).analyze(&model).unwrap()
+    }
+
+    #[test]
+    fn goals_are_argued_over_mitigations_and_supported_by_verification() {
+        let graph = build_gsn(&analysis(BRAKES));
+
+        let goal = graph.node("G_SG_001").expect("top goal");
+        assert_eq!(goal.kind, GsnNodeKind::Goal);
+        assert!(!goal.unsupported);
+        assert!(graph.edges.contains(&GsnEdge {
+            from: "G_SG_001".to_string(),
+            to: "C_HAZ_001".to_string(),
+            relation: GsnRelation::InContextOf,
+        }));
+        let children: Vec<&str> = graph.children("G_SG_001").iter().map(|n| n.id.as_str()).collect();
+        assert!(children.contains(&"S_HAZ_001_M1"), "{:?}", children);
+        assert!(children.contains(&"Sn_SR_001"), "{:?}", children);
+        assert_eq!(graph.node("Sn_SR_001").unwrap().kind, GsnNodeKind::Solution);
+        assert!(graph.node("S_HAZ_001_M1").unwrap().undeveloped);
+    }
+
+    #[test]
+    fn goals_without_evidence_are_unsupported_and_gaps_undeveloped() {
+        let graph = build_gsn(&analysis(BRAKES));
+
+        assert!(graph.node("G_SG_002").unwrap().unsupported);
+        let gaps = graph.children("G_SG_002");
+        assert!(!gaps.is_empty());
+        assert!(gaps.iter().all(|n| n.kind == GsnNodeKind::Goal && n.undeveloped), "{:?}", gaps);
+        assert!(gaps.iter().any(|n| n.text.contains("HAZ-002")), "{:?}", gaps);
+    }
+}
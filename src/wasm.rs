@@ -0,0 +1,79 @@
+//! JavaScript entry point for running the compiler in a browser (feature
+//! `wasm32`), so a web editor validates models client-side:
+//!
+//! ```js
+//! import init, { compile_string } from "./pkg/arclang.js";
+//! await init();
+//! const { ok, diagnostics, semantic_model } = compile_string(source);
+//! ```
+//!
+//! Only the compiler core is exposed; build without `native` so the CLI, the
+//! servers and the HTTP integrations stay out of the module.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::compiler::ast;
+use crate::compiler::diagnostic::{records, DiagnosticRecord};
+use crate::compiler::semantic::SemanticModel;
+use crate::{Compiler, CompilerConfig};
+
+/// What [`compile_string`] hands to JavaScript.
+#[derive(Debug, Serialize)]
+pub struct CompileReport {
+    /// The source compiled; `diagnostics` may still hold warnings.
+    pub ok: bool,
+    /// The AST, semantic model and generated output, when it compiled.
+    pub ast: Option<ast::Model>,
+    pub semantic_model: Option<SemanticModel>,
+    pub output: Option<String>,
+    /// Errors, or warnings and traceability findings, as `arclang check
+    /// --json` reports them.
+    pub diagnostics: Vec<DiagnosticRecord>,
+}
+
+/// Compile `source` through the full pipeline.
+pub fn compile_report(source: &str) -> CompileReport {
+    let outcome = Compiler::new(CompilerConfig::default()).compile_string(source);
+    let diagnostics = records(&outcome);
+    match outcome {
+        Ok(result) => CompileReport {
+            ok: true,
+            ast: Some(result.ast),
+            semantic_model: Some(result.semantic_model),
+            output: Some(result.output),
+            diagnostics,
+        },
+        Err(_) => CompileReport { ok: false, ast: None, semantic_model: None, output: None, diagnostics },
+    }
+}
+
+/// [`compile_report`] as a plain JavaScript object (maps become objects,
+/// as with `JSON.parse`).
+#[wasm_bindgen]
+pub fn compile_string(source: &str) -> JsValue {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    compile_report(source)
+        .serialize(&serializer)
+        .unwrap_or_else(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::diagnostic::Severity;
+
+    #[test]
+    fn reports_the_model_or_the_diagnostics() {
+        let report = compile_report("system_analysis \"SA\" {\n    requirement \"REQ-001\" { description: \"brake\" }\n}\n");
+        assert!(report.ok);
+        assert_eq!(report.semantic_model.as_ref().map(|m| m.requirements.len()), Some(1));
+        assert!(report.diagnostics.iter().all(|d| d.severity == Severity::Warning));
+
+        let report = compile_report("system_analysis \"SA\" {\n    requirement\n");
+        assert!(!report.ok && report.semantic_model.is_none());
+        assert_eq!(report.diagnostics[0].code, "E-SYNTAX");
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+    }
+}
@@ -90,3 +90,37 @@ fn flagship_example_extracts_real_elements() {
         "functions must be extracted"
     );
 }
+
+#[test]
+fn all_active_examples_format_idempotently() {
+    use arclang::compiler::formatter::format_source;
+
+    let examples_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let mut files = Vec::new();
+    collect_arc_files(&examples_dir, &mut files);
+    files.sort();
+
+    let mut failures = Vec::new();
+    for file in &files {
+        let source = std::fs::read_to_string(file).expect("readable example");
+        let once = match format_source(&source) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                failures.push(format!("{}: {}", file.display(), e));
+                continue;
+            }
+        };
+        match format_source(&once) {
+            Ok(twice) if twice == once => {}
+            Ok(_) => failures.push(format!("{}: second pass changed the output", file.display())),
+            Err(e) => failures.push(format!("{}: formatted output rejected: {}", file.display(), e)),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} example(s) failed to format:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
@@ -157,6 +157,28 @@ model Test {
     );
 }
 
+#[test]
+fn test_unresolved_trace_reports_the_reference_position() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model.arc");
+    std::fs::write(
+        &path,
+        "requirements system {\n  req \"R-1\" \"A\" { description: \"a\" }\n}\ntrace \"LC-9\" satisfies \"R-1\" {}\n",
+    )
+    .unwrap();
+
+    let error = Compiler::new(CompilerConfig::default())
+        .compile_file(&path)
+        .expect_err("dangling trace must fail");
+    let diagnostics = error.diagnostics();
+
+    assert_eq!(diagnostics.len(), 1);
+    let span = diagnostics[0].span.as_ref().expect("located");
+    assert_eq!((span.line, span.column, span.len), (4, 7, 6));
+    assert_eq!(span.file.as_deref(), Some(path.as_path()));
+    assert!(diagnostics[0].render(Some(&std::fs::read_to_string(&path).unwrap())).ends_with("|       ^^^^^^\n"));
+}
+
 #[test]
 fn test_compile_multiple_architectures() {
     let input = r#"
@@ -250,6 +272,100 @@ trace "LC-001" satisfies "REQ-DOES-NOT-EXIST" { rationale: "test" }
     );
 }
 
+#[test]
+fn test_allow_dangling_downgrades_unknown_trace_endpoints_to_warnings() {
+    let input = r#"
+model Test {
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" }
+    component "Monitor" { id: "LC-002" }
+}
+
+trace "LC-001" satisfies "REQ-DOES-NOT-EXIST" { rationale: "not written yet" }
+trace "LC-002" refines "LC-001" { rationale: "kept" }
+"#;
+    let config = CompilerConfig { allow_dangling: true, ..CompilerConfig::default() };
+    let result = Compiler::new(config).compile_string(input).expect("dangling traces are warnings");
+
+    assert_eq!(result.semantic_model.traces.len(), 1);
+    let warning = result
+        .warnings
+        .iter()
+        .find(|w| w.contains("REQ-DOES-NOT-EXIST"))
+        .unwrap_or_else(|| panic!("expected a dangling trace warning, got: {:?}", result.warnings));
+    assert!(warning.contains("at line 10, column"), "{warning}");
+    assert_eq!(arclang::compiler::diagnostic::warning_code(warning), "E-TRACE-UNRESOLVED");
+}
+
+#[test]
+fn test_trace_declared_before_its_endpoints_resolves() {
+    let input = r#"
+model Test {
+}
+
+trace "LC-001" satisfies "REQ-001" { rationale: "forward reference" }
+
+requirements safety {
+    req "REQ-001" "Braking" { description: "Brake on demand" }
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" }
+}
+"#;
+    let result = Compiler::new(CompilerConfig::default())
+        .compile_string(input)
+        .expect("forward references must resolve");
+    assert_eq!(result.semantic_model.traces.len(), 1);
+    assert_eq!(result.semantic_model.traces[0].to, "REQ-001");
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+}
+
+#[test]
+fn test_custom_attributes_are_typed_and_exported() {
+    use arclang::compiler::semantic::CustomValue;
+
+    let input = r#"
+model Test {
+}
+
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "The system shall brake"
+        attr safety_classification: "SC-2"
+        attr weight: 3
+        attr reviewed: true
+        attr review_date: "2026-05-01"
+        attr tags: ["brakes", false]
+    }
+}
+
+logical_architecture "LA" {
+    component "Controller" {
+        id: "LC-001"
+        attr supplier: "Acme"
+    }
+}
+"#;
+    let config = CompilerConfig { target: "json".to_string(), ..CompilerConfig::default() };
+    let result = Compiler::new(config).compile_string(input).expect("custom attributes compile");
+
+    let custom = &result.semantic_model.all_elements["REQ-001"].custom_attributes;
+    assert_eq!(custom["safety_classification"], CustomValue::String("SC-2".to_string()));
+    assert_eq!(custom["weight"], CustomValue::Number(3.0));
+    assert_eq!(custom["reviewed"], CustomValue::Boolean(true));
+    assert_eq!(custom["review_date"], CustomValue::Date(chrono::NaiveDate::from_ymd_opt(2026, 5, 1).unwrap()));
+    assert_eq!(
+        custom["tags"],
+        CustomValue::List(vec![CustomValue::String("brakes".to_string()), CustomValue::Boolean(false)])
+    );
+    let json: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+    assert_eq!(json["requirements"][0]["custom_attributes"]["review_date"], "2026-05-01");
+    assert_eq!(json["components"][0]["custom_attributes"]["supplier"], "Acme");
+}
+
 #[test]
 fn test_trace_by_name_is_normalized_to_id() {
     let input = r#"
@@ -434,6 +550,69 @@ trace "LC-001" satisfies "REQ-001" { rationale: "direct" }
     assert_eq!(actuator.depth, 2);
 }
 
+#[test]
+fn test_scoped_model_keeps_only_elements_within_depth() {
+    let input = r#"
+model Test {
+}
+
+requirements safety {
+    req "REQ-001" "Braking" { description: "Brake on demand" }
+    req "REQ-002" "Logging" { description: "Log events" }
+}
+
+system_analysis SA {
+    functional_exchange Flow1 {
+        from: "LC-001"
+        to: "LC-002"
+        exchange_item: "command"
+    }
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" }
+    component "Actuator" { id: "LC-002" }
+    component "Logger" { id: "LC-003" }
+}
+
+trace "LC-001" satisfies "REQ-001" { rationale: "direct" }
+trace "LC-003" satisfies "REQ-002" { rationale: "direct" }
+"#;
+    let result = Compiler::new(CompilerConfig::default())
+        .compile_string(input)
+        .expect("compiles");
+    let model = result.semantic_model;
+
+    let near = model.scoped("REQ-001", 1).expect("element resolves");
+    let ids: Vec<&str> = near.components.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["LC-001"]);
+    assert_eq!(near.requirements.len(), 1);
+    assert_eq!(near.traces.len(), 1);
+    assert!(near.interfaces.is_empty(), "the exchange leaves the scope");
+
+    let wider = model.scoped("REQ-001", 2).expect("element resolves");
+    let ids: Vec<&str> = wider.components.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["LC-001", "LC-002"]);
+    assert_eq!(wider.interfaces.len(), 1);
+
+    let by_name = model.scoped("Logger", 1).expect("names resolve too");
+    assert_eq!(by_name.requirements.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec!["REQ-002"]);
+    assert!(model.scoped("REQ-404", 1).is_none());
+
+    // The AST the Capella diagrams draw is pruned to the same scope
+    let ast = model.scoped_ast(&result.ast, "REQ-001", 1).expect("element resolves");
+    let ids: Vec<&str> = ast.logical_architecture[0].components.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["LC-001"]);
+    assert_eq!(ast.system_analysis.iter().map(|sa| sa.requirements.len()).sum::<usize>(), 1);
+    assert_eq!(ast.traces.len(), 1);
+    assert!(ast.system_analysis.iter().all(|sa| sa.functional_exchanges.is_empty()));
+
+    let ast = model.scoped_ast(&result.ast, "REQ-001", 2).expect("element resolves");
+    assert_eq!(ast.logical_architecture[0].components.len(), 2);
+    assert_eq!(ast.system_analysis.iter().map(|sa| sa.functional_exchanges.len()).sum::<usize>(), 1);
+    assert!(model.scoped_ast(&result.ast, "REQ-404", 1).is_none());
+}
+
 #[test]
 fn test_missions_capabilities_and_chains_are_first_class() {
     let input = r#"
@@ -970,6 +1149,329 @@ fn test_multifile_missing_import_is_a_localized_error() {
     assert!(message.contains("nope.arc"), "must name the missing file: {message}");
 }
 
+#[test]
+fn test_multifile_include_resolves_cross_file_trace() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("arch")).unwrap();
+    std::fs::write(dir.path().join("main.arc"),
+        "include \"arch/logical.arc\"\nrequirements system { req \"R-1\" \"Brake\" { description: \"b\" } }\n").unwrap();
+    std::fs::write(dir.path().join("arch/logical.arc"),
+        "architecture logical { component \"Brake\" { id: \"LC-1\" } }\ntrace \"R-1\" allocated_to \"LC-1\" {}\n").unwrap();
+
+    let mut compiler = Compiler::new(CompilerConfig::default());
+    let result = compiler.compile_file(dir.path().join("main.arc")).expect("included model compiles");
+
+    assert_eq!(result.semantic_model.requirements.len(), 1);
+    assert_eq!(result.semantic_model.components.len(), 1);
+    assert_eq!(result.semantic_model.traces.len(), 1, "trace from the included file resolves into the root");
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+}
+
+#[test]
+fn test_multifile_duplicate_id_across_files_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("main.arc"),
+        "include \"other.arc\"\nrequirements system { req \"R-1\" \"Brake\" { description: \"b\" } }\narchitecture logical { component \"Brake\" { id: \"LC-1\" } }\n").unwrap();
+    // Same requirement id, and a differently named component reusing the id;
+    // a component merely sharing the name "Brake" is fine.
+    std::fs::write(dir.path().join("other.arc"),
+        "requirements system { req \"R-1\" \"Brake\" { description: \"b\" } }\narchitecture physical { component \"Brake\" { id: \"PC-1\" } }\n").unwrap();
+
+    let mut compiler = Compiler::new(CompilerConfig::default());
+    let result = compiler.compile_file(dir.path().join("main.arc")).expect("duplicates are warnings");
+
+    let duplicates: Vec<&String> = result.warnings.iter()
+        .filter(|w| w.starts_with("duplicate element id"))
+        .collect();
+    assert_eq!(duplicates.len(), 1, "{:?}", result.warnings);
+    assert!(duplicates[0].contains("'R-1'") && duplicates[0].contains("main.arc:2:")
+        && duplicates[0].contains("other.arc:1:"), "{}", duplicates[0]);
+}
+
+#[test]
+fn test_parallel_parse_matches_the_serial_parse() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |name: &str, source: &str| std::fs::write(dir.path().join(name), source).unwrap();
+    // `shared.arc` is imported twice (diamond) and redeclares an id
+    write("main.arc", "import \"left.arc\"\nimport \"right.arc\"\nrequirements system { req \"R-1\" \"Brake\" { description: \"b\" } }\n");
+    write("left.arc", "import \"shared.arc\"\narchitecture logical { component \"Brake\" { id: \"LC-1\" } }\ntrace \"LC-1\" satisfies \"R-1\" {}\n");
+    write("right.arc", "import \"shared.arc\"\narchitecture logical { component \"Pedal\" { id: \"LC-2\" } }\n");
+    write("shared.arc", "requirements system { req \"R-1\" \"Brake\" { description: \"b\" } req \"R-2\" \"Warn\" { description: \"w\" } }\n");
+
+    let compile = |enable_parallel: bool, num_threads: usize| {
+        Compiler::new(CompilerConfig { enable_parallel, num_threads, ..CompilerConfig::default() })
+            .compile_file(dir.path().join("main.arc"))
+    };
+    let serial = compile(false, 0).expect("serial parse");
+    assert!(serial.parse_stats.is_none());
+    for threads in [1, 4] {
+        let parallel = compile(true, threads).expect("parallel parse");
+        assert_eq!(parallel.output, serial.output);
+        assert_eq!(parallel.warnings, serial.warnings);
+        let stats = parallel.parse_stats.expect("parallel parses report their timings");
+        assert_eq!((stats.files, stats.threads), (4, threads));
+    }
+
+    // The same first error, whichever file fails to parse first
+    write("right.arc", "import \"main.arc\"\n");
+    write("shared.arc", "requirements system { req");
+    let serial = compile(false, 0).expect_err("broken import").to_string();
+    let parallel = compile(true, 4).expect_err("broken import").to_string();
+    assert_eq!(parallel, serial);
+}
+
+#[test]
+fn test_profile_times_each_phase_only_when_asked() {
+    let source = "requirements { req \"R-1\" { description: \"a\" } req \"R-2\" { description: \"b\" } }\n";
+    let result = Compiler::new(CompilerConfig::default()).compile_string(source).unwrap();
+    assert!(result.profile.is_none());
+
+    let result = Compiler::new(CompilerConfig { profile: true, ..CompilerConfig::default() })
+        .compile_string(source)
+        .unwrap();
+    let profile = result.profile.expect("profiled");
+    let phases: Vec<_> = profile.phases.iter().map(|p| p.phase.as_str()).collect();
+    assert_eq!(phases, ["parse", "semantic analysis", "codegen"]);
+    assert_eq!(profile.elements["requirements"], 2);
+    let json: serde_json::Value = serde_json::from_str(&profile.to_json()).unwrap();
+    assert_eq!(json["phases"].as_array().unwrap().len(), 3);
+    assert!(json["total_ms"].as_f64().unwrap() >= 0.0);
+}
+
+#[test]
+fn test_incremental_recompiles_only_changed_file_and_dependents() {
+    use arclang::compiler::incremental::{CacheStrategy, IncrementalCompiler, IncrementalConfig};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    std::fs::write(path("base.arc"), "requirements { req \"R-BASE\" { description: \"base\" } }\n").unwrap();
+    std::fs::write(path("root.arc"), "import \"base.arc\"\nrequirements { req \"R-ROOT\" { description: \"root\" } }\n\
+        trace \"R-ROOT\" derives_from \"R-BASE\" { rationale: \"refines\" }\n").unwrap();
+    std::fs::write(path("other.arc"), "requirements { req \"R-OTHER\" { description: \"other\" } }\n").unwrap();
+
+    let mut compiler = IncrementalCompiler::new(IncrementalConfig {
+        cache_dir: dir.path().join(".cache"),
+        max_cache_size_mb: 16,
+        enable_parallel: false,
+        num_threads: 1,
+        cache_strategy: CacheStrategy::ContentBased,
+        global_store_dir: None,
+    })
+    .unwrap();
+
+    let all = [path("base.arc"), path("root.arc"), path("other.arc")];
+    let first = compiler.compile_incremental(&all).expect("initial build");
+    assert_eq!(first.compiled_files.len(), 3);
+
+    std::fs::write(path("base.arc"), "requirements { req \"R-BASE\" { description: \"changed\" } }\n").unwrap();
+    let second = compiler.compile_incremental(&[path("base.arc")]).expect("incremental build");
+
+    let mut compiled = second.compiled_files.clone();
+    compiled.sort();
+    assert_eq!(compiled, vec![path("base.arc"), path("root.arc")]);
+    assert_eq!(second.cached_files, vec![path("other.arc")]);
+    assert!(second.cache_size_bytes > 0);
+    let reasons: Vec<_> = second.invalidations.iter().map(|i| i.reason.to_string()).collect();
+    assert_eq!(reasons, ["content changed".to_string(), format!("uses R-BASE changed in {}", path("base.arc"))]);
+}
+
+#[test]
+fn test_incremental_starts_over_from_a_cache_in_the_old_format() {
+    use arclang::compiler::incremental::{CacheStrategy, IncrementalCompiler, IncrementalConfig};
+    use chrono::{DateTime, Utc};
+    use std::collections::HashMap;
+
+    // The layout before entries recorded `last_accessed`
+    #[derive(serde::Serialize)]
+    struct OldEntry {
+        file_path: String,
+        content_hash: String,
+        timestamp: DateTime<Utc>,
+        compiled_at: DateTime<Utc>,
+        artifacts: Vec<()>,
+        dependencies: Vec<String>,
+        symbols_exported: Vec<String>,
+        symbols_imported: Vec<String>,
+    }
+    #[derive(serde::Serialize)]
+    struct OldCache {
+        version: String,
+        entries: HashMap<String, OldEntry>,
+        dependency_graph: (HashMap<String, ()>, Vec<()>),
+        last_full_build: Option<DateTime<Utc>>,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("a.arc").to_string_lossy().into_owned();
+    let content = "requirements { req \"R-A\" { description: \"a\" } }\n";
+    std::fs::write(&file, content).unwrap();
+    let content_hash = {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(content))
+    };
+    let old = OldCache {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        entries: HashMap::from([(file.clone(), OldEntry {
+            file_path: file.clone(),
+            content_hash,
+            timestamp: Utc::now(),
+            compiled_at: Utc::now(),
+            artifacts: Vec::new(),
+            dependencies: Vec::new(),
+            symbols_exported: vec!["R-A".to_string()],
+            symbols_imported: Vec::new(),
+        })]),
+        dependency_graph: (HashMap::new(), Vec::new()),
+        last_full_build: Some(Utc::now()),
+    };
+    std::fs::create_dir_all(dir.path().join(".cache")).unwrap();
+    std::fs::write(dir.path().join(".cache/compilation_cache.bin"), bincode::serialize(&old).unwrap()).unwrap();
+
+    let mut compiler = IncrementalCompiler::new(IncrementalConfig {
+        cache_dir: dir.path().join(".cache"),
+        max_cache_size_mb: 16,
+        enable_parallel: false,
+        num_threads: 1,
+        cache_strategy: CacheStrategy::ContentBased,
+        global_store_dir: None,
+    })
+    .expect("an old cache is dropped, not an error");
+    let result = compiler.compile_incremental(std::slice::from_ref(&file)).expect("build");
+    assert_eq!(result.compiled_files, vec![file]);
+    assert!(result.cached_files.is_empty());
+}
+
+#[test]
+fn test_incremental_skips_dependents_not_using_a_changed_symbol() {
+    use arclang::compiler::incremental::invalidation::InvalidationReason;
+    use arclang::compiler::incremental::{CacheStrategy, IncrementalCompiler, IncrementalConfig};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let base = |a: &str| format!("requirements {{\n    req \"R-A\" {{ description: \"{a}\" }}\n    req \"R-B\" {{ description: \"b\" }}\n}}\n");
+    std::fs::write(path("base.arc"), base("a")).unwrap();
+    std::fs::write(path("uses_a.arc"), "import \"base.arc\"\nrequirements { req \"R-UA\" { description: \"ua\" } }\n\
+        trace \"R-UA\" derives_from \"R-A\" { rationale: \"r\" }\n").unwrap();
+    std::fs::write(path("uses_b.arc"), "import \"base.arc\"\nrequirements { req \"R-UB\" { description: \"ub\" } }\n\
+        trace \"R-UB\" derives_from \"R-B\" { rationale: \"r\" }\n").unwrap();
+
+    let mut compiler = IncrementalCompiler::new(IncrementalConfig {
+        cache_dir: dir.path().join(".cache"),
+        max_cache_size_mb: 16,
+        enable_parallel: false,
+        num_threads: 1,
+        cache_strategy: CacheStrategy::ContentBased,
+        global_store_dir: None,
+    })
+    .unwrap();
+    compiler.compile_incremental(&[path("uses_a.arc"), path("uses_b.arc")]).expect("initial build");
+
+    // Only R-A changes: the file tracing R-B keeps its artifacts
+    std::fs::write(path("base.arc"), base("changed")).unwrap();
+    let result = compiler.compile_incremental(&[path("base.arc")]).expect("incremental build");
+    let mut compiled = result.compiled_files.clone();
+    compiled.sort();
+    assert_eq!(compiled, vec![path("base.arc"), path("uses_a.arc")]);
+    assert_eq!(result.invalidations[1].file, path("uses_a.arc"));
+    assert_eq!(
+        result.invalidations[1].reason,
+        InvalidationReason::DependencyChanged { dependency: path("base.arc"), symbols: vec!["R-A".to_string()] }
+    );
+
+    // Its model still sees the new R-A through the import
+    let model = compiler.cached_semantic_model(&path("uses_b.arc")).unwrap();
+    let a = model.requirements.iter().find(|r| r.id == "R-A").unwrap();
+    assert_eq!(a.description, "changed");
+
+    // Removing R-B reaches its user, which no longer resolves
+    std::fs::write(path("base.arc"), "requirements { req \"R-A\" { description: \"changed\" } }\n").unwrap();
+    let error = compiler.compile_incremental(&[path("base.arc")]).expect_err("R-B is gone").to_string();
+    assert!(error.contains("uses_b.arc") && error.contains("unknown element 'R-B'"), "{error}");
+}
+
+#[test]
+fn test_incremental_build_from_empty_cache_matches_full_compile() {
+    use arclang::compiler::incremental::{CacheStrategy, IncrementalCompiler, IncrementalConfig};
+
+    let main = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples/multifile/main.arc")
+        .canonicalize()
+        .unwrap();
+    let root = main.to_string_lossy().into_owned();
+    let cache = tempfile::tempdir().unwrap();
+    let mut compiler = IncrementalCompiler::new(IncrementalConfig {
+        cache_dir: cache.path().to_path_buf(),
+        max_cache_size_mb: 16,
+        enable_parallel: false,
+        num_threads: 1,
+        cache_strategy: CacheStrategy::ContentBased,
+        global_store_dir: None,
+    })
+    .unwrap();
+
+    // No cache: only the root is known, and compiling it pulls in its imports.
+    assert_eq!(compiler.stale_files(&root).unwrap(), vec![root.clone()]);
+    let result = compiler.compile_incremental(&[root.clone()]).unwrap();
+    assert_eq!(result.compiled_files.len(), 3);
+    assert!(compiler.stale_files(&root).unwrap().is_empty());
+
+    let cached = compiler.cached_semantic_model(&root).unwrap();
+    let fresh = Compiler::new(CompilerConfig::default()).compile_file(&main).unwrap();
+    assert_eq!(
+        serde_json::to_value(&cached).unwrap(),
+        serde_json::to_value(&fresh.semantic_model).unwrap()
+    );
+}
+
+#[test]
+fn test_global_artifact_store_shares_an_included_library_across_projects() {
+    use arclang::compiler::incremental::artifact_store::ArtifactStore;
+    use arclang::compiler::incremental::{CacheStrategy, IncrementalCompiler, IncrementalConfig};
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("store");
+    let build = |project: &str| {
+        let root = dir.path().join(project);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("lib.arc"), "requirements system { req \"LIB-1\" \"Shared\" { description: \"s\" } }\n").unwrap();
+        std::fs::write(root.join("main.arc"),
+            format!("import \"lib.arc\"\nrequirements system {{ req \"{project}-1\" \"Own\" {{ description: \"o\" }} }}\n")).unwrap();
+        let main = root.join("main.arc").canonicalize().unwrap().to_string_lossy().into_owned();
+        let mut compiler = IncrementalCompiler::new(IncrementalConfig {
+            cache_dir: root.join(".cache"),
+            max_cache_size_mb: 16,
+            enable_parallel: false,
+            num_threads: 1,
+            cache_strategy: CacheStrategy::ContentBased,
+            global_store_dir: Some(store.clone()),
+        })
+        .unwrap();
+        let result = compiler.compile_incremental(&[main.clone()]).unwrap();
+        let model = compiler.cached_semantic_model(&main).unwrap();
+        (result, model)
+    };
+
+    let (first, _) = build("A");
+    assert!(first.global_store_hits.is_empty());
+    let (second, model) = build("B");
+    let hits: Vec<_> = second.global_store_hits.iter().map(|f| f.rsplit('/').next().unwrap()).collect();
+    assert_eq!(hits, ["lib.arc"], "the library compiled by A is reused");
+    let ids: Vec<_> = model.requirements.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["B-1", "LIB-1"]);
+
+    // Both caches use the library's blob; removing both frees it
+    let store = ArtifactStore::open(&store).unwrap();
+    let lib_hash = {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(std::fs::read(dir.path().join("A/lib.arc")).unwrap()))
+    };
+    assert_eq!(store.ref_count(&lib_hash), 2);
+    std::fs::remove_dir_all(dir.path().join("A/.cache")).unwrap();
+    std::fs::remove_dir_all(dir.path().join("B/.cache")).unwrap();
+    assert_eq!(store.gc().unwrap().blobs, 1);
+    assert!(!store.contains(&lib_hash));
+}
+
 #[test]
 fn test_string_compile_with_imports_fails_honestly() {
     let mut compiler = Compiler::new(CompilerConfig::default());
@@ -978,3 +1480,546 @@ fn test_string_compile_with_imports_fails_honestly() {
         .expect_err("string compile cannot resolve imports");
     assert!(err.to_string().contains("compile it from its file"), "got: {err}");
 }
+
+#[test]
+fn test_physical_nodes_render_as_their_own_layer() {
+    let input = r#"
+architecture logical {
+    component "Controller" { id: "LC-001" }
+}
+
+physical_architecture "PA" {
+    node "Main ECU" {
+        deploys "LC-001"
+    }
+}
+"#;
+    let mut compiler = Compiler::new(CompilerConfig { optimization_level: 0, target: "capella".to_string(), ..CompilerConfig::default() });
+    let result = compiler.compile_string(input).expect("model compiles");
+
+    let physical = result.output.split("pa:PhysicalArchitecture").nth(1).expect("a physical layer");
+    assert!(physical.contains("name=\"Main ECU\" sid=\"Main ECU\" nature=\"NODE\""), "{}", result.output);
+    assert!(physical.contains("pa.deployment:PartDeploymentLink"), "{}", result.output);
+    let logical = result.output.split("pa:PhysicalArchitecture").next().unwrap();
+    assert!(!logical.contains("Main ECU"), "nodes are not logical components");
+
+    let mermaid = arclang::compiler::mermaid_generator::generate_mermaid_flowchart(&result.semantic_model, "T", "elk", Default::default())
+        .unwrap();
+    assert!(mermaid.contains("subgraph physicalLayer[\"Physical Architecture\"]"), "{mermaid}");
+    assert!(mermaid.contains("subgraph Main_ECU[\"Main ECU\"]"), "{mermaid}");
+    assert!(mermaid.contains("LC-001[\"LC-001<br>Controller\"]:::physical"), "{mermaid}");
+}
+
+#[test]
+fn test_mermaid_flowchart_groups_layers_and_marks_cross_layer_traces() {
+    use arclang::compiler::mermaid_generator::{generate_mermaid_flowchart, FlowDirection};
+    let input = r#"
+operational_analysis "Ops" {
+    actor "Driver" { id: "OA-001" }
+}
+
+system_analysis "S" {
+    requirement "SYS-001" { description: "Brake on demand" }
+}
+
+logical_architecture "L" {
+    component "Controller" { id: "LC-001" }
+    component "Monitor" { id: "LC-002" }
+}
+
+trace "SYS-001" allocated_to "LC-001" { rationale: "r" }
+trace "LC-002" implements "LC-001" { rationale: "r" }
+"#;
+    let model = Compiler::new(CompilerConfig::default())
+        .compile_string(input)
+        .expect("model compiles")
+        .semantic_model;
+
+    let mermaid = generate_mermaid_flowchart(&model, "T", "elk", FlowDirection::LeftRight).unwrap();
+
+    assert!(mermaid.contains("flowchart LR\n"), "{mermaid}");
+    let operational = mermaid.find("subgraph operationalLayer[\"Operational Analysis\"]").expect("operational layer");
+    let system = mermaid.find("subgraph systemLayer[\"System Analysis\"]").expect("system layer");
+    let logical = mermaid.find("subgraph logicalLayer[\"Logical Architecture\"]").expect("logical layer");
+    assert!(operational < system && system < logical, "{mermaid}");
+    assert!(mermaid.contains("OA-001[\"OA-001<br>Driver\"]:::actor"), "{mermaid}");
+    assert!(mermaid.contains("LC-001[\"LC-001<br>Controller\"]:::component"), "{mermaid}");
+    assert!(mermaid.contains("SYS-001 -.->|allocated_to| LC-001"), "{mermaid}");
+    assert!(mermaid.contains("LC-002 -->|implements| LC-001"), "{mermaid}");
+    assert!(mermaid.contains("linkStyle 0 stroke:"), "{mermaid}");
+}
+
+#[test]
+fn test_functional_chain_sequence_links_and_gaps() {
+    let input = r#"
+model Test {
+}
+
+system_analysis SA {
+    function Acquire { id: "F1" outputs: ["raw: Frame"] }
+    function Fuse { id: "F2" inputs: ["raw"] }
+    function Decide { id: "F3" }
+    function Brake { id: "F4" }
+
+    functional_exchange "decision" {
+        from: "F3"
+        to: "F4"
+    }
+
+    functional_chain BrakeChain {
+        id: "FC-001"
+        sequence: ["F1", "F2", "F3", "F4"]
+    }
+}
+"#;
+    let result = Compiler::new(CompilerConfig::default())
+        .compile_string(input)
+        .expect("must compile");
+    let chain = &result.semantic_model.functional_chains[0];
+
+    assert_eq!(chain.involves, vec!["F1", "F2", "F3", "F4"]);
+    let exchanges: Vec<Option<&str>> = chain.links.iter().map(|l| l.exchange.as_deref()).collect();
+    assert_eq!(exchanges, vec![Some("raw"), None, Some("decision")]);
+
+    let gaps: Vec<String> = result
+        .semantic_model
+        .traceability_issues()
+        .into_iter()
+        .filter(|issue| issue.code == "E-CHAIN-GAP")
+        .map(|issue| issue.diagnostic.message)
+        .collect();
+    assert_eq!(gaps, vec!["Functional chain FC-001: no data exchange from F2 to F3"]);
+
+    let mermaid = arclang::compiler::mermaid_generator::generate_mermaid_functional_chains(
+        &result.semantic_model,
+        "Chains",
+    )
+    .unwrap();
+    assert!(mermaid.contains(" subgraph FC_001[\"BrakeChain\"]"), "{mermaid}");
+    assert!(mermaid.contains("FC_001_0[\"F1<br>Acquire\"]"), "{mermaid}");
+    assert!(mermaid.contains("FC_001_0 -->|\"raw\"| FC_001_1"), "{mermaid}");
+    assert!(mermaid.contains("FC_001_1 -.->|\"no exchange\"| FC_001_2"), "{mermaid}");
+}
+
+#[test]
+fn test_functional_chain_steps_must_be_functions() {
+    let input = r#"
+model Test {
+}
+
+system_analysis SA {
+    function Acquire { id: "F1" }
+    functional_chain Chain {
+        id: "FC-001"
+        sequence: ["F1", "LC-001"]
+    }
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" }
+}
+"#;
+    let result = Compiler::new(CompilerConfig::default())
+        .compile_string(input)
+        .expect("must compile");
+
+    let issues = result.semantic_model.traceability_issues();
+    let step = issues.iter().find(|issue| issue.code == "E-CHAIN-STEP").expect("non-function step flagged");
+    assert!(step.diagnostic.message.contains("LC-001, a Component"), "{}", step.diagnostic.message);
+    assert!(!issues.iter().any(|issue| issue.code == "E-CHAIN-GAP"));
+}
+
+#[test]
+fn test_coverage_gaps_classify_requirements_and_components() {
+    use arclang::compiler::semantic::{GapSeverity, GapType};
+
+    let input = r#"
+model Test {
+}
+
+requirements system {
+    req "REQ-001" "Brake" { description: "Brake on demand" safety_level: "ASIL-B" }
+    req "REQ-002" "Warn" { description: "Warn the driver" }
+    req "REQ-003" "Log" { description: "Log events" }
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" }
+    component "Display" { id: "LC-002" }
+    component "Spare" { id: "LC-003" }
+}
+
+trace "LC-001" satisfies "REQ-001" {}
+trace "REQ-002" allocated_to "LC-002" {}
+test_case "TC-001" { verifies: ["REQ-002", "Log"] method: "test" }
+"#;
+    let result = Compiler::new(CompilerConfig::default())
+        .compile_string(input)
+        .expect("must compile");
+    let gaps = result.semantic_model.coverage_gaps();
+
+    let summary: Vec<(&str, GapType, GapSeverity)> = gaps
+        .iter()
+        .map(|g| (g.requirement_id.as_str(), g.gap_type, g.severity))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("REQ-001", GapType::NoVerification, GapSeverity::High),
+            ("REQ-003", GapType::NoTraceToDesign, GapSeverity::Medium),
+            ("LC-003", GapType::NoRequirement, GapSeverity::Low),
+        ]
+    );
+    assert_eq!(gaps[0].description, "Requirement REQ-001 (ASIL-B) is verified by no test case");
+}
+
+#[test]
+fn test_exports_are_byte_identical_across_compilations() {
+    use arclang::compiler::mermaid_generator::{generate_mermaid_flowchart, FlowDirection};
+    use arclang::compiler::plantuml_generator::generate_plantuml_component;
+    use arclang::compiler::yaml::export_yaml;
+
+    let input = r#"
+model Test {
+    metadata { version: "1.0" author: "Chassis" domain: "Braking" }
+}
+
+requirements system {
+    req "REQ-001" "Brake" { description: "Brake on demand" priority: High safety_level: "ASIL-B" attr owner: "A" attr review: "R1" }
+    req "REQ-002" "Warn" { description: "Warn the driver" attr owner: "B" }
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" description: "d" level: "Logical" }
+    component "Display" { id: "LC-002" }
+}
+
+class Frame {
+    id: "CL-001"
+    range_m: "float"
+    azimuth_deg: "float"
+    velocity_mps: "float"
+}
+
+trace "LC-001" satisfies "REQ-001" {}
+trace "LC-002" satisfies "REQ-002" {}
+"#;
+    // Hash maps are seeded per instance: every compilation iterates them
+    // in a different order.
+    let exports = || {
+        let result = Compiler::new(CompilerConfig { target: "markdown".to_string(), ..CompilerConfig::default() })
+            .compile_string(input)
+            .expect("must compile");
+        let model = &result.semantic_model;
+        vec![
+            result.ast.to_json().unwrap(),
+            export_yaml(model).unwrap(),
+            generate_mermaid_flowchart(model, "Test", "elk", FlowDirection::TopBottom).unwrap(),
+            generate_plantuml_component(model, "Test").unwrap(),
+            result.output,
+        ]
+    };
+    let first = exports();
+    for _ in 0..5 {
+        assert_eq!(exports(), first);
+    }
+}
+
+#[test]
+fn test_yaml_export_does_not_depend_on_declaration_order() {
+    use arclang::compiler::yaml::export_yaml;
+
+    let requirements = [
+        "    req \"REQ-001\" \"Brake\" { description: \"Brake on demand\" }\n",
+        "    req \"REQ-002\" \"Warn\" { description: \"Warn the driver\" }\n",
+    ];
+    let components = [
+        "    component \"Controller\" { id: \"LC-001\" }\n",
+        "    component \"Display\" { id: \"LC-002\" }\n",
+    ];
+    let traces = [
+        "trace \"LC-001\" satisfies \"REQ-001\" {}\n",
+        "trace \"LC-002\" satisfies \"REQ-002\" {}\n",
+    ];
+    let export = |order: [usize; 2]| {
+        let source = format!(
+            "requirements system {{\n{}{}}}\narchitecture logical {{\n{}{}}}\n{}{}",
+            requirements[order[0]], requirements[order[1]],
+            components[order[0]], components[order[1]],
+            traces[order[0]], traces[order[1]],
+        );
+        let result = Compiler::new(CompilerConfig::default()).compile_string(&source).expect("must compile");
+        (export_yaml(&result.semantic_model).unwrap(), serde_json::to_string(&result.semantic_model).unwrap())
+    };
+    assert_eq!(export([0, 1]), export([1, 0]));
+}
+
+#[test]
+fn test_markdown_report_links_elements_by_anchor() {
+    let input = r#"
+model Demo {
+}
+
+system_analysis "SA" {
+    requirement "REQ-001" { description: "The vehicle shall stop" }
+    requirement "REQ_001" { description: "The controller shall brake" }
+}
+
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+    component "Metrics" { id: "METRICS" }
+}
+
+trace "REQ_001" refines "REQ-001" {}
+trace "LC-001" satisfies "REQ_001" {}
+"#;
+    let md = Compiler::new(CompilerConfig { target: "markdown".to_string(), ..CompilerConfig::default() })
+        .compile_string(input)
+        .expect("must compile")
+        .output;
+
+    // Contents first, then the summary table
+    let contents = md.find("## Contents").expect("table of contents");
+    let summary = md.find("## Traceability Summary").expect("traceability summary");
+    assert!(contents < summary && summary < md.find("## Metrics").unwrap());
+    assert!(md.contains("- [Logical Architecture](#logical-architecture)\n  - [Controller](#lc-001)\n"));
+    assert!(md.contains("| [REQ_001](#req-001-1) | [REQ-001](#req-001) | [LC-001](#lc-001) | - | NotStarted |"));
+
+    // Anchors are unique: colliding slugs get a suffix, sections come first
+    assert!(md.contains("| <a id=\"req-001\"></a>REQ-001 |"));
+    assert!(md.contains("| <a id=\"req-001-1\"></a>REQ_001 |"));
+    assert!(md.contains("<a id=\"metrics-1\"></a>\n\n### Metrics"));
+    assert!(md.contains("| [LC-001](#lc-001) | [REQ_001](#req-001-1) | satisfies | - |"));
+}
+
+#[test]
+fn test_adjacency_graph_edges_reference_declared_nodes() {
+    use arclang::compiler::graph_model::AdjacencyGraph;
+
+    let input = r#"
+model Demo {
+}
+
+system_analysis "SA" {
+    requirement "REQ-001" { description: "The vehicle shall stop" }
+}
+
+logical_architecture "LA" {
+    component "Sensor" { id: "LC-001" function "sense" }
+    component "Controller" { id: "LC-002" }
+    component_exchange "data" { from_port: "LC-001" to_port: "LC-002" }
+    component_exchange "bus" { from_port: "LC-002" to_port: "Gateway.out" }
+}
+
+physical_architecture "PA" {
+    node "NodeA" { id: "PN-A" deploys "LC-001" }
+}
+
+trace "LC-001" satisfies "REQ-001" {}
+"#;
+    let result = Compiler::new(CompilerConfig::default()).compile_string(input).expect("must compile");
+    let graph = AdjacencyGraph::from_model(&result.semantic_model).unwrap();
+
+    let requirement = graph.nodes.iter().find(|n| n.id == "REQ-001").expect("requirement node");
+    assert_eq!((requirement.element_type.as_str(), requirement.layer.as_str()), ("Requirement", "Requirements"));
+    let edges: Vec<_> = graph.edges.iter().map(|e| (e.source.as_str(), e.target.as_str(), e.kind.as_str())).collect();
+    assert_eq!(
+        edges,
+        [
+            ("LC-001", "LC-002", "interface"),
+            ("LC-001", "REQ-001", "satisfies"),
+            ("LC-001", "sense", "allocation"),
+            ("LC-001", "PN-A", "allocation"),
+        ],
+        "the exchange to the undeclared Gateway is left out"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+    assert_eq!(json["nodes"][0], serde_json::json!({"id": "LC-001", "type": "Component", "layer": "Logical", "label": "Sensor"}));
+    let cytoscape: serde_json::Value = serde_json::from_str(&graph.to_cytoscape_json().unwrap()).unwrap();
+    assert_eq!(cytoscape["elements"]["nodes"][0]["data"]["id"], "LC-001");
+    assert_eq!(cytoscape["elements"]["edges"][1]["data"]["id"], "e1");
+    assert_eq!(cytoscape["elements"]["edges"][1]["data"]["kind"], "satisfies");
+}
+
+#[test]
+fn test_capella_import_regenerates_all_four_layers() {
+    use arclang::compiler::capella_importer::{ArcCodeGenerator, CapellaImporter};
+
+    let model = CapellaImporter::new()
+        .import_file("tests/fixtures/capella/layers.capella")
+        .expect("fixture imports");
+    let source = ArcCodeGenerator::new().generate(&model).unwrap();
+
+    for expected in [
+        "actor \"Driver\"",
+        "entity \"Vehicle\"",
+        "activity \"Slow down the vehicle\"",
+        "operational_activity \"Press brake pedal\"",
+        "communication_means \"Pedal\"",
+        "function \"Acquire pedal position\"",
+        "functional_exchange \"Pedal position\"",
+        "component \"Pedal Sensor\"",
+        "port out \"position\"",
+        "component_exchange \"Pedal position\"",
+        "node \"Brake ECU\"",
+        "deploys \"0d000000-0000-4000-8000-000000000021\"",
+        "link \"Chassis CAN\"",
+    ] {
+        assert!(source.contains(expected), "missing {expected}:\n{source}");
+    }
+    // The Capella root functions are containers, not functions
+    assert!(!source.contains("Root"), "{source}");
+
+    let mut compiler = Compiler::new(CompilerConfig::default());
+    let output = compiler.compile_string(&source).expect("regenerated source compiles");
+    let model = output.semantic_model;
+    for level in ["Operational", "System", "Logical", "Physical"] {
+        assert!(model.components.iter().any(|c| c.level == level), "no {level} component");
+    }
+    assert_eq!(model.connections.len(), 1);
+    assert_eq!(model.connections[0].from_port, "position");
+    assert_eq!(model.deployments.len(), 1);
+}
+
+#[test]
+fn test_capella_import_reads_the_model_behind_an_aird() {
+    use arclang::compiler::capella_importer::{ArcCodeGenerator, CapellaImporter};
+
+    let model = CapellaImporter::new()
+        .import_file("tests/fixtures/capella/test7_0/Model Test 7.0.aird")
+        .expect("the .capella resource imports");
+    assert!(!model.operational_analysis.is_empty());
+    assert!(!model.physical_architecture[0].links.is_empty());
+    let source = ArcCodeGenerator::new().generate(&model).unwrap();
+    let mut compiler = Compiler::new(CompilerConfig::default());
+    assert!(compiler.compile_string(&source).is_ok());
+}
+
+#[test]
+fn test_capella_export_round_trips_through_the_importer() {
+    use arclang::compiler::capella_exporter::export_capella;
+    use arclang::compiler::capella_importer::{ArcCodeGenerator, CapellaImporter};
+    use arclang::compiler::semantic::SemanticModel;
+
+    let input = r#"
+operational_analysis "Ops" {
+    actor "Driver" { id: "OA-001" }
+    entity "Vehicle" {
+        id: "OE-001"
+        activity "Slow down" { id: "OACT-001" }
+    }
+    operational_activity "Watch traffic" { id: "OACT-002" }
+}
+
+system_analysis "SA" {
+    system_component "Brake System" { id: "SC-001" }
+    function "Acquire pedal" { id: "SF-001" }
+    function "Apply torque" { id: "SF-002" }
+    functional_exchange "Pedal demand" {
+        from: "SF-001"
+        to: "SF-002"
+    }
+}
+
+logical_architecture "LA" {
+    component "Pedal Sensor" {
+        id: "LC-001"
+        port out "position"
+        function "Read pedal" { id: "LF-001" }
+    }
+    component "Brake Controller" {
+        id: "LC-002"
+        port in "position"
+        function "Compute torque" { id: "LF-002" }
+    }
+    connect "LC-001.position" to "LC-002.position"
+}
+
+physical_architecture "PA" {
+    node "Brake ECU" {
+        id: "PN-001"
+        port "CAN"
+        deploys "LC-002"
+    }
+}
+"#;
+    let mut compiler = Compiler::new(CompilerConfig::default());
+    let original = compiler.compile_string(input).expect("model compiles").semantic_model;
+    let capella = export_capella(&original);
+    assert_eq!(export_capella(&original), capella, "export is deterministic");
+    // Capella ids are the stable ArcLang UUIDs; ArcLang ids are kept as sids
+    let uuid = &original.all_elements["LC-001"].uuid;
+    assert!(capella.contains(&format!("id=\"{uuid}\" name=\"Pedal Sensor\" sid=\"LC-001\"")), "{capella}");
+
+    let imported = CapellaImporter::new().import_string(&capella).expect("export is Capella XMI");
+    let source = ArcCodeGenerator::new().generate(&imported).unwrap();
+    let mut compiler = Compiler::new(CompilerConfig::default());
+    let round_tripped = compiler.compile_string(&source).expect("imported model compiles").semantic_model;
+
+    let count = |model: &SemanticModel, element_type: &str| {
+        model.all_elements.values().filter(|e| e.element_type == element_type).count()
+    };
+    for element_type in ["Actor", "Entity", "Activity", "OperationalActivity", "SystemComponent", "SystemFunction", "Function", "ComponentPort", "PhysicalPort"] {
+        assert_eq!(count(&round_tripped, element_type), count(&original, element_type), "{element_type}:\n{source}");
+    }
+    for level in ["Operational", "System", "Logical", "Physical"] {
+        let at = |model: &SemanticModel| model.components.iter().filter(|c| c.level == level).count();
+        assert_eq!(at(&round_tripped), at(&original), "{level}:\n{source}");
+    }
+    assert_eq!(round_tripped.interfaces.len(), original.interfaces.len(), "{source}");
+    assert_eq!(round_tripped.connections.len(), 1, "{source}");
+    assert_eq!(round_tripped.deployments.len(), 1, "{source}");
+    assert_eq!(round_tripped.deployments[0].component, imported.logical_architecture[0].components[1].id);
+}
+
+#[test]
+fn test_each_optimization_level_adds_its_checks() {
+    let input = r#"
+model Test {
+}
+
+system_analysis SA {
+    requirement "REQ-001" { description: "Brake on demand" }
+    function Compute { outputs: ["x"] }
+    functional_exchange Flow1 {
+        from: Compute
+        to: DoesNotExist
+    }
+}
+
+logical_architecture LA {
+    component "Controller" { id: "LC-001" }
+    component "Spare" { id: "LC-002" }
+}
+
+trace "LC-001" satisfies "REQ-001" {}
+"#;
+    let warnings = |optimization_level| {
+        Compiler::new(CompilerConfig { optimization_level, ..CompilerConfig::default() })
+            .compile_string(input)
+            .expect("compiles at every level")
+            .warnings
+    };
+    let has = |warnings: &[String], needle: &str| warnings.iter().any(|w| w.contains(needle));
+
+    let fast = warnings(0);
+    assert!(!has(&fast, "DoesNotExist"), "level 0 skips endpoint checks: {fast:?}");
+
+    let full = warnings(1);
+    assert!(has(&full, "DoesNotExist"), "{full:?}");
+    assert!(!has(&full, "unreferenced"), "{full:?}");
+    assert_eq!(CompilerConfig::default().optimization_level, 1);
+
+    let dead = warnings(2);
+    assert!(has(&dead, "unreferenced component 'LC-002'"), "{dead:?}");
+    assert!(!has(&dead, "'LC-001'") && !has(&dead, "'REQ-001'"), "{dead:?}");
+    assert!(!has(&dead, "cross-layer: "), "{dead:?}");
+
+    let release = warnings(3);
+    assert!(
+        has(&release, "cross-layer: system analysis without an operational analysis"),
+        "{release:?}"
+    );
+    assert_eq!(release.len(), dead.len() + release.iter().filter(|w| w.starts_with("cross-layer: ")).count());
+}
@@ -214,3 +214,99 @@ architecture logical {
     assert_eq!(exchange.from_port, "A1", "from field should be A1");
     assert_eq!(exchange.to_port, "B1", "to field should be B1");
 }
+
+#[test]
+fn test_parse_nested_requirements() {
+    let input = r#"
+model Test {
+}
+
+requirements {
+    req "REQ-1" "Braking" {
+        priority: High
+        req "REQ-1.1" {
+            description: "Child"
+            req "REQ-1.1.1" { description: "Grandchild" }
+        }
+    }
+}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let model = Parser::new(tokens).parse().expect("nested reqs parse");
+
+    let reqs = &model.system_analysis[0].requirements;
+    let ids: Vec<&str> = reqs.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["REQ-1", "REQ-1.1", "REQ-1.1.1"]);
+    let parent = |i: usize| reqs[i].attributes.get("parent").and_then(|v| v.as_string());
+    assert_eq!(parent(0), None);
+    assert_eq!(parent(1), Some("REQ-1"));
+    assert_eq!(parent(2), Some("REQ-1.1"));
+}
+
+#[test]
+fn test_parse_requirement_verification_fields() {
+    let input = r#"
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "Brake"
+        verification: FormalProof
+        verification_status: InProgress
+    }
+}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let model = Parser::new(tokens).parse().unwrap();
+
+    let req = &model.system_analysis[0].requirements[0];
+    let value = |key: &str| req.attributes.get(key).and_then(|v| v.as_string());
+    assert_eq!(value("verification"), Some("FormalProof"));
+    assert_eq!(value("verification_status"), Some("InProgress"));
+}
+
+#[test]
+fn test_parse_trace_link_keywords() {
+    for keyword in [
+        "satisfies",
+        "derives_from",
+        "refines",
+        "verifies",
+        "allocated_to",
+        "implements",
+        "depends_on",
+    ] {
+        let input = format!("trace \"A-001\" {keyword} \"B-001\" {{ rationale: \"r\" }}\n");
+        let tokens = Lexer::new(&input).tokenize().unwrap();
+        let model = Parser::new(tokens)
+            .parse()
+            .unwrap_or_else(|e| panic!("Failed to parse '{keyword}' trace: {e}"));
+
+        assert_eq!(model.traces.len(), 1, "'{keyword}' should yield one trace");
+        assert_eq!(model.traces[0].trace_type, keyword);
+        assert_eq!(model.traces[0].from, "A-001");
+        assert_eq!(model.traces[0].to, "B-001");
+    }
+}
+
+#[test]
+fn test_parse_unknown_trace_keyword_is_an_error() {
+    let tokens = Lexer::new("trace \"A-001\" blesses \"B-001\" {}\n").tokenize().unwrap();
+    let err = Parser::new(tokens).parse().unwrap_err();
+
+    assert!(err.to_string().contains("Unknown trace type 'blesses'"), "{err}");
+}
+
+#[test]
+fn test_parse_records_declaration_spans() {
+    let input = "requirements system {\n  req \"REQ-001\" \"R\" { description: \"r\" }\n}\n\narchitecture logical {\n  component \"Brake\" { id: \"LC-001\" }\n}\n\ntrace \"REQ-001\" allocated_to \"LC-001\" {}\n";
+    let (tokens, spans) = Lexer::new(input).tokenize_spanned().unwrap();
+    let model = Parser::with_spans(tokens, spans).parse().unwrap();
+
+    let requirement = &model.declarations["REQ-001"];
+    assert_eq!((requirement.line, requirement.column), (2, 7));
+    let component = &model.declarations["Brake"];
+    assert_eq!((component.line, component.column), (6, 13));
+    let id = &model.declarations["LC-001"];
+    assert_eq!((id.line, id.column), (6, 27));
+    // References are not declarations.
+    assert_eq!(model.declarations.len(), 3);
+}
@@ -1,6 +1,6 @@
 use arclang::compiler::lexer::Lexer;
 use arclang::compiler::parser::Parser;
-use arclang::compiler::semantic::SemanticAnalyzer;
+use arclang::compiler::semantic::{GapType, SemanticAnalyzer, VerificationMethod, VerificationStatus};
 
 #[test]
 fn test_semantic_analysis_basic() {
@@ -174,3 +174,506 @@ architecture logical {
     assert_eq!(metrics.components_count, 3);
     assert_eq!(metrics.total_elements, 5);
 }
+
+#[test]
+fn test_trace_link_types_and_endpoint_validation() {
+    let input = r#"
+model Test {
+}
+
+requirements system {
+    req "REQ-001" "Brake" { description: "Brake" }
+    req "REQ-002" "Brake hard" { description: "Brake hard" }
+}
+
+architecture logical {
+    component "Controller" { id: "COMP-001" }
+    component "Tester" { id: "COMP-002" }
+}
+
+trace "COMP-001" satisfies "REQ-001" {}
+trace "REQ-002" derives_from "REQ-001" {}
+trace "COMP-002" verifies "COMP-001" {}
+trace "REQ-001" allocated_to "COMP-001" {}
+trace "COMP-002" depends_on "COMP-001" {}
+"#;
+    use arclang::compiler::semantic::TraceLinkType;
+
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    let link_types: Vec<_> = model.traces.iter().map(|t| t.link_type.clone()).collect();
+    assert_eq!(
+        link_types,
+        vec![
+            TraceLinkType::Satisfies,
+            TraceLinkType::DerivedFrom,
+            TraceLinkType::VerifiedBy,
+            TraceLinkType::AllocatedTo,
+            TraceLinkType::DependsOn,
+        ]
+    );
+
+    let issues = model.validate_traceability();
+    let endpoint_issues: Vec<_> = issues.iter().filter(|i| i.starts_with("Trace ")).collect();
+    assert_eq!(endpoint_issues.len(), 1, "{issues:?}");
+    assert!(endpoint_issues[0].contains("COMP-002 verifies COMP-001: target must be a requirement"));
+}
+
+#[test]
+fn test_trace_cycles_derivation_is_error_dependency_is_warning() {
+    let input = r#"
+model Test {
+}
+
+requirements system {
+    req "REQ-A" "A" { description: "A" }
+    req "REQ-B" "B" { description: "B" }
+    req "REQ-C" "C" { description: "C" }
+}
+
+architecture logical {
+    component "X" { id: "COMP-X" }
+    component "Y" { id: "COMP-Y" }
+}
+
+trace "REQ-A" derives_from "REQ-B" {}
+trace "REQ-B" refines "REQ-C" {}
+trace "REQ-C" derives_from "REQ-A" {}
+trace "COMP-X" depends_on "COMP-Y" {}
+trace "COMP-Y" depends_on "COMP-X" {}
+trace "COMP-X" satisfies "REQ-A" {}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    assert_eq!(
+        model.traceability_errors(),
+        vec!["Circular derivation: REQ-A derives_from REQ-B refines REQ-C derives_from REQ-A"]
+    );
+    let warnings = model.validate_traceability();
+    assert!(
+        warnings.contains(&"Trace cycle: COMP-X depends_on COMP-Y depends_on COMP-X".to_string()),
+        "{warnings:?}"
+    );
+    assert_eq!(model.trace_cycles().len(), 2);
+}
+
+#[test]
+fn test_traceability_issues_carry_stable_codes() {
+    use arclang::compiler::diagnostic::Severity;
+
+    let input = r#"
+model Test {
+}
+
+requirements system {
+    req "REQ-A" "A" { description: "A" }
+    req "REQ-B" "B" { description: "B" }
+}
+
+architecture logical {
+    component "X" { id: "COMP-X" }
+}
+
+trace "REQ-A" derives_from "REQ-B" {}
+trace "REQ-B" derives_from "REQ-A" {}
+trace "COMP-X" verifies "COMP-X" {}
+"#;
+    let tokens = Lexer::new(input).tokenize_spanned().unwrap();
+    let ast = Parser::with_spans(tokens.0, tokens.1).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    let issues: Vec<_> = model
+        .traceability_issues()
+        .into_iter()
+        .map(|i| (i.severity, i.code, i.diagnostic.span.map(|s| (s.line, s.column))))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            (Severity::Warning, "E-TRACE-ENDPOINT", Some((16, 25))),
+            (Severity::Error, "E-TRACE-CYCLE", None),
+        ]
+    );
+}
+
+#[test]
+fn test_semantic_dependencies_from_traces_and_interfaces() {
+    let input = r#"
+model Test {
+}
+
+requirements system {
+    req "REQ-001" "Brake" {
+        description: "Brake"
+    }
+}
+
+architecture logical {
+    component "A" { id: "A1" }
+    component "B" { id: "B1" }
+
+    connection "AB" {
+        from: "A"
+        to: "B1"
+    }
+}
+
+trace "A1" satisfies "REQ-001" {}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    let edges: Vec<(String, String, String)> = model
+        .dependencies()
+        .into_iter()
+        .map(|d| (d.from, d.to, d.relation))
+        .collect();
+    assert_eq!(
+        edges,
+        vec![
+            ("A1".to_string(), "B1".to_string(), "exchange 'A -> B1'".to_string()),
+            ("A1".to_string(), "REQ-001".to_string(), "satisfies".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_semantic_physical_deployments_and_undeployed_warning() {
+    let input = r#"
+model Test {
+}
+
+architecture logical {
+    component "Controller" { id: "LC-001" }
+    component "Monitor" { id: "LC-002" }
+    component "Logger" { id: "LC-003" }
+}
+
+physical_architecture "PA" {
+    node "Main ECU" {
+        id: "PN-001"
+        deploys "LC-001"
+    }
+    node "Safety ECU" {
+        id: "PN-002"
+    }
+}
+
+trace "LC-002" allocated_to "PN-002" {}
+"#;
+    let (tokens, spans) = Lexer::new(input).tokenize_spanned().unwrap();
+    let ast = Parser::with_spans(tokens, spans).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    let deployments: Vec<(&str, &str)> = model
+        .deployments
+        .iter()
+        .map(|d| (d.component.as_str(), d.node.as_str()))
+        .collect();
+    assert_eq!(deployments, vec![("LC-001", "PN-001"), ("LC-002", "PN-002")]);
+
+    let undeployed: Vec<_> = model
+        .traceability_issues()
+        .into_iter()
+        .filter(|issue| issue.code == "E-NOT-DEPLOYED")
+        .collect();
+    assert_eq!(undeployed.len(), 1);
+    assert!(undeployed[0].diagnostic.message.contains("LC-003"));
+    let span = undeployed[0].diagnostic.span.as_ref().expect("located at the component");
+    assert_eq!(span.line, 8);
+}
+
+#[test]
+fn test_semantic_port_connections_and_interface_checks() {
+    let input = r#"
+model Test {
+}
+
+architecture logical {
+    component "Sensor" {
+        id: "SENSOR"
+        interface "speed" { direction: out provides: "ISpeed" }
+        interface "status" { direction: out provides: "IStatus" }
+    }
+    component "Controller" {
+        id: "CTRL"
+        interface "speed" { direction: in requires: "ISpeed" }
+        interface "config" { direction: in requires: "IConfig" }
+        port out command { interface: "ICommand" }
+    }
+    component "Actuator" {
+        id: "ACT"
+        interface "command" { direction: out provides: "ICommand" }
+    }
+
+    connect SENSOR.speed to CTRL.speed { label: "vehicle speed" }
+    connect "CTRL.command" to "ACT.command"
+}
+"#;
+    let (tokens, spans) = Lexer::new(input).tokenize_spanned().unwrap();
+    let ast = Parser::with_spans(tokens, spans).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    assert_eq!(model.ports.len(), 6);
+    let speed = model.get_port("CTRL", "speed").unwrap();
+    assert_eq!((speed.interface.as_str(), speed.required), ("ISpeed", true));
+    assert!(!model.get_port("SENSOR", "speed").unwrap().required);
+
+    assert_eq!(model.connections.len(), 2);
+    assert_eq!(model.connections[0].from_component, "SENSOR");
+    assert_eq!(model.connections[0].to_port, "speed");
+    assert_eq!(model.connections[0].label.as_deref(), Some("vehicle speed"));
+
+    let issues = model.traceability_issues();
+    let messages = |code: &str| -> Vec<String> {
+        issues
+            .iter()
+            .filter(|issue| issue.code == code)
+            .map(|issue| issue.diagnostic.message.clone())
+            .collect()
+    };
+    assert_eq!(messages("E-PORT-UNCONNECTED"), vec!["Required interface CTRL.config (IConfig) is not connected"]);
+    assert_eq!(
+        messages("E-PORT-DIRECTION"),
+        vec!["Connection CTRL.command -> ACT.command: its target is an out port"]
+    );
+}
+
+#[test]
+fn test_semantic_requirement_verification_declarations() {
+    let input = r#"
+model Test {
+}
+
+system_analysis "SA" {
+    requirement "REQ-001" {
+        description: "The controller shall limit torque"
+        safety_level: "ASIL-D"
+        verification: FormalProof
+        verification_status: Verified
+    }
+    requirement "REQ-002" {
+        description: "The display shall show the speed"
+        verification: Test
+        verification_status: "in_progress"
+    }
+}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    let proven = model.requirements.iter().find(|r| r.id == "REQ-001").unwrap();
+    assert_eq!(proven.verification_method, Some(VerificationMethod::FormalProof));
+    assert_eq!(proven.verification_status, Some(VerificationStatus::Verified));
+    let tested = model.requirements.iter().find(|r| r.id == "REQ-002").unwrap();
+    assert_eq!(tested.verification_method, Some(VerificationMethod::Test));
+    assert_eq!(tested.verification_status, Some(VerificationStatus::InProgress));
+
+    let unverified: Vec<String> = model
+        .coverage_gaps()
+        .iter()
+        .filter(|gap| gap.gap_type == GapType::NoVerification)
+        .map(|gap| gap.requirement_id.clone())
+        .collect();
+    assert_eq!(unverified, vec!["REQ-002"], "a declared Verified requirement has no verification gap");
+}
+
+#[test]
+fn test_semantic_rejects_invalid_verification_declarations() {
+    let analyze = |body: &str| {
+        let input = format!("model Test {{\n}}\n\nsystem_analysis \"SA\" {{\n    requirement \"REQ-001\" {{\n{body}\n    }}\n}}\n");
+        let tokens = Lexer::new(&input).tokenize().unwrap();
+        let ast = Parser::new(tokens).parse().unwrap();
+        SemanticAnalyzer::new().analyze(&ast).err().map(|e| e.to_string())
+    };
+
+    let unknown = analyze("description: \"x\"\nverification: Guesswork").unwrap();
+    assert!(unknown.contains("unknown verification method 'Guesswork'"), "{unknown}");
+    let proof = analyze("description: \"x\"\nverification: FormalProof").unwrap();
+    assert!(proof.contains("only allowed on safety requirements"), "{proof}");
+    let status = analyze("description: \"x\"\nverification_status: Done").unwrap();
+    assert!(status.contains("unknown verification status 'Done'"), "{status}");
+    assert!(analyze("description: \"x\"\nverification: FormalProof\nsafety_level: \"DAL-A\"").is_none());
+}
+
+#[test]
+fn test_refines_builds_requirement_hierarchy_with_rolled_up_status() {
+    let input = r#"
+model Test {
+}
+
+system_analysis "SA" {
+    requirement "REQ-001" { description: "The vehicle shall stop" }
+    requirement "REQ-010" {
+        description: "The controller shall brake"
+        verification_status: Verified
+    }
+    requirement "REQ-011" {
+        description: "The controller shall warn"
+        verification_status: Verified
+    }
+    requirement "REQ-002" { description: "The vehicle shall steer" }
+    requirement "REQ-020" {
+        description: "The controller shall steer"
+        verification_status: Verified
+    }
+    requirement "REQ-021" {
+        description: "The controller shall self-test"
+        verification_status: Failed
+    }
+}
+
+trace "REQ-010" refines "REQ-001" {}
+trace "REQ-011" refines "REQ-001" {}
+trace "REQ-020" refines "REQ-002" {}
+trace "REQ-021" refines "REQ-002" {}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    let parent = model.requirements.iter().find(|r| r.id == "REQ-001").unwrap();
+    assert_eq!(parent.children_ids, vec!["REQ-010", "REQ-011"]);
+    let child = model.requirements.iter().find(|r| r.id == "REQ-010").unwrap();
+    assert_eq!(child.parent_id.as_deref(), Some("REQ-001"));
+
+    let rollup = model.rollup_status();
+    assert_eq!(rollup["REQ-001"], VerificationStatus::Verified);
+    assert_eq!(rollup["REQ-002"], VerificationStatus::Failed);
+    assert_eq!(rollup["REQ-021"], VerificationStatus::Failed);
+
+    let unverified: Vec<String> = model
+        .coverage_gaps()
+        .iter()
+        .filter(|gap| gap.gap_type == GapType::NoVerification)
+        .map(|gap| gap.requirement_id.clone())
+        .collect();
+    assert_eq!(unverified, vec!["REQ-002", "REQ-021"], "REQ-001 is satisfied through its children");
+}
+
+#[test]
+fn test_semantic_rejects_requirement_that_is_its_own_ancestor() {
+    let input = r#"
+model Test {
+}
+
+system_analysis "SA" {
+    requirement "REQ-A" { description: "A" }
+    requirement "REQ-B" { description: "B" }
+    requirement "REQ-C" { description: "C" }
+}
+
+trace "REQ-A" refines "REQ-B" {}
+trace "REQ-B" refines "REQ-C" {}
+trace "REQ-C" refines "REQ-A" {}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let err = SemanticAnalyzer::new().analyze(&ast).unwrap_err().to_string();
+    assert!(
+        err.contains("requirement 'REQ-A' is its own ancestor: REQ-A refines REQ-B refines REQ-C refines REQ-A"),
+        "{err}"
+    );
+    assert_eq!(err.matches("own ancestor").count(), 1, "one error per cycle: {err}");
+}
+
+#[test]
+fn test_trace_filtering_by_element_kind() {
+    use arclang::compiler::semantic::parse_element_kind;
+    use arclang::integrations::requirements_management::TraceabilityMatrix;
+
+    let input = r#"
+model Test {
+}
+
+system_analysis "SA" {
+    requirement "REQ-001" { description: "Brake" }
+    requirement "REQ-002" { description: "Log" }
+    requirement "REQ-003" { description: "Stop" }
+}
+
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+}
+
+trace "LC-001" satisfies "REQ-001" { rationale: "brakes" }
+trace "REQ-003" derives_from "REQ-001" {}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    assert_eq!(parse_element_kind("Requirements"), Some("requirement"));
+    assert_eq!(parse_element_kind("test-case"), Some("test_case"));
+    assert_eq!(parse_element_kind("widget"), None);
+
+    // Written component -> requirement, matched either way round.
+    let traces = model.traces_between(Some("requirement"), Some("component"));
+    assert_eq!(traces.len(), 1);
+    assert_eq!(traces[0].from, "LC-001");
+
+    let matrix = TraceabilityMatrix::between(&model, Some("requirement"), Some("component"));
+    let rows: Vec<&str> = matrix.rows.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(rows, vec!["REQ-001", "REQ-002", "REQ-003"]);
+    assert_eq!(matrix.cells.len(), 1);
+    assert_eq!(matrix.cell("REQ-001", "LC-001").unwrap().rationale.as_deref(), Some("brakes"));
+    assert!((matrix.coverage_percentage - 100.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_unreferenced_elements_skip_stakeholder_roots_and_the_system() {
+    use arclang::compiler::semantic_analyzer::ElementType;
+    use arclang::compiler::{Compiler, CompilerConfig};
+
+    let input = r#"
+model Test {
+}
+
+system_analysis "SA" {
+    requirement "STK-001" { description: "Drivers can stop" category: "Stakeholder" }
+    requirement "REQ-001" { description: "Brake" }
+    requirement "REQ-002" { description: "Log" }
+    system_component "Vehicle" { id: "SYS-001" }
+}
+
+logical_architecture "LA" {
+    component "Controller" { id: "LC-001" }
+    component "Sensor" { id: "LC-002" }
+    component "Spare" { id: "LC-003" }
+    component "Legacy" { id: "LC-004" }
+    component_exchange "Reading" { from: "Sensor" to: "Controller" }
+}
+
+trace "LC-001" satisfies "REQ-001" {}
+"#;
+    let tokens = Lexer::new(input).tokenize().unwrap();
+    let ast = Parser::new(tokens).parse().unwrap();
+    let model = SemanticAnalyzer::new().analyze(&ast).unwrap();
+
+    assert_eq!(
+        model.unreferenced_elements(),
+        vec![
+            ("REQ-002".to_string(), ElementType::Requirement),
+            ("LC-003".to_string(), ElementType::Component),
+            ("LC-004".to_string(), ElementType::Component),
+        ]
+    );
+
+    let warnings = Compiler::new(CompilerConfig {
+        optimization_level: 2,
+        standalone: vec!["LC-004".to_string()],
+        ..CompilerConfig::default()
+    })
+    .compile_string(input)
+    .unwrap()
+    .warnings;
+    let dead: Vec<&String> = warnings.iter().filter(|w| w.starts_with("unreferenced ")).collect();
+    assert_eq!(dead.len(), 2, "{warnings:?}");
+    assert!(dead[1].contains("'LC-003'"), "{dead:?}");
+}
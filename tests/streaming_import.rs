@@ -0,0 +1,147 @@
+//! Streaming imports of large requirement exports under a memory ceiling.
+//!
+//! The whole binary counts its heap through a global allocator, so this file
+//! holds a single test: nothing else may allocate while it measures.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{BufReader, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use arclang::compiler::doors_importer::import_doors_stream;
+use arclang::compiler::requirements_csv::import_requirements_csv_stream;
+
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            let current = CURRENT.fetch_add(new_size, Ordering::Relaxed) + new_size;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// The heap `f` needs beyond what was allocated before it ran.
+fn peak_heap_of(f: impl FnOnce()) -> usize {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    f();
+    PEAK.load(Ordering::Relaxed) - baseline
+}
+
+/// An export generated row by row as it is read, so the test itself never
+/// holds the file.
+struct SyntheticExport {
+    row: fn(usize) -> String,
+    rows: usize,
+    next: usize,
+    pending: Vec<u8>,
+    offset: usize,
+    produced: usize,
+}
+
+impl SyntheticExport {
+    fn new(header: &'static str, rows: usize, row: fn(usize) -> String) -> Self {
+        Self { row, rows, next: 0, pending: header.as_bytes().to_vec(), offset: 0, produced: 0 }
+    }
+}
+
+impl Read for SyntheticExport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.offset == self.pending.len() {
+            if self.next == self.rows {
+                return Ok(0);
+            }
+            self.pending = (self.row)(self.next).into_bytes();
+            self.offset = 0;
+            self.next += 1;
+        }
+        let n = buf.len().min(self.pending.len() - self.offset);
+        buf[..n].copy_from_slice(&self.pending[self.offset..self.offset + n]);
+        self.offset += n;
+        self.produced += n;
+        Ok(n)
+    }
+}
+
+/// Counts the `req` blocks it is given and keeps the last bytes.
+#[derive(Default)]
+struct Sink {
+    blocks: usize,
+    tail: Vec<u8>,
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.blocks += buf.windows(5).filter(|w| w == b"req \"").count();
+        self.tail = buf[buf.len().saturating_sub(16)..].to_vec();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+const ROWS: usize = 100_000;
+const CEILING: usize = 1024 * 1024;
+
+#[test]
+fn large_exports_import_in_bounded_memory() {
+    // DOORS: a heading per section and a multi-line text object under it
+    let mut export = SyntheticExport::new("ID,Object Number,Object Heading,Object Text,Priority\n", ROWS, |i| {
+        let section = i / 2 + 1;
+        if i % 2 == 0 {
+            format!("REQ-{i},{section},Section {section},,High\n")
+        } else {
+            format!(
+                "REQ-{i},{section}-1,,\"The system shall, within {i} ms,\nreport \"\"status\"\" of section {section} \
+                 to the operator console and log it.\",Medium\n"
+            )
+        }
+    });
+    let mut sink = Sink::default();
+    let peak = peak_heap_of(|| {
+        import_doors_stream(BufReader::new(&mut export), &mut sink).expect("imports");
+    });
+    assert!(export.produced > 8 * CEILING, "the export is only {} bytes", export.produced);
+    assert_eq!(sink.blocks, ROWS);
+    assert!(sink.tail.ends_with(b"  }\n  }\n}\n"), "{}", String::from_utf8_lossy(&sink.tail));
+    assert!(peak < CEILING, "DOORS import of {} bytes peaked at {peak} bytes", export.produced);
+
+    // CSV: one requirement per row
+    let mut export = SyntheticExport::new("id,title,text,priority,Owner\n", ROWS, |i| {
+        format!("R-{i},Requirement {i},\"The system shall handle case {i}, as specified.\nSee annex {i}.\",Low,Team {}\n", i % 7)
+    });
+    let mut sink = Sink::default();
+    let mut count = 0;
+    let peak = peak_heap_of(|| {
+        count = import_requirements_csv_stream(BufReader::new(&mut export), &[], &mut sink).expect("imports");
+    });
+    assert!(export.produced > 8 * CEILING, "the export is only {} bytes", export.produced);
+    assert_eq!((count, sink.blocks), (ROWS, ROWS));
+    assert!(peak < CEILING, "CSV import of {} bytes peaked at {peak} bytes", export.produced);
+}